@@ -125,6 +125,44 @@ impl<'a, 'b> HeaderWriter<'a, 'b> {
         self.cursor.at_pos(pos_of_count, |cur| count.write(cur))
     }
 
+    pub(crate) fn write_range_with<I, T>(
+        &mut self,
+        variation: Variation,
+        start: I,
+        stop: I,
+        items: &[T],
+        write: fn(&T, &mut WriteCursor) -> Result<(), scursor::WriteError>,
+    ) -> Result<(), scursor::WriteError>
+    where
+        I: Index,
+    {
+        self.write_range_only(variation, start, stop)?;
+        for item in items {
+            write(item, self.cursor)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn write_prefixed_with<I, T>(
+        &mut self,
+        variation: Variation,
+        count: I,
+        items: &[(T, I)],
+        write: fn(&T, &mut WriteCursor) -> Result<(), scursor::WriteError>,
+    ) -> Result<(), scursor::WriteError>
+    where
+        I: Index,
+    {
+        variation.write(self.cursor)?;
+        I::COUNT_AND_PREFIX_QUALIFIER.write(self.cursor)?;
+        count.write(self.cursor)?;
+        for (item, index) in items {
+            index.write(self.cursor)?;
+            write(item, self.cursor)?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn write_count_of_one<V>(&mut self, item: V) -> Result<(), scursor::WriteError>
     where
         V: FixedSizeVariation,
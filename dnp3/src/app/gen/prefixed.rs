@@ -27,18 +27,24 @@ use scursor::ReadCursor;
 pub(crate) enum PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Display {
     /// Device Attributes - Specific Attribute
     Group0(crate::app::attr::Attribute<'a>),
+    /// Binary Input - With Flags
+    Group1Var2(CountSequence<'a, Prefix<I, Group1Var2>>),
     /// Binary Input Event - Without Time
     Group2Var1(CountSequence<'a, Prefix<I, Group2Var1>>),
     /// Binary Input Event - With Absolute Time
     Group2Var2(CountSequence<'a, Prefix<I, Group2Var2>>),
     /// Binary Input Event - With Relative Time
     Group2Var3(CountSequence<'a, Prefix<I, Group2Var3>>),
+    /// Double-bit Binary Input - With Flags
+    Group3Var2(CountSequence<'a, Prefix<I, Group3Var2>>),
     /// Double-bit Binary Input Event - Without Time
     Group4Var1(CountSequence<'a, Prefix<I, Group4Var1>>),
     /// Double-bit Binary Input Event - With Absolute Time
     Group4Var2(CountSequence<'a, Prefix<I, Group4Var2>>),
     /// Double-bit Binary Input Event - With Relative Time
     Group4Var3(CountSequence<'a, Prefix<I, Group4Var3>>),
+    /// Binary Output - Output Status With Flags
+    Group10Var2(CountSequence<'a, Prefix<I, Group10Var2>>),
     /// Binary Output Event - Output Status Without Time
     Group11Var1(CountSequence<'a, Prefix<I, Group11Var1>>),
     /// Binary Output Event - Output Status With Time
@@ -49,6 +55,26 @@ pub(crate) enum PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt:
     Group13Var1(CountSequence<'a, Prefix<I, Group13Var1>>),
     /// Binary Output Command Event - With Time
     Group13Var2(CountSequence<'a, Prefix<I, Group13Var2>>),
+    /// Counter - 32-bit With Flag
+    Group20Var1(CountSequence<'a, Prefix<I, Group20Var1>>),
+    /// Counter - 16-bit With Flag
+    Group20Var2(CountSequence<'a, Prefix<I, Group20Var2>>),
+    /// Counter - 32-bit Without Flag
+    Group20Var5(CountSequence<'a, Prefix<I, Group20Var5>>),
+    /// Counter - 16-bit Without Flag
+    Group20Var6(CountSequence<'a, Prefix<I, Group20Var6>>),
+    /// Frozen Counter - 16-bit Without Flag
+    Group21Var10(CountSequence<'a, Prefix<I, Group21Var10>>),
+    /// Frozen Counter - 32-bit Without Flag
+    Group21Var9(CountSequence<'a, Prefix<I, Group21Var9>>),
+    /// Frozen Counter - 16-bit With Flag and Time
+    Group21Var6(CountSequence<'a, Prefix<I, Group21Var6>>),
+    /// Frozen Counter - 32-bit With Flag and Time
+    Group21Var5(CountSequence<'a, Prefix<I, Group21Var5>>),
+    /// Frozen Counter - 16-bit With Flag
+    Group21Var2(CountSequence<'a, Prefix<I, Group21Var2>>),
+    /// Frozen Counter - 32-bit With Flag
+    Group21Var1(CountSequence<'a, Prefix<I, Group21Var1>>),
     /// Counter Event - 32-bit With Flag
     Group22Var1(CountSequence<'a, Prefix<I, Group22Var1>>),
     /// Counter Event - 16-bit With Flag
@@ -65,6 +91,18 @@ pub(crate) enum PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt:
     Group23Var5(CountSequence<'a, Prefix<I, Group23Var5>>),
     /// Frozen Counter Event - 16-bit With Flag and Time
     Group23Var6(CountSequence<'a, Prefix<I, Group23Var6>>),
+    /// Analog Input - Double-precision With Flag
+    Group30Var6(CountSequence<'a, Prefix<I, Group30Var6>>),
+    /// Analog Input - Single-precision With Flag
+    Group30Var5(CountSequence<'a, Prefix<I, Group30Var5>>),
+    /// Analog Input - 16-bit Without Flag
+    Group30Var4(CountSequence<'a, Prefix<I, Group30Var4>>),
+    /// Analog Input - 32-bit Without Flag
+    Group30Var3(CountSequence<'a, Prefix<I, Group30Var3>>),
+    /// Analog Input - 16-bit With Flag
+    Group30Var2(CountSequence<'a, Prefix<I, Group30Var2>>),
+    /// Analog Input - 32-bit With Flag
+    Group30Var1(CountSequence<'a, Prefix<I, Group30Var1>>),
     /// Analog Input Event - 32-bit With Flag
     Group32Var1(CountSequence<'a, Prefix<I, Group32Var1>>),
     /// Analog Input Event - 16-bit With Flag
@@ -103,6 +141,14 @@ pub(crate) enum PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt:
     Group34Var2(CountSequence<'a, Prefix<I, Group34Var2>>),
     /// Analog Input Reporting Deadband - Single-precision
     Group34Var3(CountSequence<'a, Prefix<I, Group34Var3>>),
+    /// Analog Output Status - 32-bit With Flag
+    Group40Var1(CountSequence<'a, Prefix<I, Group40Var1>>),
+    /// Analog Output Status - 16-bit With Flag
+    Group40Var2(CountSequence<'a, Prefix<I, Group40Var2>>),
+    /// Analog Output Status - Single-precision With Flag
+    Group40Var3(CountSequence<'a, Prefix<I, Group40Var3>>),
+    /// Analog Output Status - Double-precision With Flag
+    Group40Var4(CountSequence<'a, Prefix<I, Group40Var4>>),
     /// Analog Output - 32-bit With Flag
     Group41Var1(CountSequence<'a, Prefix<I, Group41Var1>>),
     /// Analog Output - 16-bit With Flag
@@ -143,6 +189,8 @@ pub(crate) enum PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt:
     Group43Var7(CountSequence<'a, Prefix<I, Group43Var7>>),
     /// Analog Output Command Event - Double-precision With Time
     Group43Var8(CountSequence<'a, Prefix<I, Group43Var8>>),
+    /// Octet String - Sized by variation
+    Group110VarX(u8, PrefixedBytesSequence<'a, I>),
     /// Octet String Event - Sized by variation
     Group111VarX(u8, PrefixedBytesSequence<'a, I>),
 }
@@ -151,17 +199,30 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
     pub(crate) fn parse(v: Variation, count: u16, options: ParseOptions, cursor: &mut ReadCursor<'a>) -> Result<PrefixedVariation<'a, I>, ObjectParseError> {
         match v {
             Variation::Group0(var) => Ok(PrefixedVariation::Group0(crate::app::attr::Attribute::parse_prefixed::<I>(var, count, cursor)?)),
+            Variation::Group1Var2 => Ok(PrefixedVariation::Group1Var2(CountSequence::parse(count, cursor)?)),
             Variation::Group2Var1 => Ok(PrefixedVariation::Group2Var1(CountSequence::parse(count, cursor)?)),
             Variation::Group2Var2 => Ok(PrefixedVariation::Group2Var2(CountSequence::parse(count, cursor)?)),
             Variation::Group2Var3 => Ok(PrefixedVariation::Group2Var3(CountSequence::parse(count, cursor)?)),
+            Variation::Group3Var2 => Ok(PrefixedVariation::Group3Var2(CountSequence::parse(count, cursor)?)),
             Variation::Group4Var1 => Ok(PrefixedVariation::Group4Var1(CountSequence::parse(count, cursor)?)),
             Variation::Group4Var2 => Ok(PrefixedVariation::Group4Var2(CountSequence::parse(count, cursor)?)),
             Variation::Group4Var3 => Ok(PrefixedVariation::Group4Var3(CountSequence::parse(count, cursor)?)),
+            Variation::Group10Var2 => Ok(PrefixedVariation::Group10Var2(CountSequence::parse(count, cursor)?)),
             Variation::Group11Var1 => Ok(PrefixedVariation::Group11Var1(CountSequence::parse(count, cursor)?)),
             Variation::Group11Var2 => Ok(PrefixedVariation::Group11Var2(CountSequence::parse(count, cursor)?)),
             Variation::Group12Var1 => Ok(PrefixedVariation::Group12Var1(CountSequence::parse(count, cursor)?)),
             Variation::Group13Var1 => Ok(PrefixedVariation::Group13Var1(CountSequence::parse(count, cursor)?)),
             Variation::Group13Var2 => Ok(PrefixedVariation::Group13Var2(CountSequence::parse(count, cursor)?)),
+            Variation::Group20Var1 => Ok(PrefixedVariation::Group20Var1(CountSequence::parse(count, cursor)?)),
+            Variation::Group20Var2 => Ok(PrefixedVariation::Group20Var2(CountSequence::parse(count, cursor)?)),
+            Variation::Group20Var5 => Ok(PrefixedVariation::Group20Var5(CountSequence::parse(count, cursor)?)),
+            Variation::Group20Var6 => Ok(PrefixedVariation::Group20Var6(CountSequence::parse(count, cursor)?)),
+            Variation::Group21Var10 => Ok(PrefixedVariation::Group21Var10(CountSequence::parse(count, cursor)?)),
+            Variation::Group21Var9 => Ok(PrefixedVariation::Group21Var9(CountSequence::parse(count, cursor)?)),
+            Variation::Group21Var6 => Ok(PrefixedVariation::Group21Var6(CountSequence::parse(count, cursor)?)),
+            Variation::Group21Var5 => Ok(PrefixedVariation::Group21Var5(CountSequence::parse(count, cursor)?)),
+            Variation::Group21Var2 => Ok(PrefixedVariation::Group21Var2(CountSequence::parse(count, cursor)?)),
+            Variation::Group21Var1 => Ok(PrefixedVariation::Group21Var1(CountSequence::parse(count, cursor)?)),
             Variation::Group22Var1 => Ok(PrefixedVariation::Group22Var1(CountSequence::parse(count, cursor)?)),
             Variation::Group22Var2 => Ok(PrefixedVariation::Group22Var2(CountSequence::parse(count, cursor)?)),
             Variation::Group22Var5 => Ok(PrefixedVariation::Group22Var5(CountSequence::parse(count, cursor)?)),
@@ -170,6 +231,12 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
             Variation::Group23Var2 => Ok(PrefixedVariation::Group23Var2(CountSequence::parse(count, cursor)?)),
             Variation::Group23Var5 => Ok(PrefixedVariation::Group23Var5(CountSequence::parse(count, cursor)?)),
             Variation::Group23Var6 => Ok(PrefixedVariation::Group23Var6(CountSequence::parse(count, cursor)?)),
+            Variation::Group30Var6 => Ok(PrefixedVariation::Group30Var6(CountSequence::parse(count, cursor)?)),
+            Variation::Group30Var5 => Ok(PrefixedVariation::Group30Var5(CountSequence::parse(count, cursor)?)),
+            Variation::Group30Var4 => Ok(PrefixedVariation::Group30Var4(CountSequence::parse(count, cursor)?)),
+            Variation::Group30Var3 => Ok(PrefixedVariation::Group30Var3(CountSequence::parse(count, cursor)?)),
+            Variation::Group30Var2 => Ok(PrefixedVariation::Group30Var2(CountSequence::parse(count, cursor)?)),
+            Variation::Group30Var1 => Ok(PrefixedVariation::Group30Var1(CountSequence::parse(count, cursor)?)),
             Variation::Group32Var1 => Ok(PrefixedVariation::Group32Var1(CountSequence::parse(count, cursor)?)),
             Variation::Group32Var2 => Ok(PrefixedVariation::Group32Var2(CountSequence::parse(count, cursor)?)),
             Variation::Group32Var3 => Ok(PrefixedVariation::Group32Var3(CountSequence::parse(count, cursor)?)),
@@ -189,6 +256,10 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
             Variation::Group34Var1 => Ok(PrefixedVariation::Group34Var1(CountSequence::parse(count, cursor)?)),
             Variation::Group34Var2 => Ok(PrefixedVariation::Group34Var2(CountSequence::parse(count, cursor)?)),
             Variation::Group34Var3 => Ok(PrefixedVariation::Group34Var3(CountSequence::parse(count, cursor)?)),
+            Variation::Group40Var1 => Ok(PrefixedVariation::Group40Var1(CountSequence::parse(count, cursor)?)),
+            Variation::Group40Var2 => Ok(PrefixedVariation::Group40Var2(CountSequence::parse(count, cursor)?)),
+            Variation::Group40Var3 => Ok(PrefixedVariation::Group40Var3(CountSequence::parse(count, cursor)?)),
+            Variation::Group40Var4 => Ok(PrefixedVariation::Group40Var4(CountSequence::parse(count, cursor)?)),
             Variation::Group41Var1 => Ok(PrefixedVariation::Group41Var1(CountSequence::parse(count, cursor)?)),
             Variation::Group41Var2 => Ok(PrefixedVariation::Group41Var2(CountSequence::parse(count, cursor)?)),
             Variation::Group41Var3 => Ok(PrefixedVariation::Group41Var3(CountSequence::parse(count, cursor)?)),
@@ -209,6 +280,7 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
             Variation::Group43Var6 => Ok(PrefixedVariation::Group43Var6(CountSequence::parse(count, cursor)?)),
             Variation::Group43Var7 => Ok(PrefixedVariation::Group43Var7(CountSequence::parse(count, cursor)?)),
             Variation::Group43Var8 => Ok(PrefixedVariation::Group43Var8(CountSequence::parse(count, cursor)?)),
+            Variation::Group110(x) => Ok(PrefixedVariation::Group110VarX(x, PrefixedBytesSequence::parse(options, x, count, cursor)?)),
             Variation::Group111(x) => Ok(PrefixedVariation::Group111VarX(x, PrefixedBytesSequence::parse(options, x, count, cursor)?)),
             _ => Err(ObjectParseError::InvalidQualifierForVariation(v, I::COUNT_AND_PREFIX_QUALIFIER)),
         }
@@ -217,17 +289,30 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
     pub(crate) fn format_objects(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             PrefixedVariation::Group0(attr) => attr.format(f),
+            PrefixedVariation::Group1Var2(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group2Var1(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group2Var2(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group2Var3(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group3Var2(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group4Var1(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group4Var2(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group4Var3(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group10Var2(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group11Var1(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group11Var2(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group12Var1(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group13Var1(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group13Var2(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group20Var1(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group20Var2(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group20Var5(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group20Var6(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group21Var10(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group21Var9(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group21Var6(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group21Var5(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group21Var2(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group21Var1(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group22Var1(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group22Var2(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group22Var5(seq) => format_prefixed_items(f, seq.iter()),
@@ -236,6 +321,12 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
             PrefixedVariation::Group23Var2(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group23Var5(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group23Var6(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group30Var6(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group30Var5(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group30Var4(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group30Var3(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group30Var2(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group30Var1(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group32Var1(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group32Var2(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group32Var3(seq) => format_prefixed_items(f, seq.iter()),
@@ -255,6 +346,10 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
             PrefixedVariation::Group34Var1(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group34Var2(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group34Var3(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group40Var1(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group40Var2(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group40Var3(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group40Var4(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group41Var1(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group41Var2(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group41Var3(seq) => format_prefixed_items(f, seq.iter()),
@@ -275,6 +370,7 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
             PrefixedVariation::Group43Var6(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group43Var7(seq) => format_prefixed_items(f, seq.iter()),
             PrefixedVariation::Group43Var8(seq) => format_prefixed_items(f, seq.iter()),
+            PrefixedVariation::Group110VarX(_, seq) => format_indexed_items(f, seq.iter().map(|(x, i)| (Bytes::new(x), i))),
             PrefixedVariation::Group111VarX(_, seq) => format_indexed_items(f, seq.iter().map(|(x, i)| (Bytes::new(x), i))),
         }
     }
@@ -286,6 +382,13 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
                 crate::master::handle_attribute(info.variation, info.qualifier, &Some(*attr), handler);
                 true
             }
+            PrefixedVariation::Group1Var2(seq) => {
+                handler.handle_binary_input(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
             PrefixedVariation::Group2Var1(seq) => {
                 handler.handle_binary_input(
                     self.get_header_info(),
@@ -307,6 +410,13 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
                 );
                 true
             }
+            PrefixedVariation::Group3Var2(seq) => {
+                handler.handle_double_bit_binary_input(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
             PrefixedVariation::Group4Var1(seq) => {
                 handler.handle_double_bit_binary_input(
                     self.get_header_info(),
@@ -328,6 +438,13 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
                 );
                 true
             }
+            PrefixedVariation::Group10Var2(seq) => {
+                handler.handle_binary_output_status(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
             PrefixedVariation::Group11Var1(seq) => {
                 handler.handle_binary_output_status(
                     self.get_header_info(),
@@ -359,6 +476,76 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
                 );
                 true
             }
+            PrefixedVariation::Group20Var1(seq) => {
+                handler.handle_counter(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group20Var2(seq) => {
+                handler.handle_counter(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group20Var5(seq) => {
+                handler.handle_counter(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group20Var6(seq) => {
+                handler.handle_counter(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group21Var10(seq) => {
+                handler.handle_frozen_counter(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group21Var9(seq) => {
+                handler.handle_frozen_counter(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group21Var6(seq) => {
+                handler.handle_frozen_counter(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group21Var5(seq) => {
+                handler.handle_frozen_counter(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group21Var2(seq) => {
+                handler.handle_frozen_counter(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group21Var1(seq) => {
+                handler.handle_frozen_counter(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
             PrefixedVariation::Group22Var1(seq) => {
                 handler.handle_counter(
                     self.get_header_info(),
@@ -415,6 +602,48 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
                 );
                 true
             }
+            PrefixedVariation::Group30Var6(seq) => {
+                handler.handle_analog_input(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group30Var5(seq) => {
+                handler.handle_analog_input(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group30Var4(seq) => {
+                handler.handle_analog_input(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group30Var3(seq) => {
+                handler.handle_analog_input(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group30Var2(seq) => {
+                handler.handle_analog_input(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group30Var1(seq) => {
+                handler.handle_analog_input(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
             PrefixedVariation::Group32Var1(seq) => {
                 handler.handle_analog_input(
                     self.get_header_info(),
@@ -536,6 +765,34 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
             PrefixedVariation::Group34Var3(_) => {
                 false // dead-band
             }
+            PrefixedVariation::Group40Var1(seq) => {
+                handler.handle_analog_output_status(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group40Var2(seq) => {
+                handler.handle_analog_output_status(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group40Var3(seq) => {
+                handler.handle_analog_output_status(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
+            PrefixedVariation::Group40Var4(seq) => {
+                handler.handle_analog_output_status(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16()))
+                );
+                true
+            }
             PrefixedVariation::Group41Var1(_) => {
                 false // command
             }
@@ -660,6 +917,13 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
                 );
                 true
             }
+            PrefixedVariation::Group110VarX(_, seq) => {
+                handler.handle_octet_string(
+                    self.get_header_info(),
+                    &mut seq.iter().map(|x| (x.0, x.1.widen_to_u16()))
+                );
+                true
+            }
             PrefixedVariation::Group111VarX(_, seq) => {
                 handler.handle_octet_string(
                     self.get_header_info(),
@@ -673,17 +937,30 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
     pub(crate) fn get_header_info(&self) -> HeaderInfo {
         match self {
             PrefixedVariation::Group0(attr) => HeaderInfo::new(Variation::Group0(attr.variation), I::COUNT_AND_PREFIX_QUALIFIER, false, false),
+            PrefixedVariation::Group1Var2(_) => HeaderInfo::new(Variation::Group1Var2, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
             PrefixedVariation::Group2Var1(_) => HeaderInfo::new(Variation::Group2Var1, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group2Var2(_) => HeaderInfo::new(Variation::Group2Var2, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group2Var3(_) => HeaderInfo::new(Variation::Group2Var3, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
+            PrefixedVariation::Group3Var2(_) => HeaderInfo::new(Variation::Group3Var2, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
             PrefixedVariation::Group4Var1(_) => HeaderInfo::new(Variation::Group4Var1, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group4Var2(_) => HeaderInfo::new(Variation::Group4Var2, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group4Var3(_) => HeaderInfo::new(Variation::Group4Var3, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
+            PrefixedVariation::Group10Var2(_) => HeaderInfo::new(Variation::Group10Var2, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
             PrefixedVariation::Group11Var1(_) => HeaderInfo::new(Variation::Group11Var1, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group11Var2(_) => HeaderInfo::new(Variation::Group11Var2, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group12Var1(_) => HeaderInfo::new(Variation::Group12Var1, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
             PrefixedVariation::Group13Var1(_) => HeaderInfo::new(Variation::Group13Var1, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group13Var2(_) => HeaderInfo::new(Variation::Group13Var2, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
+            PrefixedVariation::Group20Var1(_) => HeaderInfo::new(Variation::Group20Var1, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
+            PrefixedVariation::Group20Var2(_) => HeaderInfo::new(Variation::Group20Var2, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
+            PrefixedVariation::Group20Var5(_) => HeaderInfo::new(Variation::Group20Var5, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
+            PrefixedVariation::Group20Var6(_) => HeaderInfo::new(Variation::Group20Var6, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
+            PrefixedVariation::Group21Var10(_) => HeaderInfo::new(Variation::Group21Var10, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
+            PrefixedVariation::Group21Var9(_) => HeaderInfo::new(Variation::Group21Var9, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
+            PrefixedVariation::Group21Var6(_) => HeaderInfo::new(Variation::Group21Var6, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
+            PrefixedVariation::Group21Var5(_) => HeaderInfo::new(Variation::Group21Var5, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
+            PrefixedVariation::Group21Var2(_) => HeaderInfo::new(Variation::Group21Var2, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
+            PrefixedVariation::Group21Var1(_) => HeaderInfo::new(Variation::Group21Var1, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
             PrefixedVariation::Group22Var1(_) => HeaderInfo::new(Variation::Group22Var1, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group22Var2(_) => HeaderInfo::new(Variation::Group22Var2, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group22Var5(_) => HeaderInfo::new(Variation::Group22Var5, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
@@ -692,6 +969,12 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
             PrefixedVariation::Group23Var2(_) => HeaderInfo::new(Variation::Group23Var2, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group23Var5(_) => HeaderInfo::new(Variation::Group23Var5, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group23Var6(_) => HeaderInfo::new(Variation::Group23Var6, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
+            PrefixedVariation::Group30Var6(_) => HeaderInfo::new(Variation::Group30Var6, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
+            PrefixedVariation::Group30Var5(_) => HeaderInfo::new(Variation::Group30Var5, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
+            PrefixedVariation::Group30Var4(_) => HeaderInfo::new(Variation::Group30Var4, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
+            PrefixedVariation::Group30Var3(_) => HeaderInfo::new(Variation::Group30Var3, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
+            PrefixedVariation::Group30Var2(_) => HeaderInfo::new(Variation::Group30Var2, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
+            PrefixedVariation::Group30Var1(_) => HeaderInfo::new(Variation::Group30Var1, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
             PrefixedVariation::Group32Var1(_) => HeaderInfo::new(Variation::Group32Var1, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group32Var2(_) => HeaderInfo::new(Variation::Group32Var2, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
             PrefixedVariation::Group32Var3(_) => HeaderInfo::new(Variation::Group32Var3, I::COUNT_AND_PREFIX_QUALIFIER, true, true),
@@ -711,6 +994,10 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
             PrefixedVariation::Group34Var1(_) => HeaderInfo::new(Variation::Group34Var1, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
             PrefixedVariation::Group34Var2(_) => HeaderInfo::new(Variation::Group34Var2, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
             PrefixedVariation::Group34Var3(_) => HeaderInfo::new(Variation::Group34Var3, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
+            PrefixedVariation::Group40Var1(_) => HeaderInfo::new(Variation::Group40Var1, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
+            PrefixedVariation::Group40Var2(_) => HeaderInfo::new(Variation::Group40Var2, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
+            PrefixedVariation::Group40Var3(_) => HeaderInfo::new(Variation::Group40Var3, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
+            PrefixedVariation::Group40Var4(_) => HeaderInfo::new(Variation::Group40Var4, I::COUNT_AND_PREFIX_QUALIFIER, false, true),
             PrefixedVariation::Group41Var1(_) => HeaderInfo::new(Variation::Group41Var1, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
             PrefixedVariation::Group41Var2(_) => HeaderInfo::new(Variation::Group41Var2, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
             PrefixedVariation::Group41Var3(_) => HeaderInfo::new(Variation::Group41Var3, I::COUNT_AND_PREFIX_QUALIFIER, false, false),
@@ -731,6 +1018,7 @@ impl<'a, I> PrefixedVariation<'a, I> where I : FixedSize + Index + std::fmt::Dis
             PrefixedVariation::Group43Var6(_) => HeaderInfo::new(Variation::Group43Var6, I::COUNT_AND_PREFIX_QUALIFIER, true, false),
             PrefixedVariation::Group43Var7(_) => HeaderInfo::new(Variation::Group43Var7, I::COUNT_AND_PREFIX_QUALIFIER, true, false),
             PrefixedVariation::Group43Var8(_) => HeaderInfo::new(Variation::Group43Var8, I::COUNT_AND_PREFIX_QUALIFIER, true, false),
+            PrefixedVariation::Group110VarX(x, _) =>  HeaderInfo::new(Variation::Group110(*x), I::COUNT_AND_PREFIX_QUALIFIER, false, false),
             PrefixedVariation::Group111VarX(x, _) =>  HeaderInfo::new(Variation::Group111(*x), I::COUNT_AND_PREFIX_QUALIFIER, true, false),
         }
     }
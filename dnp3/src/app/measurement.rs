@@ -316,6 +316,11 @@ impl BinaryOutputCommandEvent {
         let status = CommandStatus::from(value & 0b0111_1111);
         (state, status)
     }
+
+    fn to_state_and_status(self) -> u8 {
+        let state = if self.commanded_state { 0b1000_0000 } else { 0 };
+        state | (self.status.as_u8() & 0b0111_1111)
+    }
 }
 
 /// Corresponds to the different ways in which an analog output value
@@ -332,6 +337,17 @@ pub enum AnalogCommandValue {
     F64(f64),
 }
 
+impl AnalogCommandValue {
+    pub(crate) fn to_f64(self) -> f64 {
+        match self {
+            Self::I16(x) => x.into(),
+            Self::I32(x) => x.into(),
+            Self::F32(x) => x.into(),
+            Self::F64(x) => x,
+        }
+    }
+}
+
 /// Event transferred from master to outstation when the outstation receives an analog output.
 /// The primary use case of these objects are that they allow one master see that commands
 /// were issued to an outstation from another master.
@@ -487,6 +503,101 @@ impl From<Group43Var8> for AnalogOutputCommandEvent {
     }
 }
 
+impl ToVariation<Group13Var1> for BinaryOutputCommandEvent {
+    fn to_variation(&self) -> Group13Var1 {
+        Group13Var1 {
+            flags: self.to_state_and_status(),
+        }
+    }
+}
+
+impl ToVariation<Group13Var2> for BinaryOutputCommandEvent {
+    fn to_variation(&self) -> Group13Var2 {
+        Group13Var2 {
+            flags: self.to_state_and_status(),
+            time: self.time.into(),
+        }
+    }
+}
+
+// the outstation selects the variation that matches the type of the commanded value,
+// so these casts never lose precision
+impl ToVariation<Group43Var1> for AnalogOutputCommandEvent {
+    fn to_variation(&self) -> Group43Var1 {
+        Group43Var1 {
+            status: self.status,
+            value: self.commanded_value.to_f64() as i32,
+        }
+    }
+}
+
+impl ToVariation<Group43Var2> for AnalogOutputCommandEvent {
+    fn to_variation(&self) -> Group43Var2 {
+        Group43Var2 {
+            status: self.status,
+            value: self.commanded_value.to_f64() as i16,
+        }
+    }
+}
+
+impl ToVariation<Group43Var3> for AnalogOutputCommandEvent {
+    fn to_variation(&self) -> Group43Var3 {
+        Group43Var3 {
+            status: self.status,
+            value: self.commanded_value.to_f64() as i32,
+            time: self.time.into(),
+        }
+    }
+}
+
+impl ToVariation<Group43Var4> for AnalogOutputCommandEvent {
+    fn to_variation(&self) -> Group43Var4 {
+        Group43Var4 {
+            status: self.status,
+            value: self.commanded_value.to_f64() as i16,
+            time: self.time.into(),
+        }
+    }
+}
+
+impl ToVariation<Group43Var5> for AnalogOutputCommandEvent {
+    fn to_variation(&self) -> Group43Var5 {
+        Group43Var5 {
+            status: self.status,
+            value: self.commanded_value.to_f64() as f32,
+        }
+    }
+}
+
+impl ToVariation<Group43Var6> for AnalogOutputCommandEvent {
+    fn to_variation(&self) -> Group43Var6 {
+        Group43Var6 {
+            status: self.status,
+            value: self.commanded_value.to_f64(),
+        }
+    }
+}
+
+impl ToVariation<Group43Var7> for AnalogOutputCommandEvent {
+    fn to_variation(&self) -> Group43Var7 {
+        Group43Var7 {
+            status: self.status,
+            value: self.commanded_value.to_f64() as f32,
+            time: self.time.into(),
+        }
+    }
+}
+
+impl ToVariation<Group43Var8> for AnalogOutputCommandEvent {
+    fn to_variation(&self) -> Group43Var8 {
+        Group43Var8 {
+            status: self.status,
+            value: self.commanded_value.to_f64(),
+            time: self.time.into(),
+        }
+    }
+}
+
 impl From<Group102Var1> for UnsignedInteger {
     fn from(obj: Group102Var1) -> Self {
         Self { value: obj.value }
@@ -146,6 +146,11 @@ impl<'a> ParsedFragment<'a> {
     ) -> Result<Self, HeaderParseError> {
         Self::parse_no_logging(options, fragment)
     }
+
+    /// iterate over the object headers, stopping at the first header that cannot be parsed
+    pub(crate) fn headers_until_error(&self) -> impl Iterator<Item = ObjectHeader<'a>> {
+        ObjectParser::one_pass(self.options, self.function, self.raw_objects).flatten()
+    }
 }
 
 #[derive(Debug)]
@@ -293,13 +298,7 @@ impl std::fmt::Display for FragmentDisplay<'_> {
             }
             Err(err) => {
                 // if an error occurred, we re-parse the object headers so we can log any headers before the error
-                for header in ObjectParser::one_pass(
-                    self.fragment.options,
-                    self.fragment.function,
-                    self.fragment.raw_objects,
-                )
-                .flatten()
-                {
+                for header in self.fragment.headers_until_error() {
                     f.write_str("\n")?;
                     header.format(self.level.object_values(), f)?;
                 }
@@ -24,7 +24,8 @@ impl Sequence {
         Self::calc_next(self.value)
     }
 
-    pub(crate) fn new(x: u8) -> Self {
+    /// construct a sequence number, masking the value to its lower 4 bits
+    pub fn new(x: u8) -> Self {
         Self {
             value: x & Self::MAX_VALUE,
         }
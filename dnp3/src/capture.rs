@@ -0,0 +1,649 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use tokio::sync::mpsc;
+
+use crate::util::phys::{PhysAddr, PhysLayer};
+
+const SECTION_HEADER_BLOCK: u32 = 0x0A0D_0D0A;
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+const INTERFACE_DESCRIPTION_BLOCK: u32 = 1;
+const ENHANCED_PACKET_BLOCK: u32 = 6;
+
+const OPTION_END: u16 = 0;
+const OPTION_EPB_FLAGS: u16 = 2;
+const EPB_FLAGS_INBOUND: u32 = 1;
+const EPB_FLAGS_OUTBOUND: u32 = 2;
+
+const LINKTYPE_RAW: u16 = 101;
+const LINKTYPE_USER0: u16 = 147;
+
+/// interface on which packets with synthetic IP headers are recorded
+const IP_INTERFACE: u32 = 0;
+/// interface on which serial traffic is recorded
+const USER_INTERFACE: u32 = 1;
+
+/// number of packets waiting to be written before further packets are dropped
+const QUEUE_SIZE: usize = 1024;
+
+const PROTOCOL_TCP: u8 = 6;
+const PROTOCOL_UDP: u8 = 17;
+const TCP_FLAGS_PSH_ACK: u8 = 0x18;
+const TTL: u8 = 64;
+
+/// Records the traffic of a master or outstation to a pcapng file
+///
+/// Traffic is recorded as it is read from and written to the physical layer. TCP, TLS,
+/// UDP, and DTLS traffic is wrapped in synthetic IP and TCP/UDP headers built from the
+/// addresses of the connection so that Wireshark's DNP3 dissector decodes it directly.
+/// Encrypted sessions are recorded in the clear.
+///
+/// Packets are written on a blocking thread so that a slow file or writer never stalls the
+/// channel. If the writer falls too far behind, packets are dropped from the capture and a
+/// warning is logged.
+///
+/// Serial traffic is recorded using the `DLT_USER0` link type. Wireshark can decode it after
+/// mapping `User 0 (DLT=147)` to the `dnp3.udp` protocol under the `DLT_USER` preferences.
+///
+/// Captures are started and stopped at runtime using
+/// [`MasterChannel::start_capture`](crate::master::MasterChannel::start_capture) and
+/// [`OutstationHandle::start_capture`](crate::outstation::OutstationHandle::start_capture).
+pub struct PcapCapture {
+    writer: Box<dyn Write + Send>,
+    /// next sequence number for each direction of each TCP connection
+    sequences: HashMap<(SocketAddr, SocketAddr), u32>,
+}
+
+impl std::fmt::Debug for PcapCapture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PcapCapture")
+    }
+}
+
+impl PcapCapture {
+    /// Create a capture file at the specified path, replacing any existing file
+    pub fn create<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        let file = std::fs::File::create(path)?;
+        Self::new(std::io::BufWriter::new(file))
+    }
+
+    /// Create a capture that writes to an arbitrary sink
+    ///
+    /// The pcapng header is written immediately and the sink is flushed after every packet.
+    pub fn new<W>(writer: W) -> std::io::Result<Self>
+    where
+        W: Write + Send + 'static,
+    {
+        let mut capture = Self {
+            writer: Box::new(writer),
+            sequences: HashMap::new(),
+        };
+        capture.write_header()?;
+        Ok(capture)
+    }
+
+    fn write_header(&mut self) -> std::io::Result<()> {
+        let mut section = Vec::new();
+        section.extend(BYTE_ORDER_MAGIC.to_le_bytes());
+        // version 1.0
+        section.extend(1u16.to_le_bytes());
+        section.extend(0u16.to_le_bytes());
+        // the section length is not specified
+        section.extend((-1i64).to_le_bytes());
+        self.write_block(SECTION_HEADER_BLOCK, &section)?;
+
+        for link_type in [LINKTYPE_RAW, LINKTYPE_USER0] {
+            let mut interface = Vec::new();
+            interface.extend(link_type.to_le_bytes());
+            interface.extend(0u16.to_le_bytes());
+            // no limit on the snapshot length
+            interface.extend(0u32.to_le_bytes());
+            self.write_block(INTERFACE_DESCRIPTION_BLOCK, &interface)?;
+        }
+
+        self.writer.flush()
+    }
+
+    fn write_block(&mut self, block_type: u32, body: &[u8]) -> std::io::Result<()> {
+        let padding = (4 - body.len() % 4) % 4;
+        let length = (12 + body.len() + padding) as u32;
+        self.writer.write_all(&block_type.to_le_bytes())?;
+        self.writer.write_all(&length.to_le_bytes())?;
+        self.writer.write_all(body)?;
+        self.writer.write_all(&[0; 3][..padding])?;
+        self.writer.write_all(&length.to_le_bytes())
+    }
+
+    fn run(mut self, mut records: mpsc::Receiver<Record>) {
+        while let Some(record) = records.blocking_recv() {
+            if let Err(err) = self.write_packet(
+                record.medium,
+                record.direction,
+                &record.data,
+                record.addr,
+                record.time,
+            ) {
+                tracing::warn!("stopping capture due to write error: {}", err);
+                return;
+            }
+        }
+    }
+
+    pub(crate) fn write_packet(
+        &mut self,
+        medium: Medium,
+        direction: Direction,
+        data: &[u8],
+        addr: PhysAddr,
+        time: SystemTime,
+    ) -> std::io::Result<()> {
+        let (interface, packet) = match medium {
+            Medium::Tcp { local, remote } => {
+                let (source, destination) = direction.order(local, remote);
+                let segment = self.tcp_segment(source, destination, data);
+                (
+                    IP_INTERFACE,
+                    ip_packet(source.ip(), destination.ip(), PROTOCOL_TCP, &segment),
+                )
+            }
+            Medium::Udp { local, remote } => {
+                let remote = match (remote, addr) {
+                    (Some(x), _) => Some(x),
+                    (None, PhysAddr::Udp(x)) => Some(x),
+                    (None, PhysAddr::None) => None,
+                };
+                match remote {
+                    Some(remote) => {
+                        let (source, destination) = direction.order(local, remote);
+                        let datagram = udp_datagram(source, destination, data);
+                        (
+                            IP_INTERFACE,
+                            ip_packet(source.ip(), destination.ip(), PROTOCOL_UDP, &datagram),
+                        )
+                    }
+                    None => (USER_INTERFACE, data.to_vec()),
+                }
+            }
+            Medium::Other => (USER_INTERFACE, data.to_vec()),
+        };
+
+        let timestamp = time
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map(|x| x.as_micros() as u64)
+            .unwrap_or(0);
+        let flags = match direction {
+            Direction::Rx => EPB_FLAGS_INBOUND,
+            Direction::Tx => EPB_FLAGS_OUTBOUND,
+        };
+
+        let mut block = Vec::with_capacity(packet.len() + 40);
+        block.extend(interface.to_le_bytes());
+        block.extend(((timestamp >> 32) as u32).to_le_bytes());
+        block.extend((timestamp as u32).to_le_bytes());
+        block.extend((packet.len() as u32).to_le_bytes());
+        block.extend((packet.len() as u32).to_le_bytes());
+        block.extend(&packet);
+        block.resize(block.len() + (4 - packet.len() % 4) % 4, 0);
+        block.extend(OPTION_EPB_FLAGS.to_le_bytes());
+        block.extend(4u16.to_le_bytes());
+        block.extend(flags.to_le_bytes());
+        block.extend(OPTION_END.to_le_bytes());
+        block.extend(0u16.to_le_bytes());
+
+        self.write_block(ENHANCED_PACKET_BLOCK, &block)?;
+        self.writer.flush()
+    }
+
+    fn tcp_segment(&mut self, source: SocketAddr, destination: SocketAddr, data: &[u8]) -> Vec<u8> {
+        let seq = self.sequences.entry((source, destination)).or_insert(1);
+        let current = *seq;
+        *seq = seq.wrapping_add(data.len() as u32);
+        let ack = self
+            .sequences
+            .get(&(destination, source))
+            .copied()
+            .unwrap_or(1);
+
+        let mut segment = Vec::with_capacity(20 + data.len());
+        segment.extend(source.port().to_be_bytes());
+        segment.extend(destination.port().to_be_bytes());
+        segment.extend(current.to_be_bytes());
+        segment.extend(ack.to_be_bytes());
+        // 5 word header, no options
+        segment.push(5 << 4);
+        segment.push(TCP_FLAGS_PSH_ACK);
+        segment.extend(u16::MAX.to_be_bytes());
+        // checksum (not computed) and urgent pointer
+        segment.extend([0; 4]);
+        segment.extend(data);
+        segment
+    }
+}
+
+fn udp_datagram(source: SocketAddr, destination: SocketAddr, data: &[u8]) -> Vec<u8> {
+    let mut datagram = Vec::with_capacity(8 + data.len());
+    datagram.extend(source.port().to_be_bytes());
+    datagram.extend(destination.port().to_be_bytes());
+    datagram.extend(((8 + data.len()) as u16).to_be_bytes());
+    // checksum (not computed)
+    datagram.extend([0; 2]);
+    datagram.extend(data);
+    datagram
+}
+
+fn ip_packet(source: IpAddr, destination: IpAddr, protocol: u8, payload: &[u8]) -> Vec<u8> {
+    match (source, destination) {
+        (IpAddr::V4(source), IpAddr::V4(destination)) => {
+            let mut packet = Vec::with_capacity(20 + payload.len());
+            packet.push(0x45);
+            packet.push(0);
+            packet.extend(((20 + payload.len()) as u16).to_be_bytes());
+            // identification, then the don't fragment flag
+            packet.extend([0, 0, 0x40, 0]);
+            packet.push(TTL);
+            packet.push(protocol);
+            packet.extend([0, 0]);
+            packet.extend(source.octets());
+            packet.extend(destination.octets());
+            let checksum = ipv4_checksum(&packet);
+            packet[10..12].copy_from_slice(&checksum.to_be_bytes());
+            packet.extend(payload);
+            packet
+        }
+        _ => {
+            let mut packet = Vec::with_capacity(40 + payload.len());
+            packet.extend([0x60, 0, 0, 0]);
+            packet.extend((payload.len() as u16).to_be_bytes());
+            packet.push(protocol);
+            packet.push(TTL);
+            packet.extend(to_ipv6(source).octets());
+            packet.extend(to_ipv6(destination).octets());
+            packet.extend(payload);
+            packet
+        }
+    }
+}
+
+fn to_ipv6(addr: IpAddr) -> Ipv6Addr {
+    match addr {
+        IpAddr::V4(x) => x.to_ipv6_mapped(),
+        IpAddr::V6(x) => x,
+    }
+}
+
+fn ipv4_checksum(header: &[u8]) -> u16 {
+    let sum: u32 = header
+        .chunks(2)
+        .map(|x| u16::from_be_bytes([x[0], x.get(1).copied().unwrap_or(0)]) as u32)
+        .sum();
+    let folded = (sum & 0xFFFF) + (sum >> 16);
+    !((folded & 0xFFFF) + (folded >> 16)) as u16
+}
+
+/// Direction of traffic relative to the local endpoint
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Rx,
+    Tx,
+}
+
+impl Direction {
+    /// order the local and remote addresses as (source, destination)
+    fn order(self, local: SocketAddr, remote: SocketAddr) -> (SocketAddr, SocketAddr) {
+        match self {
+            Direction::Rx => (remote, local),
+            Direction::Tx => (local, remote),
+        }
+    }
+}
+
+/// Addressing of a physical layer used to build synthetic headers
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Medium {
+    Tcp {
+        local: SocketAddr,
+        remote: SocketAddr,
+    },
+    /// remote address is only known for connected sockets, otherwise it comes from the [`PhysAddr`]
+    Udp {
+        local: SocketAddr,
+        remote: Option<SocketAddr>,
+    },
+    /// serial ports or anything else without IP addresses
+    Other,
+}
+
+impl Medium {
+    pub(crate) fn remote(&self) -> Option<SocketAddr> {
+        match self {
+            Self::Tcp { remote, .. } => Some(*remote),
+            Self::Udp { remote, .. } => *remote,
+            Self::Other => None,
+        }
+    }
+}
+
+/// Packet handed from the channel to the thread writing the capture
+struct Record {
+    medium: Medium,
+    direction: Direction,
+    data: Vec<u8>,
+    addr: PhysAddr,
+    time: SystemTime,
+}
+
+struct Recorder {
+    records: mpsc::Sender<Record>,
+    /// true while packets are being dropped because the queue is full
+    dropping: bool,
+}
+
+impl Recorder {
+    /// Must be called from within the Tokio runtime since the writer runs on a blocking thread
+    fn spawn(capture: PcapCapture) -> Self {
+        let (tx, rx) = mpsc::channel(QUEUE_SIZE);
+        tokio::task::spawn_blocking(move || capture.run(rx));
+        Self {
+            records: tx,
+            dropping: false,
+        }
+    }
+
+    /// Returns false if the writer has stopped
+    fn send(&mut self, record: Record) -> bool {
+        match self.records.try_send(record) {
+            Ok(()) => self.dropping = false,
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                if !self.dropping {
+                    tracing::warn!("capture is not keeping up with the traffic, dropping packets");
+                    self.dropping = true;
+                }
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => return false,
+        }
+        true
+    }
+}
+
+/// Shared reference to the capture of a session, if one is active
+///
+/// Clones are held by the session and by its transport reader and writer so that
+/// the capture can be started and stopped while the session is running.
+#[derive(Clone, Default)]
+pub(crate) struct Capture {
+    inner: Arc<Mutex<Option<Recorder>>>,
+}
+
+impl Capture {
+    pub(crate) fn set(&self, capture: Option<PcapCapture>) {
+        // replacing the sender lets the previous writer finish the packets it already has
+        *self.inner.lock().unwrap() = capture.map(Recorder::spawn);
+    }
+
+    pub(crate) fn record(&self, io: &PhysLayer, direction: Direction, data: &[u8], addr: PhysAddr) {
+        let mut guard = self.inner.lock().unwrap();
+        if let Some(recorder) = guard.as_mut() {
+            let record = Record {
+                medium: io.medium(),
+                direction,
+                data: data.to_vec(),
+                addr,
+                time: SystemTime::now(),
+            };
+            if !recorder.send(record) {
+                // the writer stopped due to an error
+                *guard = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// writer that blocks until it is opened and counts the flushed packets
+    #[derive(Clone)]
+    struct Gate {
+        open: Arc<(Mutex<bool>, std::sync::Condvar)>,
+        flushes: Arc<Mutex<usize>>,
+        closed: std::sync::mpsc::SyncSender<()>,
+    }
+
+    impl Gate {
+        fn set(&self, value: bool) {
+            *self.open.0.lock().unwrap() = value;
+            self.open.1.notify_all();
+        }
+    }
+
+    impl Write for Gate {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let guard = self.open.0.lock().unwrap();
+            let _guard = self.open.1.wait_while(guard, |open| !*open).unwrap();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            *self.flushes.lock().unwrap() += 1;
+            Ok(())
+        }
+    }
+
+    impl Drop for Gate {
+        fn drop(&mut self) {
+            let _ = self.closed.try_send(());
+        }
+    }
+
+    fn record() -> Record {
+        Record {
+            medium: Medium::Other,
+            direction: Direction::Tx,
+            data: vec![1, 2, 3],
+            addr: PhysAddr::None,
+            time: SystemTime::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn drops_packets_while_the_writer_is_blocked() {
+        let (closed_tx, closed_rx) = std::sync::mpsc::sync_channel(1);
+        let gate = Gate {
+            open: Arc::new((Mutex::new(true), std::sync::Condvar::new())),
+            flushes: Arc::default(),
+            closed: closed_tx,
+        };
+        let capture = PcapCapture::new(gate.clone()).unwrap();
+        gate.set(false);
+
+        let mut recorder = Recorder::spawn(capture);
+        // never blocks even though nothing can be written
+        for _ in 0..2 * QUEUE_SIZE {
+            assert!(recorder.send(record()));
+        }
+        assert!(recorder.dropping);
+
+        gate.set(true);
+        drop(recorder);
+        // signalled once the writer drops its copy of the gate
+        tokio::task::spawn_blocking(move || closed_rx.recv().unwrap())
+            .await
+            .unwrap();
+
+        // the header, then the queue plus any packet taken by the writer before it blocked
+        let packets = *gate.flushes.lock().unwrap() - 1;
+        assert!(packets >= QUEUE_SIZE);
+        assert!(packets <= QUEUE_SIZE + 1);
+    }
+
+    #[tokio::test]
+    async fn detects_that_the_writer_has_stopped() {
+        struct Failing;
+
+        impl Write for Failing {
+            fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+                Err(std::io::ErrorKind::BrokenPipe.into())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let mut capture = PcapCapture::new(SharedBuffer::default()).unwrap();
+        capture.writer = Box::new(Failing);
+        let mut recorder = Recorder::spawn(capture);
+
+        assert!(recorder.send(record()));
+        tokio::time::timeout(std::time::Duration::from_secs(5), recorder.records.closed())
+            .await
+            .unwrap();
+        assert!(!recorder.send(record()));
+    }
+
+    /// split the capture into (block type, body) pairs
+    fn blocks(data: &[u8]) -> Vec<(u32, Vec<u8>)> {
+        let mut blocks = Vec::new();
+        let mut rest = data;
+        while !rest.is_empty() {
+            let block_type = u32::from_le_bytes(rest[0..4].try_into().unwrap());
+            let length = u32::from_le_bytes(rest[4..8].try_into().unwrap()) as usize;
+            assert_eq!(&rest[4..8], &rest[length - 4..length]);
+            blocks.push((block_type, rest[8..length - 4].to_vec()));
+            rest = &rest[length..];
+        }
+        blocks
+    }
+
+    /// returns (interface, packet data, flags) of an enhanced packet block
+    fn packet(body: &[u8]) -> (u32, &[u8], u32) {
+        let interface = u32::from_le_bytes(body[0..4].try_into().unwrap());
+        let length = u32::from_le_bytes(body[12..16].try_into().unwrap()) as usize;
+        let data = &body[20..20 + length];
+        let options = &body[20 + length + (4 - length % 4) % 4..];
+        assert_eq!(&options[0..4], &[2, 0, 4, 0]);
+        let flags = u32::from_le_bytes(options[4..8].try_into().unwrap());
+        (interface, data, flags)
+    }
+
+    #[test]
+    fn writes_header_and_interfaces() {
+        let buffer = SharedBuffer::default();
+        PcapCapture::new(buffer.clone()).unwrap();
+
+        let blocks = blocks(&buffer.0.lock().unwrap());
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0].0, SECTION_HEADER_BLOCK);
+        assert_eq!(&blocks[0].1[0..4], &BYTE_ORDER_MAGIC.to_le_bytes());
+        assert_eq!(
+            blocks[1],
+            (INTERFACE_DESCRIPTION_BLOCK, vec![101, 0, 0, 0, 0, 0, 0, 0])
+        );
+        assert_eq!(
+            blocks[2],
+            (INTERFACE_DESCRIPTION_BLOCK, vec![147, 0, 0, 0, 0, 0, 0, 0])
+        );
+    }
+
+    #[test]
+    fn wraps_tcp_traffic_in_synthetic_headers() {
+        let local: SocketAddr = "127.0.0.1:20000".parse().unwrap();
+        let remote: SocketAddr = "10.0.0.1:50000".parse().unwrap();
+        let medium = Medium::Tcp { local, remote };
+
+        let buffer = SharedBuffer::default();
+        let mut capture = PcapCapture::new(buffer.clone()).unwrap();
+        capture
+            .write_packet(
+                medium,
+                Direction::Rx,
+                &[0x05, 0x64, 0x05],
+                PhysAddr::None,
+                SystemTime::now(),
+            )
+            .unwrap();
+        capture
+            .write_packet(
+                medium,
+                Direction::Tx,
+                &[0xAA, 0xBB],
+                PhysAddr::None,
+                SystemTime::now(),
+            )
+            .unwrap();
+        capture
+            .write_packet(
+                medium,
+                Direction::Rx,
+                &[0xCC],
+                PhysAddr::None,
+                SystemTime::now(),
+            )
+            .unwrap();
+
+        let data = buffer.0.lock().unwrap();
+        let blocks = blocks(&data);
+        assert_eq!(blocks.len(), 6);
+
+        let (interface, rx, flags) = packet(&blocks[3].1);
+        assert_eq!(interface, IP_INTERFACE);
+        assert_eq!(flags, EPB_FLAGS_INBOUND);
+        assert_eq!(rx.len(), 20 + 20 + 3);
+        assert_eq!(ipv4_checksum(&rx[0..20]), 0);
+        assert_eq!(&rx[12..16], &[10, 0, 0, 1]);
+        assert_eq!(&rx[16..20], &[127, 0, 0, 1]);
+        // ports, then sequence number
+        assert_eq!(&rx[20..28], &[0xC3, 0x50, 0x4E, 0x20, 0, 0, 0, 1]);
+        assert_eq!(&rx[40..], &[0x05, 0x64, 0x05]);
+
+        let (_, tx, flags) = packet(&blocks[4].1);
+        assert_eq!(flags, EPB_FLAGS_OUTBOUND);
+        // sequence number, then acknowledgement of the 3 received bytes
+        assert_eq!(&tx[24..32], &[0, 0, 0, 1, 0, 0, 0, 4]);
+        assert_eq!(&tx[40..], &[0xAA, 0xBB]);
+
+        let (_, rx, _) = packet(&blocks[5].1);
+        assert_eq!(&rx[24..32], &[0, 0, 0, 4, 0, 0, 0, 3]);
+    }
+
+    #[test]
+    fn records_traffic_without_addresses_as_user_link_type() {
+        let buffer = SharedBuffer::default();
+        let mut capture = PcapCapture::new(buffer.clone()).unwrap();
+        capture
+            .write_packet(
+                Medium::Other,
+                Direction::Tx,
+                &[1, 2, 3, 4, 5],
+                PhysAddr::None,
+                SystemTime::now(),
+            )
+            .unwrap();
+
+        let data = buffer.0.lock().unwrap();
+        let blocks = blocks(&data);
+        assert_eq!(
+            packet(&blocks[3].1),
+            (USER_INTERFACE, &[1, 2, 3, 4, 5][..], 2)
+        );
+    }
+}
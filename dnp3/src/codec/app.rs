@@ -0,0 +1,491 @@
+use crate::app::attr::{AnyAttribute, OwnedAttribute};
+use crate::app::control::{Group12Var1, Group41Var1, Group41Var2, Group41Var3, Group41Var4};
+use crate::app::gen::prefixed::PrefixedVariation;
+use crate::app::gen::ranged::RangedVariation;
+use crate::app::measurement::*;
+use crate::app::parse::options::ParseOptions;
+use crate::app::parse::parser;
+use crate::app::parse::parser::{HeaderDetails, ParsedFragment};
+use crate::app::parse::traits::Index;
+use crate::app::{ControlField, FunctionCode, Iin, QualifierCode, Timestamp, Variation};
+use crate::codec::CodecError;
+use crate::decode::{AppDecodeLevel, ApplicationEvent, DecodeDirection};
+use crate::master::extract::extract_header;
+use crate::master::{HeaderInfo, ReadHandler};
+
+/// Range or count of the objects in an object header
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HeaderRange {
+    /// all objects of the variation (qualifier 0x06)
+    AllObjects,
+    /// start and stop indices (qualifiers 0x00 and 0x01)
+    StartStop(u16, u16),
+    /// count of objects (qualifiers 0x07, 0x08, 0x17, 0x28 and 0x5B)
+    Count(u16),
+}
+
+/// Values carried by an object header, converted to owned types
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum ObjectValues {
+    /// header does not carry values, e.g. a header in a READ request or a common
+    /// time-of-occurrence, or values that are not converted by the codec
+    None,
+    /// binary input values and their indices
+    BinaryInput(Vec<(BinaryInput, u16)>),
+    /// double-bit binary input values and their indices
+    DoubleBitBinaryInput(Vec<(DoubleBitBinaryInput, u16)>),
+    /// binary output status values and their indices
+    BinaryOutputStatus(Vec<(BinaryOutputStatus, u16)>),
+    /// counter values and their indices
+    Counter(Vec<(Counter, u16)>),
+    /// frozen counter values and their indices
+    FrozenCounter(Vec<(FrozenCounter, u16)>),
+    /// analog input values and their indices
+    AnalogInput(Vec<(AnalogInput, u16)>),
+    /// frozen analog input values and their indices
+    FrozenAnalogInput(Vec<(FrozenAnalogInput, u16)>),
+    /// analog input dead-bands and their indices
+    AnalogInputDeadBand(Vec<(AnalogInputDeadBand, u16)>),
+    /// analog output status values and their indices
+    AnalogOutputStatus(Vec<(AnalogOutputStatus, u16)>),
+    /// analog output command events and their indices
+    AnalogOutputCommandEvent(Vec<(AnalogOutputCommandEvent, u16)>),
+    /// binary output command events and their indices
+    BinaryOutputCommandEvent(Vec<(BinaryOutputCommandEvent, u16)>),
+    /// unsigned integer values and their indices
+    UnsignedInteger(Vec<(UnsignedInteger, u16)>),
+    /// octet strings and their indices
+    OctetString(Vec<(Vec<u8>, u16)>),
+    /// device attribute
+    Attribute(OwnedAttribute),
+    /// absolute time (g50v1)
+    AbsoluteTime(Timestamp),
+    /// control relay output blocks and their indices
+    Crob(Vec<(Group12Var1, u16)>),
+    /// 32-bit analog output commands and their indices
+    AnalogOutputI32(Vec<(Group41Var1, u16)>),
+    /// 16-bit analog output commands and their indices
+    AnalogOutputI16(Vec<(Group41Var2, u16)>),
+    /// single-precision analog output commands and their indices
+    AnalogOutputF32(Vec<(Group41Var3, u16)>),
+    /// double-precision analog output commands and their indices
+    AnalogOutputF64(Vec<(Group41Var4, u16)>),
+}
+
+/// Object header of a parsed [`Message`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectHeader {
+    /// variation of the objects
+    pub variation: Variation,
+    /// qualifier code of the header
+    pub qualifier: QualifierCode,
+    /// range or count of the objects
+    pub range: HeaderRange,
+    /// values carried by the header
+    pub values: ObjectValues,
+}
+
+/// Application-layer request or response parsed into owned values
+#[derive(Clone, Debug, PartialEq)]
+pub struct Message {
+    /// application control field
+    pub control: ControlField,
+    /// function code
+    pub function: FunctionCode,
+    /// internal indications, only present in responses
+    pub iin: Option<Iin>,
+    /// parsed object headers
+    pub headers: Vec<ObjectHeader>,
+    /// encoded object headers, which can be passed to [`crate::codec::MessageBuilder::objects`]
+    /// to re-encode the message
+    pub objects: Vec<u8>,
+}
+
+impl Message {
+    /// Parse a complete application fragment
+    pub fn parse(fragment: &[u8]) -> Result<Self, CodecError> {
+        let parsed = ParsedFragment::parse(ParseOptions::get_static(), fragment)
+            .map_err(CodecError::BadHeader)?;
+        let objects = parsed.objects.map_err(CodecError::BadObjects)?;
+
+        let mut converter = HeaderConverter::default();
+        let headers = objects
+            .iter()
+            .map(|header| converter.convert(&header, true))
+            .collect();
+
+        Ok(Self {
+            control: parsed.control,
+            function: parsed.function,
+            iin: parsed.iin,
+            headers,
+            objects: parsed.raw_objects.to_vec(),
+        })
+    }
+}
+
+/// Format a complete application fragment just like the application-layer decoding in the log
+pub fn format_fragment(fragment: &[u8], level: AppDecodeLevel) -> Result<String, CodecError> {
+    let parsed = ParsedFragment::parse(ParseOptions::get_static(), fragment)
+        .map_err(CodecError::BadHeader)?;
+    Ok(parsed.display(level).to_string())
+}
+
+/// Convert a complete application fragment to the [`ApplicationEvent`] that a
+/// [`DecodeSink`](crate::decode::DecodeSink) receives, or `None` if the level doesn't decode
+/// fragments
+///
+/// Errors in the object headers are reported in [`ApplicationEvent::error`], only a malformed
+/// application header is returned as an error.
+pub fn fragment_event(
+    fragment: &[u8],
+    direction: DecodeDirection,
+    level: AppDecodeLevel,
+) -> Result<Option<ApplicationEvent>, CodecError> {
+    if !level.header() {
+        return Ok(None);
+    }
+    let parsed = ParsedFragment::parse(ParseOptions::get_static(), fragment)
+        .map_err(CodecError::BadHeader)?;
+    Ok(Some(ApplicationEvent::new(direction, &parsed, level)))
+}
+
+/// Converts parsed object headers into owned headers, tracking the common time-of-occurrence
+/// that applies to subsequent headers
+#[derive(Default)]
+pub(crate) struct HeaderConverter {
+    cto: Option<Time>,
+}
+
+impl HeaderConverter {
+    pub(crate) fn convert(&mut self, header: &parser::ObjectHeader, values: bool) -> ObjectHeader {
+        let values = if values {
+            owned_values(&header.details).unwrap_or_else(|| {
+                let mut collector = Collector::default();
+                self.cto = extract_header(self.cto, header, &mut collector).0;
+                collector.values
+            })
+        } else {
+            ObjectValues::None
+        };
+        ObjectHeader {
+            variation: header.variation,
+            qualifier: header.details.qualifier(),
+            range: range(&header.details),
+            values,
+        }
+    }
+}
+
+fn range(details: &HeaderDetails) -> HeaderRange {
+    match details {
+        HeaderDetails::AllObjects(_) => HeaderRange::AllObjects,
+        HeaderDetails::OneByteStartStop(start, stop, _) => {
+            HeaderRange::StartStop(*start as u16, *stop as u16)
+        }
+        HeaderDetails::TwoByteStartStop(start, stop, _) => HeaderRange::StartStop(*start, *stop),
+        HeaderDetails::OneByteCount(count, _) => HeaderRange::Count(*count as u16),
+        HeaderDetails::TwoByteCount(count, _) => HeaderRange::Count(*count),
+        HeaderDetails::OneByteCountAndPrefix(count, _) => HeaderRange::Count(*count as u16),
+        HeaderDetails::TwoByteCountAndPrefix(count, _) => HeaderRange::Count(*count),
+        HeaderDetails::TwoByteFreeFormat(count, _) => HeaderRange::Count(*count as u16),
+    }
+}
+
+/// Convert the headers that the measurement extraction doesn't handle: device attributes,
+/// which are only reported to a `ReadHandler` in borrowed form, and controls
+fn owned_values(details: &HeaderDetails) -> Option<ObjectValues> {
+    match details {
+        HeaderDetails::OneByteStartStop(_, _, RangedVariation::Group0(_, attr))
+        | HeaderDetails::TwoByteStartStop(_, _, RangedVariation::Group0(_, attr)) => Some(
+            attr.and_then(|x| x.to_owned())
+                .map_or(ObjectValues::None, ObjectValues::Attribute),
+        ),
+        HeaderDetails::OneByteCountAndPrefix(_, var) => prefixed_values(var),
+        HeaderDetails::TwoByteCountAndPrefix(_, var) => prefixed_values(var),
+        _ => None,
+    }
+}
+
+fn prefixed_values<I>(var: &PrefixedVariation<I>) -> Option<ObjectValues>
+where
+    I: Index,
+{
+    let values = match var {
+        PrefixedVariation::Group0(attr) => (*attr)
+            .to_owned()
+            .map_or(ObjectValues::None, ObjectValues::Attribute),
+        PrefixedVariation::Group12Var1(seq) => ObjectValues::Crob(
+            seq.iter()
+                .map(|x| (x.value, x.index.widen_to_u16()))
+                .collect(),
+        ),
+        PrefixedVariation::Group41Var1(seq) => ObjectValues::AnalogOutputI32(
+            seq.iter()
+                .map(|x| (x.value, x.index.widen_to_u16()))
+                .collect(),
+        ),
+        PrefixedVariation::Group41Var2(seq) => ObjectValues::AnalogOutputI16(
+            seq.iter()
+                .map(|x| (x.value, x.index.widen_to_u16()))
+                .collect(),
+        ),
+        PrefixedVariation::Group41Var3(seq) => ObjectValues::AnalogOutputF32(
+            seq.iter()
+                .map(|x| (x.value, x.index.widen_to_u16()))
+                .collect(),
+        ),
+        PrefixedVariation::Group41Var4(seq) => ObjectValues::AnalogOutputF64(
+            seq.iter()
+                .map(|x| (x.value, x.index.widen_to_u16()))
+                .collect(),
+        ),
+        _ => return None,
+    };
+    Some(values)
+}
+
+/// Collects the values of a single header reported by the measurement extraction
+struct Collector {
+    values: ObjectValues,
+}
+
+impl Default for Collector {
+    fn default() -> Self {
+        Self {
+            values: ObjectValues::None,
+        }
+    }
+}
+
+impl ReadHandler for Collector {
+    fn handle_binary_input(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (BinaryInput, u16)>,
+    ) {
+        self.values = ObjectValues::BinaryInput(iter.collect());
+    }
+
+    fn handle_double_bit_binary_input(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (DoubleBitBinaryInput, u16)>,
+    ) {
+        self.values = ObjectValues::DoubleBitBinaryInput(iter.collect());
+    }
+
+    fn handle_binary_output_status(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (BinaryOutputStatus, u16)>,
+    ) {
+        self.values = ObjectValues::BinaryOutputStatus(iter.collect());
+    }
+
+    fn handle_counter(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (Counter, u16)>,
+    ) {
+        self.values = ObjectValues::Counter(iter.collect());
+    }
+
+    fn handle_frozen_counter(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (FrozenCounter, u16)>,
+    ) {
+        self.values = ObjectValues::FrozenCounter(iter.collect());
+    }
+
+    fn handle_analog_input(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (AnalogInput, u16)>,
+    ) {
+        self.values = ObjectValues::AnalogInput(iter.collect());
+    }
+
+    fn handle_frozen_analog_input(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (FrozenAnalogInput, u16)>,
+    ) {
+        self.values = ObjectValues::FrozenAnalogInput(iter.collect());
+    }
+
+    fn handle_analog_input_dead_band(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (AnalogInputDeadBand, u16)>,
+    ) {
+        self.values = ObjectValues::AnalogInputDeadBand(iter.collect());
+    }
+
+    fn handle_analog_output_status(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (AnalogOutputStatus, u16)>,
+    ) {
+        self.values = ObjectValues::AnalogOutputStatus(iter.collect());
+    }
+
+    fn handle_analog_output_command_event(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (AnalogOutputCommandEvent, u16)>,
+    ) {
+        self.values = ObjectValues::AnalogOutputCommandEvent(iter.collect());
+    }
+
+    fn handle_binary_output_command_event(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (BinaryOutputCommandEvent, u16)>,
+    ) {
+        self.values = ObjectValues::BinaryOutputCommandEvent(iter.collect());
+    }
+
+    fn handle_unsigned_integer(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (UnsignedInteger, u16)>,
+    ) {
+        self.values = ObjectValues::UnsignedInteger(iter.collect());
+    }
+
+    fn handle_octet_string<'a>(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &'a mut dyn Iterator<Item = (&'a [u8], u16)>,
+    ) {
+        self.values = ObjectValues::OctetString(iter.map(|(x, i)| (x.to_vec(), i)).collect());
+    }
+
+    fn handle_device_attribute(&mut self, _info: HeaderInfo, _attr: AnyAttribute) {
+        // attributes are converted by owned_values
+    }
+
+    fn handle_abs_time(&mut self, _info: HeaderInfo, time: Timestamp) {
+        self.values = ObjectValues::AbsoluteTime(time);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::control::{CommandStatus, ControlCode};
+    use crate::app::Sequence;
+
+    #[test]
+    fn parses_read_request() {
+        // read of class 1, 2, 3 and 0
+        let message = Message::parse(&[
+            0xC3, 0x01, 0x3C, 0x02, 0x06, 0x3C, 0x03, 0x06, 0x3C, 0x04, 0x06, 0x3C, 0x01, 0x06,
+        ])
+        .unwrap();
+
+        assert_eq!(message.function, FunctionCode::Read);
+        assert_eq!(message.control.seq, Sequence::new(3));
+        assert_eq!(message.iin, None);
+        assert_eq!(message.headers.len(), 4);
+        assert_eq!(message.headers[0].variation, Variation::Group60Var2);
+        assert_eq!(message.headers[0].range, HeaderRange::AllObjects);
+        assert_eq!(message.headers[0].values, ObjectValues::None);
+    }
+
+    #[test]
+    fn fragment_events_match_the_decode_level() {
+        // read of class 1 followed by a truncated header
+        let fragment = [0xC0, 0x01, 0x3C, 0x02, 0x06, 0x3C];
+
+        assert_eq!(
+            fragment_event(&fragment, DecodeDirection::Tx, AppDecodeLevel::Nothing),
+            Ok(None)
+        );
+
+        let event = fragment_event(
+            &fragment,
+            DecodeDirection::Tx,
+            AppDecodeLevel::ObjectHeaders,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(event.function, FunctionCode::Read);
+        assert_eq!(event.headers.unwrap().len(), 1);
+        assert!(event.error.is_some());
+
+        assert!(fragment_event(&[0xC0], DecodeDirection::Rx, AppDecodeLevel::Header).is_err());
+    }
+
+    #[test]
+    fn parses_response_with_measurements() {
+        // g30v2 (16-bit analog input with flags) with a one byte range of [1, 2]
+        let message = Message::parse(&[
+            0xC0, 0x81, 0x00, 0x00, 0x1E, 0x02, 0x00, 0x01, 0x02, 0x01, 0x0A, 0x00, 0x01, 0x0B,
+            0x00,
+        ])
+        .unwrap();
+
+        assert_eq!(message.function, FunctionCode::Response);
+        assert_eq!(message.iin, Some(Iin::default()));
+        assert_eq!(message.headers[0].range, HeaderRange::StartStop(1, 2));
+        match &message.headers[0].values {
+            ObjectValues::AnalogInput(values) => {
+                assert_eq!(values.len(), 2);
+                assert_eq!(values[0].0.value, 10.0);
+                assert_eq!(values[0].1, 1);
+                assert_eq!(values[1].0.value, 11.0);
+                assert_eq!(values[1].1, 2);
+            }
+            x => panic!("unexpected values: {x:?}"),
+        }
+    }
+
+    #[test]
+    fn parses_control_request() {
+        // g12v1 with a single CROB at index 7
+        let message = Message::parse(&[
+            0xC1, 0x05, 0x0C, 0x01, 0x17, 0x01, 0x07, 0x03, 0x01, 0x64, 0x00, 0x00, 0x00, 0x64,
+            0x00, 0x00, 0x00, 0x00,
+        ])
+        .unwrap();
+
+        assert_eq!(message.function, FunctionCode::DirectOperate);
+        match &message.headers[0].values {
+            ObjectValues::Crob(values) => {
+                assert_eq!(values.len(), 1);
+                let (crob, index) = values[0];
+                assert_eq!(index, 7);
+                assert_eq!(crob.code, ControlCode::from(0x03));
+                assert_eq!(crob.count, 1);
+                assert_eq!(crob.on_time, 100);
+                assert_eq!(crob.status, CommandStatus::Success);
+            }
+            x => panic!("unexpected values: {x:?}"),
+        }
+    }
+
+    #[test]
+    fn formats_fragment_like_the_log() {
+        let text = format_fragment(
+            &[0xC3, 0x01, 0x3C, 0x02, 0x06],
+            AppDecodeLevel::ObjectHeaders,
+        )
+        .unwrap();
+        assert!(
+            text.starts_with("ctrl: [fir: true fin: true con: false uns: false seq: 3] func: Read")
+        );
+        assert!(text.contains("g60v2"));
+    }
+
+    #[test]
+    fn reports_malformed_objects() {
+        assert_matches!(
+            Message::parse(&[0xC0, 0x01, 0x3C]),
+            Err(CodecError::BadObjects(_))
+        );
+    }
+}
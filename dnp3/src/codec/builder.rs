@@ -0,0 +1,340 @@
+use scursor::WriteCursor;
+
+use crate::app::format::write::HeaderWriter;
+use crate::app::measurement::*;
+use crate::app::{
+    ControlField, FunctionCode, Iin, RequestHeader, ResponseFunction, ResponseHeader,
+};
+use crate::codec::CodecError;
+use crate::master::{CommandHeaders, Headers, ReadRequest};
+use crate::outstation::database::details::event::writer::{EventWriter, Writable};
+use crate::outstation::database::details::range::traits::StaticVariation;
+use crate::outstation::database::details::range::writer::RangeWriter;
+use crate::outstation::database::*;
+use crate::util::BadWrite;
+
+/// Maximum size of a fragment produced by [`MessageBuilder`]
+const MAX_FRAGMENT_SIZE: usize = u16::MAX as usize;
+
+/// Builds application-layer requests and responses
+///
+/// Object headers are written using the same types that the master API uses to build
+/// requests, from measurement values using the [`WriteStatic`] and [`WriteEvents`] traits,
+/// or from pre-encoded objects such as [`crate::codec::Message::objects`].
+pub struct MessageBuilder {
+    buffer: Vec<u8>,
+    length: usize,
+}
+
+impl MessageBuilder {
+    /// Start a request with the specified control field and function code
+    pub fn request(control: ControlField, function: FunctionCode) -> Self {
+        let mut builder = Self::new();
+        // cannot fail, the buffer is larger than the header
+        let _ = builder.write(|cursor| RequestHeader::new(control, function).write(cursor));
+        builder
+    }
+
+    /// Start a response with the specified control field, function, and internal indications
+    pub fn response(control: ControlField, function: ResponseFunction, iin: Iin) -> Self {
+        let mut builder = Self::new();
+        // cannot fail, the buffer is larger than the header
+        let _ = builder.write(|cursor| ResponseHeader::new(control, function, iin).write(cursor));
+        builder
+    }
+
+    /// Append the object headers of a READ request
+    pub fn read(mut self, request: &ReadRequest) -> Result<Self, CodecError> {
+        self.write(|cursor| request.format(&mut HeaderWriter::new(cursor)))?;
+        Ok(self)
+    }
+
+    /// Append a generic collection of object headers
+    pub fn headers(mut self, headers: &Headers) -> Result<Self, CodecError> {
+        self.write(|cursor| headers.write(&mut HeaderWriter::new(cursor)))?;
+        Ok(self)
+    }
+
+    /// Append the object headers of a control request
+    pub fn commands(mut self, commands: &CommandHeaders) -> Result<Self, CodecError> {
+        self.write(|cursor| commands.write(&mut HeaderWriter::new(cursor)))?;
+        Ok(self)
+    }
+
+    /// Append pre-encoded object headers
+    pub fn objects(mut self, objects: &[u8]) -> Result<Self, CodecError> {
+        self.write(|cursor| cursor.write_bytes(objects))?;
+        Ok(self)
+    }
+
+    /// Retrieve the encoded fragment
+    pub fn build(mut self) -> Vec<u8> {
+        self.buffer.truncate(self.length);
+        self.buffer
+    }
+
+    fn new() -> Self {
+        Self {
+            buffer: vec![0; MAX_FRAGMENT_SIZE],
+            length: 0,
+        }
+    }
+
+    fn write_static<T, V>(&mut self, variation: V, values: &[(T, u16)]) -> Result<(), CodecError>
+    where
+        V: StaticVariation<T>,
+    {
+        self.write(|cursor| {
+            let mut writer = RangeWriter::new();
+            for (value, index) in values {
+                let info = variation.promote(value).get_write_info(value);
+                writer.write(cursor, *index, value, info)?;
+            }
+            Ok::<(), BadWrite>(())
+        })
+    }
+
+    fn write_events<T>(
+        &mut self,
+        variation: T::EventVariation,
+        values: &[(T, u16)],
+    ) -> Result<(), CodecError>
+    where
+        T: Writable,
+        T::EventVariation: Copy,
+    {
+        self.write(|cursor| {
+            let mut writer = EventWriter::new();
+            for (value, index) in values {
+                writer.write(cursor, value, *index, variation)?;
+            }
+            Ok::<(), BadWrite>(())
+        })
+    }
+
+    fn write<F, E>(&mut self, write: F) -> Result<(), CodecError>
+    where
+        F: FnOnce(&mut WriteCursor) -> Result<(), E>,
+    {
+        let mut cursor = WriteCursor::new(&mut self.buffer[self.length..]);
+        write(&mut cursor).map_err(|_| CodecError::TooLarge)?;
+        self.length += cursor.position();
+        Ok(())
+    }
+}
+
+/// Trait for appending static values of a measurement type to a response
+pub trait WriteStatic<T>: Sized {
+    /// Static variation used to encode the values
+    type Variation;
+
+    /// Append the values using range-qualified headers
+    ///
+    /// Values with consecutive indices share a header. Variations that cannot encode the flags
+    /// of a value are promoted in the same way as the outstation does, e.g. g1v1 to g1v2.
+    fn static_values(
+        self,
+        variation: Self::Variation,
+        values: &[(T, u16)],
+    ) -> Result<Self, CodecError>;
+}
+
+/// Trait for appending events of a measurement type to a response
+pub trait WriteEvents<T>: Sized {
+    /// Event variation used to encode the values
+    type Variation;
+
+    /// Append the events using index-prefixed headers
+    ///
+    /// Variations that use relative time are preceded by a common time-of-occurrence object.
+    fn events(self, variation: Self::Variation, values: &[(T, u16)]) -> Result<Self, CodecError>;
+}
+
+macro_rules! impl_write_values {
+    ($type:ty, $static_variation:ty, $event_variation:ty) => {
+        impl WriteStatic<$type> for MessageBuilder {
+            type Variation = $static_variation;
+
+            fn static_values(
+                mut self,
+                variation: Self::Variation,
+                values: &[($type, u16)],
+            ) -> Result<Self, CodecError> {
+                self.write_static(variation, values)?;
+                Ok(self)
+            }
+        }
+
+        impl WriteEvents<$type> for MessageBuilder {
+            type Variation = $event_variation;
+
+            fn events(
+                mut self,
+                variation: Self::Variation,
+                values: &[($type, u16)],
+            ) -> Result<Self, CodecError> {
+                self.write_events(variation, values)?;
+                Ok(self)
+            }
+        }
+    };
+}
+
+impl_write_values!(
+    BinaryInput,
+    StaticBinaryInputVariation,
+    EventBinaryInputVariation
+);
+impl_write_values!(
+    DoubleBitBinaryInput,
+    StaticDoubleBitBinaryInputVariation,
+    EventDoubleBitBinaryInputVariation
+);
+impl_write_values!(
+    BinaryOutputStatus,
+    StaticBinaryOutputStatusVariation,
+    EventBinaryOutputStatusVariation
+);
+impl_write_values!(Counter, StaticCounterVariation, EventCounterVariation);
+impl_write_values!(
+    FrozenCounter,
+    StaticFrozenCounterVariation,
+    EventFrozenCounterVariation
+);
+impl_write_values!(
+    AnalogInput,
+    StaticAnalogInputVariation,
+    EventAnalogInputVariation
+);
+impl_write_values!(
+    AnalogOutputStatus,
+    StaticAnalogOutputStatusVariation,
+    EventAnalogOutputStatusVariation
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::control::*;
+    use crate::app::{Sequence, Variation};
+    use crate::codec::{Message, ObjectValues};
+    use crate::master::{Classes, CommandBuilder, CommandSupport};
+
+    fn control() -> ControlField {
+        ControlField {
+            fir: true,
+            fin: true,
+            con: false,
+            uns: false,
+            seq: Sequence::new(3),
+        }
+    }
+
+    #[test]
+    fn builds_read_request() {
+        let fragment = MessageBuilder::request(control(), FunctionCode::Read)
+            .read(&ReadRequest::class_scan(Classes::all()))
+            .unwrap()
+            .build();
+
+        assert_eq!(
+            fragment,
+            [0xC3, 0x01, 0x3C, 0x02, 0x06, 0x3C, 0x03, 0x06, 0x3C, 0x04, 0x06, 0x3C, 0x01, 0x06]
+        );
+    }
+
+    #[test]
+    fn builds_control_request_that_parses_back() {
+        let mut commands = CommandBuilder::new();
+        commands.add_u16(Group12Var1::from_op_type(OpType::LatchOn), 7);
+        let fragment = MessageBuilder::request(control(), FunctionCode::DirectOperate)
+            .commands(&commands.build())
+            .unwrap()
+            .build();
+
+        let message = Message::parse(&fragment).unwrap();
+        assert_eq!(message.function, FunctionCode::DirectOperate);
+        assert_eq!(message.headers[0].variation, Variation::Group12Var1);
+        assert_eq!(
+            message.headers[0].values,
+            ObjectValues::Crob(vec![(Group12Var1::from_op_type(OpType::LatchOn), 7)])
+        );
+    }
+
+    #[test]
+    fn builds_response_with_static_values_and_events() {
+        let binaries = [
+            (
+                BinaryInput::new(true, Flags::ONLINE, Time::synchronized(0)),
+                0,
+            ),
+            (
+                BinaryInput::new(false, Flags::ONLINE, Time::synchronized(0)),
+                1,
+            ),
+            (
+                BinaryInput::new(true, Flags::ONLINE, Time::synchronized(0)),
+                2,
+            ),
+        ];
+        let event = AnalogInput::new(42.0, Flags::ONLINE, Time::synchronized(1234));
+
+        let fragment =
+            MessageBuilder::response(control(), ResponseFunction::Response, Iin::default())
+                .static_values(StaticBinaryInputVariation::Group1Var1, &binaries)
+                .unwrap()
+                .events(EventAnalogInputVariation::Group32Var3, &[(event, 7)])
+                .unwrap()
+                .build();
+
+        let message = Message::parse(&fragment).unwrap();
+        assert_eq!(message.headers.len(), 2);
+        assert_eq!(message.headers[0].variation, Variation::Group1Var1);
+        assert_eq!(
+            message.headers[0].values,
+            ObjectValues::BinaryInput(
+                binaries
+                    .iter()
+                    .map(|(v, i)| (BinaryInput { time: None, ..*v }, *i))
+                    .collect()
+            )
+        );
+        assert_eq!(message.headers[1].variation, Variation::Group32Var3);
+        assert_eq!(
+            message.headers[1].values,
+            ObjectValues::AnalogInput(vec![(event, 7)])
+        );
+    }
+
+    #[test]
+    fn promotes_static_variation_to_encode_flags() {
+        let value = BinaryInput::new(true, Flags::COMM_LOST, Time::synchronized(0));
+        let fragment =
+            MessageBuilder::response(control(), ResponseFunction::Response, Iin::default())
+                .static_values(StaticBinaryInputVariation::Group1Var1, &[(value, 3)])
+                .unwrap()
+                .build();
+
+        let message = Message::parse(&fragment).unwrap();
+        assert_eq!(message.headers[0].variation, Variation::Group1Var2);
+    }
+
+    #[test]
+    fn re_encodes_parsed_response() {
+        let original = [
+            0xC0, 0x81, 0x00, 0x00, 0x1E, 0x02, 0x00, 0x01, 0x02, 0x01, 0x0A, 0x00, 0x01, 0x0B,
+            0x00,
+        ];
+        let message = Message::parse(&original).unwrap();
+        let fragment = MessageBuilder::response(
+            message.control,
+            ResponseFunction::Response,
+            message.iin.unwrap(),
+        )
+        .objects(&message.objects)
+        .unwrap()
+        .build();
+
+        assert_eq!(fragment, original);
+    }
+}
@@ -0,0 +1,214 @@
+use scursor::{ReadCursor, WriteCursor};
+
+use crate::codec::CodecError;
+use crate::decode::{DecodeDirection, LinkDecodeLevel, LinkEvent};
+use crate::link::constant;
+use crate::link::display::LinkDisplay;
+use crate::link::error::ParseError;
+use crate::link::format::{format_data_frame, format_header_only, Payload};
+use crate::link::header::{AnyAddress, ControlField, Header};
+use crate::link::parser::{FramePayload, Parser};
+use crate::link::{Function, LinkErrorMode};
+
+/// Header of a link-layer frame
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct LinkHeader {
+    /// link-layer function code
+    pub function: Function,
+    /// true if the frame was sent by a master (DIR bit)
+    pub master: bool,
+    /// frame count bit
+    pub fcb: bool,
+    /// frame count valid bit
+    pub fcv: bool,
+    /// destination address
+    pub destination: u16,
+    /// source address
+    pub source: u16,
+}
+
+impl LinkHeader {
+    /// Construct the header of an unconfirmed user data frame, the type of frame
+    /// used to carry all application data
+    pub fn unconfirmed_user_data(master: bool, destination: u16, source: u16) -> Self {
+        Self {
+            function: Function::PriUnconfirmedUserData,
+            master,
+            fcb: false,
+            fcv: false,
+            destination,
+            source,
+        }
+    }
+
+    pub(crate) fn from_internal(header: Header) -> Self {
+        Self {
+            function: header.control.func,
+            master: header.control.master,
+            fcb: header.control.fcb,
+            fcv: header.control.fcv,
+            destination: header.destination.value(),
+            source: header.source.value(),
+        }
+    }
+
+    fn to_internal(self) -> Header {
+        Header::new(
+            ControlField {
+                func: self.function,
+                master: self.master,
+                fcb: self.fcb,
+                fcv: self.fcv,
+            },
+            AnyAddress::from(self.destination),
+            AnyAddress::from(self.source),
+        )
+    }
+}
+
+/// Link-layer frame with its user data stripped of CRCs
+///
+/// For user data frames, the first byte of the payload is the transport header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LinkFrame {
+    /// header of the frame
+    pub header: LinkHeader,
+    /// user data of the frame, empty for frames that only consist of a header
+    pub payload: Vec<u8>,
+}
+
+impl LinkFrame {
+    /// Construct a frame from a header and its user data
+    pub fn new(header: LinkHeader, payload: Vec<u8>) -> Self {
+        Self { header, payload }
+    }
+
+    /// Serialize the frame, inserting the CRC of the header and of each block of user data
+    pub fn encode(&self) -> Result<Vec<u8>, CodecError> {
+        let mut buffer = [0; constant::MAX_LINK_FRAME_LENGTH];
+        let mut cursor = WriteCursor::new(&mut buffer);
+        let header = self.header.to_internal();
+        let frame = match self.payload.split_first() {
+            None => format_header_only(header, &mut cursor),
+            Some((transport, data)) => {
+                format_data_frame(header, Payload::new(*transport, data), &mut cursor)
+            }
+        }
+        .map_err(|_| CodecError::TooLarge)?;
+        Ok(frame.frame.to_vec())
+    }
+
+    /// Format the frame just like the link-layer decoding in the log
+    pub fn format(&self, level: LinkDecodeLevel) -> String {
+        LinkDisplay::new(self.header.to_internal(), &self.payload, level).to_string()
+    }
+
+    /// Convert the frame to the [`LinkEvent`] that a [`DecodeSink`](crate::decode::DecodeSink)
+    /// receives, or `None` if the level doesn't decode frames
+    pub fn event(&self, direction: DecodeDirection, level: LinkDecodeLevel) -> Option<LinkEvent> {
+        level.header_enabled().then(|| LinkEvent {
+            direction,
+            header: self.header,
+            length: self.payload.len(),
+            payload: level.payload_enabled().then(|| self.payload.clone()),
+        })
+    }
+}
+
+/// Decodes link-layer frames from a stream of bytes
+///
+/// Bytes that do not complete a frame are retained until the next call to [`LinkDecoder::decode`],
+/// so the input can be split at arbitrary boundaries.
+pub struct LinkDecoder {
+    parser: Parser,
+    payload: FramePayload,
+    pending: Vec<u8>,
+}
+
+impl LinkDecoder {
+    /// Construct a decoder
+    ///
+    /// With [`LinkErrorMode::Discard`], malformed frames are skipped and the decoder scans for
+    /// the next 0x0564 start sequence. With [`LinkErrorMode::Close`], they are reported as errors.
+    pub fn new(mode: LinkErrorMode) -> Self {
+        Self {
+            parser: Parser::new(mode),
+            payload: FramePayload::new(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Decode all of the complete frames contained in `data` and any bytes retained from
+    /// previous calls
+    ///
+    /// When an error is returned, the decoder is reset and all buffered data is discarded.
+    pub fn decode(&mut self, data: &[u8]) -> Result<Vec<LinkFrame>, CodecError> {
+        self.pending.extend_from_slice(data);
+
+        let mut frames = Vec::new();
+        let mut cursor = ReadCursor::new(&self.pending);
+        let result = loop {
+            match self.parser.parse(&mut cursor, &mut self.payload) {
+                Ok(Some(header)) => frames.push(LinkFrame::new(
+                    LinkHeader::from_internal(header),
+                    self.payload.get().to_vec(),
+                )),
+                Ok(None) => break Ok(()),
+                Err(err) => break Err(err),
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                let consumed = self.pending.len() - cursor.remaining();
+                self.pending.drain(..consumed);
+                Ok(frames)
+            }
+            Err(err) => {
+                self.parser.reset();
+                self.pending.clear();
+                Err(match err {
+                    ParseError::BadFrame(err) => CodecError::BadLinkFrame(err.to_string()),
+                    ParseError::BadLogic(err) => CodecError::BadLinkFrame(err.to_string()),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::link::test_data::*;
+
+    #[test]
+    fn decodes_frames_split_across_calls() {
+        let mut decoder = LinkDecoder::new(LinkErrorMode::Close);
+        let (first, second) = CONFIRM_USER_DATA.bytes.split_at(13);
+
+        assert!(decoder.decode(first).unwrap().is_empty());
+        let frames = decoder.decode(second).unwrap();
+
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].header.function, Function::PriConfirmedUserData);
+        assert_eq!(frames[0].payload, CONFIRM_USER_DATA.payload);
+    }
+
+    #[test]
+    fn encoding_reproduces_the_original_bytes() {
+        for frame in [&ACK, &CONFIRM_USER_DATA] {
+            let mut decoder = LinkDecoder::new(LinkErrorMode::Close);
+            let decoded = decoder.decode(frame.bytes).unwrap();
+            assert_eq!(decoded.len(), 1);
+            assert_eq!(decoded[0].encode().unwrap(), frame.bytes);
+        }
+    }
+
+    #[test]
+    fn reports_bad_crc_in_close_mode() {
+        let mut bytes = ACK.bytes.to_vec();
+        bytes[9] ^= 0xFF;
+        let mut decoder = LinkDecoder::new(LinkErrorMode::Close);
+        assert_matches!(decoder.decode(&bytes), Err(CodecError::BadLinkFrame(_)));
+    }
+}
@@ -0,0 +1,41 @@
+mod app;
+mod builder;
+mod link;
+mod transport;
+
+pub use app::*;
+pub use builder::*;
+pub use link::*;
+pub use transport::*;
+
+use crate::app::{HeaderParseError, ObjectParseError};
+
+/// Errors that can occur when decoding or encoding messages with the codec
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CodecError {
+    /// link-layer frame is malformed, e.g. a CRC doesn't match
+    BadLinkFrame(String),
+    /// user data frame doesn't contain a transport header
+    EmptySegment,
+    /// data exceeds the maximum size of a frame, fragment, or builder
+    TooLarge,
+    /// application-layer header is malformed
+    BadHeader(HeaderParseError),
+    /// object headers are malformed
+    BadObjects(ObjectParseError),
+}
+
+impl std::fmt::Display for CodecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CodecError::BadLinkFrame(err) => write!(f, "bad link-layer frame: {err}"),
+            CodecError::EmptySegment => f.write_str("user data frame without transport header"),
+            CodecError::TooLarge => f.write_str("data exceeds the maximum size"),
+            CodecError::BadHeader(err) => write!(f, "bad application header: {err}"),
+            CodecError::BadObjects(err) => write!(f, "bad object headers: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
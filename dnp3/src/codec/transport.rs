@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+use crate::codec::{CodecError, LinkFrame, LinkHeader};
+use crate::decode::{DecodeDirection, TransportDecodeLevel, TransportEvent};
+use crate::link::constant;
+use crate::link::Function;
+
+const FIN_MASK: u8 = 0b1000_0000;
+const FIR_MASK: u8 = 0b0100_0000;
+const SEQ_MASK: u8 = 0b0011_1111;
+
+/// Transport-layer segment carried in the user data of a link-layer frame
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransportSegment {
+    /// true if this is the final segment of a fragment
+    pub fin: bool,
+    /// true if this is the first segment of a fragment
+    pub fir: bool,
+    /// sequence number in the range [0, 63]
+    pub seq: u8,
+    /// application data carried by the segment
+    pub data: Vec<u8>,
+}
+
+impl TransportSegment {
+    /// Parse a segment from the user data of a link-layer frame
+    pub fn parse(payload: &[u8]) -> Result<Self, CodecError> {
+        let (header, data) = payload.split_first().ok_or(CodecError::EmptySegment)?;
+        Ok(Self {
+            fin: header & FIN_MASK != 0,
+            fir: header & FIR_MASK != 0,
+            seq: header & SEQ_MASK,
+            data: data.to_vec(),
+        })
+    }
+
+    /// Serialize the segment to the user data of a link-layer frame
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut header = self.seq & SEQ_MASK;
+        if self.fin {
+            header |= FIN_MASK;
+        }
+        if self.fir {
+            header |= FIR_MASK;
+        }
+        let mut bytes = Vec::with_capacity(self.data.len() + 1);
+        bytes.push(header);
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+
+    /// Format the segment just like the transport-layer decoding in the log
+    pub fn format(&self, level: TransportDecodeLevel) -> String {
+        struct Display<'a>(&'a TransportSegment, TransportDecodeLevel);
+
+        impl std::fmt::Display for Display<'_> {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                let Display(segment, level) = self;
+                if level.header_enabled() {
+                    write!(
+                        f,
+                        "FIN: {} FIR: {} SEQ: {} length: {}",
+                        segment.fin,
+                        segment.fir,
+                        segment.seq,
+                        segment.data.len()
+                    )?;
+                }
+                if level.payload_enabled() {
+                    crate::util::decode::format_bytes(f, &segment.data)?;
+                }
+                Ok(())
+            }
+        }
+
+        Display(self, level).to_string()
+    }
+
+    /// Convert the segment to the [`TransportEvent`] that a
+    /// [`DecodeSink`](crate::decode::DecodeSink) receives, or `None` if the level doesn't decode
+    /// segments
+    pub fn event(
+        &self,
+        direction: DecodeDirection,
+        level: TransportDecodeLevel,
+    ) -> Option<TransportEvent> {
+        level.header_enabled().then(|| TransportEvent {
+            direction,
+            fin: self.fin,
+            fir: self.fir,
+            seq: self.seq,
+            length: self.data.len(),
+            payload: level.payload_enabled().then(|| self.data.clone()),
+        })
+    }
+}
+
+/// Application fragment reassembled from one or more transport segments
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AssembledFragment {
+    /// link-layer source address of the segments
+    pub source: u16,
+    /// link-layer destination address of the segments
+    pub destination: u16,
+    /// application data of the fragment
+    pub data: Vec<u8>,
+}
+
+struct Partial {
+    seq: u8,
+    data: Vec<u8>,
+}
+
+/// Reassembles application fragments from the transport segments of link-layer frames
+///
+/// Segments are tracked separately for each pair of source and destination addresses, so
+/// frames flowing in both directions or between several devices can be fed through a
+/// single assembler.
+pub struct FragmentAssembler {
+    max_fragment_size: usize,
+    partials: HashMap<(u16, u16), Partial>,
+}
+
+impl FragmentAssembler {
+    /// Construct an assembler that rejects fragments larger than `max_fragment_size`
+    pub fn new(max_fragment_size: usize) -> Self {
+        Self {
+            max_fragment_size,
+            partials: HashMap::new(),
+        }
+    }
+
+    /// Process a link-layer frame, returning a fragment when it carries the FIN segment
+    ///
+    /// Frames other than user data are ignored. A segment that does not follow the previously
+    /// received segment silently discards the partially assembled fragment, just like the
+    /// transport layer of a master or outstation.
+    pub fn push(&mut self, frame: &LinkFrame) -> Result<Option<AssembledFragment>, CodecError> {
+        match frame.header.function {
+            Function::PriConfirmedUserData | Function::PriUnconfirmedUserData => {}
+            _ => return Ok(None),
+        }
+
+        let segment = TransportSegment::parse(&frame.payload)?;
+        let key = (frame.header.source, frame.header.destination);
+
+        let mut partial = match self.partials.remove(&key) {
+            _ if segment.fir => Partial {
+                seq: segment.seq,
+                data: Vec::new(),
+            },
+            Some(partial) if segment.seq == (partial.seq + 1) & SEQ_MASK => Partial {
+                seq: segment.seq,
+                data: partial.data,
+            },
+            _ => return Ok(None),
+        };
+
+        if partial.data.len() + segment.data.len() > self.max_fragment_size {
+            return Err(CodecError::TooLarge);
+        }
+        partial.data.extend_from_slice(&segment.data);
+
+        if segment.fin {
+            return Ok(Some(AssembledFragment {
+                source: frame.header.source,
+                destination: frame.header.destination,
+                data: partial.data,
+            }));
+        }
+
+        self.partials.insert(key, partial);
+        Ok(None)
+    }
+}
+
+/// Split an application fragment into transport segments and serialize them as link-layer frames
+///
+/// Every frame uses the provided header. `seq` is the transport sequence number of the first
+/// segment.
+pub fn encode_fragment(
+    fragment: &[u8],
+    header: LinkHeader,
+    seq: u8,
+) -> Result<Vec<u8>, CodecError> {
+    let mut bytes = Vec::new();
+    let mut chunks: Vec<&[u8]> = fragment.chunks(constant::MAX_APP_BYTES_PER_FRAME).collect();
+    if chunks.is_empty() {
+        chunks.push(&[]);
+    }
+
+    let last = chunks.len() - 1;
+    for (count, chunk) in chunks.into_iter().enumerate() {
+        let segment = TransportSegment {
+            fin: count == last,
+            fir: count == 0,
+            seq: seq.wrapping_add(count as u8) & SEQ_MASK,
+            data: chunk.to_vec(),
+        };
+        bytes.extend(LinkFrame::new(header, segment.to_bytes()).encode()?);
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::codec::LinkDecoder;
+    use crate::link::LinkErrorMode;
+
+    #[test]
+    fn reassembles_fragment_split_across_frames() {
+        let fragment: Vec<u8> = (0..600).map(|x| x as u8).collect();
+        let header = LinkHeader::unconfirmed_user_data(false, 1, 1024);
+        let bytes = encode_fragment(&fragment, header, 62).unwrap();
+
+        let frames = LinkDecoder::new(LinkErrorMode::Close)
+            .decode(&bytes)
+            .unwrap();
+        assert_eq!(frames.len(), 3);
+
+        let mut assembler = FragmentAssembler::new(2048);
+        assert_eq!(assembler.push(&frames[0]).unwrap(), None);
+        assert_eq!(assembler.push(&frames[1]).unwrap(), None);
+        assert_eq!(
+            assembler.push(&frames[2]).unwrap(),
+            Some(AssembledFragment {
+                source: 1024,
+                destination: 1,
+                data: fragment,
+            })
+        );
+    }
+
+    #[test]
+    fn discards_fragment_with_missing_segment() {
+        let fragment = [0; 300];
+        let header = LinkHeader::unconfirmed_user_data(false, 1, 1024);
+        let bytes = encode_fragment(&fragment, header, 0).unwrap();
+        let frames = LinkDecoder::new(LinkErrorMode::Close)
+            .decode(&bytes)
+            .unwrap();
+
+        let mut assembler = FragmentAssembler::new(2048);
+        assert_eq!(assembler.push(&frames[1]).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_fragment_larger_than_maximum() {
+        let header = LinkHeader::unconfirmed_user_data(false, 1, 1024);
+        let bytes = encode_fragment(&[0; 300], header, 0).unwrap();
+        let frames = LinkDecoder::new(LinkErrorMode::Close)
+            .decode(&bytes)
+            .unwrap();
+
+        let mut assembler = FragmentAssembler::new(249);
+        assert_eq!(assembler.push(&frames[0]).unwrap(), None);
+        assert_eq!(assembler.push(&frames[1]), Err(CodecError::TooLarge));
+    }
+}
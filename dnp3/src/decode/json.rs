@@ -0,0 +1,399 @@
+use std::fmt::Debug;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender, TrySendError};
+
+use serde_json::{json, Map, Value};
+
+use crate::app::measurement::{Flags, Time};
+use crate::codec::{HeaderRange, ObjectHeader, ObjectValues};
+use crate::decode::{ApplicationEvent, DecodeDirection, DecodeSink, LinkEvent, TransportEvent};
+
+/// number of events waiting to be written before further events are dropped
+const QUEUE_SIZE: usize = 1024;
+
+/// [`DecodeSink`] that serializes every event to a single line of JSON
+///
+/// Events are either written to an [`std::io::Write`] as newline-delimited JSON, or logged via
+/// `tracing` at the INFO level with the `dnp3::decode` target so that they flow through the
+/// same log pipeline as the rest of the application.
+pub struct JsonDecodeSink {
+    output: Output,
+}
+
+enum Output {
+    Tracing,
+    Writer {
+        events: SyncSender<Value>,
+        /// true while events are being dropped because the queue is full
+        dropping: AtomicBool,
+    },
+}
+
+impl JsonDecodeSink {
+    /// Create a sink that logs each event as JSON via `tracing`
+    pub fn tracing() -> Self {
+        Self {
+            output: Output::Tracing,
+        }
+    }
+
+    /// Create a sink that writes each event as a line of JSON to a writer
+    ///
+    /// Events are written and flushed on a dedicated thread so that a slow writer never stalls
+    /// the channel. If the thread falls too far behind, events are dropped and a warning is
+    /// logged. Write errors are logged and stop the output. The thread exits once the sink is
+    /// dropped and the queued events are written.
+    pub fn writer<W>(writer: W) -> Self
+    where
+        W: Write + Send + 'static,
+    {
+        let (tx, rx) = sync_channel(QUEUE_SIZE);
+        std::thread::spawn(move || write_events(writer, rx));
+        Self {
+            output: Output::Writer {
+                events: tx,
+                dropping: AtomicBool::new(false),
+            },
+        }
+    }
+
+    fn emit(&self, value: Value) {
+        match &self.output {
+            Output::Tracing => tracing::info!(target: "dnp3::decode", "{value}"),
+            Output::Writer { events, dropping } => match events.try_send(value) {
+                Ok(()) => dropping.store(false, Ordering::Relaxed),
+                Err(TrySendError::Full(_)) => {
+                    if !dropping.swap(true, Ordering::Relaxed) {
+                        tracing::warn!("decode output is not keeping up, dropping events");
+                    }
+                }
+                // the writer stopped due to an error which was already logged
+                Err(TrySendError::Disconnected(_)) => {}
+            },
+        }
+    }
+}
+
+fn write_events<W: Write>(mut writer: W, events: Receiver<Value>) {
+    for value in events {
+        let result = serde_json::to_writer(&mut writer, &value)
+            .map_err(std::io::Error::from)
+            .and_then(|_| writer.write_all(b"\n"))
+            .and_then(|_| writer.flush());
+        if let Err(err) = result {
+            tracing::warn!("stopping decode output due to write error: {err}");
+            return;
+        }
+    }
+}
+
+impl DecodeSink for JsonDecodeSink {
+    fn on_link(&self, event: &LinkEvent) {
+        self.emit(event.to_json())
+    }
+
+    fn on_transport(&self, event: &TransportEvent) {
+        self.emit(event.to_json())
+    }
+
+    fn on_application(&self, event: &ApplicationEvent) {
+        self.emit(event.to_json())
+    }
+}
+
+impl DecodeDirection {
+    fn to_json(self) -> Value {
+        match self {
+            Self::Rx => "rx".into(),
+            Self::Tx => "tx".into(),
+        }
+    }
+}
+
+impl LinkEvent {
+    /// Convert the event to a JSON object with `layer` set to `"link"`
+    pub fn to_json(&self) -> Value {
+        let mut value = json!({
+            "layer": "link",
+            "direction": self.direction.to_json(),
+            "function": format!("{:?}", self.header.function),
+            "master": self.header.master,
+            "fcb": self.header.fcb,
+            "fcv": self.header.fcv,
+            "destination": self.header.destination,
+            "source": self.header.source,
+            "length": self.length,
+        });
+        if let Some(payload) = &self.payload {
+            value["payload"] = hex(payload);
+        }
+        value
+    }
+}
+
+impl TransportEvent {
+    /// Convert the event to a JSON object with `layer` set to `"transport"`
+    pub fn to_json(&self) -> Value {
+        let mut value = json!({
+            "layer": "transport",
+            "direction": self.direction.to_json(),
+            "fin": self.fin,
+            "fir": self.fir,
+            "seq": self.seq,
+            "length": self.length,
+        });
+        if let Some(payload) = &self.payload {
+            value["payload"] = hex(payload);
+        }
+        value
+    }
+}
+
+impl ApplicationEvent {
+    /// Convert the event to a JSON object with `layer` set to `"application"`
+    pub fn to_json(&self) -> Value {
+        let mut value = json!({
+            "layer": "application",
+            "direction": self.direction.to_json(),
+            "control": {
+                "fir": self.control.fir,
+                "fin": self.control.fin,
+                "con": self.control.con,
+                "uns": self.control.uns,
+                "seq": self.control.seq.value(),
+            },
+            "function": format!("{:?}", self.function),
+            "length": self.length,
+        });
+        if let Some(iin) = self.iin {
+            value["iin"] = json!({
+                "iin1": iin.iin1.value,
+                "iin2": iin.iin2.value,
+            });
+        }
+        if let Some(headers) = &self.headers {
+            value["headers"] = headers.iter().map(header).collect();
+        }
+        if let Some(error) = &self.error {
+            value["error"] = error.as_str().into();
+        }
+        value
+    }
+}
+
+fn header(header: &ObjectHeader) -> Value {
+    let mut value = json!({
+        "variation": header.variation.to_string(),
+        "qualifier": format!("0x{:02X}", header.qualifier.as_u8()),
+    });
+    match header.range {
+        HeaderRange::AllObjects => {}
+        HeaderRange::StartStop(start, stop) => {
+            value["start"] = start.into();
+            value["stop"] = stop.into();
+        }
+        HeaderRange::Count(count) => {
+            value["count"] = count.into();
+        }
+    }
+    if let Some(values) = values(&header.values) {
+        value["values"] = values;
+    }
+    value
+}
+
+fn values(values: &ObjectValues) -> Option<Value> {
+    let values = match values {
+        ObjectValues::None => return None,
+        ObjectValues::BinaryInput(x) => points(x, |v| (v.value.into(), v.flags, v.time)),
+        ObjectValues::DoubleBitBinaryInput(x) => {
+            points(x, |v| (format!("{:?}", v.value).into(), v.flags, v.time))
+        }
+        ObjectValues::BinaryOutputStatus(x) => points(x, |v| (v.value.into(), v.flags, v.time)),
+        ObjectValues::Counter(x) => points(x, |v| (v.value.into(), v.flags, v.time)),
+        ObjectValues::FrozenCounter(x) => points(x, |v| (v.value.into(), v.flags, v.time)),
+        ObjectValues::AnalogInput(x) => points(x, |v| (v.value.into(), v.flags, v.time)),
+        ObjectValues::FrozenAnalogInput(x) => points(x, |v| (v.value.into(), v.flags, v.time)),
+        ObjectValues::AnalogOutputStatus(x) => points(x, |v| (v.value.into(), v.flags, v.time)),
+        ObjectValues::UnsignedInteger(x) => indexed(x, |v| v.value.into()),
+        ObjectValues::OctetString(x) => indexed(x, |v| hex(v)),
+        ObjectValues::AnalogInputDeadBand(x) => indexed(x, debug),
+        ObjectValues::AnalogOutputCommandEvent(x) => indexed(x, debug),
+        ObjectValues::BinaryOutputCommandEvent(x) => indexed(x, debug),
+        ObjectValues::Crob(x) => indexed(x, debug),
+        ObjectValues::AnalogOutputI32(x) => indexed(x, debug),
+        ObjectValues::AnalogOutputI16(x) => indexed(x, debug),
+        ObjectValues::AnalogOutputF32(x) => indexed(x, debug),
+        ObjectValues::AnalogOutputF64(x) => indexed(x, debug),
+        ObjectValues::Attribute(x) => debug(x),
+        ObjectValues::AbsoluteTime(x) => x.raw_value().into(),
+    };
+    Some(values)
+}
+
+fn points<T, F>(values: &[(T, u16)], convert: F) -> Value
+where
+    F: Fn(&T) -> (Value, Flags, Option<Time>),
+{
+    values
+        .iter()
+        .map(|(x, index)| {
+            let (value, flags, time) = convert(x);
+            let mut map = Map::new();
+            map.insert("index".into(), (*index).into());
+            map.insert("value".into(), value);
+            map.insert("flags".into(), flags.value.into());
+            match time {
+                Some(Time::Synchronized(x)) => {
+                    map.insert("time".into(), x.raw_value().into());
+                    map.insert("synchronized".into(), true.into());
+                }
+                Some(Time::Unsynchronized(x)) => {
+                    map.insert("time".into(), x.raw_value().into());
+                    map.insert("synchronized".into(), false.into());
+                }
+                None => {}
+            }
+            Value::Object(map)
+        })
+        .collect()
+}
+
+fn indexed<T, F>(values: &[(T, u16)], convert: F) -> Value
+where
+    F: Fn(&T) -> Value,
+{
+    values
+        .iter()
+        .map(|(x, index)| json!({ "index": index, "value": convert(x) }))
+        .collect()
+}
+
+fn debug<T: Debug>(value: &T) -> Value {
+    format!("{value:?}").into()
+}
+
+fn hex(data: &[u8]) -> Value {
+    data.iter()
+        .map(|x| format!("{x:02x}"))
+        .collect::<String>()
+        .into()
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+
+    use super::*;
+    use crate::app::measurement::AnalogInput;
+    use crate::app::{
+        ControlField, FunctionCode, Iin, QualifierCode, Sequence, Timestamp, Variation,
+    };
+
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn application_event_is_written_as_a_line_of_json() {
+        let buffer = SharedBuffer::default();
+        let sink = JsonDecodeSink::writer(buffer.clone());
+
+        let event = ApplicationEvent {
+            direction: DecodeDirection::Rx,
+            control: ControlField {
+                fir: true,
+                fin: true,
+                con: false,
+                uns: false,
+                seq: Sequence::new(3),
+            },
+            function: FunctionCode::Response,
+            iin: Some(Iin::default()),
+            length: 14,
+            headers: Some(vec![ObjectHeader {
+                variation: Variation::Group30Var1,
+                qualifier: QualifierCode::Range8,
+                range: HeaderRange::StartStop(1, 1),
+                values: ObjectValues::AnalogInput(vec![(
+                    AnalogInput::new(5.0, Flags::ONLINE, Time::Synchronized(Timestamp::new(42))),
+                    1,
+                )]),
+            }]),
+            error: None,
+        };
+        sink.on_application(&event);
+
+        // the writer thread releases the buffer once the sink is dropped and the event is written
+        drop(sink);
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Arc::strong_count(&buffer.0) > 1 {
+            assert!(Instant::now() < deadline);
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert!(output.ends_with('\n'));
+        let value: Value = serde_json::from_str(output.trim_end()).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "layer": "application",
+                "direction": "rx",
+                "control": { "fir": true, "fin": true, "con": false, "uns": false, "seq": 3 },
+                "function": "Response",
+                "iin": { "iin1": 0, "iin2": 0 },
+                "length": 14,
+                "headers": [{
+                    "variation": "g30v1",
+                    "qualifier": "0x00",
+                    "start": 1,
+                    "stop": 1,
+                    "values": [{
+                        "index": 1,
+                        "value": 5.0,
+                        "flags": 1,
+                        "time": 42,
+                        "synchronized": true
+                    }]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn transport_payload_is_hex_encoded() {
+        let event = TransportEvent {
+            direction: DecodeDirection::Tx,
+            fin: true,
+            fir: false,
+            seq: 7,
+            length: 2,
+            payload: Some(vec![0xCA, 0xFE]),
+        };
+        assert_eq!(
+            event.to_json(),
+            json!({
+                "layer": "transport",
+                "direction": "tx",
+                "fin": true,
+                "fir": false,
+                "seq": 7,
+                "length": 2,
+                "payload": "cafe"
+            })
+        );
+    }
+}
@@ -1,3 +1,11 @@
+#[cfg(feature = "decode-json")]
+pub use json::*;
+pub use sink::*;
+
+#[cfg(feature = "decode-json")]
+mod json;
+mod sink;
+
 /// Controls the decoding of transmitted and received data at the application, transport, and link layer
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
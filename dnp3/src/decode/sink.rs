@@ -0,0 +1,283 @@
+use std::sync::{Arc, Mutex};
+
+use crate::app::parse::options::ParseOptions;
+use crate::app::parse::parser::ParsedFragment;
+use crate::app::{ControlField, FunctionCode, Iin};
+use crate::codec::{HeaderConverter, LinkHeader, ObjectHeader};
+use crate::decode::{AppDecodeLevel, LinkDecodeLevel, TransportDecodeLevel};
+
+/// Direction in which decoded data travelled
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DecodeDirection {
+    /// Data was received from the remote device
+    Rx,
+    /// Data was transmitted to the remote device
+    Tx,
+}
+
+impl DecodeDirection {
+    /// Name of the direction as it appears in the text log, i.e. "RX" or "TX"
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Rx => "RX",
+            Self::Tx => "TX",
+        }
+    }
+}
+
+/// Link-layer frame decoded according to the [`LinkDecodeLevel`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LinkEvent {
+    /// direction of the frame
+    pub direction: DecodeDirection,
+    /// link-layer header
+    pub header: LinkHeader,
+    /// length of the user data in the frame, excluding CRCs
+    pub length: usize,
+    /// user data of the frame, present when decoding at [`LinkDecodeLevel::Payload`]
+    pub payload: Option<Vec<u8>>,
+}
+
+/// Transport-layer segment decoded according to the [`TransportDecodeLevel`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TransportEvent {
+    /// direction of the segment
+    pub direction: DecodeDirection,
+    /// final segment of the fragment
+    pub fin: bool,
+    /// first segment of the fragment
+    pub fir: bool,
+    /// sequence number
+    pub seq: u8,
+    /// length of the segment payload
+    pub length: usize,
+    /// payload of the segment, present when decoding at [`TransportDecodeLevel::Payload`]
+    pub payload: Option<Vec<u8>>,
+}
+
+/// Application-layer fragment decoded according to the [`AppDecodeLevel`]
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct ApplicationEvent {
+    /// direction of the fragment
+    pub direction: DecodeDirection,
+    /// application control field
+    pub control: ControlField,
+    /// function code
+    pub function: FunctionCode,
+    /// internal indications, only present in responses
+    pub iin: Option<Iin>,
+    /// length of the object headers and their values
+    pub length: usize,
+    /// object headers, present when decoding at [`AppDecodeLevel::ObjectHeaders`] or above
+    ///
+    /// Values are only converted when decoding at [`AppDecodeLevel::ObjectValues`]. If the
+    /// objects could not be parsed, this contains the headers that precede the error.
+    pub headers: Option<Vec<ObjectHeader>>,
+    /// description of the error if the object headers could not be parsed
+    pub error: Option<String>,
+}
+
+/// Receives typed protocol decode events as an alternative to parsing the text log
+///
+/// Events are produced at the same points and with the same level of detail as the text
+/// decoding controlled by [`DecodeLevel`](crate::decode::DecodeLevel), which continues
+/// to be logged in addition to the events.
+///
+/// The methods are called from the task that runs the channel and should return quickly.
+pub trait DecodeSink: Send + Sync {
+    /// Called when a link-layer frame is transmitted or received
+    #[allow(unused_variables)]
+    fn on_link(&self, event: &LinkEvent) {}
+
+    /// Called when a transport-layer segment is transmitted or received
+    #[allow(unused_variables)]
+    fn on_transport(&self, event: &TransportEvent) {}
+
+    /// Called when an application-layer fragment is transmitted or received
+    #[allow(unused_variables)]
+    fn on_application(&self, event: &ApplicationEvent) {}
+}
+
+/// Shared reference to the decode sink of a channel, if one is set
+///
+/// Like the capture, clones are held by every layer so the sink can be changed at runtime.
+#[derive(Clone, Default)]
+pub(crate) struct DecodeSinkSlot {
+    inner: Arc<Mutex<Option<Arc<dyn DecodeSink>>>>,
+}
+
+impl DecodeSinkSlot {
+    pub(crate) fn set(&self, sink: Option<Arc<dyn DecodeSink>>) {
+        *self.inner.lock().unwrap() = sink;
+    }
+
+    fn get(&self) -> Option<Arc<dyn DecodeSink>> {
+        self.inner.lock().unwrap().clone()
+    }
+
+    pub(crate) fn link(
+        &self,
+        direction: DecodeDirection,
+        header: crate::link::header::Header,
+        payload: &[u8],
+        level: LinkDecodeLevel,
+    ) {
+        if !level.header_enabled() {
+            return;
+        }
+        if let Some(sink) = self.get() {
+            sink.on_link(&LinkEvent {
+                direction,
+                header: LinkHeader::from_internal(header),
+                length: payload.len(),
+                payload: level.payload_enabled().then(|| payload.to_vec()),
+            });
+        }
+    }
+
+    pub(crate) fn transport(
+        &self,
+        direction: DecodeDirection,
+        header: u8,
+        payload: &[u8],
+        level: TransportDecodeLevel,
+    ) {
+        if !level.header_enabled() {
+            return;
+        }
+        if let Some(sink) = self.get() {
+            sink.on_transport(&TransportEvent {
+                direction,
+                fin: header & 0x80 != 0,
+                fir: header & 0x40 != 0,
+                seq: header & 0x3F,
+                length: payload.len(),
+                payload: level.payload_enabled().then(|| payload.to_vec()),
+            });
+        }
+    }
+
+    pub(crate) fn application(
+        &self,
+        direction: DecodeDirection,
+        fragment: &[u8],
+        level: AppDecodeLevel,
+    ) {
+        if !level.header() {
+            return;
+        }
+        let sink = match self.get() {
+            Some(x) => x,
+            None => return,
+        };
+        // the text log already reports fragments with bad headers
+        let parsed = match ParsedFragment::parse(ParseOptions::get_static(), fragment) {
+            Ok(x) => x,
+            Err(_) => return,
+        };
+        sink.on_application(&ApplicationEvent::new(direction, &parsed, level));
+    }
+}
+
+impl ApplicationEvent {
+    pub(crate) fn new(
+        direction: DecodeDirection,
+        parsed: &ParsedFragment,
+        level: AppDecodeLevel,
+    ) -> Self {
+        let headers = level.object_headers().then(|| {
+            let mut converter = HeaderConverter::default();
+            parsed
+                .headers_until_error()
+                .map(|x| converter.convert(&x, level.object_values()))
+                .collect()
+        });
+
+        Self {
+            direction,
+            control: parsed.control,
+            function: parsed.function,
+            iin: parsed.iin,
+            length: parsed.raw_objects.len(),
+            headers,
+            error: parsed.objects.err().map(|x| x.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::Variation;
+    use crate::codec::{HeaderRange, ObjectValues};
+
+    #[derive(Default)]
+    struct Events(Mutex<Vec<ApplicationEvent>>);
+
+    impl DecodeSink for Events {
+        fn on_application(&self, event: &ApplicationEvent) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    // response with g30v1 values for indices 1 and 2
+    const RESPONSE: &[u8] = &[
+        0xC0, 0x81, 0x00, 0x00, 0x1E, 0x01, 0x00, 0x01, 0x02, 0x01, 0x05, 0x00, 0x00, 0x00, 0x01,
+        0x06, 0x00, 0x00, 0x00,
+    ];
+
+    fn decode(level: AppDecodeLevel) -> Vec<ApplicationEvent> {
+        let events = Arc::new(Events::default());
+        let slot = DecodeSinkSlot::default();
+        slot.set(Some(events.clone()));
+        slot.application(DecodeDirection::Rx, RESPONSE, level);
+        let x = events.0.lock().unwrap().clone();
+        x
+    }
+
+    #[test]
+    fn application_events_follow_decode_level() {
+        assert!(decode(AppDecodeLevel::Nothing).is_empty());
+
+        let event = &decode(AppDecodeLevel::Header)[0];
+        assert_eq!(event.function, FunctionCode::Response);
+        assert!(event.iin.is_some());
+        assert!(event.headers.is_none());
+
+        let event = &decode(AppDecodeLevel::ObjectHeaders)[0];
+        let headers = event.headers.as_ref().unwrap();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[0].variation, Variation::Group30Var1);
+        assert_eq!(headers[0].range, HeaderRange::StartStop(1, 2));
+        assert_eq!(headers[0].values, ObjectValues::None);
+
+        let event = &decode(AppDecodeLevel::ObjectValues)[0];
+        match &event.headers.as_ref().unwrap()[0].values {
+            ObjectValues::AnalogInput(values) => {
+                let values: Vec<(f64, u16)> = values.iter().map(|(x, i)| (x.value, *i)).collect();
+                assert_eq!(values, vec![(5.0, 1), (6.0, 2)]);
+            }
+            x => panic!("unexpected values: {x:?}"),
+        }
+    }
+
+    #[test]
+    fn application_event_reports_headers_before_error() {
+        let events = Arc::new(Events::default());
+        let slot = DecodeSinkSlot::default();
+        slot.set(Some(events.clone()));
+        // READ of class 1, followed by a truncated header
+        let request = [0xC0, 0x01, 0x3C, 0x02, 0x06, 0x3C];
+        slot.application(DecodeDirection::Tx, &request, AppDecodeLevel::ObjectHeaders);
+
+        let events = events.0.lock().unwrap();
+        let event = &events[0];
+        assert_eq!(event.direction, DecodeDirection::Tx);
+        assert_eq!(event.function, FunctionCode::Read);
+        assert_eq!(event.headers.as_ref().unwrap().len(), 1);
+        assert!(event.error.is_some());
+    }
+}
@@ -10,17 +10,31 @@ pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// Application layer types shared by both the master and outstation APIs
 pub mod app;
+/// Recording of live traffic to pcapng files for analysis in Wireshark
+pub mod capture;
+/// Offline decoding and encoding of link frames, transport segments, and application messages
+///
+/// The codec is independent of any I/O and can be used to analyze captured traffic or to
+/// build messages for testing other implementations.
+pub mod codec;
 /// Types used to control decoding in the log
 pub mod decode;
 /// Types specific to the link-layer
 pub mod link;
 /// Types and traits specific to masters
 pub mod master;
+/// Export of communication statistics in the OpenMetrics text format
+#[cfg(feature = "metrics")]
+pub mod metrics;
 /// Types and traits specific to outstations
 pub mod outstation;
+/// Entry points and types for redundant (dual-path) master channels
+pub mod redundant;
 /// Entry points and types for serial
 #[cfg(feature = "serial")]
 pub mod serial;
+/// Communication statistics maintained by masters and outstations
+pub mod statistics;
 /// Entry points and types for TCP
 pub mod tcp;
 /// Entry points and types for UDP
@@ -34,8 +34,8 @@ impl<'a> Payload<'a> {
 // A view that we can use for tx logging
 pub(crate) struct FrameData<'a> {
     pub(crate) frame: &'a [u8],
-    header: Header,
-    payload_only: &'a [u8],
+    pub(crate) header: Header,
+    pub(crate) payload_only: &'a [u8],
 }
 
 impl FrameData<'_> {
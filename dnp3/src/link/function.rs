@@ -1,14 +1,25 @@
-#[derive(Copy, Clone, PartialEq, Debug)]
-pub(crate) enum Function {
+/// Link-layer function code, including the PRM bit
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Function {
+    /// primary frame - reset the link states
     PriResetLinkStates,
+    /// primary frame - test the link states
     PriTestLinkStates,
+    /// primary frame - user data that requires a link-layer confirmation
     PriConfirmedUserData,
+    /// primary frame - user data without link-layer confirmation
     PriUnconfirmedUserData,
+    /// primary frame - request the status of the link
     PriRequestLinkStatus,
+    /// secondary frame - positive acknowledgement
     SecAck,
+    /// secondary frame - negative acknowledgement
     SecNack,
+    /// secondary frame - response to a link status request
     SecLinkStatus,
+    /// secondary frame - the requested function is not supported
     SecNotSupported,
+    /// unknown function code
     Unknown(u8),
 }
 
@@ -1,5 +1,5 @@
 use crate::app::EndpointType;
-use crate::decode::DecodeLevel;
+use crate::decode::{DecodeDirection, DecodeLevel};
 use crate::link::display::LinkDisplay;
 use crate::link::error::LinkError;
 use crate::link::format::format_header_fixed_size;
@@ -11,6 +11,7 @@ use crate::link::parser::FramePayload;
 use crate::link::reader::LinkModes;
 use crate::link::EndpointAddress;
 use crate::outstation::Feature;
+use crate::util::monitor::ChannelMonitor;
 use crate::util::phys::{PhysAddr, PhysLayer};
 
 enum SecondaryState {
@@ -60,6 +61,10 @@ impl Layer {
         self.reader.seed(seed_data)
     }
 
+    pub(crate) fn set_local_address(&mut self, address: EndpointAddress) {
+        self.local_address = address;
+    }
+
     pub(crate) fn reset(&mut self) {
         self.secondary_state = SecondaryState::NotReset;
         self.reader.reset();
@@ -69,10 +74,11 @@ impl Layer {
         &mut self,
         io: &mut PhysLayer,
         level: DecodeLevel,
+        monitor: &ChannelMonitor,
         payload: &mut FramePayload,
     ) -> Result<FrameInfo, LinkError> {
         loop {
-            if let Some(address) = self.read_one(io, level, payload).await? {
+            if let Some(address) = self.read_one(io, level, monitor, payload).await? {
                 return Ok(address);
             }
         }
@@ -95,17 +101,22 @@ impl Layer {
         &mut self,
         io: &mut PhysLayer,
         level: DecodeLevel,
+        monitor: &ChannelMonitor,
         payload: &mut FramePayload,
     ) -> Result<Option<FrameInfo>, LinkError> {
-        let (header, addr) = self.reader.read_frame(io, payload, level).await?;
+        let (header, addr) = self.reader.read_frame(io, payload, level, monitor).await?;
         let (info, reply) = self.process_header(header, addr);
         if let Some(reply) = reply {
             let header = self.get_header(reply);
             if level.link.enabled() {
                 tracing::info!("LINK TX - {}", LinkDisplay::new(header, &[], level.link));
+                monitor
+                    .decode
+                    .link(DecodeDirection::Tx, header, &[], level.link);
             }
-            io.write(self.format_reply(header), addr, level.physical)
-                .await?
+            io.write(self.format_reply(header), addr, level.physical, monitor)
+                .await?;
+            monitor.counters.on_frame_tx();
         }
         Ok(info)
     }
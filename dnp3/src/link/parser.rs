@@ -50,9 +50,19 @@ impl Default for FramePayload {
     }
 }
 
+/// Number of framing errors detected by the parser
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ParseErrorCounts {
+    pub(crate) crc: u64,
+    pub(crate) other: u64,
+}
+
 pub(crate) struct Parser {
     mode: LinkErrorMode,
     state: ParseState,
+    errors: ParseErrorCounts,
+    /// true while discarding bytes that don't contain a valid frame
+    discarding: bool,
 }
 
 impl From<ReadError> for ParseError {
@@ -78,6 +88,24 @@ impl Parser {
         Parser {
             mode,
             state: ParseState::FindSync1,
+            errors: ParseErrorCounts::default(),
+            discarding: false,
+        }
+    }
+
+    /// Take the errors counted since the last call
+    pub(crate) fn take_errors(&mut self) -> ParseErrorCounts {
+        std::mem::take(&mut self.errors)
+    }
+
+    fn count_error(&mut self, err: &ParseError) {
+        match err {
+            ParseError::BadFrame(FrameError::BadHeaderCrc | FrameError::BadBodyCrc) => {
+                self.errors.crc += 1
+            }
+            // every byte of a run of garbage fails to parse, count the run once
+            ParseError::BadFrame(_) if !self.discarding => self.errors.other += 1,
+            _ => {}
         }
     }
 
@@ -92,14 +120,25 @@ impl Parser {
     ) -> Result<Option<Header>, ParseError> {
         loop {
             if self.mode == LinkErrorMode::Close {
-                return self.parse_impl(cursor, payload);
+                let res = self.parse_impl(cursor, payload);
+                if let Err(err) = &res {
+                    self.count_error(err);
+                }
+                return res;
             }
 
             let res = cursor.transaction(|cur| self.parse_impl(cur, payload));
 
             match res {
-                Ok(x) => return Ok(x),
-                Err(_) => {
+                Ok(x) => {
+                    if x.is_some() {
+                        self.discarding = false;
+                    }
+                    return Ok(x);
+                }
+                Err(err) => {
+                    self.count_error(&err);
+                    self.discarding = true;
                     let _ = cursor.read_u8(); // advance one byte
                     self.reset();
                     // goto next iteration
@@ -363,4 +402,29 @@ mod test {
             Ok(Some(RESET_LINK.header)),
         );
     }
+
+    #[test]
+    fn counts_crc_errors_separately_from_runs_of_garbage() {
+        let mut parser = Parser::new(LinkErrorMode::Discard);
+        let mut data = vec![0x06, 0x07, 0x08];
+        // reset link with a corrupted header CRC
+        data.extend_from_slice(&[0x05, 0x64, 0x05, 0xC0, 0x01, 0x00, 0x00, 0x04, 0xE9, 0x20]);
+        data.extend_from_slice(RESET_LINK.bytes);
+        data.extend_from_slice(&[0xFF, 0xFF]);
+        data.extend_from_slice(RESET_LINK.bytes);
+
+        let mut cursor = ReadCursor::new(&data);
+        let mut payload = FramePayload::new();
+
+        assert_eq!(
+            parser.parse(&mut cursor, &mut payload),
+            Ok(Some(RESET_LINK.header)),
+        );
+        assert_eq!(parser.take_errors(), ParseErrorCounts { crc: 1, other: 1 });
+        assert_eq!(
+            parser.parse(&mut cursor, &mut payload),
+            Ok(Some(RESET_LINK.header)),
+        );
+        assert_eq!(parser.take_errors(), ParseErrorCounts { crc: 0, other: 1 });
+    }
 }
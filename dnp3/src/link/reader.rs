@@ -1,9 +1,10 @@
-use crate::decode::DecodeLevel;
+use crate::decode::{DecodeDirection, DecodeLevel};
 use crate::link::display::LinkDisplay;
 use crate::link::error::LinkError;
 use crate::link::header::Header;
 use crate::link::parser::{FramePayload, Parser};
 use crate::link::{LinkErrorMode, LinkReadMode};
+use crate::util::monitor::ChannelMonitor;
 use crate::util::phys::{PhysAddr, PhysLayer};
 
 use crate::link;
@@ -161,6 +162,7 @@ impl Reader {
         io: &mut PhysLayer,
         payload: &mut FramePayload,
         level: DecodeLevel,
+        monitor: &ChannelMonitor,
     ) -> Result<(Header, PhysAddr), LinkError> {
         let mut addr = PhysAddr::None;
 
@@ -170,9 +172,9 @@ impl Reader {
 
             if length == 0 {
                 self.buffer.reset();
-                addr = self.read_more_data(io, level).await?;
+                addr = self.read_more_data(io, level, monitor).await?;
             } else {
-                match self.parse_buffer(payload, level)? {
+                match self.parse_buffer(payload, level, monitor)? {
                     None => {
                         if self.read_mode == LinkReadMode::Datagram {
                             // We didn't read a frame this iteration even though there was data in the buffer.
@@ -181,7 +183,7 @@ impl Reader {
                             self.buffer.reset();
                             self.parser.reset();
                         }
-                        addr = self.read_more_data(io, level).await?;
+                        addr = self.read_more_data(io, level, monitor).await?;
                     }
                     Some(header) => return Ok((header, addr)),
                 }
@@ -193,6 +195,7 @@ impl Reader {
         &mut self,
         io: &mut PhysLayer,
         level: DecodeLevel,
+        monitor: &ChannelMonitor,
     ) -> Result<PhysAddr, LinkError> {
         // if we've consumed all the data, we need to shift contents
         if self.buffer.is_full() {
@@ -200,7 +203,9 @@ impl Reader {
         }
 
         // now we can read more data
-        let (count, addr) = io.read(self.buffer.writable(), level.physical).await?;
+        let (count, addr) = io
+            .read(self.buffer.writable(), level.physical, monitor)
+            .await?;
 
         self.buffer.advance_write(count);
         Ok(addr)
@@ -211,20 +216,28 @@ impl Reader {
         &mut self,
         payload: &mut FramePayload,
         level: DecodeLevel,
+        monitor: &ChannelMonitor,
     ) -> Result<Option<Header>, LinkError> {
         // the readable portion of the buffer
         let mut cursor = ReadCursor::new(self.buffer.readable());
-        let result = self.parser.parse(&mut cursor, payload)?;
+        let result = self.parser.parse(&mut cursor, payload);
+        let errors = self.parser.take_errors();
+        monitor.counters.on_link_errors(errors.crc, errors.other);
+        let result = result?;
         let consumed = cursor.position();
         self.buffer.advance_read(consumed);
         match result {
             // complete frame
             Some(header) => {
+                monitor.counters.on_frame_rx();
                 if level.link.enabled() {
                     tracing::info!(
                         "LINK RX - {}",
                         LinkDisplay::new(header, payload.get(), level.link)
                     );
+                    monitor
+                        .decode
+                        .link(DecodeDirection::Rx, header, payload.get(), level.link);
                 }
                 Ok(Some(header))
             }
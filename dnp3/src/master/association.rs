@@ -4,22 +4,26 @@ use std::time::Duration;
 use xxhash_rust::xxh64::xxh64;
 
 use crate::app::parse::parser::{HeaderCollection, Response};
-use crate::app::Timestamp;
 use crate::app::{ExponentialBackOff, FunctionCode, RetryStrategy};
 use crate::app::{Iin, ResponseHeader};
 use crate::app::{Sequence, Timeout};
 use crate::link::EndpointAddress;
-use crate::master::error::{AssociationError, TaskError, TimeSyncError};
+use crate::master::error::{AssociationError, SelectError, TaskError, TimeSyncError};
 use crate::master::extract::extract_measurements;
 use crate::master::handler::AssociationHandler;
 use crate::master::messages::AssociationMsgType;
 use crate::master::poll::{PollHandle, PollMap, PollMsg};
-use crate::master::request::{Classes, EventClasses, TimeSyncProcedure};
+use crate::master::request::{
+    Classes, EventClasses, TimeSyncPolicy, TimeSyncProcedure, UnsuitableClockAction,
+};
 use crate::master::tasks::auto::AutoTask;
 use crate::master::tasks::time::TimeSyncTask;
 use crate::master::tasks::NonReadTask::TimeSync;
 use crate::master::tasks::{AppTask, AssociationTask, ReadTask, Task};
-use crate::master::{AssociationInformation, ReadHandler, ReadType, TaskType};
+use crate::master::{
+    AssociationInformation, ReadHandler, ReadType, TaskType, TimeReading, TimeSyncReport,
+};
+use crate::statistics::AssociationCounters;
 use crate::util::Smallest;
 
 use crate::master::promise::Promise;
@@ -51,6 +55,15 @@ pub struct AssociationConfig {
     /// automatic time synchronization based on NEED_TIME IIN bit
     #[cfg_attr(feature = "serialization", serde(default))]
     pub auto_time_sync: Option<TimeSyncProcedure>,
+    /// period of automatic time synchronizations performed regardless of the NEED_TIME IIN bit
+    ///
+    /// Requires `auto_time_sync` to be set. The first synchronization is performed when
+    /// communications are established.
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub auto_time_sync_period: Option<Duration>,
+    /// policy applied to time synchronizations based on the quality of the master's clock
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub time_sync_policy: TimeSyncPolicy,
     /// automatic tasks retry strategy
     #[cfg_attr(feature = "serialization", serde(default))]
     pub auto_tasks_retry_strategy: RetryStrategy,
@@ -95,6 +108,8 @@ impl AssociationConfig {
             enable_unsol_classes,
             startup_integrity_classes,
             auto_time_sync: None,
+            auto_time_sync_period: None,
+            time_sync_policy: TimeSyncPolicy::default(),
             auto_tasks_retry_strategy: RetryStrategy::default(),
             keep_alive_timeout: None,
             auto_integrity_scan_on_buffer_overflow: false,
@@ -112,6 +127,8 @@ impl AssociationConfig {
             enable_unsol_classes: EventClasses::none(),
             startup_integrity_classes: Classes::none(),
             auto_time_sync: None,
+            auto_time_sync_period: None,
+            time_sync_policy: TimeSyncPolicy::default(),
             auto_tasks_retry_strategy: RetryStrategy::default(),
             keep_alive_timeout: None,
             auto_integrity_scan_on_buffer_overflow: false,
@@ -129,6 +146,8 @@ impl Default for AssociationConfig {
             enable_unsol_classes: EventClasses::all(),
             startup_integrity_classes: Classes::all(),
             auto_time_sync: None,
+            auto_time_sync_period: None,
+            time_sync_policy: TimeSyncPolicy::default(),
             auto_tasks_retry_strategy: RetryStrategy::default(),
             keep_alive_timeout: None,
             auto_integrity_scan_on_buffer_overflow: true,
@@ -298,6 +317,22 @@ impl LastUnsolFragment {
     }
 }
 
+/// Selection made by a SELECT that has yet to be operated
+#[derive(Copy, Clone)]
+struct PendingSelection {
+    id: u64,
+    /// sequence number the OPERATE must use
+    seq: Sequence,
+    /// `None` if the select timeout is too large to be represented
+    deadline: Option<Instant>,
+}
+
+impl PendingSelection {
+    fn is_expired(&self, now: Instant) -> bool {
+        self.deadline.is_some_and(|x| now >= x)
+    }
+}
+
 /// A logical connection between a master and an outstation
 /// as defined by the DNP3 standard. A master manages requests
 /// and responses for multiple associations (i.e. multi-drop).
@@ -315,8 +350,12 @@ pub(crate) struct Association {
     config: AssociationConfig,
     polls: PollMap,
     next_link_status_deadline: Option<Instant>,
+    next_periodic_time_sync: Option<Instant>,
+    selection: Option<PendingSelection>,
+    next_selection_id: u64,
     startup_integrity_done: bool,
     events_available: EventClasses,
+    statistics: AssociationCounters,
 }
 
 impl Association {
@@ -343,8 +382,12 @@ impl Association {
             config,
             polls: PollMap::new(),
             next_link_status_deadline: config.keep_alive_timeout.map(|delay| now + delay),
+            next_periodic_time_sync: Self::periodic_time_sync(&config).map(|_| now),
+            selection: None,
+            next_selection_id: 0,
             startup_integrity_done: false,
             events_available: EventClasses::none(),
+            statistics: AssociationCounters::default(),
         }
     }
 
@@ -364,6 +407,15 @@ impl Association {
             AssociationMsgType::Poll(msg) => {
                 self.process_poll_message(msg);
             }
+            AssociationMsgType::GetStatistics(promise) => {
+                promise.complete(Ok(self.statistics.get()));
+            }
+            AssociationMsgType::CancelSelection(id) => {
+                if self.selection.map(|x| x.id) == Some(id) {
+                    tracing::info!("selection cancelled");
+                    self.selection = None;
+                }
+            }
         }
     }
 
@@ -392,13 +444,66 @@ impl Association {
         // Reset the auto tasks
         self.auto_tasks.reset();
         self.startup_integrity_done = false;
+        self.next_periodic_time_sync =
+            Self::periodic_time_sync(&self.config).map(|_| Instant::now());
+
+        // The outstation discards the selection when a new request arrives
+        self.selection = None;
 
         // Clear last unsolicited fragment
         self.last_unsol_frag = None;
     }
 
-    pub(crate) fn get_system_time(&self) -> Option<Timestamp> {
-        self.assoc_handler.get_current_time()
+    /// Record a successful SELECT, replacing any previous selection
+    pub(crate) fn begin_selection(&mut self, deadline: Option<Instant>) -> u64 {
+        self.next_selection_id = self.next_selection_id.wrapping_add(1);
+        self.selection = Some(PendingSelection {
+            id: self.next_selection_id,
+            seq: self.seq,
+            deadline,
+        });
+        self.next_selection_id
+    }
+
+    /// Consume the selection just before its OPERATE is sent
+    pub(crate) fn take_selection(&mut self, id: u64) -> Result<(), SelectError> {
+        match self.selection {
+            Some(x) if x.id == id => {
+                self.selection = None;
+                if x.is_expired(Instant::now()) {
+                    tracing::warn!("select timeout elapsed before the operate was requested");
+                    return Err(SelectError::SelectTimeout);
+                }
+                if x.seq != self.seq {
+                    return Err(SelectError::NotSelected);
+                }
+                Ok(())
+            }
+            _ => Err(SelectError::NotSelected),
+        }
+    }
+
+    /// pending selection if its select timeout hasn't elapsed
+    fn active_selection(&self, now: Instant) -> Option<PendingSelection> {
+        self.selection.filter(|x| !x.is_expired(now))
+    }
+
+    pub(crate) fn get_time_reading(&self) -> Option<TimeReading> {
+        self.assoc_handler.get_time_reading()
+    }
+
+    pub(crate) fn time_sync_policy(&self) -> TimeSyncPolicy {
+        self.config.time_sync_policy
+    }
+
+    /// period of the automatic time synchronization, if one is configured
+    fn periodic_time_sync(config: &AssociationConfig) -> Option<Duration> {
+        config.auto_time_sync.and(config.auto_time_sync_period)
+    }
+
+    fn schedule_periodic_time_sync(&mut self) {
+        self.next_periodic_time_sync =
+            Self::periodic_time_sync(&self.config).map(|period| Instant::now() + period);
     }
 
     pub(crate) fn complete_poll(&mut self, id: u64) {
@@ -492,6 +597,34 @@ impl Association {
 
     pub(crate) fn on_time_sync_success(&mut self) {
         self.auto_tasks.time_sync.done();
+        self.schedule_periodic_time_sync();
+    }
+
+    pub(crate) fn on_time_sync_unsuitable_clock(&mut self, action: UnsuitableClockAction) {
+        match action {
+            UnsuitableClockAction::Proceed | UnsuitableClockAction::Skip => {
+                self.auto_tasks.time_sync.done();
+                self.schedule_periodic_time_sync();
+            }
+            UnsuitableClockAction::Delay(delay) => {
+                self.auto_tasks.time_sync = AutoTaskState::Failed(
+                    ExponentialBackOff::new(self.config.auto_tasks_retry_strategy),
+                    Instant::now() + delay,
+                );
+            }
+        }
+    }
+
+    pub(crate) fn notify_time_sync_success(&mut self, report: TimeSyncReport) {
+        self.assoc_info.time_sync_success(report);
+    }
+
+    pub(crate) fn notify_time_sync_unsuitable_clock(
+        &mut self,
+        reading: TimeReading,
+        action: UnsuitableClockAction,
+    ) {
+        self.assoc_info.time_sync_unsuitable_clock(reading, action);
     }
 
     pub(crate) fn on_time_sync_failure(&mut self, err: TimeSyncError) {
@@ -630,6 +763,7 @@ impl Association {
         fc: FunctionCode,
         seq: Sequence,
     ) {
+        self.statistics.on_task_start();
         self.assoc_info.task_start(task_type, fc, seq)
     }
 
@@ -639,18 +773,44 @@ impl Association {
         fc: FunctionCode,
         seq: Sequence,
     ) {
+        self.statistics.on_task_success();
         self.assoc_info.task_success(task_type, fc, seq);
     }
 
     pub(crate) fn notify_task_fail(&mut self, task_type: TaskType, err: TaskError) {
+        self.statistics.on_task_failure(&err);
         self.assoc_info.task_fail(task_type, err);
     }
 
     fn notify_unsolicited_response(&mut self, is_duplicate: bool, seq: Sequence) {
+        self.statistics.on_unsolicited_response(is_duplicate);
         self.assoc_info.unsolicited_response(is_duplicate, seq);
     }
 
+    /// a request or link status request was sent to the outstation
+    pub(crate) fn notify_request_sent(&mut self) {
+        self.statistics.on_request_sent();
+    }
+
+    /// a solicited response matching the outstanding request was received
+    pub(crate) fn notify_solicited_response(&mut self) {
+        self.statistics.on_solicited_response();
+    }
+
+    pub(crate) fn notify_response_timeout(&mut self) {
+        self.statistics.on_response_timeout();
+    }
+
     pub(crate) fn priority_task(&mut self) -> Option<Task> {
+        if let Some(selection) = self.active_selection(Instant::now()) {
+            // only the OPERATE of the pending selection may be sent until it expires
+            let index = self
+                .request_queue
+                .iter()
+                .position(|x| x.selection_id() == Some(selection.id))?;
+            return self.request_queue.remove(index)?.start(self);
+        }
+
         while let Some(task) = self.request_queue.pop_front() {
             if let Some(task) = task.start(self) {
                 return Some(task);
@@ -661,6 +821,21 @@ impl Association {
     }
 
     fn next_task(&mut self, now: Instant) -> Next<Task> {
+        if let Some(selection) = self.active_selection(now) {
+            return match selection.deadline {
+                Some(deadline) => Next::NotBefore(deadline),
+                None => Next::None,
+            };
+        }
+
+        if let Some(deadline) = self.next_periodic_time_sync {
+            if now >= deadline {
+                // rescheduled once the synchronization completes
+                self.next_periodic_time_sync = None;
+                self.auto_tasks.time_sync.demand();
+            }
+        }
+
         loop {
             let next_task = self.get_next_task(now);
 
@@ -670,7 +845,7 @@ impl Association {
                     return Next::Now(task);
                 }
             } else {
-                return next_task;
+                return next_task.not_after(self.next_periodic_time_sync);
             }
         }
     }
@@ -726,6 +901,18 @@ pub(crate) enum Next<T> {
     NotBefore(Instant),
 }
 
+impl<T> Next<T> {
+    /// Ensure that the next check happens no later than the deadline
+    fn not_after(self, deadline: Option<Instant>) -> Self {
+        match (self, deadline) {
+            (x, None) => x,
+            (Self::None, Some(deadline)) => Self::NotBefore(deadline),
+            (Self::NotBefore(x), Some(deadline)) => Self::NotBefore(Instant::min(x, deadline)),
+            (Self::Now(x), Some(_)) => Self::Now(x),
+        }
+    }
+}
+
 pub(crate) struct AssociationMap {
     map: BTreeMap<EndpointAddress, Association>,
     priority: VecDeque<EndpointAddress>,
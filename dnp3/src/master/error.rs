@@ -39,12 +39,17 @@ pub enum AssociationError {
 pub enum BadEncoding {
     /// Attribute could not be encoded
     Attribute(BadAttribute),
+    /// An index or the number of values in a header exceeds the maximum of its qualifier
+    IndexOverflow,
 }
 
 impl std::fmt::Display for BadEncoding {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             BadEncoding::Attribute(x) => write!(f, "Bad attribute encoding: {x}"),
+            BadEncoding::IndexOverflow => {
+                f.write_str("index or count exceeds the maximum value of the qualifier")
+            }
         }
     }
 }
@@ -154,6 +159,19 @@ pub enum CommandError {
     Response(CommandResponseError),
 }
 
+/// Error type for [`AssociationHandle::select`](crate::master::AssociationHandle::select) and
+/// [`AssociationHandle::operate_selected`](crate::master::AssociationHandle::operate_selected)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SelectError {
+    /// Failed b/c of an error during the SELECT or OPERATE
+    Command(CommandError),
+    /// The select timeout of a [`Selection`](crate::master::Selection) elapsed before it was operated
+    SelectTimeout,
+    /// The [`Selection`](crate::master::Selection) is no longer pending, e.g. it was cancelled,
+    /// replaced by another selection, or the connection was lost
+    NotSelected,
+}
+
 impl std::fmt::Display for AssociationError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -216,6 +234,18 @@ impl std::fmt::Display for CommandError {
     }
 }
 
+impl std::fmt::Display for SelectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SelectError::Command(x) => std::fmt::Display::fmt(x, f),
+            SelectError::SelectTimeout => {
+                f.write_str("select timeout elapsed before the operate was requested")
+            }
+            SelectError::NotSelected => f.write_str("selection is no longer pending"),
+        }
+    }
+}
+
 impl std::fmt::Display for TaskError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -356,6 +386,24 @@ impl From<TaskError> for CommandError {
     }
 }
 
+impl From<CommandError> for SelectError {
+    fn from(err: CommandError) -> Self {
+        SelectError::Command(err)
+    }
+}
+
+impl From<CommandResponseError> for SelectError {
+    fn from(err: CommandResponseError) -> Self {
+        SelectError::Command(err.into())
+    }
+}
+
+impl From<TaskError> for SelectError {
+    fn from(err: TaskError) -> Self {
+        SelectError::Command(err.into())
+    }
+}
+
 impl From<TaskError> for TimeSyncError {
     fn from(err: TaskError) -> Self {
         TimeSyncError::Task(err)
@@ -401,6 +449,12 @@ impl From<RecvError> for CommandError {
     }
 }
 
+impl From<RecvError> for SelectError {
+    fn from(_: RecvError) -> Self {
+        SelectError::Command(CommandError::Task(TaskError::Shutdown))
+    }
+}
+
 impl From<RecvError> for TimeSyncError {
     fn from(_: RecvError) -> Self {
         TimeSyncError::Task(TaskError::Shutdown)
@@ -431,6 +485,12 @@ impl From<Shutdown> for CommandError {
     }
 }
 
+impl From<Shutdown> for SelectError {
+    fn from(_: Shutdown) -> Self {
+        SelectError::Command(CommandError::Task(TaskError::Shutdown))
+    }
+}
+
 impl From<Shutdown> for TimeSyncError {
     fn from(_: Shutdown) -> Self {
         TimeSyncError::Task(TaskError::Shutdown)
@@ -453,6 +513,7 @@ impl Error for AssociationError {}
 impl Error for TaskError {}
 impl Error for PollError {}
 impl Error for CommandError {}
+impl Error for SelectError {}
 impl Error for CommandResponseError {}
 impl Error for TimeSyncError {}
 impl Error for WriteError {}
@@ -26,72 +26,8 @@ pub(crate) fn extract_measurements_inner(
     objects: HeaderCollection<'_>,
     handler: &mut dyn ReadHandler,
 ) {
-    fn extract_cto_g51v1(prev: Option<Time>, item: Option<Group51Var1>) -> Option<Time> {
-        item.map_or(prev, |x| Some(Time::Synchronized(x.time)))
-    }
-
-    fn extract_cto_g51v2(prev: Option<Time>, item: Option<Group51Var2>) -> Option<Time> {
-        item.map_or(prev, |x| Some(Time::Unsynchronized(x.time)))
-    }
-
-    fn extract_g50v1(
-        seq: &CountSequence<Group50Var1>,
-        header: &ObjectHeader,
-        handler: &mut dyn ReadHandler,
-    ) -> bool {
-        if let Some(item) = seq.single() {
-            handler.handle_abs_time(
-                HeaderInfo::new(header.variation, header.details.qualifier(), false, false),
-                item.time,
-            );
-            true
-        } else {
-            false
-        }
-    }
-
-    fn handle(
-        cto: Option<Time>,
-        header: ObjectHeader,
-        handler: &mut dyn ReadHandler,
-    ) -> Option<Time> {
-        let handled = match &header.details {
-            // these are common-time-of-occurrence headers
-            HeaderDetails::OneByteCount(1, CountVariation::Group51Var1(seq)) => {
-                return extract_cto_g51v1(cto, seq.single())
-            }
-            HeaderDetails::OneByteCount(1, CountVariation::Group51Var2(seq)) => {
-                return extract_cto_g51v2(cto, seq.single())
-            }
-            HeaderDetails::TwoByteCount(1, CountVariation::Group51Var1(seq)) => {
-                return extract_cto_g51v1(cto, seq.single())
-            }
-            HeaderDetails::TwoByteCount(1, CountVariation::Group51Var2(seq)) => {
-                return extract_cto_g51v2(cto, seq.single())
-            }
-            // handle g50v1 - absolute time (only count == 1 is valid)
-            HeaderDetails::OneByteCount(1, CountVariation::Group50Var1(seq)) => {
-                extract_g50v1(seq, &header, handler)
-            }
-            HeaderDetails::TwoByteCount(1, CountVariation::Group50Var1(seq)) => {
-                extract_g50v1(seq, &header, handler)
-            }
-            // everything else
-            HeaderDetails::OneByteStartStop(_, _, var) => {
-                var.extract_measurements_to(header.variation, header.details.qualifier(), handler)
-            }
-            HeaderDetails::TwoByteStartStop(_, _, var) => {
-                var.extract_measurements_to(header.variation, header.details.qualifier(), handler)
-            }
-            HeaderDetails::OneByteCountAndPrefix(_, var) => {
-                var.extract_measurements_to(cto, handler)
-            }
-            HeaderDetails::TwoByteCountAndPrefix(_, var) => {
-                var.extract_measurements_to(cto, handler)
-            }
-            _ => false,
-        };
-
+    objects.iter().fold(None, |cto, header| {
+        let (cto, handled) = extract_header(cto, &header, handler);
         if !handled {
             tracing::warn!(
                 "ignored header variation: {} qualifier: {:?}",
@@ -99,14 +35,76 @@ pub(crate) fn extract_measurements_inner(
                 &header.details.qualifier()
             );
         }
-
-        // if we didn't return early b/c the header is a CTO, then we just return the previous value
         cto
+    });
+}
+
+fn extract_cto_g51v1(prev: Option<Time>, item: Option<Group51Var1>) -> Option<Time> {
+    item.map_or(prev, |x| Some(Time::Synchronized(x.time)))
+}
+
+fn extract_cto_g51v2(prev: Option<Time>, item: Option<Group51Var2>) -> Option<Time> {
+    item.map_or(prev, |x| Some(Time::Unsynchronized(x.time)))
+}
+
+fn extract_g50v1(
+    seq: &CountSequence<Group50Var1>,
+    header: &ObjectHeader,
+    handler: &mut dyn ReadHandler,
+) -> bool {
+    if let Some(item) = seq.single() {
+        handler.handle_abs_time(
+            HeaderInfo::new(header.variation, header.details.qualifier(), false, false),
+            item.time,
+        );
+        true
+    } else {
+        false
     }
+}
+
+/// Extract the measurements in a single header, returning the common time-of-occurrence
+/// for any subsequent headers and true if the header was handled
+pub(crate) fn extract_header(
+    cto: Option<Time>,
+    header: &ObjectHeader,
+    handler: &mut dyn ReadHandler,
+) -> (Option<Time>, bool) {
+    let handled = match &header.details {
+        // these are common-time-of-occurrence headers
+        HeaderDetails::OneByteCount(1, CountVariation::Group51Var1(seq)) => {
+            return (extract_cto_g51v1(cto, seq.single()), true)
+        }
+        HeaderDetails::OneByteCount(1, CountVariation::Group51Var2(seq)) => {
+            return (extract_cto_g51v2(cto, seq.single()), true)
+        }
+        HeaderDetails::TwoByteCount(1, CountVariation::Group51Var1(seq)) => {
+            return (extract_cto_g51v1(cto, seq.single()), true)
+        }
+        HeaderDetails::TwoByteCount(1, CountVariation::Group51Var2(seq)) => {
+            return (extract_cto_g51v2(cto, seq.single()), true)
+        }
+        // handle g50v1 - absolute time (only count == 1 is valid)
+        HeaderDetails::OneByteCount(1, CountVariation::Group50Var1(seq)) => {
+            extract_g50v1(seq, header, handler)
+        }
+        HeaderDetails::TwoByteCount(1, CountVariation::Group50Var1(seq)) => {
+            extract_g50v1(seq, header, handler)
+        }
+        // everything else
+        HeaderDetails::OneByteStartStop(_, _, var) => {
+            var.extract_measurements_to(header.variation, header.details.qualifier(), handler)
+        }
+        HeaderDetails::TwoByteStartStop(_, _, var) => {
+            var.extract_measurements_to(header.variation, header.details.qualifier(), handler)
+        }
+        HeaderDetails::OneByteCountAndPrefix(_, var) => var.extract_measurements_to(cto, handler),
+        HeaderDetails::TwoByteCountAndPrefix(_, var) => var.extract_measurements_to(cto, handler),
+        _ => false,
+    };
 
-    objects
-        .iter()
-        .fold(None, |cto, header| handle(cto, header, handler));
+    // if we didn't return early b/c the header is a CTO, then we just return the previous value
+    (cto, handled)
 }
 
 #[cfg(test)]
@@ -1,17 +1,23 @@
 use std::net::SocketAddr;
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 use crate::app::*;
+use crate::capture::PcapCapture;
 
-use crate::decode::DecodeLevel;
+use crate::decode::{DecodeLevel, DecodeSink};
 use crate::link::EndpointAddress;
 use crate::master::association::AssociationConfig;
-use crate::master::error::{AssociationError, CommandError, PollError, TaskError, TimeSyncError};
+use crate::master::error::{
+    AssociationError, CommandError, PollError, SelectError, TaskError, TimeSyncError,
+};
 use crate::master::messages::{AssociationMsg, AssociationMsgType, MasterMsg, Message};
 use crate::master::poll::{PollHandle, PollMsg};
 use crate::master::promise::Promise;
-use crate::master::request::{CommandHeaders, CommandMode, ReadRequest, TimeSyncProcedure};
-use crate::master::tasks::command::CommandTask;
+use crate::master::request::{
+    CommandHeaders, CommandMode, ReadRequest, Selection, TimeSyncProcedure, UnsuitableClockAction,
+};
+use crate::master::tasks::command::{CommandTask, OperateSelectedTask, SelectTask};
 use crate::master::tasks::deadbands::WriteDeadBandsTask;
 use crate::master::tasks::empty_response::EmptyResponseTask;
 use crate::master::tasks::file::authenticate::AuthFileTask;
@@ -24,11 +30,15 @@ use crate::master::tasks::file::write_block::{WriteBlockRequest, WriteBlockTask}
 use crate::master::tasks::read::SingleReadTask;
 use crate::master::tasks::restart::{RestartTask, RestartType};
 use crate::master::tasks::time::TimeSyncTask;
+use crate::master::tasks::write::WriteTask;
 use crate::master::tasks::Task;
 use crate::master::{
-    AuthKey, BlockNumber, DeadBandHeader, DirReadConfig, FileCredentials, FileError, FileHandle,
-    FileInfo, FileMode, FileReadConfig, FileReader, Headers, OpenFile, ReadHandler, WriteError,
+    AuthKey, BlockNumber, CommandSequence, DeadBandHeader, DirReadConfig, FileCredentials,
+    FileError, FileHandle, FileInfo, FileMode, FileReadConfig, FileReader, Headers, OpenFile,
+    ReadHandler, SequenceReport, WriteError, WriteRequest,
 };
+use crate::statistics::{AssociationStatistics, ChannelStatistics};
+use crate::tcp::PeerIdentity;
 use crate::transport::FragmentAddr;
 use crate::util::channel::Sender;
 use crate::util::phys::PhysAddr;
@@ -135,6 +145,52 @@ impl MasterChannel {
         rx.await?
     }
 
+    /// Start recording the traffic of the channel to a pcapng capture
+    ///
+    /// Any capture already in progress is stopped and replaced.
+    pub async fn start_capture(&mut self, capture: PcapCapture) -> Result<(), Shutdown> {
+        self.send_master_message(MasterMsg::SetCapture(Some(capture)))
+            .await?;
+        Ok(())
+    }
+
+    /// Stop recording traffic, closing the capture if one is in progress
+    pub async fn stop_capture(&mut self) -> Result<(), Shutdown> {
+        self.send_master_message(MasterMsg::SetCapture(None))
+            .await?;
+        Ok(())
+    }
+
+    /// Set a sink that receives structured decode events in addition to the text log
+    ///
+    /// Events are produced according to the current [`DecodeLevel`]. Passing `None` removes the sink.
+    pub async fn set_decode_sink(
+        &mut self,
+        sink: Option<Arc<dyn DecodeSink>>,
+    ) -> Result<(), Shutdown> {
+        self.send_master_message(MasterMsg::SetDecodeSink(sink))
+            .await?;
+        Ok(())
+    }
+
+    /// Get the traffic statistics of the channel
+    pub async fn get_statistics(&mut self) -> Result<ChannelStatistics, Shutdown> {
+        let (promise, rx) = Promise::one_shot();
+        self.send_master_message(MasterMsg::GetStatistics(promise))
+            .await?;
+        rx.await?
+    }
+
+    /// Get the identity of the outstation of the current session
+    ///
+    /// Returns `None` if the channel is not connected or if the session does not use TLS.
+    pub async fn get_peer_identity(&mut self) -> Result<Option<PeerIdentity>, Shutdown> {
+        let (promise, rx) = Promise::one_shot();
+        self.send_master_message(MasterMsg::GetPeerIdentity(promise))
+            .await?;
+        rx.await?
+    }
+
     fn assert_channel_type(&self, required: MasterChannelType) -> Result<(), AssociationError> {
         if self.channel_type == required {
             Ok(())
@@ -272,6 +328,15 @@ impl AssociationHandle {
         rx.await?
     }
 
+    /// Get the statistics the master maintains for the association
+    pub async fn get_statistics(&mut self) -> Result<AssociationStatistics, TaskError> {
+        let (promise, rx) = Promise::one_shot();
+        self.master
+            .send_association_message(self.address, AssociationMsgType::GetStatistics(promise))
+            .await?;
+        rx.await?
+    }
+
     /// Remove the association from the master
     pub async fn remove(mut self) -> Result<(), Shutdown> {
         self.master
@@ -334,6 +399,58 @@ impl AssociationHandle {
         rx.await?
     }
 
+    /// Perform a SELECT without operating the commands
+    ///
+    /// `select_timeout` should match the select timeout of the outstation. It is enforced by
+    /// the master, which refuses to operate the returned [`Selection`] once it has elapsed.
+    /// No other requests are sent to the outstation until the selection is operated with
+    /// [`AssociationHandle::operate_selected`], cancelled, or expires.
+    pub async fn select(
+        &mut self,
+        headers: CommandHeaders,
+        select_timeout: Duration,
+    ) -> Result<Selection, SelectError> {
+        let (promise, rx) = Promise::one_shot();
+        let task = SelectTask::new(headers, select_timeout, promise);
+        self.send_task(task).await?;
+        rx.await?
+    }
+
+    /// Perform the OPERATE of a [`Selection`] returned by [`AssociationHandle::select`]
+    ///
+    /// The objects of the SELECT are echoed using the next sequence number. Fails without
+    /// sending a request if the select timeout has elapsed or the selection is no longer pending.
+    pub async fn operate_selected(&mut self, selection: Selection) -> Result<(), SelectError> {
+        if selection.is_expired() {
+            return Err(SelectError::SelectTimeout);
+        }
+        let (promise, rx) = Promise::one_shot();
+        let task = OperateSelectedTask::new(selection, promise);
+        self.send_task(task).await?;
+        rx.await?
+    }
+
+    /// Release a [`Selection`] without operating it
+    ///
+    /// The association resumes sending other requests, which causes the outstation to discard
+    /// its selection.
+    pub async fn cancel_selection(&mut self, selection: Selection) -> Result<(), Shutdown> {
+        self.master
+            .send_association_message(
+                self.address,
+                AssociationMsgType::CancelSelection(selection.id),
+            )
+            .await
+    }
+
+    /// Execute a [`CommandSequence`] using `read` and `operate` requests
+    ///
+    /// The returned report describes the outcome of each executed step, including any
+    /// rollback steps performed after a failure.
+    pub async fn execute_sequence(&mut self, sequence: &CommandSequence) -> SequenceReport {
+        sequence.execute(self).await
+    }
+
     /// Perform a WARM_RESTART operation
     ///
     /// Returns the delay from the outstation's response as a [Duration](Duration)
@@ -377,6 +494,17 @@ impl AssociationHandle {
         rx.await?
     }
 
+    /// Write static values such as counter presets, analog output values, or octet strings to the outstation
+    ///
+    /// If the outstation rejects the request, the IIN2 bits of the response are returned in
+    /// [`WriteError::IinError`]
+    pub async fn write(&mut self, request: WriteRequest) -> Result<(), WriteError> {
+        let (promise, rx) = Promise::one_shot();
+        let task = WriteTask::new(request, promise);
+        self.send_task(task).await?;
+        rx.await?
+    }
+
     /// Trigger the master to issue a REQUEST_LINK_STATUS function in advance of the link status timeout
     ///
     /// This function is provided for testing purposes. Using the configured link status timeout
@@ -563,12 +691,92 @@ pub enum TaskType {
     GetFileInfo,
 }
 
+/// Synchronization status of the clock used by the master to synchronize outstations
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ClockSyncStatus {
+    /// The status of the clock is not known, e.g. a system clock that is not monitored
+    Unknown,
+    /// The clock is disciplined by a reference such as NTP, PTP, or GPS
+    Synchronized,
+    /// The clock is free-running or has lost its reference
+    Unsynchronized,
+}
+
+/// Time read from the master's clock along with an assessment of its quality
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TimeReading {
+    /// Current time
+    pub timestamp: Timestamp,
+    /// Synchronization status of the clock
+    pub status: ClockSyncStatus,
+    /// Estimated maximum error of the clock relative to UTC, if known
+    pub accuracy: Option<Duration>,
+    /// True if the clock is currently smearing a leap second
+    pub leap_smear: bool,
+}
+
+impl TimeReading {
+    /// Construct a reading with an unknown accuracy and no leap smear
+    pub fn new(timestamp: Timestamp, status: ClockSyncStatus) -> Self {
+        Self {
+            timestamp,
+            status,
+            accuracy: None,
+            leap_smear: false,
+        }
+    }
+
+    /// Set the estimated maximum error of the clock
+    pub fn with_accuracy(self, accuracy: Duration) -> Self {
+        Self {
+            accuracy: Some(accuracy),
+            ..self
+        }
+    }
+
+    /// Indicate whether the clock is smearing a leap second
+    pub fn with_leap_smear(self, leap_smear: bool) -> Self {
+        Self { leap_smear, ..self }
+    }
+}
+
 /// callbacks associated with a single master to outstation association
 pub trait AssociationHandler: Send + Sync {
     /// Retrieve the system time used for time synchronization
     fn get_current_time(&self) -> Option<Timestamp> {
         Timestamp::try_from_system_time(SystemTime::now())
     }
+
+    /// Retrieve the time used for time synchronization along with the quality of the clock
+    ///
+    /// The default implementation reports the value of [`AssociationHandler::get_current_time`]
+    /// with [`ClockSyncStatus::Unknown`]. Time sources that know whether they are synchronized,
+    /// e.g. by querying an NTP or PTP daemon, should override this method so that the
+    /// [`TimeSyncPolicy`](crate::master::TimeSyncPolicy) can be applied.
+    fn get_time_reading(&self) -> Option<TimeReading> {
+        self.get_current_time()
+            .map(|x| TimeReading::new(x, ClockSyncStatus::Unknown))
+    }
+}
+
+/// Measurements taken during a successful time synchronization
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TimeSyncReport {
+    /// Procedure used to synchronize the outstation
+    pub procedure: TimeSyncProcedure,
+    /// Reading of the master clock used for the synchronization
+    pub reading: TimeReading,
+    /// Time between sending the first request of the procedure and receiving its response
+    pub round_trip_delay: Duration,
+    /// Processing delay reported by the outstation in response to `DELAY_MEASURE`
+    ///
+    /// Only present for [`TimeSyncProcedure::NonLan`].
+    pub outstation_delay: Option<Duration>,
+    /// One-way propagation delay added to the time written to the outstation
+    ///
+    /// Only present for [`TimeSyncProcedure::NonLan`].
+    pub propagation_delay: Option<Duration>,
 }
 
 /// Informational callbacks that can be used to monitor master communication
@@ -583,4 +791,19 @@ pub trait AssociationInformation: Send + Sync {
 
     /// Called when an unsolicited response is received
     fn unsolicited_response(&mut self, _is_duplicate: bool, _seq: Sequence) {}
+
+    /// Called when a time synchronization completes successfully, whether it was
+    /// performed automatically or requested by the user
+    fn time_sync_success(&mut self, _report: TimeSyncReport) {}
+
+    /// Called when the master clock is not suitable for a time synchronization according
+    /// to the [`TimeSyncPolicy`](crate::master::TimeSyncPolicy)
+    ///
+    /// The action indicates whether the synchronization proceeded, was skipped, or will be retried
+    fn time_sync_unsuitable_clock(
+        &mut self,
+        _reading: TimeReading,
+        _action: UnsuitableClockAction,
+    ) {
+    }
 }
@@ -1,5 +1,8 @@
+use std::sync::Arc;
+
 use crate::app::Shutdown;
-use crate::decode::DecodeLevel;
+use crate::capture::PcapCapture;
+use crate::decode::{DecodeLevel, DecodeSink};
 use crate::link::EndpointAddress;
 use crate::master::error::PollError;
 use crate::master::error::{AssociationError, TaskError};
@@ -7,6 +10,8 @@ use crate::master::poll::PollMsg;
 use crate::master::promise::Promise;
 use crate::master::tasks::Task;
 use crate::master::{AssociationConfig, AssociationHandler, AssociationInformation, ReadHandler};
+use crate::statistics::{AssociationStatistics, ChannelStatistics};
+use crate::tcp::PeerIdentity;
 use crate::transport::FragmentAddr;
 use crate::util::session::Enabled;
 
@@ -36,6 +41,14 @@ pub(crate) enum MasterMsg {
     SetDecodeLevel(DecodeLevel),
     /// Get the decoding level
     GetDecodeLevel(Promise<Result<DecodeLevel, Shutdown>>),
+    /// Start or stop capturing traffic
+    SetCapture(Option<PcapCapture>),
+    /// Set or clear the sink for structured decode events
+    SetDecodeSink(Option<Arc<dyn DecodeSink>>),
+    /// Get the statistics of the channel
+    GetStatistics(Promise<Result<ChannelStatistics, Shutdown>>),
+    /// Get the identity of the peer of the current session
+    GetPeerIdentity(Promise<Result<Option<PeerIdentity>, Shutdown>>),
 }
 
 pub(crate) struct AssociationMsg {
@@ -48,6 +61,10 @@ pub(crate) enum AssociationMsgType {
     QueueTask(Task),
     /// Modify polls
     Poll(PollMsg),
+    /// Get the statistics of the association
+    GetStatistics(Promise<Result<AssociationStatistics, TaskError>>),
+    /// Release a pending selection without operating it
+    CancelSelection(u64),
 }
 
 impl AssociationMsg {
@@ -65,6 +82,10 @@ impl AssociationMsgType {
             AssociationMsgType::Poll(msg) => {
                 msg.on_error(PollError::NoSuchAssociation(address));
             }
+            AssociationMsgType::GetStatistics(promise) => {
+                promise.complete(Err(TaskError::NoSuchAssociation(address)));
+            }
+            AssociationMsgType::CancelSelection(_) => {}
         }
     }
 }
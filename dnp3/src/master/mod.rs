@@ -5,6 +5,7 @@ pub use handler::*;
 pub use poll::PollHandle;
 pub use read_handler::*;
 pub use request::*;
+pub use sequence::*;
 
 mod association;
 mod error;
@@ -12,6 +13,7 @@ mod file;
 mod handler;
 mod read_handler;
 mod request;
+mod sequence;
 
 pub(crate) mod convert;
 pub(crate) mod extract;
@@ -1,5 +1,7 @@
 use crate::app::attr::{AttrSet, OwnedAttribute};
+use crate::app::measurement::{AnalogOutputStatus, BinaryOutputStatus, Counter, OctetString};
 use std::ops::BitAnd;
+use std::time::Duration;
 
 use crate::app::control::CommandStatus;
 use crate::app::format::write::HeaderWriter;
@@ -13,8 +15,12 @@ use crate::app::Timestamp;
 use crate::app::Variation::Group0;
 use crate::master::error::CommandResponseError;
 use crate::master::TaskError;
+use crate::master::{ClockSyncStatus, TimeReading};
+use crate::outstation::database::{StaticAnalogOutputStatusVariation, StaticCounterVariation};
 use crate::outstation::FreezeInterval;
 
+use tokio::time::Instant;
+
 /// Controls how a command request is issued
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
@@ -49,6 +55,98 @@ pub enum TimeSyncProcedure {
     DirectWriteAbsTime,
 }
 
+/// Action taken when the master's clock is not suitable for synchronizing outstations
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum UnsuitableClockAction {
+    /// Synchronize the outstation anyway, logging a warning
+    Proceed,
+    /// Skip the synchronization until it is requested again by the outstation or the periodic schedule
+    Skip,
+    /// Retry the synchronization after a delay
+    Delay(Duration),
+}
+
+/// Controls how the quality of the master's clock affects time synchronization
+///
+/// The quality of the clock is reported by [`AssociationHandler::get_time_reading`](crate::master::AssociationHandler::get_time_reading).
+/// A clock whose status is [`ClockSyncStatus::Unknown`](crate::master::ClockSyncStatus::Unknown) is always
+/// considered suitable.
+///
+/// Automatic synchronizations apply the configured action, while synchronizations requested using
+/// [`AssociationHandle::synchronize_time`](crate::master::AssociationHandle::synchronize_time) always
+/// proceed. In both cases, the rejection is reported to
+/// [`AssociationInformation::time_sync_unsuitable_clock`](crate::master::AssociationInformation::time_sync_unsuitable_clock)
+/// along with the action that was taken.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct TimeSyncPolicy {
+    /// Action taken when the clock is not suitable
+    #[cfg_attr(
+        feature = "serialization",
+        serde(default = "TimeSyncPolicy::default_action")
+    )]
+    pub action: UnsuitableClockAction,
+    /// Clocks reporting a larger error than this value are not suitable
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub max_clock_error: Option<Duration>,
+    /// Clocks that are smearing a leap second are not suitable
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub reject_leap_smear: bool,
+}
+
+impl TimeSyncPolicy {
+    const fn default_action() -> UnsuitableClockAction {
+        UnsuitableClockAction::Proceed
+    }
+
+    /// Construct a policy that skips synchronizations when the clock is not synchronized
+    /// or its error exceeds `max_clock_error`
+    pub fn skip_unsynchronized(max_clock_error: Option<Duration>) -> Self {
+        Self {
+            action: UnsuitableClockAction::Skip,
+            max_clock_error,
+            reject_leap_smear: false,
+        }
+    }
+
+    /// Returns a description of why the reading is not suitable, if it isn't
+    pub(crate) fn check(&self, reading: &TimeReading) -> Option<String> {
+        if reading.status == ClockSyncStatus::Unsynchronized {
+            return Some("the master clock is not synchronized".to_string());
+        }
+        if let (Some(max), Some(accuracy)) = (self.max_clock_error, reading.accuracy) {
+            if accuracy > max {
+                return Some(format!(
+                    "the master clock error ({} ms) exceeds the maximum ({} ms)",
+                    accuracy.as_millis(),
+                    max.as_millis()
+                ));
+            }
+        }
+        if self.reject_leap_smear && reading.leap_smear {
+            return Some("the master clock is smearing a leap second".to_string());
+        }
+        None
+    }
+}
+
+impl Default for TimeSyncPolicy {
+    fn default() -> Self {
+        Self {
+            action: Self::default_action(),
+            max_clock_error: None,
+            reject_leap_smear: false,
+        }
+    }
+}
+
 /// struct recording which event classes are enabled
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
@@ -221,6 +319,106 @@ pub(crate) enum DeadBandHeaderVariants {
     G34V3U16(Vec<(Group34Var3, u16)>),
 }
 
+/// Values in a single header of a [`WriteRequest`] along with the qualifier used to encode their indices
+#[derive(Debug, Clone)]
+pub enum WriteValues<T> {
+    /// Values at contiguous indices beginning at the specified index, encoded with a 1-byte start/stop range (0x00)
+    RangeU8(u8, Vec<T>),
+    /// Values at contiguous indices beginning at the specified index, encoded with a 2-byte start/stop range (0x01)
+    RangeU16(u16, Vec<T>),
+    /// Values with 1-byte index prefixes and a 1-byte count (0x17)
+    PrefixedU8(Vec<(T, u8)>),
+    /// Values with 2-byte index prefixes and a 2-byte count (0x28)
+    PrefixedU16(Vec<(T, u16)>),
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum WriteHeader {
+    /// Group 10 variation 2
+    BinaryOutputStatus(WriteValues<BinaryOutputStatus>),
+    /// Group 20 variation 1, 2, 5 or 6
+    Counter(StaticCounterVariation, WriteValues<Counter>),
+    /// Group 40 variation 1, 2, 3 or 4
+    AnalogOutputStatus(
+        StaticAnalogOutputStatusVariation,
+        WriteValues<AnalogOutputStatus>,
+    ),
+    /// Group 110 with the length of the strings as the variation
+    OctetString(WriteValues<OctetString>),
+}
+
+/// WRITE request of static values sent using [`AssociationHandle::write`](crate::master::AssociationHandle::write)
+///
+/// Use a [`WriteBuilder`] to create a request
+#[derive(Debug, Clone)]
+pub struct WriteRequest {
+    pub(crate) headers: Vec<WriteHeader>,
+}
+
+/// Builder object used to create a [`WriteRequest`]
+///
+/// Each call adds a header to the request. Headers are written in the order they are added.
+#[derive(Debug, Clone)]
+pub struct WriteBuilder {
+    headers: Vec<WriteHeader>,
+}
+
+impl WriteBuilder {
+    /// construct a new `WriteBuilder` instance
+    pub fn new() -> Self {
+        Self {
+            headers: Vec::new(),
+        }
+    }
+
+    /// Add a header of binary output status values written as group 10 variation 2
+    pub fn add_binary_output_status(&mut self, values: WriteValues<BinaryOutputStatus>) {
+        self.headers.push(WriteHeader::BinaryOutputStatus(values));
+    }
+
+    /// Add a header of counter values written using the specified variation
+    ///
+    /// This is typically used to preset counters in the outstation
+    pub fn add_counters(
+        &mut self,
+        variation: StaticCounterVariation,
+        values: WriteValues<Counter>,
+    ) {
+        self.headers.push(WriteHeader::Counter(variation, values));
+    }
+
+    /// Add a header of analog output status values written using the specified variation
+    pub fn add_analog_output_status(
+        &mut self,
+        variation: StaticAnalogOutputStatusVariation,
+        values: WriteValues<AnalogOutputStatus>,
+    ) {
+        self.headers
+            .push(WriteHeader::AnalogOutputStatus(variation, values));
+    }
+
+    /// Add octet strings written as group 110
+    ///
+    /// The variation of group 110 is the length of the strings, so the values are
+    /// split into multiple headers wherever the length changes
+    pub fn add_octet_strings(&mut self, values: WriteValues<OctetString>) {
+        self.headers.push(WriteHeader::OctetString(values));
+    }
+
+    /// Consume the instance and return a fully built `WriteRequest`
+    pub fn build(self) -> WriteRequest {
+        WriteRequest {
+            headers: self.headers,
+        }
+    }
+}
+
+impl Default for WriteBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl EventClasses {
     /// construct an `EventClasses` from its fields
     pub fn new(class1: bool, class2: bool, class3: bool) -> Self {
@@ -718,6 +916,7 @@ impl Command for Group41Var4 {
 }
 
 /// Collection of command headers sent from the master API
+#[derive(Clone)]
 pub struct CommandHeaders {
     headers: Vec<CommandHeader>,
 }
@@ -755,6 +954,42 @@ impl CommandHeaders {
     }
 }
 
+/// Selection made by [`AssociationHandle::select`](crate::master::AssociationHandle::select)
+/// that has yet to be operated
+///
+/// The commands are executed by passing the selection to
+/// [`AssociationHandle::operate_selected`](crate::master::AssociationHandle::operate_selected)
+/// before the select timeout elapses, or released with
+/// [`AssociationHandle::cancel_selection`](crate::master::AssociationHandle::cancel_selection).
+///
+/// The OPERATE must immediately follow the SELECT with the next sequence number, so the
+/// association does not send any other requests to the outstation while the selection is
+/// pending. Dropping a selection without operating or cancelling it holds off other requests
+/// until the select timeout elapses.
+pub struct Selection {
+    pub(crate) id: u64,
+    pub(crate) headers: CommandHeaders,
+    /// `None` if the select timeout is too large to be represented
+    pub(crate) deadline: Option<Instant>,
+}
+
+impl Selection {
+    /// Time remaining before the select timeout elapses
+    ///
+    /// Returns [`Duration::MAX`] if the select timeout is too large to ever elapse.
+    pub fn remaining(&self) -> Duration {
+        match self.deadline {
+            Some(x) => x.saturating_duration_since(Instant::now()),
+            None => Duration::MAX,
+        }
+    }
+
+    /// True if the select timeout has elapsed and the selection can no longer be operated
+    pub fn is_expired(&self) -> bool {
+        self.deadline.is_some_and(|x| Instant::now() >= x)
+    }
+}
+
 /// Builder object used to create a [CommandHeaders](CommandHeaders)
 #[derive(Clone)]
 pub struct CommandBuilder {
@@ -0,0 +1,518 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::app::control::CommandStatus;
+use crate::app::measurement::*;
+use crate::app::Variation;
+use crate::master::error::{CommandError, CommandResponseError, TaskError};
+use crate::master::request::{CommandHeaders, CommandMode, ReadRequest};
+use crate::master::{AssociationHandle, HeaderInfo, ReadHandler};
+
+/// Value of a point read back from the outstation
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PointValue {
+    /// Value of a binary input or binary output status
+    Binary(bool),
+    /// Value of a double-bit binary input
+    DoubleBit(DoubleBit),
+    /// Value of an analog input or analog output status
+    Analog(f64),
+}
+
+/// Value and flags of a point read back from the outstation
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PointReading {
+    /// Value of the point
+    pub value: PointValue,
+    /// Flags reported with the value
+    pub flags: Flags,
+}
+
+impl PointReading {
+    /// Construct a reading from a value and its flags
+    pub fn new(value: PointValue, flags: Flags) -> Self {
+        Self { value, flags }
+    }
+}
+
+/// Condition on the current value of a point in the outstation
+///
+/// The point is read using its default (variation 0) static variation and a two-byte range.
+/// A condition is never satisfied by a point that is not reported as [`Flags::ONLINE`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PointCondition {
+    /// Binary input has the specified value
+    BinaryInput {
+        /// index of the point
+        index: u16,
+        /// expected value
+        value: bool,
+    },
+    /// Double-bit binary input has the specified value
+    DoubleBitBinaryInput {
+        /// index of the point
+        index: u16,
+        /// expected value
+        value: DoubleBit,
+    },
+    /// Binary output status has the specified value
+    BinaryOutputStatus {
+        /// index of the point
+        index: u16,
+        /// expected value
+        value: bool,
+    },
+    /// Analog input is within the inclusive range
+    AnalogInput {
+        /// index of the point
+        index: u16,
+        /// minimum value
+        min: f64,
+        /// maximum value
+        max: f64,
+    },
+    /// Analog output status is within the inclusive range
+    AnalogOutputStatus {
+        /// index of the point
+        index: u16,
+        /// minimum value
+        min: f64,
+        /// maximum value
+        max: f64,
+    },
+}
+
+impl PointCondition {
+    fn index(&self) -> u16 {
+        match self {
+            Self::BinaryInput { index, .. } => *index,
+            Self::DoubleBitBinaryInput { index, .. } => *index,
+            Self::BinaryOutputStatus { index, .. } => *index,
+            Self::AnalogInput { index, .. } => *index,
+            Self::AnalogOutputStatus { index, .. } => *index,
+        }
+    }
+
+    fn variation(&self) -> Variation {
+        match self {
+            Self::BinaryInput { .. } => Variation::Group1Var0,
+            Self::DoubleBitBinaryInput { .. } => Variation::Group3Var0,
+            Self::BinaryOutputStatus { .. } => Variation::Group10Var0,
+            Self::AnalogInput { .. } => Variation::Group30Var0,
+            Self::AnalogOutputStatus { .. } => Variation::Group40Var0,
+        }
+    }
+
+    /// Check if the condition is satisfied by a reading
+    ///
+    /// Returns false if the reading does not have the [`Flags::ONLINE`] flag set.
+    pub fn is_satisfied_by(&self, reading: PointReading) -> bool {
+        if !reading.flags.is_set(Flags::ONLINE) {
+            return false;
+        }
+        match (self, reading.value) {
+            (Self::BinaryInput { value: x, .. }, PointValue::Binary(y)) => *x == y,
+            (Self::BinaryOutputStatus { value: x, .. }, PointValue::Binary(y)) => *x == y,
+            (Self::DoubleBitBinaryInput { value: x, .. }, PointValue::DoubleBit(y)) => *x == y,
+            (Self::AnalogInput { min, max, .. }, PointValue::Analog(y)) => *min <= y && y <= *max,
+            (Self::AnalogOutputStatus { min, max, .. }, PointValue::Analog(y)) => {
+                *min <= y && y <= *max
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Type of step in a [`CommandSequence`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StepKind {
+    /// Check a condition before continuing
+    Precondition,
+    /// Operate one or more points
+    Operate,
+    /// Wait for a condition to become true
+    Verify,
+    /// Wait for a fixed amount of time
+    Delay,
+    /// Undo a previously executed operate step
+    Rollback,
+}
+
+/// Reason a step of a [`CommandSequence`] failed
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StepError {
+    /// Reading the point failed
+    Read(TaskError),
+    /// Operating the points failed
+    Command(CommandError),
+    /// The precondition was not satisfied by the reading, or the point was not returned
+    ConditionNotMet(Option<PointReading>),
+    /// The condition was not satisfied before the timeout with the last reading, if any
+    VerifyTimeout(Option<PointReading>),
+}
+
+impl std::fmt::Display for StepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            StepError::Read(err) => write!(f, "unable to read point: {err}"),
+            StepError::Command(err) => write!(f, "command failed: {err}"),
+            StepError::ConditionNotMet(Some(reading)) => write!(
+                f,
+                "condition not satisfied by value: {:?} with flags: {:?}",
+                reading.value, reading.flags
+            ),
+            StepError::ConditionNotMet(None) => f.write_str("point not returned by outstation"),
+            StepError::VerifyTimeout(Some(reading)) => write!(
+                f,
+                "condition not satisfied before timeout, last value: {:?} with flags: {:?}",
+                reading.value, reading.flags
+            ),
+            StepError::VerifyTimeout(None) => f.write_str(
+                "condition not satisfied before timeout, point not returned by outstation",
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StepError {}
+
+/// Outcome of a single step of a [`CommandSequence`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct StepReport {
+    /// Index of the step within the sequence
+    ///
+    /// For rollback steps, this is the index of the operate step being undone.
+    pub index: usize,
+    /// Type of step
+    pub kind: StepKind,
+    /// Result of the step
+    pub result: Result<(), StepError>,
+}
+
+impl StepReport {
+    /// The [`CommandStatus`] reported by the outstation for operate and rollback steps
+    ///
+    /// Returns `None` for other steps, or if the command failed before a status was received.
+    pub fn command_status(&self) -> Option<CommandStatus> {
+        if !matches!(self.kind, StepKind::Operate | StepKind::Rollback) {
+            return None;
+        }
+        match self.result {
+            Ok(()) => Some(CommandStatus::Success),
+            Err(StepError::Command(CommandError::Response(CommandResponseError::BadStatus(
+                status,
+            )))) => Some(status),
+            Err(_) => None,
+        }
+    }
+}
+
+/// Result of executing a [`CommandSequence`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SequenceReport {
+    /// Steps that were executed, in order
+    ///
+    /// Execution stops at the first step that fails.
+    pub steps: Vec<StepReport>,
+    /// Rollback steps executed after a failure, in reverse order of the operate steps they undo
+    pub rollback: Vec<StepReport>,
+}
+
+impl SequenceReport {
+    /// True if every step of the sequence succeeded
+    pub fn is_success(&self) -> bool {
+        self.steps.iter().all(|x| x.result.is_ok())
+    }
+
+    /// The step that caused the sequence to stop, if any
+    pub fn failed_step(&self) -> Option<&StepReport> {
+        self.steps.iter().find(|x| x.result.is_err())
+    }
+}
+
+enum Step {
+    Precondition(PointCondition),
+    Operate {
+        mode: CommandMode,
+        headers: CommandHeaders,
+        rollback: Option<CommandHeaders>,
+    },
+    Verify {
+        condition: PointCondition,
+        timeout: Duration,
+    },
+    Delay(Duration),
+}
+
+impl Step {
+    fn kind(&self) -> StepKind {
+        match self {
+            Step::Precondition(_) => StepKind::Precondition,
+            Step::Operate { .. } => StepKind::Operate,
+            Step::Verify { .. } => StepKind::Verify,
+            Step::Delay(_) => StepKind::Delay,
+        }
+    }
+}
+
+/// Ordered plan of interlocked commands executed using
+/// [`AssociationHandle::execute_sequence`](crate::master::AssociationHandle::execute_sequence)
+///
+/// Steps are executed in order until one fails. When a step fails, the rollback commands
+/// of the operate steps that already succeeded are executed in reverse order. The rollback
+/// of the failed step itself is not executed.
+pub struct CommandSequence {
+    steps: Vec<Step>,
+    verify_poll_interval: Duration,
+}
+
+impl CommandSequence {
+    /// Default interval between reads while waiting for a verify condition
+    pub const DEFAULT_VERIFY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Construct an empty sequence
+    pub fn new() -> Self {
+        Self {
+            steps: Vec::new(),
+            verify_poll_interval: Self::DEFAULT_VERIFY_POLL_INTERVAL,
+        }
+    }
+
+    /// Set the interval between reads while waiting for a verify condition
+    pub fn set_verify_poll_interval(&mut self, interval: Duration) {
+        self.verify_poll_interval = interval;
+    }
+
+    /// Read a point and stop the sequence unless the condition is satisfied
+    pub fn add_precondition(&mut self, condition: PointCondition) {
+        self.steps.push(Step::Precondition(condition));
+    }
+
+    /// Operate points without a rollback
+    pub fn add_operate(&mut self, mode: CommandMode, headers: CommandHeaders) {
+        self.steps.push(Step::Operate {
+            mode,
+            headers,
+            rollback: None,
+        });
+    }
+
+    /// Operate points, undoing the operation with `rollback` if a later step fails
+    ///
+    /// The rollback commands are executed using the same [`CommandMode`].
+    pub fn add_operate_with_rollback(
+        &mut self,
+        mode: CommandMode,
+        headers: CommandHeaders,
+        rollback: CommandHeaders,
+    ) {
+        self.steps.push(Step::Operate {
+            mode,
+            headers,
+            rollback: Some(rollback),
+        });
+    }
+
+    /// Read a point until the condition is satisfied, failing if it isn't within `timeout`
+    ///
+    /// Reads that fail are retried until the timeout expires, in which case the step fails
+    /// with the last read error.
+    pub fn add_verify(&mut self, condition: PointCondition, timeout: Duration) {
+        self.steps.push(Step::Verify { condition, timeout });
+    }
+
+    /// Wait before executing the next step
+    pub fn add_delay(&mut self, delay: Duration) {
+        self.steps.push(Step::Delay(delay));
+    }
+
+    pub(crate) async fn execute(&self, association: &mut AssociationHandle) -> SequenceReport {
+        let mut report = SequenceReport::default();
+        let mut executed: Vec<(usize, CommandMode, &CommandHeaders)> = Vec::new();
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let result = self.execute_step(association, step).await;
+            let failed = result.is_err();
+            report.steps.push(StepReport {
+                index,
+                kind: step.kind(),
+                result,
+            });
+
+            if failed {
+                tracing::warn!("command sequence failed at step {index}");
+                for (index, mode, headers) in executed.into_iter().rev() {
+                    let result = association
+                        .operate(mode, headers.clone())
+                        .await
+                        .map_err(StepError::Command);
+                    if let Err(err) = result {
+                        tracing::warn!("rollback of step {index} failed: {err}");
+                    }
+                    report.rollback.push(StepReport {
+                        index,
+                        kind: StepKind::Rollback,
+                        result,
+                    });
+                }
+                return report;
+            }
+
+            if let Step::Operate {
+                mode,
+                rollback: Some(rollback),
+                ..
+            } = step
+            {
+                executed.push((index, *mode, rollback));
+            }
+        }
+
+        report
+    }
+
+    async fn execute_step(
+        &self,
+        association: &mut AssociationHandle,
+        step: &Step,
+    ) -> Result<(), StepError> {
+        match step {
+            Step::Precondition(condition) => {
+                let value = read_point(association, *condition).await?;
+                match value {
+                    Some(x) if condition.is_satisfied_by(x) => Ok(()),
+                    _ => Err(StepError::ConditionNotMet(value)),
+                }
+            }
+            Step::Operate { mode, headers, .. } => association
+                .operate(*mode, headers.clone())
+                .await
+                .map_err(StepError::Command),
+            Step::Verify { condition, timeout } => {
+                let deadline = Instant::now().checked_add(*timeout);
+                let mut last = None;
+                loop {
+                    let result = read_point(association, *condition).await;
+                    match result {
+                        Ok(Some(x)) if condition.is_satisfied_by(x) => return Ok(()),
+                        Ok(reading) => last = reading,
+                        Err(ref err) => tracing::warn!("verify step read failed: {err}"),
+                    }
+                    let now = Instant::now();
+                    let remaining = match deadline {
+                        Some(deadline) => deadline.saturating_duration_since(now),
+                        None => self.verify_poll_interval,
+                    };
+                    if remaining.is_zero() {
+                        return Err(match result {
+                            Err(err) => err,
+                            Ok(_) => StepError::VerifyTimeout(last),
+                        });
+                    }
+                    tokio::time::sleep(self.verify_poll_interval.min(remaining)).await;
+                }
+            }
+            Step::Delay(delay) => {
+                tokio::time::sleep(*delay).await;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Default for CommandSequence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn read_point(
+    association: &mut AssociationHandle,
+    condition: PointCondition,
+) -> Result<Option<PointReading>, StepError> {
+    let index = condition.index();
+    let value = Arc::new(Mutex::new(None));
+    let handler = PointReader {
+        index,
+        value: value.clone(),
+    };
+    association
+        .read_with_handler(
+            ReadRequest::two_byte_range(condition.variation(), index, index),
+            Box::new(handler),
+        )
+        .await
+        .map_err(StepError::Read)?;
+    let value = *value.lock().unwrap();
+    Ok(value)
+}
+
+/// Captures the value of a single point from a READ response
+struct PointReader {
+    index: u16,
+    value: Arc<Mutex<Option<PointReading>>>,
+}
+
+impl PointReader {
+    fn capture<T>(&self, iter: &mut dyn Iterator<Item = (T, u16)>, convert: fn(T) -> PointReading) {
+        for (x, index) in iter {
+            if index == self.index {
+                *self.value.lock().unwrap() = Some(convert(x));
+            }
+        }
+    }
+}
+
+impl ReadHandler for PointReader {
+    fn handle_binary_input(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (BinaryInput, u16)>,
+    ) {
+        self.capture(iter, |x| {
+            PointReading::new(PointValue::Binary(x.value), x.flags)
+        })
+    }
+
+    fn handle_double_bit_binary_input(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (DoubleBitBinaryInput, u16)>,
+    ) {
+        self.capture(iter, |x| {
+            PointReading::new(PointValue::DoubleBit(x.value), x.flags)
+        })
+    }
+
+    fn handle_binary_output_status(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (BinaryOutputStatus, u16)>,
+    ) {
+        self.capture(iter, |x| {
+            PointReading::new(PointValue::Binary(x.value), x.flags)
+        })
+    }
+
+    fn handle_analog_input(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (AnalogInput, u16)>,
+    ) {
+        self.capture(iter, |x| {
+            PointReading::new(PointValue::Analog(x.value), x.flags)
+        })
+    }
+
+    fn handle_analog_output_status(
+        &mut self,
+        _info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (AnalogOutputStatus, u16)>,
+    ) {
+        self.capture(iter, |x| {
+            PointReading::new(PointValue::Analog(x.value), x.flags)
+        })
+    }
+}
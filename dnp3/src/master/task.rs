@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use tracing::Instrument;
 
 use crate::app::format::write;
@@ -11,9 +13,11 @@ use crate::master::error::TaskError;
 use crate::master::messages::{MasterMsg, Message};
 use crate::master::tasks::{AppTask, AssociationTask, NonReadTask, ReadTask, RequestWriter, Task};
 use crate::master::{Association, MasterChannelConfig};
+use crate::tcp::PeerIdentity;
 use crate::transport::{FragmentAddr, TransportReader, TransportResponse, TransportWriter};
 use crate::util::buffer::Buffer;
 use crate::util::channel::Receiver;
+use crate::util::monitor::ChannelMonitor;
 use crate::util::phys::PhysLayer;
 
 use crate::app::parse::options::ParseOptions;
@@ -36,17 +40,20 @@ impl MasterTask {
         config: MasterChannelConfig,
         messages: Receiver<Message>,
     ) -> Self {
+        let monitor = ChannelMonitor::default();
         let session = MasterSession::new(
             initial_state,
             config.decode_level,
             config.tx_buffer_size,
             messages,
+            monitor.clone(),
         );
         let (reader, writer) = crate::transport::create_master_transport_layer(
             link_modes,
             parse_options,
             config.master_address,
             config.rx_buffer_size,
+            monitor,
         );
         Self {
             session,
@@ -69,10 +76,70 @@ impl MasterTask {
     }
 
     pub(crate) async fn run(&mut self, io: &mut PhysLayer) -> RunError {
+        self.session
+            .monitor
+            .counters
+            .on_session_start(io.medium().remote());
+        self.session.peer_identity = io.peer_identity();
         let ret = self
             .session
             .run(io, &mut self.writer, &mut self.reader)
             .await;
+        self.session.peer_identity = None;
+
+        self.writer.reset();
+        self.reader.reset();
+
+        ret
+    }
+
+    /// Change the link address of the master on subsequent physical layers
+    pub(crate) fn change_master_address(&mut self, address: EndpointAddress) {
+        self.reader.set_local_address(address);
+        self.writer.set_local_address(address);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn master_address(&self) -> EndpointAddress {
+        self.writer.local_address()
+    }
+
+    /// Limit the number of consecutive response timeouts that are tolerated before the session
+    /// considers the physical layer unhealthy and fails with a link error
+    pub(crate) fn set_max_response_timeouts(&mut self, limit: Option<usize>) {
+        self.session.max_response_timeouts = limit;
+        self.session.response_timeouts = 0;
+    }
+
+    /// Run the master on a physical layer until an error occurs, or the `interrupt` future completes
+    /// while the session is idle.
+    ///
+    /// Unlike [`MasterTask::run`], link errors do not reset the state of the associations. This allows
+    /// a task that manages multiple physical layers to resume communications on another layer without
+    /// repeating the startup procedure, i.e. sequence numbers, polls, and the state of
+    /// unsolicited reporting are all preserved.
+    pub(crate) async fn run_until<F>(
+        &mut self,
+        io: &mut PhysLayer,
+        interrupt: F,
+    ) -> Result<F::Output, RunError>
+    where
+        F: std::future::Future,
+    {
+        self.session
+            .monitor
+            .counters
+            .on_session_start(io.medium().remote());
+        self.session.peer_identity = io.peer_identity();
+        let ret = self
+            .session
+            .run_until(io, &mut self.writer, &mut self.reader, interrupt)
+            .await;
+        self.session.peer_identity = None;
+
+        if let Err(RunError::Stop(reason)) = ret {
+            self.session.on_run_error(RunError::Stop(reason));
+        }
 
         self.writer.reset();
         self.reader.reset();
@@ -91,6 +158,11 @@ struct MasterSession {
     associations: AssociationMap,
     messages: Receiver<Message>,
     tx_buffer: Buffer,
+    max_response_timeouts: Option<usize>,
+    response_timeouts: usize,
+    monitor: ChannelMonitor,
+    /// identity of the peer of the current session, if it uses TLS
+    peer_identity: Option<Arc<PeerIdentity>>,
 }
 
 enum ReadResponseAction {
@@ -105,6 +177,7 @@ impl MasterSession {
         decode_level: DecodeLevel,
         tx_buffer_size: BufferSize<249, 2048>,
         messages: Receiver<Message>,
+        monitor: ChannelMonitor,
     ) -> Self {
         Self {
             enabled: initial_state,
@@ -112,6 +185,10 @@ impl MasterSession {
             associations: AssociationMap::new(),
             messages,
             tx_buffer: tx_buffer_size.create_buffer(),
+            max_response_timeouts: None,
+            response_timeouts: 0,
+            monitor,
+            peer_identity: None,
         }
     }
 
@@ -122,6 +199,37 @@ impl MasterSession {
         writer: &mut TransportWriter,
         reader: &mut TransportReader,
     ) -> RunError {
+        let never = std::future::pending::<std::convert::Infallible>();
+        let err = match self.run_until(io, writer, reader, never).await {
+            Ok(x) => match x {},
+            Err(err) => err,
+        };
+        self.on_run_error(err);
+        err
+    }
+
+    fn on_run_error(&mut self, err: RunError) {
+        self.reset(err);
+
+        if RunError::Stop(StopReason::Shutdown) == err {
+            self.messages.close_and_drain();
+        }
+    }
+
+    /// Run the master until an error occurs or the interrupt completes while idle
+    async fn run_until<F>(
+        &mut self,
+        io: &mut PhysLayer,
+        writer: &mut TransportWriter,
+        reader: &mut TransportReader,
+        interrupt: F,
+    ) -> Result<F::Output, RunError>
+    where
+        F: std::future::Future,
+    {
+        tokio::pin!(interrupt);
+        self.response_timeouts = 0;
+
         loop {
             let result = match self.get_next_task() {
                 Next::Now(task) => {
@@ -130,86 +238,85 @@ impl MasterSession {
                     self.run_task(io, task, writer, reader)
                         .instrument(tracing::info_span!("task", "type" = ?id, "dest" = address))
                         .await
+                        .map(|_| None)
+                }
+                Next::NotBefore(time) => {
+                    self.idle_until(Some(time), io, writer, reader, &mut interrupt)
+                        .await
+                }
+                Next::None => {
+                    self.idle_until(None, io, writer, reader, &mut interrupt)
+                        .await
                 }
-                Next::NotBefore(time) => self.idle_until(time, io, writer, reader).await,
-                Next::None => self.idle_forever(io, writer, reader).await,
             };
 
-            if let Err(err) = result {
-                self.reset(err);
+            match result {
+                Ok(None) => {}
+                Ok(Some(x)) => return Ok(x),
+                Err(err) => return Err(err),
+            }
 
-                if RunError::Stop(StopReason::Shutdown) == err {
-                    self.messages.close_and_drain();
+            if let Some(limit) = self.max_response_timeouts {
+                if self.response_timeouts >= limit {
+                    tracing::warn!("{} consecutive response timeouts", self.response_timeouts);
+                    self.response_timeouts = 0;
+                    return Err(RunError::Link(LinkError::Stdio(
+                        std::io::ErrorKind::TimedOut,
+                    )));
                 }
-
-                return err;
             }
         }
     }
 
-    /// Wait until a message is received or a response is received.
+    /// Wait until a message is received, a response is received, we reach the defined time (if any),
+    /// or the interrupt completes.
     ///
     /// Returns an error only if shutdown or link layer error occurred.
-    async fn idle_forever(
+    async fn idle_until<F>(
         &mut self,
+        instant: Option<Instant>,
         io: &mut PhysLayer,
         writer: &mut TransportWriter,
         reader: &mut TransportReader,
-    ) -> Result<(), RunError> {
-        loop {
-            let decode_level = self.decode_level;
-            tokio::select! {
-                result = self.process_message(true) => {
-                   // we need to recheck the tasks
-                   return Ok(result?);
-                }
-                result = reader.read(io, decode_level) => {
-                   result?;
-                   match reader.pop_response() {
-                        Some(TransportResponse::Response(addr, response)) => {
-                            self.notify_link_activity(addr.link);
-                            return self.handle_fragment_while_idle(io, writer, addr, response).await
-                        }
-                        Some(TransportResponse::LinkLayerMessage(msg)) => self.notify_link_activity(msg.source),
-                        Some(TransportResponse::Error(_)) => return Ok(()), // ignore the malformed response
-                        None => return Ok(()),
-                   }
-                }
+        interrupt: &mut std::pin::Pin<&mut F>,
+    ) -> Result<Option<F::Output>, RunError>
+    where
+        F: std::future::Future,
+    {
+        let sleep = async move {
+            match instant {
+                Some(instant) => tokio::time::sleep_until(instant).await,
+                None => std::future::pending().await,
             }
-        }
-    }
+        };
+        tokio::pin!(sleep);
 
-    /// Wait until a message is received, a response is received, or we reach the defined time.
-    ///
-    /// Returns an error only if shutdown or link layer error occured.
-    async fn idle_until(
-        &mut self,
-        instant: Instant,
-        io: &mut PhysLayer,
-        writer: &mut TransportWriter,
-        reader: &mut TransportReader,
-    ) -> Result<(), RunError> {
         loop {
             let decode_level = self.decode_level;
             tokio::select! {
                 result = self.process_message(true) => {
                    // we need to recheck the tasks
-                   return Ok(result?);
+                   result?;
+                   return Ok(None);
                 }
                 result = reader.read(io, decode_level) => {
                    result?;
                    match reader.pop_response() {
                         Some(TransportResponse::Response(addr, response)) => {
                             self.notify_link_activity(addr.link);
-                            return self.handle_fragment_while_idle(io, writer, addr, response).await
+                            self.handle_fragment_while_idle(io, writer, addr, response).await?;
+                            return Ok(None);
                         }
                         Some(TransportResponse::LinkLayerMessage(msg)) => self.notify_link_activity(msg.source),
-                        Some(TransportResponse::Error(_)) => return Ok(()), // ignore the malformed response
-                        None => return Ok(()),
+                        Some(TransportResponse::Error(_)) => return Ok(None), // ignore the malformed response
+                        None => return Ok(None),
                    }
                 }
-                _ = tokio::time::sleep_until(instant) => {
-                   return Ok(());
+                _ = &mut sleep => {
+                   return Ok(None);
+                }
+                x = interrupt.as_mut() => {
+                   return Ok(Some(x));
                 }
             }
         }
@@ -273,6 +380,18 @@ impl MasterSession {
             MasterMsg::GetDecodeLevel(promise) => {
                 promise.complete(Ok(self.decode_level));
             }
+            MasterMsg::SetCapture(capture) => {
+                self.monitor.capture.set(capture);
+            }
+            MasterMsg::SetDecodeSink(sink) => {
+                self.monitor.decode.set(sink);
+            }
+            MasterMsg::GetStatistics(promise) => {
+                promise.complete(Ok(self.monitor.counters.get()));
+            }
+            MasterMsg::GetPeerIdentity(promise) => {
+                promise.complete(Ok(self.peer_identity.as_deref().cloned()));
+            }
         }
     }
 
@@ -329,6 +448,15 @@ impl MasterSession {
             }
         };
 
+        if matches!(result, Err(TaskError::ResponseTimeout)) {
+            self.response_timeouts += 1;
+            if let Ok(association) = self.associations.get_mut(task.dest.link) {
+                association.notify_response_timeout();
+            }
+        } else {
+            self.response_timeouts = 0;
+        }
+
         // if a task error occurs, if might be a run error
         match result {
             Ok(()) => Ok(()),
@@ -485,6 +613,10 @@ impl MasterSession {
             return Ok(None);
         }
 
+        if let Ok(association) = self.associations.get_mut(source.link) {
+            association.notify_solicited_response();
+        }
+
         if !response.header.control.is_fir_and_fin() {
             return Err(TaskError::MultiFragmentResponse);
         }
@@ -614,6 +746,10 @@ impl MasterSession {
             return Ok(ReadResponseAction::Ignore);
         }
 
+        if let Ok(association) = self.associations.get_mut(source.link) {
+            association.notify_solicited_response();
+        }
+
         // now do validations
 
         if response.header.control.fir && !is_first {
@@ -760,6 +896,9 @@ impl MasterSession {
         writer
             .write(io, self.decode_level, addr, cursor.written())
             .await?;
+        if let Ok(association) = self.associations.get_mut(addr.link) {
+            association.notify_request_sent();
+        }
         Ok(seq)
     }
 }
@@ -1,7 +1,12 @@
+use std::time::Duration;
+
+use tokio::time::Instant;
+
 use crate::app::format::write::HeaderWriter;
 use crate::app::parse::parser::{HeaderCollection, Response};
 use crate::app::FunctionCode;
-use crate::master::error::{CommandError, CommandResponseError, TaskError};
+use crate::master::association::Association;
+use crate::master::error::{CommandError, CommandResponseError, SelectError, TaskError};
 use crate::master::promise::Promise;
 use crate::master::request::*;
 use crate::master::tasks::{AppTask, NonReadTask, Task};
@@ -111,3 +116,159 @@ impl CommandTask {
         }
     }
 }
+
+/// SELECT that completes with a [`Selection`] instead of proceeding to OPERATE
+pub(crate) struct SelectTask {
+    headers: CommandHeaders,
+    timeout: Duration,
+    sent: Option<Instant>,
+    promise: Promise<Result<Selection, SelectError>>,
+}
+
+impl From<SelectTask> for Task {
+    fn from(value: SelectTask) -> Self {
+        Task::App(AppTask::NonRead(NonReadTask::Select(value)))
+    }
+}
+
+impl SelectTask {
+    pub(crate) fn new(
+        headers: CommandHeaders,
+        timeout: Duration,
+        promise: Promise<Result<Selection, SelectError>>,
+    ) -> Self {
+        Self {
+            headers,
+            timeout,
+            sent: None,
+            promise,
+        }
+    }
+
+    pub(crate) fn wrap(self) -> NonReadTask {
+        NonReadTask::Select(self)
+    }
+
+    pub(crate) fn start(mut self) -> Self {
+        // the outstation starts its select timer when it receives the request
+        self.sent = Some(Instant::now());
+        self
+    }
+
+    pub(crate) fn function(&self) -> FunctionCode {
+        FunctionCode::Select
+    }
+
+    pub(crate) fn write(&self, writer: &mut HeaderWriter) -> Result<(), scursor::WriteError> {
+        self.headers.write(writer)
+    }
+
+    pub(crate) fn on_task_error(self, err: TaskError) {
+        self.promise.complete(Err(err.into()))
+    }
+
+    pub(crate) fn handle(
+        self,
+        association: &mut Association,
+        response: Response,
+    ) -> Result<Option<NonReadTask>, TaskError> {
+        let headers = match response.objects {
+            Ok(x) => x,
+            Err(err) => {
+                self.promise
+                    .complete(Err(TaskError::MalformedResponse(err).into()));
+                return Err(TaskError::MalformedResponse(err));
+            }
+        };
+
+        if let Err(err) = self.headers.compare(headers) {
+            self.promise.complete(Err(err.into()));
+            return Err(TaskError::UnexpectedResponseHeaders);
+        }
+
+        // a select timeout too large to represent never elapses
+        let deadline = self
+            .sent
+            .unwrap_or_else(Instant::now)
+            .checked_add(self.timeout);
+        let id = association.begin_selection(deadline);
+        self.promise.complete(Ok(Selection {
+            id,
+            headers: self.headers,
+            deadline,
+        }));
+        Ok(None)
+    }
+}
+
+/// OPERATE of a selection made by a previous [`SelectTask`]
+pub(crate) struct OperateSelectedTask {
+    id: u64,
+    headers: CommandHeaders,
+    promise: Promise<Result<(), SelectError>>,
+}
+
+impl From<OperateSelectedTask> for Task {
+    fn from(value: OperateSelectedTask) -> Self {
+        Task::App(AppTask::NonRead(NonReadTask::OperateSelected(value)))
+    }
+}
+
+impl OperateSelectedTask {
+    pub(crate) fn new(selection: Selection, promise: Promise<Result<(), SelectError>>) -> Self {
+        Self {
+            id: selection.id,
+            headers: selection.headers,
+            promise,
+        }
+    }
+
+    pub(crate) fn wrap(self) -> NonReadTask {
+        NonReadTask::OperateSelected(self)
+    }
+
+    /// identifier of the selection operated by this task
+    pub(crate) fn selection_id(&self) -> u64 {
+        self.id
+    }
+
+    pub(crate) fn start(self, association: &mut Association) -> Option<Self> {
+        if let Err(err) = association.take_selection(self.id) {
+            self.promise.complete(Err(err));
+            return None;
+        }
+
+        Some(self)
+    }
+
+    pub(crate) fn function(&self) -> FunctionCode {
+        FunctionCode::Operate
+    }
+
+    pub(crate) fn write(&self, writer: &mut HeaderWriter) -> Result<(), scursor::WriteError> {
+        self.headers.write(writer)
+    }
+
+    pub(crate) fn on_task_error(self, err: TaskError) {
+        self.promise.complete(Err(err.into()))
+    }
+
+    pub(crate) fn handle(self, response: Response) -> Result<Option<NonReadTask>, TaskError> {
+        let headers = match response.objects {
+            Ok(x) => x,
+            Err(err) => {
+                self.promise
+                    .complete(Err(TaskError::MalformedResponse(err).into()));
+                return Err(TaskError::MalformedResponse(err));
+            }
+        };
+
+        if let Err(err) = self.headers.compare(headers) {
+            self.promise.complete(Err(err.into()));
+            return Err(TaskError::UnexpectedResponseHeaders);
+        }
+
+        self.promise.complete(Ok(()));
+        Ok(None)
+    }
+}
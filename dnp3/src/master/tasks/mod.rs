@@ -9,10 +9,11 @@ use crate::master::poll::Poll;
 use crate::master::promise::Promise;
 use crate::master::request::{Classes, EventClasses};
 use crate::master::tasks::auto::AutoTask;
-use crate::master::tasks::command::CommandTask;
+use crate::master::tasks::command::{CommandTask, OperateSelectedTask, SelectTask};
 use crate::master::tasks::read::SingleReadTask;
 use crate::master::tasks::restart::RestartTask;
 use crate::master::tasks::time::TimeSyncTask;
+use crate::master::tasks::write::WriteTask;
 use crate::master::{ReadType, TaskType};
 
 use crate::master::tasks::deadbands::WriteDeadBandsTask;
@@ -34,6 +35,7 @@ pub(crate) mod file;
 pub(crate) mod read;
 pub(crate) mod restart;
 pub(crate) mod time;
+pub(crate) mod write;
 
 /// Queued task requiring I/O
 pub(crate) struct AssociationTask {
@@ -121,6 +123,16 @@ impl Task {
         Some(self)
     }
 
+    /// identifier of the selection operated by the task, if any
+    pub(crate) fn selection_id(&self) -> Option<u64> {
+        match self {
+            Task::App(AppTask::NonRead(NonReadTask::OperateSelected(task))) => {
+                Some(task.selection_id())
+            }
+            _ => None,
+        }
+    }
+
     pub(crate) fn get_id(&self) -> TaskId {
         match self {
             Task::LinkStatus(_) => TaskId::LinkStatus,
@@ -150,12 +162,18 @@ pub(crate) enum NonReadTask {
     Auto(AutoTask),
     /// commands initiated from the user API
     Command(CommandTask),
+    /// select initiated from the user API, operated later by a separate command
+    Select(SelectTask),
+    /// operate of a selection made by a previous select
+    OperateSelected(OperateSelectedTask),
     /// time synchronization
     TimeSync(TimeSyncTask),
     /// restart operation
     Restart(RestartTask),
     /// write dead-bands
     DeadBands(WriteDeadBandsTask),
+    /// write static values
+    Write(WriteTask),
     /// Generic task for anything that doesn't have response object headers
     EmptyResponseTask(EmptyResponseTask),
     /// Read file from the outstation
@@ -197,9 +215,12 @@ impl RequestWriter for NonReadTask {
         match self {
             NonReadTask::Auto(t) => t.write(writer)?,
             NonReadTask::Command(t) => t.write(writer)?,
+            NonReadTask::Select(t) => t.write(writer)?,
+            NonReadTask::OperateSelected(t) => t.write(writer)?,
             NonReadTask::TimeSync(t) => t.write(writer)?,
             NonReadTask::Restart(_) => {}
             NonReadTask::DeadBands(t) => t.write(writer)?,
+            NonReadTask::Write(t) => t.write(writer)?,
             NonReadTask::EmptyResponseTask(t) => t.write(writer)?,
             NonReadTask::FileRead(t) => t.write(writer)?,
             NonReadTask::GetFileInfo(t) => t.write(writer)?,
@@ -298,10 +319,13 @@ impl NonReadTask {
     pub(crate) fn start(self, association: &mut Association) -> Option<NonReadTask> {
         match self {
             Self::Command(_) => Some(self),
+            Self::Select(task) => Some(task.start().wrap()),
+            Self::OperateSelected(task) => task.start(association).map(|task| task.wrap()),
             Self::Auto(_) => Some(self),
             Self::TimeSync(task) => task.start(association).map(|task| task.wrap()),
             Self::Restart(_) => Some(self),
             Self::DeadBands(_) => Some(self),
+            Self::Write(_) => Some(self),
             Self::EmptyResponseTask(_) => Some(self),
             Self::FileRead(_) => Some(self),
             Self::GetFileInfo(_) => Some(self),
@@ -315,10 +339,13 @@ impl NonReadTask {
     pub(crate) fn function(&self) -> FunctionCode {
         match self {
             Self::Command(task) => task.function(),
+            Self::Select(task) => task.function(),
+            Self::OperateSelected(task) => task.function(),
             Self::Auto(task) => task.function(),
             Self::TimeSync(task) => task.function(),
             Self::Restart(task) => task.function(),
             Self::DeadBands(task) => task.function(),
+            Self::Write(task) => task.function(),
             Self::EmptyResponseTask(task) => task.function(),
             Self::FileRead(task) => task.function(),
             Self::GetFileInfo(task) => task.function(),
@@ -332,10 +359,13 @@ impl NonReadTask {
     pub(crate) fn on_task_error(self, association: Option<&mut Association>, err: TaskError) {
         match self {
             Self::Command(task) => task.on_task_error(err),
+            Self::Select(task) => task.on_task_error(err),
+            Self::OperateSelected(task) => task.on_task_error(err),
             Self::TimeSync(task) => task.on_task_error(association, err),
             Self::Auto(task) => task.on_task_error(association, err),
             Self::Restart(task) => task.on_task_error(err),
             Self::DeadBands(task) => task.on_task_error(err),
+            Self::Write(task) => task.on_task_error(err),
             Self::EmptyResponseTask(task) => task.on_task_error(err),
             Self::FileRead(task) => task.on_task_error(err),
             Self::GetFileInfo(task) => task.on_task_error(err),
@@ -353,10 +383,13 @@ impl NonReadTask {
     ) -> Result<Option<NonReadTask>, TaskError> {
         match self {
             Self::Command(task) => task.handle(response),
+            Self::Select(task) => task.handle(association, response),
+            Self::OperateSelected(task) => task.handle(response),
             Self::Auto(task) => task.handle(association, response),
             Self::TimeSync(task) => task.handle(association, response),
             Self::Restart(task) => task.handle(response),
             Self::DeadBands(task) => task.handle(response),
+            Self::Write(task) => task.handle(response),
             Self::EmptyResponseTask(task) => task.handle(response),
             Self::FileRead(task) => task.handle(response).await,
             Self::GetFileInfo(task) => task.handle(response),
@@ -370,6 +403,8 @@ impl NonReadTask {
     pub(crate) fn as_task_type(&self) -> TaskType {
         match self {
             Self::Command(_) => TaskType::Command,
+            Self::Select(_) => TaskType::Command,
+            Self::OperateSelected(_) => TaskType::Command,
             Self::Auto(x) => match x {
                 AutoTask::ClearRestartBit => TaskType::ClearRestartBit,
                 AutoTask::EnableUnsolicited(_) => TaskType::EnableUnsolicited,
@@ -378,6 +413,7 @@ impl NonReadTask {
             Self::TimeSync(_) => TaskType::TimeSync,
             Self::Restart(_) => TaskType::Restart,
             Self::DeadBands(_) => TaskType::WriteDeadBands,
+            Self::Write(_) => TaskType::GenericEmptyResponse(self.function()),
             Self::EmptyResponseTask(_) => TaskType::GenericEmptyResponse(self.function()),
             Self::FileRead(_) => TaskType::FileRead,
             Self::GetFileInfo(_) => TaskType::GetFileInfo,
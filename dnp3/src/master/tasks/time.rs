@@ -9,20 +9,41 @@ use crate::app::Timestamp;
 use crate::master::association::Association;
 use crate::master::error::{TaskError, TimeSyncError};
 use crate::master::promise::Promise;
-use crate::master::request::TimeSyncProcedure;
+use crate::master::request::{TimeSyncProcedure, UnsuitableClockAction};
 use crate::master::tasks::{AppTask, NonReadTask, Task};
+use crate::master::{TimeReading, TimeSyncReport};
 
 use tokio::time::Instant;
 
 enum State {
-    MeasureDelay(Option<Instant>),
+    MeasureDelay,
     WriteAbsoluteTime(Option<Timestamp>),
     RecordCurrentTime(Option<Timestamp>),
     WriteLastRecordedTime(Timestamp),
 }
 
+impl State {
+    /// True if this is the first request of the procedure, before the master clock was read
+    fn is_initial(&self) -> bool {
+        match self {
+            State::MeasureDelay => true,
+            State::WriteAbsoluteTime(x) => x.is_none(),
+            State::RecordCurrentTime(_) => true,
+            State::WriteLastRecordedTime(_) => false,
+        }
+    }
+}
+
 pub(crate) struct TimeSyncTask {
     state: State,
+    procedure: TimeSyncProcedure,
+    /// reading of the master clock used to compute the time written to the outstation
+    reading: Option<TimeReading>,
+    /// when the first request of the procedure was started
+    sent: Option<Instant>,
+    round_trip: Option<Duration>,
+    outstation_delay: Option<Duration>,
+    propagation_delay: Option<Duration>,
     promise: Option<Promise<Result<(), TimeSyncError>>>,
 }
 
@@ -36,26 +57,31 @@ impl TimeSyncProcedure {
     fn get_start_state(&self) -> State {
         match self {
             TimeSyncProcedure::Lan => State::RecordCurrentTime(None),
-            TimeSyncProcedure::NonLan => State::MeasureDelay(None),
+            TimeSyncProcedure::NonLan => State::MeasureDelay,
             TimeSyncProcedure::DirectWriteAbsTime => State::WriteAbsoluteTime(None),
         }
     }
 }
 
 impl TimeSyncTask {
-    fn new(state: State, promise: Option<Promise<Result<(), TimeSyncError>>>) -> Self {
-        Self { state, promise }
-    }
-
     fn change_state(self, state: State) -> Self {
-        TimeSyncTask::new(state, self.promise)
+        Self { state, ..self }
     }
 
     pub(crate) fn get_procedure(
         procedure: TimeSyncProcedure,
         promise: Option<Promise<Result<(), TimeSyncError>>>,
     ) -> Self {
-        Self::new(procedure.get_start_state(), promise)
+        Self {
+            state: procedure.get_start_state(),
+            procedure,
+            reading: None,
+            sent: None,
+            round_trip: None,
+            outstation_delay: None,
+            propagation_delay: None,
+            promise,
+        }
     }
 
     pub(crate) fn wrap(self) -> NonReadTask {
@@ -63,50 +89,53 @@ impl TimeSyncTask {
     }
 
     pub(crate) fn start(mut self, association: &mut Association) -> Option<Self> {
-        match &mut self.state {
-            State::MeasureDelay(time) => {
-                time.replace(Instant::now());
-
-                match association.get_system_time() {
-                    Some(_) => Some(self),
-                    None => {
-                        self.report_error(association, TimeSyncError::SystemTimeNotAvailable);
-                        None
-                    }
-                }
+        if !self.state.is_initial() {
+            return Some(self);
+        }
+
+        let reading = match association.get_time_reading() {
+            Some(x) => x,
+            None => {
+                self.report_error(association, TimeSyncError::SystemTimeNotAvailable);
+                return None;
             }
-            State::WriteAbsoluteTime(time) => {
-                if time.is_none() {
-                    *time = association.get_system_time();
-                    match time {
-                        Some(_) => Some(self),
-                        None => {
-                            self.report_error(association, TimeSyncError::SystemTimeNotAvailable);
-                            None
-                        }
-                    }
-                } else {
-                    Some(self)
+        };
+
+        let policy = association.time_sync_policy();
+        if let Some(reason) = policy.check(&reading) {
+            // synchronizations requested by the user always proceed
+            let action = match self.promise {
+                Some(_) => UnsuitableClockAction::Proceed,
+                None => policy.action,
+            };
+            association.notify_time_sync_unsuitable_clock(reading, action);
+            match action {
+                UnsuitableClockAction::Proceed => {
+                    tracing::warn!("synchronizing outstation even though {reason}");
+                }
+                action => {
+                    tracing::warn!("not synchronizing outstation because {reason}");
+                    association.on_time_sync_unsuitable_clock(action);
+                    return None;
                 }
             }
-            State::RecordCurrentTime(time) => {
-                *time = association.get_system_time();
+        }
 
-                match time {
-                    Some(_) => Some(self),
-                    None => {
-                        self.report_error(association, TimeSyncError::SystemTimeNotAvailable);
-                        None
-                    }
-                }
+        match &mut self.state {
+            State::WriteAbsoluteTime(time) | State::RecordCurrentTime(time) => {
+                *time = Some(reading.timestamp);
             }
-            State::WriteLastRecordedTime(_) => Some(self),
+            State::MeasureDelay | State::WriteLastRecordedTime(_) => {}
         }
+
+        self.reading = Some(reading);
+        self.sent = Some(Instant::now());
+        Some(self)
     }
 
     pub(crate) fn function(&self) -> FunctionCode {
         match self.state {
-            State::MeasureDelay(_) => FunctionCode::DelayMeasure,
+            State::MeasureDelay => FunctionCode::DelayMeasure,
             State::WriteAbsoluteTime(_) => FunctionCode::Write,
             State::RecordCurrentTime(_) => FunctionCode::RecordCurrentTime,
             State::WriteLastRecordedTime(_) => FunctionCode::Write,
@@ -115,7 +144,7 @@ impl TimeSyncTask {
 
     pub(crate) fn write(&self, writer: &mut HeaderWriter) -> Result<(), scursor::WriteError> {
         match self.state {
-            State::MeasureDelay(_) => Ok(()),
+            State::MeasureDelay => Ok(()),
             State::WriteAbsoluteTime(x) => writer.write_count_of_one(Group50Var1 {
                 time: x.expect("WriteAbsoluteTime timestamp must be set by start()"),
             }),
@@ -136,12 +165,26 @@ impl TimeSyncTask {
     }
 
     pub(crate) fn handle(
-        self,
+        mut self,
         association: &mut Association,
         response: Response,
     ) -> Result<Option<NonReadTask>, TaskError> {
+        // the round trip is measured using the first request of the procedure
+        if self.round_trip.is_none() {
+            let sent = self.sent.unwrap_or_else(Instant::now);
+            match Instant::now().checked_duration_since(sent) {
+                Some(x) => self.round_trip = Some(x),
+                None => {
+                    // This should NEVER happen. `tokio::time::Instant` is guaranteed to be monotonic
+                    tracing::error!("clock rollback detected while synchronizing outstation");
+                    self.report_error(association, TimeSyncError::ClockRollback);
+                    return Err(TaskError::UnexpectedResponseHeaders);
+                }
+            }
+        }
+
         match self.state {
-            State::MeasureDelay(time) => self.handle_delay_measure(association, time, response),
+            State::MeasureDelay => self.handle_delay_measure(association, response),
             State::WriteAbsoluteTime(_) => self.handle_write_absolute_time(association, response),
             State::RecordCurrentTime(time) => {
                 self.handle_record_current_time(association, time, response)
@@ -153,23 +196,11 @@ impl TimeSyncTask {
     }
 
     fn handle_delay_measure(
-        self,
+        mut self,
         association: &mut Association,
-        request_tx: Option<Instant>,
         response: Response,
     ) -> Result<Option<NonReadTask>, TaskError> {
-        let request_tx = request_tx.unwrap_or_else(Instant::now);
-        let now = Instant::now();
-
-        let interval = match now.checked_duration_since(request_tx) {
-            Some(x) => x,
-            None => {
-                // This should NEVER happen. `tokio::time::Instant` is guaranteed to be monotonic
-                tracing::error!("clock rollback detected while synchronizing outstation");
-                self.report_error(association, TimeSyncError::ClockRollback);
-                return Err(TaskError::UnexpectedResponseHeaders);
-            }
-        };
+        let interval = self.round_trip.unwrap_or_default();
 
         let header = match response.get_only_object_header() {
             Ok(x) => x,
@@ -207,8 +238,8 @@ impl TimeSyncTask {
                 }
             };
 
-        let time = match association.get_system_time() {
-            Some(time) => time,
+        let reading = match association.get_time_reading() {
+            Some(x) => x,
             None => {
                 tracing::warn!("system time not available");
                 self.report_error(association, TimeSyncError::SystemTimeNotAvailable);
@@ -216,7 +247,7 @@ impl TimeSyncTask {
             }
         };
 
-        let timestamp = match Self::get_timestamp(time, propagation_delay) {
+        let timestamp = match Self::get_timestamp(reading.timestamp, propagation_delay) {
             Err(err) => {
                 tracing::error!("{}", err);
                 self.report_error(association, err);
@@ -225,6 +256,10 @@ impl TimeSyncTask {
             Ok(ts) => ts,
         };
 
+        self.reading = Some(reading);
+        self.outstation_delay = Some(Duration::from_millis(delay_ms as u64));
+        self.propagation_delay = Some(propagation_delay);
+
         Ok(Some(
             self.change_state(State::WriteAbsoluteTime(Some(timestamp)))
                 .wrap(),
@@ -307,6 +342,16 @@ impl TimeSyncTask {
     }
 
     fn report_success(self, association: &mut Association) {
+        if let Some(reading) = self.reading {
+            association.notify_time_sync_success(TimeSyncReport {
+                procedure: self.procedure,
+                reading,
+                round_trip_delay: self.round_trip.unwrap_or_default(),
+                outstation_delay: self.outstation_delay,
+                propagation_delay: self.propagation_delay,
+            });
+        }
+
         match self.promise {
             None => association.on_time_sync_success(),
             Some(x) => x.complete(Ok(())),
@@ -656,7 +701,7 @@ mod tests {
         fn no_system_time_at_start() {
             let (task, _system_time, mut association, mut rx) =
                 non_lan_time_sync_single_time_setup();
-            association.get_system_time(); // Empty the time
+            association.get_time_reading(); // Empty the time
 
             assert!(task.start(&mut association).is_none());
             assert_eq!(
@@ -896,7 +941,7 @@ mod tests {
         #[test]
         fn no_system_time_available() {
             let (task, _system_time, mut association, mut rx) = lan_time_sync_setup();
-            association.get_system_time(); // Empty the time
+            association.get_time_reading(); // Empty the time
 
             assert!(task.start(&mut association).is_none());
             assert_eq!(
@@ -934,7 +979,7 @@ mod tests {
 
             assert_eq!(request.header.function, FunctionCode::RecordCurrentTime);
             //assert!(request.raw_objects.is_empty());
-            assert!(association.get_system_time().is_none());
+            assert!(association.get_time_reading().is_none());
             task
         }
 
@@ -1021,7 +1066,7 @@ mod tests {
         #[test]
         fn no_system_time_available() {
             let (task, _system_time, mut association, mut rx) = direct_write_setup();
-            association.get_system_time(); // Empty the time
+            association.get_time_reading(); // Empty the time
 
             assert!(task.start(&mut association).is_none());
             assert_eq!(
@@ -1041,4 +1086,206 @@ mod tests {
             })
         }
     }
+
+    mod clock_quality {
+        use crate::app::variations::Group52Var2;
+        use crate::master::request::{TimeSyncPolicy, UnsuitableClockAction};
+        use crate::master::{ClockSyncStatus, TimeReading, TimeSyncReport};
+
+        use super::*;
+
+        struct QualityHandler {
+            reading: TimeReading,
+        }
+
+        impl AssociationHandler for QualityHandler {
+            fn get_time_reading(&self) -> Option<TimeReading> {
+                Some(self.reading)
+            }
+        }
+
+        #[derive(Default)]
+        struct Reports {
+            success: Vec<TimeSyncReport>,
+            unsuitable: Vec<UnsuitableClockAction>,
+        }
+
+        type SharedReports = Arc<Mutex<Reports>>;
+
+        struct ReportInformation(SharedReports);
+
+        impl AssociationInformation for ReportInformation {
+            fn time_sync_success(&mut self, report: TimeSyncReport) {
+                self.0.lock().unwrap().success.push(report);
+            }
+
+            fn time_sync_unsuitable_clock(
+                &mut self,
+                _reading: TimeReading,
+                action: UnsuitableClockAction,
+            ) {
+                self.0.lock().unwrap().unsuitable.push(action);
+            }
+        }
+
+        fn setup(
+            procedure: TimeSyncProcedure,
+            reading: TimeReading,
+            policy: TimeSyncPolicy,
+        ) -> (
+            NonReadTask,
+            Association,
+            SharedReports,
+            tokio::sync::oneshot::Receiver<Result<(), TimeSyncError>>,
+        ) {
+            let dest = FragmentAddr {
+                link: EndpointAddress::try_new(1).unwrap(),
+                phys: PhysAddr::None,
+            };
+            let config = AssociationConfig {
+                time_sync_policy: policy,
+                ..AssociationConfig::default()
+            };
+            let reports = Arc::new(Mutex::new(Reports::default()));
+            let association = Association::new(
+                dest,
+                config,
+                Box::new(NullReadHandler),
+                Box::new(QualityHandler { reading }),
+                Box::new(ReportInformation(reports.clone())),
+            );
+            let (promise, rx) = Promise::one_shot();
+            let task = NonReadTask::TimeSync(TimeSyncTask::get_procedure(procedure, Some(promise)));
+            (task, association, reports, rx)
+        }
+
+        fn reading(status: ClockSyncStatus) -> TimeReading {
+            TimeReading::new(Timestamp::new(1_000_000), status)
+        }
+
+        #[test]
+        fn unsynchronized_clock_skips_automatic_sync() {
+            let (_, mut association, reports, _) = setup(
+                TimeSyncProcedure::Lan,
+                reading(ClockSyncStatus::Unsynchronized),
+                TimeSyncPolicy::skip_unsynchronized(None),
+            );
+            let task =
+                NonReadTask::TimeSync(TimeSyncTask::get_procedure(TimeSyncProcedure::Lan, None));
+
+            assert!(task.start(&mut association).is_none());
+            assert_eq!(
+                reports.lock().unwrap().unsuitable,
+                [UnsuitableClockAction::Skip]
+            );
+        }
+
+        #[test]
+        fn inaccurate_clock_reports_user_request_that_proceeds() {
+            let (task, mut association, reports, mut rx) = setup(
+                TimeSyncProcedure::DirectWriteAbsTime,
+                reading(ClockSyncStatus::Synchronized).with_accuracy(Duration::from_millis(50)),
+                TimeSyncPolicy::skip_unsynchronized(Some(Duration::from_millis(10))),
+            );
+
+            let task = task.start(&mut association).unwrap();
+            assert_eq!(task.function(), FunctionCode::Write);
+            assert!(rx.try_recv().is_err());
+            assert_eq!(
+                reports.lock().unwrap().unsuitable,
+                [UnsuitableClockAction::Proceed]
+            );
+        }
+
+        #[test]
+        fn unsynchronized_clock_proceeds_by_default() {
+            let (task, mut association, _, _rx) = setup(
+                TimeSyncProcedure::Lan,
+                reading(ClockSyncStatus::Unsynchronized),
+                TimeSyncPolicy::default(),
+            );
+
+            let task = task.start(&mut association).unwrap();
+            assert_eq!(task.function(), FunctionCode::RecordCurrentTime);
+        }
+
+        #[test]
+        fn policy_checks() {
+            let policy = TimeSyncPolicy {
+                action: UnsuitableClockAction::Delay(Duration::from_secs(5)),
+                max_clock_error: Some(Duration::from_millis(10)),
+                reject_leap_smear: true,
+            };
+
+            assert!(policy.check(&reading(ClockSyncStatus::Unknown)).is_none());
+            assert!(policy
+                .check(&reading(ClockSyncStatus::Synchronized))
+                .is_none());
+            assert!(policy
+                .check(&reading(ClockSyncStatus::Unsynchronized))
+                .is_some());
+            assert!(policy
+                .check(
+                    &reading(ClockSyncStatus::Synchronized)
+                        .with_accuracy(Duration::from_millis(10))
+                )
+                .is_none());
+            assert!(policy
+                .check(
+                    &reading(ClockSyncStatus::Synchronized)
+                        .with_accuracy(Duration::from_millis(11))
+                )
+                .is_some());
+            assert!(policy
+                .check(&reading(ClockSyncStatus::Synchronized).with_leap_smear(true))
+                .is_some());
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn non_lan_reports_measured_delays() {
+            let clock = reading(ClockSyncStatus::Synchronized);
+            let (task, mut association, reports, mut rx) =
+                setup(TimeSyncProcedure::NonLan, clock, TimeSyncPolicy::default());
+
+            let task = task.start(&mut association).unwrap();
+            tokio::time::advance(Duration::from_millis(200)).await;
+
+            let task = {
+                let mut buffer = [0; 20];
+                let mut cursor = WriteCursor::new(&mut buffer);
+                let mut writer = start_response(
+                    response_control_field(Sequence::default()),
+                    ResponseFunction::Response,
+                    Iin::default(),
+                    &mut cursor,
+                )
+                .unwrap();
+                writer
+                    .write_count_of_one(Group52Var2 { time: 100 })
+                    .unwrap();
+                let response = writer.to_parsed().to_response().unwrap();
+                task.handle_response(&mut association, response)
+                    .await
+                    .unwrap()
+                    .unwrap()
+            };
+
+            let task = task.start(&mut association).unwrap();
+            tokio::time::advance(Duration::from_millis(30)).await;
+            send_write_response(task, &mut association).await;
+            assert!(rx.try_recv().unwrap().is_ok());
+
+            let reports = reports.lock().unwrap();
+            assert_eq!(
+                reports.success.as_slice(),
+                &[TimeSyncReport {
+                    procedure: TimeSyncProcedure::NonLan,
+                    reading: clock,
+                    round_trip_delay: Duration::from_millis(200),
+                    outstation_delay: Some(Duration::from_millis(100)),
+                    propagation_delay: Some(Duration::from_millis(50)),
+                }]
+            );
+        }
+    }
 }
@@ -0,0 +1,221 @@
+use scursor::WriteCursor;
+
+use crate::app::format::write::HeaderWriter;
+use crate::app::measurement::{OctetString, ToVariation};
+use crate::app::parse::parser::Response;
+use crate::app::parse::traits::{FixedSize, FixedSizeVariation, Index};
+use crate::app::variations::*;
+use crate::app::FunctionCode;
+use crate::master::promise::Promise;
+use crate::master::tasks::{AppTask, NonReadTask, Task};
+use crate::master::{BadEncoding, TaskError, WriteError, WriteHeader, WriteRequest, WriteValues};
+use crate::outstation::database::{StaticAnalogOutputStatusVariation, StaticCounterVariation};
+
+type WriteFn<T> = fn(&T, &mut WriteCursor) -> Result<(), scursor::WriteError>;
+
+pub(crate) struct WriteTask {
+    request: WriteRequest,
+    promise: Promise<Result<(), WriteError>>,
+}
+
+impl From<WriteTask> for Task {
+    fn from(value: WriteTask) -> Self {
+        Task::App(AppTask::NonRead(NonReadTask::Write(value)))
+    }
+}
+
+impl WriteTask {
+    pub(crate) fn new(request: WriteRequest, promise: Promise<Result<(), WriteError>>) -> Self {
+        Self { request, promise }
+    }
+
+    pub(crate) const fn function(&self) -> FunctionCode {
+        FunctionCode::Write
+    }
+
+    pub(crate) fn write(&self, writer: &mut HeaderWriter) -> Result<(), TaskError> {
+        for header in self.request.headers.iter() {
+            header.write(writer)?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn on_task_error(self, err: TaskError) {
+        let err = match err {
+            TaskError::RejectedByIin2(iin) => WriteError::IinError(iin.iin2),
+            err => err.into(),
+        };
+        self.promise.complete(Err(err))
+    }
+
+    pub(crate) fn handle(self, response: Response) -> Result<Option<NonReadTask>, TaskError> {
+        if !response.raw_objects.is_empty() {
+            self.promise
+                .complete(Err(WriteError::Task(TaskError::UnexpectedResponseHeaders)));
+            return Err(TaskError::UnexpectedResponseHeaders);
+        }
+
+        self.promise.complete(Ok(()));
+
+        Ok(None)
+    }
+}
+
+impl WriteHeader {
+    fn write(&self, writer: &mut HeaderWriter) -> Result<(), TaskError> {
+        match self {
+            Self::BinaryOutputStatus(values) => write_fixed::<_, Group10Var2>(writer, values),
+            Self::Counter(variation, values) => match variation {
+                StaticCounterVariation::Group20Var1 => {
+                    write_fixed::<_, Group20Var1>(writer, values)
+                }
+                StaticCounterVariation::Group20Var2 => {
+                    write_fixed::<_, Group20Var2>(writer, values)
+                }
+                StaticCounterVariation::Group20Var5 => {
+                    write_fixed::<_, Group20Var5>(writer, values)
+                }
+                StaticCounterVariation::Group20Var6 => {
+                    write_fixed::<_, Group20Var6>(writer, values)
+                }
+            },
+            Self::AnalogOutputStatus(variation, values) => match variation {
+                StaticAnalogOutputStatusVariation::Group40Var1 => {
+                    write_fixed::<_, Group40Var1>(writer, values)
+                }
+                StaticAnalogOutputStatusVariation::Group40Var2 => {
+                    write_fixed::<_, Group40Var2>(writer, values)
+                }
+                StaticAnalogOutputStatusVariation::Group40Var3 => {
+                    write_fixed::<_, Group40Var3>(writer, values)
+                }
+                StaticAnalogOutputStatusVariation::Group40Var4 => {
+                    write_fixed::<_, Group40Var4>(writer, values)
+                }
+            },
+            Self::OctetString(values) => write_octet_strings(writer, values),
+        }
+    }
+}
+
+fn write_fixed<T, V>(writer: &mut HeaderWriter, values: &WriteValues<T>) -> Result<(), TaskError>
+where
+    T: ToVariation<V>,
+    V: FixedSizeVariation,
+{
+    fn write<T, V>(value: &T, cursor: &mut WriteCursor) -> Result<(), scursor::WriteError>
+    where
+        T: ToVariation<V>,
+        V: FixedSize,
+    {
+        value.to_variation().write(cursor)
+    }
+
+    match values {
+        WriteValues::RangeU8(start, items) => {
+            write_range::<u8, T>(writer, V::VARIATION, *start as usize, items, write)
+        }
+        WriteValues::RangeU16(start, items) => {
+            write_range::<u16, T>(writer, V::VARIATION, *start as usize, items, write)
+        }
+        WriteValues::PrefixedU8(items) => write_prefixed(writer, V::VARIATION, items, write),
+        WriteValues::PrefixedU16(items) => write_prefixed(writer, V::VARIATION, items, write),
+    }
+}
+
+fn write_octet_strings(
+    writer: &mut HeaderWriter,
+    values: &WriteValues<OctetString>,
+) -> Result<(), TaskError> {
+    fn write(value: &OctetString, cursor: &mut WriteCursor) -> Result<(), scursor::WriteError> {
+        cursor.write_bytes(value.value())
+    }
+
+    // each run of strings with the same length is written as a separate header
+    match values {
+        WriteValues::RangeU8(start, items) => {
+            for_each_run(items, OctetString::len, |offset, run| {
+                let variation = Variation::Group110(run[0].len());
+                write_range::<u8, _>(writer, variation, *start as usize + offset, run, write)
+            })
+        }
+        WriteValues::RangeU16(start, items) => {
+            for_each_run(items, OctetString::len, |offset, run| {
+                let variation = Variation::Group110(run[0].len());
+                write_range::<u16, _>(writer, variation, *start as usize + offset, run, write)
+            })
+        }
+        WriteValues::PrefixedU8(items) => for_each_run(
+            items,
+            |(x, _)| x.len(),
+            |_, run| write_prefixed(writer, Variation::Group110(run[0].0.len()), run, write),
+        ),
+        WriteValues::PrefixedU16(items) => for_each_run(
+            items,
+            |(x, _)| x.len(),
+            |_, run| write_prefixed(writer, Variation::Group110(run[0].0.len()), run, write),
+        ),
+    }
+}
+
+fn for_each_run<T>(
+    items: &[T],
+    len: fn(&T) -> u8,
+    mut action: impl FnMut(usize, &[T]) -> Result<(), TaskError>,
+) -> Result<(), TaskError> {
+    let mut offset = 0;
+    while let Some(first) = items.get(offset) {
+        let length = len(first);
+        let count = items[offset..]
+            .iter()
+            .take_while(|x| len(x) == length)
+            .count();
+        action(offset, &items[offset..offset + count])?;
+        offset += count;
+    }
+    Ok(())
+}
+
+fn write_range<I, T>(
+    writer: &mut HeaderWriter,
+    variation: Variation,
+    start: usize,
+    items: &[T],
+    write: WriteFn<T>,
+) -> Result<(), TaskError>
+where
+    I: Index + TryFrom<usize>,
+{
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    let stop = to_index::<I>(start + items.len() - 1)?;
+    writer.write_range_with(variation, to_index::<I>(start)?, stop, items, write)?;
+    Ok(())
+}
+
+fn write_prefixed<I, T>(
+    writer: &mut HeaderWriter,
+    variation: Variation,
+    items: &[(T, I)],
+    write: WriteFn<T>,
+) -> Result<(), TaskError>
+where
+    I: Index + TryFrom<usize>,
+{
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    writer.write_prefixed_with(variation, to_index::<I>(items.len())?, items, write)?;
+    Ok(())
+}
+
+fn to_index<I>(value: usize) -> Result<I, TaskError>
+where
+    I: TryFrom<usize>,
+{
+    I::try_from(value).map_err(|_| TaskError::BadEncoding(BadEncoding::IndexOverflow))
+}
@@ -1,8 +1,11 @@
+use std::time::Duration;
+
+use super::harness::AssocInfoEvent;
 use crate::app::Sequence;
-use crate::app::{Iin, Iin1, Iin2};
+use crate::app::{FunctionCode, Iin, Iin1, Iin2};
 use crate::master::association::AssociationConfig;
 use crate::master::request::EventClasses;
-use crate::master::Classes;
+use crate::master::{Classes, TimeSyncProcedure};
 
 use super::harness::create_association;
 use super::harness::requests::*;
@@ -119,3 +122,39 @@ async fn auto_event_class_scan_disabled() {
     // make sure this does not result in a write
     assert_eq!(harness.io.pop_event(), None);
 }
+
+#[tokio::test]
+async fn periodic_time_sync() {
+    let mut config = AssociationConfig::quiet();
+    config.auto_time_sync = Some(TimeSyncProcedure::Lan);
+    config.auto_time_sync_period = Some(Duration::from_secs(60));
+    let mut seq = Sequence::default();
+    let mut harness = create_association(config).await;
+
+    // first synchronization is performed on startup without NEED_TIME
+    lan_time_sync(&mut harness, &mut seq).await;
+    assert!(harness
+        .assoc_events
+        .pop()
+        .contains(&AssocInfoEvent::TimeSyncSuccess(TimeSyncProcedure::Lan)));
+
+    // auto advance time
+    tokio::time::pause();
+    let start = tokio::time::Instant::now();
+    lan_time_sync(&mut harness, &mut seq).await;
+    assert!(start.elapsed() >= Duration::from_secs(60));
+}
+
+async fn lan_time_sync(harness: &mut super::harness::TestHarness, seq: &mut Sequence) {
+    harness
+        .expect_write_and_respond(
+            record_current_time_request(*seq),
+            empty_response(seq.increment()),
+        )
+        .await;
+    let write = harness.pop_write().await;
+    assert_eq!(write[1], FunctionCode::Write.as_u8());
+    harness
+        .process_response(empty_response(seq.increment()))
+        .await;
+}
@@ -122,6 +122,7 @@ pub(crate) enum AssocInfoEvent {
     ),
     TaskFailure(crate::master::TaskType, crate::master::TaskError),
     Unsolicited(bool, crate::app::Sequence),
+    TimeSyncSuccess(crate::master::TimeSyncProcedure),
 }
 
 pub(crate) struct AssocInfoEventQueue(Arc<Mutex<Vec<AssocInfoEvent>>>);
@@ -172,6 +173,10 @@ impl AssociationInformation for AssociationInformationEventHandler {
     fn unsolicited_response(&mut self, is_duplicate: bool, seq: crate::app::Sequence) {
         self.push(AssocInfoEvent::Unsolicited(is_duplicate, seq));
     }
+
+    fn time_sync_success(&mut self, report: crate::master::TimeSyncReport) {
+        self.push(AssocInfoEvent::TimeSyncSuccess(report.procedure));
+    }
 }
 
 pub(crate) struct TestHarness {
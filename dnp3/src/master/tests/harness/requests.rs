@@ -142,6 +142,19 @@ pub(crate) fn clear_restart_iin(seq: Sequence) -> Vec<u8> {
     cursor.written().to_vec()
 }
 
+pub(crate) fn record_current_time_request(seq: Sequence) -> Vec<u8> {
+    // RECORD_CURRENT_TIME request
+    let mut buffer = [0; 2];
+    let mut cursor = WriteCursor::new(&mut buffer);
+    start_request(
+        ControlField::request(seq),
+        FunctionCode::RecordCurrentTime,
+        &mut cursor,
+    )
+    .unwrap();
+    cursor.written().to_vec()
+}
+
 pub(crate) fn empty_response(seq: Sequence) -> Vec<u8> {
     empty_response_custom_iin(seq, Iin::default())
 }
@@ -1,6 +1,11 @@
 mod harness;
 
 mod auto_tasks;
+mod redundant;
+mod select;
+mod sequence;
 mod startup;
+mod statistics;
+mod write;
 
 mod file;
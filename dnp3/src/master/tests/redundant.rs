@@ -0,0 +1,294 @@
+use std::sync::{Arc, Mutex};
+
+use crate::app::parse::options::ParseOptions;
+use crate::app::{Listener, MaybeAsync, Sequence};
+use crate::decode::AppDecodeLevel;
+use crate::link::header::{FrameInfo, FrameType};
+use crate::link::reader::LinkModes;
+use crate::link::EndpointAddress;
+use crate::master::task::MasterTask;
+use crate::master::*;
+use crate::redundant::path::PathType;
+use crate::redundant::task::RedundantTask;
+use crate::redundant::{ChannelPath, FailbackPolicy, RedundancyConfig, RedundantChannelState};
+use crate::util::phys::{PhysAddr, PhysLayer};
+use crate::util::session::Enabled;
+
+use super::harness::requests::*;
+
+struct NullHandler;
+
+impl ReadHandler for NullHandler {}
+impl AssociationHandler for NullHandler {}
+impl AssociationInformation for NullHandler {}
+
+#[derive(Clone, Default)]
+struct StateListener(Arc<Mutex<Vec<RedundantChannelState>>>);
+
+impl StateListener {
+    fn pop(&self) -> Vec<RedundantChannelState> {
+        self.0.lock().unwrap().drain(..).collect()
+    }
+}
+
+impl Listener<RedundantChannelState> for StateListener {
+    fn update(&mut self, value: RedundantChannelState) -> MaybeAsync<()> {
+        self.0.lock().unwrap().push(value);
+        MaybeAsync::ready(())
+    }
+}
+
+async fn expect_write_and_respond(
+    io: &mut sfio_tokio_mock_io::Handle,
+    expected: Vec<u8>,
+    response: Vec<u8>,
+) {
+    assert_eq!(
+        io.next_event().await,
+        sfio_tokio_mock_io::Event::Write(expected)
+    );
+    io.read(&response);
+    assert_eq!(io.next_event().await, sfio_tokio_mock_io::Event::Read);
+}
+
+struct Fixture {
+    association: AssociationHandle,
+    listener: StateListener,
+    task: tokio::task::JoinHandle<RedundantTask>,
+}
+
+fn path(links: Vec<PhysLayer>, master_address: Option<u16>) -> PathType {
+    PathType::Mock {
+        links: links.into(),
+        master_address: master_address.map(|x| EndpointAddress::try_new(x).unwrap()),
+    }
+}
+
+async fn spawn(
+    primary: Vec<PhysLayer>,
+    backup: Vec<PhysLayer>,
+    failback: FailbackPolicy,
+) -> Fixture {
+    spawn_with_paths(path(primary, None), path(backup, None), failback).await
+}
+
+async fn spawn_with_paths(
+    primary: PathType,
+    backup: PathType,
+    failback: FailbackPolicy,
+) -> Fixture {
+    let master_address = EndpointAddress::try_new(1).unwrap();
+    let outstation_address = EndpointAddress::try_new(1024).unwrap();
+
+    let (tx, rx) = crate::util::channel::request_channel();
+    let mut task = MasterTask::new(
+        Enabled::No,
+        LinkModes::serial(),
+        ParseOptions::default(),
+        MasterChannelConfig {
+            decode_level: AppDecodeLevel::ObjectValues.into(),
+            ..MasterChannelConfig::new(master_address)
+        },
+        rx,
+    );
+    task.set_rx_frame_info(FrameInfo::new(
+        outstation_address,
+        None,
+        FrameType::Data,
+        PhysAddr::None,
+    ));
+
+    let listener = StateListener::default();
+    let mut task = RedundantTask::new(
+        task,
+        master_address,
+        primary,
+        backup,
+        RedundancyConfig {
+            failback,
+            max_response_timeouts: None,
+        },
+        Box::new(listener.clone()),
+    );
+    let task = tokio::spawn(async move {
+        task.run().await;
+        task
+    });
+
+    let mut master = MasterChannel::new(tx, MasterChannelType::Stream);
+    let association = master
+        .add_association(
+            outstation_address,
+            AssociationConfig::default(),
+            Box::new(NullHandler),
+            Box::new(NullHandler),
+            Box::new(NullHandler),
+        )
+        .await
+        .unwrap();
+    master.enable().await.unwrap();
+
+    Fixture {
+        association,
+        listener,
+        task,
+    }
+}
+
+async fn startup(io: &mut sfio_tokio_mock_io::Handle, seq: &mut Sequence) {
+    expect_write_and_respond(
+        io,
+        disable_unsol_request(*seq),
+        empty_response(seq.increment()),
+    )
+    .await;
+    expect_write_and_respond(
+        io,
+        integrity_poll_request(*seq),
+        empty_response(seq.increment()),
+    )
+    .await;
+    expect_write_and_respond(
+        io,
+        enable_unsol_request(*seq),
+        empty_response(seq.increment()),
+    )
+    .await;
+}
+
+async fn class0_read(
+    association: &mut AssociationHandle,
+    io: &mut sfio_tokio_mock_io::Handle,
+    seq: &mut Sequence,
+) {
+    let mut association = association.clone();
+    let read = tokio::spawn(async move {
+        association
+            .read(ReadRequest::class_scan(Classes::class0()))
+            .await
+    });
+    expect_write_and_respond(
+        io,
+        class_scan_request(Classes::class0(), *seq),
+        empty_response(seq.increment()),
+    )
+    .await;
+    assert_eq!(read.await.unwrap(), Ok(()));
+}
+
+#[tokio::test]
+async fn failover_preserves_association_state() {
+    let (primary, mut primary_io) = sfio_tokio_mock_io::mock();
+    let (backup, mut backup_io) = sfio_tokio_mock_io::mock();
+    let mut fixture = spawn(
+        vec![PhysLayer::Mock(primary)],
+        vec![PhysLayer::Mock(backup)],
+        FailbackPolicy::Never,
+    )
+    .await;
+
+    // the startup procedure runs on the primary path
+    let mut seq = Sequence::default();
+    startup(&mut primary_io, &mut seq).await;
+
+    // the primary path fails
+    primary_io.read_error(std::io::ErrorKind::ConnectionReset);
+
+    // requests continue on the backup path without repeating the startup procedure
+    class0_read(&mut fixture.association, &mut backup_io, &mut seq).await;
+
+    assert_eq!(
+        fixture.listener.pop().as_slice(),
+        &[
+            RedundantChannelState::Disabled,
+            RedundantChannelState::Connecting(ChannelPath::Primary),
+            RedundantChannelState::Connected(ChannelPath::Primary),
+            RedundantChannelState::Connecting(ChannelPath::Backup),
+            RedundantChannelState::Connected(ChannelPath::Backup),
+        ]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn fails_back_to_primary_when_restored() {
+    let (primary, mut primary_io) = sfio_tokio_mock_io::mock();
+    let (restored, mut restored_io) = sfio_tokio_mock_io::mock();
+    let (backup, mut backup_io) = sfio_tokio_mock_io::mock();
+    let mut fixture = spawn(
+        vec![PhysLayer::Mock(primary), PhysLayer::Mock(restored)],
+        vec![PhysLayer::Mock(backup)],
+        FailbackPolicy::Automatic(std::time::Duration::from_secs(10)),
+    )
+    .await;
+
+    let mut seq = Sequence::default();
+    startup(&mut primary_io, &mut seq).await;
+    primary_io.read_error(std::io::ErrorKind::ConnectionReset);
+    class0_read(&mut fixture.association, &mut backup_io, &mut seq).await;
+
+    // once the failback delay elapses, the primary path is restored
+    tokio::time::sleep(std::time::Duration::from_secs(11)).await;
+    class0_read(&mut fixture.association, &mut restored_io, &mut seq).await;
+
+    assert_eq!(
+        fixture.listener.pop().as_slice(),
+        &[
+            RedundantChannelState::Disabled,
+            RedundantChannelState::Connecting(ChannelPath::Primary),
+            RedundantChannelState::Connected(ChannelPath::Primary),
+            RedundantChannelState::Connecting(ChannelPath::Backup),
+            RedundantChannelState::Connected(ChannelPath::Backup),
+            RedundantChannelState::Connected(ChannelPath::Primary),
+        ]
+    );
+}
+
+/// shut down the channel and return the master address in use on the last path
+async fn shutdown(fixture: Fixture) -> u16 {
+    drop(fixture.association);
+    fixture.task.await.unwrap().master_address().raw_value()
+}
+
+#[tokio::test]
+async fn each_path_uses_the_master_address_of_its_connection() {
+    let (primary, mut primary_io) = sfio_tokio_mock_io::mock();
+    let (backup, mut backup_io) = sfio_tokio_mock_io::mock();
+    let mut fixture = spawn_with_paths(
+        path(vec![PhysLayer::Mock(primary)], Some(10)),
+        path(vec![PhysLayer::Mock(backup)], Some(20)),
+        FailbackPolicy::Never,
+    )
+    .await;
+
+    let mut seq = Sequence::default();
+    startup(&mut primary_io, &mut seq).await;
+    primary_io.read_error(std::io::ErrorKind::ConnectionReset);
+    class0_read(&mut fixture.association, &mut backup_io, &mut seq).await;
+
+    assert_eq!(shutdown(fixture).await, 20);
+}
+
+#[tokio::test(start_paused = true)]
+async fn failback_restores_the_configured_master_address() {
+    let (primary, mut primary_io) = sfio_tokio_mock_io::mock();
+    let (restored, mut restored_io) = sfio_tokio_mock_io::mock();
+    let (backup, mut backup_io) = sfio_tokio_mock_io::mock();
+    let mut fixture = spawn_with_paths(
+        path(
+            vec![PhysLayer::Mock(primary), PhysLayer::Mock(restored)],
+            None,
+        ),
+        path(vec![PhysLayer::Mock(backup)], Some(20)),
+        FailbackPolicy::Automatic(std::time::Duration::from_secs(10)),
+    )
+    .await;
+
+    let mut seq = Sequence::default();
+    startup(&mut primary_io, &mut seq).await;
+    primary_io.read_error(std::io::ErrorKind::ConnectionReset);
+    class0_read(&mut fixture.association, &mut backup_io, &mut seq).await;
+    tokio::time::sleep(std::time::Duration::from_secs(11)).await;
+    class0_read(&mut fixture.association, &mut restored_io, &mut seq).await;
+
+    assert_eq!(shutdown(fixture).await, 1);
+}
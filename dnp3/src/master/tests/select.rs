@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+use scursor::WriteCursor;
+
+use crate::app::control::*;
+use crate::app::format::write::{start_request, start_response};
+use crate::app::{ControlField, FunctionCode, Iin, ResponseFunction, Sequence};
+use crate::master::association::AssociationConfig;
+use crate::master::*;
+
+use super::harness::create_association;
+use super::harness::requests::{empty_response, integrity_poll_request};
+
+const SELECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn headers() -> CommandHeaders {
+    CommandBuilder::single_header_u16(Group12Var1::from_op_type(OpType::LatchOn), 7)
+}
+
+fn request(seq: Sequence, function: FunctionCode) -> Vec<u8> {
+    let mut buffer = [0; 64];
+    let mut cursor = WriteCursor::new(&mut buffer);
+    let mut writer = start_request(ControlField::request(seq), function, &mut cursor).unwrap();
+    headers().write(&mut writer).unwrap();
+    cursor.written().to_vec()
+}
+
+fn response(seq: Sequence) -> Vec<u8> {
+    let mut buffer = [0; 64];
+    let mut cursor = WriteCursor::new(&mut buffer);
+    let mut writer = start_response(
+        ControlField::response(seq, true, true, false),
+        ResponseFunction::Response,
+        Iin::default(),
+        &mut cursor,
+    )
+    .unwrap();
+    headers().write(&mut writer).unwrap();
+    cursor.written().to_vec()
+}
+
+async fn select(harness: &mut super::harness::TestHarness, seq: Sequence) -> Selection {
+    select_with_timeout(harness, seq, SELECT_TIMEOUT).await
+}
+
+async fn select_with_timeout(
+    harness: &mut super::harness::TestHarness,
+    seq: Sequence,
+    timeout: Duration,
+) -> Selection {
+    let mut association = harness.association.clone();
+    let task = tokio::spawn(async move { association.select(headers(), timeout).await });
+    harness
+        .expect_write_and_respond(request(seq, FunctionCode::Select), response(seq))
+        .await;
+    task.await.unwrap().unwrap()
+}
+
+#[tokio::test]
+async fn operate_follows_select_with_next_sequence() {
+    let mut seq = Sequence::default();
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+    let selection = select(&mut harness, seq.increment()).await;
+    assert!(!selection.is_expired());
+
+    // a read queued while the selection is pending must wait for the operate
+    let mut association = harness.association.clone();
+    let read = tokio::spawn(async move {
+        association
+            .read(ReadRequest::class_scan(Classes::all()))
+            .await
+    });
+    let mut association = harness.association.clone();
+    let operate = tokio::spawn(async move { association.operate_selected(selection).await });
+
+    let operate_seq = seq.increment();
+    harness
+        .expect_write_and_respond(
+            request(operate_seq, FunctionCode::Operate),
+            response(operate_seq),
+        )
+        .await;
+    assert_eq!(operate.await.unwrap(), Ok(()));
+
+    let read_seq = seq.increment();
+    harness
+        .expect_write_and_respond(integrity_poll_request(read_seq), empty_response(read_seq))
+        .await;
+    assert_eq!(read.await.unwrap(), Ok(()));
+}
+
+#[tokio::test]
+async fn operate_fails_after_select_timeout() {
+    let mut seq = Sequence::default();
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+    let selection = select(&mut harness, seq.increment()).await;
+
+    tokio::time::pause();
+    tokio::time::sleep(SELECT_TIMEOUT).await;
+    assert!(selection.is_expired());
+    assert_eq!(
+        harness.association.operate_selected(selection).await,
+        Err(SelectError::SelectTimeout)
+    );
+    harness.assert_no_events();
+}
+
+#[tokio::test]
+async fn cancelled_selection_cannot_be_operated() {
+    let mut seq = Sequence::default();
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+    let selection = select(&mut harness, seq.increment()).await;
+    let id = selection.id;
+    harness
+        .association
+        .cancel_selection(selection)
+        .await
+        .unwrap();
+
+    // other requests are sent immediately once the selection is cancelled
+    let mut association = harness.association.clone();
+    let read = tokio::spawn(async move {
+        association
+            .read(ReadRequest::class_scan(Classes::all()))
+            .await
+    });
+    let read_seq = seq.increment();
+    harness
+        .expect_write_and_respond(integrity_poll_request(read_seq), empty_response(read_seq))
+        .await;
+    assert_eq!(read.await.unwrap(), Ok(()));
+
+    let selection = Selection {
+        id,
+        headers: headers(),
+        deadline: Some(tokio::time::Instant::now() + SELECT_TIMEOUT),
+    };
+    assert_eq!(
+        harness.association.operate_selected(selection).await,
+        Err(SelectError::NotSelected)
+    );
+    harness.assert_no_events();
+}
+
+#[tokio::test]
+async fn select_timeout_too_large_to_represent_never_elapses() {
+    let mut seq = Sequence::default();
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+    let selection = select_with_timeout(&mut harness, seq.increment(), Duration::MAX).await;
+    assert!(!selection.is_expired());
+    assert_eq!(selection.remaining(), Duration::MAX);
+
+    let mut association = harness.association.clone();
+    let operate = tokio::spawn(async move { association.operate_selected(selection).await });
+    let operate_seq = seq.increment();
+    harness
+        .expect_write_and_respond(
+            request(operate_seq, FunctionCode::Operate),
+            response(operate_seq),
+        )
+        .await;
+    assert_eq!(operate.await.unwrap(), Ok(()));
+}
@@ -0,0 +1,329 @@
+use std::time::Duration;
+
+use scursor::WriteCursor;
+
+use crate::app::control::*;
+use crate::app::format::write::{start_request, start_response, HeaderWriter};
+use crate::app::measurement::Flags;
+use crate::app::variations::Variation;
+use crate::app::{ControlField, FunctionCode, Iin, ResponseFunction, Sequence};
+use crate::master::association::AssociationConfig;
+use crate::master::*;
+
+use super::harness::create_association;
+use super::harness::requests::empty_response;
+
+fn latch(op_type: OpType) -> Group12Var1 {
+    Group12Var1::from_op_type(op_type)
+}
+
+fn headers(crob: Group12Var1, index: u16) -> CommandHeaders {
+    CommandBuilder::single_header_u16(crob, index)
+}
+
+fn request(
+    seq: Sequence,
+    function: FunctionCode,
+    write: impl FnOnce(&mut HeaderWriter),
+) -> Vec<u8> {
+    let mut buffer = [0; 64];
+    let mut cursor = WriteCursor::new(&mut buffer);
+    let mut writer = start_request(ControlField::request(seq), function, &mut cursor).unwrap();
+    write(&mut writer);
+    cursor.written().to_vec()
+}
+
+fn read_bos_request(seq: Sequence, index: u16) -> Vec<u8> {
+    request(seq, FunctionCode::Read, |writer| {
+        writer
+            .write_range_only(Variation::Group10Var0, index, index)
+            .unwrap();
+    })
+}
+
+fn operate_request(seq: Sequence, headers: &CommandHeaders) -> Vec<u8> {
+    request(seq, FunctionCode::DirectOperate, |writer| {
+        headers.write(writer).unwrap();
+    })
+}
+
+fn operate_response(seq: Sequence, headers: &CommandHeaders) -> Vec<u8> {
+    let mut buffer = [0; 64];
+    let mut cursor = WriteCursor::new(&mut buffer);
+    let mut writer = start_response(
+        ControlField::response(seq, true, true, false),
+        ResponseFunction::Response,
+        Iin::default(),
+        &mut cursor,
+    )
+    .unwrap();
+    headers.write(&mut writer).unwrap();
+    cursor.written().to_vec()
+}
+
+fn bos_response(seq: Sequence, index: u16, value: bool) -> Vec<u8> {
+    bos_response_with_flags(seq, index, value, Flags::ONLINE)
+}
+
+fn bos_response_with_flags(seq: Sequence, index: u16, value: bool, flags: Flags) -> Vec<u8> {
+    let mut response = empty_response(seq);
+    // g10v2 with a 2-byte start/stop range
+    response.extend([0x0A, 0x02, 0x01]);
+    response.extend(index.to_le_bytes());
+    response.extend(index.to_le_bytes());
+    response.push(if value { 0x80 } else { 0x00 } | flags.value);
+    response
+}
+
+fn online(value: bool) -> PointReading {
+    // binary flags also carry the state bit
+    let state = if value { 0x80 } else { 0x00 };
+    PointReading::new(
+        PointValue::Binary(value),
+        Flags::new(Flags::ONLINE.value | state),
+    )
+}
+
+fn switching_sequence() -> CommandSequence {
+    let mut sequence = CommandSequence::new();
+    sequence.add_precondition(PointCondition::BinaryOutputStatus {
+        index: 0,
+        value: false,
+    });
+    sequence.add_operate_with_rollback(
+        CommandMode::DirectOperate,
+        headers(latch(OpType::LatchOn), 0),
+        headers(latch(OpType::LatchOff), 0),
+    );
+    sequence.add_verify(
+        PointCondition::BinaryOutputStatus {
+            index: 0,
+            value: true,
+        },
+        Duration::ZERO,
+    );
+    sequence.add_operate(
+        CommandMode::DirectOperate,
+        headers(latch(OpType::LatchOn), 1),
+    );
+    sequence
+}
+
+#[tokio::test]
+async fn executes_steps_in_order() {
+    let mut seq = Sequence::default();
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+    let mut association = harness.association.clone();
+    let task =
+        tokio::spawn(async move { association.execute_sequence(&switching_sequence()).await });
+
+    harness
+        .expect_write_and_respond(
+            read_bos_request(seq, 0),
+            bos_response(seq.increment(), 0, false),
+        )
+        .await;
+    let on = headers(latch(OpType::LatchOn), 0);
+    harness
+        .expect_write_and_respond(
+            operate_request(seq, &on),
+            operate_response(seq.increment(), &on),
+        )
+        .await;
+    harness
+        .expect_write_and_respond(
+            read_bos_request(seq, 0),
+            bos_response(seq.increment(), 0, true),
+        )
+        .await;
+    let on = headers(latch(OpType::LatchOn), 1);
+    harness
+        .expect_write_and_respond(
+            operate_request(seq, &on),
+            operate_response(seq.increment(), &on),
+        )
+        .await;
+
+    let report = task.await.unwrap();
+    assert!(report.is_success());
+    assert!(report.rollback.is_empty());
+    let kinds: Vec<StepKind> = report.steps.iter().map(|x| x.kind).collect();
+    assert_eq!(
+        kinds,
+        [
+            StepKind::Precondition,
+            StepKind::Operate,
+            StepKind::Verify,
+            StepKind::Operate
+        ]
+    );
+    assert_eq!(
+        report.steps[1].command_status(),
+        Some(CommandStatus::Success)
+    );
+}
+
+#[tokio::test]
+async fn rolls_back_when_verification_fails() {
+    let mut seq = Sequence::default();
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+    let mut association = harness.association.clone();
+    let task =
+        tokio::spawn(async move { association.execute_sequence(&switching_sequence()).await });
+
+    harness
+        .expect_write_and_respond(
+            read_bos_request(seq, 0),
+            bos_response(seq.increment(), 0, false),
+        )
+        .await;
+    let on = headers(latch(OpType::LatchOn), 0);
+    harness
+        .expect_write_and_respond(
+            operate_request(seq, &on),
+            operate_response(seq.increment(), &on),
+        )
+        .await;
+    // the output never changes state
+    harness
+        .expect_write_and_respond(
+            read_bos_request(seq, 0),
+            bos_response(seq.increment(), 0, false),
+        )
+        .await;
+    let off = headers(latch(OpType::LatchOff), 0);
+    harness
+        .expect_write_and_respond(
+            operate_request(seq, &off),
+            operate_response(seq.increment(), &off),
+        )
+        .await;
+
+    let report = task.await.unwrap();
+    assert!(!report.is_success());
+    assert_eq!(report.steps.len(), 3);
+    assert_eq!(
+        report.failed_step().unwrap().result,
+        Err(StepError::VerifyTimeout(Some(online(false))))
+    );
+    assert_eq!(
+        report.rollback,
+        [StepReport {
+            index: 1,
+            kind: StepKind::Rollback,
+            result: Ok(()),
+        }]
+    );
+}
+
+#[tokio::test]
+async fn stops_when_precondition_fails() {
+    let seq = Sequence::default();
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+    let mut association = harness.association.clone();
+    let task =
+        tokio::spawn(async move { association.execute_sequence(&switching_sequence()).await });
+
+    harness
+        .expect_write_and_respond(read_bos_request(seq, 0), bos_response(seq, 0, true))
+        .await;
+
+    let report = task.await.unwrap();
+    assert_eq!(report.steps.len(), 1);
+    assert_eq!(
+        report.steps[0].result,
+        Err(StepError::ConditionNotMet(Some(online(true))))
+    );
+    assert!(report.rollback.is_empty());
+    harness.assert_no_events();
+}
+
+#[tokio::test]
+async fn reports_command_status_of_rejected_operate() {
+    let seq = Sequence::default();
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+    let mut association = harness.association.clone();
+    let on = headers(latch(OpType::LatchOn), 3);
+    let mut sequence = CommandSequence::new();
+    sequence.add_operate(CommandMode::DirectOperate, on.clone());
+    let task = tokio::spawn(async move { association.execute_sequence(&sequence).await });
+
+    let rejected = headers(
+        Group12Var1 {
+            status: CommandStatus::NotSupported,
+            ..latch(OpType::LatchOn)
+        },
+        3,
+    );
+    harness
+        .expect_write_and_respond(operate_request(seq, &on), operate_response(seq, &rejected))
+        .await;
+
+    let report = task.await.unwrap();
+    assert_eq!(
+        report.steps[0].command_status(),
+        Some(CommandStatus::NotSupported)
+    );
+}
+
+#[tokio::test]
+async fn precondition_requires_point_to_be_online() {
+    let seq = Sequence::default();
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+    let mut association = harness.association.clone();
+    let task =
+        tokio::spawn(async move { association.execute_sequence(&switching_sequence()).await });
+
+    harness
+        .expect_write_and_respond(
+            read_bos_request(seq, 0),
+            bos_response_with_flags(seq, 0, false, Flags::COMM_LOST),
+        )
+        .await;
+
+    let report = task.await.unwrap();
+    assert_eq!(report.steps.len(), 1);
+    assert_eq!(
+        report.steps[0].result,
+        Err(StepError::ConditionNotMet(Some(PointReading::new(
+            PointValue::Binary(false),
+            Flags::COMM_LOST
+        ))))
+    );
+    harness.assert_no_events();
+}
+
+#[tokio::test]
+async fn verify_retries_after_read_error() {
+    let mut seq = Sequence::default();
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+    let mut association = harness.association.clone();
+    let mut sequence = CommandSequence::new();
+    sequence.set_verify_poll_interval(Duration::ZERO);
+    sequence.add_verify(
+        PointCondition::BinaryOutputStatus {
+            index: 0,
+            value: true,
+        },
+        Duration::from_secs(60),
+    );
+    let task = tokio::spawn(async move { association.execute_sequence(&sequence).await });
+
+    // a response with a malformed object header fails the read
+    let mut malformed = empty_response(seq);
+    malformed.extend([0x0A, 0x02, 0x01, 0x00]);
+    harness
+        .expect_write_and_respond(read_bos_request(seq, 0), malformed)
+        .await;
+    seq.increment();
+    harness
+        .expect_write_and_respond(
+            read_bos_request(seq, 0),
+            bos_response(seq.increment(), 0, true),
+        )
+        .await;
+
+    let report = task.await.unwrap();
+    assert!(report.is_success());
+    assert_eq!(report.steps.len(), 1);
+}
@@ -0,0 +1,39 @@
+use crate::app::Sequence;
+use crate::app::{Iin, Iin1, Iin2};
+use crate::master::association::AssociationConfig;
+use crate::statistics::TaskErrorKind;
+
+use super::harness::create_association;
+use super::harness::requests::*;
+
+#[tokio::test]
+async fn counts_task_results_and_traffic() {
+    let mut seq = Sequence::default();
+    let mut harness = create_association(AssociationConfig::default()).await;
+
+    harness
+        .expect_write_and_respond(disable_unsol_request(seq), empty_response(seq.increment()))
+        .await;
+
+    let iin = Iin::new(Iin1::default(), Iin2::PARAMETER_ERROR);
+    harness
+        .expect_write_and_respond(
+            integrity_poll_request(seq),
+            empty_response_custom_iin(seq.increment(), iin),
+        )
+        .await;
+
+    let statistics = harness.association.get_statistics().await.unwrap();
+    assert_eq!(statistics.tasks_started, 2);
+    assert_eq!(statistics.tasks_succeeded, 1);
+    assert_eq!(
+        statistics.tasks_failed.get(&TaskErrorKind::RejectedByIin2),
+        Some(&1)
+    );
+    assert_eq!(statistics.response_timeouts, 0);
+    assert!(statistics.average_response_latency.is_some());
+
+    let channel = harness.master.get_statistics().await.unwrap();
+    assert_eq!(channel.frames_tx, 2);
+    assert_eq!(channel.reconnects, 0);
+}
@@ -0,0 +1,115 @@
+use crate::app::measurement::*;
+use crate::app::Iin2;
+use crate::master::association::AssociationConfig;
+use crate::master::*;
+use crate::outstation::database::{StaticAnalogOutputStatusVariation, StaticCounterVariation};
+
+use super::harness::create_association;
+
+fn counter(value: u32) -> Counter {
+    Counter {
+        value,
+        flags: Flags::ONLINE,
+        time: None,
+    }
+}
+
+fn octet_string(value: &[u8]) -> OctetString {
+    OctetString::new(value).unwrap()
+}
+
+#[tokio::test]
+async fn writes_headers_with_range_and_prefix_qualifiers() {
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+
+    let mut builder = WriteBuilder::new();
+    builder.add_counters(
+        StaticCounterVariation::Group20Var5,
+        WriteValues::RangeU8(3, vec![counter(7), counter(8)]),
+    );
+    builder.add_analog_output_status(
+        StaticAnalogOutputStatusVariation::Group40Var2,
+        WriteValues::PrefixedU16(vec![(
+            AnalogOutputStatus {
+                value: 300.0,
+                flags: Flags::ONLINE,
+                time: None,
+            },
+            0x0102,
+        )]),
+    );
+    // the strings of different length are written in separate g110 headers
+    builder.add_octet_strings(WriteValues::RangeU16(
+        10,
+        vec![
+            octet_string(b"ab"),
+            octet_string(b"cd"),
+            octet_string(b"xyz"),
+        ],
+    ));
+    let request = builder.build();
+
+    let mut association = harness.association.clone();
+    let task = tokio::spawn(async move { association.write(request).await });
+
+    harness
+        .expect_write_and_respond(
+            vec![
+                0xC0, 0x02, // g20v5, 0x00 qualifier, 3 to 4
+                0x14, 0x05, 0x00, 0x03, 0x04, 0x07, 0x00, 0x00, 0x00, 0x08, 0x00, 0x00, 0x00,
+                // g40v2, 0x28 qualifier, count of 1, index 0x0102
+                0x28, 0x02, 0x28, 0x01, 0x00, 0x02, 0x01, 0x01, 0x2C, 0x01,
+                // g110v2, 0x01 qualifier, 10 to 11
+                0x6E, 0x02, 0x01, 0x0A, 0x00, 0x0B, 0x00, b'a', b'b', b'c', b'd',
+                // g110v3, 0x01 qualifier, 12 to 12
+                0x6E, 0x03, 0x01, 0x0C, 0x00, 0x0C, 0x00, b'x', b'y', b'z',
+            ],
+            vec![0xC0, 0x81, 0x00, 0x00],
+        )
+        .await;
+
+    assert_eq!(task.await.unwrap(), Ok(()));
+}
+
+#[tokio::test]
+async fn returns_iin2_error_when_outstation_rejects_write() {
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+
+    let mut builder = WriteBuilder::new();
+    builder.add_octet_strings(WriteValues::PrefixedU8(vec![(octet_string(b"a"), 5)]));
+    let request = builder.build();
+
+    let mut association = harness.association.clone();
+    let task = tokio::spawn(async move { association.write(request).await });
+
+    harness
+        .expect_write_and_respond(
+            vec![0xC0, 0x02, 0x6E, 0x01, 0x17, 0x01, 0x05, b'a'],
+            vec![0xC0, 0x81, 0x00, 0x04],
+        )
+        .await;
+
+    assert_eq!(
+        task.await.unwrap(),
+        Err(WriteError::IinError(Iin2::PARAMETER_ERROR))
+    );
+}
+
+#[tokio::test]
+async fn range_exceeding_qualifier_is_not_sent() {
+    let mut harness = create_association(AssociationConfig::quiet()).await;
+
+    let mut builder = WriteBuilder::new();
+    builder.add_counters(
+        StaticCounterVariation::Group20Var1,
+        WriteValues::RangeU8(255, vec![counter(1), counter(2)]),
+    );
+
+    assert_eq!(
+        harness.association.write(builder.build()).await,
+        Err(WriteError::Task(TaskError::BadEncoding(
+            BadEncoding::IndexOverflow
+        )))
+    );
+    harness.assert_no_events();
+}
@@ -0,0 +1,747 @@
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::Instrument;
+
+use crate::link::EndpointAddress;
+use crate::master::{AssociationHandle, MasterChannel};
+use crate::outstation::OutstationHandle;
+use crate::statistics::{
+    AssociationStatistics, ChannelStatistics, OutstationStatistics, ServerCounters,
+    ServerStatistics,
+};
+use crate::tcp::ServerHandle;
+use crate::util::shutdown::{shutdown_token, ShutdownListener};
+
+/// Content type of the OpenMetrics text format
+pub const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// largest HTTP request head accepted by the metrics endpoint
+const MAX_REQUEST_SIZE: usize = 8192;
+/// time allowed for a scraper to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// time allowed for each source to report its statistics when rendering
+const SAMPLE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Labels attached to every sample of a registered source
+///
+/// Label names are sanitized so that they only contain characters that are valid in
+/// OpenMetrics, i.e. letters, digits, and underscores.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Labels {
+    pairs: Vec<(String, String)>,
+}
+
+impl Labels {
+    /// Create an empty set of labels
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a label with an arbitrary name and value, replacing any existing label with the same name
+    pub fn with<N: AsRef<str>, V: ToString>(mut self, name: N, value: V) -> Self {
+        let name = sanitize_name(name.as_ref());
+        let value = value.to_string();
+        match self.pairs.iter_mut().find(|(n, _)| *n == name) {
+            Some(pair) => pair.1 = value,
+            None => self.pairs.push((name, value)),
+        }
+        self
+    }
+
+    /// Add a label containing a link-layer address, e.g. `master` or `outstation`
+    pub fn endpoint<N: AsRef<str>>(self, name: N, address: EndpointAddress) -> Self {
+        self.with(name, address.raw_value())
+    }
+
+    /// Add a `remote_addr` label containing a socket address
+    pub fn remote_addr(self, addr: SocketAddr) -> Self {
+        self.with("remote_addr", addr)
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.pairs.iter().any(|(n, _)| n == name)
+    }
+}
+
+/// Collection of masters, associations, outstations and servers whose statistics are exported
+/// in the OpenMetrics text format used by Prometheus
+///
+/// Sources are registered with a set of [`Labels`] that identify them, and are removed
+/// automatically once the underlying channel, association, outstation, or server shuts down.
+///
+/// The exposition can be rendered to a string using [`MetricsRegistry::render`] and served by
+/// the application's own HTTP stack, or served directly by the minimal endpoint started with
+/// [`MetricsRegistry::spawn_http_server`].
+#[derive(Clone, Default)]
+pub struct MetricsRegistry {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Default)]
+struct Inner {
+    next_id: u64,
+    sources: Vec<Entry>,
+}
+
+#[derive(Clone)]
+struct Entry {
+    id: u64,
+    labels: Labels,
+    source: Source,
+}
+
+#[derive(Clone)]
+enum Source {
+    MasterChannel(MasterChannel),
+    Association(AssociationHandle),
+    Outstation(OutstationHandle),
+    Server(Weak<ServerCounters>),
+}
+
+enum Sample {
+    MasterChannel(ChannelStatistics),
+    Association(AssociationStatistics),
+    Outstation(OutstationStatistics),
+    Server(ServerStatistics),
+}
+
+impl Source {
+    async fn sample(&mut self) -> Option<Sample> {
+        match self {
+            Self::MasterChannel(x) => x.get_statistics().await.ok().map(Sample::MasterChannel),
+            Self::Association(x) => x.get_statistics().await.ok().map(Sample::Association),
+            Self::Outstation(x) => x.get_statistics().await.ok().map(Sample::Outstation),
+            Self::Server(x) => x.upgrade().map(|x| Sample::Server(x.get())),
+        }
+    }
+}
+
+impl std::fmt::Debug for MetricsRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MetricsRegistry")
+            .field("sources", &self.inner.lock().unwrap().sources.len())
+            .finish()
+    }
+}
+
+impl MetricsRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Export the traffic statistics of a master channel
+    ///
+    /// Associations on the channel are registered separately using [`MetricsRegistry::add_association`].
+    pub fn add_master_channel(&self, channel: &MasterChannel, labels: Labels) {
+        self.add(Source::MasterChannel(channel.clone()), labels);
+    }
+
+    /// Export the statistics of an association
+    ///
+    /// An `outstation` label containing the address of the outstation is added unless the
+    /// provided labels already contain one.
+    pub fn add_association(&self, association: &AssociationHandle, labels: Labels) {
+        let labels = if labels.contains("outstation") {
+            labels
+        } else {
+            labels.endpoint("outstation", association.address())
+        };
+        self.add(Source::Association(association.clone()), labels);
+    }
+
+    /// Export the statistics of an outstation
+    pub fn add_outstation(&self, outstation: &OutstationHandle, labels: Labels) {
+        self.add(Source::Outstation(outstation.clone()), labels);
+    }
+
+    /// Export the connection counters of a TCP or TLS server
+    ///
+    /// A `listen_addr` label containing the local address of the server is added unless the
+    /// provided labels already contain one.
+    pub fn add_server(&self, server: &ServerHandle, labels: Labels) {
+        let labels = match server.local_addr() {
+            Some(addr) if !labels.contains("listen_addr") => labels.with("listen_addr", addr),
+            _ => labels,
+        };
+        self.add(Source::Server(Arc::downgrade(&server.counters)), labels);
+    }
+
+    fn add(&self, source: Source, labels: Labels) {
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.sources.push(Entry { id, labels, source });
+    }
+
+    /// Query every registered source and render the results in the OpenMetrics text format
+    ///
+    /// Sources are queried concurrently. A source that doesn't respond within 2 seconds, e.g. an
+    /// outstation whose task is busy, is omitted from this exposition but remains registered.
+    pub async fn render(&self) -> String {
+        let entries = self.inner.lock().unwrap().sources.clone();
+
+        let samples = futures_util::future::join_all(entries.into_iter().map(|mut entry| async {
+            let sample = tokio::time::timeout(SAMPLE_TIMEOUT, entry.source.sample()).await;
+            (entry, sample)
+        }))
+        .await;
+
+        let mut exposition = Exposition::default();
+        let mut removed = Vec::new();
+        for (entry, sample) in samples {
+            match sample {
+                Ok(Some(sample)) => exposition.add_sample(&entry.labels, sample),
+                Ok(None) => removed.push(entry.id),
+                Err(_) => tracing::warn!("timeout sampling metrics source {:?}", entry.labels),
+            }
+        }
+
+        if !removed.is_empty() {
+            self.inner
+                .lock()
+                .unwrap()
+                .sources
+                .retain(|x| !removed.contains(&x.id));
+        }
+
+        exposition.finish()
+    }
+
+    /// Spawn a minimal HTTP server that responds to `GET` requests with the rendered metrics
+    ///
+    /// The metrics are served on every path, so the conventional `/metrics` path works for scrapers.
+    /// Dropping the returned handle shuts down the server.
+    ///
+    /// This must be called from within the Tokio runtime.
+    pub async fn spawn_http_server(&self, addr: SocketAddr) -> std::io::Result<ServerHandle> {
+        let listener = TcpListener::bind(addr).await?;
+        let local = listener.local_addr().ok();
+        let (token, shutdown) = shutdown_token();
+        let counters = Arc::new(ServerCounters::default());
+
+        tokio::spawn(
+            run_http_server(self.clone(), listener, shutdown)
+                .instrument(tracing::info_span!("dnp3-metrics", "listen" = ?addr)),
+        );
+
+        Ok(ServerHandle {
+            addr: local,
+            _token: token,
+            counters,
+        })
+    }
+}
+
+async fn run_http_server(
+    registry: MetricsRegistry,
+    listener: TcpListener,
+    mut shutdown: ShutdownListener,
+) {
+    loop {
+        tokio::select! {
+            _ = shutdown.listen() => return,
+            res = listener.accept() => match res {
+                Ok((stream, addr)) => {
+                    let registry = registry.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = serve(registry, stream).await {
+                            tracing::debug!("error serving {addr}: {err}");
+                        }
+                    });
+                }
+                Err(err) => {
+                    tracing::warn!("unable to accept connection: {err}");
+                    return;
+                }
+            }
+        }
+    }
+}
+
+async fn serve(registry: MetricsRegistry, mut stream: TcpStream) -> std::io::Result<()> {
+    let head = match tokio::time::timeout(REQUEST_TIMEOUT, read_request_head(&mut stream)).await {
+        Ok(x) => x?,
+        Err(_) => return Ok(()),
+    };
+
+    let response = match head.split_whitespace().next() {
+        Some("GET") => {
+            let body = registry.render().await;
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {CONTENT_TYPE}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+        }
+        _ => "HTTP/1.1 405 Method Not Allowed\r\nAllow: GET\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0; 1024];
+    while !buffer.windows(4).any(|x| x == b"\r\n\r\n") {
+        if buffer.len() > MAX_REQUEST_SIZE {
+            return Err(std::io::Error::other("request too large"));
+        }
+        let count = stream.read(&mut chunk).await?;
+        if count == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..count]);
+    }
+    Ok(String::from_utf8_lossy(&buffer).into_owned())
+}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum Kind {
+    Counter,
+    Gauge,
+    Info,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+            Self::Info => "info",
+        }
+    }
+
+    fn suffix(self) -> &'static str {
+        match self {
+            Self::Counter => "_total",
+            Self::Gauge => "",
+            Self::Info => "_info",
+        }
+    }
+}
+
+struct Family {
+    name: &'static str,
+    kind: Kind,
+    help: &'static str,
+    samples: Vec<String>,
+}
+
+/// Samples grouped by metric family in the order in which the families were first seen
+#[derive(Default)]
+struct Exposition {
+    families: Vec<Family>,
+}
+
+impl Exposition {
+    fn add(
+        &mut self,
+        name: &'static str,
+        kind: Kind,
+        help: &'static str,
+        labels: &Labels,
+        extra: &[(&str, &str)],
+        value: impl std::fmt::Display,
+    ) {
+        let index = match self.families.iter().position(|x| x.name == name) {
+            Some(x) => x,
+            None => {
+                self.families.push(Family {
+                    name,
+                    kind,
+                    help,
+                    samples: Vec::new(),
+                });
+                self.families.len() - 1
+            }
+        };
+
+        let pairs = labels
+            .pairs
+            .iter()
+            .map(|(n, v)| (n.as_str(), v.as_str()))
+            .chain(extra.iter().copied());
+
+        let mut sample = format!("{name}{}", kind.suffix());
+        let mut first = true;
+        for (n, v) in pairs {
+            sample.push(if first { '{' } else { ',' });
+            first = false;
+            let _ = write!(sample, "{n}=\"{}\"", escape_value(v));
+        }
+        if !first {
+            sample.push('}');
+        }
+        let _ = write!(sample, " {value}");
+
+        self.families[index].samples.push(sample);
+    }
+
+    fn add_sample(&mut self, labels: &Labels, sample: Sample) {
+        match sample {
+            Sample::MasterChannel(x) => self.add_channel(labels, "master", &x),
+            Sample::Association(x) => self.add_association(labels, &x),
+            Sample::Outstation(x) => self.add_outstation(labels, &x),
+            Sample::Server(x) => self.add_server(labels, &x),
+        }
+    }
+
+    fn add_channel(&mut self, labels: &Labels, role: &str, stats: &ChannelStatistics) {
+        let role = [("role", role)];
+        let counters = [
+            (
+                "dnp3_channel_received_bytes",
+                "Bytes read from the physical layer",
+                stats.bytes_rx,
+            ),
+            (
+                "dnp3_channel_sent_bytes",
+                "Bytes written to the physical layer",
+                stats.bytes_tx,
+            ),
+            (
+                "dnp3_channel_received_frames",
+                "Valid link-layer frames received",
+                stats.frames_rx,
+            ),
+            (
+                "dnp3_channel_sent_frames",
+                "Link-layer frames transmitted",
+                stats.frames_tx,
+            ),
+            (
+                "dnp3_channel_crc_errors",
+                "Link-layer frames discarded because of a bad CRC",
+                stats.crc_errors,
+            ),
+            (
+                "dnp3_channel_link_parse_errors",
+                "Link-layer framing errors other than bad CRCs",
+                stats.link_parse_errors,
+            ),
+            (
+                "dnp3_channel_reconnects",
+                "Times communications were re-established on a new physical layer",
+                stats.reconnects,
+            ),
+        ];
+        for (name, help, value) in counters {
+            self.add(name, Kind::Counter, help, labels, &role, value);
+        }
+
+        if let Some(addr) = stats.remote_addr {
+            let addr = addr.to_string();
+            self.add(
+                "dnp3_channel_remote",
+                Kind::Info,
+                "Remote address of the current or most recent connection",
+                labels,
+                &[role[0], ("remote_addr", &addr)],
+                1,
+            );
+        }
+    }
+
+    fn add_association(&mut self, labels: &Labels, stats: &AssociationStatistics) {
+        let counters = [
+            (
+                "dnp3_association_tasks_started",
+                "Tasks started by the master",
+                stats.tasks_started,
+            ),
+            (
+                "dnp3_association_tasks_succeeded",
+                "Tasks that completed successfully",
+                stats.tasks_succeeded,
+            ),
+            (
+                "dnp3_association_response_timeouts",
+                "Requests for which no response was received",
+                stats.response_timeouts,
+            ),
+            (
+                "dnp3_association_unsolicited_responses",
+                "Unsolicited responses received, including duplicates",
+                stats.unsolicited_responses,
+            ),
+            (
+                "dnp3_association_duplicate_unsolicited_responses",
+                "Unsolicited responses that duplicated the previous one",
+                stats.duplicate_unsolicited_responses,
+            ),
+        ];
+        for (name, help, value) in counters {
+            self.add(name, Kind::Counter, help, labels, &[], value);
+        }
+
+        for (kind, count) in stats.tasks_failed.iter() {
+            let kind = format!("{kind:?}");
+            self.add(
+                "dnp3_association_tasks_failed",
+                Kind::Counter,
+                "Tasks that failed by kind of error",
+                labels,
+                &[("error", &kind)],
+                count,
+            );
+        }
+
+        if let Some(latency) = stats.average_response_latency {
+            self.add(
+                "dnp3_association_average_response_latency_seconds",
+                Kind::Gauge,
+                "Average time between sending a request and receiving its response",
+                labels,
+                &[],
+                latency.as_secs_f64(),
+            );
+        }
+    }
+
+    fn add_outstation(&mut self, labels: &Labels, stats: &OutstationStatistics) {
+        self.add_channel(labels, "outstation", &stats.channel);
+
+        for (function, count) in stats.requests.iter() {
+            let function = format!("{function:?}");
+            self.add(
+                "dnp3_outstation_requests",
+                Kind::Counter,
+                "Requests received by function code",
+                labels,
+                &[("function", &function)],
+                count,
+            );
+        }
+
+        let counters = [
+            (
+                "dnp3_outstation_solicited_confirm_timeouts",
+                "Solicited responses for which no confirmation was received",
+                stats.solicited_confirm_timeouts,
+            ),
+            (
+                "dnp3_outstation_unsolicited_confirm_timeouts",
+                "Unsolicited responses for which no confirmation was received",
+                stats.unsolicited_confirm_timeouts,
+            ),
+            (
+                "dnp3_outstation_event_buffer_overflows",
+                "Events discarded because the event buffer was full",
+                stats.event_buffer_overflows,
+            ),
+        ];
+        for (name, help, value) in counters {
+            self.add(name, Kind::Counter, help, labels, &[], value);
+        }
+
+        self.add(
+            "dnp3_outstation_event_buffer_high_water_mark",
+            Kind::Gauge,
+            "Largest number of events ever held in the event buffer",
+            labels,
+            &[],
+            stats.event_buffer_high_water_mark,
+        );
+    }
+
+    fn add_server(&mut self, labels: &Labels, stats: &ServerStatistics) {
+        self.add(
+            "dnp3_server_accepted_connections",
+            Kind::Counter,
+            "Connections accepted by the server",
+            labels,
+            &[],
+            stats.connections_accepted,
+        );
+        self.add(
+            "dnp3_server_rejected_connections",
+            Kind::Counter,
+            "Connections rejected by the server",
+            labels,
+            &[],
+            stats.connections_rejected,
+        );
+    }
+
+    fn finish(self) -> String {
+        let mut output = String::new();
+        for family in self.families {
+            let _ = writeln!(output, "# TYPE {} {}", family.name, family.kind.name());
+            let _ = writeln!(output, "# HELP {} {}", family.name, family.help);
+            for sample in family.samples {
+                output.push_str(&sample);
+                output.push('\n');
+            }
+        }
+        output.push_str("# EOF\n");
+        output
+    }
+}
+
+fn sanitize_name(name: &str) -> String {
+    let mut output: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if output.is_empty() || output.starts_with(|c: char| c.is_ascii_digit()) {
+        output.insert(0, '_');
+    }
+    output
+}
+
+fn escape_value(value: &str) -> String {
+    let mut output = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => output.push_str("\\\\"),
+            '"' => output.push_str("\\\""),
+            '\n' => output.push_str("\\n"),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn label_names_are_sanitized_and_values_escaped() {
+        let labels = Labels::new()
+            .with("site-name", "a \"b\"\\c")
+            .with("1st", "x");
+        let mut exposition = Exposition::default();
+        exposition.add("test", Kind::Gauge, "help", &labels, &[], 1);
+
+        assert_eq!(
+            exposition.finish(),
+            "# TYPE test gauge\n# HELP test help\ntest{site_name=\"a \\\"b\\\"\\\\c\",_1st=\"x\"} 1\n# EOF\n"
+        );
+    }
+
+    #[test]
+    fn samples_are_grouped_by_family() {
+        let mut exposition = Exposition::default();
+        let first = Labels::new().with("outstation", 1);
+        let second = Labels::new().with("outstation", 2);
+
+        let server = ServerStatistics {
+            connections_accepted: 3,
+            connections_rejected: 1,
+        };
+        exposition.add_sample(&first, Sample::Server(server));
+        exposition.add_sample(&second, Sample::Server(server));
+
+        let text = exposition.finish();
+        let expected = "# TYPE dnp3_server_accepted_connections counter\n\
+            # HELP dnp3_server_accepted_connections Connections accepted by the server\n\
+            dnp3_server_accepted_connections_total{outstation=\"1\"} 3\n\
+            dnp3_server_accepted_connections_total{outstation=\"2\"} 3\n\
+            # TYPE dnp3_server_rejected_connections counter\n\
+            # HELP dnp3_server_rejected_connections Connections rejected by the server\n\
+            dnp3_server_rejected_connections_total{outstation=\"1\"} 1\n\
+            dnp3_server_rejected_connections_total{outstation=\"2\"} 1\n\
+            # EOF\n";
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn remote_address_is_exported_as_info() {
+        let stats = ChannelStatistics {
+            remote_addr: Some("127.0.0.1:20000".parse().unwrap()),
+            ..Default::default()
+        };
+        let mut exposition = Exposition::default();
+        exposition.add_sample(&Labels::new(), Sample::MasterChannel(stats));
+
+        let text = exposition.finish();
+        assert!(text.contains("# TYPE dnp3_channel_remote info\n"));
+        assert!(text.contains(
+            "dnp3_channel_remote_info{role=\"master\",remote_addr=\"127.0.0.1:20000\"} 1\n"
+        ));
+        assert!(text.contains("dnp3_channel_reconnects_total{role=\"master\"} 0\n"));
+    }
+
+    #[tokio::test]
+    async fn sources_are_removed_once_they_shut_down() {
+        let registry = MetricsRegistry::new();
+        let (token, _) = shutdown_token();
+        let server = ServerHandle {
+            addr: None,
+            _token: token,
+            counters: Arc::new(ServerCounters::default()),
+        };
+        server.counters.on_accept();
+
+        registry.add_server(&server, Labels::new());
+        assert!(registry
+            .render()
+            .await
+            .contains("dnp3_server_accepted_connections_total 1\n"));
+
+        drop(server);
+        assert_eq!(registry.render().await, "# EOF\n");
+        assert!(registry.inner.lock().unwrap().sources.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn unresponsive_sources_are_skipped_but_remain_registered() {
+        let registry = MetricsRegistry::new();
+        let (token, _) = shutdown_token();
+        let server = ServerHandle {
+            addr: None,
+            _token: token,
+            counters: Arc::new(ServerCounters::default()),
+        };
+        // a channel whose task never processes the request for its statistics
+        let (tx, _rx) = crate::util::channel::request_channel();
+        let channel = MasterChannel::new(tx, crate::master::MasterChannelType::Stream);
+
+        registry.add_master_channel(&channel, Labels::new());
+        registry.add_server(&server, Labels::new());
+
+        let text = registry.render().await;
+        assert!(text.contains("dnp3_server_accepted_connections_total 0\n"));
+        assert!(!text.contains("dnp3_channel"));
+        assert_eq!(registry.inner.lock().unwrap().sources.len(), 2);
+    }
+
+    async fn request(addr: SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn http_server_serves_exposition() {
+        let registry = MetricsRegistry::new();
+        let server = registry
+            .spawn_http_server("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+
+        let response = request(addr, "GET /metrics HTTP/1.1\r\nHost: test\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(CONTENT_TYPE));
+        assert!(response.ends_with("\r\n\r\n# EOF\n"));
+
+        let response = request(addr, "POST /metrics HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+    }
+}
@@ -0,0 +1,396 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use tokio::time::Instant;
+
+use crate::app::measurement::Time;
+use crate::app::Timestamp;
+
+/// Drift is only measured between synchronizations that are at least this far apart,
+/// otherwise the millisecond resolution of DNP3 timestamps dominates the estimate
+const MIN_DRIFT_MEASUREMENT_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Drift rate of a clock in parts per million
+///
+/// The value is guaranteed to be finite and in the range `[0, 1_000_000]`.
+#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+#[cfg_attr(feature = "serialization", serde(try_from = "f64"))]
+pub struct DriftRate(f64);
+
+/// Error returned when a [`DriftRate`] is constructed from a value that is negative, larger
+/// than [`DriftRate::MAX_PPM`], infinite, or NaN
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DriftRangeError(pub f64);
+
+impl DriftRate {
+    /// Maximum drift rate in parts per million, i.e. a clock running at twice or zero speed
+    pub const MAX_PPM: f64 = 1_000_000.0;
+
+    /// Default drift assumed for a local clock, typical of an uncompensated crystal oscillator
+    pub const DEFAULT: Self = Self(100.0);
+
+    /// Construct a drift rate from a value in parts per million
+    pub fn from_ppm(ppm: f64) -> Result<Self, DriftRangeError> {
+        if (0.0..=Self::MAX_PPM).contains(&ppm) {
+            Ok(Self(ppm))
+        } else {
+            Err(DriftRangeError(ppm))
+        }
+    }
+
+    /// Value in parts per million
+    pub fn ppm(self) -> f64 {
+        self.0
+    }
+}
+
+impl Default for DriftRate {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl TryFrom<f64> for DriftRate {
+    type Error = DriftRangeError;
+
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Self::from_ppm(value)
+    }
+}
+
+impl std::fmt::Display for DriftRangeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "drift rate of {} ppm is not in the range [0, {}]",
+            self.0,
+            DriftRate::MAX_PPM
+        )
+    }
+}
+
+impl std::error::Error for DriftRangeError {}
+
+/// Configuration of the optional [`OutstationClock`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct ClockConfig {
+    /// NEED_TIME is asserted when this much time has elapsed since the last synchronization
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub sync_interval: Option<Duration>,
+    /// NEED_TIME is asserted when the estimated drift since the last synchronization exceeds this value
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub max_drift: Option<Duration>,
+    /// Drift of the local clock, assumed until it can be measured from two synchronizations
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub assumed_drift: DriftRate,
+}
+
+impl ClockConfig {
+    /// Construct a configuration that asserts NEED_TIME once per `sync_interval`
+    pub fn new(sync_interval: Duration) -> Self {
+        Self {
+            sync_interval: Some(sync_interval),
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            sync_interval: None,
+            max_drift: None,
+            assumed_drift: DriftRate::DEFAULT,
+        }
+    }
+}
+
+/// Procedure the master used to write the time to the outstation
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TimeSyncMethod {
+    /// g50v3 written after a `RECORD_CURRENT_TIME` request
+    Lan,
+    /// g50v1 written after a `DELAY_MEASURE` request
+    NonLan,
+    /// g50v1 written without a preceding `DELAY_MEASURE` request
+    Direct,
+}
+
+/// Snapshot of the state of an [`OutstationClock`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct OutstationClockStatus {
+    /// Current time of the clock
+    pub time: Time,
+    /// True if the clock is asserting NEED_TIME
+    pub need_time: bool,
+    /// Procedure used for the last synchronization, if the clock was ever synchronized
+    pub last_sync_method: Option<TimeSyncMethod>,
+    /// Time elapsed since the last synchronization
+    pub since_last_sync: Option<Duration>,
+    /// Correction applied by the last synchronization in milliseconds
+    ///
+    /// A positive value means the clock was behind the master.
+    pub last_correction_ms: Option<i64>,
+    /// Drift measured between the last two synchronizations in parts per million
+    pub measured_drift_ppm: Option<f64>,
+    /// Estimated drift accumulated since the last synchronization
+    pub estimated_drift: Option<Duration>,
+}
+
+/// Clock maintained by the outstation from the time written by the master
+///
+/// The clock keeps the master's time as an offset from a monotonic local clock. It asserts
+/// NEED_TIME until the first synchronization, and again when the configured interval
+/// elapses or the estimated drift exceeds the configured maximum.
+///
+/// Clones refer to the same clock, so the handle returned by
+/// [`OutstationHandle::get_clock`](crate::outstation::OutstationHandle::get_clock) can be used
+/// to timestamp database updates with [`OutstationClock::now`].
+#[derive(Clone)]
+pub struct OutstationClock {
+    inner: Arc<Mutex<ClockState>>,
+}
+
+#[derive(Copy, Clone)]
+struct Reference {
+    instant: Instant,
+    time: Timestamp,
+    method: TimeSyncMethod,
+}
+
+struct ClockState {
+    config: ClockConfig,
+    reference: Option<Reference>,
+    last_correction_ms: Option<i64>,
+    measured_drift_ppm: Option<f64>,
+}
+
+impl OutstationClock {
+    pub(crate) fn new(config: ClockConfig) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(ClockState {
+                config,
+                reference: None,
+                last_correction_ms: None,
+                measured_drift_ppm: None,
+            })),
+        }
+    }
+
+    /// Current time of the clock for timestamping measurements
+    ///
+    /// The time is [`Time::Synchronized`] if the clock has been synchronized and is not
+    /// asserting NEED_TIME. Before the first synchronization the system time is returned
+    /// as [`Time::Unsynchronized`].
+    pub fn now(&self) -> Time {
+        self.inner.lock().unwrap().now(Instant::now())
+    }
+
+    /// True if the clock is asserting NEED_TIME
+    pub fn need_time(&self) -> bool {
+        self.inner.lock().unwrap().need_time(Instant::now())
+    }
+
+    /// Retrieve the current state of the clock
+    pub fn status(&self) -> OutstationClockStatus {
+        self.inner.lock().unwrap().status(Instant::now())
+    }
+
+    pub(crate) fn synchronize(&self, time: Timestamp, method: TimeSyncMethod) {
+        self.inner
+            .lock()
+            .unwrap()
+            .synchronize(Instant::now(), time, method)
+    }
+}
+
+impl ClockState {
+    fn time(&self, now: Instant) -> Option<Timestamp> {
+        let reference = self.reference?;
+        reference
+            .time
+            .checked_add(now.saturating_duration_since(reference.instant))
+    }
+
+    fn now(&self, now: Instant) -> Time {
+        match self.time(now) {
+            Some(time) if !self.need_time(now) => Time::Synchronized(time),
+            Some(time) => Time::Unsynchronized(time),
+            None => Time::Unsynchronized(
+                Timestamp::try_from_system_time(SystemTime::now()).unwrap_or(Timestamp::zero()),
+            ),
+        }
+    }
+
+    fn drift_ppm(&self) -> f64 {
+        self.measured_drift_ppm
+            .unwrap_or(self.config.assumed_drift.ppm())
+            .abs()
+    }
+
+    fn estimated_drift(&self, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.reference?.instant);
+        // a measured drift can be arbitrarily large if the master corrects the time by a lot
+        let drift = elapsed.as_secs_f64() * self.drift_ppm() / 1_000_000.0;
+        Some(Duration::try_from_secs_f64(drift).unwrap_or(Duration::MAX))
+    }
+
+    fn need_time(&self, now: Instant) -> bool {
+        let reference = match self.reference {
+            Some(x) => x,
+            None => return true,
+        };
+
+        if let Some(interval) = self.config.sync_interval {
+            if now.saturating_duration_since(reference.instant) >= interval {
+                return true;
+            }
+        }
+
+        match (self.config.max_drift, self.estimated_drift(now)) {
+            (Some(max), Some(drift)) => drift > max,
+            _ => false,
+        }
+    }
+
+    fn status(&self, now: Instant) -> OutstationClockStatus {
+        OutstationClockStatus {
+            time: self.now(now),
+            need_time: self.need_time(now),
+            last_sync_method: self.reference.map(|x| x.method),
+            since_last_sync: self
+                .reference
+                .map(|x| now.saturating_duration_since(x.instant)),
+            last_correction_ms: self.last_correction_ms,
+            measured_drift_ppm: self.measured_drift_ppm,
+            estimated_drift: self.estimated_drift(now),
+        }
+    }
+
+    fn synchronize(&mut self, now: Instant, time: Timestamp, method: TimeSyncMethod) {
+        if let (Some(reference), Some(predicted)) = (self.reference, self.time(now)) {
+            let correction = time.raw_value() as i64 - predicted.raw_value() as i64;
+            let elapsed = now.saturating_duration_since(reference.instant);
+            self.last_correction_ms = Some(correction);
+            if elapsed >= MIN_DRIFT_MEASUREMENT_INTERVAL {
+                self.measured_drift_ppm =
+                    Some(correction as f64 * 1_000_000.0 / elapsed.as_millis() as f64);
+            }
+            tracing::info!(
+                "outstation clock synchronized ({method:?}), correction: {correction} ms"
+            );
+        } else {
+            tracing::info!("outstation clock synchronized ({method:?})");
+        }
+
+        self.reference = Some(Reference {
+            instant: now,
+            time,
+            method,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn state(config: ClockConfig) -> ClockState {
+        ClockState {
+            config,
+            reference: None,
+            last_correction_ms: None,
+            measured_drift_ppm: None,
+        }
+    }
+
+    #[test]
+    fn needs_time_until_synchronized() {
+        let start = Instant::now();
+        let mut clock = state(ClockConfig::default());
+        assert!(clock.need_time(start));
+        assert!(!clock.now(start).is_synchronized());
+
+        clock.synchronize(start, Timestamp::new(1_000_000), TimeSyncMethod::Direct);
+        let later = start + Duration::from_secs(3600);
+        assert!(!clock.need_time(later));
+        assert_eq!(
+            clock.now(later),
+            Time::Synchronized(Timestamp::new(1_000_000 + 3_600_000))
+        );
+    }
+
+    #[test]
+    fn needs_time_after_interval() {
+        let start = Instant::now();
+        let mut clock = state(ClockConfig::new(Duration::from_secs(600)));
+        clock.synchronize(start, Timestamp::new(1_000_000), TimeSyncMethod::Lan);
+
+        assert!(!clock.need_time(start + Duration::from_secs(599)));
+        let later = start + Duration::from_secs(600);
+        assert!(clock.need_time(later));
+        assert_eq!(
+            clock.now(later),
+            Time::Unsynchronized(Timestamp::new(1_600_000))
+        );
+    }
+
+    #[test]
+    fn measures_drift_between_synchronizations() {
+        let start = Instant::now();
+        let mut clock = state(ClockConfig {
+            max_drift: Some(Duration::from_millis(100)),
+            ..ClockConfig::default()
+        });
+        clock.synchronize(start, Timestamp::new(1_000_000), TimeSyncMethod::NonLan);
+
+        // the local clock lost 50 ms over 1000 seconds, i.e. 50 ppm
+        let second = start + Duration::from_secs(1000);
+        clock.synchronize(second, Timestamp::new(2_000_050), TimeSyncMethod::NonLan);
+
+        let status = clock.status(second);
+        assert_eq!(status.last_correction_ms, Some(50));
+        assert_eq!(status.measured_drift_ppm, Some(50.0));
+        assert_eq!(status.last_sync_method, Some(TimeSyncMethod::NonLan));
+
+        // 100 ms of drift at 50 ppm takes 2000 seconds to accumulate
+        assert!(!clock.need_time(second + Duration::from_secs(2000)));
+        assert!(clock.need_time(second + Duration::from_secs(2001)));
+    }
+
+    #[test]
+    fn rejects_invalid_drift_rates() {
+        for ppm in [-1.0, f64::NAN, f64::INFINITY, DriftRate::MAX_PPM + 1.0] {
+            assert!(DriftRate::from_ppm(ppm).is_err());
+        }
+        assert_eq!(DriftRate::from_ppm(0.0).unwrap().ppm(), 0.0);
+        assert_eq!(DriftRate::from_ppm(50.0).unwrap().ppm(), 50.0);
+    }
+
+    #[test]
+    fn large_corrections_do_not_overflow_drift_estimate() {
+        let start = Instant::now();
+        let mut clock = state(ClockConfig {
+            max_drift: Some(Duration::from_millis(100)),
+            ..ClockConfig::default()
+        });
+        clock.synchronize(start, Timestamp::new(1_000_000), TimeSyncMethod::Lan);
+
+        // the master moves the clock forward by more than a century after a minute
+        let second = start + MIN_DRIFT_MEASUREMENT_INTERVAL;
+        clock.synchronize(second, Timestamp::max(), TimeSyncMethod::Lan);
+
+        let later = second + Duration::from_secs(365 * 24 * 3600);
+        assert!(clock.need_time(later));
+        assert!(clock.status(later).estimated_drift.is_some());
+    }
+}
@@ -1,7 +1,8 @@
 use crate::app::{BufferSize, Timeout};
 use crate::decode::DecodeLevel;
 use crate::link::EndpointAddress;
-use crate::outstation::database::{ClassZeroConfig, EventBufferConfig};
+use crate::outstation::database::{ClassZeroConfig, EventBufferConfig, EventClass};
+use crate::outstation::ClockConfig;
 
 /// describes whether an optional feature is enabled or disabled
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -35,6 +36,14 @@ pub struct Features {
     /// This feature is a hack that can make configuration of some systems easier/more flexible, but
     /// should not be used when unsolicited reporting is also required.
     pub respond_to_any_master: Feature,
+    /// if enabled, static data for sparse indices is reported using index-prefixed headers (qualifier 0x28)
+    /// instead of ranged headers (qualifier 0x01) where this produces a more compact response (default == Disabled)
+    ///
+    /// Each run of consecutive indices is written using whichever encoding is smaller. Packed variations
+    /// (g1v1, g3v1, g10v1) and octet strings are always written using ranged headers. Only enable this
+    /// feature if the master accepts index-prefixed static objects in responses, as the master in this
+    /// library does.
+    pub index_prefixed_static: Feature,
 }
 
 impl Default for Features {
@@ -44,6 +53,166 @@ impl Default for Features {
             broadcast: Feature::Enabled,
             unsolicited: Feature::Enabled,
             respond_to_any_master: Feature::Disabled,
+            index_prefixed_static: Feature::Disabled,
+        }
+    }
+}
+
+/// Conditions under which the buffered events of a class trigger an unsolicited response
+///
+/// A response is triggered when `count` events of the class are buffered, or when the oldest
+/// of them has been held for `hold_time`, whichever happens first.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct UnsolicitedClassTrigger {
+    /// Number of buffered events of the class that triggers a response
+    ///
+    /// A value of 0 is treated as 1
+    pub count: u16,
+    /// Maximum time the oldest buffered event of the class is held before a response is triggered
+    ///
+    /// A value of `None` holds events until `count` is reached
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub hold_time: Option<std::time::Duration>,
+}
+
+impl UnsolicitedClassTrigger {
+    /// Trigger a response as soon as any event of the class is buffered
+    pub const fn immediate() -> Self {
+        Self {
+            count: 1,
+            hold_time: None,
+        }
+    }
+
+    /// Trigger a response when `count` events are buffered or the oldest has been held for `hold_time`
+    pub const fn new(count: u16, hold_time: std::time::Duration) -> Self {
+        Self {
+            count,
+            hold_time: Some(hold_time),
+        }
+    }
+}
+
+impl Default for UnsolicitedClassTrigger {
+    fn default() -> Self {
+        Self::immediate()
+    }
+}
+
+/// Per-class conditions that control when buffered events are reported in unsolicited responses
+///
+/// These correspond to the unsolicited class hold time and hold count parameters of device
+/// profiles. Once any enabled class triggers a response, the response contains the events
+/// of all the classes enabled for unsolicited reporting. By default, every class triggers
+/// a response immediately.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct UnsolicitedTriggers {
+    /// trigger for Class 1 events
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub class1: UnsolicitedClassTrigger,
+    /// trigger for Class 2 events
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub class2: UnsolicitedClassTrigger,
+    /// trigger for Class 3 events
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub class3: UnsolicitedClassTrigger,
+}
+
+impl UnsolicitedTriggers {
+    /// Apply the same trigger to every class
+    pub const fn all(trigger: UnsolicitedClassTrigger) -> Self {
+        Self {
+            class1: trigger,
+            class2: trigger,
+            class3: trigger,
+        }
+    }
+
+    pub(crate) fn get(&self, class: EventClass) -> UnsolicitedClassTrigger {
+        match class {
+            EventClass::Class1 => self.class1,
+            EventClass::Class2 => self.class2,
+            EventClass::Class3 => self.class3,
+        }
+    }
+}
+
+/// Settings that replace those of the [`OutstationConfig`] while a particular master is connected
+///
+/// Overrides are attached to the IP addresses, and optionally the link addresses, of masters
+/// connecting to a TCP server with
+/// [`Server::add_connection_overrides`](crate::tcp::Server::add_connection_overrides). They
+/// only apply for the duration of the connection and do not affect the database, which
+/// is shared by every connection to the outstation. A value of `None` keeps the configured setting.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ConnectionOverrides {
+    /// buffer size for transmitted solicited responses, i.e. the maximum size of a solicited fragment
+    pub solicited_buffer_size: Option<BufferSize>,
+    /// buffer size for transmitted unsolicited responses, i.e. the maximum size of an unsolicited fragment
+    pub unsolicited_buffer_size: Option<BufferSize>,
+    /// decoding level
+    pub decode_level: Option<DecodeLevel>,
+    /// whether unsolicited responses are enabled
+    pub unsolicited: Option<Feature>,
+    /// time without any link activity before the outstation will send REQUEST_LINK_STATES
+    pub keep_alive_timeout: KeepAliveOverride,
+}
+
+/// Override of [`OutstationConfig::keep_alive_timeout`] within [`ConnectionOverrides`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum KeepAliveOverride {
+    /// keep the configured keep-alive timeout
+    #[default]
+    Inherit,
+    /// never send REQUEST_LINK_STATES on this connection
+    Disabled,
+    /// send REQUEST_LINK_STATES after this amount of time without link activity
+    Timeout(std::time::Duration),
+}
+
+impl KeepAliveOverride {
+    pub(crate) fn apply(
+        self,
+        configured: Option<std::time::Duration>,
+    ) -> Option<std::time::Duration> {
+        match self {
+            Self::Inherit => configured,
+            Self::Disabled => None,
+            Self::Timeout(x) => Some(x),
+        }
+    }
+}
+
+/// [`ConnectionOverrides`] that apply when the outstation communicates with a particular master
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct OverrideRule {
+    /// link address of the master, `None` matches any master address
+    pub(crate) master_address: Option<EndpointAddress>,
+    pub(crate) overrides: ConnectionOverrides,
+}
+
+impl OverrideRule {
+    pub(crate) fn matches(&self, master_address: EndpointAddress) -> bool {
+        match self.master_address {
+            None => true,
+            Some(x) => x == master_address,
         }
     }
 }
@@ -113,6 +282,25 @@ pub struct OutstationConfig {
     /// controls responses to class 0 READ requests
     #[cfg_attr(feature = "serialization", serde(default))]
     pub class_zero: ClassZeroConfig,
+    /// optional clock maintained from the time written by the master
+    ///
+    /// When configured, the outstation manages the NEED_TIME IIN bit automatically
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub clock: Option<ClockConfig>,
+    /// per-class conditions that trigger unsolicited responses
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub unsolicited_triggers: UnsolicitedTriggers,
+    /// maximum time the outstation waits for [`OutstationApplication::before_read`](crate::outstation::OutstationApplication::before_read)
+    /// to refresh point values before responding to a READ request
+    ///
+    /// This is separate from [`OutstationApplication::get_processing_delay_ms`](crate::outstation::OutstationApplication::get_processing_delay_ms),
+    /// which is reported to the master in response to DELAY_MEASURE and must only describe how long
+    /// that request takes to process, not how long a refresh before a READ may take.
+    #[cfg_attr(
+        feature = "serialization",
+        serde(default = "OutstationConfig::default_before_read_timeout")
+    )]
+    pub before_read_timeout: std::time::Duration,
 }
 
 impl Feature {
@@ -134,6 +322,10 @@ impl OutstationConfig {
         std::time::Duration::from_secs(5)
     }
 
+    const fn default_before_read_timeout() -> core::time::Duration {
+        std::time::Duration::from_secs(1)
+    }
+
     /// Default number of object headers supported in a READ request
     pub const DEFAULT_MAX_READ_REQUEST_HEADERS: u16 = 64;
     /// Default confirmation timeout
@@ -143,6 +335,9 @@ impl OutstationConfig {
     /// Default unsolicited retry delay between series
     pub const DEFAULT_UNSOLICITED_RETRY_DELAY: std::time::Duration =
         Self::default_unsolicited_retry_delay();
+    /// Default maximum time to wait for the application to refresh values before a READ response
+    pub const DEFAULT_BEFORE_READ_TIMEOUT: std::time::Duration =
+        Self::default_before_read_timeout();
 
     /// constructs an `OutstationConfig` with default settings, except for the
     /// master and outstation link addresses which really don't have good defaults
@@ -168,6 +363,9 @@ impl OutstationConfig {
             max_read_request_headers: None,
             max_controls_per_request: None,
             class_zero: ClassZeroConfig::default(),
+            clock: None,
+            unsolicited_triggers: UnsolicitedTriggers::default(),
+            before_read_timeout: Self::DEFAULT_BEFORE_READ_TIMEOUT,
         }
     }
 }
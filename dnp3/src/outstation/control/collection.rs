@@ -9,6 +9,7 @@ use crate::outstation::control::control_type::ControlType;
 use crate::outstation::control::prefix::PrefixWriter;
 use crate::outstation::database::DatabaseHandle;
 use crate::outstation::traits::{ControlHandler, ControlSupport, OperateType};
+use crate::outstation::Command;
 
 use scursor::{WriteCursor, WriteError};
 
@@ -46,6 +47,28 @@ impl<'a> ControlTransaction<'a> {
             self.handler.begin_fragment();
         }
     }
+
+    fn start_operate<T>(
+        &mut self,
+        control: T,
+        index: u16,
+        op_type: OperateType,
+        database: &mut DatabaseHandle,
+    ) -> CommandStatus
+    where
+        T: Into<Command> + Copy,
+        dyn ControlHandler: ControlSupport<T>,
+    {
+        self.start();
+        let command = control.into();
+        // the point can't be operated again until the pending operation completes
+        if database.is_operate_pending(index, command) {
+            return CommandStatus::AlreadyActive;
+        }
+        self.handler
+            .start_operate(control, index, op_type, database)
+            .accept(database, index, command)
+    }
 }
 
 impl ControlSupport<Group12Var1> for ControlTransaction<'_> {
@@ -66,8 +89,7 @@ impl ControlSupport<Group12Var1> for ControlTransaction<'_> {
         op_type: OperateType,
         database: &mut DatabaseHandle,
     ) -> CommandStatus {
-        self.start();
-        self.handler.operate(control, index, op_type, database)
+        self.start_operate(control, index, op_type, database)
     }
 }
 
@@ -89,8 +111,7 @@ impl ControlSupport<Group41Var1> for ControlTransaction<'_> {
         op_type: OperateType,
         database: &mut DatabaseHandle,
     ) -> CommandStatus {
-        self.start();
-        self.handler.operate(control, index, op_type, database)
+        self.start_operate(control, index, op_type, database)
     }
 }
 
@@ -112,8 +133,7 @@ impl ControlSupport<Group41Var2> for ControlTransaction<'_> {
         op_type: OperateType,
         database: &mut DatabaseHandle,
     ) -> CommandStatus {
-        self.start();
-        self.handler.operate(control, index, op_type, database)
+        self.start_operate(control, index, op_type, database)
     }
 }
 
@@ -135,8 +155,7 @@ impl ControlSupport<Group41Var3> for ControlTransaction<'_> {
         op_type: OperateType,
         database: &mut DatabaseHandle,
     ) -> CommandStatus {
-        self.start();
-        self.handler.operate(control, index, op_type, database)
+        self.start_operate(control, index, op_type, database)
     }
 }
 
@@ -158,8 +177,7 @@ impl ControlSupport<Group41Var4> for ControlTransaction<'_> {
         op_type: OperateType,
         database: &mut DatabaseHandle,
     ) -> CommandStatus {
-        self.start();
-        self.handler.operate(control, index, op_type, database)
+        self.start_operate(control, index, op_type, database)
     }
 }
 
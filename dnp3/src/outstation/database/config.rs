@@ -1,3 +1,7 @@
+use crate::app::measurement::{
+    AnalogCommandValue, AnalogOutputCommandEvent, BinaryOutputCommandEvent,
+};
+
 /// Enum representing all possible `BinaryInput` event variations
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
@@ -158,6 +162,50 @@ pub enum EventAnalogOutputStatusVariation {
 )]
 pub(crate) struct EventOctetStringVariation;
 
+// g13 variation, selected according to the presence of a timestamp
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum EventBinaryOutputCommandVariation {
+    Group13Var1,
+    Group13Var2,
+}
+
+impl EventBinaryOutputCommandVariation {
+    pub(crate) fn select(event: &BinaryOutputCommandEvent) -> Self {
+        match event.time {
+            None => Self::Group13Var1,
+            Some(_) => Self::Group13Var2,
+        }
+    }
+}
+
+// g43 variation, selected according to the type of the commanded value and the presence of a timestamp
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum EventAnalogOutputCommandVariation {
+    Group43Var1,
+    Group43Var2,
+    Group43Var3,
+    Group43Var4,
+    Group43Var5,
+    Group43Var6,
+    Group43Var7,
+    Group43Var8,
+}
+
+impl EventAnalogOutputCommandVariation {
+    pub(crate) fn select(event: &AnalogOutputCommandEvent) -> Self {
+        match (event.commanded_value, event.time.is_some()) {
+            (AnalogCommandValue::I32(_), false) => Self::Group43Var1,
+            (AnalogCommandValue::I16(_), false) => Self::Group43Var2,
+            (AnalogCommandValue::I32(_), true) => Self::Group43Var3,
+            (AnalogCommandValue::I16(_), true) => Self::Group43Var4,
+            (AnalogCommandValue::F32(_), false) => Self::Group43Var5,
+            (AnalogCommandValue::F64(_), false) => Self::Group43Var6,
+            (AnalogCommandValue::F32(_), true) => Self::Group43Var7,
+            (AnalogCommandValue::F64(_), true) => Self::Group43Var8,
+        }
+    }
+}
+
 /// Enum representing all possible `BinaryInput` static variations
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
@@ -167,15 +167,14 @@ impl Selection {
     }
 }
 
+/// attributes selected by READ requests, which is specific to each outstation sharing the attributes
 pub(crate) struct AttrHandler {
-    map: SetMap,
     selection: Selection,
 }
 
 impl AttrHandler {
     pub(crate) fn new(max_selected: usize) -> Self {
         Self {
-            map: SetMap::default(),
             selection: Selection {
                 max: max_selected,
                 selected: VecDeque::with_capacity(max_selected),
@@ -183,33 +182,29 @@ impl AttrHandler {
         }
     }
 
-    pub(crate) fn get_attr_map(&mut self) -> &mut SetMap {
-        &mut self.map
-    }
-
-    pub(crate) fn write(&mut self, cursor: &mut WriteCursor) -> bool {
-        self.selection.write_all(cursor, &self.map)
+    pub(crate) fn write(&mut self, map: &SetMap, cursor: &mut WriteCursor) -> bool {
+        self.selection.write_all(cursor, map)
     }
 
     pub(crate) fn reset(&mut self) {
         self.selection.clear();
     }
 
-    pub(crate) fn select(&mut self, header: AttrHeader) -> Iin2 {
+    pub(crate) fn select(&mut self, map: &SetMap, header: AttrHeader) -> Iin2 {
         match header {
             AttrHeader::All(var) => {
                 match var {
                     255 => {
                         // list of variations for every set
                         let mut iin2 = Iin2::default();
-                        for set in self.map.sets() {
+                        for set in map.sets() {
                             iin2 |= self.selection.push(Selected::single(set, 255));
                         }
                         iin2
                     }
                     254 => {
                         // all attributes in every set
-                        self.map.sets().fold(Iin2::default(), |iin, set| {
+                        map.sets().fold(Iin2::default(), |iin, set| {
                             iin | self.selection.push(Selected::all(set))
                         })
                     }
@@ -234,7 +229,7 @@ impl AttrHandler {
                     254 => self.selection.push(Selected::all(set)),
                     255 => self.selection.push(Selected::single(set, 255)),
                     _ => {
-                        if self.map.exists(set, var) {
+                        if map.exists(set, var) {
                             self.selection.push(Selected::single(set, var))
                         } else {
                             Iin2::NO_FUNC_CODE_SUPPORT
@@ -1,84 +1,316 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::app::control::CommandStatus;
 use crate::app::Iin2;
 use crate::master::EventClasses;
-use crate::outstation::database::details::event::buffer::{EventBuffer, InsertError};
+use crate::outstation::database::details::attrs::AttrHandler;
+use crate::outstation::database::details::event::buffer::{EventBuffer, InsertError, Insertable};
 use crate::outstation::database::details::range::static_db::{
-    PointConfig, StaticDatabase, Updatable, UpdatableFlags,
+    PointConfig, StaticDatabase, StaticSelection, Updatable, UpdatableFlags,
 };
 use crate::outstation::database::read::ReadHeader;
 use crate::outstation::database::{
-    ClassZeroConfig, EventBufferConfig, ResponseInfo, UpdateFlagsType, UpdateInfo, UpdateOptions,
+    BufferedEventInfo, ClassZeroConfig, EventAnalogOutputCommandVariation,
+    EventBinaryOutputCommandVariation, EventBufferConfig, EventClass, EventOverflowListener,
+    EventType, QualityChange, ResponseInfo, SourceId, SourceUpdateOptions, StaleConfig,
+    UpdateFlagsType, UpdateInfo, UpdateOptions,
 };
 
 use crate::app::measurement::{
-    AnalogInput, AnalogOutputStatus, BinaryInput, BinaryOutputStatus, Counter,
-    DoubleBitBinaryInput, Flags, FrozenCounter, Time,
+    AnalogInput, AnalogOutputCommandEvent, AnalogOutputStatus, BinaryInput,
+    BinaryOutputCommandEvent, BinaryOutputStatus, Counter, DoubleBitBinaryInput, Flags,
+    FrozenCounter, Time,
 };
 use crate::outstation::database::details::attrs::map::SetMap;
-use crate::outstation::{BufferState, OutstationApplication};
+use crate::outstation::replication::*;
+use crate::outstation::{
+    BufferState, Command, OutputType, OutstationApplication, UnsolicitedTriggers,
+};
 use scursor::WriteCursor;
+use tokio::time::Instant;
+
+/// identifies an outstation sharing the database
+pub(crate) type MemberId = u64;
+
+/// state of the database that is specific to each outstation sharing it
+struct Member {
+    event_buffer: EventBuffer,
+    selection: StaticSelection,
+    attrs: AttrHandler,
+}
+
+struct Replication {
+    // outstation whose events are replicated
+    member: MemberId,
+    sink: Box<dyn ReplicationSink>,
+}
 
+/// The points and attributes are stored once and shared by every member, while each
+/// member has its own event buffer and READ selection
 pub(crate) struct Database {
     static_db: StaticDatabase,
-    event_buffer: EventBuffer,
-    attrs: super::attrs::AttrHandler,
+    attrs: SetMap,
+    members: BTreeMap<MemberId, Member>,
+    next_member: MemberId,
+    replication: Option<Replication>,
+    stale: BTreeMap<SourceId, StaleState>,
+    // points with an operation that hasn't completed yet
+    pending_operates: BTreeSet<(OutputType, u16)>,
+}
+
+#[derive(Copy, Clone)]
+struct StaleState {
+    config: StaleConfig,
+    // None once the points have been marked stale, until the next update
+    deadline: Option<Instant>,
+}
+
+impl StaleState {
+    fn new(config: StaleConfig, now: Instant) -> Self {
+        Self {
+            config,
+            deadline: Some(now + config.timeout),
+        }
+    }
 }
 
 impl Database {
-    pub(crate) fn new(
+    /// create a database without any members
+    pub(crate) fn new() -> Self {
+        Self {
+            static_db: StaticDatabase::new(),
+            attrs: SetMap::default(),
+            members: BTreeMap::new(),
+            next_member: 0,
+            replication: None,
+            stale: BTreeMap::new(),
+            pending_operates: BTreeSet::new(),
+        }
+    }
+
+    /// add an outstation with an empty event buffer to the database and return its id
+    pub(crate) fn join(
+        &mut self,
         max_read_selection: Option<u16>,
         class_zero_config: ClassZeroConfig,
         config: EventBufferConfig,
-    ) -> Self {
-        Self {
-            static_db: StaticDatabase::new(max_read_selection, class_zero_config),
-            event_buffer: EventBuffer::new(config),
-            attrs: super::attrs::AttrHandler::new(32),
+    ) -> MemberId {
+        let id = self.next_member;
+        self.next_member += 1;
+        self.members.insert(
+            id,
+            Member {
+                event_buffer: EventBuffer::new(config),
+                selection: StaticSelection::new(max_read_selection, class_zero_config),
+                attrs: AttrHandler::new(32),
+            },
+        );
+        id
+    }
+
+    /// remove an outstation and its event buffer from the database
+    pub(crate) fn leave(&mut self, id: MemberId) {
+        self.members.remove(&id);
+        if self.replication.as_ref().map(|x| x.member) == Some(id) {
+            self.replication = None;
         }
     }
 
-    pub(crate) fn get_attr_map(&mut self) -> &mut SetMap {
-        self.attrs.get_attr_map()
+    /// access the state of a particular outstation, if it's still a member of the database
+    pub(crate) fn member(&mut self, id: MemberId) -> Option<MemberRef<'_>> {
+        let member = self.members.get_mut(&id)?;
+        let replication = self
+            .replication
+            .as_mut()
+            .filter(|x| x.member == id)
+            .map(|x| &mut x.sink);
+        Some(MemberRef {
+            static_db: &self.static_db,
+            attrs: &self.attrs,
+            member,
+            replication,
+        })
     }
 
-    pub(crate) fn reset(&mut self) {
-        self.static_db.reset();
-        self.event_buffer.reset();
-        self.attrs.reset();
+    pub(crate) fn get_attr_map(&mut self) -> &mut SetMap {
+        &mut self.attrs
     }
 
     pub(crate) fn set_analog_deadband(&mut self, index: u16, deadband: f64) -> bool {
-        self.static_db.set_analog_deadband(index, deadband)
+        let success = self.static_db.set_analog_deadband(index, deadband);
+        if success {
+            self.replicate(|| Message::AnalogDeadband(index, deadband));
+        }
+        success
     }
 
-    pub(crate) fn clear_written_events(
+    pub(crate) fn start_replication(&mut self, id: MemberId, mut sink: Box<dyn ReplicationSink>) {
+        if let Some(snapshot) = self.snapshot(id) {
+            sink.replicate(ReplicationMessage::new(Message::Snapshot(snapshot)));
+            self.replication = Some(Replication { member: id, sink });
+        }
+    }
+
+    pub(crate) fn stop_replication(&mut self) {
+        self.replication = None;
+    }
+
+    /// apply a message to the points and to the event buffer of the specified member
+    pub(crate) fn apply_replication(
         &mut self,
-        app: &mut dyn OutstationApplication,
-    ) -> BufferState {
-        self.event_buffer.clear_written(app);
-        self.event_buffer.buffer_state()
+        id: MemberId,
+        msg: Message,
+    ) -> Result<(), ReplicationError> {
+        match msg {
+            Message::Snapshot(snapshot) => self.restore(id, snapshot),
+            Message::Update(update) => {
+                let report = Some(id);
+                let result = match &update.value {
+                    PointValue::BinaryInput(x) => {
+                        self.update_for(report, x, update.index, update.options)
+                    }
+                    PointValue::DoubleBitBinaryInput(x) => {
+                        self.update_for(report, x, update.index, update.options)
+                    }
+                    PointValue::BinaryOutputStatus(x) => {
+                        self.update_for(report, x, update.index, update.options)
+                    }
+                    PointValue::Counter(x) => {
+                        self.update_for(report, x, update.index, update.options)
+                    }
+                    PointValue::FrozenCounter(x) => {
+                        self.update_for(report, x, update.index, update.options)
+                    }
+                    PointValue::AnalogInput(x) => {
+                        self.update_for(report, x, update.index, update.options)
+                    }
+                    PointValue::AnalogOutputStatus(x) => {
+                        self.update_for(report, x, update.index, update.options)
+                    }
+                    PointValue::OctetString(x) => {
+                        self.update_for(report, &**x, update.index, update.options)
+                    }
+                };
+                if result == update.result {
+                    Ok(())
+                } else if result == UpdateInfo::NoPoint {
+                    Err(ReplicationError::UnknownPoint)
+                } else {
+                    Err(ReplicationError::OutOfSync)
+                }
+            }
+            Message::AnalogDeadband(index, deadband) => {
+                if self.set_analog_deadband(index, deadband) {
+                    Ok(())
+                } else {
+                    Err(ReplicationError::UnknownPoint)
+                }
+            }
+            Message::EventsCleared(ids) => {
+                // events may have already been discarded by an overflow in the standby
+                if let Some(member) = self.members.get_mut(&id) {
+                    member.event_buffer.remove_by_id(ids);
+                }
+                Ok(())
+            }
+        }
     }
 
-    pub(crate) fn unwritten_classes(&self) -> EventClasses {
-        self.event_buffer.unwritten_classes()
+    fn replicate<F>(&mut self, create: F)
+    where
+        F: FnOnce() -> Message,
+    {
+        if let Some(replication) = self.replication.as_mut() {
+            replication
+                .sink
+                .replicate(ReplicationMessage::new(create()));
+        }
     }
 
-    pub(crate) fn is_overflown(&self) -> bool {
-        self.event_buffer.is_overflown()
+    /// points of the database along with the events of the specified member
+    pub(crate) fn snapshot(&self, id: MemberId) -> Option<Snapshot> {
+        let member = self.members.get(&id)?;
+        Some(Snapshot {
+            points: self.static_db.point_states(),
+            deadbands: self.static_db.analog_deadbands(),
+            events: member.event_buffer.buffered_events(),
+            next_event_id: member.event_buffer.next_id(),
+            is_overflown: member.event_buffer.is_overflown(),
+        })
     }
 
-    pub(crate) fn select_by_header(&mut self, header: ReadHeader) -> Iin2 {
-        match header {
-            ReadHeader::Static(header) => self.static_db.select(header),
-            ReadHeader::Event(header) => {
-                self.event_buffer.select_by_header(header);
-                Iin2::default()
+    fn restore(&mut self, id: MemberId, snapshot: Snapshot) -> Result<(), ReplicationError> {
+        for point in snapshot.points {
+            let success = match (point.current, point.last_event) {
+                (PointValue::BinaryInput(c), PointValue::BinaryInput(l)) => {
+                    self.static_db.restore(point.index, c, l)
+                }
+                (PointValue::DoubleBitBinaryInput(c), PointValue::DoubleBitBinaryInput(l)) => {
+                    self.static_db.restore(point.index, c, l)
+                }
+                (PointValue::BinaryOutputStatus(c), PointValue::BinaryOutputStatus(l)) => {
+                    self.static_db.restore(point.index, c, l)
+                }
+                (PointValue::Counter(c), PointValue::Counter(l)) => {
+                    self.static_db.restore(point.index, c, l)
+                }
+                (PointValue::FrozenCounter(c), PointValue::FrozenCounter(l)) => {
+                    self.static_db.restore(point.index, c, l)
+                }
+                (PointValue::AnalogInput(c), PointValue::AnalogInput(l)) => {
+                    self.static_db.restore(point.index, c, l)
+                }
+                (PointValue::AnalogOutputStatus(c), PointValue::AnalogOutputStatus(l)) => {
+                    self.static_db.restore(point.index, c, l)
+                }
+                (PointValue::OctetString(c), PointValue::OctetString(l)) => {
+                    self.static_db.restore(point.index, *c, *l)
+                }
+                _ => return Err(ReplicationError::BadEncoding),
+            };
+            if !success {
+                return Err(ReplicationError::UnknownPoint);
             }
-            ReadHeader::Attr(header) => self.attrs.select(header),
         }
-    }
 
-    pub(crate) fn select_event_classes(&mut self, classes: EventClasses) -> usize {
-        self.event_buffer.select_by_class(classes, None)
+        for (index, deadband) in snapshot.deadbands {
+            if !self.static_db.set_analog_deadband(index, deadband) {
+                return Err(ReplicationError::UnknownPoint);
+            }
+        }
+
+        let member = match self.members.get_mut(&id) {
+            Some(x) => x,
+            // the outstation no longer exists, so there is no event buffer to restore
+            None => return Ok(()),
+        };
+
+        member
+            .event_buffer
+            .clear(snapshot.next_event_id, snapshot.is_overflown);
+        for event in snapshot.events {
+            let success = match &event.value {
+                PointValue::BinaryInput(x) => restore_event(&self.static_db, member, &event, x),
+                PointValue::DoubleBitBinaryInput(x) => {
+                    restore_event(&self.static_db, member, &event, x)
+                }
+                PointValue::BinaryOutputStatus(x) => {
+                    restore_event(&self.static_db, member, &event, x)
+                }
+                PointValue::Counter(x) => restore_event(&self.static_db, member, &event, x),
+                PointValue::FrozenCounter(x) => restore_event(&self.static_db, member, &event, x),
+                PointValue::AnalogInput(x) => restore_event(&self.static_db, member, &event, x),
+                PointValue::AnalogOutputStatus(x) => {
+                    restore_event(&self.static_db, member, &event, x)
+                }
+                PointValue::OctetString(x) => restore_event(&self.static_db, member, &event, &**x),
+            };
+            if !success {
+                return Err(ReplicationError::UnknownPoint);
+            }
+        }
+
+        Ok(())
     }
 
     pub(crate) fn add<T>(&mut self, index: u16, config: PointConfig<T>) -> bool
@@ -152,33 +384,456 @@ impl Database {
     }
 
     pub(crate) fn update<T>(&mut self, value: &T, index: u16, options: UpdateOptions) -> UpdateInfo
+    where
+        T: Updatable,
+    {
+        let result = self.update_no_refresh(value, index, options);
+        if result != UpdateInfo::NoPoint {
+            self.refresh_source::<T>(index);
+        }
+        result
+    }
+
+    /// restart the stale timer of the source of the point, if any
+    fn refresh_source<T>(&mut self, index: u16)
+    where
+        T: Updatable,
+    {
+        if self.stale.is_empty() {
+            return;
+        }
+
+        if let Some(source) = self.static_db.source::<T>(index) {
+            if let Some(state) = self.stale.get_mut(&source) {
+                state.deadline = Some(Instant::now() + state.config.timeout);
+            }
+        }
+    }
+
+    pub(crate) fn set_source(
+        &mut self,
+        flags_type: UpdateFlagsType,
+        index: u16,
+        source: Option<SourceId>,
+    ) -> bool {
+        match flags_type {
+            UpdateFlagsType::BinaryInput => self.static_db.set_source::<BinaryInput>(index, source),
+            UpdateFlagsType::DoubleBitBinaryInput => self
+                .static_db
+                .set_source::<DoubleBitBinaryInput>(index, source),
+            UpdateFlagsType::BinaryOutputStatus => self
+                .static_db
+                .set_source::<BinaryOutputStatus>(index, source),
+            UpdateFlagsType::Counter => self.static_db.set_source::<Counter>(index, source),
+            UpdateFlagsType::FrozenCounter => {
+                self.static_db.set_source::<FrozenCounter>(index, source)
+            }
+            UpdateFlagsType::AnalogInput => self.static_db.set_source::<AnalogInput>(index, source),
+            UpdateFlagsType::AnalogOutputStatus => self
+                .static_db
+                .set_source::<AnalogOutputStatus>(index, source),
+        }
+    }
+
+    pub(crate) fn update_source_quality(
+        &mut self,
+        source: SourceId,
+        change: QualityChange,
+        time: Option<Time>,
+        options: SourceUpdateOptions,
+    ) -> usize {
+        self.update_source_quality_by_type::<BinaryInput>(
+            source,
+            change,
+            time,
+            options.binary_input,
+        ) + self.update_source_quality_by_type::<DoubleBitBinaryInput>(
+            source,
+            change,
+            time,
+            options.double_bit_binary_input,
+        ) + self.update_source_quality_by_type::<BinaryOutputStatus>(
+            source,
+            change,
+            time,
+            options.binary_output_status,
+        ) + self.update_source_quality_by_type::<Counter>(source, change, time, options.counter)
+            + self.update_source_quality_by_type::<FrozenCounter>(
+                source,
+                change,
+                time,
+                options.frozen_counter,
+            )
+            + self.update_source_quality_by_type::<AnalogInput>(
+                source,
+                change,
+                time,
+                options.analog_input,
+            )
+            + self.update_source_quality_by_type::<AnalogOutputStatus>(
+                source,
+                change,
+                time,
+                options.analog_output_status,
+            )
+    }
+
+    fn update_source_quality_by_type<T: UpdatableFlags>(
+        &mut self,
+        source: SourceId,
+        change: QualityChange,
+        time: Option<Time>,
+        options: UpdateOptions,
+    ) -> usize {
+        let indices = self.static_db.indices_with_source::<T>(source);
+        for index in indices.iter() {
+            if let Some(mut x) = self.static_db.get::<T>(*index) {
+                let flags = change.apply(x.flags());
+                let time = time.or(x.time());
+                x.update_flags(flags, time);
+                // a quality change does not count as an update from the source
+                self.update_no_refresh::<T>(&x, *index, options);
+            }
+        }
+        indices.len()
+    }
+
+    pub(crate) fn set_stale_detection(&mut self, source: SourceId, config: Option<StaleConfig>) {
+        match config {
+            None => {
+                self.stale.remove(&source);
+            }
+            Some(config) => {
+                self.stale
+                    .insert(source, StaleState::new(config, Instant::now()));
+            }
+        }
+    }
+
+    /// apply the quality change to sources whose timer expired and return the next deadline, if any
+    ///
+    /// The points of the expired sources are timestamped with `time`, or keep their current
+    /// timestamp if it's `None`
+    pub(crate) fn check_stale(&mut self, now: Instant, time: Option<Time>) -> Option<Instant> {
+        let expired: Vec<(SourceId, StaleConfig)> = self
+            .stale
+            .iter_mut()
+            .filter_map(|(source, state)| match state.deadline {
+                Some(deadline) if deadline <= now => {
+                    state.deadline = None;
+                    Some((*source, state.config))
+                }
+                _ => None,
+            })
+            .collect();
+
+        for (source, config) in expired {
+            self.update_source_quality(source, config.change, time, config.options);
+        }
+
+        self.stale.values().filter_map(|x| x.deadline).min()
+    }
+
+    fn update_no_refresh<T>(&mut self, value: &T, index: u16, options: UpdateOptions) -> UpdateInfo
+    where
+        T: Updatable,
+    {
+        self.update_for_no_refresh(None, value, index, options)
+    }
+
+    /// update a point and return the result for the event buffer of `report`, or the
+    /// [`reporting member`](Self::reporting_member) if `None`
+    fn update_for<T>(
+        &mut self,
+        report: Option<MemberId>,
+        value: &T,
+        index: u16,
+        options: UpdateOptions,
+    ) -> UpdateInfo
+    where
+        T: Updatable,
+    {
+        let result = self.update_for_no_refresh(report, value, index, options);
+        if result != UpdateInfo::NoPoint {
+            self.refresh_source::<T>(index);
+        }
+        result
+    }
+
+    fn update_for_no_refresh<T>(
+        &mut self,
+        report: Option<MemberId>,
+        value: &T,
+        index: u16,
+        options: UpdateOptions,
+    ) -> UpdateInfo
     where
         T: Updatable,
     {
         let (exists, event_data) = self.static_db.update(value, index, options);
 
-        // if an event should be produced, insert it into the buffer
-        if let Some((variation, class)) = event_data {
-            // Overflow is handled in the event buffer
-            return match self.event_buffer.insert(index, class, value, variation) {
-                Ok(x) => UpdateInfo::Created(x),
-                Err(InsertError::TypeMaxIsZero) => UpdateInfo::NoEvent,
-                Err(InsertError::Overflow { created, discarded }) => {
-                    UpdateInfo::Overflow { created, discarded }
+        if !exists {
+            return UpdateInfo::NoPoint;
+        }
+
+        // if an event should be produced, insert it into the buffer of every member
+        let result = match event_data {
+            None => UpdateInfo::NoEvent,
+            Some((variation, class)) => self.insert_event(report, index, class, value, variation),
+        };
+
+        self.replicate(|| {
+            Message::Update(PointUpdate {
+                index,
+                value: value.to_point_value(),
+                options,
+                result,
+            })
+        });
+
+        result
+    }
+
+    /// record the command event of an operation that completed asynchronously and, if the
+    /// operation succeeded, update the value of the output status point
+    pub(crate) fn is_operate_pending(&self, index: u16, command: Command) -> bool {
+        self.pending_operates
+            .contains(&(command.output_type(), index))
+    }
+
+    pub(crate) fn begin_operate(&mut self, index: u16, command: Command) {
+        self.pending_operates.insert((command.output_type(), index));
+    }
+
+    pub(crate) fn complete_operate(
+        &mut self,
+        index: u16,
+        command: Command,
+        status: CommandStatus,
+        time: Option<Time>,
+    ) -> UpdateInfo {
+        self.pending_operates
+            .remove(&(command.output_type(), index));
+        match command {
+            Command::Binary { state, latch } => {
+                let event = BinaryOutputCommandEvent {
+                    commanded_state: state,
+                    status,
+                    time,
+                };
+                let variation = EventBinaryOutputCommandVariation::select(&event);
+                let info =
+                    self.insert_command_event::<BinaryOutputStatus, _>(index, &event, variation);
+                if status == CommandStatus::Success && latch {
+                    if let Some(mut x) = self.static_db.get::<BinaryOutputStatus>(index) {
+                        x.value = state;
+                        x.time = time.or(x.time);
+                        self.update(&x, index, UpdateOptions::detect_event());
+                    }
                 }
-            };
+                info
+            }
+            Command::Analog(value) => {
+                let event = AnalogOutputCommandEvent {
+                    status,
+                    commanded_value: value,
+                    time,
+                };
+                let variation = EventAnalogOutputCommandVariation::select(&event);
+                let info =
+                    self.insert_command_event::<AnalogOutputStatus, _>(index, &event, variation);
+                if status == CommandStatus::Success {
+                    if let Some(mut x) = self.static_db.get::<AnalogOutputStatus>(index) {
+                        x.value = value.to_f64();
+                        x.time = time.or(x.time);
+                        self.update(&x, index, UpdateOptions::detect_event());
+                    }
+                }
+                info
+            }
         }
+    }
 
-        if exists {
-            UpdateInfo::NoEvent
-        } else {
-            UpdateInfo::NoPoint
+    /// command events use the event class of the output status point, `P`, at the same index
+    fn insert_command_event<P, E>(
+        &mut self,
+        index: u16,
+        event: &E,
+        variation: E::EventVariation,
+    ) -> UpdateInfo
+    where
+        P: Updatable,
+        E: Insertable,
+    {
+        match self.static_db.event_class::<P>(index) {
+            None => UpdateInfo::NoPoint,
+            Some(None) => UpdateInfo::NoEvent,
+            Some(Some(class)) => self.insert_event(None, index, class, event, variation),
+        }
+    }
+
+    /// insert an event into the buffer of every member and return the result for `report`, or
+    /// the [`reporting member`](Self::reporting_member) if `None`
+    fn insert_event<E>(
+        &mut self,
+        report: Option<MemberId>,
+        index: u16,
+        class: EventClass,
+        event: &E,
+        variation: E::EventVariation,
+    ) -> UpdateInfo
+    where
+        E: Insertable,
+    {
+        let report = report.or_else(|| self.reporting_member());
+        let mut result = UpdateInfo::NoEvent;
+        for (id, member) in self.members.iter_mut() {
+            let info = insert_result(member.event_buffer.insert(index, class, event, variation));
+            if Some(*id) == report {
+                result = info;
+            }
+        }
+        result
+    }
+
+    /// member whose events are replicated or, if replication isn't active, the oldest member
+    fn reporting_member(&self) -> Option<MemberId> {
+        match self.replication.as_ref() {
+            Some(x) => Some(x.member),
+            None => self.members.keys().next().copied(),
+        }
+    }
+}
+
+/// state of a single member of the database along with the points and attributes it shares
+pub(crate) struct MemberRef<'a> {
+    static_db: &'a StaticDatabase,
+    attrs: &'a SetMap,
+    member: &'a mut Member,
+    // sink of the replication if the events of this member are replicated
+    replication: Option<&'a mut Box<dyn ReplicationSink>>,
+}
+
+impl MemberRef<'_> {
+    pub(crate) fn reset(&mut self) {
+        self.member.selection.reset();
+        self.member.event_buffer.reset();
+        self.member.attrs.reset();
+    }
+
+    pub(crate) fn clear_written_events(
+        &mut self,
+        app: &mut dyn OutstationApplication,
+    ) -> BufferState {
+        match self.replication.as_mut() {
+            None => {
+                self.member.event_buffer.clear_written(app);
+            }
+            Some(sink) => {
+                let mut ids = Vec::new();
+                self.member.event_buffer.clear_written_with(|id| {
+                    app.event_cleared(id);
+                    ids.push(id);
+                });
+                if !ids.is_empty() {
+                    sink.replicate(ReplicationMessage::new(Message::EventsCleared(ids)));
+                }
+            }
+        }
+        self.member.event_buffer.buffer_state()
+    }
+
+    pub(crate) fn buffer_state(&self) -> BufferState {
+        self.member.event_buffer.buffer_state()
+    }
+
+    pub(crate) fn events_info(&self) -> Vec<BufferedEventInfo> {
+        self.member.event_buffer.events_info()
+    }
+
+    pub(crate) fn set_overflow_listener(
+        &mut self,
+        listener: Option<Box<dyn EventOverflowListener>>,
+    ) {
+        self.member.event_buffer.set_overflow_listener(listener);
+    }
+
+    pub(crate) fn clear_events_by_class(&mut self, classes: EventClasses) -> usize {
+        let mut ids = Vec::new();
+        let count = self
+            .member
+            .event_buffer
+            .clear_by_class(classes, |id| ids.push(id));
+        self.replicate_cleared(ids);
+        count
+    }
+
+    pub(crate) fn clear_events_by_type(&mut self, event_type: EventType) -> usize {
+        let mut ids = Vec::new();
+        let count = self
+            .member
+            .event_buffer
+            .clear_by_type(event_type, |id| ids.push(id));
+        self.replicate_cleared(ids);
+        count
+    }
+
+    fn replicate_cleared(&mut self, ids: Vec<u64>) {
+        if let Some(sink) = self.replication.as_mut() {
+            if !ids.is_empty() {
+                sink.replicate(ReplicationMessage::new(Message::EventsCleared(ids)));
+            }
         }
     }
 
-    pub(crate) fn write_response_headers(&mut self, cursor: &mut WriteCursor) -> ResponseInfo {
+    pub(crate) fn unwritten_classes(&self) -> EventClasses {
+        self.member.event_buffer.unwritten_classes()
+    }
+
+    pub(crate) fn unsolicited_trigger_time(
+        &self,
+        classes: EventClasses,
+        triggers: &UnsolicitedTriggers,
+    ) -> Option<Instant> {
+        self.member
+            .event_buffer
+            .unsolicited_trigger_time(classes, triggers)
+    }
+
+    pub(crate) fn is_overflown(&self) -> bool {
+        self.member.event_buffer.is_overflown()
+    }
+
+    /// high-water mark and number of overflows of the event buffer
+    pub(crate) fn event_buffer_statistics(&self) -> (usize, u64) {
+        (
+            self.member.event_buffer.high_water_mark(),
+            self.member.event_buffer.overflows(),
+        )
+    }
+
+    pub(crate) fn select_by_header(&mut self, header: ReadHeader) -> Iin2 {
+        match header {
+            ReadHeader::Static(header) => self.member.selection.select(self.static_db, header),
+            ReadHeader::Event(header) => {
+                self.member.event_buffer.select_by_header(header);
+                Iin2::default()
+            }
+            ReadHeader::Attr(header) => self.member.attrs.select(self.attrs, header),
+        }
+    }
+
+    pub(crate) fn select_event_classes(&mut self, classes: EventClasses) -> usize {
+        self.member.event_buffer.select_by_class(classes, None)
+    }
+
+    pub(crate) fn write_response_headers(
+        &mut self,
+        cursor: &mut WriteCursor,
+        prefixed: bool,
+    ) -> ResponseInfo {
         // first we write events
-        let result = self.event_buffer.write_events(cursor);
+        let result = self.member.event_buffer.write_events(cursor);
         let has_events = match result {
             Ok(count) => count > 0,
             Err(count) => count > 0,
@@ -190,12 +845,15 @@ impl Database {
             false
         } else {
             // write all events to we can try to write all static data
-            self.static_db.write(cursor).is_ok()
+            self.member
+                .selection
+                .write(self.static_db, cursor, prefixed)
+                .is_ok()
         };
 
         // next write device attributes
         let complete = if complete {
-            self.attrs.write(cursor)
+            self.member.attrs.write(self.attrs, cursor)
         } else {
             false
         };
@@ -208,9 +866,39 @@ impl Database {
 
     pub(crate) fn write_events_only(&mut self, cursor: &mut WriteCursor) -> usize {
         // doesn't matter if we wrote all of them or not
-        match self.event_buffer.write_events(cursor) {
+        match self.member.event_buffer.write_events(cursor) {
             Ok(x) => x,
             Err(x) => x,
         }
     }
 }
+
+fn restore_event<T>(
+    static_db: &StaticDatabase,
+    member: &mut Member,
+    event: &BufferedEvent,
+    value: &T,
+) -> bool
+where
+    T: Updatable,
+{
+    match static_db.event_variation::<T>(event.index) {
+        None => false,
+        Some(variation) => {
+            member
+                .event_buffer
+                .restore(event.index, event.id, event.class, value, variation);
+            true
+        }
+    }
+}
+
+fn insert_result(result: Result<u64, InsertError>) -> UpdateInfo {
+    match result {
+        Ok(x) => UpdateInfo::Created(x),
+        Err(InsertError::TypeMaxIsZero) => UpdateInfo::NoEvent,
+        Err(InsertError::Overflow { created, discarded }) => {
+            UpdateInfo::Overflow { created, discarded }
+        }
+    }
+}
@@ -6,15 +6,25 @@ use crate::app::measurement::{self};
 use crate::master::EventClasses;
 use crate::outstation::database::config::*;
 use crate::outstation::database::read::EventReadHeader;
-use crate::outstation::database::{EventBufferConfig, EventClass};
+use crate::outstation::database::{
+    BufferedEventInfo, EventBufferConfig, EventClass, EventOverflow, EventOverflowListener,
+    EventType,
+};
 
 use crate::util::BadWrite;
 
+use std::collections::BTreeSet;
+
+use tokio::time::Instant;
+
 use super::list::VecList;
 use super::writer::EventWriter;
 
 use crate::outstation::database::details::event::traits::OctetStringLength;
-use crate::outstation::{BufferState, ClassCount, OutstationApplication, TypeCount};
+use crate::outstation::replication::{BufferedEvent, PointValue};
+use crate::outstation::{
+    BufferState, ClassCount, OutstationApplication, TypeCount, UnsolicitedTriggers,
+};
 use scursor::WriteCursor;
 
 impl From<EventClass> for EventClasses {
@@ -165,6 +175,8 @@ pub(crate) struct TypeCounter {
     num_analog: Count,
     num_analog_output_status: Count,
     num_octet_string: Count,
+    num_binary_output_command: Count,
+    num_analog_output_command: Count,
 }
 
 impl From<TypeCounter> for TypeCount {
@@ -193,6 +205,8 @@ impl TypeCounter {
             num_analog: Count::new(),
             num_analog_output_status: Count::new(),
             num_octet_string: Count::new(),
+            num_binary_output_command: Count::new(),
+            num_analog_output_command: Count::new(),
         }
     }
 
@@ -205,6 +219,8 @@ impl TypeCounter {
         self.num_analog.zero();
         self.num_analog_output_status.zero();
         self.num_octet_string.zero();
+        self.num_binary_output_command.zero();
+        self.num_analog_output_command.zero();
     }
 
     fn increment(&mut self, event: &Event) {
@@ -230,6 +246,8 @@ impl TypeCounter {
             Event::Analog(_, _) => op(&mut self.num_analog),
             Event::AnalogOutputStatus(_, _) => op(&mut self.num_analog_output_status),
             Event::OctetString(_, _) => op(&mut self.num_octet_string),
+            Event::BinaryOutputCommand(_, _) => op(&mut self.num_binary_output_command),
+            Event::AnalogOutputCommand(_, _) => op(&mut self.num_analog_output_command),
         }
     }
 }
@@ -278,6 +296,8 @@ impl Counters {
             Event::Analog(_, _) => self.types.num_analog.decrement(),
             Event::AnalogOutputStatus(_, _) => self.types.num_analog_output_status.decrement(),
             Event::OctetString(_, _) => self.types.num_octet_string.decrement(),
+            Event::BinaryOutputCommand(_, _) => self.types.num_binary_output_command.decrement(),
+            Event::AnalogOutputCommand(_, _) => self.types.num_analog_output_command.decrement(),
         }
     }
 }
@@ -335,6 +355,14 @@ pub(crate) enum Event {
         Variation<EventAnalogOutputStatusVariation>,
     ),
     OctetString(Box<[u8]>, Variation<EventOctetStringVariation>),
+    BinaryOutputCommand(
+        measurement::BinaryOutputCommandEvent,
+        Variation<EventBinaryOutputCommandVariation>,
+    ),
+    AnalogOutputCommand(
+        measurement::AnalogOutputCommandEvent,
+        Variation<EventAnalogOutputCommandVariation>,
+    ),
 }
 
 impl Event {
@@ -348,9 +376,60 @@ impl Event {
             Event::Analog(_, v) => v.select_default(),
             Event::AnalogOutputStatus(_, v) => v.select_default(),
             Event::OctetString(_, v) => v.select_default(),
+            Event::BinaryOutputCommand(_, v) => v.select_default(),
+            Event::AnalogOutputCommand(_, v) => v.select_default(),
         }
     }
 
+    fn event_type(&self) -> EventType {
+        match self {
+            Event::Binary(_, _) => EventType::BinaryInput,
+            Event::DoubleBitBinary(_, _) => EventType::DoubleBitBinaryInput,
+            Event::BinaryOutputStatus(_, _) => EventType::BinaryOutputStatus,
+            Event::Counter(_, _) => EventType::Counter,
+            Event::FrozenCounter(_, _) => EventType::FrozenCounter,
+            Event::Analog(_, _) => EventType::AnalogInput,
+            Event::AnalogOutputStatus(_, _) => EventType::AnalogOutputStatus,
+            Event::OctetString(_, _) => EventType::OctetString,
+            Event::BinaryOutputCommand(_, _) => EventType::BinaryOutputCommand,
+            Event::AnalogOutputCommand(_, _) => EventType::AnalogOutputCommand,
+        }
+    }
+
+    fn time(&self) -> Option<measurement::Time> {
+        match self {
+            Event::Binary(x, _) => x.time,
+            Event::DoubleBitBinary(x, _) => x.time,
+            Event::BinaryOutputStatus(x, _) => x.time,
+            Event::Counter(x, _) => x.time,
+            Event::FrozenCounter(x, _) => x.time,
+            Event::Analog(x, _) => x.time,
+            Event::AnalogOutputStatus(x, _) => x.time,
+            Event::OctetString(_, _) => None,
+            Event::BinaryOutputCommand(x, _) => x.time,
+            Event::AnalogOutputCommand(x, _) => x.time,
+        }
+    }
+
+    /// command events are not replicated
+    fn to_point_value(&self) -> Option<PointValue> {
+        let value = match self {
+            Event::Binary(x, _) => PointValue::BinaryInput(*x),
+            Event::DoubleBitBinary(x, _) => PointValue::DoubleBitBinaryInput(*x),
+            Event::BinaryOutputStatus(x, _) => PointValue::BinaryOutputStatus(*x),
+            Event::Counter(x, _) => PointValue::Counter(*x),
+            Event::FrozenCounter(x, _) => PointValue::FrozenCounter(*x),
+            Event::Analog(x, _) => PointValue::AnalogInput(*x),
+            Event::AnalogOutputStatus(x, _) => PointValue::AnalogOutputStatus(*x),
+            // the buffer only ever contains octet strings created from a valid OctetString
+            Event::OctetString(x, _) => PointValue::OctetString(Box::new(
+                measurement::OctetString::new(x).unwrap_or_default(),
+            )),
+            Event::BinaryOutputCommand(_, _) | Event::AnalogOutputCommand(_, _) => return None,
+        };
+        Some(value)
+    }
+
     fn write(
         &self,
         index: u16,
@@ -368,6 +447,12 @@ impl Event {
             Event::OctetString(evt, _) => {
                 writer.write(cursor, evt, index, OctetStringLength(evt.len()))
             }
+            Event::BinaryOutputCommand(evt, v) => {
+                writer.write(cursor, evt, index, v.selected.get())
+            }
+            Event::AnalogOutputCommand(evt, v) => {
+                writer.write(cursor, evt, index, v.selected.get())
+            }
         }
     }
 }
@@ -386,6 +471,8 @@ pub(crate) struct EventRecord {
     class: EventClass,
     event: Event,
     state: Cell<EventState>,
+    /// when the event was added to the buffer
+    created: Instant,
 }
 
 impl EventRecord {
@@ -396,6 +483,18 @@ impl EventRecord {
             class,
             event,
             state: Cell::new(EventState::Unselected),
+            created: Instant::now(),
+        }
+    }
+
+    fn info(&self) -> BufferedEventInfo {
+        BufferedEventInfo {
+            id: self.id,
+            event_type: self.event.event_type(),
+            index: self.index,
+            class: self.class,
+            time: self.event.time(),
+            written: self.state.get() == EventState::Written,
         }
     }
 }
@@ -413,13 +512,64 @@ pub(crate) trait Insertable: Sized {
     fn select_variation(record: &EventRecord, variation: Self::EventVariation) -> bool;
 }
 
+/// creation times of the buffered events of each class, ordered oldest first
+#[derive(Default)]
+struct CreationTimes {
+    class_1: BTreeSet<(Instant, u64)>,
+    class_2: BTreeSet<(Instant, u64)>,
+    class_3: BTreeSet<(Instant, u64)>,
+}
+
+impl CreationTimes {
+    fn get(&self, class: EventClass) -> &BTreeSet<(Instant, u64)> {
+        match class {
+            EventClass::Class1 => &self.class_1,
+            EventClass::Class2 => &self.class_2,
+            EventClass::Class3 => &self.class_3,
+        }
+    }
+
+    fn get_mut(&mut self, class: EventClass) -> &mut BTreeSet<(Instant, u64)> {
+        match class {
+            EventClass::Class1 => &mut self.class_1,
+            EventClass::Class2 => &mut self.class_2,
+            EventClass::Class3 => &mut self.class_3,
+        }
+    }
+
+    fn add(&mut self, class: EventClass, created: Instant, id: u64) {
+        self.get_mut(class).insert((created, id));
+    }
+
+    fn remove(&mut self, record: &EventRecord) {
+        self.get_mut(record.class)
+            .remove(&(record.created, record.id));
+    }
+
+    fn oldest(&self, class: EventClass) -> Option<Instant> {
+        self.get(class).first().map(|(time, _)| *time)
+    }
+
+    fn clear(&mut self) {
+        self.class_1.clear();
+        self.class_2.clear();
+        self.class_3.clear();
+    }
+}
+
 pub(crate) struct EventBuffer {
     config: EventBufferConfig,
     events: VecList<EventRecord>,
+    created: CreationTimes,
     total: Counters,
     written: Counters,
     is_overflown: bool,
     next: u64,
+    /// largest number of events ever held in the buffer
+    high_water_mark: usize,
+    /// number of events discarded because the buffer was full
+    overflows: u64,
+    overflow_listener: Option<Box<dyn EventOverflowListener>>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -434,13 +584,24 @@ impl EventBuffer {
         Self {
             config,
             events: VecList::new(max_size),
+            created: CreationTimes::default(),
             total: Counters::new(),
             written: Counters::new(),
             is_overflown: false,
             next: 0,
+            high_water_mark: 0,
+            overflows: 0,
+            overflow_listener: None,
         }
     }
 
+    pub(crate) fn set_overflow_listener(
+        &mut self,
+        listener: Option<Box<dyn EventOverflowListener>>,
+    ) {
+        self.overflow_listener = listener;
+    }
+
     pub(crate) fn unwritten_classes(&self) -> EventClasses {
         let unwritten = self.total.classes.subtract(&self.written.classes);
         EventClasses::new(
@@ -450,6 +611,46 @@ impl EventBuffer {
         )
     }
 
+    /// time at which the buffered events of the specified classes trigger an unsolicited response
+    ///
+    /// The returned time is in the past if a response is already due.
+    pub(crate) fn unsolicited_trigger_time(
+        &self,
+        classes: EventClasses,
+        triggers: &UnsolicitedTriggers,
+    ) -> Option<Instant> {
+        let counts = &self.total.classes;
+        let mut earliest: Option<Instant> = None;
+        for (class, count) in [
+            (EventClass::Class1, counts.num_class_1.value),
+            (EventClass::Class2, counts.num_class_2.value),
+            (EventClass::Class3, counts.num_class_3.value),
+        ] {
+            if count == 0 || !classes.matches(class) {
+                continue;
+            }
+
+            let oldest = match self.created.oldest(class) {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let trigger = triggers.get(class);
+            let time = if count >= trigger.count.max(1) as usize {
+                oldest
+            } else {
+                // a hold time too large to represent never elapses
+                match trigger.hold_time.and_then(|x| oldest.checked_add(x)) {
+                    Some(time) => time,
+                    None => continue,
+                }
+            };
+
+            earliest = Some(earliest.map_or(time, |x| x.min(time)));
+        }
+        earliest
+    }
+
     pub(crate) fn insert<T>(
         &mut self,
         index: u16,
@@ -471,9 +672,17 @@ impl EventBuffer {
 
         let ret = if T::get_type_count(&self.total.types) == max as usize {
             if let Some(record) = self.events.remove_first(T::is_type) {
+                self.created.remove(record);
                 T::decrement_type(&mut self.total.types);
                 self.total.classes.decrement(record.class);
                 self.is_overflown = true;
+                self.overflows += 1;
+                if let Some(listener) = self.overflow_listener.as_mut() {
+                    listener.on_overflow(EventOverflow {
+                        created: id,
+                        discarded: record.info(),
+                    });
+                }
                 Err(InsertError::Overflow {
                     created: id,
                     discarded: record.id,
@@ -487,9 +696,13 @@ impl EventBuffer {
 
         let record = EventRecord::new(index, id, class, event.create_event(default_variation));
 
-        self.events.add(record);
+        let created = record.created;
+        if self.events.add(record).is_some() {
+            self.created.add(class, created, id);
+        }
         self.total.classes.increment(class);
         T::increment_type(&mut self.total.types);
+        self.high_water_mark = self.high_water_mark.max(self.events.len());
 
         ret
     }
@@ -615,11 +828,20 @@ impl EventBuffer {
     }
 
     pub(crate) fn clear_written(&mut self, app: &mut dyn OutstationApplication) -> usize {
+        self.clear_written_with(|id| app.event_cleared(id))
+    }
+
+    pub(crate) fn clear_written_with<F>(&mut self, mut on_cleared: F) -> usize
+    where
+        F: FnMut(u64),
+    {
         let total = &mut self.total;
+        let created = &mut self.created;
         let count = self.events.remove_all(|event| {
             if event.state.get() == EventState::Written {
-                app.event_cleared(event.id);
+                on_cleared(event.id);
                 total.decrement(event);
+                created.remove(event);
                 true
             } else {
                 false
@@ -633,6 +855,128 @@ impl EventBuffer {
         count
     }
 
+    /// remove events by id, regardless of their state
+    pub(crate) fn remove_by_id(&mut self, mut ids: Vec<u64>) -> usize {
+        // ids are usually already sorted, so this is cheap and allows a binary search per event
+        ids.sort_unstable();
+        let total = &mut self.total;
+        let created = &mut self.created;
+        let count = self.events.remove_all(|event| {
+            if ids.binary_search(&event.id).is_ok() {
+                total.decrement(event);
+                created.remove(event);
+                true
+            } else {
+                false
+            }
+        });
+
+        if !self.is_any_full() {
+            self.is_overflown = false;
+        }
+        count
+    }
+
+    /// remove events of the specified classes, regardless of their state
+    pub(crate) fn clear_by_class<F>(&mut self, classes: EventClasses, on_cleared: F) -> usize
+    where
+        F: FnMut(u64),
+    {
+        self.remove_where(|e| classes.matches(e.class), on_cleared)
+    }
+
+    /// remove events of the specified type, regardless of their state
+    pub(crate) fn clear_by_type<F>(&mut self, event_type: EventType, on_cleared: F) -> usize
+    where
+        F: FnMut(u64),
+    {
+        self.remove_where(|e| e.event.event_type() == event_type, on_cleared)
+    }
+
+    fn remove_where<P, F>(&mut self, predicate: P, mut on_cleared: F) -> usize
+    where
+        P: Fn(&EventRecord) -> bool,
+        F: FnMut(u64),
+    {
+        let total = &mut self.total;
+        let written = &mut self.written;
+        let created = &mut self.created;
+        let count = self.events.remove_all(|event| {
+            if predicate(event) {
+                if event.state.get() == EventState::Written {
+                    written.decrement(event);
+                }
+                on_cleared(event.id);
+                total.decrement(event);
+                created.remove(event);
+                true
+            } else {
+                false
+            }
+        });
+
+        if !self.is_any_full() {
+            self.is_overflown = false;
+        }
+        count
+    }
+
+    pub(crate) fn events_info(&self) -> Vec<BufferedEventInfo> {
+        self.events
+            .iter()
+            .map(|(_, record)| record.info())
+            .collect()
+    }
+
+    pub(crate) fn next_id(&self) -> u64 {
+        self.next
+    }
+
+    pub(crate) fn buffered_events(&self) -> Vec<BufferedEvent> {
+        self.events
+            .iter()
+            .filter_map(|(_, record)| {
+                Some(BufferedEvent {
+                    id: record.id,
+                    index: record.index,
+                    class: record.class,
+                    value: record.event.to_point_value()?,
+                })
+            })
+            .collect()
+    }
+
+    /// discard all events and restart id assignment at the specified value
+    pub(crate) fn clear(&mut self, next: u64, is_overflown: bool) {
+        self.events.remove_all(|_| true);
+        self.created.clear();
+        self.total.zero();
+        self.written.zero();
+        self.next = next;
+        self.is_overflown = is_overflown;
+    }
+
+    /// add an event with an id assigned elsewhere, bypassing the overflow logic
+    pub(crate) fn restore<T>(
+        &mut self,
+        index: u16,
+        id: u64,
+        class: EventClass,
+        event: &T,
+        default_variation: T::EventVariation,
+    ) where
+        T: Insertable,
+    {
+        let record = EventRecord::new(index, id, class, event.create_event(default_variation));
+        let created = record.created;
+        if self.events.add(record).is_some() {
+            self.created.add(class, created, id);
+            self.total.classes.increment(class);
+            T::increment_type(&mut self.total.types);
+            self.high_water_mark = self.high_water_mark.max(self.events.len());
+        }
+    }
+
     pub(crate) fn buffer_state(&self) -> BufferState {
         self.total.into()
     }
@@ -648,6 +992,14 @@ impl EventBuffer {
         self.is_overflown
     }
 
+    pub(crate) fn high_water_mark(&self) -> usize {
+        self.high_water_mark
+    }
+
+    pub(crate) fn overflows(&self) -> u64 {
+        self.overflows
+    }
+
     fn is_any_full(&self) -> bool {
         self.is_full::<measurement::BinaryInput>()
             || self.is_full::<measurement::DoubleBitBinaryInput>()
@@ -657,6 +1009,8 @@ impl EventBuffer {
             || self.is_full::<measurement::AnalogInput>()
             || self.is_full::<measurement::AnalogOutputStatus>()
             || self.is_full::<measurement::OctetString>()
+            || self.is_full::<measurement::BinaryOutputCommandEvent>()
+            || self.is_full::<measurement::AnalogOutputCommandEvent>()
     }
 
     fn is_full<T>(&self) -> bool
@@ -996,10 +1350,85 @@ impl Insertable for measurement::OctetString {
     }
 }
 
+impl Insertable for measurement::BinaryOutputCommandEvent {
+    type EventVariation = EventBinaryOutputCommandVariation;
+
+    fn get_max(config: &EventBufferConfig) -> u16 {
+        config.max_binary_output_command
+    }
+
+    fn get_type_count(counter: &TypeCounter) -> usize {
+        counter.num_binary_output_command.get()
+    }
+
+    fn is_type(record: &EventRecord) -> bool {
+        std::matches!(record.event, Event::BinaryOutputCommand(_, _))
+    }
+
+    fn decrement_type(counter: &mut TypeCounter) {
+        counter.num_binary_output_command.decrement();
+    }
+
+    fn increment_type(counter: &mut TypeCounter) {
+        counter.num_binary_output_command.increment();
+    }
+
+    fn create_event(&self, default_variation: EventBinaryOutputCommandVariation) -> Event {
+        Event::BinaryOutputCommand(*self, Variation::new(default_variation))
+    }
+
+    fn select_variation(record: &EventRecord, variation: Self::EventVariation) -> bool {
+        if let Event::BinaryOutputCommand(_, v) = &record.event {
+            v.selected.set(variation);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl Insertable for measurement::AnalogOutputCommandEvent {
+    type EventVariation = EventAnalogOutputCommandVariation;
+
+    fn get_max(config: &EventBufferConfig) -> u16 {
+        config.max_analog_output_command
+    }
+
+    fn get_type_count(counter: &TypeCounter) -> usize {
+        counter.num_analog_output_command.get()
+    }
+
+    fn is_type(record: &EventRecord) -> bool {
+        std::matches!(record.event, Event::AnalogOutputCommand(_, _))
+    }
+
+    fn decrement_type(counter: &mut TypeCounter) {
+        counter.num_analog_output_command.decrement();
+    }
+
+    fn increment_type(counter: &mut TypeCounter) {
+        counter.num_analog_output_command.increment();
+    }
+
+    fn create_event(&self, default_variation: EventAnalogOutputCommandVariation) -> Event {
+        Event::AnalogOutputCommand(*self, Variation::new(default_variation))
+    }
+
+    fn select_variation(record: &EventRecord, variation: Self::EventVariation) -> bool {
+        if let Event::AnalogOutputCommand(_, v) = &record.event {
+            v.selected.set(variation);
+            true
+        } else {
+            false
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::app::measurement::*;
     use crate::app::MaybeAsync;
+    use crate::outstation::UnsolicitedClassTrigger;
     use std::collections::VecDeque;
 
     use super::*;
@@ -1077,6 +1506,69 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn removes_events_by_id_in_any_order() {
+        let mut buffer = EventBuffer::new(EventBufferConfig::all_types(3));
+        insert_events(&mut buffer);
+        let ids: Vec<u64> = buffer.buffered_events().iter().map(|x| x.id).collect();
+
+        // unknown ids are ignored
+        assert_eq!(buffer.remove_by_id(vec![ids[3], ids[0], 1000]), 2);
+        let remaining: Vec<u64> = buffer.buffered_events().iter().map(|x| x.id).collect();
+        assert_eq!(
+            remaining,
+            ids.iter()
+                .copied()
+                .filter(|x| *x != ids[0] && *x != ids[3])
+                .collect::<Vec<u64>>()
+        );
+    }
+
+    #[test]
+    fn unsolicited_trigger_time_follows_the_oldest_remaining_event() {
+        let mut buffer = EventBuffer::new(EventBufferConfig::all_types(3));
+        let hold_time = std::time::Duration::from_secs(5);
+        let triggers = UnsolicitedTriggers::all(UnsolicitedClassTrigger::new(10, hold_time));
+        insert_events(&mut buffer);
+
+        let records: Vec<(u64, Instant)> = buffer
+            .events
+            .iter()
+            .filter(|(_, x)| x.class == EventClass::Class1)
+            .map(|(_, x)| (x.id, x.created))
+            .collect();
+        assert_eq!(
+            buffer.unsolicited_trigger_time(EventClass::Class1.into(), &triggers),
+            Some(records[0].1 + hold_time)
+        );
+
+        // removing the oldest event moves the trigger to the next oldest of the class
+        buffer.remove_by_id(vec![records[0].0]);
+        assert_eq!(
+            buffer.unsolicited_trigger_time(EventClass::Class1.into(), &triggers),
+            Some(records[1].1 + hold_time)
+        );
+
+        buffer.clear_by_class(EventClass::Class1.into(), |_| {});
+        assert_eq!(
+            buffer.unsolicited_trigger_time(EventClass::Class1.into(), &triggers),
+            None
+        );
+    }
+
+    #[test]
+    fn unsolicited_hold_time_too_large_to_represent_never_triggers() {
+        let mut buffer = EventBuffer::new(EventBufferConfig::all_types(3));
+        let triggers =
+            UnsolicitedTriggers::all(UnsolicitedClassTrigger::new(10, std::time::Duration::MAX));
+        insert_events(&mut buffer);
+
+        assert_eq!(
+            buffer.unsolicited_trigger_time(EventClasses::all(), &triggers),
+            None
+        );
+    }
+
     #[test]
     fn cannot_insert_if_max_for_type_is_zero() {
         let mut buffer = EventBuffer::new(EventBufferConfig::no_events());
@@ -1120,7 +1612,27 @@ mod tests {
                 EventBinaryInputVariation::Group2Var1
             ),
             Err(expected)
-        )
+        );
+        assert_eq!(buffer.overflows(), 1);
+        assert_eq!(buffer.high_water_mark(), 1);
+    }
+
+    #[test]
+    fn high_water_mark_is_retained_after_events_are_cleared() {
+        let mut buffer = EventBuffer::new(EventBufferConfig::all_types(3));
+
+        insert_events(&mut buffer);
+        assert_eq!(buffer.high_water_mark(), 5);
+
+        buffer.select_by_class(EventClasses::all(), None);
+        let mut backing = [0u8; 1024];
+        let mut cursor = WriteCursor::new(backing.as_mut());
+        buffer.write_events(&mut cursor).unwrap();
+        buffer.clear_written(&mut MockApplication::default());
+
+        assert_eq!(buffer.unwritten_classes(), EventClasses::none());
+        assert_eq!(buffer.high_water_mark(), 5);
+        assert_eq!(buffer.overflows(), 0);
     }
 
     #[test]
@@ -1198,4 +1710,91 @@ mod tests {
         assert_eq!(mock.events.pop_front(), Some(Event::Clear(3)));
         assert_eq!(mock.events.pop_front(), None);
     }
+
+    #[test]
+    fn overflow_listener_is_notified_of_discarded_event() {
+        let mut buffer = EventBuffer::new(EventBufferConfig::all_types(1));
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        buffer.set_overflow_listener(Some(Box::new(tx)));
+
+        let binary = BinaryInput::new(true, Flags::ONLINE, Time::synchronized(7));
+        for _ in 0..2 {
+            let _ = buffer.insert(
+                3,
+                EventClass::Class2,
+                &binary,
+                EventBinaryInputVariation::Group2Var1,
+            );
+        }
+
+        assert_eq!(
+            rx.try_recv(),
+            Ok(EventOverflow {
+                created: 1,
+                discarded: BufferedEventInfo {
+                    id: 0,
+                    event_type: EventType::BinaryInput,
+                    index: 3,
+                    class: EventClass::Class2,
+                    time: Some(Time::synchronized(7)),
+                    written: false,
+                }
+            })
+        );
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn can_clear_events_by_class_including_written_ones() {
+        let mut buffer = EventBuffer::new(EventBufferConfig::all_types(3));
+
+        insert_events(&mut buffer);
+
+        assert_eq!(2, buffer.select_by_class(EventClass::Class1.into(), None));
+        let mut backing = [0u8; 64];
+        let mut cursor = WriteCursor::new(backing.as_mut());
+        assert_eq!(2, buffer.write_events(&mut cursor).unwrap());
+        assert!(buffer.events_info()[0].written);
+
+        let mut cleared = Vec::new();
+        assert_eq!(
+            2,
+            buffer.clear_by_class(EventClass::Class1.into(), |id| cleared.push(id))
+        );
+        assert_eq!(cleared, [0, 4]);
+
+        let state = buffer.buffer_state();
+        assert_eq!(state.classes.num_class_1, 0);
+        assert_eq!(state.types.num_binary_input, 1);
+        assert_eq!(state.types.num_analog, 0);
+
+        // nothing is left to clear when the master confirms
+        assert_eq!(0, buffer.clear_written(&mut MockApplication::default()));
+        let ids: Vec<u64> = buffer.events_info().iter().map(|x| x.id).collect();
+        assert_eq!(ids, [1, 2, 3]);
+    }
+
+    #[test]
+    fn can_clear_events_by_type() {
+        let mut buffer = EventBuffer::new(EventBufferConfig::all_types(3));
+
+        insert_events(&mut buffer);
+
+        assert_eq!(2, buffer.clear_by_type(EventType::BinaryInput, |_| {}));
+        assert_eq!(0, buffer.clear_by_type(EventType::BinaryInput, |_| {}));
+
+        let info: Vec<(u64, EventType)> = buffer
+            .events_info()
+            .iter()
+            .map(|x| (x.id, x.event_type))
+            .collect();
+        assert_eq!(
+            info,
+            [
+                (1, EventType::Counter),
+                (2, EventType::DoubleBitBinaryInput),
+                (4, EventType::AnalogInput)
+            ]
+        );
+    }
 }
@@ -316,6 +316,90 @@ impl EventVariation<AnalogOutputStatus> for EventAnalogOutputStatusVariation {
     }
 }
 
+impl EventVariation<BinaryOutputCommandEvent> for EventBinaryOutputCommandVariation {
+    fn write(
+        &self,
+        cursor: &mut WriteCursor,
+        event: &BinaryOutputCommandEvent,
+        index: u16,
+        cto: Time,
+    ) -> Result<Continue, WriteError> {
+        match self {
+            Self::Group13Var1 => {
+                write_fixed_size::<Group13Var1, BinaryOutputCommandEvent>(cursor, event, index, cto)
+            }
+            Self::Group13Var2 => {
+                write_fixed_size::<Group13Var2, BinaryOutputCommandEvent>(cursor, event, index, cto)
+            }
+        }
+    }
+
+    fn wrap(&self) -> HeaderType {
+        HeaderType::BinaryOutputCommand(*self)
+    }
+
+    fn get_group_var(&self, _event: &BinaryOutputCommandEvent) -> (u8, u8) {
+        match self {
+            Self::Group13Var1 => (13, 1),
+            Self::Group13Var2 => (13, 2),
+        }
+    }
+}
+
+impl EventVariation<AnalogOutputCommandEvent> for EventAnalogOutputCommandVariation {
+    fn write(
+        &self,
+        cursor: &mut WriteCursor,
+        event: &AnalogOutputCommandEvent,
+        index: u16,
+        cto: Time,
+    ) -> Result<Continue, WriteError> {
+        match self {
+            Self::Group43Var1 => {
+                write_fixed_size::<Group43Var1, AnalogOutputCommandEvent>(cursor, event, index, cto)
+            }
+            Self::Group43Var2 => {
+                write_fixed_size::<Group43Var2, AnalogOutputCommandEvent>(cursor, event, index, cto)
+            }
+            Self::Group43Var3 => {
+                write_fixed_size::<Group43Var3, AnalogOutputCommandEvent>(cursor, event, index, cto)
+            }
+            Self::Group43Var4 => {
+                write_fixed_size::<Group43Var4, AnalogOutputCommandEvent>(cursor, event, index, cto)
+            }
+            Self::Group43Var5 => {
+                write_fixed_size::<Group43Var5, AnalogOutputCommandEvent>(cursor, event, index, cto)
+            }
+            Self::Group43Var6 => {
+                write_fixed_size::<Group43Var6, AnalogOutputCommandEvent>(cursor, event, index, cto)
+            }
+            Self::Group43Var7 => {
+                write_fixed_size::<Group43Var7, AnalogOutputCommandEvent>(cursor, event, index, cto)
+            }
+            Self::Group43Var8 => {
+                write_fixed_size::<Group43Var8, AnalogOutputCommandEvent>(cursor, event, index, cto)
+            }
+        }
+    }
+
+    fn wrap(&self) -> HeaderType {
+        HeaderType::AnalogOutputCommand(*self)
+    }
+
+    fn get_group_var(&self, _event: &AnalogOutputCommandEvent) -> (u8, u8) {
+        match self {
+            Self::Group43Var1 => (43, 1),
+            Self::Group43Var2 => (43, 2),
+            Self::Group43Var3 => (43, 3),
+            Self::Group43Var4 => (43, 4),
+            Self::Group43Var5 => (43, 5),
+            Self::Group43Var6 => (43, 6),
+            Self::Group43Var7 => (43, 7),
+            Self::Group43Var8 => (43, 8),
+        }
+    }
+}
+
 impl EventVariation<Box<[u8]>> for OctetStringLength {
     fn write(
         &self,
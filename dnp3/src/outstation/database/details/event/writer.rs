@@ -47,6 +47,8 @@ pub(crate) enum HeaderType {
     Analog(EventAnalogInputVariation),
     AnalogOutputStatus(EventAnalogOutputStatusVariation),
     OctetString(OctetStringLength),
+    BinaryOutputCommand(EventBinaryOutputCommandVariation),
+    AnalogOutputCommand(EventAnalogOutputCommandVariation),
 }
 
 #[derive(Copy, Clone)]
@@ -339,6 +341,36 @@ impl Writable for Box<[u8]> {
     }
 }
 
+impl Writable for BinaryOutputCommandEvent {
+    type EventVariation = EventBinaryOutputCommandVariation;
+
+    fn get_header_variation(&self, header: &HeaderType) -> Option<Self::EventVariation> {
+        match header {
+            HeaderType::BinaryOutputCommand(var) => Some(*var),
+            _ => None,
+        }
+    }
+
+    fn get_time(&self) -> Option<Time> {
+        self.time
+    }
+}
+
+impl Writable for AnalogOutputCommandEvent {
+    type EventVariation = EventAnalogOutputCommandVariation;
+
+    fn get_header_variation(&self, header: &HeaderType) -> Option<Self::EventVariation> {
+        match header {
+            HeaderType::AnalogOutputCommand(var) => Some(*var),
+            _ => None,
+        }
+    }
+
+    fn get_time(&self) -> Option<Time> {
+        self.time
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::app::measurement::Flags;
@@ -10,9 +10,14 @@ use crate::outstation::database::details::event::buffer::Insertable;
 use crate::outstation::database::details::range::traits::StaticVariation;
 use crate::outstation::database::details::range::writer::RangeWriter;
 use crate::outstation::database::read::StaticReadHeader;
-use crate::outstation::database::{ClassZeroConfig, EventClass, EventMode, UpdateOptions};
+use crate::outstation::database::{
+    ClassZeroConfig, EventClass, EventMode, SourceId, UpdateOptions,
+};
+use crate::outstation::replication::{PointState, PointValue};
+use crate::outstation::ReadIndices;
 
 use crate::app::attr::AttrSet;
+use crate::app::variations::Variation;
 use crate::util::BadWrite;
 use scursor::WriteCursor;
 
@@ -28,11 +33,16 @@ pub(crate) trait Updatable: Insertable + Clone + Default {
     type Detector: EventDetector<Self>;
     fn get_map(maps: &StaticDatabase) -> &PointMap<Self>;
     fn get_mut_map(maps: &mut StaticDatabase) -> &mut PointMap<Self>;
+    fn get_selected(selection: &StaticSelection) -> &SelectedMap<Self>;
+    fn get_selected_mut(selection: &mut StaticSelection) -> &mut SelectedMap<Self>;
     fn wrap(range: IndexRange, variation: Option<Self::StaticVariation>) -> VariationRange;
     fn enabled_class_zero(config: &ClassZeroConfig) -> bool;
+    fn to_point_value(&self) -> PointValue;
 }
 
 pub(crate) trait UpdatableFlags: Updatable {
+    fn flags(&self) -> Flags;
+    fn time(&self) -> Option<Time>;
     fn update_flags(&mut self, flags: Flags, time: Option<Time>);
 }
 
@@ -62,6 +72,12 @@ impl IndexRange {
     }
 }
 
+impl From<IndexRange> for ReadIndices {
+    fn from(value: IndexRange) -> Self {
+        ReadIndices::Range(value.start, value.stop)
+    }
+}
+
 impl RangeBounds<u16> for IndexRange {
     fn start_bound(&self) -> Bound<&u16> {
         Bound::Included(&self.start)
@@ -182,12 +198,12 @@ where
 {
     // current value
     current: T,
-    // value that is frozen during READ requests to be reported
-    selected: T,
     // last value that produced an event
     last_event: T,
     // configuration
     config: PointConfig<T>,
+    // source of the point used for bulk quality changes
+    source: Option<SourceId>,
 }
 
 impl<T> Point<T>
@@ -197,9 +213,9 @@ where
     pub(crate) fn new(config: PointConfig<T>) -> Self {
         Self {
             current: T::default(),
-            selected: T::default(),
             last_event: T::default(),
             config,
+            source: None,
         }
     }
 }
@@ -230,42 +246,68 @@ where
     fn get_mut(&mut self, index: u16) -> Option<&mut Point<T>> {
         self.inner.get_mut(&index)
     }
+}
 
-    fn select_range_with_variation(
+/// value of a point copied when it is selected by a READ request, along with its static variation
+struct Selected<T>
+where
+    T: Updatable,
+{
+    value: T,
+    s_var: T::StaticVariation,
+}
+
+pub(crate) struct SelectedMap<T>
+where
+    T: Updatable,
+{
+    inner: BTreeMap<u16, Selected<T>>,
+}
+
+impl<T> SelectedMap<T>
+where
+    T: Updatable,
+{
+    fn empty() -> Self {
+        Self {
+            inner: BTreeMap::new(),
+        }
+    }
+
+    fn select_range(
         &mut self,
+        points: &PointMap<T>,
         range: IndexRange,
         variation: Option<T::StaticVariation>,
-    ) -> Option<VariationRange> {
-        for (_index, point) in self
-            .inner
-            .range_mut((Bound::Included(&range.start), Bound::Included(&range.stop)))
-        {
-            // for every point in the range, we copy the current value into a distinct 'selected' cell
+    ) -> VariationRange {
+        for (index, point) in points.inner.range(range) {
+            // for every point in the range, we copy the current value into the snapshot of this selection
             // when writing the response(s) we use the selected value
             // this allows the outstation to send consistent snapshot of the values when a multi-fragment response is required
-            point.selected = point.current.clone();
+            self.inner.insert(
+                *index,
+                Selected {
+                    value: point.current.clone(),
+                    s_var: point.config.s_var,
+                },
+            );
         }
-        Some(T::wrap(range, variation))
-    }
-
-    fn select_all(&mut self) -> Option<VariationRange> {
-        self.select_all_with_variation(None)
+        T::wrap(range, variation)
     }
 
-    fn select_all_with_variation(
+    fn select_all(
         &mut self,
+        points: &PointMap<T>,
         variation: Option<T::StaticVariation>,
     ) -> Option<VariationRange> {
-        let range = self.full_range()?;
+        let range = points.full_range()?;
 
         // as far at the processing goes, we treat this just like a range scan over all the values
-        self.select_range_with_variation(range, variation)
+        Some(self.select_range(points, range, variation))
     }
 }
 
 pub(crate) struct StaticDatabase {
-    class_zero: ClassZeroConfig,
-    selected: SelectionQueue,
     // maps for the various types
     binary: PointMap<BinaryInput>,
     double_bit_binary: PointMap<DoubleBitBinaryInput>,
@@ -277,22 +319,9 @@ pub(crate) struct StaticDatabase {
     octet_strings: PointMap<OctetString>,
 }
 
-impl Default for StaticDatabase {
-    fn default() -> Self {
-        Self::new(None, ClassZeroConfig::default())
-    }
-}
-
 impl StaticDatabase {
-    pub(crate) fn new(max_read_selection: Option<u16>, class_zero: ClassZeroConfig) -> Self {
-        // don't allow values smaller than the default
-        let max_read_selection = max_read_selection
-            .map(|x| x.max(OutstationConfig::DEFAULT_MAX_READ_REQUEST_HEADERS))
-            .unwrap_or(OutstationConfig::DEFAULT_MAX_READ_REQUEST_HEADERS);
-
+    pub(crate) fn new() -> Self {
         Self {
-            class_zero,
-            selected: SelectionQueue::new(max_read_selection),
             binary: PointMap::empty(),
             double_bit_binary: PointMap::empty(),
             binary_output_status: PointMap::empty(),
@@ -322,13 +351,106 @@ impl StaticDatabase {
         }
     }
 
-    #[cfg(test)]
-    pub(crate) fn selection_capacity(&self) -> usize {
-        self.selected.queue.capacity()
+    pub(crate) fn analog_deadbands(&self) -> Vec<(u16, f64)> {
+        self.analog
+            .inner
+            .iter()
+            .map(|(index, point)| (*index, point.config.detector.deadband))
+            .collect()
     }
 
-    pub(crate) fn reset(&mut self) {
-        self.selected.reset();
+    pub(crate) fn point_states(&self) -> Vec<PointState> {
+        let mut states = Vec::new();
+        self.append_point_states::<BinaryInput>(&mut states);
+        self.append_point_states::<DoubleBitBinaryInput>(&mut states);
+        self.append_point_states::<BinaryOutputStatus>(&mut states);
+        self.append_point_states::<Counter>(&mut states);
+        self.append_point_states::<FrozenCounter>(&mut states);
+        self.append_point_states::<AnalogInput>(&mut states);
+        self.append_point_states::<AnalogOutputStatus>(&mut states);
+        self.append_point_states::<OctetString>(&mut states);
+        states
+    }
+
+    fn append_point_states<T>(&self, states: &mut Vec<PointState>)
+    where
+        T: Updatable,
+    {
+        for (index, point) in self.get_map::<T>().inner.iter() {
+            states.push(PointState {
+                index: *index,
+                current: point.current.to_point_value(),
+                last_event: point.last_event.to_point_value(),
+            });
+        }
+    }
+
+    /// overwrite the current and last event values of a point without producing an event
+    pub(crate) fn restore<T>(&mut self, index: u16, current: T, last_event: T) -> bool
+    where
+        T: Updatable,
+    {
+        match self.get_mut_map::<T>().get_mut(index) {
+            None => false,
+            Some(x) => {
+                x.current = current;
+                x.last_event = last_event;
+                true
+            }
+        }
+    }
+
+    pub(crate) fn set_source<T>(&mut self, index: u16, source: Option<SourceId>) -> bool
+    where
+        T: Updatable,
+    {
+        match self.get_mut_map::<T>().get_mut(index) {
+            None => false,
+            Some(x) => {
+                x.source = source;
+                true
+            }
+        }
+    }
+
+    pub(crate) fn source<T>(&self, index: u16) -> Option<SourceId>
+    where
+        T: Updatable,
+    {
+        self.get_map::<T>().inner.get(&index)?.source
+    }
+
+    pub(crate) fn indices_with_source<T>(&self, source: SourceId) -> Vec<u16>
+    where
+        T: Updatable,
+    {
+        self.get_map::<T>()
+            .inner
+            .iter()
+            .filter(|(_, point)| point.source == Some(source))
+            .map(|(index, _)| *index)
+            .collect()
+    }
+
+    /// `None` if the point doesn't exist, `Some(None)` if it doesn't produce events
+    pub(crate) fn event_class<T>(&self, index: u16) -> Option<Option<EventClass>>
+    where
+        T: Updatable,
+    {
+        self.get_map::<T>()
+            .inner
+            .get(&index)
+            .map(|point| point.config.class)
+    }
+
+    pub(crate) fn event_variation<T>(&self, index: u16) -> Option<T::EventVariation>
+    where
+        T: Updatable,
+    {
+        self.get_map::<T>()
+            .inner
+            .get(&index)
+            .map(|point| point.config.e_var)
     }
 
     pub(crate) fn add<T>(&mut self, index: u16, config: PointConfig<T>) -> bool
@@ -401,9 +523,89 @@ impl StaticDatabase {
         }
     }
 
-    pub(crate) fn write(&mut self, cursor: &mut WriteCursor) -> Result<(), BadWrite> {
+    fn get_map<T>(&self) -> &PointMap<T>
+    where
+        T: Updatable,
+    {
+        T::get_map(self)
+    }
+
+    fn get_mut_map<T>(&mut self) -> &mut PointMap<T>
+    where
+        T: Updatable,
+    {
+        T::get_mut_map(self)
+    }
+}
+
+/// static data selected by READ requests, which is specific to each outstation sharing the points
+pub(crate) struct StaticSelection {
+    class_zero: ClassZeroConfig,
+    selected: SelectionQueue,
+    // snapshots of the selected values for the various types
+    binary: SelectedMap<BinaryInput>,
+    double_bit_binary: SelectedMap<DoubleBitBinaryInput>,
+    binary_output_status: SelectedMap<BinaryOutputStatus>,
+    counter: SelectedMap<Counter>,
+    frozen_counter: SelectedMap<FrozenCounter>,
+    analog: SelectedMap<AnalogInput>,
+    analog_output_status: SelectedMap<AnalogOutputStatus>,
+    octet_strings: SelectedMap<OctetString>,
+}
+
+impl Default for StaticSelection {
+    fn default() -> Self {
+        Self::new(None, ClassZeroConfig::default())
+    }
+}
+
+impl StaticSelection {
+    pub(crate) fn new(max_read_selection: Option<u16>, class_zero: ClassZeroConfig) -> Self {
+        // don't allow values smaller than the default
+        let max_read_selection = max_read_selection
+            .map(|x| x.max(OutstationConfig::DEFAULT_MAX_READ_REQUEST_HEADERS))
+            .unwrap_or(OutstationConfig::DEFAULT_MAX_READ_REQUEST_HEADERS);
+
+        Self {
+            class_zero,
+            selected: SelectionQueue::new(max_read_selection),
+            binary: SelectedMap::empty(),
+            double_bit_binary: SelectedMap::empty(),
+            binary_output_status: SelectedMap::empty(),
+            counter: SelectedMap::empty(),
+            frozen_counter: SelectedMap::empty(),
+            analog: SelectedMap::empty(),
+            analog_output_status: SelectedMap::empty(),
+            octet_strings: SelectedMap::empty(),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn selection_capacity(&self) -> usize {
+        self.selected.queue.capacity()
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.selected.reset();
+        self.binary.inner.clear();
+        self.double_bit_binary.inner.clear();
+        self.binary_output_status.inner.clear();
+        self.counter.inner.clear();
+        self.frozen_counter.inner.clear();
+        self.analog.inner.clear();
+        self.analog_output_status.inner.clear();
+        self.octet_strings.inner.clear();
+    }
+
+    /// write the selected static data, using index-prefixed headers where they are more compact if `prefixed` is true
+    pub(crate) fn write(
+        &mut self,
+        points: &StaticDatabase,
+        cursor: &mut WriteCursor,
+        prefixed: bool,
+    ) -> Result<(), BadWrite> {
         while let Some(range) = self.selected.peek() {
-            match self.write_range(cursor, range) {
+            match self.write_range(points, cursor, range, prefixed) {
                 // done with this header
                 Ok(()) => {
                     self.selected.pop();
@@ -422,36 +624,38 @@ impl StaticDatabase {
 
     fn write_range(
         &mut self,
+        points: &StaticDatabase,
         cursor: &mut WriteCursor,
         range: VariationRange,
+        prefixed: bool,
     ) -> Result<(), VariationRange> {
         match range.variation {
             SpecificVariation::Binary(var) => {
-                self.write_typed_range::<BinaryInput>(cursor, range.range, var)
+                self.write_typed_range::<BinaryInput>(cursor, range.range, var, prefixed)
             }
             SpecificVariation::DoubleBitBinary(var) => {
-                self.write_typed_range::<DoubleBitBinaryInput>(cursor, range.range, var)
+                self.write_typed_range::<DoubleBitBinaryInput>(cursor, range.range, var, prefixed)
             }
             SpecificVariation::BinaryOutputStatus(var) => {
-                self.write_typed_range::<BinaryOutputStatus>(cursor, range.range, var)
+                self.write_typed_range::<BinaryOutputStatus>(cursor, range.range, var, prefixed)
             }
             SpecificVariation::Counter(var) => {
-                self.write_typed_range::<Counter>(cursor, range.range, var)
+                self.write_typed_range::<Counter>(cursor, range.range, var, prefixed)
             }
             SpecificVariation::FrozenCounter(var) => {
-                self.write_typed_range::<FrozenCounter>(cursor, range.range, var)
+                self.write_typed_range::<FrozenCounter>(cursor, range.range, var, prefixed)
             }
             SpecificVariation::Analog(var) => {
-                self.write_typed_range::<AnalogInput>(cursor, range.range, var)
+                self.write_typed_range::<AnalogInput>(cursor, range.range, var, prefixed)
             }
             SpecificVariation::AnalogOutputStatus(var) => {
-                self.write_typed_range::<AnalogOutputStatus>(cursor, range.range, var)
+                self.write_typed_range::<AnalogOutputStatus>(cursor, range.range, var, prefixed)
             }
             SpecificVariation::OctetString => {
-                self.write_typed_range::<OctetString>(cursor, range.range, None)
+                self.write_typed_range::<OctetString>(cursor, range.range, None, prefixed)
             }
             SpecificVariation::AnalogDeadBand(var) => {
-                self.write_analog_dead_bands(cursor, range.range, var)
+                self.write_analog_dead_bands(points, cursor, range.range, var)
             }
         }
     }
@@ -461,19 +665,48 @@ impl StaticDatabase {
         cursor: &mut WriteCursor,
         range: IndexRange,
         variation: Option<T::StaticVariation>,
+        prefixed: bool,
     ) -> Result<(), VariationRange>
     where
         T: Updatable,
     {
+        let map = &T::get_selected(self).inner;
         let mut writer = RangeWriter::new();
-        for (index, item) in self.get_map::<T>().inner.range(range) {
-            // first determine what variation should be written
-            let info = variation
-                .unwrap_or(item.config.s_var)
-                .promote(&item.selected)
-                .get_write_info(&item.selected);
+        // determine what variation should be written for each point
+        let write_info = |item: &Selected<T>| {
+            variation
+                .unwrap_or(item.s_var)
+                .promote(&item.value)
+                .get_write_info(&item.value)
+        };
+        // last index and variation written and whether the run they belong to is index-prefixed
+        let mut run: Option<(u16, Variation, bool)> = None;
+        for (index, item) in map.range(range) {
+            let info = write_info(item);
+
+            let short = match run {
+                Some((last, var, short))
+                    if var == info.variation && last.checked_add(1) == Some(*index) =>
+                {
+                    short
+                }
+                _ => {
+                    prefixed && {
+                        let (count, more) =
+                            measure_run(map, *index, range.stop, |x| write_info(x).variation);
+                        !writer.prefers_ranged(&info, count, more)
+                    }
+                }
+            };
+            run = Some((*index, info.variation, short));
+
+            let result = if short {
+                writer.write_prefixed(cursor, *index, &item.value, info)
+            } else {
+                writer.write(cursor, *index, &item.value, info)
+            };
 
-            if writer.write(cursor, *index, &item.selected, info).is_err() {
+            if result.is_err() {
                 // ran out of space, tell calling code to resume at this index
                 return Err(T::wrap(IndexRange::new(*index, range.stop), variation));
             }
@@ -484,12 +717,13 @@ impl StaticDatabase {
 
     fn write_analog_dead_bands(
         &mut self,
+        points: &StaticDatabase,
         cursor: &mut WriteCursor,
         range: IndexRange,
         variation: Option<AnalogInputDeadBandVariation>,
     ) -> Result<(), VariationRange> {
         let mut writer = RangeWriter::new();
-        for (index, item) in self.analog.inner.range(range) {
+        for (index, item) in points.analog.inner.range(range) {
             // first determine what variation should be written
             let var = variation.unwrap_or(AnalogInputDeadBandVariation::Group34Var3);
 
@@ -510,31 +744,33 @@ impl StaticDatabase {
         Ok(())
     }
 
-    pub(crate) fn select(&mut self, variation: StaticReadHeader) -> Iin2 {
+    pub(crate) fn select(&mut self, points: &StaticDatabase, variation: StaticReadHeader) -> Iin2 {
         match variation {
-            StaticReadHeader::Class0 => self.select_class_zero(),
+            StaticReadHeader::Class0 => self.select_class_zero(points),
             StaticReadHeader::Binary(variation, range) => {
-                self.select_by_type::<BinaryInput>(variation, range)
+                self.select_by_type::<BinaryInput>(points, variation, range)
             }
             StaticReadHeader::DoubleBitBinary(variation, range) => {
-                self.select_by_type::<DoubleBitBinaryInput>(variation, range)
+                self.select_by_type::<DoubleBitBinaryInput>(points, variation, range)
             }
             StaticReadHeader::BinaryOutputStatus(variation, range) => {
-                self.select_by_type::<BinaryOutputStatus>(variation, range)
+                self.select_by_type::<BinaryOutputStatus>(points, variation, range)
             }
             StaticReadHeader::Counter(variation, range) => {
-                self.select_by_type::<Counter>(variation, range)
+                self.select_by_type::<Counter>(points, variation, range)
             }
             StaticReadHeader::FrozenCounter(variation, range) => {
-                self.select_by_type::<FrozenCounter>(variation, range)
+                self.select_by_type::<FrozenCounter>(points, variation, range)
             }
             StaticReadHeader::Analog(variation, range) => {
-                self.select_by_type::<AnalogInput>(variation, range)
+                self.select_by_type::<AnalogInput>(points, variation, range)
             }
             StaticReadHeader::AnalogOutputStatus(variation, range) => {
-                self.select_by_type::<AnalogOutputStatus>(variation, range)
+                self.select_by_type::<AnalogOutputStatus>(points, variation, range)
+            }
+            StaticReadHeader::OctetString(range) => {
+                self.select_by_type::<OctetString>(points, None, range)
             }
-            StaticReadHeader::OctetString(range) => self.select_by_type::<OctetString>(None, range),
             StaticReadHeader::FrozenAnalog(_, _) => {
                 // we don't support this, but we know what it is
                 Iin2::default()
@@ -542,7 +778,7 @@ impl StaticDatabase {
             StaticReadHeader::AnalogInputDeadBand(var, range) => {
                 match range {
                     None => {
-                        if let Some(range) = self.analog.full_range() {
+                        if let Some(range) = points.analog.full_range() {
                             self.push_selection(VariationRange::new(
                                 range,
                                 SpecificVariation::AnalogDeadBand(var),
@@ -563,15 +799,17 @@ impl StaticDatabase {
 
     fn select_by_type<T>(
         &mut self,
+        points: &StaticDatabase,
         variation: Option<T::StaticVariation>,
         range: Option<IndexRange>,
     ) -> Iin2
     where
         T: Updatable,
     {
+        let map = points.get_map::<T>();
         let selected = match range {
-            Some(range) => T::get_mut_map(self).select_range_with_variation(range, variation),
-            None => T::get_mut_map(self).select_all_with_variation(variation),
+            Some(range) => Some(T::get_selected_mut(self).select_range(map, range, variation)),
+            None => T::get_selected_mut(self).select_all(map, variation),
         };
 
         match selected {
@@ -588,26 +826,13 @@ impl StaticDatabase {
         }
     }
 
-    fn get_map<T>(&self) -> &PointMap<T>
-    where
-        T: Updatable,
-    {
-        T::get_map(self)
-    }
-
-    fn get_mut_map<T>(&mut self) -> &mut PointMap<T>
-    where
-        T: Updatable,
-    {
-        T::get_mut_map(self)
-    }
-
-    fn select_class_zero_type<T>(&mut self) -> Iin2
+    fn select_class_zero_type<T>(&mut self, points: &StaticDatabase) -> Iin2
     where
         T: Updatable,
     {
         if T::enabled_class_zero(&self.class_zero) {
-            let full_range = match T::get_mut_map(self).select_all() {
+            let full_range = match T::get_selected_mut(self).select_all(points.get_map::<T>(), None)
+            {
                 None => return Iin2::default(),
                 Some(x) => x,
             };
@@ -623,16 +848,43 @@ impl StaticDatabase {
         }
     }
 
-    fn select_class_zero(&mut self) -> Iin2 {
-        self.select_class_zero_type::<BinaryInput>()
-            | self.select_class_zero_type::<DoubleBitBinaryInput>()
-            | self.select_class_zero_type::<BinaryOutputStatus>()
-            | self.select_class_zero_type::<Counter>()
-            | self.select_class_zero_type::<FrozenCounter>()
-            | self.select_class_zero_type::<AnalogInput>()
-            | self.select_class_zero_type::<AnalogOutputStatus>()
-            | self.select_class_zero_type::<OctetString>()
+    fn select_class_zero(&mut self, points: &StaticDatabase) -> Iin2 {
+        self.select_class_zero_type::<BinaryInput>(points)
+            | self.select_class_zero_type::<DoubleBitBinaryInput>(points)
+            | self.select_class_zero_type::<BinaryOutputStatus>(points)
+            | self.select_class_zero_type::<Counter>(points)
+            | self.select_class_zero_type::<FrozenCounter>(points)
+            | self.select_class_zero_type::<AnalogInput>(points)
+            | self.select_class_zero_type::<AnalogOutputStatus>(points)
+            | self.select_class_zero_type::<OctetString>(points)
+    }
+}
+
+/// count the consecutive indices starting at `start` that are written with the same variation
+/// and determine if any points follow them up to `stop`
+fn measure_run<V>(
+    map: &BTreeMap<u16, V>,
+    start: u16,
+    stop: u16,
+    variation: impl Fn(&V) -> Variation,
+) -> (usize, bool) {
+    let mut iter = map.range(start..=stop);
+    let first = match iter.next() {
+        Some((_, value)) => variation(value),
+        None => return (0, false),
+    };
+
+    let mut count = 1;
+    let mut next = start.checked_add(1);
+    for (index, value) in iter {
+        if next != Some(*index) || variation(value) != first {
+            return (count, true);
+        }
+        count += 1;
+        next = index.checked_add(1);
     }
+
+    (count, false)
 }
 
 pub(crate) struct FlagsDetector;
@@ -738,6 +990,14 @@ impl Updatable for BinaryInput {
         &mut maps.binary
     }
 
+    fn get_selected(selection: &StaticSelection) -> &SelectedMap<Self> {
+        &selection.binary
+    }
+
+    fn get_selected_mut(selection: &mut StaticSelection) -> &mut SelectedMap<Self> {
+        &mut selection.binary
+    }
+
     fn wrap(range: IndexRange, variation: Option<Self::StaticVariation>) -> VariationRange {
         SpecificVariation::Binary(variation).with(range)
     }
@@ -745,9 +1005,21 @@ impl Updatable for BinaryInput {
     fn enabled_class_zero(config: &ClassZeroConfig) -> bool {
         config.binary
     }
+
+    fn to_point_value(&self) -> PointValue {
+        PointValue::BinaryInput(*self)
+    }
 }
 
 impl UpdatableFlags for BinaryInput {
+    fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    fn time(&self) -> Option<Time> {
+        self.time
+    }
+
     fn update_flags(&mut self, flags: Flags, time: Option<Time>) {
         self.flags = flags;
         self.time = time;
@@ -766,6 +1038,14 @@ impl Updatable for DoubleBitBinaryInput {
         &mut maps.double_bit_binary
     }
 
+    fn get_selected(selection: &StaticSelection) -> &SelectedMap<Self> {
+        &selection.double_bit_binary
+    }
+
+    fn get_selected_mut(selection: &mut StaticSelection) -> &mut SelectedMap<Self> {
+        &mut selection.double_bit_binary
+    }
+
     fn wrap(range: IndexRange, variation: Option<Self::StaticVariation>) -> VariationRange {
         SpecificVariation::DoubleBitBinary(variation).with(range)
     }
@@ -773,9 +1053,21 @@ impl Updatable for DoubleBitBinaryInput {
     fn enabled_class_zero(config: &ClassZeroConfig) -> bool {
         config.double_bit_binary
     }
+
+    fn to_point_value(&self) -> PointValue {
+        PointValue::DoubleBitBinaryInput(*self)
+    }
 }
 
 impl UpdatableFlags for DoubleBitBinaryInput {
+    fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    fn time(&self) -> Option<Time> {
+        self.time
+    }
+
     fn update_flags(&mut self, flags: Flags, time: Option<Time>) {
         self.flags = flags;
         self.time = time;
@@ -794,6 +1086,14 @@ impl Updatable for BinaryOutputStatus {
         &mut maps.binary_output_status
     }
 
+    fn get_selected(selection: &StaticSelection) -> &SelectedMap<Self> {
+        &selection.binary_output_status
+    }
+
+    fn get_selected_mut(selection: &mut StaticSelection) -> &mut SelectedMap<Self> {
+        &mut selection.binary_output_status
+    }
+
     fn wrap(range: IndexRange, variation: Option<Self::StaticVariation>) -> VariationRange {
         SpecificVariation::BinaryOutputStatus(variation).with(range)
     }
@@ -801,9 +1101,21 @@ impl Updatable for BinaryOutputStatus {
     fn enabled_class_zero(config: &ClassZeroConfig) -> bool {
         config.binary_output_status
     }
+
+    fn to_point_value(&self) -> PointValue {
+        PointValue::BinaryOutputStatus(*self)
+    }
 }
 
 impl UpdatableFlags for BinaryOutputStatus {
+    fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    fn time(&self) -> Option<Time> {
+        self.time
+    }
+
     fn update_flags(&mut self, flags: Flags, time: Option<Time>) {
         self.flags = flags;
         self.time = time;
@@ -821,6 +1133,14 @@ impl Updatable for Counter {
         &mut maps.counter
     }
 
+    fn get_selected(selection: &StaticSelection) -> &SelectedMap<Self> {
+        &selection.counter
+    }
+
+    fn get_selected_mut(selection: &mut StaticSelection) -> &mut SelectedMap<Self> {
+        &mut selection.counter
+    }
+
     fn wrap(range: IndexRange, variation: Option<Self::StaticVariation>) -> VariationRange {
         SpecificVariation::Counter(variation).with(range)
     }
@@ -828,9 +1148,21 @@ impl Updatable for Counter {
     fn enabled_class_zero(config: &ClassZeroConfig) -> bool {
         config.counter
     }
+
+    fn to_point_value(&self) -> PointValue {
+        PointValue::Counter(*self)
+    }
 }
 
 impl UpdatableFlags for Counter {
+    fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    fn time(&self) -> Option<Time> {
+        self.time
+    }
+
     fn update_flags(&mut self, flags: Flags, time: Option<Time>) {
         self.flags = flags;
         self.time = time;
@@ -849,6 +1181,14 @@ impl Updatable for FrozenCounter {
         &mut maps.frozen_counter
     }
 
+    fn get_selected(selection: &StaticSelection) -> &SelectedMap<Self> {
+        &selection.frozen_counter
+    }
+
+    fn get_selected_mut(selection: &mut StaticSelection) -> &mut SelectedMap<Self> {
+        &mut selection.frozen_counter
+    }
+
     fn wrap(range: IndexRange, variation: Option<Self::StaticVariation>) -> VariationRange {
         SpecificVariation::FrozenCounter(variation).with(range)
     }
@@ -856,9 +1196,21 @@ impl Updatable for FrozenCounter {
     fn enabled_class_zero(config: &ClassZeroConfig) -> bool {
         config.frozen_counter
     }
+
+    fn to_point_value(&self) -> PointValue {
+        PointValue::FrozenCounter(*self)
+    }
 }
 
 impl UpdatableFlags for FrozenCounter {
+    fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    fn time(&self) -> Option<Time> {
+        self.time
+    }
+
     fn update_flags(&mut self, flags: Flags, time: Option<Time>) {
         self.flags = flags;
         self.time = time;
@@ -877,6 +1229,14 @@ impl Updatable for AnalogInput {
         &mut maps.analog
     }
 
+    fn get_selected(selection: &StaticSelection) -> &SelectedMap<Self> {
+        &selection.analog
+    }
+
+    fn get_selected_mut(selection: &mut StaticSelection) -> &mut SelectedMap<Self> {
+        &mut selection.analog
+    }
+
     fn wrap(range: IndexRange, variation: Option<Self::StaticVariation>) -> VariationRange {
         SpecificVariation::Analog(variation).with(range)
     }
@@ -884,9 +1244,21 @@ impl Updatable for AnalogInput {
     fn enabled_class_zero(config: &ClassZeroConfig) -> bool {
         config.analog
     }
+
+    fn to_point_value(&self) -> PointValue {
+        PointValue::AnalogInput(*self)
+    }
 }
 
 impl UpdatableFlags for AnalogInput {
+    fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    fn time(&self) -> Option<Time> {
+        self.time
+    }
+
     fn update_flags(&mut self, flags: Flags, time: Option<Time>) {
         self.flags = flags;
         self.time = time;
@@ -905,6 +1277,14 @@ impl Updatable for AnalogOutputStatus {
         &mut maps.analog_output_status
     }
 
+    fn get_selected(selection: &StaticSelection) -> &SelectedMap<Self> {
+        &selection.analog_output_status
+    }
+
+    fn get_selected_mut(selection: &mut StaticSelection) -> &mut SelectedMap<Self> {
+        &mut selection.analog_output_status
+    }
+
     fn wrap(range: IndexRange, variation: Option<Self::StaticVariation>) -> VariationRange {
         SpecificVariation::AnalogOutputStatus(variation).with(range)
     }
@@ -912,9 +1292,21 @@ impl Updatable for AnalogOutputStatus {
     fn enabled_class_zero(config: &ClassZeroConfig) -> bool {
         config.analog_output_status
     }
+
+    fn to_point_value(&self) -> PointValue {
+        PointValue::AnalogOutputStatus(*self)
+    }
 }
 
 impl UpdatableFlags for AnalogOutputStatus {
+    fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    fn time(&self) -> Option<Time> {
+        self.time
+    }
+
     fn update_flags(&mut self, flags: Flags, time: Option<Time>) {
         self.flags = flags;
         self.time = time;
@@ -933,6 +1325,14 @@ impl Updatable for OctetString {
         &mut maps.octet_strings
     }
 
+    fn get_selected(selection: &StaticSelection) -> &SelectedMap<Self> {
+        &selection.octet_strings
+    }
+
+    fn get_selected_mut(selection: &mut StaticSelection) -> &mut SelectedMap<Self> {
+        &mut selection.octet_strings
+    }
+
     fn wrap(range: IndexRange, _variation: Option<Self::StaticVariation>) -> VariationRange {
         SpecificVariation::OctetString.with(range)
     }
@@ -940,6 +1340,10 @@ impl Updatable for OctetString {
     fn enabled_class_zero(config: &ClassZeroConfig) -> bool {
         config.octet_string
     }
+
+    fn to_point_value(&self) -> PointValue {
+        PointValue::OctetString(Box::new(self.clone()))
+    }
 }
 
 impl Default for BinaryInput {
@@ -1027,18 +1431,19 @@ mod tests {
 
     #[test]
     fn can_write_integrity() {
-        let mut db = StaticDatabase::default();
+        let mut db = StaticDatabase::new();
+        let mut selection = StaticSelection::default();
 
         assert!(db.add(0, binary_config(StaticBinaryInputVariation::Group1Var2)));
         assert!(db.add(1, counter_config(StaticCounterVariation::Group20Var1)));
         assert!(db.add(2, analog_config(StaticAnalogInputVariation::Group30Var1)));
 
-        db.select_class_zero();
+        selection.select_class_zero(&db);
 
         let mut buffer = [0u8; 64];
         let mut cursor = WriteCursor::new(buffer.as_mut());
 
-        db.write(&mut cursor).unwrap();
+        selection.write(&db, &mut cursor, false).unwrap();
 
         assert_eq!(
             cursor.written(),
@@ -1054,19 +1459,20 @@ mod tests {
 
     #[test]
     fn can_write_multiple_cycles() {
-        let mut db = StaticDatabase::default();
+        let mut db = StaticDatabase::new();
+        let mut selection = StaticSelection::default();
 
         assert!(db.add(0, binary_config(StaticBinaryInputVariation::Group1Var2)));
         assert!(db.add(1, counter_config(StaticCounterVariation::Group20Var1)));
         assert!(db.add(2, analog_config(StaticAnalogInputVariation::Group30Var1)));
 
-        db.select_class_zero();
+        selection.select_class_zero(&db);
 
         let mut buffer = [0u8; 12]; // can only fit one header at a time
 
         {
             let mut cursor = WriteCursor::new(buffer.as_mut());
-            db.write(&mut cursor).unwrap_err(); // incomplete !
+            selection.write(&db, &mut cursor, false).unwrap_err(); // incomplete !
 
             assert_eq!(
                 cursor.written(),
@@ -1079,7 +1485,7 @@ mod tests {
 
         {
             let mut cursor = WriteCursor::new(buffer.as_mut());
-            db.write(&mut cursor).unwrap_err(); // incomplete !
+            selection.write(&db, &mut cursor, false).unwrap_err(); // incomplete !
 
             assert_eq!(
                 cursor.written(),
@@ -1092,7 +1498,7 @@ mod tests {
 
         {
             let mut cursor = WriteCursor::new(buffer.as_mut());
-            db.write(&mut cursor).unwrap(); // complete !
+            selection.write(&db, &mut cursor, false).unwrap(); // complete !
 
             assert_eq!(
                 cursor.written(),
@@ -1106,16 +1512,17 @@ mod tests {
 
     #[test]
     fn promotes_g1v1_to_g1v2_if_flags_other_than_just_online() {
-        let mut db = StaticDatabase::default();
+        let mut db = StaticDatabase::new();
+        let mut selection = StaticSelection::default();
 
         assert!(db.add(0, binary_config(StaticBinaryInputVariation::Group1Var1)));
 
-        db.select_class_zero();
+        selection.select_class_zero(&db);
 
         let mut buffer = [0u8; 64];
         let mut cursor = WriteCursor::new(buffer.as_mut());
 
-        db.write(&mut cursor).unwrap();
+        selection.write(&db, &mut cursor, false).unwrap();
 
         assert_eq!(
             cursor.written(),
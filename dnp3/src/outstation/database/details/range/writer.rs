@@ -95,10 +95,37 @@ impl<T> HeaderState<T> {
     }
 }
 
+struct PrefixedState<T> {
+    /// number of objects written in the header
+    count: u16,
+    /// immutable position of the u16 count field within the cursor
+    count_pos: usize,
+    /// function used to write each value
+    write: FixedWriteFn<T>,
+}
+
+impl<T> PrefixedState<T> {
+    fn new(count: u16, count_pos: usize, write: FixedWriteFn<T>) -> Self {
+        Self {
+            count,
+            count_pos,
+            write,
+        }
+    }
+}
+
+/// size of a header with qualifier 0x01 (16-bit start and stop)
+const RANGED_HEADER_SIZE: usize = 7;
+/// size of a header with qualifier 0x28 (16-bit count)
+const PREFIXED_HEADER_SIZE: usize = 5;
+/// size of the 16-bit index that prefixes each object in a header with qualifier 0x28
+const INDEX_PREFIX_SIZE: usize = 2;
+
 //#[derive(Debug)]
 enum State<T> {
     Start,
     Header(Variation, HeaderState<T>),
+    Prefixed(Variation, PrefixedState<T>),
     Full,
 }
 
@@ -143,19 +170,78 @@ impl<T> RangeWriter<T> {
         let variation = info.variation;
         let state = match &self.state {
             State::Full => return Err(BadWrite),
-            State::Start => Self::start_header(cursor, index, value, info)?,
-            State::Header(variation, header) => {
-                if *variation == info.variation && is_consecutive(header.index, index) {
-                    Self::write_next_value(cursor, header, index, value)?
-                } else {
-                    Self::start_header(cursor, index, value, info)?
-                }
+            State::Header(variation, header)
+                if *variation == info.variation && is_consecutive(header.index, index) =>
+            {
+                Self::write_next_value(cursor, header, index, value)?
             }
+            _ => Self::start_header(cursor, index, value, info)?,
         };
 
         Ok(State::Header(variation, state))
     }
 
+    /// determine if a run of `count` consecutive values is encoded in fewer bytes using a ranged
+    /// header than using index prefixes, given whether other values follow the run
+    ///
+    /// The objects are the same size in both encodings so only the overhead is compared. Values
+    /// following a ranged header require a new index-prefixed header, whereas an index-prefixed
+    /// run continues the current header if one is already open for the same variation.
+    pub(crate) fn prefers_ranged(&self, info: &WriteInfo<T>, count: usize, more: bool) -> bool {
+        // packed and variable sized values can't be index-prefixed
+        if !matches!(info.write_type, WriteType::Fixed(_)) {
+            return true;
+        }
+
+        let open = matches!(
+            &self.state,
+            State::Prefixed(variation, prefixed)
+                if *variation == info.variation && usize::from(prefixed.count) + count <= u16::MAX.into()
+        );
+
+        let ranged = RANGED_HEADER_SIZE + if more { PREFIXED_HEADER_SIZE } else { 0 };
+        let prefixed = count * INDEX_PREFIX_SIZE + if open { 0 } else { PREFIXED_HEADER_SIZE };
+
+        ranged <= prefixed
+    }
+
+    /// write a value using a header with qualifier 0x28 where each object is prefixed by its index
+    ///
+    /// Values that cannot be index-prefixed (packed bits) are written using ranged headers instead
+    pub(crate) fn write_prefixed(
+        &mut self,
+        cursor: &mut WriteCursor,
+        index: u16,
+        value: &T,
+        info: WriteInfo<T>,
+    ) -> Result<(), BadWrite> {
+        let write = match info.write_type {
+            WriteType::Fixed(write) => write,
+            _ => return self.write(cursor, index, value, info),
+        };
+
+        let result = match &self.state {
+            State::Full => Err(BadWrite),
+            State::Prefixed(variation, prefixed)
+                if *variation == info.variation && prefixed.count < u16::MAX =>
+            {
+                Self::write_next_prefixed(cursor, prefixed, index, value)
+            }
+            _ => Self::start_prefixed(cursor, info.variation, index, value, write),
+        };
+
+        match result {
+            Ok(prefixed) => {
+                self.state = State::Prefixed(info.variation, prefixed);
+                Ok(())
+            }
+            Err(_) => {
+                self.state = State::Full;
+                Err(BadWrite)
+            }
+        }
+    }
+
     fn start_header(
         cursor: &mut WriteCursor,
         index: u16,
@@ -187,6 +273,46 @@ impl<T> RangeWriter<T> {
         })?;
         Ok(ret)
     }
+
+    fn start_prefixed(
+        cursor: &mut WriteCursor,
+        variation: Variation,
+        index: u16,
+        value: &T,
+        write: FixedWriteFn<T>,
+    ) -> Result<PrefixedState<T>, BadWrite> {
+        let ret = cursor.transaction(|cur| {
+            variation.write(cur)?;
+            QualifierCode::CountAndPrefix16.write(cur)?;
+            let count_pos = cur.position();
+            cur.write_u16_le(1)?;
+            cur.write_u16_le(index)?;
+            (write)(cur, value)?;
+            Ok(PrefixedState::new(1, count_pos, write))
+        })?;
+        Ok(ret)
+    }
+
+    fn write_next_prefixed(
+        cursor: &mut WriteCursor,
+        prefixed: &PrefixedState<T>,
+        index: u16,
+        value: &T,
+    ) -> Result<PrefixedState<T>, BadWrite> {
+        let ret = cursor.transaction(|cur| {
+            cur.write_u16_le(index)?;
+            (prefixed.write)(cur, value)?;
+            // update the count field
+            let count = prefixed.count + 1;
+            cur.at_pos(prefixed.count_pos, |cur| cur.write_u16_le(count))?;
+            Ok(PrefixedState::new(
+                count,
+                prefixed.count_pos,
+                prefixed.write,
+            ))
+        })?;
+        Ok(ret)
+    }
 }
 
 impl<T> TypeState<T> {
@@ -474,4 +600,52 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    #[rustfmt::skip]
+    fn prefixed_values_share_a_header_until_variation_changes() {
+        let mut buffer = [0u8; 64];
+        let mut cursor = WriteCursor::new(buffer.as_mut());
+        let mut writer = RangeWriter::new();
+
+        let g1v2 = StaticBinaryInputVariation::Group1Var2.get_write_info(&binary(false));
+
+        writer.write_prefixed(&mut cursor, 1, &binary(true), g1v2).unwrap();
+        writer.write_prefixed(&mut cursor, 7, &binary(false), g1v2).unwrap();
+        writer.write(&mut cursor, 9, &binary(true), g1v2).unwrap();
+        writer.write_prefixed(&mut cursor, 300, &binary(true), g1v2).unwrap();
+
+        assert_eq!(
+            cursor.written(),
+            [
+                // g1v2 - 16-bit count and prefix
+                1, 2, 0x28, 2, 0, 1, 0, 0x81, 7, 0, 0x01,
+                // ranged header
+                1, 2, 0x01, 9, 0, 9, 0, 0x81,
+                // new prefixed header
+                1, 2, 0x28, 1, 0, 0x2C, 0x01, 0x81,
+            ]
+        )
+    }
+
+    #[test]
+    #[rustfmt::skip]
+    fn packed_values_are_never_prefixed() {
+        let mut buffer = [0u8; 64];
+        let mut cursor = WriteCursor::new(buffer.as_mut());
+        let mut writer = RangeWriter::new();
+
+        let g1v1 = StaticBinaryInputVariation::Group1Var1.get_write_info(&binary(false));
+
+        writer.write_prefixed(&mut cursor, 3, &binary(true), g1v1).unwrap();
+        writer.write_prefixed(&mut cursor, 4, &binary(true), g1v1).unwrap();
+
+        assert_eq!(
+            cursor.written(),
+            [
+                // g1v1 - 16-bit start/stop
+                1, 1, 0x01, 3, 0, 4, 0, 0b0000_0011,
+            ]
+        )
+    }
 }
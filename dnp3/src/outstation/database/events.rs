@@ -0,0 +1,69 @@
+use crate::app::measurement::Time;
+use crate::outstation::database::EventClass;
+
+/// Type of measurement that produced a buffered event
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EventType {
+    /// Binary input event
+    BinaryInput,
+    /// Double-bit binary input event
+    DoubleBitBinaryInput,
+    /// Binary output status event
+    BinaryOutputStatus,
+    /// Counter event
+    Counter,
+    /// Frozen counter event
+    FrozenCounter,
+    /// Analog input event
+    AnalogInput,
+    /// Analog output status event
+    AnalogOutputStatus,
+    /// Octet string event
+    OctetString,
+    /// Binary output command event
+    BinaryOutputCommand,
+    /// Analog output command event
+    AnalogOutputCommand,
+}
+
+/// Information about an event held in the event buffer of an outstation
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BufferedEventInfo {
+    /// Unique id assigned to the event when it was created
+    pub id: u64,
+    /// Type of the event
+    pub event_type: EventType,
+    /// Index of the point that produced the event
+    pub index: u16,
+    /// Class assigned to the event
+    pub class: EventClass,
+    /// Timestamp of the measurement, if any
+    pub time: Option<Time>,
+    /// True if the event was reported to the master and is awaiting confirmation
+    pub written: bool,
+}
+
+/// Information about an event that was discarded because the event buffer was full
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EventOverflow {
+    /// Id of the event whose creation caused the overflow
+    pub created: u64,
+    /// The event that was discarded to make room
+    pub discarded: BufferedEventInfo,
+}
+
+/// User-supplied callback that is notified when events are discarded due to an overflow
+///
+/// The listener is invoked while the database lock is held, so implementations must not
+/// block. Typically the notification is just queued for processing by another task.
+pub trait EventOverflowListener: Send {
+    /// An event was discarded from the event buffer
+    fn on_overflow(&mut self, overflow: EventOverflow);
+}
+
+impl EventOverflowListener for tokio::sync::mpsc::UnboundedSender<EventOverflow> {
+    fn on_overflow(&mut self, overflow: EventOverflow) {
+        // receiver is gone, nothing to do
+        let _ = self.send(overflow);
+    }
+}
@@ -1,21 +1,34 @@
-use std::sync::{Arc, Mutex};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::SystemTime;
 
 pub use config::*;
+use details::database::{MemberId, MemberRef};
 use details::range::static_db::{Deadband, FlagsDetector, OctetStringDetector, PointConfig};
+pub use events::*;
+pub use quality::*;
 
+use crate::app::control::CommandStatus;
 use crate::app::measurement::*;
 use crate::app::parse::parser::HeaderCollection;
-use crate::app::Iin2;
+use crate::app::{Iin2, Timestamp};
 use crate::master::EventClasses;
 use crate::outstation::database::read::ReadHeader;
 
 use crate::app::attr::{AttrProp, AttrSet, OwnedAttribute, TypeError};
-use crate::outstation::OutstationApplication;
+use crate::outstation::replication::{ReplicationError, ReplicationMessage, ReplicationSink};
+use crate::outstation::{
+    BufferState, Command, OutstationApplication, OutstationClock, UnsolicitedTriggers,
+};
 use scursor::WriteCursor;
 
 mod config;
-/// private internal control only needed by the parent module
-mod details;
+/// internal details shared with the codec
+pub(crate) mod details;
+/// inspection of buffered events
+mod events;
+/// quality management of points by source
+mod quality;
 /// read headers
 pub(crate) mod read;
 
@@ -120,6 +133,7 @@ impl Default for ClassZeroConfig {
     feature = "serialization",
     derive(serde::Serialize, serde::Deserialize)
 )]
+#[cfg_attr(not(feature = "ffi"), non_exhaustive)]
 pub struct EventBufferConfig {
     /// maximum number of binary input events (g2)
     #[cfg_attr(feature = "serialization", serde(default))]
@@ -145,12 +159,27 @@ pub struct EventBufferConfig {
     /// maximum number of octet string events (g111)
     #[cfg_attr(feature = "serialization", serde(default))]
     pub max_octet_string: u16,
+    /// maximum number of binary output command events (g13)
+    #[cfg_attr(
+        feature = "serialization",
+        serde(default = "EventBufferConfig::default_max_command_events")
+    )]
+    pub max_binary_output_command: u16,
+    /// maximum number of analog output command events (g43)
+    #[cfg_attr(
+        feature = "serialization",
+        serde(default = "EventBufferConfig::default_max_command_events")
+    )]
+    pub max_analog_output_command: u16,
 }
 
 impl EventBufferConfig {
+    /// default maximum number of binary (g13) and analog (g43) output command events
+    pub const DEFAULT_MAX_COMMAND_EVENTS: u16 = 10;
+
     /// initialize with the same maximum values for all types
     pub fn all_types(max: u16) -> Self {
-        Self::new(max, max, max, max, max, max, max, max)
+        Self::new(max, max, max, max, max, max, max, max).with_command_events(max, max)
     }
 
     /// initialize the configuration to support no events
@@ -159,6 +188,9 @@ impl EventBufferConfig {
     }
 
     /// create a configuration specifying the max for each type individually
+    ///
+    /// Up to [`Self::DEFAULT_MAX_COMMAND_EVENTS`] binary and analog output command events are
+    /// buffered, see [`Self::with_command_events`]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         max_binary: u16,
@@ -179,6 +211,22 @@ impl EventBufferConfig {
             max_analog,
             max_analog_output_status,
             max_octet_string,
+            max_binary_output_command: Self::DEFAULT_MAX_COMMAND_EVENTS,
+            max_analog_output_command: Self::DEFAULT_MAX_COMMAND_EVENTS,
+        }
+    }
+
+    #[cfg(feature = "serialization")]
+    const fn default_max_command_events() -> u16 {
+        Self::DEFAULT_MAX_COMMAND_EVENTS
+    }
+
+    /// set the maximum number of binary (g13) and analog (g43) output command events
+    pub fn with_command_events(self, max_binary: u16, max_analog: u16) -> Self {
+        Self {
+            max_binary_output_command: max_binary,
+            max_analog_output_command: max_analog,
+            ..self
         }
     }
 
@@ -191,6 +239,8 @@ impl EventBufferConfig {
             + self.max_analog as usize
             + self.max_analog_output_status as usize
             + self.max_octet_string as usize
+            + self.max_binary_output_command as usize
+            + self.max_analog_output_command as usize
     }
 }
 
@@ -211,12 +261,12 @@ pub(crate) struct EventsInfo {
 /// Options that control how the update is performed. 99% of the time
 /// the default() method should be used to initialize this struct. Very
 /// few applications need to use the other options.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct UpdateOptions {
     /// optionally bypass updating the static_db (current value)
-    update_static: bool,
+    pub(crate) update_static: bool,
     /// determines how/if an event is produced
-    event_mode: EventMode,
+    pub(crate) event_mode: EventMode,
 }
 
 impl UpdateOptions {
@@ -308,7 +358,7 @@ pub trait Update<T> {
 }
 
 /// Point type on which to update the flags
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 pub enum UpdateFlagsType {
     /// Binary input
@@ -390,19 +440,53 @@ impl From<TypeError> for AttrDefError {
 
 /// Core database implementation shared between an outstation task and the user facing API.
 /// This type is always guarded by a `DatabaseHandle` which provides a transactional API.
+///
+/// When several outstations share a database, the points and attributes are stored once
+/// while each outstation has its own event buffer. Events produced by a change are inserted
+/// into the buffer of every outstation, and the returned [`UpdateInfo`] describes the buffer of
+/// the outstation whose events are replicated or, if replication isn't active, of the outstation
+/// that joined the database first.
 pub struct Database {
     pub(crate) inner: details::database::Database,
+    // notification of each outstation sharing the database
+    notify: BTreeMap<MemberId, Arc<tokio::sync::Notify>>,
+    // wakes the stale timer when stale detection is enabled for a source
+    stale_changed: Arc<tokio::sync::Notify>,
+    stale_timer: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl Drop for Database {
+    fn drop(&mut self) {
+        if let Some(timer) = self.stale_timer.take() {
+            timer.abort();
+        }
+    }
 }
 
 impl Database {
-    /// Create a database by specified how it will buffer events
+    /// Create a database served by a single outstation
+    #[cfg(test)]
     pub(crate) fn new(
         max_read_selection: Option<u16>,
         class_zero_config: ClassZeroConfig,
         config: EventBufferConfig,
     ) -> Self {
+        let mut db = Self::empty();
+        db.join(
+            max_read_selection,
+            class_zero_config,
+            config,
+            Arc::new(tokio::sync::Notify::new()),
+        );
+        db
+    }
+
+    fn empty() -> Self {
         Self {
-            inner: details::database::Database::new(max_read_selection, class_zero_config, config),
+            inner: details::database::Database::new(),
+            notify: BTreeMap::new(),
+            stale_changed: Arc::new(tokio::sync::Notify::new()),
+            stale_timer: None,
         }
     }
 
@@ -414,29 +498,220 @@ impl Database {
     ) -> Result<(), AttrDefError> {
         self.inner.get_attr_map().define(prop, attr)
     }
+
+    /// Assign a point to a source, or remove it from its source by passing `None`
+    ///
+    /// Returns false if the point does not exist
+    pub fn set_source(
+        &mut self,
+        flags_type: UpdateFlagsType,
+        index: u16,
+        source: Option<SourceId>,
+    ) -> bool {
+        self.inner.set_source(flags_type, index, source)
+    }
+
+    /// Apply a quality change to every point assigned to the source, e.g. when communication
+    /// with the source is lost, and return the number of points that were changed
+    ///
+    /// If `time` is `None`, each point keeps its current timestamp. The values of the points are
+    /// not modified, and the change does not restart the stale timer of the source.
+    pub fn update_source_quality(
+        &mut self,
+        source: SourceId,
+        change: QualityChange,
+        time: Option<Time>,
+        options: SourceUpdateOptions,
+    ) -> usize {
+        self.inner
+            .update_source_quality(source, change, time, options)
+    }
+
+    /// Enable stale detection for the points assigned to the source, or disable it with `None`
+    ///
+    /// The timer starts immediately and restarts whenever any point of the source is updated.
+    /// The timers are run by the database once an outstation using it has been started, whether
+    /// or not a master is connected. The points are timestamped with the
+    /// [`OutstationClock`] of that outstation, if one is configured.
+    pub fn set_stale_detection(&mut self, source: SourceId, config: Option<StaleConfig>) {
+        self.inner.set_stale_detection(source, config);
+        self.stale_changed.notify_one();
+    }
+
+    pub(crate) fn complete_operate(
+        &mut self,
+        index: u16,
+        command: Command,
+        status: CommandStatus,
+        time: Option<Time>,
+    ) -> UpdateInfo {
+        self.inner.complete_operate(index, command, status, time)
+    }
+
+    /// add an outstation with an empty event buffer and return its id
+    fn join(
+        &mut self,
+        max_read_selection: Option<u16>,
+        class_zero_config: ClassZeroConfig,
+        config: EventBufferConfig,
+        notify: Arc<tokio::sync::Notify>,
+    ) -> MemberId {
+        let id = self
+            .inner
+            .join(max_read_selection, class_zero_config, config);
+        self.notify.insert(id, notify);
+        id
+    }
+
+    fn leave(&mut self, id: MemberId) {
+        self.inner.leave(id);
+        self.notify.remove(&id);
+    }
+
+    /// degrade the quality of stale sources and return the time of the next stale check, if any
+    fn check_stale(&mut self, time: Option<Time>) -> Option<tokio::time::Instant> {
+        let next = self.inner.check_stale(tokio::time::Instant::now(), time);
+        self.notify_all();
+        next
+    }
+
+    fn notify_all(&self) {
+        for notify in self.notify.values() {
+            notify.notify_one();
+        }
+    }
+}
+
+struct StaleTimer {
+    database: Weak<Mutex<Database>>,
+    changed: Arc<tokio::sync::Notify>,
+    clock: Option<OutstationClock>,
+}
+
+impl StaleTimer {
+    async fn run(self) {
+        loop {
+            let next = match self.database.upgrade() {
+                Some(db) => db.lock().unwrap().check_stale(self.now()),
+                None => return,
+            };
+
+            match next {
+                Some(deadline) => {
+                    tokio::select! {
+                        _ = tokio::time::sleep_until(deadline) => {}
+                        _ = self.changed.notified() => {}
+                    }
+                }
+                None => self.changed.notified().await,
+            }
+        }
+    }
+
+    fn now(&self) -> Option<Time> {
+        match &self.clock {
+            Some(clock) => Some(clock.now()),
+            None => Timestamp::try_from_system_time(SystemTime::now()).map(Time::Unsynchronized),
+        }
+    }
 }
 
 /// Handle type that can be used to perform transactions on an underlying database
+///
+/// Cloning the handle refers to the same database and the same outstation.
 #[derive(Clone)]
 pub struct DatabaseHandle {
     inner: Arc<Mutex<Database>>,
     notify: Arc<tokio::sync::Notify>,
+    // id of the outstation served by this handle within the database
+    member: MemberId,
 }
 
 impl DatabaseHandle {
     /// Acquire a mutex on the underlying database and apply a set of changes as a transaction
+    ///
+    /// If the database is shared by several outstations, the changes are visible to all of them.
     pub fn transaction<F, R>(&self, mut func: F) -> R
     where
         F: FnMut(&mut Database) -> R,
     {
-        let ret = {
-            let mut db = self.inner.lock().unwrap();
-            func(&mut db)
-        };
-        self.notify.notify_one();
+        let mut db = self.inner.lock().unwrap();
+        let ret = func(&mut db);
+        db.notify_all();
+        ret
+    }
+
+    /// Start replicating this database to a standby instance via the supplied sink
+    ///
+    /// A snapshot of all point values and buffered events is published immediately,
+    /// followed by every subsequent static update, event and event confirmation. Any
+    /// previously installed sink is replaced.
+    ///
+    /// Point configuration is not replicated. The standby database must be configured
+    /// with exactly the same points and event buffer settings as this database.
+    ///
+    /// If the database is shared, the events of the outstation served by this handle
+    /// are replicated. Replication stops if this outstation is shut down.
+    pub fn start_replication(&self, sink: Box<dyn ReplicationSink>) {
+        self.inner
+            .lock()
+            .unwrap()
+            .inner
+            .start_replication(self.member, sink);
+    }
+
+    /// Stop replicating this database
+    pub fn stop_replication(&self) {
+        self.inner.lock().unwrap().inner.stop_replication();
+    }
+
+    /// Apply a message produced by an active database to this standby database
+    ///
+    /// Events are assigned the same ids that they were assigned in the active database and
+    /// events confirmed by the master are removed, so that this database can be promoted
+    /// without losing unreported events or reporting duplicates. If an error is returned,
+    /// the standby should be re-synchronized by restarting replication on the active database.
+    pub fn apply_replication(&self, message: ReplicationMessage) -> Result<(), ReplicationError> {
+        let mut db = self.inner.lock().unwrap();
+        let ret = db.inner.apply_replication(self.member, message.inner);
+        db.notify_all();
         ret
     }
 
+    /// Number of events of each class and type in the event buffer
+    pub fn get_buffer_state(&self) -> BufferState {
+        self.with_member(|db| db.buffer_state()).unwrap_or_default()
+    }
+
+    /// Snapshot of the events in the event buffer, from oldest to newest
+    pub fn get_buffered_events(&self) -> Vec<BufferedEventInfo> {
+        self.with_member(|db| db.events_info()).unwrap_or_default()
+    }
+
+    /// Discard all buffered events of the specified classes and return the number removed
+    ///
+    /// Events awaiting confirmation by the master are discarded as well. The master will
+    /// never receive these events.
+    pub fn clear_events_by_class(&self, classes: EventClasses) -> usize {
+        self.member_transaction(|mut db| db.clear_events_by_class(classes))
+            .unwrap_or(0)
+    }
+
+    /// Discard all buffered events of the specified type and return the number removed
+    ///
+    /// Events awaiting confirmation by the master are discarded as well. The master will
+    /// never receive these events.
+    pub fn clear_events_by_type(&self, event_type: EventType) -> usize {
+        self.member_transaction(|mut db| db.clear_events_by_type(event_type))
+            .unwrap_or(0)
+    }
+
+    /// Install a listener that is notified whenever an event is discarded because the event
+    /// buffer is full, replacing any previous listener. Passing `None` removes the listener.
+    pub fn set_overflow_listener(&self, listener: Option<Box<dyn EventOverflowListener>>) {
+        self.with_member(|mut db| db.set_overflow_listener(listener));
+    }
+
     pub(crate) async fn wait_for_change(&self) {
         self.notify.notified().await
     }
@@ -446,51 +721,179 @@ impl DatabaseHandle {
         class_zero_config: ClassZeroConfig,
         event_config: EventBufferConfig,
     ) -> Self {
+        Self::join_impl(
+            Arc::new(Mutex::new(Database::empty())),
+            max_read_selection,
+            class_zero_config,
+            event_config,
+        )
+    }
+
+    /// create a handle for another outstation that shares the underlying database
+    ///
+    /// The new outstation shares the existing points and starts with an empty event buffer
+    pub(crate) fn join(
+        &self,
+        max_read_selection: Option<u16>,
+        class_zero_config: ClassZeroConfig,
+        event_config: EventBufferConfig,
+    ) -> Self {
+        Self::join_impl(
+            self.inner.clone(),
+            max_read_selection,
+            class_zero_config,
+            event_config,
+        )
+    }
+
+    fn join_impl(
+        inner: Arc<Mutex<Database>>,
+        max_read_selection: Option<u16>,
+        class_zero_config: ClassZeroConfig,
+        event_config: EventBufferConfig,
+    ) -> Self {
+        let notify = Arc::new(tokio::sync::Notify::new());
+        let member = inner.lock().unwrap().join(
+            max_read_selection,
+            class_zero_config,
+            event_config,
+            notify.clone(),
+        );
         Self {
-            inner: Arc::new(Mutex::new(Database::new(
-                max_read_selection,
-                class_zero_config,
-                event_config,
-            ))),
-            notify: Arc::new(tokio::sync::Notify::new()),
+            inner,
+            notify,
+            member,
+        }
+    }
+
+    /// true if an operation on the output point hasn't completed yet
+    pub(crate) fn is_operate_pending(&self, index: u16, command: Command) -> bool {
+        self.inner
+            .lock()
+            .unwrap()
+            .inner
+            .is_operate_pending(index, command)
+    }
+
+    /// record that an operation on the output point will complete later
+    pub(crate) fn begin_operate(&self, index: u16, command: Command) {
+        self.inner
+            .lock()
+            .unwrap()
+            .inner
+            .begin_operate(index, command)
+    }
+
+    /// remove the outstation served by this handle, and its event buffer, from the database
+    ///
+    /// The points remain available to the other outstations sharing the database
+    pub(crate) fn leave(&self) {
+        self.inner.lock().unwrap().leave(self.member);
+    }
+
+    /// apply a function to the state of the outstation served by this handle, if it's still a member
+    fn with_member<F, R>(&self, func: F) -> Option<R>
+    where
+        F: FnOnce(MemberRef) -> R,
+    {
+        self.inner
+            .lock()
+            .unwrap()
+            .inner
+            .member(self.member)
+            .map(func)
+    }
+
+    /// apply a change to the state of the outstation served by this handle only
+    pub(crate) fn member_transaction<F, R>(&self, func: F) -> Option<R>
+    where
+        F: FnOnce(MemberRef) -> R,
+    {
+        let ret = self.with_member(func);
+        self.notify.notify_one();
+        ret
+    }
+
+    #[cfg(test)]
+    pub(crate) fn snapshot(&self) -> Option<crate::outstation::replication::Snapshot> {
+        self.inner.lock().unwrap().inner.snapshot(self.member)
+    }
+
+    /// start the timer that detects stale sources, unless it's already running
+    ///
+    /// The timer stops when the database is dropped
+    pub(crate) fn start_stale_timer(&self, clock: Option<OutstationClock>) {
+        let mut db = self.inner.lock().unwrap();
+        if db.stale_timer.is_none() {
+            let timer = StaleTimer {
+                database: Arc::downgrade(&self.inner),
+                changed: db.stale_changed.clone(),
+                clock,
+            };
+            db.stale_timer = Some(tokio::spawn(timer.run()));
         }
     }
 
     pub(crate) async fn clear_written_events(&mut self, app: &mut dyn OutstationApplication) {
         app.begin_confirm();
-        let state = self.inner.lock().unwrap().inner.clear_written_events(app);
+        let state = self
+            .with_member(|mut db| db.clear_written_events(app))
+            .unwrap_or_default();
         app.end_confirm(state).get().await;
     }
 
     pub(crate) fn get_events_info(&self) -> EventsInfo {
-        let guard = self.inner.lock().unwrap();
+        self.with_member(|db| EventsInfo {
+            unwritten_classes: db.unwritten_classes(),
+            is_overflown: db.is_overflown(),
+        })
+        .unwrap_or(EventsInfo {
+            unwritten_classes: EventClasses::none(),
+            is_overflown: false,
+        })
+    }
 
-        EventsInfo {
-            unwritten_classes: guard.inner.unwritten_classes(),
-            is_overflown: guard.inner.is_overflown(),
-        }
+    pub(crate) fn get_event_buffer_statistics(&self) -> (usize, u64) {
+        self.with_member(|db| db.event_buffer_statistics())
+            .unwrap_or_default()
     }
 
     pub(crate) fn select(&mut self, headers: &HeaderCollection) -> Iin2 {
-        let mut iin2 = Iin2::default();
-        let mut guard = self.inner.lock().unwrap();
-        for header in headers.iter() {
-            match ReadHeader::get(&header) {
-                None => {
-                    iin2 |= Iin2::NO_FUNC_CODE_SUPPORT;
+        self.with_member(|mut db| {
+            let mut iin2 = Iin2::default();
+            for header in headers.iter() {
+                match ReadHeader::get(&header) {
+                    None => {
+                        iin2 |= Iin2::NO_FUNC_CODE_SUPPORT;
+                    }
+                    Some(x) => iin2 |= db.select_by_header(x),
                 }
-                Some(x) => iin2 |= guard.inner.select_by_header(x),
             }
-        }
-        iin2
+            iin2
+        })
+        .unwrap_or_default()
     }
 
-    pub(crate) fn write_response_headers(&mut self, cursor: &mut WriteCursor) -> ResponseInfo {
-        self.inner
-            .lock()
-            .unwrap()
-            .inner
-            .write_response_headers(cursor)
+    pub(crate) fn write_response_headers(
+        &mut self,
+        cursor: &mut WriteCursor,
+        prefixed: bool,
+    ) -> ResponseInfo {
+        self.with_member(|mut db| db.write_response_headers(cursor, prefixed))
+            .unwrap_or(ResponseInfo {
+                has_events: false,
+                complete: true,
+            })
+    }
+
+    /// time at which the buffered events trigger an unsolicited response, if ever
+    pub(crate) fn unsolicited_trigger_time(
+        &self,
+        classes: EventClasses,
+        triggers: &UnsolicitedTriggers,
+    ) -> Option<tokio::time::Instant> {
+        self.with_member(|db| db.unsolicited_trigger_time(classes, triggers))
+            .flatten()
     }
 
     pub(crate) fn write_unsolicited(
@@ -498,17 +901,19 @@ impl DatabaseHandle {
         classes: EventClasses,
         cursor: &mut WriteCursor,
     ) -> usize {
-        let mut guard = self.inner.lock().unwrap();
-        guard.inner.reset();
-        let count = guard.inner.select_event_classes(classes);
-        if count == 0 {
-            return 0;
-        }
-        guard.inner.write_events_only(cursor)
+        self.with_member(|mut db| {
+            db.reset();
+            let count = db.select_event_classes(classes);
+            if count == 0 {
+                return 0;
+            }
+            db.write_events_only(cursor)
+        })
+        .unwrap_or(0)
     }
 
     pub(crate) fn reset(&mut self) {
-        self.inner.lock().unwrap().inner.reset()
+        self.with_member(|mut db| db.reset());
     }
 }
 
@@ -895,4 +1300,68 @@ mod test {
             db.update2(0, &binary(true), UpdateOptions::default())
         );
     }
+
+    #[test]
+    fn quality_change_applies_to_points_of_source_only() {
+        let handle = DatabaseHandle::new(
+            None,
+            ClassZeroConfig::default(),
+            EventBufferConfig::all_types(10),
+        );
+        let source = SourceId(3);
+        let online = AnalogInput::new(1.0, Flags::ONLINE, Time::Synchronized(Timestamp::zero()));
+
+        let count = handle.transaction(|db| {
+            for index in 0..3 {
+                db.add(
+                    index,
+                    Some(EventClass::Class1),
+                    BinaryInputConfig::default(),
+                );
+                db.update(index, &binary(true), UpdateOptions::no_event());
+                db.add(
+                    index,
+                    Some(EventClass::Class2),
+                    AnalogInputConfig::default(),
+                );
+                db.update(index, &online, UpdateOptions::no_event());
+            }
+            for index in 0..2 {
+                db.set_source(UpdateFlagsType::BinaryInput, index, Some(source));
+                db.set_source(UpdateFlagsType::AnalogInput, index, Some(source));
+            }
+            assert!(!db.set_source(UpdateFlagsType::Counter, 0, Some(source)));
+
+            // events are only generated for the binary inputs
+            let options = SourceUpdateOptions {
+                analog_input: UpdateOptions::no_event(),
+                ..SourceUpdateOptions::all(UpdateOptions::detect_event())
+            };
+            db.update_source_quality(source, QualityChange::comm_lost(), None, options)
+        });
+        assert_eq!(count, 4);
+
+        handle.transaction(|db| {
+            for index in 0..2 {
+                let value: BinaryInput = db.get(index).unwrap();
+                assert_eq!(value.flags, Flags::COMM_LOST);
+                assert!(value.value);
+                assert_eq!(value.time, Some(Time::Synchronized(Timestamp::zero())));
+                let value: AnalogInput = db.get(index).unwrap();
+                assert_eq!(value.flags, Flags::COMM_LOST);
+            }
+            let value: BinaryInput = db.get(2).unwrap();
+            assert_eq!(value.flags, Flags::ONLINE);
+        });
+
+        let events: Vec<(EventType, u16)> = handle
+            .get_buffered_events()
+            .iter()
+            .map(|x| (x.event_type, x.index))
+            .collect();
+        assert_eq!(
+            events,
+            [(EventType::BinaryInput, 0), (EventType::BinaryInput, 1)]
+        );
+    }
 }
@@ -0,0 +1,118 @@
+use std::time::Duration;
+
+use crate::app::measurement::Flags;
+use crate::outstation::database::{EventMode, UpdateFlagsType, UpdateOptions};
+
+/// Identifies the source of a group of points, e.g. a downstream device polled by a gateway
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SourceId(pub u16);
+
+/// Change applied to the flags of every point sourced from a [`SourceId`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct QualityChange {
+    /// Flags that are set on each point
+    pub set: Flags,
+    /// Flags that are cleared on each point
+    pub clear: Flags,
+}
+
+impl QualityChange {
+    /// Create a change that sets and clears the specified flags
+    pub const fn new(set: Flags, clear: Flags) -> Self {
+        Self { set, clear }
+    }
+
+    /// Set `COMM_LOST` and clear `ONLINE`, used when communication with the source is lost
+    pub const fn comm_lost() -> Self {
+        Self::new(Flags::COMM_LOST, Flags::ONLINE)
+    }
+
+    /// Set `ONLINE` and clear `COMM_LOST`, used when communication with the source is restored
+    pub const fn restored() -> Self {
+        Self::new(Flags::ONLINE, Flags::COMM_LOST)
+    }
+
+    pub(crate) fn apply(self, flags: Flags) -> Flags {
+        Flags::new((flags.value | self.set.value) & !self.clear.value)
+    }
+}
+
+/// Options that control how each type of point is updated when the quality of a source changes
+///
+/// This allows events to be generated for some types and suppressed for others.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SourceUpdateOptions {
+    /// Options for binary inputs
+    pub binary_input: UpdateOptions,
+    /// Options for double-bit binary inputs
+    pub double_bit_binary_input: UpdateOptions,
+    /// Options for binary output statuses
+    pub binary_output_status: UpdateOptions,
+    /// Options for counters
+    pub counter: UpdateOptions,
+    /// Options for frozen counters
+    pub frozen_counter: UpdateOptions,
+    /// Options for analog inputs
+    pub analog_input: UpdateOptions,
+    /// Options for analog output statuses
+    pub analog_output_status: UpdateOptions,
+}
+
+impl SourceUpdateOptions {
+    /// Use the same options for every type of point
+    pub const fn all(options: UpdateOptions) -> Self {
+        Self {
+            binary_input: options,
+            double_bit_binary_input: options,
+            binary_output_status: options,
+            counter: options,
+            frozen_counter: options,
+            analog_input: options,
+            analog_output_status: options,
+        }
+    }
+
+    /// Options for the specified type of point
+    pub fn get(&self, flags_type: UpdateFlagsType) -> UpdateOptions {
+        match flags_type {
+            UpdateFlagsType::BinaryInput => self.binary_input,
+            UpdateFlagsType::DoubleBitBinaryInput => self.double_bit_binary_input,
+            UpdateFlagsType::BinaryOutputStatus => self.binary_output_status,
+            UpdateFlagsType::Counter => self.counter,
+            UpdateFlagsType::FrozenCounter => self.frozen_counter,
+            UpdateFlagsType::AnalogInput => self.analog_input,
+            UpdateFlagsType::AnalogOutputStatus => self.analog_output_status,
+        }
+    }
+}
+
+impl Default for SourceUpdateOptions {
+    fn default() -> Self {
+        Self::all(UpdateOptions::default())
+    }
+}
+
+/// Configuration of stale detection for the points of a [`SourceId`]
+///
+/// If none of the points are updated within the timeout, the quality change is applied
+/// to all of them. The timer restarts on the next update of any point of the source.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StaleConfig {
+    /// Period without updates after which the points are considered stale
+    pub timeout: Duration,
+    /// Change applied to the flags of the points when they become stale
+    pub change: QualityChange,
+    /// Options used to update the points when they become stale
+    pub options: SourceUpdateOptions,
+}
+
+impl StaleConfig {
+    /// Mark the points `COMM_LOST` after the specified timeout, generating events for all types
+    pub const fn comm_lost(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            change: QualityChange::comm_lost(),
+            options: SourceUpdateOptions::all(UpdateOptions::new(true, EventMode::Detect)),
+        }
+    }
+}
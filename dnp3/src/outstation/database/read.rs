@@ -4,6 +4,8 @@ use crate::app::gen::ranged::RangedVariation;
 use crate::app::parse::parser::{HeaderDetails, ObjectHeader};
 use crate::outstation::database::config::*;
 use crate::outstation::database::details::range::static_db::IndexRange;
+use crate::outstation::database::{EventClass, EventType};
+use crate::outstation::{ReadIndices, ReadObjects, StaticDataType};
 
 #[derive(Copy, Clone)]
 pub(crate) enum StaticReadHeader {
@@ -98,7 +100,90 @@ impl ReadHeader {
         res
     }
 
-    fn get_impl(header: &HeaderDetails) -> Option<ReadHeader> {
+    /// the data requested by the header, as reported to the application
+    pub(crate) fn to_read_objects(self) -> Option<ReadObjects> {
+        fn indices(range: Option<IndexRange>) -> ReadIndices {
+            range.map_or(ReadIndices::All, ReadIndices::from)
+        }
+
+        let objects = match self {
+            ReadHeader::Static(x) => match x {
+                StaticReadHeader::Class0 => ReadObjects::Class0,
+                StaticReadHeader::Binary(_, r) => {
+                    ReadObjects::Static(StaticDataType::BinaryInput, indices(r))
+                }
+                StaticReadHeader::DoubleBitBinary(_, r) => {
+                    ReadObjects::Static(StaticDataType::DoubleBitBinaryInput, indices(r))
+                }
+                StaticReadHeader::BinaryOutputStatus(_, r) => {
+                    ReadObjects::Static(StaticDataType::BinaryOutputStatus, indices(r))
+                }
+                StaticReadHeader::Counter(_, r) => {
+                    ReadObjects::Static(StaticDataType::Counter, indices(r))
+                }
+                StaticReadHeader::FrozenCounter(_, r) => {
+                    ReadObjects::Static(StaticDataType::FrozenCounter, indices(r))
+                }
+                StaticReadHeader::Analog(_, r) => {
+                    ReadObjects::Static(StaticDataType::AnalogInput, indices(r))
+                }
+                // frozen analog inputs are not supported by the database
+                StaticReadHeader::FrozenAnalog(_, _) => return None,
+                StaticReadHeader::AnalogOutputStatus(_, r) => {
+                    ReadObjects::Static(StaticDataType::AnalogOutputStatus, indices(r))
+                }
+                StaticReadHeader::OctetString(r) => {
+                    ReadObjects::Static(StaticDataType::OctetString, indices(r))
+                }
+                StaticReadHeader::AnalogInputDeadBand(_, r) => {
+                    ReadObjects::Static(StaticDataType::AnalogInputDeadBand, indices(r))
+                }
+            },
+            ReadHeader::Event(x) => match x {
+                EventReadHeader::Class1(count) => {
+                    ReadObjects::ClassEvents(EventClass::Class1, count)
+                }
+                EventReadHeader::Class2(count) => {
+                    ReadObjects::ClassEvents(EventClass::Class2, count)
+                }
+                EventReadHeader::Class3(count) => {
+                    ReadObjects::ClassEvents(EventClass::Class3, count)
+                }
+                EventReadHeader::Binary(_, count) => {
+                    ReadObjects::TypedEvents(EventType::BinaryInput, count)
+                }
+                EventReadHeader::DoubleBitBinary(_, count) => {
+                    ReadObjects::TypedEvents(EventType::DoubleBitBinaryInput, count)
+                }
+                EventReadHeader::BinaryOutputStatus(_, count) => {
+                    ReadObjects::TypedEvents(EventType::BinaryOutputStatus, count)
+                }
+                EventReadHeader::Counter(_, count) => {
+                    ReadObjects::TypedEvents(EventType::Counter, count)
+                }
+                EventReadHeader::FrozenCounter(_, count) => {
+                    ReadObjects::TypedEvents(EventType::FrozenCounter, count)
+                }
+                EventReadHeader::Analog(_, count) => {
+                    ReadObjects::TypedEvents(EventType::AnalogInput, count)
+                }
+                // frozen analog input events are not supported by the database
+                EventReadHeader::FrozenAnalog(_, _) => return None,
+                EventReadHeader::AnalogOutputStatus(_, count) => {
+                    ReadObjects::TypedEvents(EventType::AnalogOutputStatus, count)
+                }
+                EventReadHeader::OctetString(count) => {
+                    ReadObjects::TypedEvents(EventType::OctetString, count)
+                }
+            },
+            ReadHeader::Attr(_) => ReadObjects::DeviceAttributes,
+        };
+
+        Some(objects)
+    }
+
+    /// same as [`ReadHeader::get`] but without logging unsupported headers
+    pub(crate) fn get_impl(header: &HeaderDetails) -> Option<ReadHeader> {
         match header {
             HeaderDetails::AllObjects(x) => Self::from_all_objects(x),
             HeaderDetails::OneByteCount(count, x) => Self::from_count(x, *count as usize),
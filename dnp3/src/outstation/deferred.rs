@@ -81,18 +81,25 @@ impl DeferredRead {
         self.info = Some(DeferredInfo::new(hash, seq, info, iin2));
     }
 
+    /// headers of the deferred request, empty if no request is deferred
+    pub(crate) fn headers(&self) -> &[ReadHeader] {
+        self.vec.as_slice()
+    }
+
     pub(crate) fn select(&mut self, database: &DatabaseHandle) -> Option<DeferredInfo> {
         match self.info {
             None => None,
             Some(x) => {
-                let iin2 = database.transaction(|db| {
-                    db.inner.reset();
-                    let mut iin2 = Iin2::default();
-                    for header in self.vec.iter() {
-                        iin2 |= db.inner.select_by_header(*header);
-                    }
-                    iin2
-                });
+                let iin2 = database
+                    .member_transaction(|mut db| {
+                        db.reset();
+                        let mut iin2 = Iin2::default();
+                        for header in self.vec.iter() {
+                            iin2 |= db.select_by_header(*header);
+                        }
+                        iin2
+                    })
+                    .unwrap_or_default();
                 self.clear();
                 Some(x.merge(iin2))
             }
@@ -1,20 +1,30 @@
+pub use clock::*;
 pub use config::*;
+pub use operate::*;
 pub use traits::*;
 
+use std::sync::Arc;
+
 use crate::app::Shutdown;
-use crate::decode::DecodeLevel;
+use crate::capture::PcapCapture;
+use crate::decode::{DecodeLevel, DecodeSink};
 use crate::outstation::database::{Database, DatabaseHandle};
 use crate::outstation::task::{ConfigurationChange, OutstationMessage};
+use crate::statistics::OutstationStatistics;
 use crate::util::channel::Sender;
 
 /// database API to add/remove/update values
 pub mod database;
 
+mod clock;
 mod config;
 /// functionality for processing control requests
 pub(crate) mod control;
 /// handling of deferred read requests
 pub(crate) mod deferred;
+mod operate;
+/// replication of the database to a hot-standby outstation
+pub mod replication;
 /// outstation session
 pub(crate) mod session;
 /// async outstation task
@@ -28,6 +38,7 @@ mod tests;
 #[derive(Clone)]
 pub struct OutstationHandle {
     database: DatabaseHandle,
+    clock: Option<OutstationClock>,
     sender: Sender<OutstationMessage>,
 }
 
@@ -37,6 +48,11 @@ impl OutstationHandle {
         self.database.clone()
     }
 
+    /// Get a handle to the clock of the outstation, if one was configured
+    pub fn get_clock(&self) -> Option<OutstationClock> {
+        self.clock.clone()
+    }
+
     /// Acquire a mutex on the underlying database and apply a set of changes as a transaction
     pub fn transaction<F, R>(&self, func: F) -> R
     where
@@ -53,6 +69,65 @@ impl OutstationHandle {
         Ok(())
     }
 
+    /// Start recording the traffic of the outstation to a pcapng capture
+    ///
+    /// Any capture already in progress is stopped and replaced.
+    pub async fn start_capture(&mut self, capture: PcapCapture) -> Result<(), Shutdown> {
+        self.sender
+            .send(ConfigurationChange::SetCapture(Some(capture)).into())
+            .await?;
+        Ok(())
+    }
+
+    /// Stop recording traffic, closing the capture if one is in progress
+    pub async fn stop_capture(&mut self) -> Result<(), Shutdown> {
+        self.sender
+            .send(ConfigurationChange::SetCapture(None).into())
+            .await?;
+        Ok(())
+    }
+
+    /// Set a sink that receives structured decode events in addition to the text log
+    ///
+    /// Events are produced according to the current [`DecodeLevel`]. Passing `None` removes the sink.
+    pub async fn set_decode_sink(
+        &mut self,
+        sink: Option<Arc<dyn DecodeSink>>,
+    ) -> Result<(), Shutdown> {
+        self.sender
+            .send(ConfigurationChange::SetDecodeSink(sink).into())
+            .await?;
+        Ok(())
+    }
+
+    /// Set the handler for WRITE requests of point values
+    ///
+    /// Until a handler is set, these writes are rejected with IIN2.NO_FUNC_CODE_SUPPORT as if the
+    /// handler were [`DefaultWriteHandler`]. Set the handler before enabling the outstation or
+    /// binding the server to ensure that no WRITE arrives before it is installed.
+    pub async fn set_write_handler(
+        &mut self,
+        handler: Box<dyn OutstationWriteHandler>,
+    ) -> Result<(), Shutdown> {
+        self.sender
+            .send(ConfigurationChange::SetWriteHandler(handler).into())
+            .await?;
+        Ok(())
+    }
+
+    /// Get the statistics of the outstation
+    pub async fn get_statistics(&mut self) -> Result<OutstationStatistics, Shutdown> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.sender
+            .send(OutstationMessage::GetStatistics(tx))
+            .await?;
+        let mut statistics = rx.await?;
+        let (high_water_mark, overflows) = self.database.get_event_buffer_statistics();
+        statistics.event_buffer_high_water_mark = high_water_mark;
+        statistics.event_buffer_overflows = overflows;
+        Ok(statistics)
+    }
+
     /// Enable communications
     pub async fn enable(&mut self) -> Result<(), Shutdown> {
         self.sender.send(OutstationMessage::Enable).await?;
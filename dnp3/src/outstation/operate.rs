@@ -0,0 +1,229 @@
+use std::sync::{Arc, Mutex};
+
+use crate::app::control::{CommandStatus, OpType, TripCloseCode};
+use crate::app::measurement::{AnalogCommandValue, Time};
+use crate::app::variations::{Group12Var1, Group41Var1, Group41Var2, Group41Var3, Group41Var4};
+use crate::outstation::database::{DatabaseHandle, UpdateInfo};
+
+/// Result of [`ControlSupport::start_operate`](crate::outstation::ControlSupport::start_operate)
+pub enum OperateResult {
+    /// The operation completed while the request was processed
+    Complete(CommandStatus),
+    /// The operation was accepted and will complete after the response is sent
+    ///
+    /// The outstation responds with `CommandStatus::Success`. Create this variant
+    /// with [`OperateResult::pending`].
+    Pending(PendingOperate),
+}
+
+impl OperateResult {
+    /// Create a pending result along with the handle used to complete the operation
+    pub fn pending() -> (Self, OperateCompletion) {
+        let state = Arc::new(Mutex::new(State::Detached));
+        (
+            Self::Pending(PendingOperate {
+                state: state.clone(),
+            }),
+            OperateCompletion { state },
+        )
+    }
+
+    /// status to respond with, attaching pending operations to the point that was operated
+    pub(crate) fn accept(
+        self,
+        database: &DatabaseHandle,
+        index: u16,
+        command: Command,
+    ) -> CommandStatus {
+        match self {
+            Self::Complete(status) => status,
+            Self::Pending(pending) => {
+                database.begin_operate(index, command);
+                pending.attach(Target {
+                    database: database.clone(),
+                    index,
+                    command,
+                });
+                CommandStatus::Success
+            }
+        }
+    }
+}
+
+/// Operation that was accepted by the control handler but has not completed yet
+///
+/// This type is only used to return [`OperateResult::Pending`] to the outstation.
+pub struct PendingOperate {
+    state: Arc<Mutex<State>>,
+}
+
+/// Handle used to report the final status of an operation accepted with [`OperateResult::Pending`]
+///
+/// Completing the operation records a binary (g13) or analog (g43) output command event with
+/// the final status. The event is assigned the event class of the binary or analog output status
+/// point at the same index, and is only recorded if that point exists and has an event class.
+/// If the operation succeeded, the value of the output status point is updated to the commanded
+/// value, except for pulse commands which leave the output in its previous state.
+///
+/// Dropping the handle without completing the operation reports `CommandStatus::HardwareError`.
+/// Operations on different points may be pending at the same time and may be completed in any
+/// order. While an operation is pending, the outstation responds to further operate requests on
+/// the same point with `CommandStatus::AlreadyActive` without calling the control handler.
+///
+/// The handle may be completed from any task or thread, but not from within a database
+/// transaction as completion acquires the database lock.
+pub struct OperateCompletion {
+    state: Arc<Mutex<State>>,
+}
+
+impl OperateCompletion {
+    /// Complete the operation with its final status and, optionally, the time at which it completed
+    ///
+    /// The command event is reported without a timestamp if `time` is `None`.
+    pub fn complete(self, status: CommandStatus, time: Option<Time>) {
+        self.finish(status, time)
+    }
+
+    fn finish(&self, status: CommandStatus, time: Option<Time>) {
+        let target = {
+            let mut state = self.state.lock().unwrap();
+            match std::mem::replace(&mut *state, State::Done) {
+                // the outstation hasn't received the pending operation yet
+                State::Detached => {
+                    *state = State::Completed(status, time);
+                    return;
+                }
+                State::Attached(target) => target,
+                other => {
+                    *state = other;
+                    return;
+                }
+            }
+        };
+
+        target.complete(status, time);
+    }
+}
+
+impl Drop for OperateCompletion {
+    fn drop(&mut self) {
+        self.finish(CommandStatus::HardwareError, None);
+    }
+}
+
+impl PendingOperate {
+    fn attach(self, target: Target) {
+        let (status, time) = {
+            let mut state = self.state.lock().unwrap();
+            match std::mem::replace(&mut *state, State::Done) {
+                State::Completed(status, time) => (status, time),
+                _ => {
+                    *state = State::Attached(target);
+                    return;
+                }
+            }
+        };
+
+        // completed before the control handler returned
+        target.complete(status, time);
+    }
+}
+
+enum State {
+    Detached,
+    Attached(Target),
+    Completed(CommandStatus, Option<Time>),
+    Done,
+}
+
+struct Target {
+    database: DatabaseHandle,
+    index: u16,
+    command: Command,
+}
+
+impl Target {
+    fn complete(self, status: CommandStatus, time: Option<Time>) {
+        let info = self
+            .database
+            .transaction(|db| db.complete_operate(self.index, self.command, status, time));
+
+        if info == UpdateInfo::NoPoint {
+            tracing::warn!(
+                "no output status at index {} for the command event of a completed operation",
+                self.index
+            );
+        }
+    }
+}
+
+/// value of a control accepted by a pending operation
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) enum Command {
+    /// `latch` is false for pulses, after which the output returns to its previous state
+    Binary {
+        state: bool,
+        latch: bool,
+    },
+    Analog(AnalogCommandValue),
+}
+
+/// type of output operated by a command, which identifies a point along with its index
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum OutputType {
+    Binary,
+    Analog,
+}
+
+impl Command {
+    pub(crate) fn output_type(&self) -> OutputType {
+        match self {
+            Self::Binary { .. } => OutputType::Binary,
+            Self::Analog(_) => OutputType::Analog,
+        }
+    }
+}
+
+impl From<Group12Var1> for Command {
+    fn from(value: Group12Var1) -> Self {
+        let code = value.code;
+        match code.tcc {
+            TripCloseCode::Close => Self::Binary {
+                state: true,
+                latch: true,
+            },
+            TripCloseCode::Trip => Self::Binary {
+                state: false,
+                latch: true,
+            },
+            _ => Self::Binary {
+                state: matches!(code.op_type, OpType::LatchOn | OpType::PulseOn),
+                latch: matches!(code.op_type, OpType::LatchOn | OpType::LatchOff),
+            },
+        }
+    }
+}
+
+impl From<Group41Var1> for Command {
+    fn from(value: Group41Var1) -> Self {
+        Self::Analog(AnalogCommandValue::I32(value.value))
+    }
+}
+
+impl From<Group41Var2> for Command {
+    fn from(value: Group41Var2) -> Self {
+        Self::Analog(AnalogCommandValue::I16(value.value))
+    }
+}
+
+impl From<Group41Var3> for Command {
+    fn from(value: Group41Var3) -> Self {
+        Self::Analog(AnalogCommandValue::F32(value.value))
+    }
+}
+
+impl From<Group41Var4> for Command {
+    fn from(value: Group41Var4) -> Self {
+        Self::Analog(AnalogCommandValue::F64(value.value))
+    }
+}
@@ -0,0 +1,437 @@
+use scursor::ReadCursor;
+
+use crate::app::measurement::*;
+use crate::app::Timestamp;
+use crate::outstation::database::{EventClass, EventMode, UpdateInfo, UpdateOptions};
+use crate::outstation::replication::{
+    BufferedEvent, Message, PointState, PointUpdate, PointValue, ReplicationError, Snapshot,
+};
+
+mod tags {
+    pub(super) const SNAPSHOT: u8 = 0;
+    pub(super) const UPDATE: u8 = 1;
+    pub(super) const ANALOG_DEADBAND: u8 = 2;
+    pub(super) const EVENTS_CLEARED: u8 = 3;
+}
+
+pub(crate) fn encode(msg: &Message, buffer: &mut Vec<u8>) {
+    let mut writer = Writer { buffer };
+    match msg {
+        Message::Snapshot(x) => {
+            writer.u8(tags::SNAPSHOT);
+            writer.snapshot(x);
+        }
+        Message::Update(x) => {
+            writer.u8(tags::UPDATE);
+            writer.update(x);
+        }
+        Message::AnalogDeadband(index, deadband) => {
+            writer.u8(tags::ANALOG_DEADBAND);
+            writer.u16(*index);
+            writer.f64(*deadband);
+        }
+        Message::EventsCleared(ids) => {
+            writer.u8(tags::EVENTS_CLEARED);
+            writer.u32(ids.len() as u32);
+            for id in ids {
+                writer.u64(*id);
+            }
+        }
+    }
+}
+
+pub(crate) fn decode(data: &[u8]) -> Result<Message, ReplicationError> {
+    let mut cursor = ReadCursor::new(data);
+    let msg = match cursor.read_u8()? {
+        tags::SNAPSHOT => Message::Snapshot(read_snapshot(&mut cursor)?),
+        tags::UPDATE => Message::Update(read_update(&mut cursor)?),
+        tags::ANALOG_DEADBAND => {
+            Message::AnalogDeadband(cursor.read_u16_le()?, cursor.read_f64_le()?)
+        }
+        tags::EVENTS_CLEARED => {
+            let count = cursor.read_u32_le()?;
+            let mut ids = Vec::new();
+            for _ in 0..count {
+                ids.push(cursor.read_u64_le()?);
+            }
+            Message::EventsCleared(ids)
+        }
+        _ => return Err(ReplicationError::BadEncoding),
+    };
+    cursor.expect_empty()?;
+    Ok(msg)
+}
+
+struct Writer<'a> {
+    buffer: &'a mut Vec<u8>,
+}
+
+impl<'a> Writer<'a> {
+    fn u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    fn u16(&mut self, value: u16) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u32(&mut self, value: u32) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn f64(&mut self, value: f64) {
+        self.buffer.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn time(&mut self, time: Option<Time>) {
+        match time {
+            None => self.u8(0),
+            Some(Time::Synchronized(ts)) => {
+                self.u8(1);
+                self.u64(ts.raw_value());
+            }
+            Some(Time::Unsynchronized(ts)) => {
+                self.u8(2);
+                self.u64(ts.raw_value());
+            }
+        }
+    }
+
+    fn common(&mut self, flags: Flags, time: Option<Time>) {
+        self.u8(flags.value);
+        self.time(time);
+    }
+
+    fn value(&mut self, value: &PointValue) {
+        match value {
+            PointValue::BinaryInput(x) => {
+                self.u8(0);
+                self.bool(x.value);
+                self.common(x.flags, x.time);
+            }
+            PointValue::DoubleBitBinaryInput(x) => {
+                self.u8(1);
+                self.u8(x.value.to_byte());
+                self.common(x.flags, x.time);
+            }
+            PointValue::BinaryOutputStatus(x) => {
+                self.u8(2);
+                self.bool(x.value);
+                self.common(x.flags, x.time);
+            }
+            PointValue::Counter(x) => {
+                self.u8(3);
+                self.u32(x.value);
+                self.common(x.flags, x.time);
+            }
+            PointValue::FrozenCounter(x) => {
+                self.u8(4);
+                self.u32(x.value);
+                self.common(x.flags, x.time);
+            }
+            PointValue::AnalogInput(x) => {
+                self.u8(5);
+                self.f64(x.value);
+                self.common(x.flags, x.time);
+            }
+            PointValue::AnalogOutputStatus(x) => {
+                self.u8(6);
+                self.f64(x.value);
+                self.common(x.flags, x.time);
+            }
+            PointValue::OctetString(x) => {
+                self.u8(7);
+                self.u8(x.len());
+                self.buffer.extend_from_slice(x.value());
+            }
+        }
+    }
+
+    fn class(&mut self, class: EventClass) {
+        self.u8(match class {
+            EventClass::Class1 => 1,
+            EventClass::Class2 => 2,
+            EventClass::Class3 => 3,
+        });
+    }
+
+    fn update(&mut self, update: &PointUpdate) {
+        self.u16(update.index);
+        self.value(&update.value);
+        self.bool(update.options.update_static);
+        self.u8(match update.options.event_mode {
+            EventMode::Detect => 0,
+            EventMode::Force => 1,
+            EventMode::Suppress => 2,
+        });
+        match update.result {
+            UpdateInfo::NoPoint => self.u8(0),
+            UpdateInfo::NoEvent => self.u8(1),
+            UpdateInfo::Created(id) => {
+                self.u8(2);
+                self.u64(id);
+            }
+            UpdateInfo::Overflow { created, discarded } => {
+                self.u8(3);
+                self.u64(created);
+                self.u64(discarded);
+            }
+        }
+    }
+
+    fn snapshot(&mut self, snapshot: &Snapshot) {
+        self.u64(snapshot.next_event_id);
+        self.bool(snapshot.is_overflown);
+        self.u32(snapshot.points.len() as u32);
+        for point in snapshot.points.iter() {
+            self.u16(point.index);
+            self.value(&point.current);
+            self.value(&point.last_event);
+        }
+        self.u32(snapshot.deadbands.len() as u32);
+        for (index, deadband) in snapshot.deadbands.iter() {
+            self.u16(*index);
+            self.f64(*deadband);
+        }
+        self.u32(snapshot.events.len() as u32);
+        for event in snapshot.events.iter() {
+            self.u64(event.id);
+            self.u16(event.index);
+            self.class(event.class);
+            self.value(&event.value);
+        }
+    }
+}
+
+fn read_bool(cursor: &mut ReadCursor) -> Result<bool, ReplicationError> {
+    match cursor.read_u8()? {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(ReplicationError::BadEncoding),
+    }
+}
+
+fn read_time(cursor: &mut ReadCursor) -> Result<Option<Time>, ReplicationError> {
+    match cursor.read_u8()? {
+        0 => Ok(None),
+        1 => Ok(Some(Time::Synchronized(Timestamp::new(
+            cursor.read_u64_le()?,
+        )))),
+        2 => Ok(Some(Time::Unsynchronized(Timestamp::new(
+            cursor.read_u64_le()?,
+        )))),
+        _ => Err(ReplicationError::BadEncoding),
+    }
+}
+
+fn read_common(cursor: &mut ReadCursor) -> Result<(Flags, Option<Time>), ReplicationError> {
+    let flags = Flags::new(cursor.read_u8()?);
+    let time = read_time(cursor)?;
+    Ok((flags, time))
+}
+
+fn read_value(cursor: &mut ReadCursor) -> Result<PointValue, ReplicationError> {
+    let value = match cursor.read_u8()? {
+        0 => {
+            let value = read_bool(cursor)?;
+            let (flags, time) = read_common(cursor)?;
+            PointValue::BinaryInput(BinaryInput { value, flags, time })
+        }
+        1 => {
+            let bits = cursor.read_u8()?;
+            if bits > 0b11 {
+                return Err(ReplicationError::BadEncoding);
+            }
+            let value = DoubleBit::from(bits & 0b10 != 0, bits & 0b01 != 0);
+            let (flags, time) = read_common(cursor)?;
+            PointValue::DoubleBitBinaryInput(DoubleBitBinaryInput { value, flags, time })
+        }
+        2 => {
+            let value = read_bool(cursor)?;
+            let (flags, time) = read_common(cursor)?;
+            PointValue::BinaryOutputStatus(BinaryOutputStatus { value, flags, time })
+        }
+        3 => {
+            let value = cursor.read_u32_le()?;
+            let (flags, time) = read_common(cursor)?;
+            PointValue::Counter(Counter { value, flags, time })
+        }
+        4 => {
+            let value = cursor.read_u32_le()?;
+            let (flags, time) = read_common(cursor)?;
+            PointValue::FrozenCounter(FrozenCounter { value, flags, time })
+        }
+        5 => {
+            let value = cursor.read_f64_le()?;
+            let (flags, time) = read_common(cursor)?;
+            PointValue::AnalogInput(AnalogInput { value, flags, time })
+        }
+        6 => {
+            let value = cursor.read_f64_le()?;
+            let (flags, time) = read_common(cursor)?;
+            PointValue::AnalogOutputStatus(AnalogOutputStatus { value, flags, time })
+        }
+        7 => {
+            let len = cursor.read_u8()?;
+            let bytes = cursor.read_bytes(len as usize)?;
+            PointValue::OctetString(Box::new(
+                OctetString::new(bytes).map_err(|_| ReplicationError::BadEncoding)?,
+            ))
+        }
+        _ => return Err(ReplicationError::BadEncoding),
+    };
+    Ok(value)
+}
+
+fn read_class(cursor: &mut ReadCursor) -> Result<EventClass, ReplicationError> {
+    match cursor.read_u8()? {
+        1 => Ok(EventClass::Class1),
+        2 => Ok(EventClass::Class2),
+        3 => Ok(EventClass::Class3),
+        _ => Err(ReplicationError::BadEncoding),
+    }
+}
+
+fn read_update(cursor: &mut ReadCursor) -> Result<PointUpdate, ReplicationError> {
+    let index = cursor.read_u16_le()?;
+    let value = read_value(cursor)?;
+    let update_static = read_bool(cursor)?;
+    let event_mode = match cursor.read_u8()? {
+        0 => EventMode::Detect,
+        1 => EventMode::Force,
+        2 => EventMode::Suppress,
+        _ => return Err(ReplicationError::BadEncoding),
+    };
+    let result = match cursor.read_u8()? {
+        0 => UpdateInfo::NoPoint,
+        1 => UpdateInfo::NoEvent,
+        2 => UpdateInfo::Created(cursor.read_u64_le()?),
+        3 => UpdateInfo::Overflow {
+            created: cursor.read_u64_le()?,
+            discarded: cursor.read_u64_le()?,
+        },
+        _ => return Err(ReplicationError::BadEncoding),
+    };
+    Ok(PointUpdate {
+        index,
+        value,
+        options: UpdateOptions::new(update_static, event_mode),
+        result,
+    })
+}
+
+fn read_snapshot(cursor: &mut ReadCursor) -> Result<Snapshot, ReplicationError> {
+    let next_event_id = cursor.read_u64_le()?;
+    let is_overflown = read_bool(cursor)?;
+
+    let mut points = Vec::new();
+    for _ in 0..cursor.read_u32_le()? {
+        points.push(PointState {
+            index: cursor.read_u16_le()?,
+            current: read_value(cursor)?,
+            last_event: read_value(cursor)?,
+        });
+    }
+
+    let mut deadbands = Vec::new();
+    for _ in 0..cursor.read_u32_le()? {
+        deadbands.push((cursor.read_u16_le()?, cursor.read_f64_le()?));
+    }
+
+    let mut events = Vec::new();
+    for _ in 0..cursor.read_u32_le()? {
+        events.push(BufferedEvent {
+            id: cursor.read_u64_le()?,
+            index: cursor.read_u16_le()?,
+            class: read_class(cursor)?,
+            value: read_value(cursor)?,
+        });
+    }
+
+    Ok(Snapshot {
+        points,
+        deadbands,
+        events,
+        next_event_id,
+        is_overflown,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn roundtrip(msg: Message) {
+        let mut buffer = Vec::new();
+        encode(&msg, &mut buffer);
+        assert_eq!(decode(&buffer), Ok(msg));
+    }
+
+    #[test]
+    fn can_roundtrip_updates() {
+        roundtrip(Message::Update(PointUpdate {
+            index: 7,
+            value: PointValue::DoubleBitBinaryInput(DoubleBitBinaryInput::new(
+                DoubleBit::DeterminedOn,
+                Flags::ONLINE,
+                Time::synchronized(42),
+            )),
+            options: UpdateOptions::new(false, EventMode::Force),
+            result: UpdateInfo::Overflow {
+                created: 12,
+                discarded: 3,
+            },
+        }));
+        roundtrip(Message::AnalogDeadband(3, 1.5));
+        roundtrip(Message::EventsCleared(vec![1, 2, 5]));
+    }
+
+    #[test]
+    fn can_roundtrip_snapshot() {
+        roundtrip(Message::Snapshot(Snapshot {
+            points: vec![
+                PointState {
+                    index: 1,
+                    current: PointValue::AnalogInput(AnalogInput {
+                        value: 3.5,
+                        flags: Flags::ONLINE,
+                        time: None,
+                    }),
+                    last_event: PointValue::AnalogInput(AnalogInput::default()),
+                },
+                PointState {
+                    index: 2,
+                    current: PointValue::OctetString(Box::new(
+                        OctetString::new(&[1, 2, 3]).unwrap(),
+                    )),
+                    last_event: PointValue::OctetString(Box::default()),
+                },
+            ],
+            deadbands: vec![(1, 0.5)],
+            events: vec![BufferedEvent {
+                id: 4,
+                index: 0,
+                class: EventClass::Class2,
+                value: PointValue::Counter(Counter::new(7, Flags::ONLINE, Time::unsynchronized(9))),
+            }],
+            next_event_id: 5,
+            is_overflown: true,
+        }));
+    }
+
+    #[test]
+    fn rejects_trailing_bytes() {
+        let mut buffer = Vec::new();
+        encode(&Message::EventsCleared(vec![1]), &mut buffer);
+        buffer.push(0);
+        assert_eq!(decode(&buffer), Err(ReplicationError::BadEncoding));
+    }
+}
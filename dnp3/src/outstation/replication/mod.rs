@@ -0,0 +1,264 @@
+use crate::app::measurement::*;
+use crate::outstation::database::{EventClass, UpdateInfo, UpdateOptions};
+
+pub use tcp::*;
+
+/// binary encoding of replication messages
+mod codec;
+/// replication over a local TCP link
+mod tcp;
+
+/// Message produced by an active outstation database and applied to a standby database
+/// using [`DatabaseHandle::apply_replication`](crate::outstation::database::DatabaseHandle::apply_replication).
+///
+/// The content of the message is opaque. Messages may be transferred between processes using
+/// [`ReplicationMessage::encode`] and [`ReplicationMessage::decode`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplicationMessage {
+    pub(crate) inner: Message,
+}
+
+impl ReplicationMessage {
+    pub(crate) fn new(inner: Message) -> Self {
+        Self { inner }
+    }
+
+    /// Returns true if the message is a full snapshot of the active database
+    ///
+    /// Snapshots are always the first message produced after replication is started
+    pub fn is_snapshot(&self) -> bool {
+        matches!(self.inner, Message::Snapshot(_))
+    }
+
+    /// Append the binary encoding of the message to a buffer
+    pub fn encode(&self, buffer: &mut Vec<u8>) {
+        codec::encode(&self.inner, buffer)
+    }
+
+    /// Decode a message that was previously encoded using [`ReplicationMessage::encode`]
+    pub fn decode(data: &[u8]) -> Result<Self, ReplicationError> {
+        Ok(Self::new(codec::decode(data)?))
+    }
+}
+
+/// User-supplied channel over which the active database publishes [`ReplicationMessage`]
+///
+/// The sink is invoked while the database lock is held, so implementations must not
+/// block. Typically the message is just queued for transmission by another task.
+pub trait ReplicationSink: Send {
+    /// Publish a message to the standby
+    fn replicate(&mut self, message: ReplicationMessage);
+}
+
+impl ReplicationSink for tokio::sync::mpsc::UnboundedSender<ReplicationMessage> {
+    fn replicate(&mut self, message: ReplicationMessage) {
+        // receiver is gone, replication will be stopped by whoever owns it
+        let _ = self.send(message);
+    }
+}
+
+/// Errors that can occur when applying a [`ReplicationMessage`] to a standby database
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReplicationError {
+    /// The message could not be decoded
+    BadEncoding,
+    /// The message references a point that does not exist in the standby database
+    UnknownPoint,
+    /// Applying the message produced a different result in the standby database than it did
+    /// in the active database. The standby must be re-synchronized with a new snapshot.
+    OutOfSync,
+}
+
+impl std::fmt::Display for ReplicationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplicationError::BadEncoding => {
+                f.write_str("replication message could not be decoded")
+            }
+            ReplicationError::UnknownPoint => {
+                f.write_str("replication message references a point that does not exist")
+            }
+            ReplicationError::OutOfSync => {
+                f.write_str("standby database is out of sync with the active database")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplicationError {}
+
+impl From<scursor::ReadError> for ReplicationError {
+    fn from(_: scursor::ReadError) -> Self {
+        ReplicationError::BadEncoding
+    }
+}
+
+impl From<scursor::TrailingBytes> for ReplicationError {
+    fn from(_: scursor::TrailingBytes) -> Self {
+        ReplicationError::BadEncoding
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Message {
+    Snapshot(Snapshot),
+    Update(PointUpdate),
+    AnalogDeadband(u16, f64),
+    EventsCleared(Vec<u64>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum PointValue {
+    BinaryInput(BinaryInput),
+    DoubleBitBinaryInput(DoubleBitBinaryInput),
+    BinaryOutputStatus(BinaryOutputStatus),
+    Counter(Counter),
+    FrozenCounter(FrozenCounter),
+    AnalogInput(AnalogInput),
+    AnalogOutputStatus(AnalogOutputStatus),
+    OctetString(Box<OctetString>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PointUpdate {
+    pub(crate) index: u16,
+    pub(crate) value: PointValue,
+    pub(crate) options: UpdateOptions,
+    /// result of the update in the active database
+    pub(crate) result: UpdateInfo,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PointState {
+    pub(crate) index: u16,
+    pub(crate) current: PointValue,
+    pub(crate) last_event: PointValue,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct BufferedEvent {
+    pub(crate) id: u64,
+    pub(crate) index: u16,
+    pub(crate) class: EventClass,
+    pub(crate) value: PointValue,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Snapshot {
+    pub(crate) points: Vec<PointState>,
+    pub(crate) deadbands: Vec<(u16, f64)>,
+    pub(crate) events: Vec<BufferedEvent>,
+    pub(crate) next_event_id: u64,
+    pub(crate) is_overflown: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::master::EventClasses;
+    use crate::outstation::database::*;
+    use crate::outstation::OutstationApplication;
+    use scursor::WriteCursor;
+
+    struct NullApplication;
+
+    impl OutstationApplication for NullApplication {}
+
+    fn database() -> DatabaseHandle {
+        let db = DatabaseHandle::new(
+            None,
+            ClassZeroConfig::default(),
+            EventBufferConfig::all_types(3),
+        );
+        db.transaction(|db| {
+            db.add(0, Some(EventClass::Class1), BinaryInputConfig::default());
+            db.add(0, Some(EventClass::Class2), AnalogInputConfig::default());
+        });
+        db
+    }
+
+    fn snapshot(db: &DatabaseHandle) -> Snapshot {
+        db.snapshot().unwrap()
+    }
+
+    fn forward(
+        rx: &mut tokio::sync::mpsc::UnboundedReceiver<ReplicationMessage>,
+        standby: &DatabaseHandle,
+    ) {
+        while let Ok(msg) = rx.try_recv() {
+            // exercise the codec as well
+            let mut buffer = Vec::new();
+            msg.encode(&mut buffer);
+            let msg = ReplicationMessage::decode(&buffer).unwrap();
+            standby.apply_replication(msg).unwrap();
+        }
+    }
+
+    fn binary(value: bool) -> BinaryInput {
+        BinaryInput::new(value, Flags::ONLINE, Time::synchronized(0))
+    }
+
+    #[tokio::test]
+    async fn standby_tracks_events_and_confirmations() {
+        let mut active = database();
+        let standby = database();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        // state that exists before replication starts is transferred in the snapshot
+        active.transaction(|db| db.update(0, &binary(true), UpdateOptions::detect_event()));
+        active.start_replication(Box::new(tx));
+        let msg = rx.try_recv().unwrap();
+        assert!(msg.is_snapshot());
+        standby.apply_replication(msg).unwrap();
+        assert_eq!(snapshot(&active), snapshot(&standby));
+
+        // subsequent updates are replayed with the same event ids
+        active.transaction(|db| {
+            db.update(0, &binary(false), UpdateOptions::detect_event());
+            db.update(
+                0,
+                &AnalogInput::new(42.0, Flags::ONLINE, Time::synchronized(1)),
+                UpdateOptions::detect_event(),
+            );
+        });
+        forward(&mut rx, &standby);
+        assert_eq!(snapshot(&active), snapshot(&standby));
+
+        // confirm only the class 1 events
+        let mut buffer = [0u8; 2048];
+        let mut cursor = WriteCursor::new(&mut buffer);
+        assert_eq!(
+            active.write_unsolicited(EventClasses::new(true, false, false), &mut cursor),
+            2
+        );
+        active.clear_written_events(&mut NullApplication).await;
+        forward(&mut rx, &standby);
+
+        let state = snapshot(&standby);
+        assert_eq!(state, snapshot(&active));
+        assert_eq!(
+            state.events.iter().map(|x| x.id).collect::<Vec<u64>>(),
+            vec![2]
+        );
+        assert_eq!(state.next_event_id, 3);
+    }
+
+    #[test]
+    fn reports_updates_that_diverge() {
+        let active = database();
+        let standby = database();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        active.start_replication(Box::new(tx));
+        // the snapshot is lost and the standby is modified locally
+        let _ = rx.try_recv();
+        standby.transaction(|db| db.update(0, &binary(true), UpdateOptions::detect_event()));
+
+        active.transaction(|db| db.update(0, &binary(true), UpdateOptions::detect_event()));
+        let msg = rx.try_recv().unwrap();
+        assert_eq!(
+            standby.apply_replication(msg),
+            Err(ReplicationError::OutOfSync)
+        );
+    }
+}
@@ -0,0 +1,667 @@
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+
+use crate::decode::PhysDecodeLevel;
+use crate::outstation::database::DatabaseHandle;
+use crate::outstation::replication::{ReplicationMessage, ReplicationSink};
+use crate::tcp::{AddressFilter, ServerHandle};
+use crate::util::monitor::ChannelMonitor;
+use crate::util::phys::{PhysAddr, PhysLayer};
+use crate::util::shutdown::{shutdown_token, ShutdownListener, ShutdownToken};
+
+/// Maximum size of an encoded replication message accepted by the receiver
+const MAX_MESSAGE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Handle to a task spawned with [`spawn_replication_sender`]
+///
+/// The task is stopped when the handle is dropped.
+pub struct ReplicationSenderHandle {
+    _token: ShutdownToken,
+}
+
+/// Spawn a task that replicates `database` to a standby outstation listening at `peer`
+///
+/// The task connects to a receiver spawned with [`spawn_replication_receiver`] and streams
+/// every change to the database over the connection. A fresh snapshot is sent each time the
+/// connection is (re)established. If the connection fails, replication is stopped and the
+/// task reconnects after `retry_delay`.
+///
+/// At most `queue_size` messages are queued for transmission. If the connection cannot keep up
+/// and the queue overflows, the queued messages are discarded and the standby is re-synchronized
+/// with a new snapshot.
+///
+/// Messages are sent in the clear and without authentication. Use
+/// [`spawn_replication_sender_tls`] unless both outstations share a trusted network.
+///
+/// This function must be called from within a Tokio runtime.
+pub fn spawn_replication_sender(
+    database: DatabaseHandle,
+    peer: SocketAddr,
+    retry_delay: Duration,
+    queue_size: usize,
+) -> ReplicationSenderHandle {
+    spawn_sender(database, peer, retry_delay, queue_size, Security::None)
+}
+
+/// Just like [`spawn_replication_sender`], but the connection to the receiver is secured
+/// using TLS. The receiver must be spawned with [`spawn_replication_receiver_tls`].
+///
+/// This function must be called from within a Tokio runtime.
+#[cfg(feature = "enable-tls")]
+pub fn spawn_replication_sender_tls(
+    database: DatabaseHandle,
+    peer: SocketAddr,
+    retry_delay: Duration,
+    queue_size: usize,
+    tls_config: crate::tcp::tls::TlsClientConfig,
+) -> ReplicationSenderHandle {
+    spawn_sender(
+        database,
+        peer,
+        retry_delay,
+        queue_size,
+        Security::TlsClient(tls_config),
+    )
+}
+
+/// Bind a listener at `addr` that applies replication messages received from an active
+/// outstation to the standby `database`
+///
+/// Connections from addresses that do not match `filter` are closed immediately. Only one
+/// connection is serviced at a time, and a new connection replaces the current one so that a
+/// stalled peer cannot prevent the active outstation from reconnecting. If a message cannot be
+/// applied, the connection is closed so that the sender reconnects and re-synchronizes the
+/// database. The standby is promoted simply by dropping the returned handle and enabling the
+/// outstation.
+///
+/// **Warning**: any peer allowed by `filter` can overwrite the contents of the standby database.
+/// The messages are neither encrypted nor authenticated, so the filter is the only protection
+/// against a malicious peer. Use [`spawn_replication_receiver_tls`] unless both outstations
+/// share a trusted network.
+///
+/// This function must be called from within a Tokio runtime.
+pub async fn spawn_replication_receiver(
+    database: DatabaseHandle,
+    addr: SocketAddr,
+    filter: AddressFilter,
+) -> Result<ServerHandle, tokio::io::Error> {
+    spawn_receiver(database, addr, filter, Security::None).await
+}
+
+/// Just like [`spawn_replication_receiver`], but connections are secured using TLS and the
+/// sender must present a certificate accepted by `tls_config`
+///
+/// This function must be called from within a Tokio runtime.
+#[cfg(feature = "enable-tls")]
+pub async fn spawn_replication_receiver_tls(
+    database: DatabaseHandle,
+    addr: SocketAddr,
+    filter: AddressFilter,
+    tls_config: crate::tcp::tls::TlsServerConfig,
+) -> Result<ServerHandle, tokio::io::Error> {
+    spawn_receiver(database, addr, filter, Security::TlsServer(tls_config)).await
+}
+
+/// How the connection between the sender and the receiver is secured
+#[derive(Clone)]
+enum Security {
+    None,
+    #[cfg(feature = "enable-tls")]
+    TlsClient(crate::tcp::tls::TlsClientConfig),
+    #[cfg(feature = "enable-tls")]
+    TlsServer(crate::tcp::tls::TlsServerConfig),
+}
+
+fn spawn_sender(
+    database: DatabaseHandle,
+    peer: SocketAddr,
+    retry_delay: Duration,
+    queue_size: usize,
+    security: Security,
+) -> ReplicationSenderHandle {
+    let (token, listener) = shutdown_token();
+    let task = Sender {
+        database,
+        peer,
+        retry_delay,
+        queue_size: queue_size.max(1),
+        security,
+        monitor: ChannelMonitor::default(),
+    };
+    tokio::spawn(
+        task.run(listener)
+            .instrument(tracing::info_span!("dnp3-replication-sender", "peer" = ?peer)),
+    );
+    ReplicationSenderHandle { _token: token }
+}
+
+async fn spawn_receiver(
+    database: DatabaseHandle,
+    addr: SocketAddr,
+    filter: AddressFilter,
+    security: Security,
+) -> Result<ServerHandle, tokio::io::Error> {
+    let listener = TcpListener::bind(addr).await?;
+    let local = listener.local_addr().ok();
+    let (token, shutdown) = shutdown_token();
+    let receiver = Receiver {
+        database,
+        filter,
+        security,
+        monitor: ChannelMonitor::default(),
+        current: None,
+    };
+    tokio::spawn(
+        receiver
+            .run(listener, shutdown)
+            .instrument(tracing::info_span!("dnp3-replication-receiver", "listen" = ?addr)),
+    );
+    Ok(ServerHandle {
+        addr: local,
+        _token: token,
+        counters: Default::default(),
+    })
+}
+
+/// Sink installed in the active database that queues messages for the sender task
+struct QueueSink {
+    tx: Option<tokio::sync::mpsc::Sender<ReplicationMessage>>,
+    overflow: Arc<AtomicBool>,
+}
+
+impl ReplicationSink for QueueSink {
+    fn replicate(&mut self, message: ReplicationMessage) {
+        if let Some(tx) = &self.tx {
+            if let Err(TrySendError::Full(_)) = tx.try_send(message) {
+                // every subsequent message is discarded until the sender re-synchronizes
+                self.overflow.store(true, Ordering::Relaxed);
+                self.tx = None;
+            }
+        }
+    }
+}
+
+/// Result of forwarding queued messages over a connection
+enum Forwarded {
+    /// replication was stopped or replaced by the user
+    Stopped,
+    /// the queue overflowed and the standby must be re-synchronized
+    Overflow,
+}
+
+struct Sender {
+    database: DatabaseHandle,
+    peer: SocketAddr,
+    retry_delay: Duration,
+    queue_size: usize,
+    security: Security,
+    monitor: ChannelMonitor,
+}
+
+impl Sender {
+    async fn run(mut self, mut shutdown: ShutdownListener) {
+        tokio::select! {
+            _ = self.run_forever() => {}
+            _ = shutdown.listen() => {}
+        }
+        self.database.stop_replication();
+        tracing::info!("shutdown");
+    }
+
+    async fn run_forever(&mut self) {
+        loop {
+            if let Some(mut phys) = self.connect().await {
+                tracing::info!("connected");
+                if let Err(err) = self.run_connection(&mut phys).await {
+                    tracing::warn!("connection closed: {}", err);
+                }
+                self.database.stop_replication();
+            }
+            tokio::time::sleep(self.retry_delay).await;
+        }
+    }
+
+    async fn connect(&mut self) -> Option<PhysLayer> {
+        let socket = match TcpStream::connect(self.peer).await {
+            Ok(x) => x,
+            Err(err) => {
+                tracing::warn!("unable to connect: {}", err);
+                return None;
+            }
+        };
+        match &mut self.security {
+            #[cfg(feature = "enable-tls")]
+            Security::TlsClient(config) => config.handle_connection(socket, &self.peer).await,
+            _ => Some(PhysLayer::Tcp(socket)),
+        }
+    }
+
+    async fn run_connection(&self, phys: &mut PhysLayer) -> Result<(), tokio::io::Error> {
+        loop {
+            let overflow = Arc::new(AtomicBool::new(false));
+            let (tx, mut rx) = tokio::sync::mpsc::channel(self.queue_size);
+            self.database.start_replication(Box::new(QueueSink {
+                tx: Some(tx),
+                overflow: overflow.clone(),
+            }));
+
+            match self.forward(&mut rx, &overflow, phys).await? {
+                Forwarded::Stopped => return Ok(()),
+                Forwarded::Overflow => {
+                    tracing::warn!("replication queue overflow, re-synchronizing the standby");
+                }
+            }
+        }
+    }
+
+    async fn forward(
+        &self,
+        rx: &mut tokio::sync::mpsc::Receiver<ReplicationMessage>,
+        overflow: &AtomicBool,
+        phys: &mut PhysLayer,
+    ) -> Result<Forwarded, tokio::io::Error> {
+        let mut buffer = Vec::new();
+        let mut discard = [0u8; 64];
+        loop {
+            tokio::select! {
+                msg = rx.recv() => {
+                    // the queued messages are incomplete, don't bother sending them
+                    if overflow.load(Ordering::Relaxed) {
+                        return Ok(Forwarded::Overflow);
+                    }
+                    let msg = match msg {
+                        Some(x) => x,
+                        None => return Ok(Forwarded::Stopped),
+                    };
+                    buffer.clear();
+                    write_frame(&msg, &mut buffer);
+                    phys.write(&buffer, PhysAddr::None, PhysDecodeLevel::Nothing, &self.monitor).await?;
+                }
+                res = phys.read(&mut discard, PhysDecodeLevel::Nothing, &self.monitor) => {
+                    // the receiver never writes, so this only detects a closed connection
+                    res?;
+                }
+            }
+        }
+    }
+}
+
+fn write_frame(msg: &ReplicationMessage, buffer: &mut Vec<u8>) {
+    // reserve space for the length
+    buffer.extend_from_slice(&[0; 4]);
+    msg.encode(buffer);
+    let length = (buffer.len() - 4) as u32;
+    buffer[0..4].copy_from_slice(&length.to_le_bytes());
+}
+
+struct Receiver {
+    database: DatabaseHandle,
+    filter: AddressFilter,
+    security: Security,
+    monitor: ChannelMonitor,
+    /// task servicing the current connection
+    current: Option<JoinHandle<()>>,
+}
+
+impl Receiver {
+    async fn run(mut self, listener: TcpListener, mut shutdown: ShutdownListener) {
+        tokio::select! {
+            _ = self.accept_loop(&listener) => {}
+            _ = shutdown.listen() => {}
+        }
+        if let Some(current) = self.current.take() {
+            current.abort();
+        }
+        tracing::info!("shutdown");
+    }
+
+    async fn accept_loop(&mut self, listener: &TcpListener) {
+        loop {
+            match listener.accept().await {
+                Err(err) => {
+                    tracing::error!("{}", err);
+                    return;
+                }
+                Ok((stream, addr)) => {
+                    if !self.filter.matches(addr.ip()) {
+                        tracing::warn!("rejected connection from: {} (address filter)", addr);
+                        continue;
+                    }
+                    // a stalled or half-open connection must not prevent the active outstation
+                    // from reconnecting, so the newest connection always replaces the current one
+                    if let Some(current) = self.current.take() {
+                        if !current.is_finished() {
+                            tracing::info!("closing current connection");
+                        }
+                        current.abort();
+                    }
+                    tracing::info!("accepted connection from: {}", addr);
+                    let connection = Connection {
+                        database: self.database.clone(),
+                        monitor: self.monitor.clone(),
+                    };
+                    let security = self.security.clone();
+                    self.current = Some(tokio::spawn(
+                        async move {
+                            if let Err(err) = connection.run(security, stream, addr).await {
+                                tracing::warn!("connection closed: {}", err);
+                            }
+                        }
+                        .instrument(tracing::info_span!("connection", "remote" = ?addr)),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Connection from a sender that is serviced by its own task
+struct Connection {
+    database: DatabaseHandle,
+    monitor: ChannelMonitor,
+}
+
+impl Connection {
+    #[cfg_attr(not(feature = "enable-tls"), allow(unused_variables, unused_mut))]
+    async fn run(
+        &self,
+        mut security: Security,
+        stream: TcpStream,
+        addr: SocketAddr,
+    ) -> Result<(), ReceiveError> {
+        let mut phys = match &mut security {
+            #[cfg(feature = "enable-tls")]
+            Security::TlsServer(config) => config
+                .handle_connection(stream, addr)
+                .await
+                .map_err(ReceiveError::Handshake)?,
+            _ => PhysLayer::Tcp(stream),
+        };
+        self.receive(&mut phys).await
+    }
+
+    async fn receive(&self, phys: &mut PhysLayer) -> Result<(), ReceiveError> {
+        let mut header = [0u8; 4];
+        let mut buffer = Vec::new();
+        loop {
+            self.read_exact(phys, &mut header).await?;
+            let length = u32::from_le_bytes(header) as usize;
+            if length > MAX_MESSAGE_SIZE {
+                return Err(ReceiveError::TooLarge(length));
+            }
+            buffer.resize(length, 0);
+            self.read_exact(phys, &mut buffer).await?;
+            let msg = ReplicationMessage::decode(&buffer)?;
+            self.database.apply_replication(msg)?;
+        }
+    }
+
+    async fn read_exact(
+        &self,
+        phys: &mut PhysLayer,
+        buffer: &mut [u8],
+    ) -> Result<(), tokio::io::Error> {
+        let mut pos = 0;
+        while let Some(remaining) = buffer.get_mut(pos..) {
+            if remaining.is_empty() {
+                break;
+            }
+            let (count, _) = phys
+                .read(remaining, PhysDecodeLevel::Nothing, &self.monitor)
+                .await?;
+            pos += count;
+        }
+        Ok(())
+    }
+}
+
+enum ReceiveError {
+    #[cfg(feature = "enable-tls")]
+    Handshake(String),
+    Io(tokio::io::Error),
+    TooLarge(usize),
+    Replication(crate::outstation::replication::ReplicationError),
+}
+
+impl std::fmt::Display for ReceiveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            #[cfg(feature = "enable-tls")]
+            ReceiveError::Handshake(err) => write!(f, "{err}"),
+            ReceiveError::Io(err) => write!(f, "{err}"),
+            ReceiveError::TooLarge(size) => write!(f, "message length of {size} is too large"),
+            ReceiveError::Replication(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl From<tokio::io::Error> for ReceiveError {
+    fn from(err: tokio::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<crate::outstation::replication::ReplicationError> for ReceiveError {
+    fn from(err: crate::outstation::replication::ReplicationError) -> Self {
+        Self::Replication(err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::measurement::*;
+    use crate::outstation::database::*;
+    use crate::outstation::replication::Snapshot;
+
+    fn database() -> DatabaseHandle {
+        let db = DatabaseHandle::new(
+            None,
+            ClassZeroConfig::default(),
+            EventBufferConfig::all_types(3),
+        );
+        db.transaction(|db| {
+            db.add(0, Some(EventClass::Class1), BinaryInputConfig::default());
+        });
+        db
+    }
+
+    fn snapshot(db: &DatabaseHandle) -> Snapshot {
+        db.snapshot().unwrap()
+    }
+
+    fn update(db: &DatabaseHandle, value: bool) {
+        db.transaction(|db| {
+            db.update(
+                0,
+                &BinaryInput::new(value, Flags::ONLINE, Time::synchronized(0)),
+                UpdateOptions::detect_event(),
+            )
+        });
+    }
+
+    async fn wait_for_sync(active: &DatabaseHandle, standby: &DatabaseHandle) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while snapshot(active) != snapshot(standby) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("standby was not synchronized");
+    }
+
+    fn localhost() -> SocketAddr {
+        "127.0.0.1:0".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn replicates_over_tcp_link() {
+        let active = database();
+        let standby = database();
+        update(&active, true);
+
+        let receiver = spawn_replication_receiver(standby.clone(), localhost(), AddressFilter::Any)
+            .await
+            .unwrap();
+        let _sender = spawn_replication_sender(
+            active.clone(),
+            receiver.local_addr().unwrap(),
+            Duration::from_millis(10),
+            16,
+        );
+
+        // the snapshot transfers the existing state
+        wait_for_sync(&active, &standby).await;
+
+        // and subsequent updates are streamed
+        update(&active, false);
+        wait_for_sync(&active, &standby).await;
+    }
+
+    #[tokio::test]
+    async fn receiver_closes_connections_not_allowed_by_filter() {
+        let active = database();
+        let standby = database();
+        update(&active, true);
+
+        let filter = AddressFilter::Exact("10.0.0.1".parse().unwrap());
+        let receiver = spawn_replication_receiver(standby.clone(), localhost(), filter)
+            .await
+            .unwrap();
+        let _sender = spawn_replication_sender(
+            active.clone(),
+            receiver.local_addr().unwrap(),
+            Duration::from_millis(10),
+            16,
+        );
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(snapshot(&standby), snapshot(&database()));
+    }
+
+    #[tokio::test]
+    async fn idle_connection_does_not_block_sender() {
+        let active = database();
+        let standby = database();
+        update(&active, true);
+
+        let receiver = spawn_replication_receiver(standby.clone(), localhost(), AddressFilter::Any)
+            .await
+            .unwrap();
+        let addr = receiver.local_addr().unwrap();
+
+        // a peer that connects but never sends anything
+        let _idle = TcpStream::connect(addr).await.unwrap();
+
+        let _sender = spawn_replication_sender(active.clone(), addr, Duration::from_millis(10), 16);
+        wait_for_sync(&active, &standby).await;
+
+        update(&active, false);
+        wait_for_sync(&active, &standby).await;
+    }
+
+    #[test]
+    fn queue_sink_discards_messages_after_overflow() {
+        let db = database();
+        let overflow = Arc::new(AtomicBool::new(false));
+        let (tx, mut rx) = tokio::sync::mpsc::channel(1);
+        db.start_replication(Box::new(QueueSink {
+            tx: Some(tx),
+            overflow: overflow.clone(),
+        }));
+        assert!(!overflow.load(Ordering::Relaxed));
+
+        update(&db, true);
+        assert!(overflow.load(Ordering::Relaxed));
+
+        // only the snapshot was queued, the channel is closed after the overflow
+        assert!(rx.try_recv().unwrap().is_snapshot());
+        assert!(matches!(
+            rx.try_recv(),
+            Err(tokio::sync::mpsc::error::TryRecvError::Disconnected)
+        ));
+    }
+
+    #[tokio::test]
+    async fn resynchronizes_standby_after_overflow() {
+        let active = database();
+        let standby = database();
+
+        let receiver = spawn_replication_receiver(standby.clone(), localhost(), AddressFilter::Any)
+            .await
+            .unwrap();
+        let _sender = spawn_replication_sender(
+            active.clone(),
+            receiver.local_addr().unwrap(),
+            Duration::from_millis(10),
+            1,
+        );
+        wait_for_sync(&active, &standby).await;
+
+        // more updates than the queue can hold
+        for i in 0..10 {
+            update(&active, i % 2 == 0);
+        }
+        wait_for_sync(&active, &standby).await;
+    }
+
+    #[cfg(feature = "enable-tls")]
+    #[tokio::test]
+    async fn replicates_over_tls_link() {
+        use crate::tcp::tls::{MinTlsVersion, TlsClientConfig, TlsServerConfig};
+
+        fn cert_path(name: &str) -> std::path::PathBuf {
+            std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("../certs/ca_chain")
+                .join(name)
+        }
+
+        let server = TlsServerConfig::full_pki(
+            None,
+            &cert_path("ca_cert.pem"),
+            &cert_path("entity1_cert.pem"),
+            &cert_path("entity1_key.pem"),
+            None,
+            MinTlsVersion::V12,
+        )
+        .unwrap();
+        let client = TlsClientConfig::full_pki(
+            None,
+            &cert_path("ca_cert.pem"),
+            &cert_path("entity2_cert.pem"),
+            &cert_path("entity2_key.pem"),
+            None,
+            MinTlsVersion::V12,
+        )
+        .unwrap();
+
+        let active = database();
+        let standby = database();
+        update(&active, true);
+
+        let receiver = spawn_replication_receiver_tls(
+            standby.clone(),
+            localhost(),
+            AddressFilter::Any,
+            server,
+        )
+        .await
+        .unwrap();
+        let _sender = spawn_replication_sender_tls(
+            active.clone(),
+            receiver.local_addr().unwrap(),
+            Duration::from_millis(10),
+            16,
+            client,
+        );
+
+        wait_for_sync(&active, &standby).await;
+    }
+}
@@ -9,6 +9,7 @@ use crate::app::format::write::HeaderWriter;
 use crate::app::gen::all::AllObjectsVariation;
 use crate::app::gen::count::CountVariation;
 use crate::app::gen::ranged::RangedVariation;
+use crate::app::measurement::{AnalogOutputStatus, BinaryOutputStatus, Counter, OctetString, Time};
 use crate::app::parse::count::CountSequence;
 use crate::app::parse::parser::{HeaderCollection, HeaderDetails, ObjectHeader, Request};
 use crate::app::variations::{
@@ -20,19 +21,28 @@ use crate::link::error::LinkError;
 use crate::link::header::BroadcastConfirmMode;
 use crate::link::EndpointAddress;
 use crate::master::EventClasses;
-use crate::outstation::config::{Feature, OutstationConfig};
+use crate::outstation::config::{
+    ConnectionOverrides, Feature, OutstationConfig, OverrideRule, UnsolicitedTriggers,
+};
 use crate::outstation::control::collection::{ControlCollection, ControlTransaction};
 use crate::outstation::control::select::SelectState;
-use crate::outstation::database::{DatabaseHandle, ResponseInfo};
+use crate::outstation::database::read::ReadHeader;
+use crate::outstation::database::{
+    Database, DatabaseHandle, Get, ResponseInfo, Update, UpdateOptions,
+};
 use crate::outstation::deferred::DeferredRead;
 use crate::outstation::task::{ConfigurationChange, OutstationMessage};
 use crate::outstation::traits::*;
+use crate::outstation::{OutstationClock, TimeSyncMethod};
+use crate::statistics::{OutstationCounters, OutstationStatistics};
+use crate::tcp::PeerIdentity;
 use crate::transport::{
     FragmentAddr, FragmentInfo, RequestGuard, TransportReader, TransportRequest,
     TransportRequestError, TransportWriter,
 };
 use crate::util::buffer::Buffer;
 use crate::util::channel::Receiver;
+use crate::util::monitor::ChannelMonitor;
 use crate::util::phys::PhysLayer;
 
 use crate::app::gen::prefixed::PrefixedVariation;
@@ -157,10 +167,13 @@ pub(crate) struct SessionConfig {
     broadcast: Feature,
     unsolicited: Feature,
     respond_to_any_master: Feature,
+    index_prefixed_static: Feature,
     max_unsolicited_retries: Option<usize>,
     unsolicited_retry_delay: std::time::Duration,
+    unsolicited_triggers: UnsolicitedTriggers,
     keep_alive_timeout: Option<std::time::Duration>,
     max_controls_per_request: Option<u16>,
+    before_read_timeout: std::time::Duration,
 }
 pub(crate) struct SessionParameters {
     max_read_headers_per_request: u16,
@@ -176,10 +189,13 @@ impl From<OutstationConfig> for SessionConfig {
             broadcast: config.features.broadcast,
             unsolicited: config.features.unsolicited,
             respond_to_any_master: config.features.respond_to_any_master,
+            index_prefixed_static: config.features.index_prefixed_static,
             max_unsolicited_retries: config.max_unsolicited_retries,
             unsolicited_retry_delay: config.unsolicited_retry_delay,
+            unsolicited_triggers: config.unsolicited_triggers,
             keep_alive_timeout: config.keep_alive_timeout,
             max_controls_per_request: config.max_controls_per_request,
+            before_read_timeout: config.before_read_timeout,
         }
     }
 }
@@ -196,6 +212,16 @@ impl From<OutstationConfig> for SessionParameters {
     }
 }
 
+/// configured values of the settings that may be replaced by [`ConnectionOverrides`]
+#[derive(Copy, Clone)]
+struct ConnectionDefaults {
+    decode_level: DecodeLevel,
+    unsolicited: Feature,
+    keep_alive_timeout: Option<std::time::Duration>,
+    sol_tx_buffer_size: BufferSize<249, 2048>,
+    unsol_tx_buffer_size: BufferSize<249, 2048>,
+}
+
 #[derive(Copy, Clone)]
 enum UnsolicitedState {
     /// need to perform NULL unsolicited
@@ -212,7 +238,9 @@ struct SessionState {
     unsolicited: UnsolicitedState,
     unsolicited_seq: Sequence,
     deferred_read: DeferredRead,
+    read_objects: Vec<ReadObjects>,
     last_recorded_time: Option<tokio::time::Instant>,
+    delay_measured: bool,
     last_broadcast_type: Option<BroadcastConfirmMode>,
 }
 
@@ -226,7 +254,9 @@ impl SessionState {
             unsolicited: UnsolicitedState::NullRequired,
             unsolicited_seq: Sequence::default(),
             deferred_read: DeferredRead::new(max_read_headers),
+            read_objects: Vec::with_capacity(max_read_headers as usize),
             last_recorded_time: None,
+            delay_measured: false,
             last_broadcast_type: None,
         }
     }
@@ -236,6 +266,7 @@ impl SessionState {
         self.last_valid_request = None;
         self.select = None;
         self.deferred_read.clear();
+        self.delay_measured = false;
     }
 }
 
@@ -250,7 +281,15 @@ pub(crate) struct OutstationSession {
     application: Box<dyn OutstationApplication>,
     info: Box<dyn OutstationInformation>,
     control_handler: Box<dyn ControlHandler>,
+    write_handler: Box<dyn OutstationWriteHandler>,
     next_link_status: Option<tokio::time::Instant>,
+    monitor: ChannelMonitor,
+    counters: OutstationCounters,
+    clock: Option<OutstationClock>,
+    defaults: ConnectionDefaults,
+    override_rules: Vec<OverrideRule>,
+    /// true while running on a physical layer, i.e. while the overrides are applied
+    connected: bool,
 }
 
 enum Confirm {
@@ -325,11 +364,21 @@ impl OutstationSession {
         application: Box<dyn OutstationApplication>,
         information: Box<dyn OutstationInformation>,
         control_handler: Box<dyn ControlHandler>,
+        monitor: ChannelMonitor,
+        clock: Option<OutstationClock>,
     ) -> Self {
         let next_link_status = config
             .keep_alive_timeout
             .map(|delay| tokio::time::Instant::now() + delay);
 
+        let defaults = ConnectionDefaults {
+            decode_level: config.decode_level,
+            unsolicited: config.unsolicited,
+            keep_alive_timeout: config.keep_alive_timeout,
+            sol_tx_buffer_size: param.sol_tx_buffer_size,
+            unsol_tx_buffer_size: param.unsol_tx_buffer_size,
+        };
+
         Self {
             enabled: initial_state,
             messages,
@@ -340,8 +389,15 @@ impl OutstationSession {
             application,
             info: information,
             control_handler,
+            write_handler: DefaultWriteHandler::create(),
             next_link_status,
             destination,
+            monitor,
+            counters: OutstationCounters::default(),
+            clock,
+            defaults,
+            override_rules: Vec::new(),
+            connected: false,
         }
     }
 
@@ -352,8 +408,48 @@ impl OutstationSession {
         }
     }
 
+    /// called each time the session starts running on a new physical layer
+    pub(crate) fn on_session_start(&self, io: &PhysLayer) {
+        self.monitor.counters.on_session_start(io.medium().remote());
+    }
+
     pub(crate) fn change_master_address(&mut self, address: EndpointAddress) {
         self.destination.link = address;
+        if self.connected {
+            self.apply_connection_overrides(self.matching_overrides());
+        }
+    }
+
+    /// overrides applied to the configuration while running on subsequent physical layers
+    pub(crate) fn set_connection_overrides(&mut self, rules: Vec<OverrideRule>) {
+        self.override_rules = rules;
+    }
+
+    /// overrides of the first rule matching the address of the master
+    fn matching_overrides(&self) -> ConnectionOverrides {
+        self.override_rules
+            .iter()
+            .find(|rule| rule.matches(self.destination.link))
+            .map(|rule| rule.overrides)
+            .unwrap_or_default()
+    }
+
+    fn apply_connection_overrides(&mut self, overrides: ConnectionOverrides) {
+        let defaults = self.defaults;
+        self.config.decode_level = overrides.decode_level.unwrap_or(defaults.decode_level);
+        self.config.unsolicited = overrides.unsolicited.unwrap_or(defaults.unsolicited);
+        self.config.keep_alive_timeout = overrides
+            .keep_alive_timeout
+            .apply(defaults.keep_alive_timeout);
+        self.sol_tx_buffer = overrides
+            .solicited_buffer_size
+            .unwrap_or(defaults.sol_tx_buffer_size)
+            .create_buffer();
+        self.unsol_tx_buffer = overrides
+            .unsolicited_buffer_size
+            .unwrap_or(defaults.unsol_tx_buffer_size)
+            .create_buffer();
+        self.on_link_activity();
     }
 
     pub(crate) fn enabled(&self) -> Enabled {
@@ -372,12 +468,34 @@ impl OutstationSession {
         writer: &mut TransportWriter,
         database: &mut DatabaseHandle,
     ) -> RunError {
-        loop {
+        let identity = io.peer_identity();
+        self.peer_identity_changed(identity.as_deref());
+
+        let overrides = self.matching_overrides();
+        if overrides != ConnectionOverrides::default() {
+            tracing::info!("applying connection overrides: {:?}", overrides);
+        }
+        self.connected = true;
+        self.apply_connection_overrides(overrides);
+
+        let err = loop {
             if let Err(err) = self.run_idle_state(io, reader, writer, database).await {
                 self.state.reset();
-                return err;
+                break err;
             }
-        }
+        };
+
+        self.connected = false;
+        self.apply_connection_overrides(ConnectionOverrides::default());
+
+        self.peer_identity_changed(None);
+
+        err
+    }
+
+    fn peer_identity_changed(&mut self, identity: Option<&PeerIdentity>) {
+        self.application.peer_identity_changed(identity);
+        self.control_handler.peer_identity_changed(identity);
     }
 
     async fn write_unsolicited(
@@ -536,6 +654,18 @@ impl OutstationSession {
                     }
                 }
 
+                // hold events until one of the enabled classes reaches its trigger
+                match database.unsolicited_trigger_time(
+                    self.state.enabled_unsolicited_classes,
+                    &self.config.unsolicited_triggers,
+                ) {
+                    None => return Ok(NextIdleAction::SleepUntilEvent),
+                    Some(time) if tokio::time::Instant::now() < time => {
+                        return Ok(NextIdleAction::SleepUnit(time));
+                    }
+                    Some(_) => {}
+                }
+
                 // perform regular unsolicited
                 match self
                     .maybe_perform_unsolicited(io, reader, writer, database)
@@ -699,6 +829,7 @@ impl OutstationSession {
                         retry = false;
                     }
 
+                    self.counters.unsolicited_confirm_timeouts += 1;
                     self.info.unsolicited_confirm_timeout(response.seq(), retry);
 
                     if !retry {
@@ -747,6 +878,7 @@ impl OutstationSession {
             }
         };
 
+        self.counters.on_request(request.header.function);
         match self.classify(info, request) {
             FragmentType::UnsolicitedConfirm(seq) => {
                 if seq == uns_ecsn {
@@ -906,6 +1038,10 @@ impl OutstationSession {
                 self.handle_config_change(change);
                 Ok(())
             }
+            OutstationMessage::GetStatistics(reply) => {
+                reply.send(self.get_statistics()).ok();
+                Ok(())
+            }
         }
     }
 
@@ -914,10 +1050,40 @@ impl OutstationSession {
             ConfigurationChange::SetDecodeLevel(level) => {
                 tracing::info!("decode level changed to: {:?}", level);
                 self.config.decode_level = level;
+                self.defaults.decode_level = level;
+            }
+            ConfigurationChange::SetCapture(capture) => {
+                tracing::info!(
+                    "capture {}",
+                    if capture.is_some() {
+                        "started"
+                    } else {
+                        "stopped"
+                    }
+                );
+                self.monitor.capture.set(capture);
+            }
+            ConfigurationChange::SetDecodeSink(sink) => {
+                self.monitor.decode.set(sink);
+            }
+            ConfigurationChange::SetWriteHandler(handler) => {
+                self.write_handler = handler;
             }
         }
     }
 
+    /// statistics maintained by the session, the event buffer statistics are filled in by the handle
+    fn get_statistics(&self) -> OutstationStatistics {
+        let mut statistics = OutstationStatistics {
+            channel: self.monitor.counters.get(),
+            ..Default::default()
+        };
+        statistics.requests = self.counters.requests.clone();
+        statistics.solicited_confirm_timeouts = self.counters.solicited_confirm_timeouts;
+        statistics.unsolicited_confirm_timeouts = self.counters.unsolicited_confirm_timeouts;
+        statistics
+    }
+
     async fn handle_deferred_read(
         &mut self,
         io: &mut PhysLayer,
@@ -925,6 +1091,18 @@ impl OutstationSession {
         writer: &mut TransportWriter,
         database: &mut DatabaseHandle,
     ) -> Result<(), RunError> {
+        if self.state.deferred_read.is_set() {
+            self.state.read_objects.clear();
+            self.state.read_objects.extend(
+                self.state
+                    .deferred_read
+                    .headers()
+                    .iter()
+                    .filter_map(|h| h.to_read_objects()),
+            );
+            self.before_read(database).await;
+        }
+
         if let Some(x) = self.state.deferred_read.select(database) {
             tracing::info!("handling deferred READ request");
             let (response, mut series) = self.format_read_response(database, true, x.seq, x.iin2);
@@ -1018,6 +1196,7 @@ impl OutstationSession {
         database: &mut DatabaseHandle,
     ) -> Option<LastValidRequest> {
         self.info.process_request_from_idle(request.header);
+        self.counters.on_request(request.header.function);
 
         let seq = request.header.control.seq;
 
@@ -1027,6 +1206,7 @@ impl OutstationSession {
                 Some(LastValidRequest::new(seq, hash, Some(response), None))
             }
             FragmentType::NewRead(hash, objects) => {
+                self.before_first_read_response(database, objects).await;
                 let (response, series) = self.format_first_read_response(database, seq, objects);
                 Some(LastValidRequest::new(seq, hash, Some(response), series))
             }
@@ -1035,6 +1215,7 @@ impl OutstationSession {
                 // also reply to duplicate READ requests from idle, but this
                 // is plainly wrong since it can't possibly handle a multi-fragmented
                 // response correctly. Answering a repeat READ with a fresh response is harmless
+                self.before_first_read_response(database, objects).await;
                 let (response, series) = self.format_first_read_response(database, seq, objects);
                 Some(LastValidRequest::new(seq, hash, Some(response), series))
             }
@@ -1105,6 +1286,39 @@ impl OutstationSession {
         Ok(())
     }
 
+    async fn before_first_read_response(
+        &mut self,
+        database: &mut DatabaseHandle,
+        object_headers: HeaderCollection<'_>,
+    ) {
+        self.state.read_objects.clear();
+        self.state.read_objects.extend(
+            object_headers
+                .iter()
+                .filter_map(|h| ReadHeader::get_impl(&h.details))
+                .filter_map(|h| h.to_read_objects()),
+        );
+        self.before_read(database).await;
+    }
+
+    /// give the application a chance to refresh the values requested in `state.read_objects`
+    async fn before_read(&mut self, database: &mut DatabaseHandle) {
+        let refresh = self
+            .application
+            .before_read(&self.state.read_objects, database)
+            .get();
+
+        if tokio::time::timeout(self.config.before_read_timeout, refresh)
+            .await
+            .is_err()
+        {
+            tracing::warn!(
+                "application did not refresh values within {:?}, responding to READ with current values",
+                self.config.before_read_timeout
+            );
+        }
+    }
+
     fn format_first_read_response(
         &mut self,
         database: &mut DatabaseHandle,
@@ -1125,7 +1339,10 @@ impl OutstationSession {
         let (len, info) = {
             let mut cursor = self.sol_tx_buffer.write_cursor();
             let _ = cursor.skip(ResponseHeader::LENGTH);
-            let info = database.write_response_headers(&mut cursor);
+            let info = database.write_response_headers(
+                &mut cursor,
+                self.config.index_prefixed_static.is_enabled(),
+            );
             (cursor.written().len(), info)
         };
 
@@ -1312,16 +1529,36 @@ impl OutstationSession {
 
     fn handle_write_abs_time(&mut self, seq: CountSequence<Group50Var1>) -> Iin2 {
         if let Some(value) = seq.single() {
-            match self.application.write_absolute_time(value.time) {
-                Err(err) => err.into(),
-                Ok(()) => Iin2::default(),
-            }
+            let method = if std::mem::take(&mut self.state.delay_measured) {
+                TimeSyncMethod::NonLan
+            } else {
+                TimeSyncMethod::Direct
+            };
+            self.write_time(value.time, method)
         } else {
             tracing::warn!("request lacks a single g50v1");
             Iin2::PARAMETER_ERROR
         }
     }
 
+    fn write_time(&mut self, time: Timestamp, method: TimeSyncMethod) -> Iin2 {
+        let result = self.application.write_absolute_time(time);
+        match &self.clock {
+            // the clock accepts the time unless the application rejects it
+            Some(clock) => match result {
+                Err(RequestError::ParameterError) => Iin2::PARAMETER_ERROR,
+                Err(RequestError::NotSupported) | Ok(()) => {
+                    clock.synchronize(time, method);
+                    Iin2::default()
+                }
+            },
+            None => match result {
+                Err(err) => err.into(),
+                Ok(()) => Iin2::default(),
+            },
+        }
+    }
+
     async fn handle_write_analog_deadbands<I, V>(
         &mut self,
         items: CountSequence<'_, Prefix<I, V>>,
@@ -1357,6 +1594,128 @@ impl OutstationSession {
         iin
     }
 
+    /// time used to stamp point values written by the master
+    fn current_time(&self) -> Time {
+        match &self.clock {
+            Some(clock) => clock.now(),
+            None => Time::Unsynchronized(
+                Timestamp::try_from_system_time(std::time::SystemTime::now())
+                    .unwrap_or(Timestamp::zero()),
+            ),
+        }
+    }
+
+    fn handle_write_binary_output_status(
+        &mut self,
+        values: impl Iterator<Item = (BinaryOutputStatus, u16)>,
+        db: &DatabaseHandle,
+    ) -> Iin2 {
+        let time = Some(self.current_time());
+        self.handle_write_values(
+            values.map(|(v, i)| (BinaryOutputStatus { time, ..v }, i)),
+            db,
+            |h, i, v| h.write_binary_output_status(i, *v),
+        )
+    }
+
+    fn handle_write_counters(
+        &mut self,
+        values: impl Iterator<Item = (Counter, u16)>,
+        db: &DatabaseHandle,
+    ) -> Iin2 {
+        let time = Some(self.current_time());
+        self.handle_write_values(
+            values.map(|(v, i)| (Counter { time, ..v }, i)),
+            db,
+            |h, i, v| h.write_counter(i, *v),
+        )
+    }
+
+    fn handle_write_analog_output_status(
+        &mut self,
+        values: impl Iterator<Item = (AnalogOutputStatus, u16)>,
+        db: &DatabaseHandle,
+    ) -> Iin2 {
+        let time = Some(self.current_time());
+        self.handle_write_values(
+            values.map(|(v, i)| (AnalogOutputStatus { time, ..v }, i)),
+            db,
+            |h, i, v| h.write_analog_output_status(i, *v),
+        )
+    }
+
+    fn handle_write_octet_strings<'a>(
+        &mut self,
+        values: impl Iterator<Item = (&'a [u8], u16)>,
+        db: &DatabaseHandle,
+    ) -> Iin2 {
+        let mut iin2 = Iin2::default();
+        let values = values.filter_map(|(v, i)| match OctetString::new(v) {
+            Ok(x) => Some((x, i)),
+            Err(_) => {
+                iin2 |= Iin2::PARAMETER_ERROR;
+                None
+            }
+        });
+        let iin = self.handle_write_values(values, db, |h, i, v| h.write_octet_string(i, v));
+        iin | iin2
+    }
+
+    /// offer each value to the write handler and update the database with the accepted ones
+    ///
+    /// The handler is called outside of the database lock so that it may freely use the database
+    fn handle_write_values<T>(
+        &mut self,
+        mut values: impl Iterator<Item = (T, u16)>,
+        db: &DatabaseHandle,
+        offer: fn(&mut dyn OutstationWriteHandler, u16, &T) -> WriteStatus,
+    ) -> Iin2
+    where
+        Database: Get<T> + Update<T>,
+    {
+        let mut iin2 = Iin2::default();
+
+        let defined: Vec<(T, u16)> = db.transaction(|db| {
+            values
+                .by_ref()
+                .filter(|(_, index)| {
+                    let exists = Get::<T>::get(db, *index).is_some();
+                    if !exists {
+                        tracing::warn!("WRITE of undefined point at index: {index}");
+                        iin2 |= Iin2::PARAMETER_ERROR;
+                    }
+                    exists
+                })
+                .collect()
+        });
+
+        let handler = self.write_handler.as_mut();
+        let accepted: Vec<(T, u16)> = defined
+            .into_iter()
+            .filter(|(value, index)| match offer(handler, *index, value) {
+                WriteStatus::Accept => true,
+                WriteStatus::Reject => {
+                    iin2 |= Iin2::PARAMETER_ERROR;
+                    false
+                }
+                WriteStatus::NotSupported => {
+                    iin2 |= Iin2::NO_FUNC_CODE_SUPPORT;
+                    false
+                }
+            })
+            .collect();
+
+        if !accepted.is_empty() {
+            db.transaction(|db| {
+                for (value, index) in accepted.iter() {
+                    db.update(*index, value, UpdateOptions::default());
+                }
+            });
+        }
+
+        iin2
+    }
+
     async fn handle_single_write_header(
         &mut self,
         header: ObjectHeader<'_>,
@@ -1397,6 +1756,139 @@ impl OutstationSession {
             HeaderDetails::TwoByteCountAndPrefix(_, PrefixedVariation::Group34Var3(seq)) => {
                 self.handle_write_analog_deadbands(seq, db).await
             }
+            // point values
+            HeaderDetails::OneByteStartStop(_, _, RangedVariation::Group10Var1(seq))
+            | HeaderDetails::TwoByteStartStop(_, _, RangedVariation::Group10Var1(seq)) => {
+                self.handle_write_binary_output_status(seq.iter().map(|(v, i)| (v.into(), i)), db)
+            }
+            HeaderDetails::OneByteStartStop(_, _, RangedVariation::Group10Var2(seq))
+            | HeaderDetails::TwoByteStartStop(_, _, RangedVariation::Group10Var2(seq)) => {
+                self.handle_write_binary_output_status(seq.iter().map(|(v, i)| (v.into(), i)), db)
+            }
+            HeaderDetails::OneByteStartStop(_, _, RangedVariation::Group20Var1(seq))
+            | HeaderDetails::TwoByteStartStop(_, _, RangedVariation::Group20Var1(seq)) => {
+                self.handle_write_counters(seq.iter().map(|(v, i)| (v.into(), i)), db)
+            }
+            HeaderDetails::OneByteStartStop(_, _, RangedVariation::Group20Var2(seq))
+            | HeaderDetails::TwoByteStartStop(_, _, RangedVariation::Group20Var2(seq)) => {
+                self.handle_write_counters(seq.iter().map(|(v, i)| (v.into(), i)), db)
+            }
+            HeaderDetails::OneByteStartStop(_, _, RangedVariation::Group20Var5(seq))
+            | HeaderDetails::TwoByteStartStop(_, _, RangedVariation::Group20Var5(seq)) => {
+                self.handle_write_counters(seq.iter().map(|(v, i)| (v.into(), i)), db)
+            }
+            HeaderDetails::OneByteStartStop(_, _, RangedVariation::Group20Var6(seq))
+            | HeaderDetails::TwoByteStartStop(_, _, RangedVariation::Group20Var6(seq)) => {
+                self.handle_write_counters(seq.iter().map(|(v, i)| (v.into(), i)), db)
+            }
+            HeaderDetails::OneByteStartStop(_, _, RangedVariation::Group40Var1(seq))
+            | HeaderDetails::TwoByteStartStop(_, _, RangedVariation::Group40Var1(seq)) => {
+                self.handle_write_analog_output_status(seq.iter().map(|(v, i)| (v.into(), i)), db)
+            }
+            HeaderDetails::OneByteStartStop(_, _, RangedVariation::Group40Var2(seq))
+            | HeaderDetails::TwoByteStartStop(_, _, RangedVariation::Group40Var2(seq)) => {
+                self.handle_write_analog_output_status(seq.iter().map(|(v, i)| (v.into(), i)), db)
+            }
+            HeaderDetails::OneByteStartStop(_, _, RangedVariation::Group40Var3(seq))
+            | HeaderDetails::TwoByteStartStop(_, _, RangedVariation::Group40Var3(seq)) => {
+                self.handle_write_analog_output_status(seq.iter().map(|(v, i)| (v.into(), i)), db)
+            }
+            HeaderDetails::OneByteStartStop(_, _, RangedVariation::Group40Var4(seq))
+            | HeaderDetails::TwoByteStartStop(_, _, RangedVariation::Group40Var4(seq)) => {
+                self.handle_write_analog_output_status(seq.iter().map(|(v, i)| (v.into(), i)), db)
+            }
+            HeaderDetails::OneByteStartStop(_, _, RangedVariation::Group110VarX(_, seq))
+            | HeaderDetails::TwoByteStartStop(_, _, RangedVariation::Group110VarX(_, seq)) => {
+                self.handle_write_octet_strings(seq.iter(), db)
+            }
+            HeaderDetails::OneByteCountAndPrefix(_, PrefixedVariation::Group10Var2(seq)) => self
+                .handle_write_binary_output_status(
+                    seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16())),
+                    db,
+                ),
+            HeaderDetails::TwoByteCountAndPrefix(_, PrefixedVariation::Group10Var2(seq)) => self
+                .handle_write_binary_output_status(
+                    seq.iter().map(|x| (x.value.into(), x.index)),
+                    db,
+                ),
+            HeaderDetails::OneByteCountAndPrefix(_, PrefixedVariation::Group20Var1(seq)) => self
+                .handle_write_counters(
+                    seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16())),
+                    db,
+                ),
+            HeaderDetails::TwoByteCountAndPrefix(_, PrefixedVariation::Group20Var1(seq)) => {
+                self.handle_write_counters(seq.iter().map(|x| (x.value.into(), x.index)), db)
+            }
+            HeaderDetails::OneByteCountAndPrefix(_, PrefixedVariation::Group20Var2(seq)) => self
+                .handle_write_counters(
+                    seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16())),
+                    db,
+                ),
+            HeaderDetails::TwoByteCountAndPrefix(_, PrefixedVariation::Group20Var2(seq)) => {
+                self.handle_write_counters(seq.iter().map(|x| (x.value.into(), x.index)), db)
+            }
+            HeaderDetails::OneByteCountAndPrefix(_, PrefixedVariation::Group20Var5(seq)) => self
+                .handle_write_counters(
+                    seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16())),
+                    db,
+                ),
+            HeaderDetails::TwoByteCountAndPrefix(_, PrefixedVariation::Group20Var5(seq)) => {
+                self.handle_write_counters(seq.iter().map(|x| (x.value.into(), x.index)), db)
+            }
+            HeaderDetails::OneByteCountAndPrefix(_, PrefixedVariation::Group20Var6(seq)) => self
+                .handle_write_counters(
+                    seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16())),
+                    db,
+                ),
+            HeaderDetails::TwoByteCountAndPrefix(_, PrefixedVariation::Group20Var6(seq)) => {
+                self.handle_write_counters(seq.iter().map(|x| (x.value.into(), x.index)), db)
+            }
+            HeaderDetails::OneByteCountAndPrefix(_, PrefixedVariation::Group40Var1(seq)) => self
+                .handle_write_analog_output_status(
+                    seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16())),
+                    db,
+                ),
+            HeaderDetails::TwoByteCountAndPrefix(_, PrefixedVariation::Group40Var1(seq)) => self
+                .handle_write_analog_output_status(
+                    seq.iter().map(|x| (x.value.into(), x.index)),
+                    db,
+                ),
+            HeaderDetails::OneByteCountAndPrefix(_, PrefixedVariation::Group40Var2(seq)) => self
+                .handle_write_analog_output_status(
+                    seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16())),
+                    db,
+                ),
+            HeaderDetails::TwoByteCountAndPrefix(_, PrefixedVariation::Group40Var2(seq)) => self
+                .handle_write_analog_output_status(
+                    seq.iter().map(|x| (x.value.into(), x.index)),
+                    db,
+                ),
+            HeaderDetails::OneByteCountAndPrefix(_, PrefixedVariation::Group40Var3(seq)) => self
+                .handle_write_analog_output_status(
+                    seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16())),
+                    db,
+                ),
+            HeaderDetails::TwoByteCountAndPrefix(_, PrefixedVariation::Group40Var3(seq)) => self
+                .handle_write_analog_output_status(
+                    seq.iter().map(|x| (x.value.into(), x.index)),
+                    db,
+                ),
+            HeaderDetails::OneByteCountAndPrefix(_, PrefixedVariation::Group40Var4(seq)) => self
+                .handle_write_analog_output_status(
+                    seq.iter().map(|x| (x.value.into(), x.index.widen_to_u16())),
+                    db,
+                ),
+            HeaderDetails::TwoByteCountAndPrefix(_, PrefixedVariation::Group40Var4(seq)) => self
+                .handle_write_analog_output_status(
+                    seq.iter().map(|x| (x.value.into(), x.index)),
+                    db,
+                ),
+            HeaderDetails::OneByteCountAndPrefix(_, PrefixedVariation::Group110VarX(_, seq)) => {
+                self.handle_write_octet_strings(seq.iter().map(|(v, i)| (v, i.widen_to_u16())), db)
+            }
+            HeaderDetails::TwoByteCountAndPrefix(_, PrefixedVariation::Group110VarX(_, seq)) => {
+                self.handle_write_octet_strings(seq.iter(), db)
+            }
             _ => {
                 tracing::warn!(
                     "WRITE not supported with qualifier: {} and variation: {}",
@@ -1439,14 +1931,11 @@ impl OutstationSession {
         };
 
         self.state.last_recorded_time = None;
-        match self.application.write_absolute_time(timestamp) {
-            Err(RequestError::NotSupported) => Iin2::NO_FUNC_CODE_SUPPORT,
-            Err(RequestError::ParameterError) => Iin2::PARAMETER_ERROR,
-            Ok(()) => Iin2::default(),
-        }
+        self.write_time(timestamp, TimeSyncMethod::Lan)
     }
 
     fn handle_delay_measure(&mut self, seq: Sequence) -> Response {
+        self.state.delay_measured = true;
         let g52v2 = Group52Var2 {
             time: self.application.get_processing_delay_ms(),
         };
@@ -1904,6 +2393,11 @@ impl OutstationSession {
         // Application-controlled IIN bits
         iin |= self.application.get_application_iin();
 
+        // Clock-controlled NEED_TIME
+        if self.clock.as_ref().is_some_and(|x| x.need_time()) {
+            iin |= Iin1::NEED_TIME;
+        }
+
         iin
     }
 
@@ -2071,6 +2565,7 @@ impl OutstationSession {
         loop {
             match self.read_until(io, reader, deadline).await? {
                 TimeoutStatus::Yes => {
+                    self.counters.solicited_confirm_timeouts += 1;
                     self.info.solicited_confirm_timeout(ecsn);
                     return Ok(Confirm::Timeout);
                 }
@@ -2123,7 +2618,7 @@ impl OutstationSession {
             None => return ConfirmAction::ContinueWait,
         };
 
-        match self.classify(info, request) {
+        let action = match self.classify(info, request) {
             FragmentType::MalformedRequest(_, _) => ConfirmAction::NewRequest,
             FragmentType::NewRead(_, _) => ConfirmAction::NewRequest,
             FragmentType::RepeatRead(_, response, _) => {
@@ -2151,7 +2646,14 @@ impl OutstationSession {
                 tracing::warn!("ignoring unsolicited confirm with seq: {}", seq.value());
                 ConfirmAction::ContinueWait
             }
+        };
+
+        // new requests are counted when they are processed after leaving the confirm wait
+        if !matches!(action, ConfirmAction::NewRequest) {
+            self.counters.on_request(request.header.function);
         }
+
+        action
     }
 
     fn classify<'a>(&self, info: FragmentInfo, request: Request<'a>) -> FragmentType<'a> {
@@ -1,18 +1,29 @@
+use std::sync::Arc;
+
 use crate::app::parse::options::ParseOptions;
-use crate::decode::DecodeLevel;
+use crate::capture::PcapCapture;
+use crate::decode::{DecodeLevel, DecodeSink};
 use crate::link::reader::LinkModes;
 use crate::link::EndpointAddress;
 use crate::outstation::config::*;
 use crate::outstation::database::DatabaseHandle;
 use crate::outstation::session::OutstationSession;
-use crate::outstation::traits::{ControlHandler, OutstationApplication, OutstationInformation};
-use crate::outstation::OutstationHandle;
+use crate::outstation::traits::{
+    ControlHandler, OutstationApplication, OutstationInformation, OutstationWriteHandler,
+};
+use crate::outstation::{OutstationClock, OutstationHandle};
+use crate::statistics::OutstationStatistics;
 use crate::transport::{FragmentAddr, TransportReader, TransportWriter};
+use crate::util::monitor::ChannelMonitor;
 use crate::util::phys::{PhysAddr, PhysLayer};
 use crate::util::session::{Enabled, RunError, StopReason};
 
+#[allow(clippy::enum_variant_names)]
 pub(crate) enum ConfigurationChange {
     SetDecodeLevel(DecodeLevel),
+    SetCapture(Option<PcapCapture>),
+    SetDecodeSink(Option<Arc<dyn DecodeSink>>),
+    SetWriteHandler(Box<dyn OutstationWriteHandler>),
 }
 
 impl From<ConfigurationChange> for OutstationMessage {
@@ -26,6 +37,7 @@ pub(crate) enum OutstationMessage {
     Disable,
     Shutdown,
     Configuration(ConfigurationChange),
+    GetStatistics(tokio::sync::oneshot::Sender<OutstationStatistics>),
 }
 
 pub(crate) struct OutstationTask {
@@ -33,6 +45,8 @@ pub(crate) struct OutstationTask {
     reader: TransportReader,
     writer: TransportWriter,
     database: DatabaseHandle,
+    // clock passed to the stale timer of the database, taken when the timer is started
+    stale_timer: Option<Option<OutstationClock>>,
 }
 
 impl OutstationTask {
@@ -48,18 +62,79 @@ impl OutstationTask {
         information: Box<dyn OutstationInformation>,
         control_handler: Box<dyn ControlHandler>,
     ) -> (Self, OutstationHandle) {
-        let (tx, rx) = crate::util::channel::request_channel();
-        let handle = DatabaseHandle::new(
+        let database = DatabaseHandle::new(
+            config.max_read_request_headers,
+            config.class_zero,
+            config.event_buffer_config,
+        );
+        Self::create_impl(
+            initial_state,
+            link_modes,
+            parse_options,
+            config,
+            phys_addr,
+            application,
+            information,
+            control_handler,
+            database,
+        )
+    }
+
+    /// create an `OutstationTask` that shares the database of another outstation
+    ///
+    /// The task gets its own event buffer, while point values are kept in sync with the other outstations
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn join(
+        initial_state: Enabled,
+        link_modes: LinkModes,
+        parse_options: ParseOptions,
+        config: OutstationConfig,
+        phys_addr: PhysAddr,
+        application: Box<dyn OutstationApplication>,
+        information: Box<dyn OutstationInformation>,
+        control_handler: Box<dyn ControlHandler>,
+        database: &DatabaseHandle,
+    ) -> (Self, OutstationHandle) {
+        let database = database.join(
             config.max_read_request_headers,
             config.class_zero,
             config.event_buffer_config,
         );
+        Self::create_impl(
+            initial_state,
+            link_modes,
+            parse_options,
+            config,
+            phys_addr,
+            application,
+            information,
+            control_handler,
+            database,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn create_impl(
+        initial_state: Enabled,
+        link_modes: LinkModes,
+        parse_options: ParseOptions,
+        config: OutstationConfig,
+        phys_addr: PhysAddr,
+        application: Box<dyn OutstationApplication>,
+        information: Box<dyn OutstationInformation>,
+        control_handler: Box<dyn ControlHandler>,
+        handle: DatabaseHandle,
+    ) -> (Self, OutstationHandle) {
+        let (tx, rx) = crate::util::channel::request_channel();
+        let monitor = ChannelMonitor::default();
+        let clock = config.clock.map(OutstationClock::new);
         let (reader, writer) = crate::transport::create_outstation_transport_layer(
             link_modes,
             parse_options,
             config.outstation_address,
             config.features.self_address,
             config.rx_buffer_size,
+            monitor.clone(),
         );
         let destination = FragmentAddr {
             link: config.master_address,
@@ -75,15 +150,19 @@ impl OutstationTask {
                 application,
                 information,
                 control_handler,
+                monitor,
+                clock.clone(),
             ),
             reader,
             writer,
             database: handle.clone(),
+            stale_timer: Some(clock.clone()),
         };
         (
             task,
             OutstationHandle {
                 database: handle,
+                clock,
                 sender: tx,
             },
         )
@@ -97,8 +176,14 @@ impl OutstationTask {
         self.session.change_master_address(address);
     }
 
+    pub(crate) fn set_connection_overrides(&mut self, rules: Vec<OverrideRule>) {
+        self.session.set_connection_overrides(rules);
+    }
+
     /// run the outstation task asynchronously until a `SessionError` occurs
     pub(crate) async fn run(&mut self, io: &mut PhysLayer) -> RunError {
+        self.start_stale_timer();
+        self.session.on_session_start(io);
         let res = self
             .session
             .run(io, &mut self.reader, &mut self.writer, &mut self.database)
@@ -112,11 +197,26 @@ impl OutstationTask {
 
     /// process received outstation messages while idle without a session
     pub(crate) async fn process_next_message(&mut self) -> Result<(), StopReason> {
+        self.start_stale_timer();
         self.session.process_next_message().await
     }
 
+    /// stale sources must be detected even while no master is connected
+    fn start_stale_timer(&mut self) {
+        if let Some(clock) = self.stale_timer.take() {
+            self.database.start_stale_timer(clock);
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn get_reader(&mut self) -> &mut TransportReader {
         &mut self.reader
     }
 }
+
+impl Drop for OutstationTask {
+    fn drop(&mut self) {
+        // other outstations sharing the database must not keep buffering events for this one
+        self.database.leave();
+    }
+}
@@ -0,0 +1,147 @@
+use std::time::Duration;
+
+use crate::app::measurement::*;
+use crate::outstation::database::*;
+use crate::outstation::{OutstationConfig, ReadIndices, ReadObjects, StaticDataType};
+
+use super::harness::*;
+
+const READ_CLASS_0: &[u8] = &[0xC0, 0x01, 60, 1, 0x06];
+// g30v1 index 0 with the default value
+const CLASS_0_RESPONSE_DEFAULT: &[u8] = &[
+    0xC0, 0x81, 0x80, 0x00, 30, 1, 0x01, 0, 0, 0, 0, 0x02, 0, 0, 0, 0,
+];
+// g30v1 index 0 with the refreshed value
+const CLASS_0_RESPONSE_REFRESHED: &[u8] = &[
+    0xC0, 0x81, 0x80, 0x00, 30, 1, 0x01, 0, 0, 0, 0, 0x01, 42, 0, 0, 0,
+];
+
+fn add_analog(db: &mut Database) {
+    db.add(
+        0,
+        Some(EventClass::Class1),
+        AnalogInputConfig::new(
+            StaticAnalogInputVariation::Group30Var1,
+            EventAnalogInputVariation::Group32Var1,
+            0.0,
+        ),
+    );
+}
+
+fn harness_with_refresh(config: OutstationConfig, delay: Duration) -> OutstationHarness {
+    let harness = new_harness(config);
+    harness.handle.transaction(add_analog);
+    harness.application_data.lock().unwrap().refresh = Some((delay, 42.0));
+    harness
+}
+
+#[tokio::test]
+async fn application_receives_requested_headers() {
+    let mut harness = new_harness(get_default_config());
+    harness.handle.transaction(add_analog);
+
+    // class 0, g30v0 indices 0 to 0, class 1, at most 5 g32v0
+    harness
+        .test_request_response(
+            &[
+                0xC0, 0x01, 60, 1, 0x06, 30, 0, 0x00, 0, 0, 60, 2, 0x06, 32, 0, 0x07, 5,
+            ],
+            &[
+                0xC0, 0x81, 0x80, 0x00, 30, 1, 0x01, 0, 0, 0, 0, 0x02, 0, 0, 0, 0, 30, 1, 0x01, 0,
+                0, 0, 0, 0x02, 0, 0, 0, 0,
+            ],
+        )
+        .await;
+
+    assert_eq!(
+        harness.application_data.lock().unwrap().read_headers,
+        [[
+            ReadObjects::Class0,
+            ReadObjects::Static(StaticDataType::AnalogInput, ReadIndices::Range(0, 0)),
+            ReadObjects::ClassEvents(EventClass::Class1, None),
+            ReadObjects::TypedEvents(EventType::AnalogInput, Some(5)),
+        ]]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn response_contains_values_refreshed_before_read() {
+    let mut harness = harness_with_refresh(get_default_config(), Duration::from_millis(500));
+
+    harness
+        .test_request_response(READ_CLASS_0, CLASS_0_RESPONSE_REFRESHED)
+        .await;
+    // the refresh doesn't generate events
+    harness
+        .test_request_response(&[0xC1, 0x01, 60, 2, 0x06], &[0xC1, 0x81, 0x80, 0x00])
+        .await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn responds_with_current_values_if_refresh_times_out() {
+    let mut config = get_default_config();
+    config.before_read_timeout = Duration::from_secs(1);
+    let mut harness = harness_with_refresh(config, Duration::from_secs(2));
+
+    harness
+        .test_request_response(READ_CLASS_0, CLASS_0_RESPONSE_DEFAULT)
+        .await;
+
+    // the refresh was abandoned
+    tokio::time::advance(Duration::from_secs(5)).await;
+    harness
+        .test_request_response(&[0xC1, 0x01, 60, 1, 0x06], &{
+            let mut response = CLASS_0_RESPONSE_DEFAULT.to_vec();
+            response[0] = 0xC1;
+            response
+        })
+        .await;
+}
+
+#[tokio::test]
+async fn deferred_read_is_refreshed_before_response() {
+    let mut harness = new_harness(get_default_unsolicited_config());
+    harness.handle.transaction(add_analog);
+
+    // confirm the NULL unsolicited response
+    harness.expect_response(&[0xF0, 0x82, 0x80, 0x00]).await;
+    harness.check_events(&[Event::EnterUnsolicitedConfirmWait(0)]);
+    harness.send_and_process(&[0xD0, 0x00]).await;
+    harness.check_events(&[Event::UnsolicitedConfirmReceived(0)]);
+    // enable unsolicited for class 1
+    harness
+        .test_request_response(&[0xC0, 0x14, 60, 2, 0x06], &[0xC0, 0x81, 0x80, 0x00])
+        .await;
+
+    harness.handle.transaction(|db| {
+        db.update(
+            0,
+            &AnalogInput::new(1.0, Flags::ONLINE, Time::synchronized(0)),
+            UpdateOptions::detect_event(),
+        );
+    });
+    harness
+        .expect_response(&[
+            0xF1, 0x82, 0x80, 0x00, 32, 1, 0x28, 1, 0, 0, 0, 0x01, 1, 0, 0, 0,
+        ])
+        .await;
+    harness.check_events(&[Event::EnterUnsolicitedConfirmWait(1)]);
+
+    // this READ is deferred until the unsolicited response is confirmed
+    harness.application_data.lock().unwrap().refresh = Some((Duration::ZERO, 42.0));
+    harness.send_and_process(&[0xC1, 0x01, 60, 1, 0x06]).await;
+    harness.send_and_process(&[0xD1, 0x00]).await;
+    harness
+        .expect_response(&[
+            0xC1, 0x81, 0x80, 0x00, 30, 1, 0x01, 0, 0, 0, 0, 0x01, 42, 0, 0, 0,
+        ])
+        .await;
+
+    let read_headers = harness
+        .application_data
+        .lock()
+        .unwrap()
+        .read_headers
+        .clone();
+    assert_eq!(read_headers, [[ReadObjects::Class0]]);
+}
@@ -0,0 +1,85 @@
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use crate::capture::PcapCapture;
+use crate::outstation::tests::harness::*;
+
+const CLEAR_RESTART_IIN: &[u8] = &[0xC0, 0x02, 80, 1, 0x00, 7, 7, 0x00];
+const RESPONSE_NO_RESTART_IIN: &[u8] = &[0xC0, 0x81, 0x00, 0x00];
+const DELAY_MEASURE: &[u8] = &[0xC1, 23];
+const RESPONSE_TIME_DELAY: &[u8] = &[0xC1, 0x81, 0x00, 0x00, 0x34, 0x02, 0x07, 0x01, 0x00, 0x00];
+
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn contains(data: &[u8], bytes: &[u8]) -> bool {
+    data.windows(bytes.len()).any(|x| x == bytes)
+}
+
+#[tokio::test]
+async fn records_requests_and_responses_once_capture_is_started() {
+    let mut harness = new_harness(get_default_config());
+    let buffer = SharedBuffer::default();
+
+    harness
+        .handle
+        .start_capture(PcapCapture::new(buffer.clone()).unwrap())
+        .await
+        .unwrap();
+
+    // the capture is guaranteed to be active once the outstation is waiting for the next request
+    harness
+        .test_request_response(CLEAR_RESTART_IIN, RESPONSE_NO_RESTART_IIN)
+        .await;
+    harness
+        .test_request_response(DELAY_MEASURE, RESPONSE_TIME_DELAY)
+        .await;
+
+    // packets are written on a separate thread
+    tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        loop {
+            {
+                let data = buffer.0.lock().unwrap();
+                if contains(&data, DELAY_MEASURE) && contains(&data, RESPONSE_TIME_DELAY) {
+                    return;
+                }
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn nothing_is_recorded_after_capture_is_stopped() {
+    let mut harness = new_harness(get_default_config());
+    let buffer = SharedBuffer::default();
+
+    harness
+        .handle
+        .start_capture(PcapCapture::new(buffer.clone()).unwrap())
+        .await
+        .unwrap();
+    harness.handle.stop_capture().await.unwrap();
+
+    harness
+        .test_request_response(CLEAR_RESTART_IIN, RESPONSE_NO_RESTART_IIN)
+        .await;
+    harness
+        .test_request_response(DELAY_MEASURE, RESPONSE_TIME_DELAY)
+        .await;
+
+    assert!(!contains(&buffer.0.lock().unwrap(), DELAY_MEASURE));
+}
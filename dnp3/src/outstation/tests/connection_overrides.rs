@@ -0,0 +1,81 @@
+use crate::app::BufferSize;
+use crate::link::EndpointAddress;
+use crate::outstation::config::{ConnectionOverrides, Feature, OverrideRule};
+use crate::outstation::database::{Add, AnalogInputConfig};
+use crate::outstation::tests::harness::*;
+
+const INTEGRITY_POLL: &[u8] = &[0xC0, 0x01, 0x3C, 0x01, 0x06];
+const NULL_UNSOL: &[u8] = &[0xF0, 0x82, 0x80, 0x00];
+
+fn rule(master_address: Option<u16>, overrides: ConnectionOverrides) -> Vec<OverrideRule> {
+    vec![OverrideRule {
+        master_address: master_address.map(|x| EndpointAddress::try_new(x).unwrap()),
+        overrides,
+    }]
+}
+
+fn unsolicited_disabled() -> ConnectionOverrides {
+    ConnectionOverrides {
+        unsolicited: Some(Feature::Disabled),
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn solicited_fragment_size_can_be_overridden() {
+    let overrides = ConnectionOverrides {
+        solicited_buffer_size: Some(BufferSize::min()),
+        ..Default::default()
+    };
+    let mut harness = new_harness_with_overrides(get_default_config(), rule(None, overrides));
+    harness.handle.database.transaction(|db| {
+        for index in 0..60 {
+            db.add(index, None, AnalogInputConfig::default());
+        }
+    });
+
+    harness.send_and_process(INTEGRITY_POLL).await;
+    let fragment = harness.expect_write().await;
+
+    // the response no longer fits in a single fragment: FIR and CON without FIN
+    assert_eq!(fragment[0], 0xA0);
+    assert!(fragment.len() <= BufferSize::<249, 2048>::MIN);
+}
+
+#[tokio::test]
+async fn unsolicited_can_be_disabled_for_a_connection() {
+    let mut harness = new_harness_with_overrides(
+        get_default_unsolicited_config(),
+        rule(None, unsolicited_disabled()),
+    );
+
+    // no NULL unsolicited response is sent before the solicited response
+    harness
+        .test_request_response(INTEGRITY_POLL, &[0xC0, 0x81, 0x80, 0x00])
+        .await;
+    harness.expect_no_response();
+}
+
+#[tokio::test]
+async fn overrides_apply_to_matching_master_address() {
+    // the default configuration communicates with master address 1
+    let mut harness = new_harness_with_overrides(
+        get_default_unsolicited_config(),
+        rule(Some(1), unsolicited_disabled()),
+    );
+
+    harness
+        .test_request_response(INTEGRITY_POLL, &[0xC0, 0x81, 0x80, 0x00])
+        .await;
+    harness.expect_no_response();
+}
+
+#[tokio::test]
+async fn overrides_do_not_apply_to_other_master_addresses() {
+    let mut harness = new_harness_with_overrides(
+        get_default_unsolicited_config(),
+        rule(Some(2), unsolicited_disabled()),
+    );
+
+    harness.expect_response(NULL_UNSOL).await;
+}
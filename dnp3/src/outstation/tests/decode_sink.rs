@@ -0,0 +1,73 @@
+use std::sync::{Arc, Mutex};
+
+use crate::app::FunctionCode;
+use crate::decode::*;
+use crate::outstation::tests::harness::*;
+
+const CLEAR_RESTART_IIN: &[u8] = &[0xC0, 0x02, 80, 1, 0x00, 7, 7, 0x00];
+const RESPONSE_NO_RESTART_IIN: &[u8] = &[0xC0, 0x81, 0x00, 0x00];
+const DELAY_MEASURE: &[u8] = &[0xC1, 23];
+const RESPONSE_TIME_DELAY: &[u8] = &[0xC1, 0x81, 0x00, 0x00, 0x34, 0x02, 0x07, 0x01, 0x00, 0x00];
+
+#[derive(Default)]
+struct Events(Mutex<Vec<ApplicationEvent>>);
+
+impl DecodeSink for Events {
+    fn on_application(&self, event: &ApplicationEvent) {
+        self.0.lock().unwrap().push(event.clone());
+    }
+}
+
+#[tokio::test]
+async fn sink_receives_requests_and_responses() {
+    let mut config = get_default_config();
+    config.decode_level = AppDecodeLevel::ObjectHeaders.into();
+    let mut harness = new_harness(config);
+    let events = Arc::new(Events::default());
+
+    harness
+        .handle
+        .set_decode_sink(Some(events.clone()))
+        .await
+        .unwrap();
+
+    // the sink is guaranteed to be set once the outstation is waiting for the next request
+    harness
+        .test_request_response(CLEAR_RESTART_IIN, RESPONSE_NO_RESTART_IIN)
+        .await;
+    events.0.lock().unwrap().clear();
+    harness
+        .test_request_response(DELAY_MEASURE, RESPONSE_TIME_DELAY)
+        .await;
+
+    let events = events.0.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].direction, DecodeDirection::Rx);
+    assert_eq!(events[0].function, FunctionCode::DelayMeasure);
+    assert_eq!(events[1].direction, DecodeDirection::Tx);
+    assert_eq!(events[1].function, FunctionCode::Response);
+    assert_eq!(events[1].headers.as_ref().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn nothing_is_received_after_sink_is_cleared() {
+    let mut config = get_default_config();
+    config.decode_level = AppDecodeLevel::Header.into();
+    let mut harness = new_harness(config);
+    let events = Arc::new(Events::default());
+
+    harness
+        .handle
+        .set_decode_sink(Some(events.clone()))
+        .await
+        .unwrap();
+    harness.handle.set_decode_sink(None).await.unwrap();
+    harness
+        .test_request_response(CLEAR_RESTART_IIN, RESPONSE_NO_RESTART_IIN)
+        .await;
+    harness
+        .test_request_response(DELAY_MEASURE, RESPONSE_TIME_DELAY)
+        .await;
+
+    assert!(events.0.lock().unwrap().is_empty());
+}
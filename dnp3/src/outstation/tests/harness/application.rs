@@ -1,10 +1,11 @@
 use std::sync::{Arc, Mutex};
 
+use crate::app::measurement::{AnalogInput, Flags, Time};
 use crate::app::{MaybeAsync, Timestamp};
-use crate::outstation::database::DatabaseHandle;
+use crate::outstation::database::{DatabaseHandle, Update, UpdateOptions};
 use crate::outstation::tests::harness::{Event, EventSender};
 use crate::outstation::traits::{OutstationApplication, RequestError, RestartDelay};
-use crate::outstation::{BufferState, FreezeIndices, FreezeType};
+use crate::outstation::{BufferState, FreezeIndices, FreezeType, ReadObjects};
 
 pub(crate) struct MockOutstationApplication {
     events: EventSender,
@@ -14,6 +15,10 @@ pub(crate) struct MockOutstationApplication {
 pub(crate) struct ApplicationData {
     pub(crate) processing_delay: u16,
     pub(crate) restart_delay: Option<RestartDelay>,
+    /// headers received by each call to `before_read`
+    pub(crate) read_headers: Vec<Vec<ReadObjects>>,
+    /// analog input 0 is updated to this value after the delay in `before_read`
+    pub(crate) refresh: Option<(std::time::Duration, f64)>,
 }
 
 impl ApplicationData {
@@ -21,6 +26,8 @@ impl ApplicationData {
         Self {
             processing_delay: 0,
             restart_delay: None,
+            read_headers: Vec::new(),
+            refresh: None,
         }
     }
 }
@@ -83,6 +90,31 @@ impl OutstationApplication for MockOutstationApplication {
         MaybeAsync::ready(())
     }
 
+    fn before_read(
+        &mut self,
+        headers: &[ReadObjects],
+        database: &mut DatabaseHandle,
+    ) -> MaybeAsync<()> {
+        let mut data = self.data.lock().unwrap();
+        data.read_headers.push(headers.to_vec());
+        match data.refresh {
+            None => MaybeAsync::ready(()),
+            Some((delay, value)) => {
+                let database = database.clone();
+                MaybeAsync::asynchronous(async move {
+                    tokio::time::sleep(delay).await;
+                    database.transaction(|db| {
+                        db.update(
+                            0,
+                            &AnalogInput::new(value, Flags::ONLINE, Time::synchronized(0)),
+                            UpdateOptions::no_event(),
+                        );
+                    });
+                })
+            }
+        }
+    }
+
     fn begin_confirm(&mut self) {
         self.events.send(Event::BeginConfirm);
     }
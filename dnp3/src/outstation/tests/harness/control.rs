@@ -4,14 +4,44 @@ use crate::app::MaybeAsync;
 use crate::outstation::database::DatabaseHandle;
 use crate::outstation::tests::harness::{Control, Event, EventSender};
 use crate::outstation::traits::{ControlHandler, ControlSupport, OperateType};
+use crate::outstation::{OperateCompletion, OperateResult};
+use std::sync::{Arc, Mutex};
+
+#[derive(Default)]
+pub(crate) struct ControlData {
+    /// accept operations and complete them later via the stored completions
+    pub(crate) complete_later: bool,
+    pub(crate) completions: Vec<OperateCompletion>,
+    /// accept operations and complete them with this status before returning them
+    pub(crate) complete_before_return: Option<CommandStatus>,
+}
 
 pub(crate) struct MockControlHandler {
     events: EventSender,
+    data: Arc<Mutex<ControlData>>,
 }
 
 impl MockControlHandler {
-    pub(crate) fn create(events: EventSender) -> Box<dyn ControlHandler> {
-        Box::new(Self { events })
+    pub(crate) fn create(
+        events: EventSender,
+    ) -> (Arc<Mutex<ControlData>>, Box<dyn ControlHandler>) {
+        let data = Arc::new(Mutex::new(ControlData::default()));
+        (data.clone(), Box::new(Self { events, data }))
+    }
+
+    fn result(&self, status: CommandStatus) -> OperateResult {
+        let mut data = self.data.lock().unwrap();
+        if let Some(status) = data.complete_before_return {
+            let (result, completion) = OperateResult::pending();
+            completion.complete(status, None);
+            result
+        } else if data.complete_later {
+            let (result, completion) = OperateResult::pending();
+            data.completions.push(completion);
+            result
+        } else {
+            OperateResult::Complete(status)
+        }
     }
 }
 
@@ -38,6 +68,17 @@ impl ControlSupport<Group12Var1> for MockControlHandler {
             .send(Event::Operate(Control::G12V1(control, index), op_type));
         CommandStatus::Success
     }
+
+    fn start_operate(
+        &mut self,
+        control: Group12Var1,
+        index: u16,
+        op_type: OperateType,
+        database: &mut DatabaseHandle,
+    ) -> OperateResult {
+        let status = self.operate(control, index, op_type, database);
+        self.result(status)
+    }
 }
 
 impl ControlSupport<Group41Var1> for MockControlHandler {
@@ -63,6 +104,17 @@ impl ControlSupport<Group41Var1> for MockControlHandler {
             .send(Event::Operate(Control::G41V1(control, index), op_type));
         CommandStatus::Success
     }
+
+    fn start_operate(
+        &mut self,
+        control: Group41Var1,
+        index: u16,
+        op_type: OperateType,
+        database: &mut DatabaseHandle,
+    ) -> OperateResult {
+        let status = self.operate(control, index, op_type, database);
+        self.result(status)
+    }
 }
 
 impl ControlSupport<Group41Var2> for MockControlHandler {
@@ -88,6 +140,17 @@ impl ControlSupport<Group41Var2> for MockControlHandler {
             .send(Event::Operate(Control::G41V2(control, index), op_type));
         CommandStatus::Success
     }
+
+    fn start_operate(
+        &mut self,
+        control: Group41Var2,
+        index: u16,
+        op_type: OperateType,
+        database: &mut DatabaseHandle,
+    ) -> OperateResult {
+        let status = self.operate(control, index, op_type, database);
+        self.result(status)
+    }
 }
 
 impl ControlSupport<Group41Var3> for MockControlHandler {
@@ -113,6 +176,17 @@ impl ControlSupport<Group41Var3> for MockControlHandler {
             .send(Event::Operate(Control::G41V3(control, index), op_type));
         CommandStatus::Success
     }
+
+    fn start_operate(
+        &mut self,
+        control: Group41Var3,
+        index: u16,
+        op_type: OperateType,
+        database: &mut DatabaseHandle,
+    ) -> OperateResult {
+        let status = self.operate(control, index, op_type, database);
+        self.result(status)
+    }
 }
 
 impl ControlSupport<Group41Var4> for MockControlHandler {
@@ -138,6 +212,17 @@ impl ControlSupport<Group41Var4> for MockControlHandler {
             .send(Event::Operate(Control::G41V4(control, index), op_type));
         CommandStatus::Success
     }
+
+    fn start_operate(
+        &mut self,
+        control: Group41Var4,
+        index: u16,
+        op_type: OperateType,
+        database: &mut DatabaseHandle,
+    ) -> OperateResult {
+        let status = self.operate(control, index, op_type, database);
+        self.result(status)
+    }
 }
 
 impl ControlHandler for MockControlHandler {
@@ -3,11 +3,11 @@ use crate::decode::AppDecodeLevel;
 use crate::link::header::{BroadcastConfirmMode, FrameInfo, FrameType};
 use crate::link::reader::LinkModes;
 use crate::link::EndpointAddress;
-use crate::outstation::config::{Feature, OutstationConfig};
-use crate::outstation::database::EventBufferConfig;
+use crate::outstation::config::{Feature, OutstationConfig, OverrideRule};
+use crate::outstation::database::{DatabaseHandle, EventBufferConfig};
 use crate::outstation::task::OutstationTask;
 use crate::outstation::tests::harness::{
-    event_handlers, ApplicationData, Event, EventReceiver, MockControlHandler,
+    event_handlers, ApplicationData, ControlData, Event, EventReceiver, MockControlHandler,
     MockOutstationApplication, MockOutstationInformation,
 };
 use crate::outstation::OutstationHandle;
@@ -40,6 +40,7 @@ pub(crate) struct OutstationHarness {
     task: JoinHandle<RunError>,
     events: EventReceiver,
     pub(crate) application_data: Arc<Mutex<ApplicationData>>,
+    pub(crate) control_data: Arc<Mutex<ControlData>>,
 }
 
 impl OutstationHarness {
@@ -71,6 +72,12 @@ impl OutstationHarness {
         assert_eq!(self.io.next_event().await, sfio_tokio_mock_io::Event::Read);
     }
 
+    /// stop the outstation task and wait until it has been dropped
+    pub(crate) async fn shutdown(self) {
+        self.task.abort();
+        let _ = self.task.await;
+    }
+
     pub(crate) async fn wait_for_events(&mut self, expected: &[Event]) {
         for event in expected {
             let next = self.events.next().await;
@@ -101,44 +108,75 @@ impl OutstationHarness {
 }
 
 pub(crate) fn new_harness(config: OutstationConfig) -> OutstationHarness {
-    new_harness_impl(config, None, None)
+    new_harness_impl(config, None, None, Vec::new(), None)
+}
+
+pub(crate) fn new_harness_with_overrides(
+    config: OutstationConfig,
+    rules: Vec<OverrideRule>,
+) -> OutstationHarness {
+    new_harness_impl(config, None, None, rules, None)
 }
 
 pub(crate) fn new_harness_with_master_addr(
     config: OutstationConfig,
     master_address: EndpointAddress,
 ) -> OutstationHarness {
-    new_harness_impl(config, None, Some(master_address))
+    new_harness_impl(config, None, Some(master_address), Vec::new(), None)
 }
 
 pub(crate) fn new_harness_for_broadcast(
     config: OutstationConfig,
     broadcast: BroadcastConfirmMode,
 ) -> OutstationHarness {
-    new_harness_impl(config, Some(broadcast), None)
+    new_harness_impl(config, Some(broadcast), None, Vec::new(), None)
+}
+
+pub(crate) fn new_harness_sharing_database(
+    config: OutstationConfig,
+    database: &DatabaseHandle,
+) -> OutstationHarness {
+    new_harness_impl(config, None, None, Vec::new(), Some(database))
 }
 
 fn new_harness_impl(
     config: OutstationConfig,
     broadcast: Option<BroadcastConfirmMode>,
     master_address: Option<EndpointAddress>,
+    override_rules: Vec<OverrideRule>,
+    database: Option<&DatabaseHandle>,
 ) -> OutstationHarness {
     let (sender, receiver) = event_handlers();
 
     let (data, application) = MockOutstationApplication::create(sender.clone());
-
-    let (task, handle) = OutstationTask::create(
-        Enabled::Yes,
-        LinkModes::test(),
-        ParseOptions::get_static(),
-        config,
-        PhysAddr::None,
-        application,
-        MockOutstationInformation::create(sender.clone()),
-        MockControlHandler::create(sender.clone()),
-    );
+    let (control_data, control_handler) = MockControlHandler::create(sender.clone());
+
+    let (task, handle) = match database {
+        None => OutstationTask::create(
+            Enabled::Yes,
+            LinkModes::test(),
+            ParseOptions::get_static(),
+            config,
+            PhysAddr::None,
+            application,
+            MockOutstationInformation::create(sender.clone()),
+            control_handler,
+        ),
+        Some(database) => OutstationTask::join(
+            Enabled::Yes,
+            LinkModes::test(),
+            ParseOptions::get_static(),
+            config,
+            PhysAddr::None,
+            application,
+            MockOutstationInformation::create(sender.clone()),
+            control_handler,
+            database,
+        ),
+    };
 
     let mut task = Box::new(task);
+    task.set_connection_overrides(override_rules);
 
     let master_address = master_address.unwrap_or(config.master_address);
 
@@ -161,5 +199,6 @@ fn new_harness_impl(
         task: tokio::spawn(async move { task.run(&mut io).await }),
         events: receiver,
         application_data: data,
+        control_data,
     }
 }
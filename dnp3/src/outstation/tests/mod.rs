@@ -2,26 +2,46 @@ pub(crate) mod harness;
 
 /// respond/ignore addresses
 mod addressing;
+/// refreshing values before READ responses
+mod before_read;
+/// recording traffic to pcapng
+mod capture;
+/// per-connection configuration overrides
+mod connection_overrides;
 /// control functionality
 mod controls;
+/// structured decode events
+mod decode_sink;
 /// freeze counters tests
 mod freeze;
 /// various IIN bit tests
 mod iin;
 /// encoding tests for octet strings
 mod octet_strings;
+/// controls that complete after the response is sent
+mod pending_operations;
+/// quality management of points by source
+mod point_quality;
 /// reading g34
 mod read_dead_band;
 /// state machine for READ requests
 mod read_states;
 /// clear restart IIN + cold/warm restart
 mod restart;
+/// several outstations sharing one database
+mod shared_database;
+/// reading sparse index spaces
+mod sparse_indices;
+/// communication statistics
+mod statistics;
 /// time synchronization
 mod time;
 /// unsolicited responses
 mod unsolicited;
 /// writing g34
 mod write_dead_band;
+/// writing point values
+mod write_values;
 
 /// test data for use in multiple tests
 mod data {
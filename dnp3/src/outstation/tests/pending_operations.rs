@@ -0,0 +1,262 @@
+use crate::app::control::*;
+use crate::app::measurement::Time;
+use crate::app::variations::{Group12Var1, Group41Var2};
+use crate::outstation::database::*;
+use crate::outstation::tests::harness::*;
+use crate::outstation::traits::OperateType;
+
+const READ_CLASS_1_SEQ1: &[u8] = &[0xC1, 0x01, 60, 2, 0x06];
+const EMPTY_RESPONSE_SEQ1: &[u8] = &[0xC1, 0x81, 0x80, 0x00];
+
+fn harness_with_outputs() -> OutstationHarness {
+    let harness = new_harness(get_default_config());
+    harness.handle.transaction(|db| {
+        db.add(
+            3,
+            Some(EventClass::Class1),
+            BinaryOutputStatusConfig::default(),
+        );
+        db.add(
+            7,
+            Some(EventClass::Class1),
+            AnalogOutputStatusConfig::default(),
+        );
+    });
+    harness.control_data.lock().unwrap().complete_later = true;
+    harness
+}
+
+fn complete_operation(harness: &OutstationHarness, status: CommandStatus, time: Option<Time>) {
+    let completion = harness
+        .control_data
+        .lock()
+        .unwrap()
+        .completions
+        .pop()
+        .unwrap();
+    completion.complete(status, time);
+}
+
+fn complete_first_operation(harness: &OutstationHarness, status: CommandStatus) {
+    let completion = harness.control_data.lock().unwrap().completions.remove(0);
+    completion.complete(status, None);
+}
+
+#[tokio::test]
+#[rustfmt::skip]
+async fn completion_records_command_event_and_output_status() {
+    let mut harness = harness_with_outputs();
+    let crob = Group12Var1::from_code(ControlCode::from_op_type(OpType::LatchOn));
+
+    // direct operate g12v1, index 3, latch on
+    harness
+        .test_request_response(
+            &[0xC0, 0x05, 12, 1, 0x17, 0x01, 0x03, 0x03, 0x01, 0xE8, 0x03, 0, 0, 0xE8, 0x03, 0, 0, 0x00],
+            &[0xC0, 0x81, 0x80, 0x00, 12, 1, 0x17, 0x01, 0x03, 0x03, 0x01, 0xE8, 0x03, 0, 0, 0xE8, 0x03, 0, 0, 0x00],
+        )
+        .await;
+    harness.check_events(&[
+        Event::BeginControls,
+        Event::Operate(Control::G12V1(crob, 3), OperateType::DirectOperate),
+        Event::EndControls,
+    ]);
+
+    // nothing is reported until the operation completes
+    harness
+        .test_request_response(READ_CLASS_1_SEQ1, EMPTY_RESPONSE_SEQ1)
+        .await;
+
+    complete_operation(&harness, CommandStatus::Success, Some(Time::synchronized(1)));
+
+    harness
+        .test_request_response(
+            &[0xC2, 0x01, 60, 2, 0x06],
+            &[
+                0xE2, 0x81, 0x80, 0x00,
+                // g13v2 - commanded state == 1, status == SUCCESS
+                13, 2, 0x28, 0x01, 0x00, 0x03, 0x00, 0x80, 0x01, 0, 0, 0, 0, 0,
+                // g11v2 - the output status changed to the commanded state, flags == RESTART
+                11, 2, 0x28, 0x01, 0x00, 0x03, 0x00, 0x82, 0x01, 0, 0, 0, 0, 0,
+            ],
+        )
+        .await;
+}
+
+#[tokio::test]
+#[rustfmt::skip]
+async fn dropped_completion_reports_hardware_error() {
+    let mut harness = harness_with_outputs();
+
+    // direct operate g41v2, index 7, value 513
+    harness
+        .test_request_response(
+            &[0xC0, 0x05, 41, 2, 0x17, 0x01, 0x07, 0x01, 0x02, 0x00],
+            &[0xC0, 0x81, 0x80, 0x00, 41, 2, 0x17, 0x01, 0x07, 0x01, 0x02, 0x00],
+        )
+        .await;
+    harness.check_events(&[
+        Event::BeginControls,
+        Event::Operate(Control::G41V2(Group41Var2::new(513), 7), OperateType::DirectOperate),
+        Event::EndControls,
+    ]);
+
+    harness.control_data.lock().unwrap().completions.clear();
+
+    // g43v2 - status == HARDWARE_ERROR, value == 513, and no change to the output status
+    harness
+        .test_request_response(
+            READ_CLASS_1_SEQ1,
+            &[0xE1, 0x81, 0x80, 0x00, 43, 2, 0x28, 0x01, 0x00, 0x07, 0x00, 0x06, 0x01, 0x02],
+        )
+        .await;
+}
+
+#[tokio::test]
+#[rustfmt::skip]
+async fn completed_pulse_does_not_change_output_status() {
+    let mut harness = harness_with_outputs();
+    let crob = Group12Var1::from_code(ControlCode::from_op_type(OpType::PulseOn));
+    const CROB: &[u8] = &[12, 1, 0x17, 0x01, 0x03, 0x01, 0x01, 0xE8, 0x03, 0, 0, 0xE8, 0x03, 0, 0, 0x00];
+
+    harness
+        .test_request_response(
+            &[&[0xC0, 0x03], CROB].concat(),
+            &[&[0xC0, 0x81, 0x80, 0x00], CROB].concat(),
+        )
+        .await;
+    harness
+        .test_request_response(
+            &[&[0xC1, 0x04], CROB].concat(),
+            &[&[0xC1, 0x81, 0x80, 0x00], CROB].concat(),
+        )
+        .await;
+    harness.check_events(&[
+        Event::BeginControls,
+        Event::Select(Control::G12V1(crob, 3)),
+        Event::EndControls,
+        Event::BeginControls,
+        Event::Operate(Control::G12V1(crob, 3), OperateType::SelectBeforeOperate),
+        Event::EndControls,
+    ]);
+
+    complete_operation(&harness, CommandStatus::Success, None);
+
+    // g13v1 - commanded state == 1, status == SUCCESS
+    harness
+        .test_request_response(
+            &[0xC2, 0x01, 60, 2, 0x06],
+            &[0xE2, 0x81, 0x80, 0x00, 13, 1, 0x28, 0x01, 0x00, 0x03, 0x00, 0x80],
+        )
+        .await;
+}
+
+#[tokio::test]
+#[rustfmt::skip]
+async fn operation_completed_before_it_is_returned_records_command_event() {
+    let mut harness = harness_with_outputs();
+    harness.control_data.lock().unwrap().complete_before_return = Some(CommandStatus::Success);
+
+    // direct operate g41v2, index 7, value 513, the command event is recorded immediately
+    harness
+        .test_request_response(
+            &[0xC0, 0x05, 41, 2, 0x17, 0x01, 0x07, 0x01, 0x02, 0x00],
+            &[0xC0, 0x81, 0x82, 0x00, 41, 2, 0x17, 0x01, 0x07, 0x01, 0x02, 0x00],
+        )
+        .await;
+
+    harness
+        .test_request_response(
+            READ_CLASS_1_SEQ1,
+            &[
+                0xE1, 0x81, 0x80, 0x00,
+                // g43v2 - status == SUCCESS, value == 513
+                43, 2, 0x28, 0x01, 0x00, 0x07, 0x00, 0x00, 0x01, 0x02,
+                // g42v1 - the output status changed to 513, flags == RESTART
+                42, 1, 0x28, 0x01, 0x00, 0x07, 0x00, 0x02, 0x01, 0x02, 0x00, 0x00,
+            ],
+        )
+        .await;
+}
+
+#[tokio::test]
+#[rustfmt::skip]
+async fn operations_may_complete_in_any_order() {
+    let mut harness = harness_with_outputs();
+
+    // direct operate g12v1, index 3, latch on
+    harness
+        .test_request_response(
+            &[0xC0, 0x05, 12, 1, 0x17, 0x01, 0x03, 0x03, 0x01, 0xE8, 0x03, 0, 0, 0xE8, 0x03, 0, 0, 0x00],
+            &[0xC0, 0x81, 0x80, 0x00, 12, 1, 0x17, 0x01, 0x03, 0x03, 0x01, 0xE8, 0x03, 0, 0, 0xE8, 0x03, 0, 0, 0x00],
+        )
+        .await;
+    // direct operate g41v2, index 7, value 513
+    harness
+        .test_request_response(
+            &[0xC1, 0x05, 41, 2, 0x17, 0x01, 0x07, 0x01, 0x02, 0x00],
+            &[0xC1, 0x81, 0x80, 0x00, 41, 2, 0x17, 0x01, 0x07, 0x01, 0x02, 0x00],
+        )
+        .await;
+
+    // the second operation completes first
+    complete_operation(&harness, CommandStatus::Success, None);
+    complete_first_operation(&harness, CommandStatus::Timeout);
+
+    harness
+        .test_request_response(
+            &[0xC2, 0x01, 60, 2, 0x06],
+            &[
+                0xE2, 0x81, 0x80, 0x00,
+                // g43v2 - status == SUCCESS, value == 513
+                43, 2, 0x28, 0x01, 0x00, 0x07, 0x00, 0x00, 0x01, 0x02,
+                // g42v1 - the output status changed to 513, flags == RESTART
+                42, 1, 0x28, 0x01, 0x00, 0x07, 0x00, 0x02, 0x01, 0x02, 0x00, 0x00,
+                // g13v1 - commanded state == 1, status == TIMEOUT
+                13, 1, 0x28, 0x01, 0x00, 0x03, 0x00, 0x81,
+            ],
+        )
+        .await;
+}
+
+#[tokio::test]
+#[rustfmt::skip]
+async fn operate_on_point_with_pending_operation_is_rejected() {
+    let mut harness = harness_with_outputs();
+    let request = |seq: u8| [0xC0 | seq, 0x05, 41, 2, 0x17, 0x01, 0x07, 0x01, 0x02, 0x00];
+
+    // direct operate g41v2, index 7, value 513
+    harness
+        .test_request_response(
+            &request(0),
+            &[0xC0, 0x81, 0x80, 0x00, 41, 2, 0x17, 0x01, 0x07, 0x01, 0x02, 0x00],
+        )
+        .await;
+    harness.check_events(&[
+        Event::BeginControls,
+        Event::Operate(Control::G41V2(Group41Var2::new(513), 7), OperateType::DirectOperate),
+        Event::EndControls,
+    ]);
+
+    // status == ALREADY_ACTIVE and the control handler is not called
+    harness
+        .test_request_response(
+            &request(1),
+            &[0xC1, 0x81, 0x80, 0x00, 41, 2, 0x17, 0x01, 0x07, 0x01, 0x02, 0x05],
+        )
+        .await;
+    harness.check_events(&[Event::BeginControls, Event::EndControls]);
+
+    // the point can be operated again once the operation completes
+    complete_operation(&harness, CommandStatus::Success, None);
+    harness
+        .test_request_response(
+            &request(2),
+            &[0xC2, 0x81, 0x82, 0x00, 41, 2, 0x17, 0x01, 0x07, 0x01, 0x02, 0x00],
+        )
+        .await;
+    harness.check_events(&[
+        Event::BeginControls,
+        Event::Operate(Control::G41V2(Group41Var2::new(513), 7), OperateType::DirectOperate),
+        Event::EndControls,
+    ]);
+}
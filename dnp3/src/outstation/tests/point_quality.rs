@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use crate::app::measurement::*;
+use crate::outstation::database::*;
+use crate::outstation::ClockConfig;
+
+use super::harness::*;
+
+const SOURCE: SourceId = SourceId(1);
+const READ_CLASS_1: &[u8] = &[0xC0, 0x01, 60, 2, 0x06];
+const READ_CLASS_1_SEQ_1: &[u8] = &[0xC1, 0x01, 60, 2, 0x06];
+const EMPTY_RESPONSE: &[u8] = &[0xC0, 0x81, 0x80, 0x00];
+const RECORD_CURRENT_TIME: &[u8] = &[0xC0, 0x18];
+const NEED_TIME_RESPONSE: &[u8] = &[0xC0, 0x81, 0x90, 0x00];
+const WRITE_LAST_RECORDED_TIME_SEQ_1: &[u8] = &[
+    0xC1, 0x02, 50, 3, 0x07, 1, 0xFE, 0xCA, 0x00, 0x00, 0x00, 0x00,
+];
+const EMPTY_RESPONSE_SEQ_1: &[u8] = &[0xC1, 0x81, 0x80, 0x00];
+// g2v1 for index 0 with only COMM_LOST set
+const COMM_LOST_EVENT_RESPONSE_SEQ_1: &[u8] = &[
+    0xE1, 0x81, 0x80, 0x00, 0x02, 0x01, 0x28, 0x01, 0x00, 0x00, 0x00, 0x04,
+];
+
+fn online_binary() -> BinaryInput {
+    BinaryInput::new(false, Flags::ONLINE, Time::synchronized(0))
+}
+
+fn add_binary_with_stale_detection(db: &mut Database) {
+    db.add(0, Some(EventClass::Class1), BinaryInputConfig::default());
+    db.update(0, &online_binary(), UpdateOptions::no_event());
+    db.set_source(UpdateFlagsType::BinaryInput, 0, Some(SOURCE));
+    db.set_stale_detection(
+        SOURCE,
+        Some(StaleConfig::comm_lost(Duration::from_secs(10))),
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn stale_source_is_marked_comm_lost() {
+    let mut harness = new_harness(get_default_config());
+    harness
+        .handle
+        .database
+        .transaction(add_binary_with_stale_detection);
+
+    tokio::time::advance(Duration::from_secs(9)).await;
+    harness
+        .test_request_response(READ_CLASS_1, EMPTY_RESPONSE)
+        .await;
+
+    tokio::time::advance(Duration::from_secs(1)).await;
+    harness
+        .test_request_response(READ_CLASS_1_SEQ_1, COMM_LOST_EVENT_RESPONSE_SEQ_1)
+        .await;
+
+    let value: BinaryInput = harness.handle.database.transaction(|db| db.get(0).unwrap());
+    assert_eq!(value.flags, Flags::COMM_LOST);
+}
+
+#[tokio::test(start_paused = true)]
+async fn update_restarts_stale_timer() {
+    let mut harness = new_harness(get_default_config());
+    harness
+        .handle
+        .database
+        .transaction(add_binary_with_stale_detection);
+
+    tokio::time::advance(Duration::from_secs(6)).await;
+    harness.handle.database.transaction(|db| {
+        db.update(0, &online_binary(), UpdateOptions::no_event());
+    });
+
+    tokio::time::advance(Duration::from_secs(6)).await;
+    harness
+        .test_request_response(READ_CLASS_1, EMPTY_RESPONSE)
+        .await;
+
+    tokio::time::advance(Duration::from_secs(4)).await;
+    harness
+        .test_request_response(READ_CLASS_1_SEQ_1, COMM_LOST_EVENT_RESPONSE_SEQ_1)
+        .await;
+}
+
+#[tokio::test(start_paused = true)]
+async fn stale_source_is_detected_without_requests_and_timestamped_by_clock() {
+    let mut config = get_default_config();
+    config.clock = Some(ClockConfig::default());
+    let mut harness = new_harness(config);
+
+    // synchronize the clock to 0xCAFE using the LAN procedure
+    harness
+        .test_request_response(RECORD_CURRENT_TIME, NEED_TIME_RESPONSE)
+        .await;
+    harness
+        .test_request_response(WRITE_LAST_RECORDED_TIME_SEQ_1, EMPTY_RESPONSE_SEQ_1)
+        .await;
+
+    harness
+        .handle
+        .database
+        .transaction(add_binary_with_stale_detection);
+
+    // the source becomes stale while the outstation is idle
+    tokio::time::sleep(Duration::from_secs(11)).await;
+
+    let value: BinaryInput = harness.handle.database.transaction(|db| db.get(0).unwrap());
+    assert_eq!(value.flags, Flags::COMM_LOST);
+    assert_eq!(value.time, Some(Time::synchronized(0xCAFE + 10_000)));
+}
@@ -0,0 +1,150 @@
+use crate::app::measurement::*;
+use crate::app::*;
+use crate::outstation::database::*;
+use crate::outstation::tests::harness::*;
+
+const READ_CLASS_123: &[u8] = &[0xC0, 0x01, 60, 2, 0x06, 60, 3, 0x06, 60, 4, 0x06];
+const READ_CLASS_123_SEQ_1: &[u8] = &[0xC1, 0x01, 60, 2, 0x06, 60, 3, 0x06, 60, 4, 0x06];
+const CONFIRM_SEQ_0: &[u8] = &[0xC0, 0x00];
+const INTEGRITY_POLL: &[u8] = &[0xC0, 0x01, 0x3C, 0x01, 0x06];
+const BINARY_EVENT_RESPONSE: &[u8] = &[
+    0xE0, 0x81, 0x80, 0x00, 0x02, 0x01, 0x28, 0x01, 0x00, 0x00, 0x00, 0x81,
+];
+
+fn binary(value: bool) -> BinaryInput {
+    BinaryInput::new(value, Flags::ONLINE, Time::Synchronized(Timestamp::new(0)))
+}
+
+#[tokio::test]
+async fn each_outstation_buffers_and_confirms_its_own_events() {
+    let mut first = new_harness(get_default_config());
+    let mut second = new_harness_sharing_database(get_default_config(), &first.handle.database);
+
+    first.handle.database.transaction(|db| {
+        db.add(0, Some(EventClass::Class1), BinaryInputConfig::default());
+        db.update(0, &binary(true), UpdateOptions::default());
+    });
+
+    first
+        .test_request_response(READ_CLASS_123, BINARY_EVENT_RESPONSE)
+        .await;
+    first.send_and_process(CONFIRM_SEQ_0).await;
+    first
+        .test_request_response(READ_CLASS_123_SEQ_1, &[0xC1, 0x81, 0x80, 0x00])
+        .await;
+
+    // the event confirmed by the first master is still buffered for the second one
+    second
+        .test_request_response(READ_CLASS_123, BINARY_EVENT_RESPONSE)
+        .await;
+}
+
+#[tokio::test]
+async fn updates_from_any_handle_apply_to_all_outstations() {
+    let mut first = new_harness(get_default_config());
+    let mut second = new_harness_sharing_database(get_default_config(), &first.handle.database);
+
+    first.handle.database.transaction(|db| {
+        db.add(0, Some(EventClass::Class1), BinaryInputConfig::default());
+    });
+    second.handle.database.transaction(|db| {
+        db.update(0, &binary(true), UpdateOptions::default());
+    });
+
+    first
+        .test_request_response(READ_CLASS_123, BINARY_EVENT_RESPONSE)
+        .await;
+    second
+        .test_request_response(READ_CLASS_123, BINARY_EVENT_RESPONSE)
+        .await;
+}
+
+#[tokio::test]
+async fn existing_points_are_shared_with_new_outstation() {
+    let first = new_harness(get_default_config());
+    first.handle.database.transaction(|db| {
+        db.add(0, Some(EventClass::Class1), AnalogInputConfig::default());
+        db.update(
+            0,
+            &AnalogInput::new(42.0, Flags::ONLINE, Time::Synchronized(Timestamp::new(0))),
+            UpdateOptions::no_event(),
+        );
+    });
+
+    let mut second = new_harness_sharing_database(get_default_config(), &first.handle.database);
+
+    const READ_G30_V1_0_TO_0: &[u8] = &[0xC0, 0x01, 0x1E, 0x01, 0x00, 0x00, 0x00];
+    second
+        .test_request_response(
+            READ_G30_V1_0_TO_0,
+            &[
+                0xC0, 0x81, 0x80, 0x00, 0x1E, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0x2A, 0x00,
+                0x00, 0x00,
+            ],
+        )
+        .await;
+}
+
+#[tokio::test]
+async fn event_buffer_is_removed_when_outstation_shuts_down() {
+    let first = new_harness(get_default_config());
+    let second = new_harness_sharing_database(get_default_config(), &first.handle.database);
+    let second_database = second.handle.database.clone();
+
+    first.handle.database.transaction(|db| {
+        db.add(0, Some(EventClass::Class1), BinaryInputConfig::default());
+        db.update(0, &binary(true), UpdateOptions::default());
+    });
+    assert_eq!(second_database.get_buffer_state().classes.num_class_1, 1);
+
+    second.shutdown().await;
+
+    // events are no longer buffered for the outstation that was shut down
+    first.handle.database.transaction(|db| {
+        db.update(0, &binary(false), UpdateOptions::default());
+    });
+    assert_eq!(second_database.get_buffer_state().classes.num_class_1, 0);
+    assert_eq!(
+        first.handle.database.get_buffer_state().classes.num_class_1,
+        2
+    );
+}
+
+#[tokio::test]
+async fn each_outstation_reports_its_own_snapshot_of_selected_values() {
+    let mut config = get_default_config();
+    config.solicited_buffer_size = BufferSize::min();
+
+    let mut first = new_harness(config);
+    let mut second = new_harness_sharing_database(config, &first.handle.database);
+
+    first.handle.database.transaction(|db| {
+        for index in 0..60 {
+            db.add(index, None, AnalogInputConfig::default());
+        }
+    });
+
+    // the response to the first master requires multiple fragments
+    first.send_and_process(INTEGRITY_POLL).await;
+    assert_eq!(first.expect_write().await[0], 0xA0);
+
+    // the second master reads the values after they change
+    let online_42 = AnalogInput::new(42.0, Flags::ONLINE, Time::Synchronized(Timestamp::new(0)));
+    first.handle.database.transaction(|db| {
+        for index in 0..60 {
+            db.update(index, &online_42, UpdateOptions::no_event());
+        }
+    });
+    second.send_and_process(INTEGRITY_POLL).await;
+    let fragment = second.expect_write().await;
+    assert!(fragment
+        .windows(5)
+        .any(|x| x == [0x01, 0x2A, 0x00, 0x00, 0x00]));
+
+    // the remaining fragments to the first master still contain the values it selected
+    first.send_and_process(CONFIRM_SEQ_0).await;
+    let fragment = first.expect_write().await;
+    assert!(!fragment
+        .windows(5)
+        .any(|x| x == [0x01, 0x2A, 0x00, 0x00, 0x00]));
+}
@@ -0,0 +1,301 @@
+use crate::app::measurement::*;
+use crate::app::parse::options::ParseOptions;
+use crate::app::parse::parser::ParsedFragment;
+use crate::app::variations::Variation;
+use crate::app::QualifierCode;
+use crate::master::extract::extract_measurements_inner;
+use crate::master::{HeaderInfo, ReadHandler};
+use crate::outstation::config::Feature;
+use crate::outstation::database::*;
+use crate::outstation::tests::harness::*;
+
+const READ_G20: &[u8] = &[0xC0, 0x01, 20, 0, 0x06];
+
+fn with_counters(indices: &[u16], prefixed: Feature) -> OutstationHarness {
+    let mut config = get_default_config();
+    config.features.index_prefixed_static = prefixed;
+    let harness = new_harness(config);
+
+    harness.handle.transaction(|db| {
+        for index in indices {
+            db.add(*index, None, CounterConfig::default());
+        }
+    });
+
+    harness
+}
+
+#[tokio::test]
+#[rustfmt::skip]
+async fn gaps_are_skipped_with_ranged_headers_by_default() {
+    let mut harness = with_counters(&[10, 20, 21, 22, 23, 40], Feature::Disabled);
+
+    harness
+        .test_request_response(
+            READ_G20,
+            &[
+                0xC0, 0x81, 0x80, 0x00,
+                20, 1, 0x01, 10, 0, 10, 0, 0x02, 0, 0, 0, 0,
+                20, 1, 0x01, 20, 0, 23, 0, 0x02, 0, 0, 0, 0, 0x02, 0, 0, 0, 0, 0x02, 0, 0, 0, 0, 0x02, 0, 0, 0, 0,
+                20, 1, 0x01, 40, 0, 40, 0, 0x02, 0, 0, 0, 0,
+            ],
+        )
+        .await;
+}
+
+#[tokio::test]
+#[rustfmt::skip]
+async fn runs_are_written_with_index_prefixes_when_smaller() {
+    let mut harness = with_counters(&[10, 20, 21, 22, 23, 40], Feature::Enabled);
+
+    // 47 bytes of objects instead of 51 bytes if the run of 4 used a ranged header
+    harness
+        .test_request_response(
+            READ_G20,
+            &[
+                0xC0, 0x81, 0x80, 0x00,
+                20, 1, 0x28, 6, 0,
+                10, 0, 0x02, 0, 0, 0, 0,
+                20, 0, 0x02, 0, 0, 0, 0,
+                21, 0, 0x02, 0, 0, 0, 0,
+                22, 0, 0x02, 0, 0, 0, 0,
+                23, 0, 0x02, 0, 0, 0, 0,
+                40, 0, 0x02, 0, 0, 0, 0,
+            ],
+        )
+        .await;
+}
+
+#[tokio::test]
+#[rustfmt::skip]
+async fn long_runs_are_written_with_ranged_headers_when_smaller() {
+    let mut harness = with_counters(&[10, 20, 21, 22, 23, 24, 25, 26, 40], Feature::Enabled);
+
+    // 66 bytes of objects instead of 68 bytes if every object was index-prefixed. The last point
+    // is the same size either way and uses a ranged header
+    harness
+        .test_request_response(
+            READ_G20,
+            &[
+                0xC0, 0x81, 0x80, 0x00,
+                20, 1, 0x28, 1, 0, 10, 0, 0x02, 0, 0, 0, 0,
+                20, 1, 0x01, 20, 0, 26, 0,
+                0x02, 0, 0, 0, 0, 0x02, 0, 0, 0, 0, 0x02, 0, 0, 0, 0, 0x02, 0, 0, 0, 0,
+                0x02, 0, 0, 0, 0, 0x02, 0, 0, 0, 0, 0x02, 0, 0, 0, 0,
+                20, 1, 0x01, 40, 0, 40, 0, 0x02, 0, 0, 0, 0,
+            ],
+        )
+        .await;
+}
+
+#[tokio::test]
+#[rustfmt::skip]
+async fn single_run_is_written_with_ranged_header() {
+    let mut harness = with_counters(&[7, 8], Feature::Enabled);
+
+    harness
+        .test_request_response(
+            READ_G20,
+            &[
+                0xC0, 0x81, 0x80, 0x00,
+                20, 1, 0x01, 7, 0, 8, 0, 0x02, 0, 0, 0, 0, 0x02, 0, 0, 0, 0,
+            ],
+        )
+        .await;
+}
+
+#[tokio::test]
+#[rustfmt::skip]
+async fn consecutive_short_runs_share_an_index_prefixed_header() {
+    let mut harness = with_counters(&[10, 12, 13, 65535], Feature::Enabled);
+
+    harness
+        .test_request_response(
+            READ_G20,
+            &[
+                0xC0, 0x81, 0x80, 0x00,
+                20, 1, 0x28, 4, 0,
+                10, 0, 0x02, 0, 0, 0, 0,
+                12, 0, 0x02, 0, 0, 0, 0,
+                13, 0, 0x02, 0, 0, 0, 0,
+                0xFF, 0xFF, 0x02, 0, 0, 0, 0,
+            ],
+        )
+        .await;
+}
+
+#[tokio::test]
+#[rustfmt::skip]
+async fn packed_variations_always_use_ranged_headers() {
+    let mut config = get_default_config();
+    config.features.index_prefixed_static = Feature::Enabled;
+    let mut harness = new_harness(config);
+
+    harness.handle.transaction(|db| {
+        for index in [1, 5] {
+            db.add(index, None, BinaryInputConfig::default());
+            db.update(
+                index,
+                &BinaryInput::new(true, Flags::ONLINE, Time::synchronized(0)),
+                UpdateOptions::no_event(),
+            );
+        }
+    });
+
+    harness
+        .test_request_response(
+            &[0xC0, 0x01, 1, 0, 0x06],
+            &[
+                0xC0, 0x81, 0x80, 0x00,
+                1, 1, 0x01, 1, 0, 1, 0, 0x01,
+                1, 1, 0x01, 5, 0, 5, 0, 0x01,
+            ],
+        )
+        .await;
+}
+
+/// header extracted by the master as (variation, qualifier, [(index, value)])
+type Header = (Variation, QualifierCode, Vec<(u16, f64)>);
+
+#[derive(Default)]
+struct Collector {
+    headers: Vec<Header>,
+}
+
+impl Collector {
+    fn push(&mut self, info: HeaderInfo, values: Vec<(u16, f64)>) {
+        self.headers.push((info.variation, info.qualifier, values));
+    }
+}
+
+impl ReadHandler for Collector {
+    fn handle_binary_input(
+        &mut self,
+        info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (BinaryInput, u16)>,
+    ) {
+        let values = iter
+            .map(|(v, i)| (i, f64::from(u8::from(v.value))))
+            .collect();
+        self.push(info, values);
+    }
+
+    fn handle_double_bit_binary_input(
+        &mut self,
+        info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (DoubleBitBinaryInput, u16)>,
+    ) {
+        let values = iter
+            .map(|(v, i)| (i, f64::from(v.value.to_byte())))
+            .collect();
+        self.push(info, values);
+    }
+
+    fn handle_frozen_counter(
+        &mut self,
+        info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (FrozenCounter, u16)>,
+    ) {
+        let values = iter.map(|(v, i)| (i, f64::from(v.value))).collect();
+        self.push(info, values);
+    }
+
+    fn handle_analog_input(
+        &mut self,
+        info: HeaderInfo,
+        iter: &mut dyn Iterator<Item = (AnalogInput, u16)>,
+    ) {
+        let values = iter.map(|(v, i)| (i, v.value)).collect();
+        self.push(info, values);
+    }
+}
+
+#[tokio::test]
+async fn master_parses_index_prefixed_static_objects() {
+    let mut config = get_default_config();
+    config.features.index_prefixed_static = Feature::Enabled;
+    let mut harness = new_harness(config);
+
+    let time = Time::synchronized(0);
+    harness.handle.transaction(|db| {
+        for index in [1, 5] {
+            let value = f64::from(index);
+            db.add(
+                index,
+                None,
+                BinaryInputConfig::new(
+                    StaticBinaryInputVariation::Group1Var2,
+                    EventBinaryInputVariation::Group2Var1,
+                ),
+            );
+            db.update(
+                index,
+                &BinaryInput::new(true, Flags::ONLINE, time),
+                UpdateOptions::no_event(),
+            );
+            db.add(
+                index,
+                None,
+                DoubleBitBinaryInputConfig::new(
+                    StaticDoubleBitBinaryInputVariation::Group3Var2,
+                    EventDoubleBitBinaryInputVariation::Group4Var1,
+                ),
+            );
+            db.update(
+                index,
+                &DoubleBitBinaryInput::new(DoubleBit::DeterminedOn, Flags::ONLINE, time),
+                UpdateOptions::no_event(),
+            );
+            db.add(
+                index,
+                None,
+                FrozenCounterConfig::new(
+                    StaticFrozenCounterVariation::Group21Var5,
+                    EventFrozenCounterVariation::Group23Var1,
+                    0,
+                ),
+            );
+            db.update(
+                index,
+                &FrozenCounter::new(index.into(), Flags::ONLINE, time),
+                UpdateOptions::no_event(),
+            );
+            db.add(
+                index,
+                None,
+                AnalogInputConfig::new(
+                    StaticAnalogInputVariation::Group30Var5,
+                    EventAnalogInputVariation::Group32Var1,
+                    0.0,
+                ),
+            );
+            db.update(
+                index,
+                &AnalogInput::new(value, Flags::ONLINE, time),
+                UpdateOptions::no_event(),
+            );
+        }
+    });
+
+    // class 0
+    harness.send_and_process(&[0xC0, 0x01, 60, 1, 0x06]).await;
+    let rx = harness.expect_write().await;
+    let response = ParsedFragment::parse(ParseOptions::default(), &rx)
+        .unwrap()
+        .to_response()
+        .unwrap();
+
+    let mut collector = Collector::default();
+    extract_measurements_inner(response.objects.unwrap(), &mut collector);
+
+    let prefixed = QualifierCode::CountAndPrefix16;
+    assert_eq!(
+        collector.headers,
+        [
+            (Variation::Group1Var2, prefixed, vec![(1, 1.0), (5, 1.0)]),
+            (Variation::Group3Var2, prefixed, vec![(1, 2.0), (5, 2.0)]),
+            (Variation::Group21Var5, prefixed, vec![(1, 1.0), (5, 5.0)]),
+            (Variation::Group30Var5, prefixed, vec![(1, 1.0), (5, 5.0)]),
+        ]
+    );
+}
@@ -0,0 +1,66 @@
+use crate::app::measurement::*;
+use crate::app::FunctionCode;
+use crate::outstation::database::*;
+
+use super::harness::*;
+
+const CLEAR_RESTART_IIN: &[u8] = &[0xC0, 0x02, 80, 1, 0x00, 7, 7, 0x00];
+const RESPONSE_NO_RESTART_IIN: &[u8] = &[0xC0, 0x81, 0x00, 0x00];
+const DELAY_MEASURE: &[u8] = &[0xC1, 23];
+const RESPONSE_TIME_DELAY: &[u8] = &[0xC1, 0x81, 0x00, 0x00, 0x34, 0x02, 0x07, 0x01, 0x00, 0x00];
+
+#[tokio::test]
+async fn counts_requests_by_function_code_and_traffic() {
+    let mut harness = new_harness(get_default_config());
+
+    harness
+        .test_request_response(CLEAR_RESTART_IIN, RESPONSE_NO_RESTART_IIN)
+        .await;
+    harness
+        .test_request_response(DELAY_MEASURE, RESPONSE_TIME_DELAY)
+        .await;
+    harness
+        .test_request_response(DELAY_MEASURE, RESPONSE_TIME_DELAY)
+        .await;
+
+    let statistics = harness.handle.get_statistics().await.unwrap();
+
+    assert_eq!(statistics.requests.get(&FunctionCode::Write), Some(&1));
+    assert_eq!(
+        statistics.requests.get(&FunctionCode::DelayMeasure),
+        Some(&2)
+    );
+    assert_eq!(statistics.requests.get(&FunctionCode::Read), None);
+    assert_eq!(
+        statistics.channel.bytes_rx,
+        (CLEAR_RESTART_IIN.len() + 2 * DELAY_MEASURE.len()) as u64
+    );
+    assert_eq!(
+        statistics.channel.bytes_tx,
+        (RESPONSE_NO_RESTART_IIN.len() + 2 * RESPONSE_TIME_DELAY.len()) as u64
+    );
+    assert_eq!(statistics.channel.frames_tx, 3);
+    assert_eq!(statistics.channel.reconnects, 0);
+}
+
+#[tokio::test]
+async fn tracks_event_buffer_high_water_mark_and_overflows() {
+    let mut harness = new_harness(get_default_config());
+
+    harness.handle.transaction(|db| {
+        db.add(0, Some(EventClass::Class1), BinaryInputConfig::default());
+        // the buffer holds 5 binary events
+        for i in 0..7 {
+            db.update(
+                0,
+                &BinaryInput::new(i % 2 == 0, Flags::ONLINE, Time::synchronized(0)),
+                UpdateOptions::default(),
+            );
+        }
+    });
+
+    let statistics = harness.handle.get_statistics().await.unwrap();
+
+    assert_eq!(statistics.event_buffer_high_water_mark, 5);
+    assert_eq!(statistics.event_buffer_overflows, 2);
+}
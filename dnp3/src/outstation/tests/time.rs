@@ -1,4 +1,8 @@
+use std::time::Duration;
+
+use crate::app::measurement::Time;
 use crate::app::Timestamp;
+use crate::outstation::{ClockConfig, TimeSyncMethod};
 
 use super::harness::*;
 
@@ -15,6 +19,9 @@ const WRITE_LAST_RECORDED_TIME: &[u8] = &[
 
 const EMPTY_RESPONSE_SEQ0: &[u8] = &[0xC0, 0x81, 0x80, 0x00];
 const EMPTY_RESPONSE_SEQ1: &[u8] = &[0xC1, 0x81, 0x80, 0x00];
+const NEED_TIME_RESPONSE_SEQ0: &[u8] = &[0xC0, 0x81, 0x90, 0x00];
+const RESPONSE_TIME_DELAY_FINE_ZERO_NEED_TIME: &[u8] =
+    &[0xC0, 0x81, 0x90, 0x00, 0x34, 0x02, 0x07, 0x01, 0x00, 0x00];
 
 #[tokio::test]
 async fn responds_to_delay_measure() {
@@ -58,3 +65,63 @@ async fn lan_procedure() {
 
     harness.check_events(&[Event::WriteAbsoluteTime(Timestamp::new(0xCAFE))]);
 }
+
+#[tokio::test(start_paused = true)]
+async fn clock_asserts_need_time_until_synchronized() {
+    let mut config = get_default_config();
+    config.clock = Some(ClockConfig::default());
+    let mut harness = new_harness(config);
+    let clock = harness.handle.get_clock().unwrap();
+    assert!(clock.need_time());
+
+    harness
+        .test_request_response(
+            super::data::DELAY_MEASURE,
+            RESPONSE_TIME_DELAY_FINE_ZERO_NEED_TIME,
+        )
+        .await;
+    harness
+        .test_request_response(WRITE_ABSOLUTE_TIME, EMPTY_RESPONSE_SEQ1)
+        .await;
+
+    harness.check_events(&[Event::WriteAbsoluteTime(Timestamp::new(1614271096000))]);
+    assert!(!clock.need_time());
+    assert_eq!(clock.now(), Time::synchronized(1614271096000));
+    assert_eq!(
+        clock.status().last_sync_method,
+        Some(TimeSyncMethod::NonLan)
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn clock_asserts_need_time_after_interval() {
+    let mut config = get_default_config();
+    config.clock = Some(ClockConfig::new(Duration::from_secs(600)));
+    let mut harness = new_harness(config);
+    let clock = harness.handle.get_clock().unwrap();
+
+    harness
+        .test_request_response(RECORD_CURRENT_TIME, NEED_TIME_RESPONSE_SEQ0)
+        .await;
+    harness
+        .test_request_response(WRITE_LAST_RECORDED_TIME, EMPTY_RESPONSE_SEQ1)
+        .await;
+    assert_eq!(clock.status().last_sync_method, Some(TimeSyncMethod::Lan));
+
+    tokio::time::advance(Duration::from_secs(599)).await;
+    assert_eq!(clock.now(), Time::synchronized(0xCAFE + 599_000));
+    harness
+        .test_request_response(
+            super::data::DELAY_MEASURE,
+            super::data::RESPONSE_TIME_DELAY_FINE_ZERO,
+        )
+        .await;
+
+    tokio::time::advance(Duration::from_secs(1)).await;
+    harness
+        .test_request_response(
+            super::data::DELAY_MEASURE,
+            RESPONSE_TIME_DELAY_FINE_ZERO_NEED_TIME,
+        )
+        .await;
+}
@@ -3,7 +3,7 @@ use crate::app::measurement::*;
 use crate::app::parse::options::ParseOptions;
 use crate::app::parse::parser::{HeaderDetails, ParsedFragment};
 use crate::app::{BufferSize, Timestamp};
-use crate::outstation::config::OutstationConfig;
+use crate::outstation::config::{OutstationConfig, UnsolicitedClassTrigger};
 use crate::outstation::database::*;
 use crate::outstation::{BufferState, ClassCount, TypeCount};
 
@@ -443,3 +443,60 @@ async fn buffer_overflow_issue() {
         )
         .await;
 }
+
+fn config_with_class1_trigger(trigger: UnsolicitedClassTrigger) -> OutstationConfig {
+    let mut config = get_default_unsolicited_config();
+    config.unsolicited_triggers.class1 = trigger;
+    config
+}
+
+#[tokio::test]
+async fn unsolicited_is_held_until_hold_time_elapses() {
+    let hold_time = std::time::Duration::from_secs(10);
+    let mut harness = new_harness(config_with_class1_trigger(UnsolicitedClassTrigger::new(
+        3, hold_time,
+    )));
+    confirm_null_unsolicited(&mut harness).await;
+    enable_unsolicited(&mut harness).await;
+
+    tokio::time::pause();
+    let start = tokio::time::Instant::now();
+    generate_binary_event(&mut harness.handle.database);
+
+    harness.expect_response(UNSOL_G2V1_SEQ1).await;
+    assert!(start.elapsed() >= hold_time);
+    harness.check_events(&[Event::EnterUnsolicitedConfirmWait(1)]);
+}
+
+#[tokio::test]
+async fn unsolicited_is_sent_when_event_count_is_reached() {
+    let mut harness = new_harness(config_with_class1_trigger(UnsolicitedClassTrigger {
+        count: 2,
+        hold_time: None,
+    }));
+    confirm_null_unsolicited(&mut harness).await;
+    enable_unsolicited(&mut harness).await;
+    generate_binary_event(&mut harness.handle.database);
+
+    // without a hold time, a single event is held indefinitely
+    tokio::time::pause();
+    tokio::time::sleep(std::time::Duration::from_secs(3600)).await;
+    harness.expect_no_response();
+
+    harness.handle.database.transaction(|db| {
+        db.update(
+            0,
+            &BinaryInput::new(false, Flags::ONLINE, Time::synchronized(0)),
+            UpdateOptions::default(),
+        )
+    });
+
+    harness
+        .expect_response(&[
+            0xF1, 0x82, 0x80, 0x00, // header
+            0x02, 0x01, 0x28, 0x02, 0x00, // 2 events g2v1
+            0x00, 0x00, 0x81, // index 0, value = true
+            0x00, 0x00, 0x01, // index 0, value = false
+        ])
+        .await;
+}
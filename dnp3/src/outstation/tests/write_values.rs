@@ -0,0 +1,258 @@
+use std::sync::{Arc, Mutex};
+
+use crate::app::measurement::*;
+use crate::outstation::database::*;
+use crate::outstation::tests::harness::*;
+use crate::outstation::{OutstationWriteHandler, WriteStatus};
+
+const CLEAR_RESTART_IIN: &[u8] = &[0xC0, 0x02, 80, 1, 0x00, 7, 7, 0x00];
+const RESPONSE_NO_RESTART_IIN: &[u8] = &[0xC0, 0x81, 0x00, 0x00];
+
+#[derive(Debug, PartialEq)]
+enum Written {
+    AnalogOutputStatus(u16, f64),
+    OctetString(u16, Vec<u8>),
+}
+
+struct Handler {
+    accepted: u16,
+    written: Arc<Mutex<Vec<Written>>>,
+}
+
+impl Handler {
+    fn status(&self, index: u16) -> WriteStatus {
+        if index == self.accepted {
+            WriteStatus::Accept
+        } else {
+            WriteStatus::Reject
+        }
+    }
+}
+
+impl OutstationWriteHandler for Handler {
+    fn write_analog_output_status(&mut self, index: u16, value: AnalogOutputStatus) -> WriteStatus {
+        self.written
+            .lock()
+            .unwrap()
+            .push(Written::AnalogOutputStatus(index, value.value));
+        self.status(index)
+    }
+
+    fn write_octet_string(&mut self, index: u16, value: &OctetString) -> WriteStatus {
+        self.written
+            .lock()
+            .unwrap()
+            .push(Written::OctetString(index, value.value().to_vec()));
+        self.status(index)
+    }
+}
+
+async fn harness_with_handler(accepted: u16) -> (OutstationHarness, Arc<Mutex<Vec<Written>>>) {
+    let mut harness = new_harness(get_default_config());
+    let written = Arc::new(Mutex::new(Vec::new()));
+    harness
+        .handle
+        .set_write_handler(Box::new(Handler {
+            accepted,
+            written: written.clone(),
+        }))
+        .await
+        .unwrap();
+
+    // the handler is guaranteed to be set once the outstation is waiting for the next request
+    harness
+        .test_request_response(CLEAR_RESTART_IIN, RESPONSE_NO_RESTART_IIN)
+        .await;
+    (harness, written)
+}
+
+#[tokio::test]
+async fn write_is_not_supported_by_default_handler() {
+    let mut harness = new_harness(get_default_config());
+    harness
+        .handle
+        .database
+        .transaction(|db| db.add(0, None, BinaryOutputStatusConfig::default()));
+
+    // g10v1 at index 0
+    harness
+        .test_request_response(
+            &[0xC0, 0x02, 0x0A, 0x01, 0x00, 0x00, 0x00, 0x01],
+            &[0xC0, 0x81, 0x80, 0x01],
+        )
+        .await;
+}
+
+#[tokio::test]
+async fn accepted_analog_output_status_is_updated_in_database() {
+    let (mut harness, written) = harness_with_handler(1).await;
+    harness.handle.database.transaction(|db| {
+        for index in [1, 2] {
+            db.add(index, None, AnalogOutputStatusConfig::default());
+        }
+    });
+
+    // g40v2 at indices 1 to 3, only 1 and 2 exist in the database
+    harness
+        .test_request_response(
+            &[
+                0xC1, 0x02, 0x28, 0x02, 0x00, 0x01, 0x03, 0x01, 0x2C, 0x01, 0x01, 0x64, 0x00, 0x01,
+                0x07, 0x00,
+            ],
+            &[0xC1, 0x81, 0x00, 0x04],
+        )
+        .await;
+
+    assert_eq!(
+        *written.lock().unwrap(),
+        [
+            Written::AnalogOutputStatus(1, 300.0),
+            Written::AnalogOutputStatus(2, 100.0)
+        ]
+    );
+    harness.handle.database.transaction(|db| {
+        let accepted: AnalogOutputStatus = db.get(1).unwrap();
+        assert_eq!(accepted.value, 300.0);
+        assert!(accepted.time.is_some());
+        let rejected: AnalogOutputStatus = db.get(2).unwrap();
+        assert_eq!(rejected.value, 0.0);
+    });
+}
+
+#[tokio::test]
+async fn accepted_octet_string_is_updated_in_database() {
+    let (mut harness, written) = harness_with_handler(5).await;
+    harness
+        .handle
+        .database
+        .transaction(|db| db.add(5, None, OctetStringConfig));
+
+    // g110v3 at index 5
+    harness
+        .test_request_response(
+            &[0xC1, 0x02, 0x6E, 0x03, 0x00, 0x05, 0x05, 0x61, 0x62, 0x63],
+            &[0xC1, 0x81, 0x00, 0x00],
+        )
+        .await;
+
+    assert_eq!(
+        *written.lock().unwrap(),
+        [Written::OctetString(5, b"abc".to_vec())]
+    );
+    harness.handle.database.transaction(|db| {
+        let value: OctetString = db.get(5).unwrap();
+        assert_eq!(value.value(), b"abc");
+    });
+}
+
+#[tokio::test]
+async fn writes_with_one_byte_count_and_prefix() {
+    let (mut harness, written) = harness_with_handler(1).await;
+    harness.handle.database.transaction(|db| {
+        for index in [1, 2] {
+            db.add(index, None, AnalogOutputStatusConfig::default());
+        }
+    });
+
+    // g40v2 with qualifier 0x17 at indices 2 and 1
+    harness
+        .test_request_response(
+            &[
+                0xC1, 0x02, 0x28, 0x02, 0x17, 0x02, 0x02, 0x01, 0x64, 0x00, 0x01, 0x01, 0x2C, 0x01,
+            ],
+            &[0xC1, 0x81, 0x00, 0x04],
+        )
+        .await;
+
+    assert_eq!(
+        *written.lock().unwrap(),
+        [
+            Written::AnalogOutputStatus(2, 100.0),
+            Written::AnalogOutputStatus(1, 300.0)
+        ]
+    );
+    harness.handle.database.transaction(|db| {
+        let accepted: AnalogOutputStatus = db.get(1).unwrap();
+        assert_eq!(accepted.value, 300.0);
+        let rejected: AnalogOutputStatus = db.get(2).unwrap();
+        assert_eq!(rejected.value, 0.0);
+    });
+}
+
+#[tokio::test]
+async fn writes_octet_string_with_two_byte_count_and_prefix() {
+    let (mut harness, written) = harness_with_handler(5).await;
+    harness
+        .handle
+        .database
+        .transaction(|db| db.add(5, None, OctetStringConfig));
+
+    // g110v3 with qualifier 0x28 at index 5
+    harness
+        .test_request_response(
+            &[
+                0xC1, 0x02, 0x6E, 0x03, 0x28, 0x01, 0x00, 0x05, 0x00, 0x61, 0x62, 0x63,
+            ],
+            &[0xC1, 0x81, 0x00, 0x00],
+        )
+        .await;
+
+    assert_eq!(
+        *written.lock().unwrap(),
+        [Written::OctetString(5, b"abc".to_vec())]
+    );
+}
+
+struct DatabaseReadingHandler {
+    database: DatabaseHandle,
+    previous: Arc<Mutex<Option<f64>>>,
+}
+
+impl OutstationWriteHandler for DatabaseReadingHandler {
+    fn write_analog_output_status(
+        &mut self,
+        index: u16,
+        _value: AnalogOutputStatus,
+    ) -> WriteStatus {
+        let previous = self
+            .database
+            .transaction(|db| Get::<AnalogOutputStatus>::get(db, index).map(|x| x.value));
+        *self.previous.lock().unwrap() = previous;
+        WriteStatus::Accept
+    }
+}
+
+#[tokio::test]
+async fn handler_may_use_the_database() {
+    let mut harness = new_harness(get_default_config());
+    harness
+        .handle
+        .database
+        .transaction(|db| db.add(1, None, AnalogOutputStatusConfig::default()));
+    let previous = Arc::new(Mutex::new(None));
+    harness
+        .handle
+        .set_write_handler(Box::new(DatabaseReadingHandler {
+            database: harness.handle.database.clone(),
+            previous: previous.clone(),
+        }))
+        .await
+        .unwrap();
+    harness
+        .test_request_response(CLEAR_RESTART_IIN, RESPONSE_NO_RESTART_IIN)
+        .await;
+
+    // g40v2 at index 1
+    harness
+        .test_request_response(
+            &[0xC1, 0x02, 0x28, 0x02, 0x00, 0x01, 0x01, 0x01, 0x2C, 0x01],
+            &[0xC1, 0x81, 0x00, 0x00],
+        )
+        .await;
+
+    assert_eq!(*previous.lock().unwrap(), Some(0.0));
+    harness.handle.database.transaction(|db| {
+        let value: AnalogOutputStatus = db.get(1).unwrap();
+        assert_eq!(value.value, 300.0);
+    });
+}
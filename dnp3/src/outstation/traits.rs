@@ -1,10 +1,13 @@
 use crate::app::attr::Attribute;
+use crate::app::measurement::{AnalogOutputStatus, BinaryOutputStatus, Counter, OctetString};
 use crate::app::variations::Group50Var2;
 use crate::app::RequestHeader;
 use crate::app::Sequence;
 use crate::app::{control::*, Timestamp};
 use crate::app::{FunctionCode, MaybeAsync};
-use crate::outstation::database::DatabaseHandle;
+use crate::outstation::database::{DatabaseHandle, EventClass, EventType};
+use crate::outstation::OperateResult;
+use crate::tcp::PeerIdentity;
 
 /// Application-controlled IIN bits
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
@@ -47,7 +50,7 @@ pub enum ConnectionState {
 }
 
 /// Information about the remaining number of class events in the buffer
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct ClassCount {
     /// number of class 1 events remaining in the buffer
     pub num_class_1: usize,
@@ -65,7 +68,8 @@ impl ClassCount {
 }
 
 /// Information about the remaining number of events on a per-type basis
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct TypeCount {
     /// number of binary input events remaining in the buffer
     pub num_binary_input: usize,
@@ -86,7 +90,7 @@ pub struct TypeCount {
 }
 
 /// Information about the state of buffer after a CONFIRM has been processed
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub struct BufferState {
     /// Remaining number of events in the buffer on a per-class basis
     pub classes: ClassCount,
@@ -113,6 +117,10 @@ pub trait OutstationApplication: Sync + Send + 'static {
     /// Handle a write of the absolute time.
     ///
     /// This is used during time synchronization procedures.
+    ///
+    /// If an [`OutstationClock`](crate::outstation::OutstationClock) is configured, it is
+    /// synchronized unless this method returns [`RequestError::ParameterError`], and
+    /// [`RequestError::NotSupported`] is not reported to the master.
     #[allow(unused_variables)]
     fn write_absolute_time(&mut self, time: Timestamp) -> Result<(), RequestError> {
         Err(RequestError::NotSupported)
@@ -209,6 +217,25 @@ pub trait OutstationApplication: Sync + Send + 'static {
         MaybeAsync::ready(true)
     }
 
+    /// Called when a READ request is received, before the requested data is selected from the database
+    ///
+    /// This allows the application to refresh point values, e.g. by polling a downstream device, and
+    /// update them in the database before the response is built. The outstation waits for the
+    /// returned value for at most [`OutstationConfig::before_read_timeout`](crate::outstation::OutstationConfig::before_read_timeout),
+    /// after which it responds with the values currently in the database.
+    ///
+    /// This method is called once for each READ request, including those that are deferred while
+    /// waiting for a confirmation, but not for the subsequent fragments of a multi-fragment response.
+    /// Headers that are not supported in READ requests are not included in `headers`.
+    #[allow(unused_variables)]
+    fn before_read(
+        &mut self,
+        headers: &[ReadObjects],
+        database: &mut DatabaseHandle,
+    ) -> MaybeAsync<()> {
+        MaybeAsync::ready(())
+    }
+
     /// Called when a CONFIRM is received to a response or unsolicited response, but before any
     /// previously transmitted events are cleared from the buffer
     fn begin_confirm(&mut self) {}
@@ -224,6 +251,12 @@ pub trait OutstationApplication: Sync + Send + 'static {
     fn end_confirm(&mut self, state: BufferState) -> MaybeAsync<()> {
         MaybeAsync::ready(())
     }
+
+    /// Called with the identity of the master when a session begins, and with `None` when the
+    /// session ends.
+    ///
+    /// The identity is only available for TLS sessions and is `None` for all other sessions.
+    fn peer_identity_changed(&mut self, _identity: Option<&PeerIdentity>) {}
 }
 
 /// enumeration describing how the outstation processed a broadcast request
@@ -269,6 +302,77 @@ pub trait OutstationInformation: Sync + Send + 'static {
     fn clear_restart_iin(&mut self) {}
 }
 
+/// Outcome of offering a value written by the master to an [`OutstationWriteHandler`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WriteStatus {
+    /// The value is accepted and updated in the database
+    Accept,
+    /// The value is rejected and IIN2.PARAMETER_ERROR is set in the response
+    Reject,
+    /// Writes of this type are not supported and IIN2.NO_FUNC_CODE_SUPPORT is set in the response
+    NotSupported,
+}
+
+/// Handles WRITE requests for point values
+///
+/// The outstation offers values written with the 0x00, 0x01, 0x17 and 0x28 qualifiers to this
+/// handler one at a time:
+///
+/// * Group 10 binary output status
+/// * Group 20 counters, i.e. presetting a counter
+/// * Group 40 analog output status
+/// * Group 110 octet strings
+///
+/// Accepted values are automatically updated in the database, which may produce events
+/// according to the configuration of the point. Written binary output status, counters and
+/// analog output status are timestamped with the outstation clock if one is configured, or
+/// with the system time otherwise. Values written to points that don't exist in the database
+/// are rejected with IIN2.PARAMETER_ERROR without calling the handler.
+///
+/// Every method defaults to [`WriteStatus::NotSupported`], so only the supported types need
+/// to be implemented. The handler is installed with
+/// [`OutstationHandle::set_write_handler`](crate::outstation::OutstationHandle::set_write_handler),
+/// and outstations use [`DefaultWriteHandler`] until then.
+pub trait OutstationWriteHandler: Send + 'static {
+    /// Called for each binary output status written via Group 10
+    fn write_binary_output_status(&mut self, index: u16, value: BinaryOutputStatus) -> WriteStatus {
+        let _ = (index, value);
+        WriteStatus::NotSupported
+    }
+
+    /// Called for each counter preset via Group 20
+    fn write_counter(&mut self, index: u16, value: Counter) -> WriteStatus {
+        let _ = (index, value);
+        WriteStatus::NotSupported
+    }
+
+    /// Called for each analog output status written via Group 40
+    fn write_analog_output_status(&mut self, index: u16, value: AnalogOutputStatus) -> WriteStatus {
+        let _ = (index, value);
+        WriteStatus::NotSupported
+    }
+
+    /// Called for each octet string written via Group 110
+    fn write_octet_string(&mut self, index: u16, value: &OctetString) -> WriteStatus {
+        let _ = (index, value);
+        WriteStatus::NotSupported
+    }
+}
+
+/// Implementation of [`OutstationWriteHandler`] that doesn't support writing any point values
+#[derive(Copy, Clone)]
+pub struct DefaultWriteHandler;
+
+impl DefaultWriteHandler {
+    /// create a boxed implementation of [`OutstationWriteHandler`] that returns
+    /// [`WriteStatus::NotSupported`] for every value
+    pub fn create() -> Box<dyn OutstationWriteHandler> {
+        Box::new(DefaultWriteHandler)
+    }
+}
+
+impl OutstationWriteHandler for DefaultWriteHandler {}
+
 /// enumeration describing how the master requested the control operation
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum OperateType {
@@ -321,6 +425,25 @@ pub trait ControlSupport<T> {
         op_type: OperateType,
         database: &mut DatabaseHandle,
     ) -> CommandStatus;
+
+    /// Operate a control point, optionally completing the operation after the response is sent
+    ///
+    /// This is the method the outstation calls to operate a control. Long-running operations,
+    /// e.g. a motorized switch, may return [`OperateResult::Pending`] to accept the operation
+    /// immediately and report the final status later through the associated
+    /// [`OperateCompletion`](crate::outstation::OperateCompletion),
+    /// which records a command event (g13/g43) and updates the output status.
+    ///
+    /// The default implementation calls [`ControlSupport::operate`] and completes immediately.
+    fn start_operate(
+        &mut self,
+        control: T,
+        index: u16,
+        op_type: OperateType,
+        database: &mut DatabaseHandle,
+    ) -> OperateResult {
+        OperateResult::Complete(self.operate(control, index, op_type, database))
+    }
 }
 
 /// Indices used by freeze operations
@@ -332,6 +455,57 @@ pub enum FreezeIndices {
     Range(u16, u16),
 }
 
+/// Type of static data requested by a header of a READ request
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StaticDataType {
+    /// Binary inputs (group 1)
+    BinaryInput,
+    /// Double-bit binary inputs (group 3)
+    DoubleBitBinaryInput,
+    /// Binary output statuses (group 10)
+    BinaryOutputStatus,
+    /// Counters (group 20)
+    Counter,
+    /// Frozen counters (group 21)
+    FrozenCounter,
+    /// Analog inputs (group 30)
+    AnalogInput,
+    /// Analog output statuses (group 40)
+    AnalogOutputStatus,
+    /// Octet strings (group 110)
+    OctetString,
+    /// Analog input dead-bands (group 34)
+    AnalogInputDeadBand,
+}
+
+/// Indices requested by a header of a READ request
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReadIndices {
+    /// All points of the type
+    All,
+    /// Range of points (the range is inclusive)
+    Range(u16, u16),
+}
+
+/// Data requested by an object header of a READ request
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ReadObjects {
+    /// Class 0, i.e. the static values of the types enabled in the
+    /// [`ClassZeroConfig`](crate::outstation::database::ClassZeroConfig)
+    Class0,
+    /// Static values of a specific type
+    Static(StaticDataType, ReadIndices),
+    /// Events of a class, limited to a maximum count if present
+    ClassEvents(EventClass, Option<usize>),
+    /// Events of a specific type, limited to a maximum count if present
+    TypedEvents(EventType, Option<usize>),
+    /// Device attributes (group 0)
+    DeviceAttributes,
+}
+
 /// This object maps to the fields of g50v2
 ///
 /// There is a table on page 57 of 1815-2012 that describes these 4 permutations
@@ -418,6 +592,13 @@ pub trait ControlHandler:
     fn end_fragment(&mut self, _database: &mut DatabaseHandle) -> MaybeAsync<()> {
         MaybeAsync::ready(())
     }
+
+    /// Called with the identity of the master when a session begins, and with `None` when the
+    /// session ends.
+    ///
+    /// The identity is only available for TLS sessions and is `None` for all other sessions.
+    /// It may be retained to authorize subsequent select and operate requests.
+    fn peer_identity_changed(&mut self, _identity: Option<&PeerIdentity>) {}
 }
 
 /// Struct with a default implementation of [ControlHandler](crate::outstation::ControlHandler)
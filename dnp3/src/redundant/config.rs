@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use crate::app::ConnectStrategy;
+use crate::redundant::path::PathType;
+use crate::tcp::{ClientConnectionHandler, ConnectOptions, EndpointList, SimpleConnectHandler};
+
+/// Determines when a redundant channel communicating over the backup path returns to the primary path
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum FailbackPolicy {
+    /// Remain on the backup path until it fails
+    Never,
+    /// After communicating on the backup path for the specified duration, the channel attempts to
+    /// connect to the primary path in the background. As soon as the primary path is connected, the channel
+    /// switches back to it the next time the master is idle.
+    Automatic(Duration),
+}
+
+/// Configuration that controls failover and failback of a redundant channel
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(
+    feature = "serialization",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct RedundancyConfig {
+    /// Policy for returning to the primary path after a failover
+    #[cfg_attr(
+        feature = "serialization",
+        serde(default = "RedundancyConfig::default_failback")
+    )]
+    pub failback: FailbackPolicy,
+    /// Number of consecutive response timeouts after which the active path is considered unhealthy
+    /// and the channel fails over to the other path.
+    ///
+    /// If `None`, only the loss of the underlying connection triggers a failover. Keep-alive
+    /// requests (see [`AssociationConfig::keep_alive_timeout`](crate::master::AssociationConfig::keep_alive_timeout))
+    /// count towards this limit and can be used to detect silent failures on an idle channel.
+    #[cfg_attr(feature = "serialization", serde(default))]
+    pub max_response_timeouts: Option<usize>,
+}
+
+impl RedundancyConfig {
+    const fn default_failback() -> FailbackPolicy {
+        FailbackPolicy::Automatic(Duration::from_secs(60))
+    }
+}
+
+impl Default for RedundancyConfig {
+    fn default() -> Self {
+        Self {
+            failback: Self::default_failback(),
+            max_response_timeouts: Some(3),
+        }
+    }
+}
+
+/// Configuration of one of the physical paths of a redundant channel
+pub struct PathConfig {
+    pub(crate) inner: PathType,
+}
+
+impl PathConfig {
+    /// A TCP client path that connects to a list of endpoints
+    pub fn tcp(
+        endpoints: EndpointList,
+        connect_strategy: ConnectStrategy,
+        connect_options: ConnectOptions,
+    ) -> Self {
+        Self::tcp_with_handler(SimpleConnectHandler::create(
+            endpoints,
+            connect_options,
+            connect_strategy,
+        ))
+    }
+
+    /// A TCP client path that uses a user-defined [`ClientConnectionHandler`]
+    ///
+    /// A master address set by the handler with [`ConnectionInfo::set_master_address`](crate::tcp::ConnectionInfo::set_master_address)
+    /// is used while the channel communicates on this path. Paths that don't set one use the address
+    /// from the channel configuration.
+    pub fn tcp_with_handler(handler: Box<dyn ClientConnectionHandler>) -> Self {
+        Self {
+            inner: PathType::tcp(handler),
+        }
+    }
+
+    /// A TLS client path that connects to a list of endpoints
+    #[cfg(feature = "enable-tls")]
+    pub fn tls(
+        endpoints: EndpointList,
+        connect_strategy: ConnectStrategy,
+        connect_options: ConnectOptions,
+        tls_config: crate::tcp::tls::TlsClientConfig,
+    ) -> Self {
+        Self::tls_with_handler(
+            SimpleConnectHandler::create(endpoints, connect_options, connect_strategy),
+            tls_config,
+        )
+    }
+
+    /// A TLS client path that uses a user-defined [`ClientConnectionHandler`]
+    ///
+    /// The master address is determined as for [`PathConfig::tcp_with_handler`].
+    #[cfg(feature = "enable-tls")]
+    pub fn tls_with_handler(
+        handler: Box<dyn ClientConnectionHandler>,
+        tls_config: crate::tcp::tls::TlsClientConfig,
+    ) -> Self {
+        Self {
+            inner: PathType::tls(handler, tls_config),
+        }
+    }
+
+    /// A serial port path
+    ///
+    /// * `path` is the name of the port, e.g. `/dev/ttyS0` or `COM1`
+    /// * `retry_delay` is the delay between attempts to open the port
+    #[cfg(feature = "serial")]
+    pub fn serial(
+        path: &str,
+        settings: crate::serial::SerialSettings,
+        retry_delay: Duration,
+    ) -> Self {
+        Self {
+            inner: PathType::serial(path, settings, retry_delay),
+        }
+    }
+}
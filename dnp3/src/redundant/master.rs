@@ -0,0 +1,63 @@
+use tracing::Instrument;
+
+use crate::app::parse::options::ParseOptions;
+use crate::app::Listener;
+use crate::link::reader::LinkModes;
+use crate::link::LinkErrorMode;
+use crate::master::task::MasterTask;
+use crate::master::{MasterChannel, MasterChannelConfig, MasterChannelType};
+use crate::redundant::task::RedundantTask;
+use crate::redundant::{PathConfig, RedundancyConfig, RedundantChannelState};
+use crate::util::session::Enabled;
+
+/// Spawn a master task that communicates with outstations over two redundant physical paths,
+/// e.g. a fiber TCP connection backed up by a cellular connection or a serial port.
+///
+/// The channel communicates on the primary path whenever possible and fails over to the backup path
+/// when the primary path is lost or becomes unhealthy. Failback to the primary path is controlled by
+/// the [`RedundancyConfig`].
+///
+/// Associations created on the returned channel are shared by both paths. Their state (sequence numbers,
+/// polls, queued requests, and the startup/unsolicited handshaking) is preserved when the channel switches
+/// paths, so the startup procedure is not repeated after a failover.
+///
+/// The same `link_error_mode` is applied to both paths. [`LinkErrorMode::Discard`] is recommended if
+/// either path is a serial port.
+///
+/// The task runs until the returned handle, and any `AssociationHandle` created from it, are dropped.
+///
+/// **Note**: This function may only be called from within the runtime itself, and panics otherwise.
+/// Use Runtime::enter() if required.
+pub fn spawn_master_redundant(
+    link_error_mode: LinkErrorMode,
+    config: MasterChannelConfig,
+    primary: PathConfig,
+    backup: PathConfig,
+    redundancy: RedundancyConfig,
+    listener: Box<dyn Listener<RedundantChannelState>>,
+) -> MasterChannel {
+    let (tx, rx) = crate::util::channel::request_channel();
+    let master_address = config.master_address;
+    let master = MasterTask::new(
+        Enabled::No,
+        LinkModes::stream(link_error_mode),
+        ParseOptions::get_static(),
+        config,
+        rx,
+    );
+    let mut task = RedundantTask::new(
+        master,
+        master_address,
+        primary.inner,
+        backup.inner,
+        redundancy,
+        listener,
+    );
+    let future = async move {
+        task.run()
+            .instrument(tracing::info_span!("dnp3-master-redundant"))
+            .await;
+    };
+    tokio::spawn(future);
+    MasterChannel::new(tx, MasterChannelType::Stream)
+}
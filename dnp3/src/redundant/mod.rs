@@ -0,0 +1,50 @@
+pub use config::*;
+pub use master::*;
+
+mod config;
+mod master;
+
+pub(crate) mod path;
+pub(crate) mod task;
+
+/// Identifies one of the two physical paths of a redundant channel
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ChannelPath {
+    /// The preferred path, e.g. a fiber connection
+    Primary,
+    /// The path used when the primary path is unavailable, e.g. a cellular connection
+    Backup,
+}
+
+impl ChannelPath {
+    pub(crate) fn other(self) -> Self {
+        match self {
+            Self::Primary => Self::Backup,
+            Self::Backup => Self::Primary,
+        }
+    }
+}
+
+impl std::fmt::Display for ChannelPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Primary => f.write_str("primary"),
+            Self::Backup => f.write_str("backup"),
+        }
+    }
+}
+
+/// State of a redundant channel
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RedundantChannelState {
+    /// channel is disabled
+    Disabled,
+    /// channel is attempting to establish a connection on the specified path
+    Connecting(ChannelPath),
+    /// channel is communicating over the specified path
+    Connected(ChannelPath),
+    /// channel is waiting to retry after failing to connect on both paths
+    WaitAfterFailedConnect(std::time::Duration),
+    /// channel has been shut down
+    Shutdown,
+}
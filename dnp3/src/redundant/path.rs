@@ -0,0 +1,123 @@
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::link::EndpointAddress;
+use crate::tcp::client::Connector;
+use crate::tcp::{ClientConnectionHandler, PostConnectionHandler};
+use crate::util::phys::PhysLayer;
+
+/// A physical layer established on one of the paths of a redundant channel
+pub(crate) struct Link {
+    pub(crate) phys: PhysLayer,
+    /// master address requested by the connection handler for this and subsequent links on the path
+    pub(crate) master_address: Option<EndpointAddress>,
+    remote: Option<(SocketAddr, Option<Arc<String>>)>,
+}
+
+/// Establishes physical layers for one path of a redundant channel
+pub(crate) enum PathType {
+    Tcp(Connector),
+    #[cfg(feature = "serial")]
+    Serial {
+        path: String,
+        settings: crate::serial::SerialSettings,
+        back_off: crate::app::ExponentialBackOff,
+    },
+    #[cfg(test)]
+    Mock {
+        links: std::collections::VecDeque<PhysLayer>,
+        master_address: Option<EndpointAddress>,
+    },
+}
+
+impl PathType {
+    pub(crate) fn tcp(handler: Box<dyn ClientConnectionHandler>) -> Self {
+        Self::Tcp(Connector::new(handler, PostConnectionHandler::Tcp))
+    }
+
+    #[cfg(feature = "enable-tls")]
+    pub(crate) fn tls(
+        handler: Box<dyn ClientConnectionHandler>,
+        config: crate::tcp::tls::TlsClientConfig,
+    ) -> Self {
+        Self::Tcp(Connector::new(handler, PostConnectionHandler::Tls(config)))
+    }
+
+    #[cfg(feature = "serial")]
+    pub(crate) fn serial(
+        path: &str,
+        settings: crate::serial::SerialSettings,
+        retry_delay: Duration,
+    ) -> Self {
+        Self::Serial {
+            path: path.to_string(),
+            settings,
+            back_off: crate::app::ExponentialBackOff::new(crate::app::RetryStrategy::new(
+                retry_delay,
+                retry_delay,
+            )),
+        }
+    }
+
+    /// Attempt to establish a physical layer, returning the delay before the next attempt on failure
+    pub(crate) async fn connect(&mut self) -> Result<Link, Duration> {
+        match self {
+            Self::Tcp(connector) => {
+                let conn = connector.connect().await?;
+                Ok(Link {
+                    phys: conn.phys,
+                    master_address: conn.settings.master_address,
+                    remote: Some((conn.addr, conn.hostname)),
+                })
+            }
+            #[cfg(feature = "serial")]
+            Self::Serial {
+                path,
+                settings,
+                back_off,
+            } => match crate::serial::open(path.as_str(), *settings) {
+                Ok(serial) => {
+                    back_off.on_success();
+                    tracing::info!("serial port {path} open");
+                    Ok(Link {
+                        phys: PhysLayer::Serial(serial),
+                        master_address: None,
+                        remote: None,
+                    })
+                }
+                Err(err) => {
+                    tracing::warn!("unable to open serial port {path}: {err}");
+                    Err(back_off.on_failure())
+                }
+            },
+            #[cfg(test)]
+            Self::Mock {
+                links,
+                master_address,
+            } => match links.pop_front() {
+                Some(phys) => Ok(Link {
+                    phys,
+                    master_address: *master_address,
+                    remote: None,
+                }),
+                None => Err(Duration::from_secs(1)),
+            },
+        }
+    }
+
+    /// Notify the path that a previously established physical layer failed
+    pub(crate) fn disconnected(&mut self, link: &Link) {
+        match self {
+            Self::Tcp(connector) => {
+                if let Some((addr, hostname)) = &link.remote {
+                    connector.disconnected(*addr, hostname.as_ref().map(|x| x.as_str()));
+                }
+            }
+            #[cfg(feature = "serial")]
+            Self::Serial { .. } => {}
+            #[cfg(test)]
+            Self::Mock { .. } => {}
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::time::Duration;
+
+use crate::app::{Listener, Shutdown};
+use crate::link::EndpointAddress;
+use crate::master::task::MasterTask;
+use crate::redundant::path::{Link, PathType};
+use crate::redundant::{ChannelPath, FailbackPolicy, RedundancyConfig, RedundantChannelState};
+use crate::util::session::{Enabled, RunError, StopReason};
+
+/// Runs a single master session over two physical paths
+pub(crate) struct RedundantTask {
+    task: MasterTask,
+    primary: PathType,
+    backup: PathType,
+    /// address of the master from the channel configuration
+    master_address: EndpointAddress,
+    /// addresses requested by the connection handlers of the primary and backup paths
+    path_addresses: [Option<EndpointAddress>; 2],
+    failback: FailbackPolicy,
+    listener: Box<dyn Listener<RedundantChannelState>>,
+}
+
+/// Why communication on a path ended without an error that stops the channel
+enum LinkEnd {
+    /// The link failed and the channel should fail over to the other path
+    Failed,
+    /// The primary path was re-established while communicating on the backup path
+    Failback(Link),
+}
+
+impl RedundantTask {
+    pub(crate) fn new(
+        mut task: MasterTask,
+        master_address: EndpointAddress,
+        primary: PathType,
+        backup: PathType,
+        config: RedundancyConfig,
+        listener: Box<dyn Listener<RedundantChannelState>>,
+    ) -> Self {
+        task.set_max_response_timeouts(config.max_response_timeouts);
+        Self {
+            task,
+            primary,
+            backup,
+            master_address,
+            path_addresses: [None; 2],
+            failback: config.failback,
+            listener,
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn master_address(&self) -> EndpointAddress {
+        self.task.master_address()
+    }
+
+    pub(crate) async fn run(&mut self) {
+        let _ = self.run_impl().await;
+        self.listener
+            .update(RedundantChannelState::Shutdown)
+            .get()
+            .await;
+    }
+
+    async fn run_impl(&mut self) -> Result<(), Shutdown> {
+        loop {
+            self.listener
+                .update(RedundantChannelState::Disabled)
+                .get()
+                .await;
+            self.wait_for_enabled().await?;
+            if let Err(StopReason::Shutdown) = self.run_enabled().await {
+                return Err(Shutdown);
+            }
+        }
+    }
+
+    async fn run_enabled(&mut self) -> Result<(), StopReason> {
+        let mut preferred = ChannelPath::Primary;
+        let mut next: Option<(ChannelPath, Link)> = None;
+
+        loop {
+            let (path, link) = match next.take() {
+                Some(x) => x,
+                None => match self.connect(preferred).await {
+                    Ok(x) => x,
+                    Err(delay) => {
+                        tracing::info!("waiting {} ms to retry connection", delay.as_millis());
+                        self.listener
+                            .update(RedundantChannelState::WaitAfterFailedConnect(delay))
+                            .get()
+                            .await;
+                        self.wait_for_retry(delay).await?;
+                        preferred = ChannelPath::Primary;
+                        continue;
+                    }
+                },
+            };
+
+            match self.run_link(path, link).await? {
+                LinkEnd::Failed => {
+                    tracing::warn!("failing over from the {path} path");
+                    preferred = path.other();
+                }
+                LinkEnd::Failback(link) => {
+                    tracing::info!("primary path restored, failing back from the backup path");
+                    next = Some((ChannelPath::Primary, link));
+                }
+            }
+        }
+    }
+
+    /// Try to connect on the first path, and then on the other path
+    async fn connect(&mut self, first: ChannelPath) -> Result<(ChannelPath, Link), Duration> {
+        let mut delay = Duration::MAX;
+        for path in [first, first.other()] {
+            self.listener
+                .update(RedundantChannelState::Connecting(path))
+                .get()
+                .await;
+            match self.get_path(path).connect().await {
+                Ok(link) => return Ok((path, link)),
+                Err(x) => {
+                    tracing::warn!("unable to connect on the {path} path");
+                    delay = delay.min(x);
+                }
+            }
+        }
+        Err(delay)
+    }
+
+    async fn run_link(&mut self, path: ChannelPath, mut link: Link) -> Result<LinkEnd, StopReason> {
+        tracing::info!("communicating on the {path} path");
+        self.apply_master_address(path, &link);
+        self.listener
+            .update(RedundantChannelState::Connected(path))
+            .get()
+            .await;
+
+        let result = match (path, self.failback) {
+            (ChannelPath::Backup, FailbackPolicy::Automatic(delay)) => {
+                let probe = probe_primary(&mut self.primary, delay);
+                self.task.run_until(&mut link.phys, probe).await
+            }
+            _ => {
+                let never = std::future::pending::<Link>();
+                self.task.run_until(&mut link.phys, never).await
+            }
+        };
+
+        match result {
+            Ok(primary) => Ok(LinkEnd::Failback(primary)),
+            Err(RunError::Stop(reason)) => Err(reason),
+            Err(RunError::Link(err)) => {
+                tracing::warn!("communication on the {path} path failed: {err}");
+                self.get_path(path).disconnected(&link);
+                Ok(LinkEnd::Failed)
+            }
+        }
+    }
+
+    /// Use the master address of the path, which persists until its connection handler changes it again
+    fn apply_master_address(&mut self, path: ChannelPath, link: &Link) {
+        let path_address = &mut self.path_addresses[path as usize];
+        if let Some(address) = link.master_address {
+            *path_address = Some(address);
+        }
+        let address = path_address.unwrap_or(self.master_address);
+        self.task.change_master_address(address);
+    }
+
+    async fn wait_for_enabled(&mut self) -> Result<(), Shutdown> {
+        loop {
+            if self.task.enabled() == Enabled::Yes {
+                return Ok(());
+            }
+
+            if let Err(StopReason::Shutdown) = self.task.process_next_message().await {
+                return Err(Shutdown);
+            }
+        }
+    }
+
+    async fn wait_for_retry(&mut self, duration: Duration) -> Result<(), StopReason> {
+        let deadline = tokio::time::Instant::now() + duration;
+
+        loop {
+            tokio::select! {
+                result = self.task.process_next_message() => {
+                   result?;
+                   if self.task.enabled() == Enabled::No {
+                       return Err(StopReason::Disable)
+                   }
+                }
+                _ = tokio::time::sleep_until(deadline) => {
+                   return Ok(());
+                }
+            }
+        }
+    }
+
+    fn get_path(&mut self, path: ChannelPath) -> &mut PathType {
+        match path {
+            ChannelPath::Primary => &mut self.primary,
+            ChannelPath::Backup => &mut self.backup,
+        }
+    }
+}
+
+/// After an initial delay, keep trying to connect on the primary path until it succeeds
+async fn probe_primary(primary: &mut PathType, delay: Duration) -> Link {
+    tokio::time::sleep(delay).await;
+    loop {
+        tracing::info!("attempting to restore the primary path");
+        match primary.connect().await {
+            Ok(link) => return link,
+            Err(delay) => tokio::time::sleep(delay).await,
+        }
+    }
+}
@@ -0,0 +1,383 @@
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::app::FunctionCode;
+use crate::master::TaskError;
+
+/// Counters for the traffic carried by a communication channel
+///
+/// The counters accumulate over the lifetime of the channel and are not reset when the
+/// underlying connection is lost or re-established.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ChannelStatistics {
+    /// Number of bytes read from the physical layer
+    pub bytes_rx: u64,
+    /// Number of bytes written to the physical layer
+    pub bytes_tx: u64,
+    /// Number of valid link-layer frames received
+    pub frames_rx: u64,
+    /// Number of link-layer frames transmitted
+    pub frames_tx: u64,
+    /// Number of frames discarded because the header or body CRC was invalid
+    pub crc_errors: u64,
+    /// Number of other link-layer framing errors, e.g. bad start bytes or an invalid length
+    ///
+    /// When the link layer discards data, a run of consecutive bytes that don't contain a
+    /// valid frame is only counted once.
+    pub link_parse_errors: u64,
+    /// Number of times communications were re-established on a new physical layer
+    pub reconnects: u64,
+    /// Remote address of the current or most recent connection
+    ///
+    /// `None` for serial channels, unconnected UDP sockets, or if the channel has never connected
+    pub remote_addr: Option<SocketAddr>,
+}
+
+/// Kind of a [`TaskError`] without any of the associated data
+///
+/// Used as the key when counting task failures in [`AssociationStatistics`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TaskErrorKind {
+    /// See [`TaskError::TooManyRequests`]
+    TooManyRequests,
+    /// See [`TaskError::Link`]
+    Link,
+    /// See [`TaskError::Transport`]
+    Transport,
+    /// See [`TaskError::RejectedByIin2`]
+    RejectedByIin2,
+    /// See [`TaskError::MalformedResponse`]
+    MalformedResponse,
+    /// See [`TaskError::UnexpectedResponseHeaders`]
+    UnexpectedResponseHeaders,
+    /// See [`TaskError::NonFinWithoutCon`]
+    NonFinWithoutCon,
+    /// See [`TaskError::NeverReceivedFir`]
+    NeverReceivedFir,
+    /// See [`TaskError::UnexpectedFir`]
+    UnexpectedFir,
+    /// See [`TaskError::MultiFragmentResponse`]
+    MultiFragmentResponse,
+    /// See [`TaskError::ResponseTimeout`]
+    ResponseTimeout,
+    /// See [`TaskError::WriteError`]
+    WriteError,
+    /// See [`TaskError::BadEncoding`]
+    BadEncoding,
+    /// See [`TaskError::NoSuchAssociation`]
+    NoSuchAssociation,
+    /// See [`TaskError::NoConnection`]
+    NoConnection,
+    /// See [`TaskError::Shutdown`]
+    Shutdown,
+    /// See [`TaskError::Disabled`]
+    Disabled,
+}
+
+impl From<&TaskError> for TaskErrorKind {
+    fn from(value: &TaskError) -> Self {
+        match value {
+            TaskError::TooManyRequests => Self::TooManyRequests,
+            TaskError::Link(_) => Self::Link,
+            TaskError::Transport => Self::Transport,
+            TaskError::RejectedByIin2(_) => Self::RejectedByIin2,
+            TaskError::MalformedResponse(_) => Self::MalformedResponse,
+            TaskError::UnexpectedResponseHeaders => Self::UnexpectedResponseHeaders,
+            TaskError::NonFinWithoutCon => Self::NonFinWithoutCon,
+            TaskError::NeverReceivedFir => Self::NeverReceivedFir,
+            TaskError::UnexpectedFir => Self::UnexpectedFir,
+            TaskError::MultiFragmentResponse => Self::MultiFragmentResponse,
+            TaskError::ResponseTimeout => Self::ResponseTimeout,
+            TaskError::WriteError => Self::WriteError,
+            TaskError::BadEncoding(_) => Self::BadEncoding,
+            TaskError::NoSuchAssociation(_) => Self::NoSuchAssociation,
+            TaskError::NoConnection => Self::NoConnection,
+            TaskError::Shutdown => Self::Shutdown,
+            TaskError::Disabled => Self::Disabled,
+        }
+    }
+}
+
+/// Counters maintained by the master for each association
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct AssociationStatistics {
+    /// Number of tasks that were started
+    pub tasks_started: u64,
+    /// Number of tasks that completed successfully
+    pub tasks_succeeded: u64,
+    /// Number of tasks that failed, keyed by the kind of error
+    pub tasks_failed: BTreeMap<TaskErrorKind, u64>,
+    /// Number of requests, including link status requests, for which no response was received
+    pub response_timeouts: u64,
+    /// Number of unsolicited responses received, including duplicates
+    pub unsolicited_responses: u64,
+    /// Number of unsolicited responses that were duplicates of the previous one
+    pub duplicate_unsolicited_responses: u64,
+    /// Average time between sending a request and receiving the first fragment of its response
+    ///
+    /// `None` until at least one response has been received
+    pub average_response_latency: Option<Duration>,
+}
+
+impl AssociationStatistics {
+    /// Total number of failed tasks regardless of the kind of error
+    pub fn total_tasks_failed(&self) -> u64 {
+        self.tasks_failed.values().sum()
+    }
+}
+
+/// Counters maintained by an outstation
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct OutstationStatistics {
+    /// Traffic on the communication channel
+    pub channel: ChannelStatistics,
+    /// Number of requests received, keyed by function code
+    ///
+    /// Confirms and repeated requests are included. Fragments with a malformed application
+    /// header are not counted because their function code is unknown.
+    pub requests: BTreeMap<FunctionCode, u64>,
+    /// Number of solicited responses for which no confirmation was received
+    pub solicited_confirm_timeouts: u64,
+    /// Number of unsolicited responses for which no confirmation was received
+    pub unsolicited_confirm_timeouts: u64,
+    /// Largest number of events ever held in the event buffer
+    pub event_buffer_high_water_mark: usize,
+    /// Number of events discarded because the event buffer was full
+    pub event_buffer_overflows: u64,
+}
+
+/// Counters maintained by a TCP or TLS server
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ServerStatistics {
+    /// Number of connections that were accepted and handed to a session
+    pub connections_accepted: u64,
+    /// Number of connections that were closed because they were rejected by a filter or the
+    /// user's handler, or because the TLS handshake or link identification failed
+    pub connections_rejected: u64,
+}
+
+/// Server counters shared between the server task and its handle
+#[derive(Debug, Default)]
+pub(crate) struct ServerCounters {
+    connections_accepted: AtomicU64,
+    connections_rejected: AtomicU64,
+}
+
+impl ServerCounters {
+    pub(crate) fn on_accept(&self) {
+        self.connections_accepted.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn on_reject(&self) {
+        self.connections_rejected.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn get(&self) -> ServerStatistics {
+        ServerStatistics {
+            connections_accepted: self.connections_accepted.load(Ordering::Relaxed),
+            connections_rejected: self.connections_rejected.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Channel counters shared by the tasks and layers that read and write a channel
+#[derive(Debug, Default)]
+pub(crate) struct ChannelCounters {
+    bytes_rx: AtomicU64,
+    bytes_tx: AtomicU64,
+    frames_rx: AtomicU64,
+    frames_tx: AtomicU64,
+    crc_errors: AtomicU64,
+    link_parse_errors: AtomicU64,
+    sessions: AtomicU64,
+    remote_addr: Mutex<Option<SocketAddr>>,
+}
+
+impl ChannelCounters {
+    fn increment(counter: &AtomicU64, count: u64) {
+        counter.fetch_add(count, Ordering::Relaxed);
+    }
+
+    pub(crate) fn on_bytes_rx(&self, count: usize) {
+        Self::increment(&self.bytes_rx, count as u64);
+    }
+
+    pub(crate) fn on_bytes_tx(&self, count: usize) {
+        Self::increment(&self.bytes_tx, count as u64);
+    }
+
+    pub(crate) fn on_frame_rx(&self) {
+        Self::increment(&self.frames_rx, 1);
+    }
+
+    pub(crate) fn on_frame_tx(&self) {
+        Self::increment(&self.frames_tx, 1);
+    }
+
+    pub(crate) fn on_link_errors(&self, crc_errors: u64, parse_errors: u64) {
+        Self::increment(&self.crc_errors, crc_errors);
+        Self::increment(&self.link_parse_errors, parse_errors);
+    }
+
+    /// called each time a session starts on a new physical layer
+    pub(crate) fn on_session_start(&self, remote_addr: Option<SocketAddr>) {
+        Self::increment(&self.sessions, 1);
+        *self.remote_addr.lock().unwrap() = remote_addr;
+    }
+
+    pub(crate) fn get(&self) -> ChannelStatistics {
+        ChannelStatistics {
+            bytes_rx: self.bytes_rx.load(Ordering::Relaxed),
+            bytes_tx: self.bytes_tx.load(Ordering::Relaxed),
+            frames_rx: self.frames_rx.load(Ordering::Relaxed),
+            frames_tx: self.frames_tx.load(Ordering::Relaxed),
+            crc_errors: self.crc_errors.load(Ordering::Relaxed),
+            link_parse_errors: self.link_parse_errors.load(Ordering::Relaxed),
+            reconnects: self.sessions.load(Ordering::Relaxed).saturating_sub(1),
+            remote_addr: *self.remote_addr.lock().unwrap(),
+        }
+    }
+}
+
+/// Association counters owned by the master task
+#[derive(Debug, Default)]
+pub(crate) struct AssociationCounters {
+    statistics: AssociationStatistics,
+    /// time at which the last request was sent if a response hasn't been received yet
+    request_sent: Option<tokio::time::Instant>,
+    total_latency: Duration,
+    latency_samples: u32,
+}
+
+impl AssociationCounters {
+    pub(crate) fn on_task_start(&mut self) {
+        self.statistics.tasks_started += 1;
+    }
+
+    pub(crate) fn on_task_success(&mut self) {
+        self.statistics.tasks_succeeded += 1;
+    }
+
+    pub(crate) fn on_task_failure(&mut self, err: &TaskError) {
+        *self.statistics.tasks_failed.entry(err.into()).or_default() += 1;
+    }
+
+    pub(crate) fn on_response_timeout(&mut self) {
+        self.statistics.response_timeouts += 1;
+        self.request_sent = None;
+    }
+
+    pub(crate) fn on_unsolicited_response(&mut self, is_duplicate: bool) {
+        self.statistics.unsolicited_responses += 1;
+        if is_duplicate {
+            self.statistics.duplicate_unsolicited_responses += 1;
+        }
+    }
+
+    pub(crate) fn on_request_sent(&mut self) {
+        self.request_sent = Some(tokio::time::Instant::now());
+    }
+
+    pub(crate) fn on_solicited_response(&mut self) {
+        if let Some(sent) = self.request_sent.take() {
+            self.total_latency = self.total_latency.saturating_add(sent.elapsed());
+            self.latency_samples = self.latency_samples.saturating_add(1);
+        }
+    }
+
+    pub(crate) fn get(&self) -> AssociationStatistics {
+        let mut statistics = self.statistics.clone();
+        if self.latency_samples > 0 {
+            statistics.average_response_latency = Some(self.total_latency / self.latency_samples);
+        }
+        statistics
+    }
+}
+
+/// Outstation counters owned by the outstation session
+#[derive(Debug, Default)]
+pub(crate) struct OutstationCounters {
+    pub(crate) requests: BTreeMap<FunctionCode, u64>,
+    pub(crate) solicited_confirm_timeouts: u64,
+    pub(crate) unsolicited_confirm_timeouts: u64,
+}
+
+impl OutstationCounters {
+    pub(crate) fn on_request(&mut self, function: FunctionCode) {
+        *self.requests.entry(function).or_default() += 1;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reconnects_exclude_the_first_session() {
+        let counters = ChannelCounters::default();
+        assert_eq!(counters.get().reconnects, 0);
+        counters.on_session_start(None);
+        assert_eq!(counters.get().reconnects, 0);
+        counters.on_session_start(None);
+        let remote = "127.0.0.1:20000".parse().unwrap();
+        counters.on_session_start(Some(remote));
+        assert_eq!(counters.get().reconnects, 2);
+        assert_eq!(counters.get().remote_addr, Some(remote));
+    }
+
+    #[test]
+    fn task_failures_are_counted_by_kind() {
+        let mut counters = AssociationCounters::default();
+        counters.on_task_failure(&TaskError::ResponseTimeout);
+        counters.on_task_failure(&TaskError::ResponseTimeout);
+        counters.on_task_failure(&TaskError::NoSuchAssociation(
+            crate::link::EndpointAddress::try_new(1).unwrap(),
+        ));
+
+        let statistics = counters.get();
+        assert_eq!(
+            statistics.tasks_failed.get(&TaskErrorKind::ResponseTimeout),
+            Some(&2)
+        );
+        assert_eq!(
+            statistics
+                .tasks_failed
+                .get(&TaskErrorKind::NoSuchAssociation),
+            Some(&1)
+        );
+        assert_eq!(statistics.total_tasks_failed(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn latency_is_averaged_over_responses() {
+        let mut counters = AssociationCounters::default();
+        assert_eq!(counters.get().average_response_latency, None);
+
+        counters.on_request_sent();
+        tokio::time::advance(Duration::from_millis(100)).await;
+        counters.on_solicited_response();
+        // later fragments of the same response are not sampled
+        counters.on_solicited_response();
+
+        counters.on_request_sent();
+        tokio::time::advance(Duration::from_millis(300)).await;
+        counters.on_solicited_response();
+
+        // requests that time out are not sampled
+        counters.on_request_sent();
+        counters.on_response_timeout();
+
+        assert_eq!(
+            counters.get().average_response_latency,
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(counters.get().response_timeouts, 1);
+    }
+}
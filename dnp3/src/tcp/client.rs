@@ -1,6 +1,7 @@
 use crate::app::{Listener, Shutdown};
 use crate::tcp::{
     ClientConnectionHandler, ClientState, ConnectionInfo, EndpointInner, PostConnectionHandler,
+    SessionSettings,
 };
 use crate::util::phys::PhysLayer;
 use crate::util::session::{RunError, Session, StopReason};
@@ -11,9 +12,25 @@ use tokio::net::TcpSocket;
 
 pub(crate) struct ClientTask {
     session: Session,
+    connector: Connector,
+    listener: Box<dyn Listener<ClientState>>,
+}
+
+/// Establishes TCP/TLS connections using a [`ClientConnectionHandler`]
+///
+/// This is shared between the [`ClientTask`] and other tasks that need to
+/// establish client connections, e.g. redundant master channels.
+pub(crate) struct Connector {
     connect_handler: Box<dyn ClientConnectionHandler>,
     post_connection: PostConnectionHandler,
-    listener: Box<dyn Listener<ClientState>>,
+}
+
+/// A successfully established client connection
+pub(crate) struct Connection {
+    pub(crate) phys: PhysLayer,
+    pub(crate) addr: SocketAddr,
+    pub(crate) hostname: Option<Arc<String>>,
+    pub(crate) settings: SessionSettings,
 }
 
 impl ClientTask {
@@ -24,8 +41,7 @@ impl ClientTask {
         listener: Box<dyn Listener<ClientState>>,
     ) -> Self {
         Self {
-            connect_handler,
-            post_connection,
+            connector: Connector::new(connect_handler, post_connection),
             session,
             listener,
         }
@@ -55,10 +71,13 @@ impl ClientTask {
     async fn run_one_connection(&mut self) -> Result<(), StopReason> {
         self.listener.update(ClientState::Connecting).get().await;
 
-        match self.connect().await {
-            Ok((phys, addr, hostname)) => {
+        match self.connector.connect().await {
+            Ok(conn) => {
+                if let Some(master_address) = conn.settings.master_address {
+                    self.session.change_master_address(master_address);
+                }
                 self.listener.update(ClientState::Connected).get().await;
-                self.run_phys(phys, addr, hostname).await
+                self.run_phys(conn.phys, conn.addr, conn.hostname).await
             }
             Err(delay) => {
                 tracing::info!("waiting {} ms to retry connection", delay.as_millis());
@@ -71,18 +90,66 @@ impl ClientTask {
         }
     }
 
-    async fn connect(&mut self) -> Result<(PhysLayer, SocketAddr, Option<Arc<String>>), Duration> {
+    async fn run_phys(
+        &mut self,
+        mut phys: PhysLayer,
+        addr: SocketAddr,
+        hostname: Option<Arc<String>>,
+    ) -> Result<(), StopReason> {
+        match self.session.run(&mut phys).await {
+            RunError::Stop(s) => Err(s),
+            RunError::Link(err) => {
+                tracing::warn!("connection to {addr} failed: {err}");
+                let hostname = hostname.as_ref().map(|x| x.as_str());
+                let reconnect_delay = self.connector.disconnected(addr, hostname);
+
+                self.listener
+                    .update(ClientState::WaitAfterDisconnect(reconnect_delay))
+                    .get()
+                    .await;
+
+                if !reconnect_delay.is_zero() {
+                    tracing::warn!("waiting {reconnect_delay:?} to reconnect");
+                    self.session.wait_for_retry(reconnect_delay).await?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Connector {
+    pub(crate) fn new(
+        connect_handler: Box<dyn ClientConnectionHandler>,
+        post_connection: PostConnectionHandler,
+    ) -> Self {
+        Self {
+            connect_handler,
+            post_connection,
+        }
+    }
+
+    /// Notify the handler that a previously established connection failed, returning the reconnect delay
+    pub(crate) fn disconnected(&mut self, addr: SocketAddr, hostname: Option<&str>) -> Duration {
+        self.connect_handler.disconnected(addr, hostname)
+    }
+
+    /// Attempt to connect to the endpoints provided by the handler until a connection
+    /// is established, or the handler indicates that the caller should wait before retrying
+    pub(crate) async fn connect(&mut self) -> Result<Connection, Duration> {
         loop {
             let conn_info = self.connect_handler.next()?;
             let settings = conn_info.settings;
             if let Some((phys, addr, hostname)) = self.connect_to_endpoint(conn_info).await {
-                if let Some(master_address) = settings.master_address {
-                    self.session.change_master_address(master_address);
-                }
-
                 self.connect_handler
                     .connected(addr, hostname.as_ref().map(|x| x.as_str()));
-                return Ok((phys, addr, hostname));
+                return Ok(Connection {
+                    phys,
+                    addr,
+                    hostname,
+                    settings,
+                });
             }
         }
     }
@@ -196,32 +263,4 @@ impl ClientTask {
 
         Some(phys)
     }
-
-    async fn run_phys(
-        &mut self,
-        mut phys: PhysLayer,
-        addr: SocketAddr,
-        hostname: Option<Arc<String>>,
-    ) -> Result<(), StopReason> {
-        match self.session.run(&mut phys).await {
-            RunError::Stop(s) => Err(s),
-            RunError::Link(err) => {
-                tracing::warn!("connection to {addr} failed: {err}");
-                let hostname = hostname.as_ref().map(|x| x.as_str());
-                let reconnect_delay = self.connect_handler.disconnected(addr, hostname);
-
-                self.listener
-                    .update(ClientState::WaitAfterDisconnect(reconnect_delay))
-                    .get()
-                    .await;
-
-                if !reconnect_delay.is_zero() {
-                    tracing::warn!("waiting {reconnect_delay:?} to reconnect");
-                    self.session.wait_for_retry(reconnect_delay).await?;
-                }
-
-                Ok(())
-            }
-        }
-    }
 }
@@ -0,0 +1,66 @@
+/// Version of the TLS protocol negotiated for a session
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsVersion {
+    /// TLS 1.2
+    V12,
+    /// TLS 1.3
+    V13,
+    /// Any other version, identified by its value on the wire
+    Other(u16),
+}
+
+/// Extension of the peer's certificate that was not interpreted by the library
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertificateExtension {
+    /// Object identifier of the extension in dotted decimal notation, e.g. "1.2.840.10070.8.1"
+    pub oid: String,
+    /// True if the extension is marked as critical
+    pub critical: bool,
+    /// DER encoded value of the extension
+    pub value: Vec<u8>,
+}
+
+/// Identity of the remote peer of an established TLS session
+///
+/// The information is extracted from the end-entity certificate presented by the peer after it
+/// has been validated by the TLS configuration. Applications can use it to authorize operations,
+/// e.g. by decoding the IEC 62351-8 `RoleSpec` extension retrieved using [`Self::extension`].
+///
+/// The type is available without TLS support so that the callbacks reporting it have the same
+/// signature regardless of the enabled features. Without TLS, no identity is ever reported.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerIdentity {
+    /// DER encoded certificate chain presented by the peer, starting with the end-entity certificate
+    pub certificates: Vec<Vec<u8>>,
+    /// Common name (CN) of the subject of the end-entity certificate
+    pub common_name: Option<String>,
+    /// Organization (O) of the subject of the end-entity certificate
+    pub organization: Option<String>,
+    /// DNS names, IP addresses, e-mail addresses and URIs from the subject alternative name extension
+    pub subject_alt_names: Vec<String>,
+    /// Role from the Modbus/TCP Security role extension (OID 1.3.6.1.4.1.50316.802.1)
+    ///
+    /// This is **not** the IEC 62351-8 `RoleSpec` extension, which is left uninterpreted in
+    /// [`Self::extensions`]. Some deployments reuse the Modbus role with DNP3 for simplicity.
+    pub modbus_role: Option<String>,
+    /// Extensions that are not interpreted by the library, e.g. the IEC 62351-8 `RoleSpec`
+    /// extension (OID 1.2.840.10070.8.1)
+    pub extensions: Vec<CertificateExtension>,
+    /// Negotiated TLS version
+    pub version: TlsVersion,
+    /// Negotiated cipher suite, e.g. "TLS13_AES_256_GCM_SHA384"
+    pub cipher_suite: String,
+}
+
+impl PeerIdentity {
+    /// Retrieve the value of an uninterpreted certificate extension by its dotted decimal OID
+    pub fn extension(&self, oid: &str) -> Option<&[u8]> {
+        self.extensions
+            .iter()
+            .find(|x| x.oid == oid)
+            .map(|x| x.value.as_slice())
+    }
+}
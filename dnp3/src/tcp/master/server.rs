@@ -6,17 +6,20 @@ use crate::link::LinkErrorMode;
 use crate::master::task::MasterTask;
 use crate::master::{MasterChannel, MasterChannelConfig, MasterChannelType};
 use crate::util::channel::{Receiver, Sender};
+use crate::util::monitor::ChannelMonitor;
 use crate::util::phys::PhysLayer;
 use crate::util::session::{Enabled, Session};
 use std::future::Future;
 
 use crate::app::parse::options::ParseOptions;
+use crate::statistics::ServerCounters;
 use crate::tcp::ServerHandle;
 use crate::util::future::forever;
 use crate::util::shutdown::ShutdownListener;
 use scursor::ReadCursor;
 use std::net::SocketAddr;
 use std::num::NonZeroUsize;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::{TcpListener, TcpStream};
 use tracing::Instrument;
@@ -94,6 +97,7 @@ pub async fn spawn_master_tcp_server<C: ConnectionHandler>(
     let (tx, rx) = crate::util::channel::request_channel();
 
     let (token, shutdown_listener) = crate::util::shutdown::shutdown_token();
+    let counters = Arc::new(ServerCounters::default());
 
     let accept_task = AcceptTask {
         conn_id: 0,
@@ -105,6 +109,7 @@ pub async fn spawn_master_tcp_server<C: ConnectionHandler>(
         handler,
         id_task_results: rx,
         id_task_sender: tx,
+        counters: counters.clone(),
     };
 
     let task = async move {
@@ -119,6 +124,7 @@ pub async fn spawn_master_tcp_server<C: ConnectionHandler>(
     let handle = ServerHandle {
         addr: assigned_addr,
         _token: token,
+        counters,
     };
 
     Ok(handle)
@@ -198,6 +204,7 @@ struct AcceptTask<C: ConnectionHandler> {
     handler: C,
     id_task_results: Receiver<LinkIdResult>,
     id_task_sender: Sender<LinkIdResult>,
+    counters: Arc<ServerCounters>,
 }
 
 enum TaskEvent {
@@ -233,6 +240,7 @@ impl<C: ConnectionHandler> AcceptTask<C> {
                     Ok((phys, addr, id)) => self.handle_link_identity(phys, addr, id).await,
                     Err(err) => {
                         tracing::warn!("unable to identify remote link: {err}");
+                        self.counters.on_reject();
                     }
                 }
             }
@@ -296,6 +304,7 @@ impl<C: ConnectionHandler> AcceptTask<C> {
                     id.source,
                     id.destination
                 );
+                self.counters.on_reject();
             }
         }
     }
@@ -306,6 +315,7 @@ impl<C: ConnectionHandler> AcceptTask<C> {
         match self.handler.accept(addr).await {
             Err(Reject) => {
                 tracing::info!("rejected connection from {addr}");
+                self.counters.on_reject();
             }
             Ok(AcceptAction::Accept(x)) => {
                 self.spawn_session(
@@ -358,6 +368,7 @@ impl<C: ConnectionHandler> AcceptTask<C> {
         }
 
         let conn_id = self.next_conn_id();
+        self.counters.on_accept();
 
         let task = SessionTask {
             phys,
@@ -443,7 +454,9 @@ async fn read_link_identity(
         let mut count = 0;
         loop {
             let remaining = &mut header[count..link::constant::LINK_HEADER_LENGTH];
-            let (num, _) = layer.read(remaining, decode_level).await?;
+            let (num, _) = layer
+                .read(remaining, decode_level, &ChannelMonitor::default())
+                .await?;
             count += num;
             if count == link::constant::LINK_HEADER_LENGTH {
                 return Ok(header);
@@ -1,18 +1,22 @@
 pub use connect::*;
 pub use connector::*;
 pub use endpoint_list::*;
+pub use identity::*;
 pub use master::*;
 pub use no_delay::*;
 pub use outstation::*;
 pub use server_handle::*;
 
 /// Entry points and types for TLS
-#[cfg(feature = "enable-tls")]
+///
+/// The types shared with DTLS are available when either feature is enabled
+#[cfg(any(feature = "enable-tls", feature = "dtls"))]
 pub mod tls;
 
 mod connect;
 mod connector;
 mod endpoint_list;
+mod identity;
 mod master;
 mod no_delay;
 mod outstation;
@@ -1,12 +1,14 @@
 use crate::app::parse::options::ParseOptions;
 use crate::app::{Listener, Shutdown};
 use crate::link::reader::LinkModes;
-use crate::link::{LinkErrorMode, LinkReadMode};
+use crate::link::{EndpointAddress, LinkErrorMode, LinkReadMode};
+use crate::outstation::database::DatabaseHandle;
 use crate::outstation::task::OutstationTask;
 use crate::outstation::{
-    ConnectionState, ControlHandler, OutstationApplication, OutstationConfig, OutstationHandle,
-    OutstationInformation,
+    ConnectionOverrides, ConnectionState, ControlHandler, OutstationApplication, OutstationConfig,
+    OutstationHandle, OutstationInformation, OverrideRule,
 };
+use crate::statistics::ServerCounters;
 use crate::tcp::server_task::{NewSession, ServerTask};
 use crate::tcp::{AddressFilter, FilterError, ServerHandle};
 use crate::util::channel::Sender;
@@ -14,6 +16,7 @@ use crate::util::phys::{PhysAddr, PhysLayer};
 use crate::util::session::{Enabled, Session};
 use crate::util::shutdown::ShutdownListener;
 use std::net::SocketAddr;
+use std::sync::Arc;
 use tracing::Instrument;
 
 struct OutstationInfo {
@@ -30,7 +33,9 @@ pub struct Server {
     connection_id: u64,
     address: SocketAddr,
     outstations: Vec<OutstationInfo>,
+    overrides: Vec<(AddressFilter, OverrideRule)>,
     connection_handler: ServerConnectionHandler,
+    counters: Arc<ServerCounters>,
 }
 
 enum ServerConnectionHandler {
@@ -40,11 +45,15 @@ enum ServerConnectionHandler {
 }
 
 impl ServerConnectionHandler {
-    async fn handle(&mut self, socket: tokio::net::TcpStream) -> Result<PhysLayer, String> {
+    async fn handle(
+        &mut self,
+        socket: tokio::net::TcpStream,
+        _addr: SocketAddr,
+    ) -> Result<PhysLayer, String> {
         match self {
             Self::Tcp => Ok(PhysLayer::Tcp(socket)),
             #[cfg(feature = "enable-tls")]
-            Self::Tls(config) => config.handle_connection(socket).await,
+            Self::Tls(config) => config.handle_connection(socket, _addr).await,
         }
     }
 }
@@ -61,7 +70,9 @@ impl Server {
             connection_id: 0,
             address,
             outstations: Vec::new(),
+            overrides: Vec::new(),
             connection_handler: ServerConnectionHandler::Tcp,
+            counters: Default::default(),
         }
     }
 
@@ -77,7 +88,9 @@ impl Server {
             connection_id: 0,
             address,
             outstations: Vec::new(),
+            overrides: Vec::new(),
             connection_handler: ServerConnectionHandler::Tls(tls_config),
+            counters: Default::default(),
         }
     }
 
@@ -90,6 +103,62 @@ impl Server {
         control_handler: Box<dyn ControlHandler>,
         listener: Box<dyn Listener<ConnectionState>>,
         filter: AddressFilter,
+    ) -> Result<(OutstationHandle, impl std::future::Future<Output = ()>), FilterError> {
+        self.add_outstation_impl(
+            config,
+            application,
+            information,
+            control_handler,
+            listener,
+            filter,
+            None,
+        )
+    }
+
+    /// associate an outstation with the TcpServer that shares the database of another outstation, but do not spawn it
+    ///
+    /// This allows several masters to poll the same points, e.g. a SCADA master and a backup master
+    /// connecting from different addresses. The points and attributes are stored once and every
+    /// update made with [`DatabaseHandle::transaction`] is visible to each outstation sharing the
+    /// database, but each outstation has its own event buffer, unsolicited response state and event
+    /// confirmations. The event buffer of the new outstation starts empty and is removed from the
+    /// database when the outstation is shut down.
+    ///
+    /// The event buffer and class 0 settings are taken from the supplied configuration.
+    ///
+    /// The returned future must be spawned for the outstation to run.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_outstation_with_database(
+        &mut self,
+        config: OutstationConfig,
+        application: Box<dyn OutstationApplication>,
+        information: Box<dyn OutstationInformation>,
+        control_handler: Box<dyn ControlHandler>,
+        listener: Box<dyn Listener<ConnectionState>>,
+        filter: AddressFilter,
+        database: &DatabaseHandle,
+    ) -> Result<(OutstationHandle, impl std::future::Future<Output = ()>), FilterError> {
+        self.add_outstation_impl(
+            config,
+            application,
+            information,
+            control_handler,
+            listener,
+            filter,
+            Some(database),
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn add_outstation_impl(
+        &mut self,
+        config: OutstationConfig,
+        application: Box<dyn OutstationApplication>,
+        information: Box<dyn OutstationInformation>,
+        control_handler: Box<dyn ControlHandler>,
+        listener: Box<dyn Listener<ConnectionState>>,
+        filter: AddressFilter,
+        database: Option<&DatabaseHandle>,
     ) -> Result<(OutstationHandle, impl std::future::Future<Output = ()>), FilterError> {
         for item in self.outstations.iter() {
             if filter.conflicts_with(&item.filter) {
@@ -97,16 +166,29 @@ impl Server {
             }
         }
 
-        let (task, handle) = OutstationTask::create(
-            Enabled::Yes,
-            self.link_modes,
-            ParseOptions::get_static(),
-            config,
-            PhysAddr::None,
-            application,
-            information,
-            control_handler,
-        );
+        let (task, handle) = match database {
+            None => OutstationTask::create(
+                Enabled::Yes,
+                self.link_modes,
+                ParseOptions::get_static(),
+                config,
+                PhysAddr::None,
+                application,
+                information,
+                control_handler,
+            ),
+            Some(database) => OutstationTask::join(
+                Enabled::Yes,
+                self.link_modes,
+                ParseOptions::get_static(),
+                config,
+                PhysAddr::None,
+                application,
+                information,
+                control_handler,
+                database,
+            ),
+        };
 
         let (mut adapter, tx) = ServerTask::create(Session::outstation(task), listener);
 
@@ -153,6 +235,30 @@ impl Server {
         Ok(handle)
     }
 
+    /// override the configuration of the outstation serving connections from addresses matching the filter
+    ///
+    /// If `master_address` is specified, the overrides only apply when the outstation serving the
+    /// connection communicates with a master at that link address. This allows masters sharing
+    /// the same IP address, e.g. behind a gateway, to be configured individually.
+    ///
+    /// The overrides only apply while the master is connected. When several rules match a
+    /// connection, the overrides added first are used. Connections that do not match any rule
+    /// use the configuration passed to [`Server::add_outstation`].
+    pub fn add_connection_overrides(
+        &mut self,
+        filter: AddressFilter,
+        master_address: Option<EndpointAddress>,
+        overrides: ConnectionOverrides,
+    ) {
+        self.overrides.push((
+            filter,
+            OverrideRule {
+                master_address,
+                overrides,
+            },
+        ));
+    }
+
     /// Consume the `TcpServer` builder object, bind it to pre-specified port, and return a (ServerHandle, Future)
     /// tuple.
     ///
@@ -165,6 +271,7 @@ impl Server {
         let addr = listener.local_addr().ok();
 
         let (token, shutdown_rx) = crate::util::shutdown::shutdown_token();
+        let counters = self.counters.clone();
 
         let task = async move {
             let local = self.address;
@@ -176,6 +283,7 @@ impl Server {
         let handle = ServerHandle {
             addr,
             _token: token,
+            counters,
         };
 
         Ok((handle, task))
@@ -247,6 +355,14 @@ impl Server {
 
         tracing::info!("accepted connection {} from: {}", id, addr);
 
+        // the link address of the master is only known by the outstation, which selects the rule
+        let overrides: Vec<OverrideRule> = self
+            .overrides
+            .iter()
+            .filter(|(filter, _)| filter.matches(addr.ip()))
+            .map(|(_, rule)| *rule)
+            .collect();
+
         let first_match = self
             .outstations
             .iter_mut()
@@ -254,14 +370,17 @@ impl Server {
 
         match first_match {
             None => {
-                tracing::warn!("no matching outstation for: {}", addr)
+                tracing::warn!("no matching outstation for: {}", addr);
+                self.counters.on_reject();
             }
-            Some(x) => match self.connection_handler.handle(stream).await {
+            Some(x) => match self.connection_handler.handle(stream, addr).await {
                 Err(err) => {
                     tracing::warn!("error from {}: {}", addr, err);
+                    self.counters.on_reject();
                 }
                 Ok(phys) => {
-                    let _ = x.sender.send(NewSession::new(id, phys)).await;
+                    self.counters.on_accept();
+                    let _ = x.sender.send(NewSession::new(id, phys, overrides)).await;
                 }
             },
         }
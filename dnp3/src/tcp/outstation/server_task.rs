@@ -1,7 +1,7 @@
 use tracing::Instrument;
 
 use crate::app::{Listener, Shutdown};
-use crate::outstation::ConnectionState;
+use crate::outstation::{ConnectionState, OverrideRule};
 use crate::util::channel::{request_channel, Receiver, Sender};
 use crate::util::phys::PhysLayer;
 use crate::util::session::{Enabled, RunError, Session, StopReason};
@@ -12,11 +12,17 @@ use crate::util::session::{Enabled, RunError, Session, StopReason};
 pub(crate) struct NewSession {
     pub(crate) id: u64,
     pub(crate) phys: PhysLayer,
+    /// override rules whose IP address filter matches the connection
+    pub(crate) overrides: Vec<OverrideRule>,
 }
 
 impl NewSession {
-    pub(crate) fn new(id: u64, phys: PhysLayer) -> Self {
-        Self { id, phys }
+    pub(crate) fn new(id: u64, phys: PhysLayer, overrides: Vec<OverrideRule>) -> Self {
+        Self {
+            id,
+            phys,
+            overrides,
+        }
     }
 }
 
@@ -74,6 +80,8 @@ impl ServerTask {
         mut session: NewSession,
     ) -> Result<Option<NewSession>, Shutdown> {
         let id = session.id;
+        self.session
+            .set_connection_overrides(std::mem::take(&mut session.overrides));
         self.listener.update(ConnectionState::Connected).get().await;
         let result = self
             .run_one_session(&mut session.phys)
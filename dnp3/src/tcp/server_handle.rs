@@ -1,9 +1,13 @@
 use std::net::SocketAddr;
+use std::sync::Arc;
+
+use crate::statistics::{ServerCounters, ServerStatistics};
 
 /// Handle to a running TCP or TLS server. Dropping the handle shuts down the server.
 pub struct ServerHandle {
     pub(crate) addr: Option<SocketAddr>,
     pub(crate) _token: crate::util::shutdown::ShutdownToken,
+    pub(crate) counters: Arc<ServerCounters>,
 }
 
 impl ServerHandle {
@@ -13,4 +17,9 @@ impl ServerHandle {
     pub fn local_addr(&self) -> Option<SocketAddr> {
         self.addr
     }
+
+    /// Get the number of connections accepted and rejected by the server
+    pub fn get_statistics(&self) -> ServerStatistics {
+        self.counters.get()
+    }
 }
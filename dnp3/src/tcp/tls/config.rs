@@ -0,0 +1,104 @@
+use std::sync::Mutex;
+
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::pki_types;
+
+use crate::tcp::tls::crl::PeerCheck;
+use crate::tcp::tls::{MinTlsVersion, TlsError};
+
+impl From<sfio_rustls_config::Error> for TlsError {
+    fn from(err: sfio_rustls_config::Error) -> Self {
+        Self::Other(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            err.to_string(),
+        ))
+    }
+}
+
+impl From<pki_types::InvalidDnsNameError> for TlsError {
+    fn from(_: pki_types::InvalidDnsNameError) -> Self {
+        Self::InvalidDnsName
+    }
+}
+
+impl From<MinTlsVersion> for sfio_rustls_config::ProtocolVersions {
+    fn from(value: MinTlsVersion) -> Self {
+        match value {
+            MinTlsVersion::V12 => sfio_rustls_config::ProtocolVersions::v12_only(),
+            MinTlsVersion::V13 => sfio_rustls_config::ProtocolVersions::new()
+                .enable_v12()
+                .enable_v13(),
+        }
+    }
+}
+
+impl MinTlsVersion {
+    /// protocol versions enabled by the configuration, matching the conversion to `ProtocolVersions`
+    pub(crate) fn versions(self) -> &'static [&'static rustls::SupportedProtocolVersion] {
+        static V12_ONLY: &[&rustls::SupportedProtocolVersion] = &[&rustls::version::TLS12];
+        static V12_AND_V13: &[&rustls::SupportedProtocolVersion] =
+            &[&rustls::version::TLS12, &rustls::version::TLS13];
+
+        match self {
+            MinTlsVersion::V12 => V12_ONLY,
+            MinTlsVersion::V13 => V12_AND_V13,
+        }
+    }
+}
+
+/// Configuration state shared between a TLS configuration and all of its clones
+///
+/// Reloading replaces the state used for subsequent connections. Established sessions
+/// only observe the checks used to disconnect peers that are no longer accepted.
+pub(crate) struct Reloadable<T> {
+    current: Mutex<(T, PeerCheck)>,
+    checks: tokio::sync::watch::Sender<PeerCheck>,
+}
+
+impl<T> Reloadable<T>
+where
+    T: Clone,
+{
+    pub(crate) fn new(config: T, check: PeerCheck) -> Self {
+        let (tx, _) = tokio::sync::watch::channel(check.clone());
+        Self {
+            current: Mutex::new((config, check)),
+            checks: tx,
+        }
+    }
+
+    /// configuration to use for a new connection
+    pub(crate) fn current(&self) -> T {
+        self.current.lock().unwrap().0.clone()
+    }
+
+    /// replace the configuration with one derived from the current configuration
+    pub(crate) fn modify<F>(&self, modify: F) -> Result<(), TlsError>
+    where
+        F: FnOnce(&T) -> Result<(T, PeerCheck), TlsError>,
+    {
+        let mut guard = self.current.lock().unwrap();
+        *guard = modify(&guard.0)?;
+        Ok(())
+    }
+
+    pub(crate) fn reload(&self, other: &Self, disconnect_rejected: bool) {
+        let (config, check) = other.current.lock().unwrap().clone();
+        if disconnect_rejected {
+            self.checks.send_replace(check.clone());
+        }
+        *self.current.lock().unwrap() = (config, check);
+    }
+
+    /// subscribe to the checks applied to established sessions
+    pub(crate) fn subscribe(&self) -> tokio::sync::watch::Receiver<PeerCheck> {
+        self.checks.subscribe()
+    }
+}
+
+pub(crate) fn crl_not_supported() -> TlsError {
+    TlsError::InvalidCrl(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "CRLs may only be used with configurations created using full_pki",
+    ))
+}
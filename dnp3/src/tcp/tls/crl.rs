@@ -0,0 +1,415 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use sfio_rustls_config::{ClientNameVerification, ServerNameVerification};
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::client::WebPkiServerVerifier;
+use tokio_rustls::rustls::crypto::CryptoProvider;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{
+    CertificateDer, CertificateRevocationListDer, ServerName, UnixTime,
+};
+use tokio_rustls::rustls::server::danger::{ClientCertVerified, ClientCertVerifier};
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{
+    CertificateError, DigitallySignedStruct, DistinguishedName, RootCertStore, SignatureScheme,
+};
+
+use crate::tcp::tls::TlsError;
+
+/// Trust anchors and certificate revocation lists (CRLs) of a configuration that expects a full PKI
+///
+/// The CRLs are checked by the certificate verifier during the handshake. A CRL is only used
+/// if its signature is valid for the issuer of the certificate being checked, and the peer is
+/// rejected if an applicable CRL cannot be verified or its `nextUpdate` time has passed.
+/// Certificates whose issuer did not publish one of the loaded CRLs are not checked.
+#[derive(Clone)]
+pub(crate) struct Authority {
+    roots: Arc<RootCertStore>,
+    crls: Vec<CertificateRevocationListDer<'static>>,
+}
+
+impl Authority {
+    /// Load the trust anchors from a file containing one or more PEM encoded certificates
+    pub(crate) fn load(ca_cert_path: &Path) -> Result<Self, TlsError> {
+        let mut roots = RootCertStore::empty();
+        for cert in CertificateDer::pem_file_iter(ca_cert_path)
+            .map_err(|err| invalid_peer_cert(err.to_string()))?
+        {
+            let cert = cert.map_err(|err| invalid_peer_cert(err.to_string()))?;
+            roots
+                .add(cert)
+                .map_err(|err| invalid_peer_cert(err.to_string()))?;
+        }
+
+        Ok(Self {
+            roots: Arc::new(roots),
+            crls: Vec::new(),
+        })
+    }
+
+    /// Load every CRL in a PEM file, or a single DER encoded CRL
+    pub(crate) fn load_crls(&mut self, path: &Path) -> Result<(), TlsError> {
+        let bytes = std::fs::read(path).map_err(TlsError::InvalidCrl)?;
+
+        let mut ders: Vec<CertificateRevocationListDer<'static>> = Vec::new();
+        for der in CertificateRevocationListDer::pem_slice_iter(&bytes) {
+            ders.push(der.map_err(|err| invalid_crl(err.to_string()))?);
+        }
+        if ders.is_empty() {
+            ders.push(CertificateRevocationListDer::from(bytes));
+        }
+
+        for der in ders.iter() {
+            webpki::OwnedCertRevocationList::from_der(der.as_ref())
+                .map_err(|err| invalid_crl(format!("{err:?}")))?;
+        }
+
+        self.crls.extend(ders);
+        Ok(())
+    }
+
+    /// Verifier used by clients to check the certificates presented by a server
+    pub(crate) fn server_verifier(
+        &self,
+        provider: Arc<CryptoProvider>,
+        name_verification: ServerNameVerification,
+    ) -> Result<Arc<dyn ServerCertVerifier>, TlsError> {
+        let inner = WebPkiServerVerifier::builder_with_provider(self.roots.clone(), provider)
+            .with_crls(self.crls.iter().cloned())
+            .allow_unknown_revocation_status()
+            .enforce_revocation_expiration()
+            .build()
+            .map_err(|err| invalid_crl(err.to_string()))?;
+
+        Ok(Arc::new(ServerVerifier {
+            inner,
+            name_verification,
+        }))
+    }
+
+    /// Verifier used by servers to check the certificates presented by a client
+    pub(crate) fn client_verifier(
+        &self,
+        provider: Arc<CryptoProvider>,
+        name_verification: ClientNameVerification,
+    ) -> Result<Arc<dyn ClientCertVerifier>, TlsError> {
+        let inner = WebPkiClientVerifier::builder_with_provider(self.roots.clone(), provider)
+            .with_crls(self.crls.iter().cloned())
+            .allow_unknown_revocation_status()
+            .enforce_revocation_expiration()
+            .build()
+            .map_err(|err| invalid_crl(err.to_string()))?;
+
+        Ok(Arc::new(ClientVerifier {
+            inner,
+            name_verification,
+        }))
+    }
+}
+
+/// Checks the certificates of the peer of an established session against a reloaded configuration
+#[derive(Clone)]
+pub(crate) enum PeerCheck {
+    /// The configuration does not validate peers against a PKI
+    None,
+    /// Used by clients to check servers
+    Server(Arc<dyn ServerCertVerifier>, ServerName<'static>),
+    /// Used by servers to check clients
+    Client(Arc<dyn ClientCertVerifier>),
+}
+
+impl PeerCheck {
+    /// Returns true if the certificate chain presented by the peer is no longer accepted
+    pub(crate) fn rejects(&self, chain: &[CertificateDer<'static>]) -> bool {
+        let (end_entity, intermediates) = match chain.split_first() {
+            Some(x) => x,
+            None => return false,
+        };
+
+        let now = UnixTime::now();
+        let result = match self {
+            Self::None => return false,
+            Self::Server(verifier, name) => verifier
+                .verify_server_cert(end_entity, intermediates, name, &[], now)
+                .map(|_| ()),
+            Self::Client(verifier) => verifier
+                .verify_client_cert(end_entity, intermediates, now)
+                .map(|_| ()),
+        };
+
+        match result {
+            Ok(()) => false,
+            Err(err) => {
+                tracing::warn!("peer certificate rejected by the reloaded configuration: {err}");
+                true
+            }
+        }
+    }
+}
+
+/// WebPKI server verifier with the relaxed name verification of [`sfio_rustls_config::client::authority`]
+#[derive(Debug)]
+struct ServerVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    name_verification: ServerNameVerification,
+}
+
+impl ServerCertVerifier for ServerVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let result = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        );
+
+        match result {
+            Err(rustls::Error::InvalidCertificate(CertificateError::NotValidForName))
+            | Err(rustls::Error::InvalidCertificate(CertificateError::NotValidForNameContext {
+                ..
+            })) => match self.name_verification {
+                ServerNameVerification::SanExtOnly => result,
+                ServerNameVerification::SanOrCommonName => {
+                    verify_common_name(end_entity, server_name)?;
+                    Ok(ServerCertVerified::assertion())
+                }
+                ServerNameVerification::DisableNameVerification => {
+                    Ok(ServerCertVerified::assertion())
+                }
+            },
+            _ => result,
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// WebPKI client verifier with the name verification of [`sfio_rustls_config::server::authority`]
+#[derive(Debug)]
+struct ClientVerifier {
+    inner: Arc<dyn ClientCertVerifier>,
+    name_verification: ClientNameVerification,
+}
+
+impl ClientVerifier {
+    fn verify_client_name(
+        end_entity: &CertificateDer<'_>,
+        name: &ServerName<'_>,
+        use_common_name: bool,
+    ) -> Result<(), rustls::Error> {
+        let cert = webpki::EndEntityCert::try_from(end_entity)
+            .map_err(|err| rustls::Error::General(err.to_string()))?;
+
+        match cert.verify_is_valid_for_subject_name(name) {
+            Ok(()) => Ok(()),
+            Err(webpki::Error::CertNotValidForName(_)) if use_common_name => {
+                verify_common_name(end_entity, name)
+            }
+            Err(webpki::Error::CertNotValidForName(_)) => Err(rustls::Error::InvalidCertificate(
+                CertificateError::NotValidForName,
+            )),
+            Err(err) => Err(rustls::Error::General(err.to_string())),
+        }
+    }
+}
+
+impl ClientCertVerifier for ClientVerifier {
+    fn offer_client_auth(&self) -> bool {
+        self.inner.offer_client_auth()
+    }
+
+    fn client_auth_mandatory(&self) -> bool {
+        self.inner.client_auth_mandatory()
+    }
+
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        self.inner.root_hint_subjects()
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+    ) -> Result<ClientCertVerified, rustls::Error> {
+        let verified = self
+            .inner
+            .verify_client_cert(end_entity, intermediates, now)?;
+
+        match &self.name_verification {
+            ClientNameVerification::None => {}
+            ClientNameVerification::SanExtOnly(name) => {
+                Self::verify_client_name(end_entity, name, false)?
+            }
+            ClientNameVerification::SanOrCommonName(name) => {
+                Self::verify_client_name(end_entity, name, true)?
+            }
+        }
+
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Verify the name using the common name of a certificate without a SAN extension
+fn verify_common_name(
+    end_entity: &CertificateDer<'_>,
+    name: &ServerName<'_>,
+) -> Result<(), rustls::Error> {
+    let not_valid = || rustls::Error::InvalidCertificate(CertificateError::NotValidForName);
+
+    let dns_name = match name {
+        ServerName::DnsName(name) => name,
+        _ => return Err(not_valid()),
+    };
+
+    let cert = rx509::x509::Certificate::parse(end_entity)
+        .map_err(|err| rustls::Error::General(format!("unable to parse certificate: {err}")))?;
+
+    if let Some(extensions) = &cert.tbs_certificate.value.extensions {
+        let extensions = extensions.parse().map_err(|err| {
+            rustls::Error::General(format!("unable to parse certificate extensions: {err:?}"))
+        })?;
+        if extensions.iter().any(|x| {
+            matches!(
+                x.content,
+                rx509::x509::ext::SpecificExtension::SubjectAlternativeName(_)
+            )
+        }) {
+            return Err(not_valid());
+        }
+    }
+
+    let subject = cert.tbs_certificate.value.subject.parse().map_err(|err| {
+        rustls::Error::General(format!("unable to parse certificate subject: {err:?}"))
+    })?;
+
+    match subject.common_name {
+        Some(cn) if cn == dns_name.as_ref() => Ok(()),
+        _ => Err(not_valid()),
+    }
+}
+
+fn invalid_peer_cert(msg: String) -> TlsError {
+    TlsError::InvalidPeerCertificate(std::io::Error::new(std::io::ErrorKind::InvalidData, msg))
+}
+
+fn invalid_crl(msg: String) -> TlsError {
+    TlsError::InvalidCrl(std::io::Error::new(std::io::ErrorKind::InvalidData, msg))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn cert_path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../certs/ca_chain")
+            .join(name)
+    }
+
+    fn read_cert(name: &str) -> CertificateDer<'static> {
+        CertificateDer::from_pem_slice(&std::fs::read(cert_path(name)).unwrap()).unwrap()
+    }
+
+    fn provider() -> Arc<CryptoProvider> {
+        #[cfg(feature = "tls")]
+        return Arc::new(rustls::crypto::ring::default_provider());
+        #[cfg(not(feature = "tls"))]
+        return Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    }
+
+    fn check(crl: Option<&str>) -> PeerCheck {
+        let mut authority = Authority::load(&cert_path("ca_cert.pem")).unwrap();
+        if let Some(crl) = crl {
+            authority.load_crls(&cert_path(crl)).unwrap();
+        }
+        let verifier = authority
+            .client_verifier(provider(), ClientNameVerification::None)
+            .unwrap();
+        PeerCheck::Client(verifier)
+    }
+
+    #[test]
+    fn detects_revoked_certificate() {
+        let check = check(Some("ca_crl.pem"));
+        assert!(!check.rejects(&[read_cert("entity1_cert.pem")]));
+        assert!(check.rejects(&[read_cert("entity2_cert.pem")]));
+    }
+
+    #[test]
+    fn no_crls_revokes_nothing() {
+        assert!(!check(None).rejects(&[read_cert("entity2_cert.pem")]));
+    }
+
+    #[test]
+    fn rejects_peers_if_crl_signature_is_invalid() {
+        // same issuer name as the CA, but signed with another key
+        assert!(check(Some("forged_crl.pem")).rejects(&[read_cert("entity1_cert.pem")]));
+    }
+
+    #[test]
+    fn rejects_peers_if_crl_is_expired() {
+        assert!(check(Some("expired_crl.pem")).rejects(&[read_cert("entity1_cert.pem")]));
+    }
+
+    #[test]
+    fn rejects_invalid_crl_file() {
+        let mut authority = Authority::load(&cert_path("ca_cert.pem")).unwrap();
+        assert!(authority.load_crls(&cert_path("entity1_cert.pem")).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+use std::net::SocketAddr;
+
+use rx509::x509::ext::{GeneralName, SpecificExtension};
+use tokio_rustls::rustls;
+use tokio_rustls::rustls::pki_types::CertificateDer;
+
+use crate::tcp::{CertificateExtension, PeerIdentity, TlsVersion};
+
+impl PeerIdentity {
+    pub(crate) fn new(chain: &[CertificateDer<'static>], connection: &rustls::CommonState) -> Self {
+        let version = match connection.protocol_version() {
+            Some(rustls::ProtocolVersion::TLSv1_2) => TlsVersion::V12,
+            Some(rustls::ProtocolVersion::TLSv1_3) => TlsVersion::V13,
+            Some(other) => TlsVersion::Other(u16::from(other)),
+            // a version is always negotiated once the handshake completes
+            None => TlsVersion::Other(0),
+        };
+
+        let cipher_suite = match connection.negotiated_cipher_suite() {
+            None => String::new(),
+            Some(suite) => match suite.suite().as_str() {
+                Some(name) => name.to_string(),
+                None => format!("{:?}", suite.suite()),
+            },
+        };
+
+        let mut identity = Self {
+            certificates: chain.iter().map(|x| x.as_ref().to_vec()).collect(),
+            common_name: None,
+            organization: None,
+            subject_alt_names: Vec::new(),
+            modbus_role: None,
+            extensions: Vec::new(),
+            version,
+            cipher_suite,
+        };
+
+        if let Some(cert) = chain.first() {
+            if let Err(err) = identity.parse_certificate(cert) {
+                tracing::warn!("unable to extract identity from peer certificate: {err}");
+            }
+        }
+
+        identity
+    }
+
+    fn parse_certificate(&mut self, cert: &[u8]) -> Result<(), rx509::der::ASNError> {
+        let cert = rx509::x509::Certificate::parse(cert)?;
+        let tbs = &cert.tbs_certificate.value;
+
+        let subject = tbs.subject.parse()?;
+        self.common_name = subject.common_name.map(|x| x.to_string());
+        self.organization = subject.organization.map(|x| x.to_string());
+
+        let extensions = match &tbs.extensions {
+            None => return Ok(()),
+            Some(x) => x.parse()?,
+        };
+
+        for ext in extensions {
+            match ext.content {
+                SpecificExtension::SubjectAlternativeName(san) => {
+                    self.subject_alt_names
+                        .extend(san.names.iter().filter_map(format_name));
+                }
+                SpecificExtension::ModbusRole(role) => {
+                    self.modbus_role = Some(role.role.to_string());
+                }
+                SpecificExtension::Unknown(value) => {
+                    let oid: Vec<String> =
+                        ext.extn_id.values().iter().map(|x| x.to_string()).collect();
+                    self.extensions.push(CertificateExtension {
+                        oid: oid.join("."),
+                        critical: ext.critical,
+                        value: value.to_vec(),
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn format_name(name: &GeneralName) -> Option<String> {
+    match name {
+        GeneralName::DnsName(x) => Some(x.to_string()),
+        GeneralName::Rfc822Name(x) => Some(x.to_string()),
+        GeneralName::UniformResourceIdentifier(x) => Some(x.to_string()),
+        GeneralName::IpAddress(x) => {
+            let addr: std::net::IpAddr = match x.len() {
+                4 => <[u8; 4]>::try_from(*x).ok()?.into(),
+                16 => <[u8; 16]>::try_from(*x).ok()?.into(),
+                _ => return None,
+            };
+            Some(addr.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Application-defined authorization of the peer of a TLS session
+///
+/// The authorization is performed after the TLS handshake has completed and the certificate
+/// chain presented by the peer has been validated. It is also the means by which TLS clients
+/// learn the identity of the server.
+pub trait PeerAuthorization: Send + Sync {
+    /// Return true if the peer is allowed to establish a session, false to close the connection
+    fn authorize(&self, addr: SocketAddr, identity: &PeerIdentity) -> bool;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio_rustls::rustls::pki_types::pem::PemObject;
+
+    #[test]
+    fn extracts_subject_and_alt_names() {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../certs/ca_chain/entity1_cert.pem");
+        let cert = CertificateDer::from_pem_file(path).unwrap();
+
+        let mut identity = PeerIdentity {
+            certificates: Vec::new(),
+            common_name: None,
+            organization: None,
+            subject_alt_names: Vec::new(),
+            modbus_role: None,
+            extensions: Vec::new(),
+            version: TlsVersion::V13,
+            cipher_suite: String::new(),
+        };
+        identity.parse_certificate(&cert).unwrap();
+
+        assert_eq!(identity.common_name.as_deref(), Some("test.com"));
+        assert_eq!(identity.organization.as_deref(), Some("Test"));
+        assert_eq!(identity.subject_alt_names, vec!["test.com".to_string()]);
+        assert_eq!(identity.modbus_role, None);
+    }
+}
@@ -6,7 +6,11 @@ use std::sync::Arc;
 use crate::app::{ConnectStrategy, Listener};
 use crate::link::LinkErrorMode;
 use crate::master::{MasterChannel, MasterChannelConfig, MasterChannelType};
-use crate::tcp::tls::{CertificateMode, MinTlsVersion, TlsError};
+use crate::tcp::tls::crl::{Authority, PeerCheck};
+use crate::tcp::tls::{
+    crl_not_supported, CertificateMode, MinTlsVersion, PeerAuthorization, Reloadable, TlsError,
+    TlsSession,
+};
 use crate::tcp::{
     wire_master_client, ClientConnectionHandler, ClientState, ConnectOptions, SimpleConnectHandler,
 };
@@ -21,10 +25,57 @@ use tokio_rustls::rustls::pki_types::{IpAddr, ServerName};
 use tracing::Instrument;
 
 /// TLS configuration for a client
+///
+/// Clones share the same underlying state. An application may retain a clone of the
+/// configuration passed to a channel and later use it to [`reload`](Self::reload)
+/// certificates without restarting the channel.
+#[derive(Clone)]
 pub struct TlsClientConfig {
+    shared: Arc<Reloadable<ClientSettings>>,
+    authorization: Option<Arc<dyn PeerAuthorization>>,
+}
+
+#[derive(Clone)]
+struct ClientSettings {
     // server name used in SNI - if and only if it's a DNS name, does nothing for IP
     server_name: ServerName<'static>,
     config: Arc<rustls::ClientConfig>,
+    // trust anchors and name verification of a full PKI configuration, used to add CRLs
+    authority: Option<(Authority, ServerNameVerification)>,
+}
+
+impl ClientSettings {
+    fn check(&self) -> Result<PeerCheck, TlsError> {
+        match &self.authority {
+            None => Ok(PeerCheck::None),
+            Some((authority, names)) => Ok(PeerCheck::Server(
+                authority.server_verifier(self.config.crypto_provider().clone(), *names)?,
+                self.server_name.clone(),
+            )),
+        }
+    }
+
+    fn with_crl(&self, crl_path: &Path) -> Result<(Self, PeerCheck), TlsError> {
+        let (authority, names) = self.authority.as_ref().ok_or_else(crl_not_supported)?;
+        let mut authority = authority.clone();
+        authority.load_crls(crl_path)?;
+        let verifier = authority.server_verifier(self.config.crypto_provider().clone(), *names)?;
+
+        let mut config = self.config.as_ref().clone();
+        config
+            .dangerous()
+            .set_certificate_verifier(verifier.clone());
+
+        let settings = Self {
+            server_name: self.server_name.clone(),
+            config: Arc::new(config),
+            authority: Some((authority, *names)),
+        };
+        Ok((
+            settings,
+            PeerCheck::Server(verifier, self.server_name.clone()),
+        ))
+    }
 }
 
 /// Spawn a task onto the `Tokio` runtime. The task runs until the returned handle, and any
@@ -167,11 +218,9 @@ impl TlsClientConfig {
             private_key_path,
             password,
         )?;
+        let authority = Authority::load(peer_cert_path)?;
 
-        Ok(Self {
-            server_name,
-            config: Arc::new(config),
-        })
+        Self::create(server_name, config, Some((authority, name_verifier)))
     }
 
     /// Create a TLS client configuration that expects the client to present a single certificate.
@@ -197,10 +246,66 @@ impl TlsClientConfig {
             password,
         )?;
 
-        Ok(Self {
+        Self::create(
             //  it doesn't matter what we put here, it just needs to be an IP so that the client won't send an SNI extension
-            server_name: ServerName::IpAddress(IpAddr::V4(Ipv4Addr::UNSPECIFIED.into())),
+            ServerName::IpAddress(IpAddr::V4(Ipv4Addr::UNSPECIFIED.into())),
+            config,
+            None,
+        )
+    }
+
+    /// Load certificate revocation lists (CRLs) from a file containing one or more PEM encoded
+    /// CRLs, or a single DER encoded CRL.
+    ///
+    /// The CRLs are checked during the handshake, and the handshake fails if any certificate
+    /// presented by the server appears in a CRL issued by that certificate's issuer. A CRL whose
+    /// signature cannot be verified by the issuer, or whose `nextUpdate` time has passed, causes
+    /// the handshake to fail for every certificate of that issuer. This method may be called
+    /// more than once to load CRLs from several files.
+    ///
+    /// CRLs are only supported by configurations created using [`full_pki`](Self::full_pki).
+    pub fn with_crl(self, crl_path: &Path) -> Result<Self, TlsError> {
+        self.shared.modify(|settings| settings.with_crl(crl_path))?;
+        Ok(self)
+    }
+
+    /// Authorize each server with which a TLS session is established using an application-defined
+    /// callback. Connections to servers that are not authorized are closed.
+    ///
+    /// The callback is not affected by [`reload`](Self::reload). The
+    /// [`PeerIdentity`](crate::tcp::PeerIdentity) of the server of the current session is also
+    /// available from [`MasterChannel::get_peer_identity`](crate::master::MasterChannel::get_peer_identity).
+    pub fn with_authorization(self, authorization: Arc<dyn PeerAuthorization>) -> Self {
+        Self {
+            authorization: Some(authorization),
+            ..self
+        }
+    }
+
+    /// Replace the certificates, keys and CRLs with those of another configuration.
+    ///
+    /// The new configuration is used for all subsequent connections of every channel using this
+    /// configuration or one of its clones. Established sessions are not affected unless
+    /// `disconnect_revoked` is true, in which case sessions with a server whose certificate
+    /// chain would be rejected by the new configuration are closed immediately.
+    pub fn reload(&self, config: TlsClientConfig, disconnect_revoked: bool) {
+        self.shared.reload(&config.shared, disconnect_revoked);
+    }
+
+    fn create(
+        server_name: ServerName<'static>,
+        config: rustls::ClientConfig,
+        authority: Option<(Authority, ServerNameVerification)>,
+    ) -> Result<Self, TlsError> {
+        let settings = ClientSettings {
+            server_name,
             config: Arc::new(config),
+            authority,
+        };
+        let check = settings.check()?;
+        Ok(Self {
+            shared: Arc::new(Reloadable::new(settings, check)),
+            authorization: None,
         })
     }
 
@@ -209,15 +314,23 @@ impl TlsClientConfig {
         socket: TcpStream,
         endpoint: &SocketAddr,
     ) -> Option<PhysLayer> {
-        let connector = tokio_rustls::TlsConnector::from(self.config.clone());
-        match connector.connect(self.server_name.clone(), socket).await {
+        let settings = self.shared.current();
+        let connector = tokio_rustls::TlsConnector::from(settings.config);
+        let stream = match connector.connect(settings.server_name, socket).await {
             Err(err) => {
                 tracing::warn!("failed to establish TLS session with {endpoint}: {err}");
-                None
+                return None;
+            }
+            Ok(stream) => tokio_rustls::TlsStream::from(stream),
+        };
+
+        let session = TlsSession::new(stream, self.shared.subscribe());
+        if let Some(authorization) = &self.authorization {
+            if !authorization.authorize(*endpoint, &session.identity()) {
+                tracing::warn!("server {endpoint} is not authorized");
+                return None;
             }
-            Ok(stream) => Some(PhysLayer::Tls(Box::new(tokio_rustls::TlsStream::from(
-                stream,
-            )))),
         }
+        Some(PhysLayer::Tls(Box::new(session)))
     }
 }
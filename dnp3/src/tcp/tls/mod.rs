@@ -1,10 +1,26 @@
+#[cfg(feature = "enable-tls")]
+mod config;
+#[cfg(feature = "enable-tls")]
+mod crl;
+#[cfg(feature = "enable-tls")]
+mod identity;
+#[cfg(feature = "enable-tls")]
 mod master;
+#[cfg(feature = "enable-tls")]
 mod outstation;
+#[cfg(feature = "enable-tls")]
+mod session;
 
+#[cfg(feature = "enable-tls")]
+pub(crate) use config::*;
+#[cfg(feature = "enable-tls")]
+pub use identity::*;
+#[cfg(feature = "enable-tls")]
 pub use master::*;
+#[cfg(feature = "enable-tls")]
 pub use outstation::*;
-
-use tokio_rustls::rustls::pki_types;
+#[cfg(feature = "enable-tls")]
+pub(crate) use session::*;
 
 /// Determines how the certificate(s) presented by the peer are validated
 ///
@@ -42,27 +58,14 @@ pub enum TlsError {
     InvalidLocalCertificate(std::io::Error),
     /// Invalid private key
     InvalidPrivateKey(std::io::Error),
+    /// Invalid certificate revocation list
+    InvalidCrl(std::io::Error),
     /// DNS name is invalid
     InvalidDnsName,
     /// Other error
     Other(std::io::Error),
 }
 
-impl From<sfio_rustls_config::Error> for TlsError {
-    fn from(err: sfio_rustls_config::Error) -> Self {
-        Self::Other(std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            err.to_string(),
-        ))
-    }
-}
-
-impl From<pki_types::InvalidDnsNameError> for TlsError {
-    fn from(_: pki_types::InvalidDnsNameError) -> Self {
-        Self::InvalidDnsName
-    }
-}
-
 impl std::fmt::Display for TlsError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -73,6 +76,7 @@ impl std::fmt::Display for TlsError {
                 write!(f, "invalid local certificate file: {err}")
             }
             Self::InvalidPrivateKey(err) => write!(f, "invalid private key file: {err}"),
+            Self::InvalidCrl(err) => write!(f, "invalid certificate revocation list: {err}"),
             Self::InvalidDnsName => write!(f, "invalid DNS name"),
             Self::Other(err) => write!(f, "miscellaneous TLS error: {err}"),
         }
@@ -94,13 +98,234 @@ pub enum MinTlsVersion {
     V13,
 }
 
-impl From<MinTlsVersion> for sfio_rustls_config::ProtocolVersions {
-    fn from(value: MinTlsVersion) -> Self {
-        match value {
-            MinTlsVersion::V12 => sfio_rustls_config::ProtocolVersions::v12_only(),
-            MinTlsVersion::V13 => sfio_rustls_config::ProtocolVersions::new()
-                .enable_v12()
-                .enable_v13(),
+#[cfg(all(test, feature = "enable-tls"))]
+mod test {
+    use super::*;
+    use crate::decode::PhysDecodeLevel;
+    use crate::tcp::{PeerIdentity, TlsVersion};
+    use crate::util::monitor::ChannelMonitor;
+    use crate::util::phys::{PhysAddr, PhysLayer};
+    use std::path::PathBuf;
+    use std::sync::Arc;
+    use tokio::net::{TcpListener, TcpStream};
+
+    fn cert_path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../certs/ca_chain")
+            .join(name)
+    }
+
+    fn server_config() -> TlsServerConfig {
+        TlsServerConfig::full_pki(
+            None,
+            &cert_path("ca_cert.pem"),
+            &cert_path("entity1_cert.pem"),
+            &cert_path("entity1_key.pem"),
+            None,
+            MinTlsVersion::V12,
+        )
+        .unwrap()
+    }
+
+    fn client_config() -> TlsClientConfig {
+        TlsClientConfig::full_pki(
+            None,
+            &cert_path("ca_cert.pem"),
+            &cert_path("entity2_cert.pem"),
+            &cert_path("entity2_key.pem"),
+            None,
+            MinTlsVersion::V12,
+        )
+        .unwrap()
+    }
+
+    async fn connect(
+        mut server: TlsServerConfig,
+        mut client: TlsClientConfig,
+    ) -> (Result<PhysLayer, String>, Option<PhysLayer>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let accept = tokio::spawn(async move {
+            let (socket, addr) = listener.accept().await.unwrap();
+            server.handle_connection(socket, addr).await
+        });
+        let socket = TcpStream::connect(addr).await.unwrap();
+        let client = client.handle_connection(socket, &addr).await;
+        (accept.await.unwrap(), client)
+    }
+
+    #[tokio::test]
+    async fn refuses_revoked_peer_certificate() {
+        let server = server_config().with_crl(&cert_path("ca_crl.pem")).unwrap();
+        let (server, client) = connect(server, client_config()).await;
+        let err = server.unwrap_err();
+        assert!(err.starts_with("failed to establish TLS session"));
+        assert!(err.contains("Revoked"), "{err}");
+        assert!(client.is_none());
+    }
+
+    #[tokio::test]
+    async fn refuses_peers_if_crl_is_expired() {
+        let server = server_config()
+            .with_crl(&cert_path("expired_crl.pem"))
+            .unwrap();
+        let (server, _client) = connect(server, client_config()).await;
+        let err = server.unwrap_err();
+        assert!(err.contains("revocation list expired"), "{err}");
+    }
+
+    #[test]
+    fn self_signed_configurations_do_not_accept_crls() {
+        let config = TlsServerConfig::self_signed(
+            &cert_path("entity2_cert.pem"),
+            &cert_path("entity1_cert.pem"),
+            &cert_path("entity1_key.pem"),
+            None,
+            MinTlsVersion::V12,
+        )
+        .unwrap();
+        assert!(matches!(
+            config.with_crl(&cert_path("ca_crl.pem")),
+            Err(TlsError::InvalidCrl(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn reload_disconnects_revoked_sessions() {
+        let config = server_config();
+        let (server, client) = connect(config.clone(), client_config()).await;
+        let mut server = server.unwrap();
+        let mut client = client.unwrap();
+
+        // reloading without disconnecting revoked sessions leaves the session intact
+        config.reload(
+            server_config().with_crl(&cert_path("ca_crl.pem")).unwrap(),
+            false,
+        );
+        client
+            .write(
+                &[0xAA],
+                PhysAddr::None,
+                PhysDecodeLevel::Nothing,
+                &ChannelMonitor::default(),
+            )
+            .await
+            .unwrap();
+        let mut buffer = [0; 16];
+        let (count, _) = server
+            .read(
+                &mut buffer,
+                PhysDecodeLevel::Nothing,
+                &ChannelMonitor::default(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(&buffer[..count], &[0xAA]);
+
+        config.reload(
+            server_config().with_crl(&cert_path("ca_crl.pem")).unwrap(),
+            true,
+        );
+        let err = server
+            .read(
+                &mut buffer,
+                PhysDecodeLevel::Nothing,
+                &ChannelMonitor::default(),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::PermissionDenied);
+    }
+
+    struct RequireRole(&'static str);
+
+    impl PeerAuthorization for RequireRole {
+        fn authorize(&self, _addr: std::net::SocketAddr, identity: &PeerIdentity) -> bool {
+            identity.modbus_role.as_deref() == Some(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn sessions_expose_peer_identity() {
+        let (server, client) = connect(server_config(), client_config()).await;
+
+        for phys in [server.unwrap(), client.unwrap()] {
+            let identity = phys.peer_identity().unwrap();
+            assert_eq!(identity.common_name.as_deref(), Some("test.com"));
+            // the test configurations only allow TLS 1.2
+            assert_eq!(identity.version, TlsVersion::V12);
+            assert!(identity.cipher_suite.starts_with("TLS_ECDHE_"));
+            assert_eq!(identity.certificates.len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn master_channel_reports_peer_identity() {
+        use crate::app::parse::options::ParseOptions;
+        use crate::link::reader::LinkModes;
+        use crate::link::EndpointAddress;
+        use crate::master::{MasterChannel, MasterChannelConfig, MasterChannelType};
+        use crate::util::session::Enabled;
+
+        let (server, client) = connect(server_config(), client_config()).await;
+        let server = server.unwrap();
+        let mut client = client.unwrap();
+
+        let (tx, rx) = crate::util::channel::request_channel();
+        let mut task = crate::master::task::MasterTask::new(
+            Enabled::No,
+            LinkModes::test(),
+            ParseOptions::default(),
+            MasterChannelConfig::new(EndpointAddress::try_new(1).unwrap()),
+            rx,
+        );
+        let mut channel = MasterChannel::new(tx, MasterChannelType::Stream);
+        let task = tokio::spawn(async move {
+            task.run(&mut client).await;
+            // keep processing messages after the session has ended
+            while task.process_next_message().await.is_ok() {}
+        });
+
+        let identity = channel.get_peer_identity().await.unwrap().unwrap();
+        assert_eq!(identity.common_name.as_deref(), Some("test.com"));
+
+        // the identity is cleared when the session ends
+        drop(server);
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while channel.get_peer_identity().await.unwrap().is_some() {
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        drop(channel);
+        task.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn refuses_unauthorized_client() {
+        let server = server_config().with_authorization(Arc::new(RequireRole("ENGINEER")));
+        let (server, _client) = connect(server, client_config()).await;
+        assert_eq!(server.unwrap_err(), "client is not authorized");
+    }
+
+    struct RequireName(&'static str);
+
+    impl PeerAuthorization for RequireName {
+        fn authorize(&self, _addr: std::net::SocketAddr, identity: &PeerIdentity) -> bool {
+            identity.common_name.as_deref() == Some(self.0)
         }
     }
+
+    #[tokio::test]
+    async fn clients_authorize_servers() {
+        let client = client_config().with_authorization(Arc::new(RequireName("test.com")));
+        let (_server, client) = connect(server_config(), client).await;
+        assert!(client.is_some());
+
+        let client = client_config().with_authorization(Arc::new(RequireName("other.com")));
+        let (_server, client) = connect(server_config(), client).await;
+        assert!(client.is_none());
+    }
 }
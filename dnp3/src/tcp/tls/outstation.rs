@@ -1,4 +1,5 @@
 use sfio_rustls_config::ClientNameVerification;
+use std::net::SocketAddr;
 use std::path::Path;
 use std::sync::Arc;
 
@@ -12,7 +13,11 @@ use crate::outstation::{
     OutstationInformation,
 };
 use crate::tcp::client::ClientTask;
-use crate::tcp::tls::{CertificateMode, MinTlsVersion, TlsClientConfig, TlsError};
+use crate::tcp::tls::crl::{Authority, PeerCheck};
+use crate::tcp::tls::{
+    crl_not_supported, CertificateMode, MinTlsVersion, PeerAuthorization, Reloadable,
+    TlsClientConfig, TlsError, TlsSession,
+};
 use crate::tcp::{
     ClientConnectionHandler, ClientState, ConnectOptions, EndpointList, PostConnectionHandler,
     SimpleConnectHandler,
@@ -105,8 +110,60 @@ pub fn spawn_outstation_tls_client_2(
 }
 
 /// TLS configuration for a server
+///
+/// Clones share the same underlying state. An application may retain a clone of the
+/// configuration passed to a server and later use it to [`reload`](Self::reload)
+/// certificates without restarting the server.
+#[derive(Clone)]
 pub struct TlsServerConfig {
+    shared: Arc<Reloadable<ServerSettings>>,
+    authorization: Option<Arc<dyn PeerAuthorization>>,
+}
+
+#[derive(Clone)]
+struct ServerSettings {
     config: Arc<rustls::ServerConfig>,
+    min_tls_version: MinTlsVersion,
+    // trust anchors and name verification of a full PKI configuration, used to add CRLs
+    authority: Option<(Authority, ClientNameVerification)>,
+}
+
+impl ServerSettings {
+    fn check(&self) -> Result<PeerCheck, TlsError> {
+        match &self.authority {
+            None => Ok(PeerCheck::None),
+            Some((authority, names)) => Ok(PeerCheck::Client(
+                authority.client_verifier(self.config.crypto_provider().clone(), names.clone())?,
+            )),
+        }
+    }
+
+    fn with_crl(&self, crl_path: &Path) -> Result<(Self, PeerCheck), TlsError> {
+        let (authority, names) = self.authority.as_ref().ok_or_else(crl_not_supported)?;
+        let mut authority = authority.clone();
+        authority.load_crls(crl_path)?;
+        let verifier =
+            authority.client_verifier(self.config.crypto_provider().clone(), names.clone())?;
+
+        let config =
+            rustls::ServerConfig::builder_with_provider(self.config.crypto_provider().clone())
+                .with_protocol_versions(self.min_tls_version.versions())
+                .map_err(|err| {
+                    TlsError::Other(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        err.to_string(),
+                    ))
+                })?
+                .with_client_cert_verifier(verifier.clone())
+                .with_cert_resolver(self.config.cert_resolver.clone());
+
+        let settings = Self {
+            config: Arc::new(config),
+            min_tls_version: self.min_tls_version,
+            authority: Some((authority, names.clone())),
+        };
+        Ok((settings, PeerCheck::Client(verifier)))
+    }
 }
 
 impl TlsServerConfig {
@@ -159,7 +216,7 @@ impl TlsServerConfig {
         password: Option<&str>,
         min_tls_version: MinTlsVersion,
     ) -> Result<Self, TlsError> {
-        let name_verification = match client_subject_name {
+        let names = match client_subject_name {
             None => ClientNameVerification::None,
             Some(name) => {
                 let name: ServerName<'static> = name.try_into()?;
@@ -169,16 +226,15 @@ impl TlsServerConfig {
 
         let config = sfio_rustls_config::server::authority(
             min_tls_version.into(),
-            name_verification,
+            names.clone(),
             peer_cert_path,
             local_cert_path,
             private_key_path,
             password,
         )?;
+        let authority = Authority::load(peer_cert_path)?;
 
-        Ok(Self {
-            config: Arc::new(config),
-        })
+        Self::create(config, min_tls_version, Some((authority, names)))
     }
 
     /// Create a TLS server configuration that expects the client to present a single certificate.
@@ -204,21 +260,79 @@ impl TlsServerConfig {
             password,
         )?;
 
-        Ok(Self {
+        Self::create(config, min_tls_version, None)
+    }
+
+    /// Load certificate revocation lists (CRLs) from a file containing one or more PEM encoded
+    /// CRLs, or a single DER encoded CRL.
+    ///
+    /// The CRLs are checked during the handshake, and the handshake fails if any certificate
+    /// presented by the client appears in a CRL issued by that certificate's issuer. A CRL whose
+    /// signature cannot be verified by the issuer, or whose `nextUpdate` time has passed, causes
+    /// the handshake to fail for every certificate of that issuer. This method may be called
+    /// more than once to load CRLs from several files.
+    ///
+    /// CRLs are only supported by configurations created using [`full_pki`](Self::full_pki).
+    pub fn with_crl(self, crl_path: &Path) -> Result<Self, TlsError> {
+        self.shared.modify(|settings| settings.with_crl(crl_path))?;
+        Ok(self)
+    }
+
+    /// Authorize each client that completes the TLS handshake using an application-defined
+    /// callback. Connections from clients that are not authorized are closed.
+    ///
+    /// The callback is not affected by [`reload`](Self::reload).
+    pub fn with_authorization(self, authorization: Arc<dyn PeerAuthorization>) -> Self {
+        Self {
+            authorization: Some(authorization),
+            ..self
+        }
+    }
+
+    /// Replace the certificates, keys and CRLs with those of another configuration.
+    ///
+    /// The new configuration is used for all subsequently accepted connections. Established
+    /// sessions are not affected unless `disconnect_revoked` is true, in which case sessions
+    /// with a client whose certificate chain would be rejected by the new configuration are
+    /// closed immediately.
+    pub fn reload(&self, config: TlsServerConfig, disconnect_revoked: bool) {
+        self.shared.reload(&config.shared, disconnect_revoked);
+    }
+
+    fn create(
+        config: rustls::ServerConfig,
+        min_tls_version: MinTlsVersion,
+        authority: Option<(Authority, ClientNameVerification)>,
+    ) -> Result<Self, TlsError> {
+        let settings = ServerSettings {
             config: Arc::new(config),
+            min_tls_version,
+            authority,
+        };
+        let check = settings.check()?;
+        Ok(Self {
+            shared: Arc::new(Reloadable::new(settings, check)),
+            authorization: None,
         })
     }
 
     pub(crate) async fn handle_connection(
         &mut self,
         socket: TcpStream,
+        addr: SocketAddr,
     ) -> Result<PhysLayer, String> {
-        let connector = tokio_rustls::TlsAcceptor::from(self.config.clone());
-        match connector.accept(socket).await {
-            Err(err) => Err(format!("failed to establish TLS session: {err}")),
-            Ok(stream) => Ok(PhysLayer::Tls(Box::new(tokio_rustls::TlsStream::from(
-                stream,
-            )))),
+        let settings = self.shared.current();
+        let connector = tokio_rustls::TlsAcceptor::from(settings.config);
+        let stream = match connector.accept(socket).await {
+            Err(err) => return Err(format!("failed to establish TLS session: {err}")),
+            Ok(stream) => tokio_rustls::TlsStream::from(stream),
+        };
+        let session = TlsSession::new(stream, self.shared.subscribe());
+        if let Some(authorization) = &self.authorization {
+            if !authorization.authorize(addr, &session.identity()) {
+                return Err("client is not authorized".to_string());
+            }
         }
+        Ok(PhysLayer::Tls(Box::new(session)))
     }
 }
@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::rustls::pki_types::CertificateDer;
+use tokio_rustls::TlsStream;
+
+use crate::capture::Medium;
+use crate::tcp::tls::crl::PeerCheck;
+use crate::tcp::PeerIdentity;
+
+/// An established TLS session that is torn down if a configuration reload no longer
+/// accepts the peer's certificates
+pub(crate) struct TlsSession {
+    stream: TlsStream<TcpStream>,
+    peer_chain: Vec<CertificateDer<'static>>,
+    identity: Arc<PeerIdentity>,
+    checks: tokio::sync::watch::Receiver<PeerCheck>,
+}
+
+impl TlsSession {
+    /// Create a session from a completed handshake
+    pub(crate) fn new(
+        stream: TlsStream<TcpStream>,
+        checks: tokio::sync::watch::Receiver<PeerCheck>,
+    ) -> Self {
+        let connection: &tokio_rustls::rustls::CommonState = match &stream {
+            TlsStream::Client(x) => x.get_ref().1,
+            TlsStream::Server(x) => x.get_ref().1,
+        };
+        let peer_chain: Vec<CertificateDer<'static>> = connection
+            .peer_certificates()
+            .map(|certs| certs.iter().map(|c| c.clone().into_owned()).collect())
+            .unwrap_or_default();
+        let identity = Arc::new(PeerIdentity::new(&peer_chain, connection));
+
+        Self {
+            stream,
+            peer_chain,
+            identity,
+            checks,
+        }
+    }
+
+    pub(crate) fn identity(&self) -> Arc<PeerIdentity> {
+        self.identity.clone()
+    }
+
+    pub(crate) fn medium(&self) -> Medium {
+        let socket = match &self.stream {
+            TlsStream::Client(x) => x.get_ref().0,
+            TlsStream::Server(x) => x.get_ref().0,
+        };
+        match (socket.local_addr(), socket.peer_addr()) {
+            (Ok(local), Ok(remote)) => Medium::Tcp { local, remote },
+            _ => Medium::Other,
+        }
+    }
+
+    pub(crate) async fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        tokio::select! {
+            res = self.stream.read(buffer) => res,
+            err = Self::wait_for_rejection(&mut self.checks, &self.peer_chain) => Err(err),
+        }
+    }
+
+    pub(crate) async fn write_all(&mut self, data: &[u8]) -> std::io::Result<()> {
+        self.stream.write_all(data).await
+    }
+
+    async fn wait_for_rejection(
+        checks: &mut tokio::sync::watch::Receiver<PeerCheck>,
+        peer_chain: &[CertificateDer<'static>],
+    ) -> std::io::Error {
+        loop {
+            if checks.changed().await.is_err() {
+                // the configuration was dropped, it can no longer be reloaded
+                return std::future::pending().await;
+            }
+
+            let check = checks.borrow_and_update().clone();
+            if check.rejects(peer_chain) {
+                tracing::warn!("closing session, peer certificate is no longer accepted");
+                return std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "peer certificate is no longer accepted",
+                );
+            }
+        }
+    }
+}
@@ -6,6 +6,7 @@ use crate::link::EndpointAddress;
 use crate::outstation::Feature;
 use crate::transport::{Fragment, FragmentAddr, FragmentInfo, TransportData};
 use crate::util::buffer::Buffer;
+use crate::util::monitor::ChannelMonitor;
 use crate::util::phys::{PhysAddr, PhysLayer};
 
 pub(crate) struct MockReader {
@@ -48,6 +49,8 @@ impl MockReader {
         self.num_reads
     }
 
+    pub(crate) fn set_local_address(&mut self, _: EndpointAddress) {}
+
     pub(crate) fn reset(&mut self) {}
 
     pub(crate) fn seed_link(&mut self, _: &[u8]) -> Result<(), scursor::WriteError> {
@@ -86,6 +89,7 @@ impl MockReader {
         &mut self,
         io: &mut PhysLayer,
         level: DecodeLevel,
+        monitor: &ChannelMonitor,
     ) -> Result<(), LinkError> {
         if self.count > 0 {
             return Ok(());
@@ -96,6 +100,7 @@ impl MockReader {
             .read(
                 self.buffer.get_mut(self.buffer.len()).unwrap(),
                 level.physical,
+                monitor,
             )
             .await?;
         self.count = count;
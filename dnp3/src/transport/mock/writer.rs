@@ -3,16 +3,29 @@ use crate::decode::DecodeLevel;
 use crate::link::error::LinkError;
 use crate::link::EndpointAddress;
 use crate::transport::FragmentAddr;
+use crate::util::monitor::ChannelMonitor;
 use crate::util::phys::PhysLayer;
 
 pub(crate) struct MockWriter {
+    local_address: EndpointAddress,
     num_writes: usize,
 }
 
 // same signature as the real transport writer
 impl MockWriter {
-    pub(crate) fn new(_: EndpointType, _: EndpointAddress) -> Self {
-        Self { num_writes: 0 }
+    pub(crate) fn new(_: EndpointType, local_address: EndpointAddress) -> Self {
+        Self {
+            local_address,
+            num_writes: 0,
+        }
+    }
+
+    pub(crate) fn local_address(&self) -> EndpointAddress {
+        self.local_address
+    }
+
+    pub(crate) fn set_local_address(&mut self, address: EndpointAddress) {
+        self.local_address = address;
     }
 
     pub(crate) fn reset(&mut self) {}
@@ -26,10 +39,13 @@ impl MockWriter {
         &mut self,
         io: &mut PhysLayer,
         level: DecodeLevel,
+        monitor: &ChannelMonitor,
         destination: FragmentAddr,
         fragment: &[u8],
     ) -> Result<(), LinkError> {
-        io.write(fragment, destination.phys, level.physical).await?;
+        io.write(fragment, destination.phys, level.physical, monitor)
+            .await?;
+        monitor.counters.on_frame_tx();
         self.num_writes += 1;
         Ok(())
     }
@@ -39,6 +55,7 @@ impl MockWriter {
         _: &mut PhysLayer,
         _: FragmentAddr,
         _: DecodeLevel,
+        _: &ChannelMonitor,
     ) -> Result<(), LinkError> {
         Ok(())
     }
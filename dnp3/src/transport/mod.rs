@@ -7,6 +7,7 @@ use crate::app::{BufferSize, EndpointType};
 use crate::link::reader::LinkModes;
 use crate::link::EndpointAddress;
 use crate::outstation::Feature;
+use crate::util::monitor::ChannelMonitor;
 
 #[cfg(test)]
 pub(crate) mod mock;
@@ -22,10 +23,17 @@ pub(crate) fn create_master_transport_layer(
     parse_options: ParseOptions,
     address: EndpointAddress,
     rx_buffer_size: BufferSize<2048, 2048>,
+    monitor: ChannelMonitor,
 ) -> (TransportReader, TransportWriter) {
     (
-        TransportReader::master(link_modes, parse_options, address, rx_buffer_size.value()),
-        TransportWriter::new(EndpointType::Master, address),
+        TransportReader::master(
+            link_modes,
+            parse_options,
+            address,
+            rx_buffer_size.value(),
+            monitor.clone(),
+        ),
+        TransportWriter::new(EndpointType::Master, address, monitor),
     )
 }
 
@@ -35,6 +43,7 @@ pub(crate) fn create_outstation_transport_layer(
     address: EndpointAddress,
     self_address: Feature,
     rx_buffer_size: BufferSize,
+    monitor: ChannelMonitor,
 ) -> (TransportReader, TransportWriter) {
     (
         TransportReader::outstation(
@@ -43,7 +52,8 @@ pub(crate) fn create_outstation_transport_layer(
             address,
             self_address,
             rx_buffer_size.value(),
+            monitor.clone(),
         ),
-        TransportWriter::new(EndpointType::Outstation, address),
+        TransportWriter::new(EndpointType::Outstation, address, monitor),
     )
 }
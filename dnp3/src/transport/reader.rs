@@ -1,7 +1,7 @@
 use crate::app::parse::options::ParseOptions;
 use crate::app::parse::parser::ParsedFragment;
 use crate::app::HeaderParseError;
-use crate::decode::{AppDecodeLevel, DecodeLevel};
+use crate::decode::{AppDecodeLevel, DecodeDirection, DecodeLevel};
 use crate::link::error::LinkError;
 use crate::link::reader::LinkModes;
 use crate::link::EndpointAddress;
@@ -10,6 +10,7 @@ use crate::transport::{
     FragmentAddr, FragmentInfo, LinkLayerMessage, TransportData, TransportRequest,
     TransportResponse,
 };
+use crate::util::monitor::ChannelMonitor;
 use crate::util::phys::PhysLayer;
 
 #[cfg(not(test))]
@@ -21,6 +22,7 @@ pub(crate) type InnerReaderType = crate::transport::mock::reader::MockReader;
 
 pub(crate) struct TransportReader {
     parse_options: ParseOptions,
+    monitor: ChannelMonitor,
     inner: InnerReaderType,
 }
 
@@ -60,9 +62,11 @@ impl TransportReader {
         parse_options: ParseOptions,
         address: EndpointAddress,
         rx_buffer_size: usize,
+        monitor: ChannelMonitor,
     ) -> Self {
         Self {
             parse_options,
+            monitor,
             inner: InnerReaderType::master(link_modes, address, rx_buffer_size),
         }
     }
@@ -73,9 +77,11 @@ impl TransportReader {
         address: EndpointAddress,
         self_address: Feature,
         rx_buffer_size: usize,
+        monitor: ChannelMonitor,
     ) -> Self {
         Self {
             parse_options,
+            monitor,
             inner: InnerReaderType::outstation(link_modes, address, self_address, rx_buffer_size),
         }
     }
@@ -94,7 +100,7 @@ impl TransportReader {
         io: &mut PhysLayer,
         decode_level: DecodeLevel,
     ) -> Result<(), LinkError> {
-        self.inner.read(io, decode_level).await?;
+        self.inner.read(io, decode_level, &self.monitor).await?;
         if decode_level.application.enabled() {
             self.decode(decode_level.application);
         }
@@ -104,8 +110,11 @@ impl TransportReader {
     fn decode(&self, level: AppDecodeLevel) {
         if let Some(TransportData::Fragment(fragment)) = self.inner.peek() {
             match ParsedFragment::parse(self.parse_options, fragment.data) {
-                Ok(fragment) => {
-                    tracing::info!("APP RX - {}", fragment.display(level));
+                Ok(parsed) => {
+                    tracing::info!("APP RX - {}", parsed.display(level));
+                    self.monitor
+                        .decode
+                        .application(DecodeDirection::Rx, fragment.data, level);
                 }
                 Err(err) => {
                     tracing::warn!("error parsing fragment header: {}", err);
@@ -114,6 +123,10 @@ impl TransportReader {
         }
     }
 
+    pub(crate) fn set_local_address(&mut self, address: EndpointAddress) {
+        self.inner.set_local_address(address)
+    }
+
     pub(crate) fn reset(&mut self) {
         self.inner.reset()
     }
@@ -1,5 +1,5 @@
 use crate::app::EndpointType;
-use crate::decode::DecodeLevel;
+use crate::decode::{DecodeDirection, DecodeLevel};
 use crate::link::error::LinkError;
 use crate::link::header::FrameType;
 use crate::link::parser::FramePayload;
@@ -10,6 +10,7 @@ use crate::transport::real::assembler::{Assembler, AssemblyState};
 use crate::transport::real::display::SegmentDisplay;
 use crate::transport::real::header::Header;
 use crate::transport::{LinkLayerMessage, LinkLayerMessageType, TransportData};
+use crate::util::monitor::ChannelMonitor;
 use crate::util::phys::PhysLayer;
 
 pub(crate) struct Reader {
@@ -56,6 +57,10 @@ impl Reader {
         }
     }
 
+    pub(crate) fn set_local_address(&mut self, address: EndpointAddress) {
+        self.link.set_local_address(address);
+    }
+
     pub(crate) fn reset(&mut self) {
         self.assembler.reset();
         self.link.reset();
@@ -86,6 +91,7 @@ impl Reader {
         &mut self,
         io: &mut PhysLayer,
         level: DecodeLevel,
+        monitor: &ChannelMonitor,
     ) -> Result<(), LinkError> {
         if self.assembler.peek().is_some() {
             return Ok(());
@@ -94,7 +100,7 @@ impl Reader {
         let mut payload = FramePayload::new();
 
         loop {
-            let info = self.link.read(io, level, &mut payload).await?;
+            let info = self.link.read(io, level, monitor, &mut payload).await?;
 
             match info.frame_type {
                 FrameType::Data => match payload.get() {
@@ -105,6 +111,12 @@ impl Reader {
                                 "TRANSPORT RX - {}",
                                 SegmentDisplay::new(header, data, level.transport)
                             );
+                            monitor.decode.transport(
+                                DecodeDirection::Rx,
+                                *transport,
+                                data,
+                                level.transport,
+                            );
                         }
 
                         if let AssemblyState::Complete = self.assembler.assemble(info, header, data)
@@ -1,11 +1,12 @@
 use crate::app::EndpointType;
-use crate::decode::DecodeLevel;
+use crate::decode::{DecodeDirection, DecodeLevel};
 use crate::link::error::LinkError;
 use crate::link::format::{format_data_frame, format_header_only, Payload};
 use crate::link::EndpointAddress;
 use crate::transport::real::display::SegmentDisplay;
 use crate::transport::real::header::Header;
 use crate::transport::real::sequence::Sequence;
+use crate::util::monitor::ChannelMonitor;
 use crate::util::phys::PhysLayer;
 
 use crate::transport::FragmentAddr;
@@ -28,6 +29,10 @@ impl Writer {
         }
     }
 
+    pub(crate) fn set_local_address(&mut self, address: EndpointAddress) {
+        self.local_address = address;
+    }
+
     pub(crate) fn reset(&mut self) {
         self.seq.reset();
     }
@@ -36,6 +41,7 @@ impl Writer {
         &mut self,
         io: &mut PhysLayer,
         level: DecodeLevel,
+        monitor: &ChannelMonitor,
         destination: FragmentAddr,
         fragment: &[u8],
     ) -> Result<(), LinkError> {
@@ -55,6 +61,12 @@ impl Writer {
                     "TRANSPORT TX - {}",
                     SegmentDisplay::new(header, chunk, level.transport)
                 );
+                monitor.decode.transport(
+                    DecodeDirection::Tx,
+                    header.to_u8(),
+                    chunk,
+                    level.transport,
+                );
             }
             let link_header = crate::link::header::Header::unconfirmed_user_data(
                 self.endpoint_type.dir_bit(),
@@ -68,9 +80,16 @@ impl Writer {
             )?;
             if level.link.header_enabled() {
                 tracing::info!("LINK TX - {}", data.to_link_display(level.link));
+                monitor.decode.link(
+                    DecodeDirection::Tx,
+                    data.header,
+                    data.payload_only,
+                    level.link,
+                );
             }
-            io.write(data.frame, destination.phys, level.physical)
+            io.write(data.frame, destination.phys, level.physical, monitor)
                 .await?;
+            monitor.counters.on_frame_tx();
         }
 
         Ok(())
@@ -81,6 +100,7 @@ impl Writer {
         io: &mut PhysLayer,
         destination: FragmentAddr,
         level: DecodeLevel,
+        monitor: &ChannelMonitor,
     ) -> Result<(), LinkError> {
         let mut cursor = WriteCursor::new(&mut self.buffer);
         let header = crate::link::header::Header::request_link_status(
@@ -92,9 +112,16 @@ impl Writer {
         let data = format_header_only(header, &mut cursor)?;
         if level.link.enabled() {
             tracing::info!("LINK TX - {}", data.to_link_display(level.link));
+            monitor.decode.link(
+                DecodeDirection::Tx,
+                data.header,
+                data.payload_only,
+                level.link,
+            );
         }
-        io.write(data.frame, destination.phys, level.physical)
+        io.write(data.frame, destination.phys, level.physical, monitor)
             .await?;
+        monitor.counters.on_frame_tx();
 
         Ok(())
     }
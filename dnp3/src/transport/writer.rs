@@ -1,10 +1,11 @@
 use crate::app::parse::options::ParseOptions;
 use crate::app::parse::parser::{FragmentDisplay, ParsedFragment};
 use crate::app::EndpointType;
-use crate::decode::DecodeLevel;
+use crate::decode::{DecodeDirection, DecodeLevel};
 use crate::link::error::LinkError;
 use crate::link::EndpointAddress;
 use crate::transport::FragmentAddr;
+use crate::util::monitor::ChannelMonitor;
 use crate::util::phys::PhysLayer;
 
 /// This type definition is used so that we can mock the transport writer during testing.
@@ -15,16 +16,31 @@ pub(crate) type InnerTransportWriter = crate::transport::real::writer::Writer;
 pub(crate) type InnerTransportWriter = crate::transport::mock::writer::MockWriter;
 
 pub(crate) struct TransportWriter {
+    monitor: ChannelMonitor,
     inner: InnerTransportWriter,
 }
 
 impl TransportWriter {
-    pub(crate) fn new(endpoint_type: EndpointType, local_address: EndpointAddress) -> Self {
+    pub(crate) fn new(
+        endpoint_type: EndpointType,
+        local_address: EndpointAddress,
+        monitor: ChannelMonitor,
+    ) -> Self {
         Self {
+            monitor,
             inner: InnerTransportWriter::new(endpoint_type, local_address),
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn local_address(&self) -> EndpointAddress {
+        self.inner.local_address()
+    }
+
+    pub(crate) fn set_local_address(&mut self, address: EndpointAddress) {
+        self.inner.set_local_address(address)
+    }
+
     pub(crate) fn reset(&mut self) {
         self.inner.reset()
     }
@@ -41,13 +57,20 @@ impl TransportWriter {
                 Ok(parsed) => {
                     let x: FragmentDisplay = parsed.display(level.application);
                     tracing::info!("APP TX - {}", x);
+                    self.monitor.decode.application(
+                        DecodeDirection::Tx,
+                        fragment,
+                        level.application,
+                    );
                 }
                 Err(err) => {
                     tracing::error!("error decoding transmitted fragment: {err}");
                 }
             }
         }
-        self.inner.write(io, level, destination, fragment).await
+        self.inner
+            .write(io, level, &self.monitor, destination, fragment)
+            .await
     }
 
     pub(crate) async fn send_link_status_request(
@@ -56,6 +79,8 @@ impl TransportWriter {
         level: DecodeLevel,
         dest: FragmentAddr,
     ) -> Result<(), LinkError> {
-        self.inner.write_link_status_request(io, dest, level).await
+        self.inner
+            .write_link_status_request(io, dest, level, &self.monitor)
+            .await
     }
 }
@@ -0,0 +1,458 @@
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+
+use openssl::ssl::Ssl;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+use tokio::net::UdpSocket;
+use tokio_openssl::SslStream;
+
+use crate::capture::Medium;
+use crate::udp::dtls::DtlsConfig;
+use crate::util::phys::PhysAddr;
+
+/// maximum size of the datagrams produced by the DTLS layer
+const MTU: u32 = 1200;
+
+/// a handshake that does not complete within this time is abandoned and retried
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Period at which a pending handshake is polled even if no datagram was received
+///
+/// OpenSSL only evaluates its DTLS retransmission timer (`DTLSv1_handle_timeout`) when the
+/// handshake is driven, and the transport only wakes the handshake when a datagram arrives.
+/// Polling periodically allows OpenSSL to retransmit a flight that was lost.
+const RETRANSMIT_CHECK_PERIOD: Duration = Duration::from_millis(100);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum DtlsRole {
+    Client,
+    Server,
+}
+
+/// Connected UDP socket presented to OpenSSL as a stream where each read
+/// returns one datagram and each write sends one datagram
+struct DatagramStream {
+    socket: UdpSocket,
+    /// fail reads when the peer starts a new handshake, set once a server session is established
+    detect_new_handshake: bool,
+}
+
+impl AsyncRead for DatagramStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let start = buf.filled().len();
+        ready!(self.socket.poll_recv(cx, buf))?;
+        // OpenSSL silently discards a ClientHello received on an established session, so a
+        // client that restarted would never be able to establish a new one
+        if self.detect_new_handshake && is_client_hello(&buf.filled()[start..]) {
+            return Poll::Ready(Err(std::io::Error::new(
+                std::io::ErrorKind::ConnectionReset,
+                "peer started a new DTLS handshake",
+            )));
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// Check if a datagram starts with an unencrypted (epoch 0) ClientHello record
+fn is_client_hello(datagram: &[u8]) -> bool {
+    const HANDSHAKE_RECORD: u8 = 22;
+    const CLIENT_HELLO: u8 = 1;
+    const RECORD_HEADER_LENGTH: usize = 13;
+
+    match datagram {
+        [HANDSHAKE_RECORD, _, _, 0, 0, ..] => {
+            datagram.get(RECORD_HEADER_LENGTH) == Some(&CLIENT_HELLO)
+        }
+        _ => false,
+    }
+}
+
+impl AsyncWrite for DatagramStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.socket.poll_send(cx, buf)
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+/// DTLS session with a single remote endpoint
+pub(crate) struct DtlsLayer {
+    stream: SslStream<DatagramStream>,
+    remote: SocketAddr,
+}
+
+impl DtlsLayer {
+    pub(crate) fn medium(&self) -> Medium {
+        match self.stream.get_ref().socket.local_addr() {
+            Ok(local) => Medium::Udp {
+                local,
+                remote: Some(self.remote),
+            },
+            Err(_) => Medium::Other,
+        }
+    }
+
+    pub(crate) async fn open(
+        local: SocketAddr,
+        remote: SocketAddr,
+        config: &DtlsConfig,
+        role: DtlsRole,
+    ) -> std::io::Result<Self> {
+        let socket = UdpSocket::bind(local).await?;
+        socket.connect(remote).await?;
+        Self::handshake(socket, remote, config, role).await
+    }
+
+    async fn handshake(
+        socket: UdpSocket,
+        remote: SocketAddr,
+        config: &DtlsConfig,
+        role: DtlsRole,
+    ) -> std::io::Result<Self> {
+        let mut ssl = Ssl::new(&config.context).map_err(std::io::Error::other)?;
+        ssl.set_mtu(MTU).map_err(std::io::Error::other)?;
+        if let Some(name) = &config.peer_subject_name {
+            ssl.param_mut()
+                .set_host(name)
+                .map_err(std::io::Error::other)?;
+        }
+
+        let mut stream = SslStream::new(
+            ssl,
+            DatagramStream {
+                socket,
+                detect_new_handshake: false,
+            },
+        )
+        .map_err(std::io::Error::other)?;
+
+        if role == DtlsRole::Server {
+            // wait indefinitely for the client to initiate the handshake
+            stream.get_ref().socket.readable().await?;
+        }
+
+        let handshake = async {
+            loop {
+                let step = std::future::poll_fn(|cx| match role {
+                    DtlsRole::Client => Pin::new(&mut stream).poll_connect(cx),
+                    DtlsRole::Server => Pin::new(&mut stream).poll_accept(cx),
+                });
+                if let Ok(result) = tokio::time::timeout(RETRANSMIT_CHECK_PERIOD, step).await {
+                    return result;
+                }
+            }
+        };
+
+        match tokio::time::timeout(HANDSHAKE_TIMEOUT, handshake).await {
+            Err(_) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "DTLS handshake timed out",
+                ))
+            }
+            Ok(Err(err)) => {
+                return Err(std::io::Error::other(format!(
+                    "DTLS handshake with {remote} failed: {err}"
+                )))
+            }
+            Ok(Ok(())) => {}
+        }
+
+        tracing::info!("DTLS session established with {remote}");
+
+        // a client restarting its handshake is only detected by the server, clients rely on the
+        // master failing the session after consecutive response timeouts
+        stream.get_mut().detect_new_handshake = role == DtlsRole::Server;
+
+        Ok(Self { stream, remote })
+    }
+
+    pub(crate) async fn read(
+        &mut self,
+        buffer: &mut [u8],
+    ) -> Result<(usize, PhysAddr), std::io::Error> {
+        let count = self.stream.read(buffer).await?;
+        Ok((count, PhysAddr::Udp(self.remote)))
+    }
+
+    pub(crate) async fn write_all(
+        &mut self,
+        data: &[u8],
+        addr: PhysAddr,
+    ) -> Result<(), std::io::Error> {
+        if let PhysAddr::Udp(addr) = addr {
+            if addr != self.remote {
+                tracing::warn!(
+                    "DTLS session cannot write to {addr}, only to {}",
+                    self.remote
+                );
+                return Ok(());
+            }
+        }
+        self.stream.write_all(data).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::tcp::tls::MinTlsVersion;
+    use std::path::PathBuf;
+
+    fn cert_path(dir: &str, name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../certs")
+            .join(dir)
+            .join(name)
+    }
+
+    fn full_pki(entity: &str, version: MinTlsVersion) -> DtlsConfig {
+        DtlsConfig::full_pki(
+            Some("test.com".to_string()),
+            &cert_path("ca_chain", "ca_cert.pem"),
+            &cert_path("ca_chain", &format!("{entity}_cert.pem")),
+            &cert_path("ca_chain", &format!("{entity}_key.pem")),
+            None,
+            version,
+        )
+        .unwrap()
+    }
+
+    fn self_signed(local: &str, peer: &str) -> DtlsConfig {
+        DtlsConfig::self_signed(
+            &cert_path("self_signed", &format!("{peer}_cert.pem")),
+            &cert_path("self_signed", &format!("{local}_cert.pem")),
+            &cert_path("self_signed", &format!("{local}_key.pem")),
+            None,
+            MinTlsVersion::V12,
+        )
+        .unwrap()
+    }
+
+    async fn connect(
+        client: DtlsConfig,
+        server: DtlsConfig,
+    ) -> (std::io::Result<DtlsLayer>, std::io::Result<DtlsLayer>) {
+        let client_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let client_addr = client_socket.local_addr().unwrap();
+        let server_addr = server_socket.local_addr().unwrap();
+        client_socket.connect(server_addr).await.unwrap();
+        server_socket.connect(client_addr).await.unwrap();
+
+        tokio::join!(
+            DtlsLayer::handshake(client_socket, server_addr, &client, DtlsRole::Client),
+            DtlsLayer::handshake(server_socket, client_addr, &server, DtlsRole::Server),
+        )
+    }
+
+    async fn exchange(client: &mut DtlsLayer, server: &mut DtlsLayer) {
+        let mut buffer = [0; 64];
+        client
+            .write_all(&[0x05, 0x64], PhysAddr::None)
+            .await
+            .unwrap();
+        let (count, addr) = server.read(&mut buffer).await.unwrap();
+        assert_eq!(&buffer[..count], &[0x05, 0x64]);
+        assert_eq!(addr, PhysAddr::Udp(server.remote));
+
+        server.write_all(&[0xAA], addr).await.unwrap();
+        let (count, _) = client.read(&mut buffer).await.unwrap();
+        assert_eq!(&buffer[..count], &[0xAA]);
+    }
+
+    #[tokio::test]
+    async fn exchanges_datagrams_with_authority_based_certificates() {
+        for version in [MinTlsVersion::V12, MinTlsVersion::V13] {
+            let (client, server) =
+                connect(full_pki("entity2", version), full_pki("entity1", version)).await;
+            exchange(&mut client.unwrap(), &mut server.unwrap()).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn exchanges_datagrams_with_self_signed_certificates() {
+        let (client, server) = connect(
+            self_signed("entity2", "entity1"),
+            self_signed("entity1", "entity2"),
+        )
+        .await;
+        exchange(&mut client.unwrap(), &mut server.unwrap()).await;
+    }
+
+    #[tokio::test]
+    async fn retransmits_lost_handshake_datagram() {
+        let client_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        // the proxy forwards datagrams between the client and server, except the first one
+        let client_side = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let server_side = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+
+        client_socket
+            .connect(client_side.local_addr().unwrap())
+            .await
+            .unwrap();
+        client_side
+            .connect(client_socket.local_addr().unwrap())
+            .await
+            .unwrap();
+        server_socket
+            .connect(server_side.local_addr().unwrap())
+            .await
+            .unwrap();
+        server_side
+            .connect(server_socket.local_addr().unwrap())
+            .await
+            .unwrap();
+
+        let proxy = tokio::spawn(async move {
+            let mut from_client = [0; 4096];
+            let mut from_server = [0; 4096];
+            let mut dropped = false;
+            loop {
+                tokio::select! {
+                    res = client_side.recv(&mut from_client) => {
+                        let count = res.unwrap();
+                        if dropped {
+                            server_side.send(&from_client[..count]).await.unwrap();
+                        } else {
+                            dropped = true;
+                        }
+                    }
+                    res = server_side.recv(&mut from_server) => {
+                        let count = res.unwrap();
+                        client_side.send(&from_server[..count]).await.unwrap();
+                    }
+                }
+            }
+        });
+
+        let client_addr = client_socket.local_addr().unwrap();
+        let server_addr = server_socket.local_addr().unwrap();
+        let client_config = full_pki("entity2", MinTlsVersion::V12);
+        let server_config = full_pki("entity1", MinTlsVersion::V12);
+        let handshakes = async {
+            tokio::join!(
+                DtlsLayer::handshake(client_socket, server_addr, &client_config, DtlsRole::Client),
+                DtlsLayer::handshake(server_socket, client_addr, &server_config, DtlsRole::Server),
+            )
+        };
+        let (client, server) = tokio::time::timeout(HANDSHAKE_TIMEOUT, handshakes)
+            .await
+            .unwrap();
+
+        exchange(&mut client.unwrap(), &mut server.unwrap()).await;
+        proxy.abort();
+    }
+
+    fn free_endpoint() -> SocketAddr {
+        std::net::UdpSocket::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+    }
+
+    async fn open(
+        local: SocketAddr,
+        remote: SocketAddr,
+        role: DtlsRole,
+    ) -> std::io::Result<DtlsLayer> {
+        let config = match role {
+            DtlsRole::Client => full_pki("entity2", MinTlsVersion::V12),
+            DtlsRole::Server => full_pki("entity1", MinTlsVersion::V12),
+        };
+        DtlsLayer::open(local, remote, &config, role).await
+    }
+
+    #[tokio::test]
+    async fn server_fails_session_when_client_restarts() {
+        let client_addr = free_endpoint();
+        let server_addr = free_endpoint();
+
+        let (client, server) = tokio::join!(
+            open(client_addr, server_addr, DtlsRole::Client),
+            open(server_addr, client_addr, DtlsRole::Server),
+        );
+        let mut server = server.unwrap();
+        exchange(&mut client.unwrap(), &mut server).await;
+
+        // the restarted client starts a new handshake from the same endpoint
+        let client = tokio::spawn(open(client_addr, server_addr, DtlsRole::Client));
+        let mut buffer = [0; 64];
+        let err = server.read(&mut buffer).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::ConnectionReset);
+        drop(server);
+
+        // the ClientHello that was consumed is retransmitted to the new session
+        let server = open(server_addr, client_addr, DtlsRole::Server).await;
+        exchange(&mut client.await.unwrap().unwrap(), &mut server.unwrap()).await;
+    }
+
+    #[tokio::test]
+    async fn client_reconnects_to_restarted_server() {
+        let client_addr = free_endpoint();
+        let server_addr = free_endpoint();
+
+        let (client, server) = tokio::join!(
+            open(client_addr, server_addr, DtlsRole::Client),
+            open(server_addr, client_addr, DtlsRole::Server),
+        );
+        let mut client = client.unwrap();
+        exchange(&mut client, &mut server.unwrap()).await;
+
+        // the restarted server discards the records of the previous session
+        let server = tokio::spawn(open(server_addr, client_addr, DtlsRole::Server));
+        client
+            .write_all(&[0x05, 0x64], PhysAddr::None)
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // which the master detects with response timeouts, and then re-opens the session
+        drop(client);
+        let client = open(client_addr, server_addr, DtlsRole::Client).await;
+        exchange(&mut client.unwrap(), &mut server.await.unwrap().unwrap()).await;
+    }
+
+    #[test]
+    fn detects_client_hello() {
+        let mut record = [0u8; 14];
+        record[0] = 22;
+        record[13] = 1;
+        assert!(is_client_hello(&record));
+        // a handshake message of a later epoch, e.g. a renegotiation
+        record[4] = 1;
+        assert!(!is_client_hello(&record));
+        // application data
+        assert!(!is_client_hello(&[23, 0xFE, 0xFD, 0, 1]));
+        assert!(!is_client_hello(&[]));
+    }
+
+    #[tokio::test]
+    async fn rejects_unexpected_self_signed_certificate() {
+        // the server expects the client to present its own certificate
+        let (client, server) = connect(
+            self_signed("entity2", "entity1"),
+            self_signed("entity1", "entity1"),
+        )
+        .await;
+        assert!(client.is_err());
+        assert!(server.is_err());
+    }
+}
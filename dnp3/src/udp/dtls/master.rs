@@ -0,0 +1,73 @@
+use crate::link::reader::LinkModes;
+use crate::link::{LinkErrorMode, LinkReadMode};
+use crate::master::task::MasterTask;
+use crate::master::{MasterChannel, MasterChannelConfig, MasterChannelType};
+use crate::udp::dtls::{DtlsConfig, DtlsRole};
+use crate::udp::layer::UdpFactory;
+use crate::udp::task::UdpTask;
+use crate::util::session::{Enabled, Session};
+
+use crate::app::parse::options::ParseOptions;
+use crate::app::Timeout;
+use std::net::SocketAddr;
+use tracing::Instrument;
+
+/// number of consecutive response timeouts after which the DTLS session is re-established
+const MAX_RESPONSE_TIMEOUTS: usize = 3;
+
+/// Spawn a UDP master task secured with DTLS onto the `Tokio` runtime. The task runs until the
+/// returned handle, and any `AssociationHandle` created from it, are dropped.
+///
+/// The master acts as the DTLS client and only communicates with `remote_endpoint`. Associations
+/// must be added using this same endpoint as their destination.
+///
+/// The DTLS session is re-established after 3 consecutive response timeouts, so that the master
+/// recovers when the outstation restarts and loses the session.
+///
+/// **Note**: This function may only be called from within the runtime itself, and panics otherwise.
+/// Use Runtime::enter() if required.
+pub fn spawn_master_dtls(
+    local_endpoint: SocketAddr,
+    remote_endpoint: SocketAddr,
+    read_mode: LinkReadMode,
+    retry_delay: Timeout,
+    config: MasterChannelConfig,
+    dtls_config: DtlsConfig,
+) -> MasterChannel {
+    let (tx, rx) = crate::util::channel::request_channel();
+    let link_modes = LinkModes {
+        error_mode: LinkErrorMode::Discard,
+        read_mode,
+    };
+
+    let mut task = MasterTask::new(
+        Enabled::No,
+        link_modes,
+        ParseOptions::get_static(),
+        config,
+        rx,
+    );
+    // an outstation that restarted silently discards the records of the previous session, so
+    // the session is re-established after consecutive response timeouts
+    task.set_max_response_timeouts(Some(MAX_RESPONSE_TIMEOUTS));
+    let session = Session::master(task);
+    let task = UdpTask {
+        session,
+        factory: UdpFactory::dtls(
+            local_endpoint,
+            remote_endpoint,
+            dtls_config,
+            DtlsRole::Client,
+        ),
+        retry_delay,
+    };
+    let future = async move {
+        let _ = task
+            .run()
+            .instrument(tracing::info_span!("dnp3-master-dtls", "endpoint" = ?local_endpoint))
+            .await;
+    };
+
+    tokio::spawn(future);
+    MasterChannel::new(tx, MasterChannelType::Udp)
+}
@@ -0,0 +1,202 @@
+mod layer;
+mod master;
+mod outstation;
+
+pub(crate) use layer::*;
+pub use master::*;
+pub use outstation::*;
+
+use std::path::Path;
+
+use openssl::asn1::Asn1Time;
+use openssl::pkey::PKey;
+use openssl::ssl::{SslContext, SslMethod, SslOptions, SslVerifyMode, SslVersion};
+use openssl::x509::X509;
+
+use crate::tcp::tls::{CertificateMode, MinTlsVersion, TlsError};
+
+/// DTLS configuration for a UDP master or outstation
+///
+/// The master always acts as the DTLS client and the outstation as the DTLS server.
+///
+/// The configuration options mirror those of the TLS configurations in [`crate::tcp::tls`].
+/// DTLS is provided by the system OpenSSL library, so DTLS 1.3 is only negotiated when the
+/// linked version of OpenSSL supports it.
+#[derive(Clone)]
+pub struct DtlsConfig {
+    context: SslContext,
+    peer_subject_name: Option<String>,
+}
+
+impl DtlsConfig {
+    /// Create a DTLS configuration using the specified [`CertificateMode`]
+    ///
+    /// `peer_subject_name` is only used with [`CertificateMode::AuthorityBased`].
+    /// See [`DtlsConfig::full_pki`] and [`DtlsConfig::self_signed`] for details.
+    pub fn new(
+        peer_subject_name: Option<String>,
+        peer_cert_path: &Path,
+        local_cert_path: &Path,
+        private_key_path: &Path,
+        password: Option<&str>,
+        min_tls_version: MinTlsVersion,
+        certificate_mode: CertificateMode,
+    ) -> Result<Self, TlsError> {
+        match certificate_mode {
+            CertificateMode::AuthorityBased => Self::full_pki(
+                peer_subject_name,
+                peer_cert_path,
+                local_cert_path,
+                private_key_path,
+                password,
+                min_tls_version,
+            ),
+            CertificateMode::SelfSigned => Self::self_signed(
+                peer_cert_path,
+                local_cert_path,
+                private_key_path,
+                password,
+                min_tls_version,
+            ),
+        }
+    }
+
+    /// Create a DTLS configuration that expects a full PKI with an authority, and possibly
+    /// intermediate CA certificates.
+    ///
+    /// If `peer_subject_name` is specified, then the name must be present in the SAN extension
+    /// or in the Common Name of the peer certificate.
+    ///
+    /// If `peer_subject_name` is set to None, then no name validation is performed, and
+    /// any authenticated peer is allowed.
+    pub fn full_pki(
+        peer_subject_name: Option<String>,
+        peer_cert_path: &Path,
+        local_cert_path: &Path,
+        private_key_path: &Path,
+        password: Option<&str>,
+        min_tls_version: MinTlsVersion,
+    ) -> Result<Self, TlsError> {
+        let mut builder =
+            Self::builder(local_cert_path, private_key_path, password, min_tls_version)?;
+
+        builder
+            .set_ca_file(peer_cert_path)
+            .map_err(|err| TlsError::InvalidPeerCertificate(std::io::Error::other(err)))?;
+        builder.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+
+        Ok(Self {
+            context: builder.build(),
+            peer_subject_name,
+        })
+    }
+
+    /// Create a DTLS configuration that expects the peer to present a single certificate.
+    ///
+    /// In lieu of performing subject name validation, the following is validated:
+    ///
+    /// 1) That the peer presents a single certificate
+    /// 2) That the certificate is a byte-for-byte match with the one loaded in `peer_cert_path`.
+    /// 3) That the certificate's Validity (not before / not after) is currently valid.
+    ///
+    pub fn self_signed(
+        peer_cert_path: &Path,
+        local_cert_path: &Path,
+        private_key_path: &Path,
+        password: Option<&str>,
+        min_tls_version: MinTlsVersion,
+    ) -> Result<Self, TlsError> {
+        let mut builder =
+            Self::builder(local_cert_path, private_key_path, password, min_tls_version)?;
+
+        let peer_cert = std::fs::read(peer_cert_path)
+            .and_then(|pem| X509::from_pem(&pem).map_err(std::io::Error::other))
+            .and_then(|cert| cert.to_der().map_err(std::io::Error::other))
+            .map_err(TlsError::InvalidPeerCertificate)?;
+
+        builder.set_verify_callback(
+            SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT,
+            move |_, ctx| {
+                // any other depth means that the peer presented a chain
+                if ctx.error_depth() != 0 {
+                    return false;
+                }
+                let cert = match ctx.current_cert() {
+                    Some(x) => x,
+                    None => return false,
+                };
+                let now = match Asn1Time::days_from_now(0) {
+                    Ok(x) => x,
+                    Err(_) => return false,
+                };
+                cert.to_der().ok().as_ref() == Some(&peer_cert)
+                    && cert.not_before() <= now
+                    && cert.not_after() >= now
+            },
+        );
+
+        Ok(Self {
+            context: builder.build(),
+            peer_subject_name: None,
+        })
+    }
+
+    fn builder(
+        local_cert_path: &Path,
+        private_key_path: &Path,
+        password: Option<&str>,
+        min_tls_version: MinTlsVersion,
+    ) -> Result<openssl::ssl::SslContextBuilder, TlsError> {
+        let mut builder = SslContext::builder(SslMethod::dtls()).map_err(other)?;
+
+        // the transport is not an OpenSSL datagram BIO, so the MTU is set on each session
+        builder.set_options(SslOptions::NO_QUERY_MTU);
+        builder
+            .set_min_proto_version(Some(SslVersion::DTLS1_2))
+            .map_err(other)?;
+        // mirrors the TLS configuration where V12 only allows 1.2 and V13 allows both
+        if let MinTlsVersion::V12 = min_tls_version {
+            builder
+                .set_max_proto_version(Some(SslVersion::DTLS1_2))
+                .map_err(other)?;
+        }
+
+        let mut chain = std::fs::read(local_cert_path)
+            .and_then(|pem| X509::stack_from_pem(&pem).map_err(std::io::Error::other))
+            .map_err(TlsError::InvalidLocalCertificate)?
+            .into_iter();
+        let cert = chain.next().ok_or_else(|| {
+            TlsError::InvalidLocalCertificate(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "no certificate found",
+            ))
+        })?;
+        builder
+            .set_certificate(&cert)
+            .map_err(|err| TlsError::InvalidLocalCertificate(std::io::Error::other(err)))?;
+        for cert in chain {
+            builder
+                .add_extra_chain_cert(cert)
+                .map_err(|err| TlsError::InvalidLocalCertificate(std::io::Error::other(err)))?;
+        }
+
+        let pem = std::fs::read(private_key_path).map_err(TlsError::InvalidPrivateKey)?;
+        let key = match password {
+            None => PKey::private_key_from_pem(&pem),
+            Some(password) => PKey::private_key_from_pem_passphrase(&pem, password.as_bytes()),
+        }
+        .map_err(|err| TlsError::InvalidPrivateKey(std::io::Error::other(err)))?;
+        builder
+            .set_private_key(&key)
+            .map_err(|err| TlsError::InvalidPrivateKey(std::io::Error::other(err)))?;
+        builder
+            .check_private_key()
+            .map_err(|err| TlsError::InvalidPrivateKey(std::io::Error::other(err)))?;
+
+        Ok(builder)
+    }
+}
+
+fn other(err: openssl::error::ErrorStack) -> TlsError {
+    TlsError::Other(std::io::Error::other(err))
+}
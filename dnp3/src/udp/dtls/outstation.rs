@@ -0,0 +1,64 @@
+use crate::app::parse::options::ParseOptions;
+use crate::outstation::task::OutstationTask;
+use crate::outstation::{
+    ControlHandler, OutstationApplication, OutstationConfig, OutstationHandle,
+    OutstationInformation,
+};
+use crate::udp::dtls::{DtlsConfig, DtlsRole};
+use crate::udp::layer::UdpFactory;
+use crate::udp::task::UdpTask;
+use crate::udp::OutstationUdpConfig;
+use crate::util::phys::PhysAddr;
+use crate::util::session::{Enabled, Session};
+use tracing::Instrument;
+
+/// Spawn an outstation task secured with DTLS onto the `Tokio` runtime. The task runs until the
+/// returned handle is dropped.
+///
+/// The outstation acts as the DTLS server and **only** communicates with the `remote_endpoint`,
+/// regardless of the configured [`UdpSocketMode`](crate::udp::UdpSocketMode). A new DTLS session
+/// is established when the master restarts and initiates a new handshake.
+///
+/// **Note**: This function may only be called from within the runtime itself, and panics otherwise.
+/// Use Runtime::enter() if required.
+pub fn spawn_outstation_dtls(
+    udp_config: OutstationUdpConfig,
+    config: OutstationConfig,
+    application: Box<dyn OutstationApplication>,
+    information: Box<dyn OutstationInformation>,
+    control_handler: Box<dyn ControlHandler>,
+    dtls_config: DtlsConfig,
+) -> OutstationHandle {
+    let (task, handle) = OutstationTask::create(
+        Enabled::Yes,
+        udp_config.link_modes(),
+        ParseOptions::get_static(),
+        config,
+        PhysAddr::Udp(udp_config.remote_endpoint),
+        application,
+        information,
+        control_handler,
+    );
+
+    let task = UdpTask {
+        session: Session::outstation(task),
+        factory: UdpFactory::dtls(
+            udp_config.local_endpoint,
+            udp_config.remote_endpoint,
+            dtls_config,
+            DtlsRole::Server,
+        ),
+        retry_delay: udp_config.retry_delay,
+    };
+
+    let future = async move {
+        let _ = task
+            .run()
+            .instrument(
+                tracing::info_span!("dnp3-outstation-dtls", "endpoint" = ?udp_config.local_endpoint),
+            )
+            .await;
+    };
+    tokio::spawn(future);
+    handle
+}
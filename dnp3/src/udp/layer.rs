@@ -1,3 +1,4 @@
+use crate::capture::Medium;
 use crate::util::phys::{PhysAddr, PhysLayer};
 use std::io::ErrorKind;
 use std::net::SocketAddr;
@@ -17,6 +18,13 @@ enum UdpFactoryType {
         local: SocketAddr,
         remote: SocketAddr,
     },
+    #[cfg(feature = "dtls")]
+    Dtls {
+        local: SocketAddr,
+        remote: SocketAddr,
+        config: crate::udp::dtls::DtlsConfig,
+        role: crate::udp::dtls::DtlsRole,
+    },
 }
 
 pub(crate) struct UdpFactory(UdpFactoryType);
@@ -29,19 +37,45 @@ impl UdpFactory {
     pub(crate) fn connected(local: SocketAddr, remote: SocketAddr) -> Self {
         Self(UdpFactoryType::Connected { local, remote })
     }
+
+    #[cfg(feature = "dtls")]
+    pub(crate) fn dtls(
+        local: SocketAddr,
+        remote: SocketAddr,
+        config: crate::udp::dtls::DtlsConfig,
+        role: crate::udp::dtls::DtlsRole,
+    ) -> Self {
+        Self(UdpFactoryType::Dtls {
+            local,
+            remote,
+            config,
+            role,
+        })
+    }
 }
 
 impl UdpFactory {
     pub(crate) async fn open(&self) -> std::io::Result<PhysLayer> {
-        match self.0 {
+        match &self.0 {
             UdpFactoryType::Bound { local } => {
-                let layer = UdpLayer::bind(local).await?;
+                let layer = UdpLayer::bind(*local).await?;
                 Ok(PhysLayer::Udp(layer))
             }
             UdpFactoryType::Connected { local, remote } => {
-                let layer = UdpLayer::connect(local, remote).await?;
+                let layer = UdpLayer::connect(*local, *remote).await?;
                 Ok(PhysLayer::Udp(layer))
             }
+            #[cfg(feature = "dtls")]
+            UdpFactoryType::Dtls {
+                local,
+                remote,
+                config,
+                role,
+            } => {
+                let layer =
+                    crate::udp::dtls::DtlsLayer::open(*local, *remote, config, *role).await?;
+                Ok(PhysLayer::Dtls(Box::new(layer)))
+            }
         }
     }
 }
@@ -104,6 +138,20 @@ impl UdpType {
 }
 
 impl UdpLayer {
+    pub(crate) fn medium(&self) -> Medium {
+        let (socket, connected) = match &self.inner {
+            UdpType::Bound(x) => (x, false),
+            UdpType::Connected(x) => (x, true),
+        };
+        match socket.local_addr() {
+            Ok(local) => Medium::Udp {
+                local,
+                remote: socket.peer_addr().ok().filter(|_| connected),
+            },
+            Err(_) => Medium::Other,
+        }
+    }
+
     pub(crate) async fn read(
         &mut self,
         buffer: &mut [u8],
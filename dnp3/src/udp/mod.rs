@@ -1,3 +1,6 @@
+/// DTLS transport for UDP
+#[cfg(feature = "dtls")]
+pub mod dtls;
 pub(crate) mod layer;
 mod master;
 mod outstation;
@@ -40,7 +40,7 @@ impl OutstationUdpConfig {
         }
     }
 
-    fn link_modes(&self) -> LinkModes {
+    pub(crate) fn link_modes(&self) -> LinkModes {
         match self.link_read_mode {
             LinkReadMode::Stream => LinkModes::stream(LinkErrorMode::Discard),
             LinkReadMode::Datagram => LinkModes::datagram(LinkErrorMode::Discard),
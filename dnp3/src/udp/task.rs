@@ -1,6 +1,7 @@
 use crate::app::{Shutdown, Timeout};
 use crate::udp::layer::UdpFactory;
-use crate::util::session::{RunError, Session, StopReason};
+use crate::util::phys::PhysLayer;
+use crate::util::session::{Enabled, RunError, Session, StopReason};
 
 pub(crate) struct UdpTask {
     pub(crate) session: Session,
@@ -33,7 +34,12 @@ impl UdpTask {
     }
 
     async fn run_one(&mut self) -> Result<Delay, Shutdown> {
-        match self.factory.open().await {
+        let result = match self.open().await {
+            Ok(x) => x,
+            Err(reason) => return Self::handle_stop(reason),
+        };
+
+        match result {
             Ok(mut phys) => match self.session.run(&mut phys).await {
                 RunError::Stop(r) => Self::handle_stop(r),
                 RunError::Link(err) => {
@@ -47,4 +53,22 @@ impl UdpTask {
             }
         }
     }
+
+    /// open the physical layer while processing messages, e.g. while waiting for a DTLS handshake
+    async fn open(&mut self) -> Result<std::io::Result<PhysLayer>, StopReason> {
+        let open = self.factory.open();
+        tokio::pin!(open);
+
+        loop {
+            tokio::select! {
+                result = &mut open => return Ok(result),
+                result = self.session.process_next_message() => {
+                    result?;
+                    if self.session.enabled() == Enabled::No {
+                        return Err(StopReason::Disable);
+                    }
+                }
+            }
+        }
+    }
 }
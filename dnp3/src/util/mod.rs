@@ -3,6 +3,7 @@ pub(crate) mod buffer;
 pub(crate) mod channel;
 pub(crate) mod decode;
 pub(crate) mod future;
+pub(crate) mod monitor;
 pub(crate) mod phys;
 pub(crate) mod session;
 pub(crate) mod shutdown;
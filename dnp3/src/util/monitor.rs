@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::capture::{Capture, Direction};
+use crate::decode::DecodeSinkSlot;
+use crate::statistics::ChannelCounters;
+use crate::util::phys::{PhysAddr, PhysLayer};
+
+/// Observes the traffic of a channel, recording it to a capture, updating its counters,
+/// and forwarding decoded frames, segments and fragments to a decode sink
+///
+/// Cloned into every layer that reads or writes the channel.
+#[derive(Clone, Default)]
+pub(crate) struct ChannelMonitor {
+    pub(crate) capture: Capture,
+    pub(crate) counters: Arc<ChannelCounters>,
+    pub(crate) decode: DecodeSinkSlot,
+}
+
+impl ChannelMonitor {
+    pub(crate) fn on_rx(&self, io: &PhysLayer, data: &[u8], addr: PhysAddr) {
+        self.counters.on_bytes_rx(data.len());
+        self.capture.record(io, Direction::Rx, data, addr);
+    }
+
+    pub(crate) fn on_tx(&self, io: &PhysLayer, data: &[u8], addr: PhysAddr) {
+        self.counters.on_bytes_tx(data.len());
+        self.capture.record(io, Direction::Tx, data, addr);
+    }
+}
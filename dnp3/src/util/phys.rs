@@ -1,8 +1,10 @@
+use crate::capture::Medium;
 use crate::decode::PhysDecodeLevel;
 use std::io::ErrorKind;
 use std::net::SocketAddr;
 
 use crate::udp::layer::UdpLayer;
+use crate::util::monitor::ChannelMonitor;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 /// Source or destination at the physical layer from which a link frame was read/written
@@ -20,7 +22,10 @@ pub(crate) enum PhysLayer {
     Udp(UdpLayer),
     /// TLS type is boxed because its size is huge
     #[cfg(feature = "enable-tls")]
-    Tls(Box<tokio_rustls::TlsStream<tokio::net::TcpStream>>),
+    Tls(Box<crate::tcp::tls::TlsSession>),
+    /// DTLS type is boxed because its size is huge
+    #[cfg(feature = "dtls")]
+    Dtls(Box<crate::udp::dtls::DtlsLayer>),
     #[cfg(feature = "serial")]
     Serial(tokio_serial::SerialStream),
     #[cfg(test)]
@@ -34,6 +39,8 @@ impl std::fmt::Debug for PhysLayer {
             PhysLayer::Udp(_) => f.write_str("Udp"),
             #[cfg(feature = "enable-tls")]
             PhysLayer::Tls(_) => f.write_str("Tls"),
+            #[cfg(feature = "dtls")]
+            PhysLayer::Dtls(_) => f.write_str("Dtls"),
             #[cfg(feature = "serial")]
             PhysLayer::Serial(_) => f.write_str("Serial"),
             #[cfg(test)]
@@ -47,6 +54,7 @@ impl PhysLayer {
         &mut self,
         buffer: &mut [u8],
         level: PhysDecodeLevel,
+        monitor: &ChannelMonitor,
     ) -> Result<(usize, PhysAddr), std::io::Error> {
         let (length, addr) = match self {
             Self::Tcp(x) => {
@@ -54,6 +62,8 @@ impl PhysLayer {
                 (count, PhysAddr::None)
             }
             Self::Udp(x) => x.read(buffer).await?,
+            #[cfg(feature = "dtls")]
+            Self::Dtls(x) => x.read(buffer).await?,
             #[cfg(feature = "enable-tls")]
             Self::Tls(x) => {
                 let count = x.read(buffer).await?;
@@ -78,10 +88,11 @@ impl PhysLayer {
             ));
         }
 
-        if level.enabled() {
-            if let Some(x) = buffer.get(0..length) {
+        if let Some(x) = buffer.get(0..length) {
+            if level.enabled() {
                 tracing::info!("PHYS RX - {}", PhysDisplay::new(level, x))
             }
+            monitor.on_rx(self, x, addr);
         }
 
         Ok((length, addr))
@@ -92,14 +103,18 @@ impl PhysLayer {
         data: &[u8],
         addr: PhysAddr,
         level: PhysDecodeLevel,
+        monitor: &ChannelMonitor,
     ) -> Result<(), std::io::Error> {
         if level.enabled() {
             tracing::info!("PHYS TX - {}", PhysDisplay::new(level, data));
         }
+        monitor.on_tx(self, data, addr);
 
         match self {
             Self::Tcp(x) => x.write_all(data).await,
             Self::Udp(x) => x.write_all(data, addr).await,
+            #[cfg(feature = "dtls")]
+            Self::Dtls(x) => x.write_all(data, addr).await,
             #[cfg(feature = "enable-tls")]
             Self::Tls(x) => x.write_all(data).await,
             #[cfg(feature = "serial")]
@@ -108,6 +123,34 @@ impl PhysLayer {
             Self::Mock(x) => x.write_all(data).await,
         }
     }
+
+    /// addresses of the layer used to build synthetic headers when capturing traffic
+    pub(crate) fn medium(&self) -> Medium {
+        match self {
+            Self::Tcp(x) => match (x.local_addr(), x.peer_addr()) {
+                (Ok(local), Ok(remote)) => Medium::Tcp { local, remote },
+                _ => Medium::Other,
+            },
+            Self::Udp(x) => x.medium(),
+            #[cfg(feature = "dtls")]
+            Self::Dtls(x) => x.medium(),
+            #[cfg(feature = "enable-tls")]
+            Self::Tls(x) => x.medium(),
+            #[cfg(feature = "serial")]
+            Self::Serial(_) => Medium::Other,
+            #[cfg(test)]
+            Self::Mock(_) => Medium::Other,
+        }
+    }
+
+    /// identity of the remote peer if the layer is an authenticated TLS session
+    pub(crate) fn peer_identity(&self) -> Option<std::sync::Arc<crate::tcp::PeerIdentity>> {
+        match self {
+            #[cfg(feature = "enable-tls")]
+            Self::Tls(x) => Some(x.identity()),
+            _ => None,
+        }
+    }
 }
 
 pub(crate) struct PhysDisplay<'a> {
@@ -110,6 +110,17 @@ impl Session {
         }
     }
 
+    pub(crate) fn set_connection_overrides(&mut self, rules: Vec<crate::outstation::OverrideRule>) {
+        match &mut self.inner {
+            SessionType::Master(_) => {
+                tracing::warn!("Attempted to set connection overrides on a master session. This feature is only supported for outstations.");
+            }
+            SessionType::Outstation(x) => {
+                x.set_connection_overrides(rules);
+            }
+        }
+    }
+
     pub(crate) fn enabled(&self) -> Enabled {
         match &self.inner {
             SessionType::Master(x) => x.enabled(),
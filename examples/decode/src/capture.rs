@@ -0,0 +1,350 @@
+//! Minimal reader for pcap and pcapng capture files
+
+use std::time::Duration;
+
+const PCAP_MICROS: u32 = 0xA1B2_C3D4;
+const PCAP_NANOS: u32 = 0xA1B2_3C4D;
+const PCAPNG_SECTION_HEADER: u32 = 0x0A0D_0D0A;
+const PCAPNG_BYTE_ORDER: u32 = 0x1A2B_3C4D;
+
+const PCAPNG_INTERFACE_DESCRIPTION: u32 = 1;
+const PCAPNG_SIMPLE_PACKET: u32 = 3;
+const PCAPNG_ENHANCED_PACKET: u32 = 6;
+const PCAPNG_OPTION_TS_RESOLUTION: u16 = 9;
+
+/// A packet read from a capture file
+pub(crate) struct Packet<'a> {
+    /// time since the UNIX epoch, if recorded
+    pub(crate) timestamp: Option<Duration>,
+    /// link-layer header type (LINKTYPE_* value)
+    pub(crate) link_type: u32,
+    pub(crate) data: &'a [u8],
+}
+
+/// Returns true if the data begins with a pcap or pcapng header
+pub(crate) fn is_capture(data: &[u8]) -> bool {
+    match read_u32(data, 0, true) {
+        Some(PCAPNG_SECTION_HEADER) => true,
+        Some(magic) => {
+            [PCAP_MICROS, PCAP_NANOS].contains(&magic)
+                || [PCAP_MICROS, PCAP_NANOS].contains(&magic.swap_bytes())
+        }
+        None => false,
+    }
+}
+
+/// Read all of the packets in a pcap or pcapng file
+pub(crate) fn read_packets(data: &[u8]) -> Result<Vec<Packet<'_>>, String> {
+    match read_u32(data, 0, true) {
+        Some(PCAPNG_SECTION_HEADER) => read_pcapng(data),
+        _ => read_pcap(data),
+    }
+}
+
+fn read_pcap(data: &[u8]) -> Result<Vec<Packet<'_>>, String> {
+    let magic = read_u32(data, 0, true).ok_or("truncated pcap header")?;
+    let (le, nanos) = match magic {
+        PCAP_MICROS => (true, false),
+        PCAP_NANOS => (true, true),
+        x if x.swap_bytes() == PCAP_MICROS => (false, false),
+        x if x.swap_bytes() == PCAP_NANOS => (false, true),
+        x => return Err(format!("unknown pcap magic number: {x:#010X}")),
+    };
+    let link_type = read_u32(data, 20, le).ok_or("truncated pcap header")?;
+
+    let mut packets = Vec::new();
+    let mut offset = 24;
+    while offset < data.len() {
+        let (seconds, fraction, length) = match (
+            read_u32(data, offset, le),
+            read_u32(data, offset + 4, le),
+            read_u32(data, offset + 8, le),
+        ) {
+            (Some(s), Some(f), Some(l)) => (s, f, l as usize),
+            _ => return Err(format!("truncated pcap record at offset {offset}")),
+        };
+        let start = offset + 16;
+        let data = data
+            .get(start..start + length)
+            .ok_or_else(|| format!("truncated pcap record at offset {offset}"))?;
+        let fraction = if nanos {
+            Duration::from_nanos(fraction as u64)
+        } else {
+            Duration::from_micros(fraction as u64)
+        };
+        packets.push(Packet {
+            timestamp: Some(Duration::from_secs(seconds as u64) + fraction),
+            link_type,
+            data,
+        });
+        offset = start + length;
+    }
+
+    Ok(packets)
+}
+
+struct Interface {
+    link_type: u32,
+    /// timestamp units per second
+    resolution: u64,
+}
+
+impl Interface {
+    fn timestamp(&self, ticks: u64) -> Duration {
+        // the fractional ticks are scaled in 128 bits since resolutions finer than a nanosecond
+        // overflow a u64 when multiplied by 10^9
+        let nanos = (ticks % self.resolution) as u128 * 1_000_000_000 / self.resolution as u128;
+        Duration::from_secs(ticks / self.resolution) + Duration::from_nanos(nanos as u64)
+    }
+}
+
+fn read_pcapng(data: &[u8]) -> Result<Vec<Packet<'_>>, String> {
+    let mut packets = Vec::new();
+    let mut interfaces: Vec<Interface> = Vec::new();
+    let mut le = true;
+    let mut offset = 0;
+
+    while offset < data.len() {
+        let block_type = read_u32(data, offset, le).ok_or("truncated pcapng block")?;
+        if block_type == PCAPNG_SECTION_HEADER {
+            le = match read_u32(data, offset + 8, true) {
+                Some(PCAPNG_BYTE_ORDER) => true,
+                Some(x) if x.swap_bytes() == PCAPNG_BYTE_ORDER => false,
+                _ => return Err("bad pcapng byte-order magic".to_string()),
+            };
+            interfaces.clear();
+        }
+
+        let length = read_u32(data, offset + 4, le).ok_or("truncated pcapng block")? as usize;
+        let block = data
+            .get(offset..offset + length)
+            .filter(|_| length >= 12)
+            .ok_or_else(|| format!("bad pcapng block at offset {offset}"))?;
+        // the body excludes the type, the length, and the trailing copy of the length
+        let body = &block[8..length - 4];
+
+        match block_type {
+            PCAPNG_INTERFACE_DESCRIPTION => {
+                let link_type = read_u16(body, 0, le).ok_or("truncated interface block")?;
+                interfaces.push(Interface {
+                    link_type: link_type as u32,
+                    resolution: read_resolution(body.get(8..).unwrap_or_default(), le),
+                });
+            }
+            PCAPNG_ENHANCED_PACKET => {
+                let (id, high, low, captured) = match (
+                    read_u32(body, 0, le),
+                    read_u32(body, 4, le),
+                    read_u32(body, 8, le),
+                    read_u32(body, 12, le),
+                ) {
+                    (Some(i), Some(h), Some(l), Some(c)) => (i as usize, h, l, c as usize),
+                    _ => return Err(format!("truncated packet block at offset {offset}")),
+                };
+                let interface = interfaces
+                    .get(id)
+                    .ok_or_else(|| format!("packet references unknown interface {id}"))?;
+                let data = body
+                    .get(20..20 + captured)
+                    .ok_or_else(|| format!("truncated packet block at offset {offset}"))?;
+                let ticks = ((high as u64) << 32) | low as u64;
+                let timestamp = interface.timestamp(ticks);
+                packets.push(Packet {
+                    timestamp: Some(timestamp),
+                    link_type: interface.link_type,
+                    data,
+                });
+            }
+            PCAPNG_SIMPLE_PACKET => {
+                let interface = interfaces
+                    .first()
+                    .ok_or("simple packet block without interface")?;
+                let original = read_u32(body, 0, le).ok_or("truncated packet block")? as usize;
+                let data = &body[4..];
+                packets.push(Packet {
+                    timestamp: None,
+                    link_type: interface.link_type,
+                    data: &data[..original.min(data.len())],
+                });
+            }
+            _ => {}
+        }
+
+        offset += length;
+    }
+
+    Ok(packets)
+}
+
+/// Parse the `if_tsresol` option of an interface description block, defaulting to microseconds
+fn read_resolution(mut options: &[u8], le: bool) -> u64 {
+    while let (Some(code), Some(length)) = (read_u16(options, 0, le), read_u16(options, 2, le)) {
+        let length = length as usize;
+        if code == PCAPNG_OPTION_TS_RESOLUTION && length == 1 {
+            return match options.get(4) {
+                Some(x) if x & 0x80 == 0 => 10u64.checked_pow(*x as u32).unwrap_or(1_000_000),
+                Some(x) => 1u64.checked_shl((x & 0x7F) as u32).unwrap_or(1_000_000),
+                None => 1_000_000,
+            };
+        }
+        // options are padded to 32 bits
+        let next = 4 + length.div_ceil(4) * 4;
+        options = match options.get(next..) {
+            Some(x) => x,
+            None => break,
+        };
+    }
+    1_000_000
+}
+
+pub(crate) fn read_u16(data: &[u8], offset: usize, le: bool) -> Option<u16> {
+    let bytes: [u8; 2] = data.get(offset..offset + 2)?.try_into().ok()?;
+    Some(if le {
+        u16::from_le_bytes(bytes)
+    } else {
+        u16::from_be_bytes(bytes)
+    })
+}
+
+pub(crate) fn read_u32(data: &[u8], offset: usize, le: bool) -> Option<u32> {
+    let bytes: [u8; 4] = data.get(offset..offset + 4)?.try_into().ok()?;
+    Some(if le {
+        u32::from_le_bytes(bytes)
+    } else {
+        u32::from_be_bytes(bytes)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pcap(magic: u32, records: &[(u32, u32, &[u8])]) -> Vec<u8> {
+        let mut file = Vec::new();
+        file.extend(magic.to_le_bytes());
+        file.extend([2, 0, 4, 0]);
+        file.extend([0; 8]);
+        file.extend(65535u32.to_le_bytes());
+        file.extend(1u32.to_le_bytes());
+        for (seconds, fraction, data) in records {
+            file.extend(seconds.to_le_bytes());
+            file.extend(fraction.to_le_bytes());
+            file.extend((data.len() as u32).to_le_bytes());
+            file.extend((data.len() as u32).to_le_bytes());
+            file.extend(*data);
+        }
+        file
+    }
+
+    fn block(block_type: u32, body: &[u8]) -> Vec<u8> {
+        let length = (12 + body.len()) as u32;
+        let mut block = Vec::new();
+        block.extend(block_type.to_le_bytes());
+        block.extend(length.to_le_bytes());
+        block.extend(body);
+        block.extend(length.to_le_bytes());
+        block
+    }
+
+    fn section_header() -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend(PCAPNG_BYTE_ORDER.to_le_bytes());
+        body.extend([1, 0, 0, 0]);
+        body.extend(u64::MAX.to_le_bytes());
+        block(PCAPNG_SECTION_HEADER, &body)
+    }
+
+    fn interface(resolution: Option<u8>) -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend(1u16.to_le_bytes());
+        body.extend([0; 2]);
+        body.extend(65535u32.to_le_bytes());
+        if let Some(resolution) = resolution {
+            body.extend(PCAPNG_OPTION_TS_RESOLUTION.to_le_bytes());
+            body.extend(1u16.to_le_bytes());
+            body.extend([resolution, 0, 0, 0]);
+            body.extend([0; 4]);
+        }
+        block(PCAPNG_INTERFACE_DESCRIPTION, &body)
+    }
+
+    fn enhanced_packet(ticks: u64, data: &[u8]) -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend(0u32.to_le_bytes());
+        body.extend(((ticks >> 32) as u32).to_le_bytes());
+        body.extend((ticks as u32).to_le_bytes());
+        body.extend((data.len() as u32).to_le_bytes());
+        body.extend((data.len() as u32).to_le_bytes());
+        body.extend(data);
+        body.resize(body.len().div_ceil(4) * 4, 0);
+        block(PCAPNG_ENHANCED_PACKET, &body)
+    }
+
+    #[test]
+    fn reads_pcap_records() {
+        let file = pcap(PCAP_MICROS, &[(10, 500_000, &[1, 2, 3]), (11, 0, &[4])]);
+        assert!(is_capture(&file));
+
+        let packets = read_packets(&file).unwrap();
+        assert_eq!(packets.len(), 2);
+        assert_eq!(packets[0].link_type, 1);
+        assert_eq!(packets[0].data, &[1, 2, 3]);
+        assert_eq!(packets[0].timestamp, Some(Duration::from_millis(10_500)));
+        assert_eq!(packets[1].data, &[4]);
+        assert_eq!(packets[1].timestamp, Some(Duration::from_secs(11)));
+    }
+
+    #[test]
+    fn reads_pcap_nanosecond_timestamps() {
+        let file = pcap(PCAP_NANOS, &[(10, 123, &[1])]);
+        let packets = read_packets(&file).unwrap();
+        assert_eq!(packets[0].timestamp, Some(Duration::new(10, 123)));
+    }
+
+    #[test]
+    fn rejects_truncated_pcap_record() {
+        let mut file = pcap(PCAP_MICROS, &[(10, 0, &[1, 2, 3])]);
+        file.pop();
+        assert!(read_packets(&file).is_err());
+    }
+
+    #[test]
+    fn reads_pcapng_packets() {
+        let mut file = section_header();
+        file.extend(interface(None));
+        file.extend(enhanced_packet(10_500_000, &[1, 2, 3, 4, 5]));
+        let mut simple = 2u32.to_le_bytes().to_vec();
+        simple.extend([6, 7, 0, 0]);
+        file.extend(block(PCAPNG_SIMPLE_PACKET, &simple));
+        assert!(is_capture(&file));
+
+        let packets = read_packets(&file).unwrap();
+        assert_eq!(packets.len(), 2);
+        assert_eq!(packets[0].link_type, 1);
+        assert_eq!(packets[0].data, &[1, 2, 3, 4, 5]);
+        assert_eq!(packets[0].timestamp, Some(Duration::from_millis(10_500)));
+        assert_eq!(packets[1].data, &[6, 7]);
+        assert_eq!(packets[1].timestamp, None);
+    }
+
+    #[test]
+    fn reads_pcapng_timestamps_finer_than_nanoseconds() {
+        // picosecond resolution, where scaling the fraction to nanoseconds overflows a u64
+        let mut file = section_header();
+        file.extend(interface(Some(12)));
+        file.extend(enhanced_packet(10_000_000_999_999_999_999, &[1]));
+
+        let packets = read_packets(&file).unwrap();
+        assert_eq!(
+            packets[0].timestamp,
+            Some(Duration::new(10_000_000, 999_999_999))
+        );
+    }
+
+    #[test]
+    fn rejects_pcapng_packet_for_unknown_interface() {
+        let mut file = section_header();
+        file.extend(enhanced_packet(0, &[1]));
+        assert!(read_packets(&file).is_err());
+    }
+}
@@ -0,0 +1,380 @@
+//! Reconstruction of frames and fragments from byte streams and their output
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use dnp3::codec::*;
+use dnp3::decode::{AppDecodeLevel, DecodeDirection, LinkDecodeLevel, TransportDecodeLevel};
+use dnp3::link::{Function, LinkErrorMode};
+use serde_json::{json, Value};
+
+use crate::packet::{Protocol, Segment};
+
+/// Largest fragment that is reassembled
+const MAX_FRAGMENT_SIZE: usize = 65535;
+/// Number of out-of-order TCP segments held per flow while waiting for the missing data
+const MAX_PENDING_SEGMENTS: usize = 64;
+
+/// Output settings
+pub(crate) struct Options {
+    pub(crate) app: AppDecodeLevel,
+    pub(crate) transport: TransportDecodeLevel,
+    pub(crate) link: LinkDecodeLevel,
+    pub(crate) json: bool,
+}
+
+/// Where a chunk of bytes came from
+#[derive(Copy, Clone)]
+pub(crate) struct Context {
+    pub(crate) timestamp: Option<Duration>,
+    pub(crate) flow: Option<(Protocol, SocketAddr, SocketAddr)>,
+}
+
+struct Flow {
+    link: LinkDecoder,
+    assembler: FragmentAssembler,
+    /// next expected TCP sequence number
+    next_seq: Option<u32>,
+    /// TCP segments received ahead of the next expected sequence number
+    pending: Vec<(u32, Vec<u8>)>,
+}
+
+impl Flow {
+    fn new() -> Self {
+        Self {
+            link: LinkDecoder::new(LinkErrorMode::Discard),
+            assembler: FragmentAssembler::new(MAX_FRAGMENT_SIZE),
+            next_seq: None,
+            pending: Vec::new(),
+        }
+    }
+}
+
+/// Decodes byte streams, keeping separate state for each direction of each flow
+pub(crate) struct Decoder {
+    options: Options,
+    flows: HashMap<Option<(Protocol, SocketAddr, SocketAddr)>, Flow>,
+}
+
+impl Decoder {
+    pub(crate) fn new(options: Options) -> Self {
+        Self {
+            options,
+            flows: HashMap::new(),
+        }
+    }
+
+    /// Decode a chunk of a byte stream that isn't associated with a network flow
+    pub(crate) fn stream(&mut self, data: &[u8]) {
+        let context = Context {
+            timestamp: None,
+            flow: None,
+        };
+        self.decode(context, data, false);
+    }
+
+    /// Decode the payload of a captured TCP or UDP segment
+    pub(crate) fn segment(&mut self, timestamp: Option<Duration>, segment: Segment) {
+        let context = Context {
+            timestamp,
+            flow: Some((segment.protocol, segment.source, segment.destination)),
+        };
+
+        let payload = match segment.tcp {
+            None => segment.payload,
+            Some((seq, syn)) => {
+                let flow = self.flows.entry(context.flow).or_insert_with(Flow::new);
+                &reassemble(flow, seq, syn, segment.payload)
+            }
+        };
+
+        // link-layer frames may not span UDP datagrams
+        let datagram = segment.protocol == Protocol::Udp;
+        self.decode(context, payload, datagram);
+    }
+
+    fn decode(&mut self, context: Context, data: &[u8], datagram: bool) {
+        if data.is_empty() {
+            return;
+        }
+
+        let flow = self.flows.entry(context.flow).or_insert_with(Flow::new);
+        if datagram {
+            flow.link = LinkDecoder::new(LinkErrorMode::Discard);
+        }
+
+        let frames = match flow.link.decode(data) {
+            Ok(x) => x,
+            // cannot occur in discard mode
+            Err(err) => {
+                eprintln!("error: {err}");
+                return;
+            }
+        };
+
+        for frame in frames {
+            let segment = match frame.header.function {
+                Function::PriConfirmedUserData | Function::PriUnconfirmedUserData => {
+                    TransportSegment::parse(&frame.payload).ok()
+                }
+                _ => None,
+            };
+            let fragment = match flow.assembler.push(&frame) {
+                Ok(x) => x,
+                Err(err) => {
+                    eprintln!("error: {err}");
+                    None
+                }
+            };
+
+            if self.options.json {
+                print_json(&self.options, context, &frame, segment.as_ref(), fragment);
+            } else {
+                print_text(&self.options, context, &frame, segment.as_ref(), fragment);
+            }
+        }
+    }
+}
+
+/// Returns the stream data that became contiguous with this segment
+///
+/// Retransmitted data is discarded and segments that arrive ahead of a gap are held until the
+/// gap is filled. If too many segments are waiting, the missing data is skipped.
+fn reassemble(flow: &mut Flow, seq: u32, syn: bool, payload: &[u8]) -> Vec<u8> {
+    if syn {
+        // a new connection, the SYN consumes one sequence number
+        *flow = Flow::new();
+        flow.next_seq = Some(seq.wrapping_add(1).wrapping_add(payload.len() as u32));
+        return payload.to_vec();
+    }
+
+    let next = match flow.next_seq {
+        // capture started in the middle of the connection
+        None => {
+            flow.next_seq = Some(seq.wrapping_add(payload.len() as u32));
+            return payload.to_vec();
+        }
+        Some(x) => x,
+    };
+
+    flow.pending.push((seq, payload.to_vec()));
+    let mut next = next;
+    if seq.wrapping_sub(next) as i32 > 0 {
+        if flow.pending.len() <= MAX_PENDING_SEGMENTS {
+            return Vec::new();
+        }
+        // give up on the gap and resume from the earliest segment received after it
+        let first = flow
+            .pending
+            .iter()
+            .map(|(seq, _)| *seq)
+            .min_by_key(|seq| seq.wrapping_sub(next))
+            .unwrap_or(seq);
+        eprintln!(
+            "warning: {} bytes missing from TCP stream",
+            first.wrapping_sub(next)
+        );
+        flow.link = LinkDecoder::new(LinkErrorMode::Discard);
+        next = first;
+    }
+
+    let mut output = Vec::new();
+    while let Some(pos) = flow
+        .pending
+        .iter()
+        .position(|(seq, _)| seq.wrapping_sub(next) as i32 <= 0)
+    {
+        let (seq, data) = flow.pending.swap_remove(pos);
+        // skip any data that was already received
+        if let Some(data) = data.get(next.wrapping_sub(seq) as usize..) {
+            output.extend_from_slice(data);
+            next = next.wrapping_add(data.len() as u32);
+        }
+    }
+    flow.next_seq = Some(next);
+    output
+}
+
+fn print_text(
+    options: &Options,
+    context: Context,
+    frame: &LinkFrame,
+    segment: Option<&TransportSegment>,
+    fragment: Option<AssembledFragment>,
+) {
+    let mut lines = Vec::new();
+    if options.link != LinkDecodeLevel::Nothing {
+        lines.push(format!("LINK - {}", frame.format(options.link)));
+    }
+    if let Some(segment) = segment {
+        if options.transport != TransportDecodeLevel::Nothing {
+            lines.push(format!("TRANSPORT - {}", segment.format(options.transport)));
+        }
+    }
+    if let Some(fragment) = fragment {
+        if options.app != AppDecodeLevel::Nothing {
+            match format_fragment(&fragment.data, options.app) {
+                Ok(text) => lines.push(format!("APP - {text}")),
+                Err(err) => lines.push(format!("APP - {err}")),
+            }
+        }
+    }
+
+    if lines.is_empty() {
+        return;
+    }
+
+    let mut context_line = String::new();
+    if let Some(timestamp) = context.timestamp {
+        context_line.push_str(&format!(
+            "{}.{:06} ",
+            timestamp.as_secs(),
+            timestamp.subsec_micros()
+        ));
+    }
+    if let Some((protocol, source, destination)) = context.flow {
+        context_line.push_str(&format!("{protocol:?} {source} -> {destination} "));
+    }
+    if frame.header.master {
+        context_line.push_str("(master -> outstation)");
+    } else {
+        context_line.push_str("(outstation -> master)");
+    }
+
+    println!("{context_line}");
+    for line in lines {
+        println!("{line}");
+    }
+    println!();
+}
+
+/// Print one line per decoded event using the same schema as `dnp3::decode::JsonDecodeSink`,
+/// adding the timestamp and flow of the packet when they are known
+fn print_json(
+    options: &Options,
+    context: Context,
+    frame: &LinkFrame,
+    segment: Option<&TransportSegment>,
+    fragment: Option<AssembledFragment>,
+) {
+    // a capture has no local endpoint, so events are reported from the master's point of view
+    let direction = if frame.header.master {
+        DecodeDirection::Tx
+    } else {
+        DecodeDirection::Rx
+    };
+
+    let mut events = Vec::new();
+    if let Some(event) = frame.event(direction, options.link) {
+        events.push(event.to_json());
+    }
+    if let Some(event) = segment.and_then(|x| x.event(direction, options.transport)) {
+        events.push(event.to_json());
+    }
+    if let Some(fragment) = fragment {
+        match fragment_event(&fragment.data, direction, options.app) {
+            Ok(Some(event)) => events.push(event.to_json()),
+            Ok(None) => {}
+            Err(err) => events.push(json!({
+                "layer": "application",
+                "direction": if frame.header.master { "tx" } else { "rx" },
+                "error": err.to_string(),
+            })),
+        }
+    }
+
+    for mut event in events {
+        add_context(&mut event, context);
+        println!("{event}");
+    }
+}
+
+fn add_context(event: &mut Value, context: Context) {
+    if let Some(timestamp) = context.timestamp {
+        event["timestamp"] = json!(timestamp.as_secs_f64());
+    }
+    if let Some((protocol, source, destination)) = context.flow {
+        // nested so that it doesn't collide with the link-layer addresses
+        event["flow"] = json!({
+            "protocol": format!("{protocol:?}").to_lowercase(),
+            "source": source.to_string(),
+            "destination": destination.to_string(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn connected(seq: u32) -> Flow {
+        let mut flow = Flow::new();
+        assert!(reassemble(&mut flow, seq.wrapping_sub(1), true, &[]).is_empty());
+        flow
+    }
+
+    #[test]
+    fn passes_through_in_order_segments() {
+        let mut flow = connected(100);
+        assert_eq!(reassemble(&mut flow, 100, false, &[1, 2]), vec![1, 2]);
+        assert_eq!(reassemble(&mut flow, 102, false, &[3]), vec![3]);
+        assert_eq!(flow.next_seq, Some(103));
+    }
+
+    #[test]
+    fn starts_from_first_segment_without_syn() {
+        let mut flow = Flow::new();
+        assert_eq!(reassemble(&mut flow, 500, false, &[1, 2]), vec![1, 2]);
+        assert_eq!(reassemble(&mut flow, 502, false, &[3]), vec![3]);
+    }
+
+    #[test]
+    fn discards_retransmitted_data() {
+        let mut flow = connected(100);
+        assert_eq!(reassemble(&mut flow, 100, false, &[1, 2, 3]), vec![1, 2, 3]);
+        // complete retransmission
+        assert!(reassemble(&mut flow, 100, false, &[1, 2, 3]).is_empty());
+        // retransmission that overlaps new data
+        assert_eq!(reassemble(&mut flow, 102, false, &[3, 4, 5]), vec![4, 5]);
+        assert_eq!(flow.next_seq, Some(105));
+    }
+
+    #[test]
+    fn reorders_out_of_order_segments() {
+        let mut flow = connected(100);
+        assert!(reassemble(&mut flow, 104, false, &[5, 6]).is_empty());
+        assert!(reassemble(&mut flow, 102, false, &[3, 4]).is_empty());
+        assert_eq!(
+            reassemble(&mut flow, 100, false, &[1, 2]),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+        assert!(flow.pending.is_empty());
+        assert_eq!(flow.next_seq, Some(106));
+    }
+
+    #[test]
+    fn handles_sequence_number_wrap() {
+        let mut flow = connected(u32::MAX - 1);
+        assert!(reassemble(&mut flow, 1, false, &[4, 5]).is_empty());
+        assert_eq!(
+            reassemble(&mut flow, u32::MAX - 1, false, &[1, 2, 3]),
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(flow.next_seq, Some(3));
+    }
+
+    #[test]
+    fn skips_gap_when_too_many_segments_are_pending() {
+        let mut flow = connected(100);
+        // the segment at 100 is never received
+        for i in 0..MAX_PENDING_SEGMENTS as u32 {
+            assert!(reassemble(&mut flow, 101 + i, false, &[i as u8]).is_empty());
+        }
+        let count = MAX_PENDING_SEGMENTS as u32;
+        let output = reassemble(&mut flow, 101 + count, false, &[count as u8]);
+        assert_eq!(output, (0..=count as u8).collect::<Vec<u8>>());
+        assert!(flow.pending.is_empty());
+        assert_eq!(flow.next_seq, Some(102 + count));
+    }
+}
@@ -0,0 +1,205 @@
+//! Decodes DNP3 traffic from hex dumps, raw byte files, and pcap/pcapng captures
+//!
+//! Link-layer frames are reconstructed from the byte stream, transport segments are reassembled
+//! into application fragments, and everything is printed using the same formatting as the
+//! library's decode logging.
+use std::io::Read;
+use std::process::ExitCode;
+
+use clap::{Parser, ValueEnum};
+use dnp3::decode::{AppDecodeLevel, LinkDecodeLevel, TransportDecodeLevel};
+
+use crate::decoder::{Decoder, Options};
+
+mod capture;
+mod decoder;
+mod packet;
+
+#[derive(ValueEnum, Debug, Copy, Clone, PartialEq, Eq)]
+enum Format {
+    /// detect the format from the content of the input
+    Auto,
+    /// hexadecimal bytes separated by whitespace, commas, or colons
+    Hex,
+    /// raw binary bytes
+    Raw,
+    /// pcap or pcapng capture file
+    Pcap,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum AppLevel {
+    Nothing,
+    Header,
+    ObjectHeaders,
+    ObjectValues,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum TransportLevel {
+    Nothing,
+    Header,
+    Payload,
+}
+
+#[derive(ValueEnum, Debug, Copy, Clone)]
+enum LinkLevel {
+    Nothing,
+    Header,
+    Payload,
+}
+
+impl From<AppLevel> for AppDecodeLevel {
+    fn from(value: AppLevel) -> Self {
+        match value {
+            AppLevel::Nothing => Self::Nothing,
+            AppLevel::Header => Self::Header,
+            AppLevel::ObjectHeaders => Self::ObjectHeaders,
+            AppLevel::ObjectValues => Self::ObjectValues,
+        }
+    }
+}
+
+impl From<TransportLevel> for TransportDecodeLevel {
+    fn from(value: TransportLevel) -> Self {
+        match value {
+            TransportLevel::Nothing => Self::Nothing,
+            TransportLevel::Header => Self::Header,
+            TransportLevel::Payload => Self::Payload,
+        }
+    }
+}
+
+impl From<LinkLevel> for LinkDecodeLevel {
+    fn from(value: LinkLevel) -> Self {
+        match value {
+            LinkLevel::Nothing => Self::Nothing,
+            LinkLevel::Header => Self::Header,
+            LinkLevel::Payload => Self::Payload,
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Cli {
+    /// File to decode, or '-' for stdin
+    #[clap(value_parser)]
+    input: Option<String>,
+    /// Decode the provided hex string instead of reading the input
+    #[clap(long, value_parser, conflicts_with = "input")]
+    hex: Option<String>,
+    /// Format of the input
+    #[clap(short, long, value_enum, default_value_t = Format::Auto)]
+    format: Format,
+    /// Application-layer decode level
+    #[clap(long, value_enum, default_value_t = AppLevel::ObjectValues)]
+    app: AppLevel,
+    /// Transport-layer decode level
+    #[clap(long, value_enum, default_value_t = TransportLevel::Nothing)]
+    transport: TransportLevel,
+    /// Link-layer decode level
+    #[clap(long, value_enum, default_value_t = LinkLevel::Nothing)]
+    link: LinkLevel,
+    /// Only decode captured packets with this source or destination port
+    #[clap(short, long, value_parser)]
+    port: Option<u16>,
+    /// Print one JSON object per decoded event instead of text, using the schema of the
+    /// library's `JsonDecodeSink`
+    #[clap(long, value_parser, default_value_t = false)]
+    json: bool,
+}
+
+fn main() -> ExitCode {
+    let args = Cli::parse();
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Cli) -> Result<(), String> {
+    let (input, format) = match &args.hex {
+        Some(hex) => (hex.as_bytes().to_vec(), Format::Hex),
+        None => (read_input(args.input.as_deref())?, args.format),
+    };
+
+    let format = match format {
+        Format::Auto if capture::is_capture(&input) => Format::Pcap,
+        Format::Auto if is_hex(&input) => Format::Hex,
+        Format::Auto => Format::Raw,
+        x => x,
+    };
+
+    let mut decoder = Decoder::new(Options {
+        app: args.app.into(),
+        transport: args.transport.into(),
+        link: args.link.into(),
+        json: args.json,
+    });
+
+    match format {
+        Format::Pcap => {
+            for packet in capture::read_packets(&input)? {
+                let segment = match packet::parse(packet.link_type, packet.data) {
+                    Some(x) => x,
+                    None => continue,
+                };
+                if let Some(port) = args.port {
+                    if segment.source.port() != port && segment.destination.port() != port {
+                        continue;
+                    }
+                }
+                decoder.segment(packet.timestamp, segment);
+            }
+        }
+        Format::Hex => {
+            let text = std::str::from_utf8(&input).map_err(|_| "hex input is not valid UTF-8")?;
+            decoder.stream(&parse_hex(text)?);
+        }
+        Format::Raw | Format::Auto => decoder.stream(&input),
+    }
+
+    Ok(())
+}
+
+fn read_input(path: Option<&str>) -> Result<Vec<u8>, String> {
+    match path {
+        None | Some("-") => {
+            let mut data = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut data)
+                .map_err(|err| format!("unable to read stdin: {err}"))?;
+            Ok(data)
+        }
+        Some(path) => std::fs::read(path).map_err(|err| format!("unable to read {path}: {err}")),
+    }
+}
+
+fn is_hex(data: &[u8]) -> bool {
+    data.iter()
+        .all(|x| x.is_ascii_hexdigit() || x.is_ascii_whitespace() || b",:xX".contains(x))
+}
+
+fn parse_hex(text: &str) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    for token in text.split(|c: char| c.is_whitespace() || c == ',' || c == ':') {
+        let token = token
+            .strip_prefix("0x")
+            .or_else(|| token.strip_prefix("0X"))
+            .unwrap_or(token);
+        if token.len() % 2 != 0 {
+            return Err(format!("odd number of hex digits in '{token}'"));
+        }
+        for pair in token.as_bytes().chunks(2) {
+            let pair = std::str::from_utf8(pair).map_err(|_| "invalid hex")?;
+            let byte =
+                u8::from_str_radix(pair, 16).map_err(|_| format!("invalid hex byte '{pair}'"))?;
+            bytes.push(byte);
+        }
+    }
+    Ok(bytes)
+}
@@ -0,0 +1,132 @@
+//! Extraction of TCP and UDP payloads from captured packets
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use crate::capture::{read_u16, read_u32};
+
+const LINKTYPE_NULL: u32 = 0;
+const LINKTYPE_ETHERNET: u32 = 1;
+const LINKTYPE_RAW: u32 = 101;
+const LINKTYPE_LINUX_SLL: u32 = 113;
+const LINKTYPE_IPV4: u32 = 228;
+const LINKTYPE_IPV6: u32 = 229;
+const LINKTYPE_LINUX_SLL2: u32 = 276;
+// DLT_RAW as written by some versions of libpcap
+const DLT_RAW: [u32; 2] = [12, 14];
+
+const ETHERTYPE_IPV4: u16 = 0x0800;
+const ETHERTYPE_IPV6: u16 = 0x86DD;
+const ETHERTYPE_VLAN: u16 = 0x8100;
+
+const PROTOCOL_TCP: u8 = 6;
+const PROTOCOL_UDP: u8 = 17;
+
+const TCP_FLAG_SYN: u8 = 0x02;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum Protocol {
+    Tcp,
+    Udp,
+}
+
+/// TCP or UDP payload of a captured packet
+pub(crate) struct Segment<'a> {
+    pub(crate) protocol: Protocol,
+    pub(crate) source: SocketAddr,
+    pub(crate) destination: SocketAddr,
+    /// sequence number and SYN flag for TCP
+    pub(crate) tcp: Option<(u32, bool)>,
+    pub(crate) payload: &'a [u8],
+}
+
+/// Extract the TCP or UDP segment from a packet, ignoring anything else
+pub(crate) fn parse(link_type: u32, data: &[u8]) -> Option<Segment<'_>> {
+    let (ethertype, ip) = match link_type {
+        LINKTYPE_ETHERNET => {
+            let mut ethertype = read_u16(data, 12, false)?;
+            let mut offset = 14;
+            while ethertype == ETHERTYPE_VLAN {
+                ethertype = read_u16(data, offset + 2, false)?;
+                offset += 4;
+            }
+            (Some(ethertype), data.get(offset..)?)
+        }
+        LINKTYPE_LINUX_SLL => (read_u16(data, 14, false), data.get(16..)?),
+        LINKTYPE_LINUX_SLL2 => (read_u16(data, 0, false), data.get(20..)?),
+        LINKTYPE_NULL => (None, data.get(4..)?),
+        LINKTYPE_RAW | LINKTYPE_IPV4 | LINKTYPE_IPV6 => (None, data),
+        x if DLT_RAW.contains(&x) => (None, data),
+        _ => return None,
+    };
+
+    // when the link-layer doesn't identify the protocol, use the IP version
+    let ethertype = match ethertype {
+        Some(x) => x,
+        None => match ip.first()? >> 4 {
+            4 => ETHERTYPE_IPV4,
+            6 => ETHERTYPE_IPV6,
+            _ => return None,
+        },
+    };
+
+    let (protocol, source, destination, payload) = match ethertype {
+        ETHERTYPE_IPV4 => parse_ipv4(ip)?,
+        ETHERTYPE_IPV6 => parse_ipv6(ip)?,
+        _ => return None,
+    };
+
+    match protocol {
+        PROTOCOL_TCP => {
+            let offset = (*payload.get(12)? >> 4) as usize * 4;
+            let flags = *payload.get(13)?;
+            Some(Segment {
+                protocol: Protocol::Tcp,
+                source: SocketAddr::new(source, read_u16(payload, 0, false)?),
+                destination: SocketAddr::new(destination, read_u16(payload, 2, false)?),
+                tcp: Some((read_u32(payload, 4, false)?, flags & TCP_FLAG_SYN != 0)),
+                payload: payload.get(offset..)?,
+            })
+        }
+        PROTOCOL_UDP => {
+            let length = read_u16(payload, 4, false)? as usize;
+            Some(Segment {
+                protocol: Protocol::Udp,
+                source: SocketAddr::new(source, read_u16(payload, 0, false)?),
+                destination: SocketAddr::new(destination, read_u16(payload, 2, false)?),
+                tcp: None,
+                payload: payload.get(8..length.min(payload.len()))?,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn parse_ipv4(data: &[u8]) -> Option<(u8, IpAddr, IpAddr, &[u8])> {
+    let header_length = (*data.first()? & 0x0F) as usize * 4;
+    let total_length = read_u16(data, 2, false)? as usize;
+    // fragmented datagrams are not reassembled
+    let fragment = read_u16(data, 6, false)?;
+    if fragment & 0x3FFF != 0 {
+        return None;
+    }
+    let source: [u8; 4] = data.get(12..16)?.try_into().ok()?;
+    let destination: [u8; 4] = data.get(16..20)?.try_into().ok()?;
+    Some((
+        *data.get(9)?,
+        Ipv4Addr::from(source).into(),
+        Ipv4Addr::from(destination).into(),
+        data.get(header_length..total_length.min(data.len()))?,
+    ))
+}
+
+fn parse_ipv6(data: &[u8]) -> Option<(u8, IpAddr, IpAddr, &[u8])> {
+    let payload_length = read_u16(data, 4, false)? as usize;
+    let source: [u8; 16] = data.get(8..24)?.try_into().ok()?;
+    let destination: [u8; 16] = data.get(24..40)?.try_into().ok()?;
+    Some((
+        *data.get(6)?,
+        Ipv6Addr::from(source).into(),
+        Ipv6Addr::from(destination).into(),
+        data.get(40..(40 + payload_length).min(data.len()))?,
+    ))
+}
@@ -16,12 +16,19 @@ use dnp3::tcp::*;
 use clap::Parser;
 use dnp3::app::control::{CommandStatus, Group12Var1, OpType};
 
-fn config(num_values: usize) -> TestConfig {
+fn config(args: &Cli) -> TestConfig {
+    let class_zero_points = match args.action {
+        Action::ClassZero => Some(args.points),
+        _ => None,
+    };
+
     TestConfig {
         outstation_level: DecodeLevel::nothing(),
         master_level: DecodeLevel::nothing(),
-        num_values,
+        num_values: class_zero_points.map_or(args.values, usize::from),
         max_index: 10,
+        class_zero_points,
+        index_prefixed_static: Feature::Disabled,
     }
 }
 
@@ -29,6 +36,9 @@ fn config(num_values: usize) -> TestConfig {
 enum Action {
     Unsolicited,
     DirectOperate,
+    /// integrity polls of a database with sparse indices spread over the entire index space,
+    /// comparing ranged (0x01) and index-prefixed (0x28) encoding of the static data
+    ClassZero,
 }
 
 #[derive(Parser, Debug)]
@@ -46,6 +56,9 @@ struct Cli {
     action: Action,
     #[clap(long, value_parser)]
     workers: Option<usize>,
+    /// number of points in the database for the class-zero action
+    #[clap(long, value_parser = clap::value_parser!(u16).range(1..), default_value_t = 1000)]
+    points: u16,
 }
 
 fn main() {
@@ -67,7 +80,7 @@ fn main() {
 }
 
 async fn run(args: Cli) {
-    let config = config(args.values);
+    let config = config(&args);
 
     if args.log {
         tracing_subscriber::fmt()
@@ -76,9 +89,32 @@ async fn run(args: Cli) {
             .init();
     }
 
-    let mut harness = TestHarness::create(args.sessions, config).await;
     println!("settings: {args:?}");
 
+    if let Action::ClassZero = args.action {
+        let mut rates = Vec::new();
+        for encoding in [Feature::Disabled, Feature::Enabled] {
+            println!("index-prefixed static encoding: {encoding:?}");
+            let config = TestConfig {
+                index_prefixed_static: encoding,
+                ..config
+            };
+            rates.push(run_test(&args, config).await);
+        }
+        println!(
+            "index-prefixed / ranged requests/sec: {:.2}",
+            rates[1] / rates[0]
+        );
+        return;
+    }
+
+    run_test(&args, config).await;
+}
+
+/// run the action for the configured duration and return the number of requests per second
+async fn run_test(args: &Cli, config: TestConfig) -> f64 {
+    let mut harness = TestHarness::create(args.sessions, config).await;
+
     println!("starting up...");
     harness.wait_for_startup().await;
     let duration = std::time::Duration::from_secs(args.seconds as u64);
@@ -88,17 +124,18 @@ async fn run(args: Cli) {
     let iterations = match args.action {
         Action::Unsolicited => harness.run_unsol(duration).await,
         Action::DirectOperate => harness.run_commands(duration).await,
+        Action::ClassZero => harness.run_class_zero(duration).await,
     };
     let elapsed = start.elapsed();
     let values = config.num_values * iterations;
+    let requests_per_sec = (iterations as f64) / elapsed.as_secs_f64();
 
     println!("elapsed time: {elapsed:?}");
     println!("num requests: {iterations}");
-    println!(
-        "requests/sec: {:.1}",
-        (iterations as f64) / elapsed.as_secs_f64()
-    );
+    println!("requests/sec: {requests_per_sec:.1}");
     println!("meas/sec: {:.1}", (values as f64) / elapsed.as_secs_f64());
+
+    requests_per_sec
 }
 
 struct NullOutstationApplication;
@@ -113,6 +150,8 @@ struct TestConfig {
     master_level: DecodeLevel,
     num_values: usize,
     max_index: u16,
+    class_zero_points: Option<u16>,
+    index_prefixed_static: Feature,
 }
 
 struct TestHarness {
@@ -176,6 +215,31 @@ impl TestHarness {
         iterations
     }
 
+    async fn run_class_zero(self, duration: std::time::Duration) -> usize {
+        let start = Instant::now();
+        let mut tasks = Vec::new();
+
+        for mut pair in self.pairs {
+            let task = tokio::spawn(async move {
+                let mut iterations: usize = 0;
+                loop {
+                    pair.integrity_poll().await;
+                    iterations += 1;
+                    if start.elapsed() >= duration {
+                        return iterations;
+                    }
+                }
+            });
+            tasks.push(task);
+        }
+
+        let mut iterations = 0;
+        for task in tasks {
+            iterations += task.await.unwrap();
+        }
+        iterations
+    }
+
     async fn run_unsol(self, duration: std::time::Duration) -> usize {
         let start = Instant::now();
         let mut tasks = Vec::new();
@@ -206,6 +270,8 @@ impl TestHarness {
 struct Pair {
     // measurements exchanged on each iteration
     values: Measurements,
+    // number of static points returned by an integrity poll
+    static_points: usize,
     // have to hold onto this to keep TCP server alive
     _server: ServerHandle,
     // have to hold onto this to keep master alive
@@ -229,6 +295,24 @@ impl Pair {
         })
     }
 
+    async fn integrity_poll(&mut self) {
+        // the handler reports the count for every fragment of the multi-fragment response
+        let expected = self.static_points;
+        let rx = &mut self.rx;
+        let (result, received) = tokio::join!(
+            self.assoc.read(ReadRequest::class_scan(Classes::class0())),
+            async move {
+                let mut received = 0;
+                while received < expected {
+                    received += rx.recv().await.unwrap();
+                }
+                received
+            }
+        );
+        result.unwrap();
+        assert_eq!(received, expected);
+    }
+
     async fn wait_for_null_unsolicited(&mut self) {
         assert_eq!(self.rx.recv().await.unwrap(), 0);
     }
@@ -244,6 +328,7 @@ impl Pair {
 
         Self {
             values: measurements,
+            static_points: config.class_zero_points.map_or(0, usize::from),
             _server: server,
             rx,
             _master: master,
@@ -257,7 +342,7 @@ impl Pair {
             Server::new_tcp_server(LinkErrorMode::Close, SocketAddr::new(Self::LOCALHOST, 0));
         let outstation = server
             .add_outstation(
-                Self::get_outstation_config(config.outstation_level),
+                Self::get_outstation_config(config),
                 Box::new(NullOutstationApplication),
                 Box::new(NullOutstationInformation),
                 DefaultControlHandler::create(),
@@ -268,6 +353,13 @@ impl Pair {
 
         // set up the database
         outstation.transaction(|db| {
+            if let Some(points) = config.class_zero_points {
+                for i in sparse_indices(points) {
+                    db.add(i, None, AnalogOutputStatusConfig::default());
+                }
+                return;
+            }
+
             for i in 0..=config.max_index {
                 db.add(
                     i,
@@ -366,13 +458,14 @@ impl Pair {
         config
     }
 
-    fn get_outstation_config(level: DecodeLevel) -> OutstationConfig {
+    fn get_outstation_config(test: TestConfig) -> OutstationConfig {
         let mut config = OutstationConfig::new(
             Self::outstation_address(),
             Self::master_address(),
             EventBufferConfig::all_types(100),
         );
-        config.decode_level = level;
+        config.decode_level = test.outstation_level;
+        config.features.index_prefixed_static = test.index_prefixed_static;
         config
     }
 }
@@ -460,9 +553,10 @@ impl ReadHandler for TestHandler {
     fn handle_analog_output_status(
         &mut self,
         _info: HeaderInfo,
-        _iter: &mut dyn Iterator<Item = (AnalogOutputStatus, u16)>,
+        iter: &mut dyn Iterator<Item = (AnalogOutputStatus, u16)>,
     ) {
-        unimplemented!()
+        // only used for the static points of the class zero test
+        self.count += iter.count();
     }
 
     fn handle_octet_string<'a>(
@@ -474,6 +568,12 @@ impl ReadHandler for TestHandler {
     }
 }
 
+/// spread the points evenly over the entire index space, leaving gaps between them
+fn sparse_indices(points: u16) -> impl Iterator<Item = u16> {
+    let points = points as u32;
+    (0..points).map(move |i| ((i * (u16::MAX as u32 + 1)) / points) as u16)
+}
+
 struct TestAssociationHandler;
 impl AssociationHandler for TestAssociationHandler {}
 
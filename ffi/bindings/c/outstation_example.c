@@ -268,6 +268,24 @@ dnp3_control_handler_t get_control_handler()
     };
 }
 
+// WriteHandler interface
+dnp3_write_status_t write_analog_output_status(dnp3_analog_output_status_t value, void *context)
+{
+    return value.index < 10 ? DNP3_WRITE_STATUS_ACCEPT : DNP3_WRITE_STATUS_REJECT;
+}
+
+dnp3_write_handler_t get_write_handler()
+{
+    return (dnp3_write_handler_t){
+        .write_binary_output_status = NULL,
+        .write_counter = NULL,
+        .write_analog_output_status = &write_analog_output_status,
+        .write_octet_string = NULL,
+        .on_destroy = NULL,
+        .ctx = NULL,
+    };
+}
+
 // ANCHOR: database_init_transaction
 void outstation_transaction_startup(dnp3_database_t *db, void *context)
 {
@@ -399,6 +417,10 @@ dnp3_port_state_listener_t get_port_state_listener()
 // loop that accepts user input and updates values
 int run_outstation(dnp3_outstation_t *outstation)
 {
+    // ANCHOR: set_write_handler
+    dnp3_outstation_set_write_handler(outstation, get_write_handler());
+    // ANCHOR_END: set_write_handler
+
     database_points_t database_points = {
         .binaryValue = false,
         .doubleBitBinaryValue = DNP3_DOUBLE_BIT_DETERMINED_OFF,
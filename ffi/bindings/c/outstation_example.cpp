@@ -170,6 +170,13 @@ private:
 };
 // ANCHOR_END: control_handler
 
+class MyWriteHandler : public WriteHandler {
+    WriteStatus write_analog_output_status(const AnalogOutputStatus& value) override
+    {
+        return value.index < 10 ? WriteStatus::accept : WriteStatus::reject;
+    }
+};
+
 class State {
 public:
     State() = default;
@@ -204,6 +211,10 @@ void run_outstation(dnp3::Outstation &outstation)
 {
     State state;
 
+    // ANCHOR: set_write_handler
+    outstation.set_write_handler(std::make_unique<MyWriteHandler>());
+    // ANCHOR_END: set_write_handler
+
     while (true) {
         std::string cmd;
         std::getline(std::cin, cmd);
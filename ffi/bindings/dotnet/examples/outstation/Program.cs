@@ -246,6 +246,14 @@ class ExampleOutstation
     }
     // ANCHOR_END: control_handler
 
+    class TestWriteHandler : IWriteHandler
+    {
+        public WriteStatus WriteAnalogOutputStatus(AnalogOutputStatus value)
+        {
+            return value.Index < 10 ? WriteStatus.Accept : WriteStatus.Reject;
+        }
+    }
+
     class TestConnectionStateListener : IConnectionStateListener
     {
         public void OnChange(ConnectionState state)
@@ -470,6 +478,10 @@ class ExampleOutstation
 
     private static void RunOutstation(Outstation outstation)
     {
+        // ANCHOR: set_write_handler
+        outstation.SetWriteHandler(new TestWriteHandler());
+        // ANCHOR_END: set_write_handler
+
         // Setup initial points
         // ANCHOR: database_init
         outstation.Transaction(db =>
@@ -198,6 +198,14 @@ class TestControlHandler implements ControlHandler {
 }
 // ANCHOR_END: control_handler
 
+class TestWriteHandler implements WriteHandler {
+
+  @Override
+  public WriteStatus writeAnalogOutputStatus(AnalogOutputStatus value) {
+    return value.index.compareTo(ushort(10)) < 0 ? WriteStatus.ACCEPT : WriteStatus.REJECT;
+  }
+}
+
 class TestConnectionStateListener implements ConnectionStateListener {
   @Override
   public void onChange(ConnectionState state) {
@@ -419,6 +427,10 @@ public class OutstationExample {
 
   private static void runOutstation(Outstation outstation) {
 
+    // ANCHOR: set_write_handler
+    outstation.setWriteHandler(new TestWriteHandler());
+    // ANCHOR_END: set_write_handler
+
     // Setup initial points
     // ANCHOR: database_init
     outstation.transaction((db) -> initializeDatabase(db));
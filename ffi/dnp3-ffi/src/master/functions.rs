@@ -60,6 +60,23 @@ pub(crate) unsafe fn file_info_iterator_next<'a>(
 pub struct MasterChannel {
     pub(crate) runtime: crate::runtime::RuntimeHandle,
     pub(crate) handle: dnp3::master::MasterChannel,
+    // retained so that certificates may be reloaded, only present for TLS channels
+    #[cfg(feature = "enable-tls")]
+    pub(crate) tls_config: Option<TlsClientConfig>,
+}
+
+impl MasterChannel {
+    pub(crate) fn new(
+        runtime: crate::runtime::RuntimeHandle,
+        handle: dnp3::master::MasterChannel,
+    ) -> Self {
+        Self {
+            runtime,
+            handle,
+            #[cfg(feature = "enable-tls")]
+            tls_config: None,
+        }
+    }
 }
 
 pub(crate) unsafe fn master_channel_create_tcp(
@@ -110,10 +127,7 @@ pub(crate) unsafe fn master_channel_create_tcp_2(
         Box::new(listener),
     );
 
-    let channel = MasterChannel {
-        runtime: runtime.handle(),
-        handle: channel,
-    };
+    let channel = MasterChannel::new(runtime.handle(), channel);
 
     Ok(Box::into_raw(Box::new(channel)))
 }
@@ -174,7 +188,7 @@ pub(crate) unsafe fn master_channel_create_tls_2(
         .map(|x| x.inner)
         .unwrap_or_else(Default::default);
 
-    let tls_config = tls_config.try_into()?;
+    let tls_config: TlsClientConfig = tls_config.try_into()?;
 
     // enter the runtime context so that we can spawn
     let _enter = runtime.enter();
@@ -186,12 +200,12 @@ pub(crate) unsafe fn master_channel_create_tls_2(
         connect_strategy.into(),
         connect_options,
         Box::new(listener),
-        tls_config,
+        tls_config.clone(),
     );
 
     let channel = MasterChannel {
-        runtime: runtime.handle(),
-        handle: channel,
+        tls_config: Some(tls_config),
+        ..MasterChannel::new(runtime.handle(), channel)
     };
 
     Ok(Box::into_raw(Box::new(channel)))
@@ -212,6 +226,10 @@ impl TryFrom<ffi::TlsClientConfig> for TlsClientConfig {
         let peer_cert_path = Path::new(value.peer_cert_path().to_str()?);
         let local_cert_path = Path::new(value.local_cert_path().to_str()?);
         let private_key_path = Path::new(value.private_key_path().to_str()?);
+        let crl_path = match value.crl_path().to_str()? {
+            "" => None,
+            path => Some(Path::new(path)),
+        };
 
         let config = match value.certificate_mode() {
             ffi::CertificateMode::AuthorityBased => {
@@ -232,6 +250,10 @@ impl TryFrom<ffi::TlsClientConfig> for TlsClientConfig {
                     optional_password,
                     value.min_tls_version().into(),
                 )
+                .and_then(|config| match crl_path {
+                    None => Ok(config),
+                    Some(path) => config.with_crl(path),
+                })
             }
             ffi::CertificateMode::SelfSigned => TlsClientConfig::self_signed(
                 peer_cert_path,
@@ -285,10 +307,7 @@ pub(crate) unsafe fn master_channel_create_serial(
         Box::new(listener),
     );
 
-    let channel = MasterChannel {
-        runtime: runtime.handle(),
-        handle: channel,
-    };
+    let channel = MasterChannel::new(runtime.handle(), channel);
 
     Ok(Box::into_raw(Box::new(channel)))
 }
@@ -313,10 +332,7 @@ pub(crate) unsafe fn master_channel_create_udp(
         config,
     );
 
-    let channel = MasterChannel {
-        runtime: runtime.handle(),
-        handle: channel,
-    };
+    let channel = MasterChannel::new(runtime.handle(), channel);
 
     Ok(Box::into_raw(Box::new(channel)))
 }
@@ -327,6 +343,30 @@ pub(crate) unsafe fn master_channel_destroy(channel: *mut MasterChannel) {
     }
 }
 
+#[cfg(not(feature = "enable-tls"))]
+pub(crate) unsafe fn master_channel_reload_tls(
+    _channel: *mut MasterChannel,
+    _tls_config: ffi::TlsClientConfig,
+    _disconnect_rejected: bool,
+) -> Result<(), ffi::ParamError> {
+    Err(ffi::ParamError::NoSupport)
+}
+
+#[cfg(feature = "enable-tls")]
+pub(crate) unsafe fn master_channel_reload_tls(
+    channel: *mut MasterChannel,
+    tls_config: ffi::TlsClientConfig,
+    disconnect_rejected: bool,
+) -> Result<(), ffi::ParamError> {
+    let channel = channel.as_mut().ok_or(ffi::ParamError::NullParameter)?;
+    let current = channel
+        .tls_config
+        .as_ref()
+        .ok_or(ffi::ParamError::WrongChannelType)?;
+    current.reload(tls_config.try_into()?, disconnect_rejected);
+    Ok(())
+}
+
 pub(crate) unsafe fn master_channel_enable(
     channel: *mut crate::MasterChannel,
 ) -> Result<(), ffi::ParamError> {
@@ -410,6 +450,13 @@ impl TryFrom<ffi::AssociationConfig> for AssociationConfig {
             enable_unsol_classes: convert_event_classes(config.enable_unsol_classes()),
             startup_integrity_classes: convert_classes(config.startup_integrity_classes()),
             auto_time_sync: convert_auto_time_sync(&config.auto_time_sync()),
+            auto_time_sync_period: if config.auto_time_sync_period() == Duration::from_secs(0) {
+                None
+            } else {
+                Some(config.auto_time_sync_period())
+            },
+            // the association handler of the bindings cannot report the quality of the clock
+            time_sync_policy: TimeSyncPolicy::default(),
             auto_tasks_retry_strategy: RetryStrategy::new(
                 config.auto_tasks_retry_strategy.min_delay(),
                 config.auto_tasks_retry_strategy.max_delay(),
@@ -1293,6 +1340,7 @@ impl From<TlsError> for ffi::ParamError {
             TlsError::InvalidPeerCertificate(_) => Self::InvalidPeerCertificate,
             TlsError::InvalidLocalCertificate(_) => Self::InvalidLocalCertificate,
             TlsError::InvalidPrivateKey(_) => Self::InvalidPrivateKey,
+            TlsError::InvalidCrl(_) => Self::InvalidCrl,
             TlsError::Other(_) => Self::OtherTlsError,
         }
     }
@@ -132,10 +132,7 @@ impl dnp3::tcp::ConnectionHandler for ffi::ConnectionHandler {
 
         let runtime = tokio::runtime::Handle::current();
 
-        let channel = crate::MasterChannel {
-            runtime: crate::RuntimeHandle::new(runtime),
-            handle: channel,
-        };
+        let channel = crate::MasterChannel::new(crate::RuntimeHandle::new(runtime), channel);
 
         Self::start(self, &addr, Box::into_raw(Box::new(channel)));
     }
@@ -169,10 +166,7 @@ impl dnp3::tcp::ConnectionHandler for ffi::ConnectionHandler {
 
         let runtime = tokio::runtime::Handle::current();
 
-        let channel = crate::MasterChannel {
-            runtime: crate::RuntimeHandle::new(runtime),
-            handle: channel,
-        };
+        let channel = crate::MasterChannel::new(crate::RuntimeHandle::new(runtime), channel);
 
         Self::start_with_link_id(
             self,
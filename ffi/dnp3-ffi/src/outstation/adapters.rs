@@ -1,6 +1,7 @@
 use crate::attr::FfiAttrValue;
 use dnp3::app::attr::{AnyAttribute, Attribute, FloatType};
 use dnp3::app::control::*;
+use dnp3::app::measurement::{AnalogOutputStatus, BinaryOutputStatus, Counter, OctetString};
 use dnp3::app::*;
 use dnp3::outstation::database::DatabaseHandle;
 use dnp3::outstation::*;
@@ -332,6 +333,49 @@ impl OutstationInformation for ffi::OutstationInformation {
     }
 }
 
+impl OutstationWriteHandler for ffi::WriteHandler {
+    fn write_binary_output_status(&mut self, index: u16, value: BinaryOutputStatus) -> WriteStatus {
+        ffi::WriteHandler::write_binary_output_status(
+            self,
+            ffi::BinaryOutputStatus::new(index, value),
+        )
+        .map(|x| x.into())
+        .unwrap_or(WriteStatus::NotSupported)
+    }
+
+    fn write_counter(&mut self, index: u16, value: Counter) -> WriteStatus {
+        ffi::WriteHandler::write_counter(self, ffi::Counter::new(index, value))
+            .map(|x| x.into())
+            .unwrap_or(WriteStatus::NotSupported)
+    }
+
+    fn write_analog_output_status(&mut self, index: u16, value: AnalogOutputStatus) -> WriteStatus {
+        ffi::WriteHandler::write_analog_output_status(
+            self,
+            ffi::AnalogOutputStatus::new(index, value),
+        )
+        .map(|x| x.into())
+        .unwrap_or(WriteStatus::NotSupported)
+    }
+
+    fn write_octet_string(&mut self, index: u16, value: &OctetString) -> WriteStatus {
+        let mut iter = crate::ByteIterator::new(value.value());
+        ffi::WriteHandler::write_octet_string(self, index, &mut iter)
+            .map(|x| x.into())
+            .unwrap_or(WriteStatus::NotSupported)
+    }
+}
+
+impl From<ffi::WriteStatus> for WriteStatus {
+    fn from(from: ffi::WriteStatus) -> Self {
+        match from {
+            ffi::WriteStatus::Accept => Self::Accept,
+            ffi::WriteStatus::Reject => Self::Reject,
+            ffi::WriteStatus::NotSupported => Self::NotSupported,
+        }
+    }
+}
+
 impl ControlHandler for ffi::ControlHandler {
     fn begin_fragment(&mut self) {
         ffi::ControlHandler::begin_fragment(self);
@@ -372,6 +416,31 @@ impl ControlSupport<Group12Var1> for ffi::ControlHandler {
         .map(|e| e.into())
         .unwrap_or(CommandStatus::NotSupported)
     }
+
+    fn start_operate(
+        &mut self,
+        control: Group12Var1,
+        index: u16,
+        op_type: OperateType,
+        database: &mut DatabaseHandle,
+    ) -> OperateResult {
+        if !ffi::ControlHandler::support_pending_operate(self).unwrap_or(false) {
+            return OperateResult::Complete(self.operate(control, index, op_type, database));
+        }
+
+        let mut pending = crate::PendingOperate::new();
+        let status = ffi::ControlHandler::start_operate_g12v1(
+            self,
+            control.into(),
+            index,
+            op_type.into(),
+            database as *mut _,
+            &mut pending as *mut _,
+        )
+        .map(|e| e.into())
+        .unwrap_or(CommandStatus::NotSupported);
+        pending.into_result(status)
+    }
 }
 
 impl ControlSupport<Group41Var1> for ffi::ControlHandler {
@@ -403,6 +472,31 @@ impl ControlSupport<Group41Var1> for ffi::ControlHandler {
         .map(|e| e.into())
         .unwrap_or(CommandStatus::NotSupported)
     }
+
+    fn start_operate(
+        &mut self,
+        control: Group41Var1,
+        index: u16,
+        op_type: OperateType,
+        database: &mut DatabaseHandle,
+    ) -> OperateResult {
+        if !ffi::ControlHandler::support_pending_operate(self).unwrap_or(false) {
+            return OperateResult::Complete(self.operate(control, index, op_type, database));
+        }
+
+        let mut pending = crate::PendingOperate::new();
+        let status = ffi::ControlHandler::start_operate_g41v1(
+            self,
+            control.value,
+            index,
+            op_type.into(),
+            database as *mut _,
+            &mut pending as *mut _,
+        )
+        .map(|e| e.into())
+        .unwrap_or(CommandStatus::NotSupported);
+        pending.into_result(status)
+    }
 }
 
 impl ControlSupport<Group41Var2> for ffi::ControlHandler {
@@ -434,6 +528,31 @@ impl ControlSupport<Group41Var2> for ffi::ControlHandler {
         .map(|e| e.into())
         .unwrap_or(CommandStatus::NotSupported)
     }
+
+    fn start_operate(
+        &mut self,
+        control: Group41Var2,
+        index: u16,
+        op_type: OperateType,
+        database: &mut DatabaseHandle,
+    ) -> OperateResult {
+        if !ffi::ControlHandler::support_pending_operate(self).unwrap_or(false) {
+            return OperateResult::Complete(self.operate(control, index, op_type, database));
+        }
+
+        let mut pending = crate::PendingOperate::new();
+        let status = ffi::ControlHandler::start_operate_g41v2(
+            self,
+            control.value,
+            index,
+            op_type.into(),
+            database as *mut _,
+            &mut pending as *mut _,
+        )
+        .map(|e| e.into())
+        .unwrap_or(CommandStatus::NotSupported);
+        pending.into_result(status)
+    }
 }
 
 impl ControlSupport<Group41Var3> for ffi::ControlHandler {
@@ -465,6 +584,31 @@ impl ControlSupport<Group41Var3> for ffi::ControlHandler {
         .map(|e| e.into())
         .unwrap_or(CommandStatus::NotSupported)
     }
+
+    fn start_operate(
+        &mut self,
+        control: Group41Var3,
+        index: u16,
+        op_type: OperateType,
+        database: &mut DatabaseHandle,
+    ) -> OperateResult {
+        if !ffi::ControlHandler::support_pending_operate(self).unwrap_or(false) {
+            return OperateResult::Complete(self.operate(control, index, op_type, database));
+        }
+
+        let mut pending = crate::PendingOperate::new();
+        let status = ffi::ControlHandler::start_operate_g41v3(
+            self,
+            control.value,
+            index,
+            op_type.into(),
+            database as *mut _,
+            &mut pending as *mut _,
+        )
+        .map(|e| e.into())
+        .unwrap_or(CommandStatus::NotSupported);
+        pending.into_result(status)
+    }
 }
 
 impl ControlSupport<Group41Var4> for ffi::ControlHandler {
@@ -496,6 +640,31 @@ impl ControlSupport<Group41Var4> for ffi::ControlHandler {
         .map(|e| e.into())
         .unwrap_or(CommandStatus::NotSupported)
     }
+
+    fn start_operate(
+        &mut self,
+        control: Group41Var4,
+        index: u16,
+        op_type: OperateType,
+        database: &mut DatabaseHandle,
+    ) -> OperateResult {
+        if !ffi::ControlHandler::support_pending_operate(self).unwrap_or(false) {
+            return OperateResult::Complete(self.operate(control, index, op_type, database));
+        }
+
+        let mut pending = crate::PendingOperate::new();
+        let status = ffi::ControlHandler::start_operate_g41v4(
+            self,
+            control.value,
+            index,
+            op_type.into(),
+            database as *mut _,
+            &mut pending as *mut _,
+        )
+        .map(|e| e.into())
+        .unwrap_or(CommandStatus::NotSupported);
+        pending.into_result(status)
+    }
 }
 
 impl From<RequestHeader> for ffi::RequestHeader {
@@ -6,8 +6,12 @@ pub use database::*;
 use dnp3::app::{BufferSize, BufferSizeError, Listener, MaybeAsync, Timeout};
 use dnp3::link::{EndpointAddress, LinkErrorMode, LinkReadMode};
 use dnp3::outstation::database::{ClassZeroConfig, EventBufferConfig};
-use dnp3::outstation::{ConnectionState, Feature, Features, OutstationConfig, OutstationHandle};
+use dnp3::outstation::{
+    ClockConfig, ConnectionState, DriftRangeError, DriftRate, Feature, Features, OutstationConfig,
+    OutstationHandle, UnsolicitedTriggers,
+};
 use dnp3::tcp::{FilterError, ServerHandle};
+pub use operate::*;
 pub use struct_constructors::*;
 
 use crate::client_connection_handler::ClientConnectionHandlerAdapter;
@@ -19,6 +23,7 @@ use dnp3::udp::{OutstationUdpConfig, UdpSocketMode};
 
 mod adapters;
 mod database;
+mod operate;
 mod struct_constructors;
 
 enum OutstationServerState {
@@ -30,6 +35,9 @@ enum OutstationServerState {
 pub struct OutstationServer {
     runtime: RuntimeHandle,
     state: OutstationServerState,
+    // retained so that certificates may be reloaded, only present for TLS servers
+    #[cfg(feature = "enable-tls")]
+    tls_config: Option<TlsServerConfig>,
 }
 
 pub struct Outstation {
@@ -50,6 +58,8 @@ pub unsafe fn outstation_server_create_tcp_server(
     Ok(Box::into_raw(Box::new(OutstationServer {
         runtime: runtime.handle(),
         state: OutstationServerState::Configuring(server),
+        #[cfg(feature = "enable-tls")]
+        tls_config: None,
     })))
 }
 
@@ -76,11 +86,24 @@ pub unsafe fn outstation_server_create_tls_server(
     address: &CStr,
     tls_config: ffi::TlsServerConfig,
 ) -> Result<*mut OutstationServer, ffi::ParamError> {
-    use std::path::Path;
-
     let runtime = runtime.as_ref().ok_or(ffi::ParamError::NullParameter)?;
     let address = address.to_str()?.parse()?;
 
+    let tls_config = tls_server_config(tls_config)?;
+    let server =
+        dnp3::tcp::Server::new_tls_server(link_error_mode.into(), address, tls_config.clone());
+
+    Ok(Box::into_raw(Box::new(OutstationServer {
+        runtime: runtime.handle(),
+        state: OutstationServerState::Configuring(server),
+        tls_config: Some(tls_config),
+    })))
+}
+
+#[cfg(feature = "enable-tls")]
+fn tls_server_config(tls_config: ffi::TlsServerConfig) -> Result<TlsServerConfig, ffi::ParamError> {
+    use std::path::Path;
+
     let password = match tls_config.password().to_str()? {
         "" => None,
         password => Some(password),
@@ -89,6 +112,10 @@ pub unsafe fn outstation_server_create_tls_server(
     let peer_cert_path = Path::new(tls_config.peer_cert_path().to_str()?);
     let local_cert_path = Path::new(tls_config.local_cert_path().to_str()?);
     let private_key_path = Path::new(tls_config.private_key_path().to_str()?);
+    let crl_path = match tls_config.crl_path().to_str()? {
+        "" => None,
+        path => Some(Path::new(path)),
+    };
     let min_tls_version: dnp3::tcp::tls::MinTlsVersion = tls_config.min_tls_version().into();
 
     let tls_config = match tls_config.certificate_mode() {
@@ -117,6 +144,10 @@ pub unsafe fn outstation_server_create_tls_server(
                 password,
                 min_tls_version,
             )
+            .and_then(|config| match crl_path {
+                None => Ok(config),
+                Some(path) => config.with_crl(path),
+            })
         }
     }
     .map_err(|err| {
@@ -124,12 +155,31 @@ pub unsafe fn outstation_server_create_tls_server(
         err
     })?;
 
-    let server = dnp3::tcp::Server::new_tls_server(link_error_mode.into(), address, tls_config);
+    Ok(tls_config)
+}
 
-    Ok(Box::into_raw(Box::new(OutstationServer {
-        runtime: runtime.handle(),
-        state: OutstationServerState::Configuring(server),
-    })))
+#[cfg(not(feature = "enable-tls"))]
+pub unsafe fn outstation_server_reload_tls(
+    _server: *mut OutstationServer,
+    _tls_config: ffi::TlsServerConfig,
+    _disconnect_rejected: bool,
+) -> Result<(), ffi::ParamError> {
+    Err(ffi::ParamError::NoSupport)
+}
+
+#[cfg(feature = "enable-tls")]
+pub unsafe fn outstation_server_reload_tls(
+    server: *mut OutstationServer,
+    tls_config: ffi::TlsServerConfig,
+    disconnect_rejected: bool,
+) -> Result<(), ffi::ParamError> {
+    let server = server.as_ref().ok_or(ffi::ParamError::NullParameter)?;
+    let current = server
+        .tls_config
+        .as_ref()
+        .ok_or(ffi::ParamError::WrongChannelType)?;
+    current.reload(tls_server_config(tls_config)?, disconnect_rejected);
+    Ok(())
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -591,6 +641,28 @@ pub unsafe fn outstation_set_decode_level(
     Ok(())
 }
 
+pub unsafe fn outstation_set_write_handler(
+    outstation: *mut Outstation,
+    handler: ffi::WriteHandler,
+) -> Result<(), ffi::ParamError> {
+    let outstation = outstation.as_mut().ok_or(ffi::ParamError::NullParameter)?;
+    outstation
+        .runtime
+        .block_on(outstation.handle.set_write_handler(Box::new(handler)))??;
+    Ok(())
+}
+
+pub unsafe fn outstation_get_clock_time(
+    outstation: *mut Outstation,
+) -> Result<ffi::Timestamp, ffi::ParamError> {
+    let outstation = outstation.as_mut().ok_or(ffi::ParamError::NullParameter)?;
+    let clock = outstation
+        .handle
+        .get_clock()
+        .ok_or(ffi::ParamError::NoSupport)?;
+    Ok(Some(clock.now()).into())
+}
+
 pub unsafe fn outstation_enable(outstation: *mut crate::Outstation) -> Result<(), ffi::ParamError> {
     let outstation = outstation.as_mut().ok_or(ffi::ParamError::NullParameter)?;
     outstation.runtime.block_on(outstation.handle.enable())??;
@@ -627,6 +699,8 @@ fn convert_outstation_config(
     let unsolicited_buffer_size = BufferSize::new(config.unsolicited_buffer_size() as usize)?;
     let rx_buffer_size = BufferSize::new(config.rx_buffer_size() as usize)?;
 
+    let clock = convert_clock_config(config.clock())?;
+
     let keep_alive_timeout = if config.keep_alive_timeout() == Duration::default() {
         None
     } else {
@@ -650,9 +724,34 @@ fn convert_outstation_config(
         class_zero: config.class_zero.into(),
         max_read_request_headers: Some(config.max_read_request_headers),
         max_controls_per_request: Some(config.max_controls_per_request),
+        clock,
+        unsolicited_triggers: UnsolicitedTriggers::default(),
+        // the bindings don't implement `before_read`, so the outstation never waits for a refresh
+        before_read_timeout: OutstationConfig::DEFAULT_BEFORE_READ_TIMEOUT,
     })
 }
 
+fn convert_clock_config(
+    config: &ffi::OutstationClockConfig,
+) -> Result<Option<ClockConfig>, ffi::ParamError> {
+    if !config.enabled() {
+        return Ok(None);
+    }
+
+    let optional = |x: Duration| if x == Duration::ZERO { None } else { Some(x) };
+    Ok(Some(ClockConfig {
+        sync_interval: optional(config.sync_interval()),
+        max_drift: optional(config.max_drift()),
+        assumed_drift: DriftRate::from_ppm(config.assumed_drift_ppm())?,
+    }))
+}
+
+impl From<DriftRangeError> for ffi::ParamError {
+    fn from(_: DriftRangeError) -> Self {
+        ffi::ParamError::InvalidDriftRate
+    }
+}
+
 impl Listener<ConnectionState> for ffi::ConnectionStateListener {
     fn update(&mut self, value: ConnectionState) -> MaybeAsync<()> {
         self.on_change(value.into());
@@ -692,6 +791,7 @@ impl From<&ffi::OutstationFeatures> for Features {
             broadcast: to_feature(from.broadcast()),
             unsolicited: to_feature(from.unsolicited()),
             respond_to_any_master: to_feature(from.respond_to_any_master()),
+            index_prefixed_static: to_feature(from.index_prefixed_static()),
         }
     }
 }
@@ -722,6 +822,8 @@ impl From<&ffi::EventBufferConfig> for EventBufferConfig {
             max_analog: from.max_analog(),
             max_analog_output_status: from.max_analog_output_status(),
             max_octet_string: from.max_octet_string(),
+            max_binary_output_command: from.max_binary_output_command(),
+            max_analog_output_command: from.max_analog_output_command(),
         }
     }
 }
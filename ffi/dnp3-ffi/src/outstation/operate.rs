@@ -0,0 +1,58 @@
+use dnp3::app::control::CommandStatus;
+use dnp3::app::measurement::Time;
+use dnp3::outstation::OperateResult;
+
+use crate::ffi;
+
+pub struct PendingOperate {
+    result: Option<OperateResult>,
+}
+
+impl PendingOperate {
+    pub(crate) fn new() -> Self {
+        Self { result: None }
+    }
+
+    /// result of the operation, the status returned by the callback is ignored if it was deferred
+    pub(crate) fn into_result(self, status: CommandStatus) -> OperateResult {
+        self.result.unwrap_or(OperateResult::Complete(status))
+    }
+}
+
+pub struct OperateCompletion {
+    inner: Option<dnp3::outstation::OperateCompletion>,
+}
+
+pub(crate) unsafe fn pending_operate_defer(
+    instance: *mut PendingOperate,
+) -> Result<*mut OperateCompletion, ffi::ParamError> {
+    let pending = instance.as_mut().ok_or(ffi::ParamError::NullParameter)?;
+    if pending.result.is_some() {
+        return Err(ffi::ParamError::Consumed);
+    }
+
+    let (result, completion) = OperateResult::pending();
+    pending.result = Some(result);
+
+    Ok(Box::into_raw(Box::new(OperateCompletion {
+        inner: Some(completion),
+    })))
+}
+
+pub(crate) unsafe fn operate_completion_complete(
+    instance: *mut OperateCompletion,
+    status: ffi::CommandStatus,
+    time: ffi::Timestamp,
+) -> Result<(), ffi::ParamError> {
+    let completion = instance.as_mut().ok_or(ffi::ParamError::NullParameter)?;
+    let completion = completion.inner.take().ok_or(ffi::ParamError::Consumed)?;
+    let time: Option<Time> = (&time).into();
+    completion.complete(status.into(), time);
+    Ok(())
+}
+
+pub(crate) unsafe fn operate_completion_destroy(instance: *mut OperateCompletion) {
+    if !instance.is_null() {
+        drop(Box::from_raw(instance));
+    }
+}
@@ -106,6 +106,8 @@ impl From<EventBufferConfig> for ffi::EventBufferConfig {
             max_analog: from.max_analog,
             max_analog_output_status: from.max_analog_output_status,
             max_octet_string: from.max_octet_string,
+            max_binary_output_command: from.max_binary_output_command,
+            max_analog_output_command: from.max_analog_output_command,
         }
     }
 }
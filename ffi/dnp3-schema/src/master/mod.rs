@@ -251,6 +251,26 @@ pub(crate) fn define(lib: &mut LibraryBuilder, shared: &SharedDefinitions) -> Ba
         .doc("stop communications")?
         .build()?;
 
+    let reload_tls_method = lib
+        .define_method("reload_tls", master_channel_class.clone())?
+        .param(
+            "tls_config",
+            shared.tls_client_config.clone(),
+            "New TLS client configuration",
+        )?
+        .param(
+            "disconnect_rejected",
+            Primitive::Bool,
+            "If true, an established session is closed if the new configuration would reject the certificate of the server",
+        )?
+        .fails_with(shared.error_type.clone())?
+        .doc(
+            doc("Replace the certificates, keys and CRLs of a TLS channel")
+                .details("The new configuration is used for all subsequent connections.")
+                .details("Fails with {enum:param_error.wrong_channel_type} if the channel was not created using TLS."),
+        )?
+        .build()?;
+
     let association_id = define_association_id(lib)?;
     let poll_id = define_poll_id(lib)?;
 
@@ -737,6 +757,7 @@ pub(crate) fn define(lib: &mut LibraryBuilder, shared: &SharedDefinitions) -> Ba
         .static_method(master_channel_create_udp_fn)?
         .method(enable_method)?
         .method(disable_method)?
+        .method(reload_tls_method)?
         .method(add_association_method)?
         .method(add_udp_association_method)?
         .method(remove_association_method)?
@@ -869,6 +890,7 @@ fn define_association_config(
         .build()?;
 
     let auto_time_sync = Name::create("auto_time_sync")?;
+    let auto_time_sync_period = Name::create("auto_time_sync_period")?;
     let auto_tasks_retry_strategy = Name::create("auto_tasks_retry_strategy")?;
     let keep_alive_timeout = Name::create("keep_alive_timeout")?;
     let auto_integrity_scan_on_buffer_overflow =
@@ -879,7 +901,10 @@ fn define_association_config(
 
     let association_config = lib
         .define_function_argument_struct(association_config)?
-        .doc("Association configuration")?
+        .doc(
+            doc("Association configuration")
+                .details("Time synchronization always proceeds regardless of the quality of the master clock since the {interface:association_handler} can only report the current time. The clock quality policy of the Rust library is not available in the bindings.")
+        )?
         .add(
             response_timeout.clone(),
             DurationType::Milliseconds,
@@ -905,6 +930,12 @@ fn define_association_config(
             auto_time_sync_enum,
             "Automatic time synchronization configuration",
         )?
+        .add(
+            &auto_time_sync_period,
+            DurationType::Seconds,
+            doc("Period of automatic time synchronizations performed regardless of the NEED_TIME IIN bit")
+                .details("Requires {struct:association_config.auto_time_sync} to be enabled. The first synchronization is performed when communications are established. A value of zero disables periodic synchronization.")
+        )?
         .add(
             &auto_tasks_retry_strategy,
             shared.retry_strategy.clone(),
@@ -930,6 +961,7 @@ fn define_association_config(
         .begin_initializer("init", InitializerType::Normal, "Initialize the configuration with the specified values")?
         .default(&response_timeout, Duration::from_secs(5))?
         .default_variant(&auto_time_sync, "none")?
+        .default(&auto_time_sync_period, Duration::from_secs(0))?
         .default_struct(&auto_tasks_retry_strategy)?
         .default(&keep_alive_timeout, Duration::from_secs(60))?
         .default(&auto_integrity_scan_on_buffer_overflow, true)?
@@ -9,6 +9,7 @@ struct OutstationTypes {
     outstation_application: AsynchronousInterface,
     outstation_information: AsynchronousInterface,
     control_handler: AsynchronousInterface,
+    write_handler: AsynchronousInterface,
     connection_state_listener: AsynchronousInterface,
     client_connection_handler: AsynchronousInterface,
 }
@@ -26,6 +27,7 @@ impl OutstationTypes {
             outstation_application: define_outstation_application(lib, shared, &database_handle)?,
             outstation_information: define_outstation_information(lib, shared)?,
             control_handler: define_control_handler(lib, &database_handle, shared)?,
+            write_handler: define_write_handler(lib, shared)?,
             connection_state_listener: define_connection_state_listener(lib)?,
             client_connection_handler: crate::client_connection_handler::define(lib, shared)?,
         })
@@ -175,7 +177,11 @@ pub(crate) fn define(lib: &mut LibraryBuilder, shared_def: &SharedDefinitions) -
             StringType,
             "Address to bind the server to e.g. 127.0.0.1:20000",
         )?
-        .param("tls_config", tls_server_config, "TLS server configuration")?
+        .param(
+            "tls_config",
+            tls_server_config.clone(),
+            "TLS server configuration",
+        )?
         .returns(outstation_server.clone(), "New TLS server instance")?
         .fails_with(shared_def.error_type.clone())?
         .doc(
@@ -203,6 +209,17 @@ pub(crate) fn define(lib: &mut LibraryBuilder, shared_def: &SharedDefinitions) -
         .doc("Bind the server to the port and starts listening. Also starts all the outstations associated to it.")?
         .build()?;
 
+    let reload_tls = lib.define_method("reload_tls", outstation_server.clone())?
+        .param("tls_config", tls_server_config, "New TLS server configuration")?
+        .param("disconnect_rejected", Primitive::Bool, "If true, established sessions are closed if the new configuration would reject the certificate of the client")?
+        .fails_with(shared_def.error_type.clone())?
+        .doc(
+            doc("Replace the certificates, keys and CRLs of a TLS server")
+                .details("The new configuration is used for all subsequently accepted connections.")
+                .details("Fails with {enum:param_error.wrong_channel_type} if the server was not created using TLS."),
+        )?
+        .build()?;
+
     let destructor = lib
         .define_destructor(outstation_server.clone(), "Gracefully shutdown all the outstations associated to this server, stops the server and release resources.")?;
 
@@ -212,6 +229,7 @@ pub(crate) fn define(lib: &mut LibraryBuilder, shared_def: &SharedDefinitions) -
         .destructor(destructor)?
         .method(add_outstation)?
         .method(bind)?
+        .method(reload_tls)?
         .custom_destroy("shutdown")?
         .doc(doc("TCP server that listens for connections and routes the messages to outstations.")
         .details("To add outstations to it, use {class:outstation_server.add_outstation()}. Once all the outstations are added, the server can be started with {class:outstation_server.bind()}.")
@@ -656,6 +674,20 @@ fn define_outstation(
         .doc("Set decoding log level")?
         .build()?;
 
+    let set_write_handler = lib
+        .define_method("set_write_handler", outstation.clone())?
+        .param(
+            "handler",
+            types.write_handler.clone(),
+            "Callbacks invoked for values written by the master",
+        )?
+        .fails_with(shared.error_type.clone())?
+        .doc(
+            doc("Set the handler for WRITE requests of point values")
+                .details("Until a handler is set, these WRITE requests are rejected with NO_FUNC_CODE_SUPPORT. Set the handler before enabling the outstation to avoid rejecting requests."),
+        )?
+        .build()?;
+
     let enable = lib
         .define_method("enable", outstation.clone())?
         .fails_with(shared.error_type.clone())?
@@ -668,6 +700,16 @@ fn define_outstation(
         .doc("disable communications")?
         .build()?;
 
+    let get_clock_time = lib
+        .define_method("get_clock_time", outstation.clone())?
+        .returns(shared.timestamp.clone(), "Current time of the clock")?
+        .fails_with(shared.error_type.clone())?
+        .doc(
+            doc("Read the clock maintained from the time written by the master, e.g. to timestamp measurements")
+                .details("The time is synchronized if the clock has been synchronized and is not asserting NEED_TIME. Fails with {enum:param_error.no_support} unless {struct:outstation_clock_config.enabled} was set.")
+        )?
+        .build()?;
+
     let outstation = lib
         .define_class(&outstation)?
         .destructor(destructor)?
@@ -683,6 +725,8 @@ fn define_outstation(
         .method(disable)?
         .method(execute_transaction)?
         .method(set_decode_level)?
+        .method(set_write_handler)?
+        .method(get_clock_time)?
         .doc(doc("Outstation handle").details("Use this handle to modify the internal database."))?
         .build()?;
 
@@ -822,6 +866,7 @@ fn define_outstation_features(lib: &mut LibraryBuilder) -> BackTraced<FunctionAr
     let broadcast = Name::create("broadcast")?;
     let unsolicited = Name::create("unsolicited")?;
     let respond_to_any_master = Name::create("respond_to_any_master")?;
+    let index_prefixed_static = Name::create("index_prefixed_static")?;
 
     let features = lib.declare_function_argument_struct("outstation_features")?;
     let features = lib
@@ -847,6 +892,12 @@ fn define_outstation_features(lib: &mut LibraryBuilder) -> BackTraced<FunctionAr
             doc("Outstation will process every request as if it came from the configured master address")
                 .details("This feature is a hack that can make configuration of some systems easier/more flexible, but should not be used when unsolicited reporting is also required.")
         )?
+        .add(
+            &index_prefixed_static,
+            Primitive::Bool,
+            doc("Report static data for sparse indices using index-prefixed headers (qualifier 0x28) where more compact than ranged headers (qualifier 0x01)")
+                .details("Each run of consecutive indices is written using whichever encoding is smaller. Only enable this feature if the master accepts index-prefixed static objects in responses, as the master in this library does.")
+        )?
         .doc("Optional outstation features that can be enabled or disabled")?
         .end_fields()?
         .begin_initializer(
@@ -858,12 +909,63 @@ fn define_outstation_features(lib: &mut LibraryBuilder) -> BackTraced<FunctionAr
         .default(&broadcast, true)?
         .default(&unsolicited, true)?
         .default(&respond_to_any_master, false)?
+        .default(&index_prefixed_static, false)?
         .end_initializer()?
         .build()?;
 
     Ok(features)
 }
 
+fn define_clock_config(lib: &mut LibraryBuilder) -> BackTraced<FunctionArgStructHandle> {
+    let enabled = Name::create("enabled")?;
+    let sync_interval = Name::create("sync_interval")?;
+    let max_drift = Name::create("max_drift")?;
+    let assumed_drift_ppm = Name::create("assumed_drift_ppm")?;
+
+    let clock_config = lib.declare_function_argument_struct("outstation_clock_config")?;
+    let clock_config = lib
+        .define_function_argument_struct(clock_config)?
+        .add(
+            &enabled,
+            Primitive::Bool,
+            doc("Maintain a clock from the time written by the master")
+                .details("The clock asserts NEED_TIME until the first synchronization and can be read with {class:outstation.get_clock_time()}. When disabled, time synchronization is left to the {interface:outstation_application}."),
+        )?
+        .add(
+            &sync_interval,
+            DurationType::Seconds,
+            doc("NEED_TIME is asserted when this much time has elapsed since the last synchronization")
+                .details("A value of zero disables this check."),
+        )?
+        .add(
+            &max_drift,
+            DurationType::Milliseconds,
+            doc("NEED_TIME is asserted when the estimated drift since the last synchronization exceeds this value")
+                .details("A value of zero disables this check."),
+        )?
+        .add(
+            &assumed_drift_ppm,
+            Primitive::Double,
+            doc("Drift of the local clock in parts per million, assumed until it can be measured from two synchronizations")
+                .details("Must be in the range [0, 1000000]."),
+        )?
+        .doc("Configuration of the clock maintained by the outstation")?
+        .end_fields()?
+        .begin_initializer(
+            "init",
+            InitializerType::Normal,
+            "Initialize to default values",
+        )?
+        .default(&enabled, false)?
+        .default(&sync_interval, Duration::from_secs(0))?
+        .default(&max_drift, Duration::from_secs(0))?
+        .default(&assumed_drift_ppm, NumberValue::Double(100.0))?
+        .end_initializer()?
+        .build()?;
+
+    Ok(clock_config)
+}
+
 fn define_outstation_config(
     lib: &mut LibraryBuilder,
     shared: &SharedDefinitions,
@@ -871,6 +973,7 @@ fn define_outstation_config(
     let event_buffer_config = define_event_buffer_config(lib)?;
     let class_zero_config = define_class_zero_config(lib)?;
     let outstation_features = define_outstation_features(lib)?;
+    let clock_config = define_clock_config(lib)?;
 
     let solicited_buffer_size = Name::create("solicited_buffer_size")?;
     let unsolicited_buffer_size = Name::create("unsolicited_buffer_size")?;
@@ -885,6 +988,7 @@ fn define_outstation_config(
     let max_read_request_headers = Name::create("max_read_request_headers")?;
     let max_controls_per_request = Name::create("max_controls_per_request")?;
     let class_zero = Name::create("class_zero")?;
+    let clock = Name::create("clock")?;
 
     let outstation_config = lib.declare_function_argument_struct("outstation_config")?;
     let outstation_config = lib
@@ -947,6 +1051,7 @@ fn define_outstation_config(
         .add(&max_read_request_headers, Primitive::U16, doc("Maximum number of headers that will be processed in a READ request.").details("Internally, this controls the size of a pre-allocated buffer used to process requests. A minimum value of `DEFAULT_READ_REQUEST_HEADERS` is always enforced. Requesting more than this number will result in the PARAMETER_ERROR IIN bit being set in the response."))?
         .add(&max_controls_per_request, Primitive::U16, doc("Maximum number of controls in a single request."))?
         .add(&class_zero, class_zero_config, "Controls responses to Class 0 reads")?
+        .add(&clock, clock_config, "Optional clock maintained from the time written by the master")?
         .end_fields()?
         .begin_initializer("init", InitializerType::Normal, "Initialize to defaults")?
         .default(&solicited_buffer_size, NumberValue::U16(2048))?
@@ -962,6 +1067,7 @@ fn define_outstation_config(
         .default(&max_read_request_headers, NumberValue::U16(64))?
         .default(&max_controls_per_request, NumberValue::U16(u16::MAX))?
         .default_struct(&class_zero)?
+        .default_struct(&clock)?
         .end_initializer()?
         .build()?;
 
@@ -977,6 +1083,8 @@ fn define_event_buffer_config(lib: &mut LibraryBuilder) -> BackTraced<FunctionAr
     let max_analog = Name::create("max_analog")?;
     let max_analog_output_status = Name::create("max_analog_output_status")?;
     let max_octet_string = Name::create("max_octet_string")?;
+    let max_binary_output_command = Name::create("max_binary_output_command")?;
+    let max_analog_output_command = Name::create("max_analog_output_command")?;
 
     let event_buffer_config = lib.declare_function_argument_struct("event_buffer_config")?;
     let event_buffer_config = lib
@@ -1021,12 +1129,31 @@ fn define_event_buffer_config(lib: &mut LibraryBuilder) -> BackTraced<FunctionAr
             Primitive::U16,
             doc("Maximum number of Octet String events (g111)"),
         )?
+        .add(
+            &max_binary_output_command,
+            Primitive::U16,
+            doc("Maximum number of Binary Output Command events (g13)")
+                .details("These events are only produced when operations deferred with {class:pending_operate.defer()} complete."),
+        )?
+        .add(
+            &max_analog_output_command,
+            Primitive::U16,
+            doc("Maximum number of Analog Output Command events (g43)")
+                .details("These events are only produced when operations deferred with {class:pending_operate.defer()} complete."),
+        )?
         .doc(
             doc("Maximum number of events for each type")
                 .details("A value of zero means that events will not be buffered for that type."),
         )?
         .end_fields()?
-        .add_full_initializer("init")?
+        .begin_initializer(
+            "init",
+            InitializerType::Normal,
+            "Create a configuration specifying the max for each type individually. Up to 10 binary and analog output command events are buffered.",
+        )?
+        .default(&max_binary_output_command, NumberValue::U16(10))?
+        .default(&max_analog_output_command, NumberValue::U16(10))?
+        .end_initializer()?
         .begin_initializer(
             "no_events",
             InitializerType::Static,
@@ -1040,6 +1167,8 @@ fn define_event_buffer_config(lib: &mut LibraryBuilder) -> BackTraced<FunctionAr
         .default(&max_analog, NumberValue::U16(0))?
         .default(&max_analog_output_status, NumberValue::U16(0))?
         .default(&max_octet_string, NumberValue::U16(0))?
+        .default(&max_binary_output_command, NumberValue::U16(0))?
+        .default(&max_analog_output_command, NumberValue::U16(0))?
         .end_initializer()?
         .build()?;
 
@@ -1435,6 +1564,69 @@ fn define_outstation_information(
     Ok(information)
 }
 
+fn define_pending_operate(
+    lib: &mut LibraryBuilder,
+    shared_def: &SharedDefinitions,
+) -> BackTraced<ClassHandle> {
+    let operate_completion = lib.declare_class("operate_completion")?;
+
+    let complete_method = lib
+        .define_method("complete", operate_completion.clone())?
+        .param(
+            "status",
+            shared_def.command_status.clone(),
+            "Final status of the operation",
+        )?
+        .param(
+            "time",
+            shared_def.timestamp.clone(),
+            "Time at which the operation completed, the command event is reported without a timestamp if the time is invalid",
+        )?
+        .fails_with(shared_def.error_type.clone())?
+        .doc(
+            doc("Complete the operation with its final status")
+                .details("This records a binary (g13) or analog (g43) output command event with the final status, and updates the output status point to the commanded value if the operation succeeded.")
+                .details("This method may be called from any thread, but not from within a database transaction. It fails with {enum:param_error.consumed} if the operation was already completed."),
+        )?
+        .build()?;
+
+    let destructor = lib.define_destructor(
+        operate_completion.clone(),
+        "Release the completion, reporting {enum:command_status.hardware_error} if the operation was not completed",
+    )?;
+
+    let operate_completion = lib
+        .define_class(&operate_completion)?
+        .method(complete_method)?
+        .destructor(destructor)?
+        .doc("Handle used to report the final status of an operation that was deferred with {class:pending_operate.defer()}")?
+        .build()?;
+
+    let pending_operate = lib.declare_class("pending_operate")?;
+
+    let defer_method = lib
+        .define_method("defer", pending_operate.clone())?
+        .returns(
+            operate_completion.declaration(),
+            "Handle used to complete the operation",
+        )?
+        .fails_with(shared_def.error_type.clone())?
+        .doc(
+            doc("Defer the completion of the operation until after the response is sent")
+                .details("The outstation responds with {enum:command_status.success} and the status returned by the callback is ignored. Further operate requests on the same point are answered with {enum:command_status.already_active} until the operation completes.")
+                .details("This method fails with {enum:param_error.consumed} if it is called more than once."),
+        )?
+        .build()?;
+
+    let pending_operate = lib
+        .define_class(&pending_operate)?
+        .method(defer_method)?
+        .doc("Operation passed to the start_operate callbacks of {interface:control_handler}, which may be deferred instead of completing it immediately")?
+        .build()?;
+
+    Ok(pending_operate)
+}
+
 fn define_control_handler(
     lib: &mut LibraryBuilder,
     database_handle: &ClassHandle,
@@ -1457,6 +1649,9 @@ fn define_control_handler(
         .doc("Enumeration describing how the master requested the control operation")?
         .build()?;
 
+    let pending_operate = define_pending_operate(lib, shared_def)?;
+    let not_supported = shared_def.command_status.value("not_supported")?;
+
     let select_details_1 = "Implementors can think of this function as asking the question \"is this control supported\"?";
     let select_details_2 = "Most implementations should not alter the database in this method. It is only provided in the event that some event counters reflected via the API get updated on SELECT, but this would be highly abnormal.";
     let select_g12_doc = doc("Select a CROB, but do not operate")
@@ -1466,6 +1661,10 @@ fn define_control_handler(
         .details(select_details_1)
         .details(select_details_2);
 
+    let start_operate_doc = doc("Start the operation of a control point")
+        .details("This is only called if {interface:control_handler.support_pending_operate()} returns true.")
+        .details("If {class:pending_operate.defer()} is called, the operation completes when the returned {class:operate_completion} is completed or destroyed. Otherwise the returned status is the result of the operation.");
+
     let control_handler = lib
         .define_interface("control_handler", "Callbacks for handling controls")?
         //------
@@ -1475,6 +1674,14 @@ fn define_control_handler(
         .param("database", database_handle.declaration(), "Database handle")?
         .end_callback()?
         //------
+        .begin_callback(
+            "support_pending_operate",
+            doc("Controls whether operations are started with the start_operate callbacks")
+                .details("If this returns true, operations are passed to the start_operate callbacks instead of the operate callbacks, which allows them to be completed after the response is sent with {class:pending_operate.defer()}."),
+        )?
+        .returns_with_default(PrimitiveValue::Bool(false), "True if operations should be passed to the start_operate callbacks")?
+        .end_callback()?
+        //------
         .begin_callback("select_g12v1", select_g12_doc)?
         .param("value", shared_def.g12v1_struct.clone(), "Received CROB")?
         .param("index", Primitive::U16, "Index of the point")?
@@ -1498,6 +1705,23 @@ fn define_control_handler(
         .returns(shared_def.command_status.clone(), "Command status")?
         .end_callback()?
         //------
+        .begin_callback("start_operate_g12v1", start_operate_doc.clone())?
+        .param("value", shared_def.g12v1_struct.clone(), "Received CROB")?
+        .param("index", Primitive::U16, "Index of the point")?
+        .param("op_type", operate_type.clone(), "Operate type")?
+        .param(
+            "database_handle",
+            database_handle.declaration(),
+            "Database handle",
+        )?
+        .param(
+            "pending",
+            pending_operate.declaration(),
+            "Operation that may be deferred",
+        )?
+        .returns_with_default(not_supported.clone(), "Command status")?
+        .end_callback()?
+        //------
         .begin_callback("select_g41v1", select_g40_doc.clone())?
         .param("value", Primitive::S32, "Received analog output value")?
         .param("index", Primitive::U16, "Index of the point")?
@@ -1521,6 +1745,23 @@ fn define_control_handler(
         .returns(shared_def.command_status.clone(), "Command status")?
         .end_callback()?
         //------
+        .begin_callback("start_operate_g41v1", start_operate_doc.clone())?
+        .param("value", Primitive::S32, "Received analog output value")?
+        .param("index", Primitive::U16, "Index of the point")?
+        .param("op_type", operate_type.clone(), "Operate type")?
+        .param(
+            "database_handle",
+            database_handle.declaration(),
+            "Database handle",
+        )?
+        .param(
+            "pending",
+            pending_operate.declaration(),
+            "Operation that may be deferred",
+        )?
+        .returns_with_default(not_supported.clone(), "Command status")?
+        .end_callback()?
+        //------
         .begin_callback("select_g41v2", select_g40_doc.clone())?
         .param("value", Primitive::S16, "Received analog output value")?
         .param("index", Primitive::U16, "Index of the point")?
@@ -1544,6 +1785,23 @@ fn define_control_handler(
         .returns(shared_def.command_status.clone(), "Command status")?
         .end_callback()?
         //------
+        .begin_callback("start_operate_g41v2", start_operate_doc.clone())?
+        .param("value", Primitive::S16, "Received analog output value")?
+        .param("index", Primitive::U16, "Index of the point")?
+        .param("op_type", operate_type.clone(), "Operate type")?
+        .param(
+            "database_handle",
+            database_handle.declaration(),
+            "Database handle",
+        )?
+        .param(
+            "pending",
+            pending_operate.declaration(),
+            "Operation that may be deferred",
+        )?
+        .returns_with_default(not_supported.clone(), "Command status")?
+        .end_callback()?
+        //------
         .begin_callback("select_g41v3", select_g40_doc.clone())?
         .param("value", Primitive::Float, "Received analog output value")?
         .param("index", Primitive::U16, "Index of the point")?
@@ -1567,6 +1825,23 @@ fn define_control_handler(
         .returns(shared_def.command_status.clone(), "Command status")?
         .end_callback()?
         //------
+        .begin_callback("start_operate_g41v3", start_operate_doc.clone())?
+        .param("value", Primitive::Float, "Received analog output value")?
+        .param("index", Primitive::U16, "Index of the point")?
+        .param("op_type", operate_type.clone(), "Operate type")?
+        .param(
+            "database_handle",
+            database_handle.declaration(),
+            "Database handle",
+        )?
+        .param(
+            "pending",
+            pending_operate.declaration(),
+            "Operation that may be deferred",
+        )?
+        .returns_with_default(not_supported.clone(), "Command status")?
+        .end_callback()?
+        //------
         .begin_callback("select_g41v4", select_g40_doc)?
         .param("value", Primitive::Double, "Received analog output value")?
         .param("index", Primitive::U16, "Index of the point")?
@@ -1581,7 +1856,7 @@ fn define_control_handler(
         .begin_callback("operate_g41v4", "Operate a control point")?
         .param("value", Primitive::Double, "Received analog output value")?
         .param("index", Primitive::U16, "Index of the point")?
-        .param("op_type", operate_type, "Operate type")?
+        .param("op_type", operate_type.clone(), "Operate type")?
         .param(
             "database_handle",
             database_handle.declaration(),
@@ -1590,11 +1865,103 @@ fn define_control_handler(
         .returns(shared_def.command_status.clone(), "Command status")?
         .end_callback()?
         //------
+        .begin_callback("start_operate_g41v4", start_operate_doc)?
+        .param("value", Primitive::Double, "Received analog output value")?
+        .param("index", Primitive::U16, "Index of the point")?
+        .param("op_type", operate_type, "Operate type")?
+        .param(
+            "database_handle",
+            database_handle.declaration(),
+            "Database handle",
+        )?
+        .param(
+            "pending",
+            pending_operate.declaration(),
+            "Operation that may be deferred",
+        )?
+        .returns_with_default(not_supported, "Command status")?
+        .end_callback()?
+        //------
         .build_async()?;
 
     Ok(control_handler)
 }
 
+fn define_write_handler(
+    lib: &mut LibraryBuilder,
+    shared_def: &SharedDefinitions,
+) -> BackTraced<AsynchronousInterface> {
+    let write_status = lib
+        .define_enum("write_status")?
+        .push("accept", "The value is accepted and updated in the database")?
+        .push(
+            "reject",
+            "The value is rejected and IIN2.PARAMETER_ERROR is set in the response",
+        )?
+        .push(
+            "not_supported",
+            "Writes of this type are not supported and IIN2.NO_FUNC_CODE_SUPPORT is set in the response",
+        )?
+        .doc("Outcome of offering a value written by the master to a {interface:write_handler}")?
+        .build()?;
+
+    let not_supported = write_status.value("not_supported")?;
+
+    let write_handler = lib
+        .define_interface(
+            "write_handler",
+            doc("Callbacks for handling WRITE requests of point values")
+                .details("Values written with the 0x00, 0x01, 0x17 and 0x28 qualifiers are offered to the handler one at a time. Accepted values are automatically updated in the database, which may produce events.")
+                .details("Binary output status, counters and analog output status are timestamped with the outstation clock if one is configured, or with the system time otherwise. Values written to points that don't exist in the database are rejected without calling the handler."),
+        )?
+        //------
+        .begin_callback(
+            "write_binary_output_status",
+            "Called for each binary output status written via Group 10",
+        )?
+        .param(
+            "value",
+            shared_def.binary_output_status_point.clone(),
+            "Written value",
+        )?
+        .returns_with_default(not_supported.clone(), "Outcome of the write")?
+        .end_callback()?
+        //------
+        .begin_callback("write_counter", "Called for each counter preset via Group 20")?
+        .param("value", shared_def.counter_point.clone(), "Written value")?
+        .returns_with_default(not_supported.clone(), "Outcome of the write")?
+        .end_callback()?
+        //------
+        .begin_callback(
+            "write_analog_output_status",
+            "Called for each analog output status written via Group 40",
+        )?
+        .param(
+            "value",
+            shared_def.analog_output_status_point.clone(),
+            "Written value",
+        )?
+        .returns_with_default(not_supported.clone(), "Outcome of the write")?
+        .end_callback()?
+        //------
+        .begin_callback(
+            "write_octet_string",
+            "Called for each octet string written via Group 110",
+        )?
+        .param("index", Primitive::U16, "Index of the point")?
+        .param(
+            "value",
+            shared_def.byte_it.clone(),
+            "Iterator over bytes of the value",
+        )?
+        .returns_with_default(not_supported, "Outcome of the write")?
+        .end_callback()?
+        //------
+        .build_async()?;
+
+    Ok(write_handler)
+}
+
 fn define_connection_state_listener(lib: &mut LibraryBuilder) -> BackTraced<AsynchronousInterface> {
     let state = lib
         .define_enum("connection_state")?
@@ -1671,6 +2038,8 @@ fn define_tls_server_config(
     let certificate_mode = Name::create("certificate_mode")?;
     let allow_client_name_wildcard = Name::create("allow_client_name_wildcard")?;
 
+    let crl_path = Name::create("crl_path")?;
+
     let tls_server_config = lib.declare_function_argument_struct("tls_server_config")?;
     let tls_server_config = lib.define_function_argument_struct(tls_server_config)?
         .add("dns_name", StringType,
@@ -1703,12 +2072,21 @@ fn define_tls_server_config(
         )?
         .add(certificate_mode.clone(), shared.certificate_mode.clone(), "Certificate validation mode")?
         .add(allow_client_name_wildcard.clone(), Primitive::Bool, "If set to true, a '*' may be used for {struct:tls_server_config.dns_name} to allow any authenticated client to connect")?
+        .add(
+            crl_path.clone(),
+            StringType,
+            doc("Optional path to a file containing PEM-encoded certificate revocation lists, or a single DER-encoded CRL")
+                .details("The CRLs are checked during the handshake, and the client is rejected if its certificate is revoked, or if a CRL of its issuer cannot be verified or has expired.")
+                .details("Pass empty string if CRLs are not used.")
+                .warning("This argument is only used when used with {enum:certificate_mode.authority_based}"),
+        )?
         .doc("TLS server configuration")?
         .end_fields()?
         .begin_initializer("init", InitializerType::Normal, "construct the configuration with defaults")?
         .default_variant(&min_tls_version, "v12")?
         .default_variant(&certificate_mode, "authority_based")?
         .default(&allow_client_name_wildcard, false)?
+        .default_string(&crl_path, "")?
         .end_initializer()?
         .build()?;
 
@@ -123,6 +123,11 @@ pub(crate) fn define(lib: &mut LibraryBuilder) -> BackTraced<SharedDefinitions>
             "consumed",
             "This object is consumed and cannot be used again",
         )?
+        .add_error("invalid_crl", "Invalid certificate revocation list file")?
+        .add_error(
+            "invalid_drift_rate",
+            "Drift rate is negative, too large, or not a number",
+        )?
         .doc("Error type used throughout the library")?
         .build()?;
 
@@ -394,6 +399,8 @@ fn define_tls_types(lib: &mut LibraryBuilder) -> BackTraced<TlsTypes> {
     let certificate_mode_name = Name::create("certificate_mode")?;
     let allow_server_name_wildcard = Name::create("allow_server_name_wildcard")?;
 
+    let crl_path = Name::create("crl_path")?;
+
     let tls_client_config = lib.declare_function_argument_struct("tls_client_config")?;
     let tls_client_config = lib.define_function_argument_struct(tls_client_config)?
         .add("dns_name", StringType,
@@ -426,12 +433,21 @@ fn define_tls_types(lib: &mut LibraryBuilder) -> BackTraced<TlsTypes> {
         )?
         .add(certificate_mode_name.clone(), certificate_mode.clone(), "Certificate validation mode")?
         .add(allow_server_name_wildcard.clone(), Primitive::Bool, "If set to true, a '*' may be used for {struct:tls_client_config.dns_name} to bypass server name validation")?
+        .add(
+            crl_path.clone(),
+            StringType,
+            doc("Optional path to a file containing PEM-encoded certificate revocation lists, or a single DER-encoded CRL")
+                .details("The CRLs are checked during the handshake, and the server is rejected if its certificate is revoked, or if a CRL of its issuer cannot be verified or has expired.")
+                .details("Pass empty string if CRLs are not used.")
+                .warning("This argument is only used when used with {enum:certificate_mode.authority_based}"),
+        )?
         .doc("TLS client configuration")?
         .end_fields()?
         .begin_initializer("init", InitializerType::Normal, "construct the configuration with defaults")?
         .default_variant(&min_tls_version_name, "v12")?
         .default_variant(&certificate_mode_name, "authority_based")?
         .default(&allow_server_name_wildcard, false)?
+        .default_string(&crl_path, "")?
         .end_initializer()?
         .build()?;
 